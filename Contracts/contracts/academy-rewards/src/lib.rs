@@ -1,5 +1,6 @@
 #![no_std]
 
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, Address, Env, String, Symbol,
 };
@@ -228,6 +229,34 @@ impl AcademyRewardsContract {
         Ok(())
     }
 
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
     // ========== USER FUNCTIONS ==========
 
     /// Redeem badge for fee discount
@@ -1,3 +1,4 @@
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
 
 const MAX_BATCH_CLAIMS: u32 = 25;
@@ -124,6 +125,20 @@ fn persist_schedules(env: &Env, schedules: &soroban_sdk::Map<u64, VestingSchedul
         .set(&symbol_short!("sched"), schedules);
 }
 
+/// Authenticate `admin` and check it is the stored admin
+fn require_admin(env: &Env, admin: &Address) -> Result<(), VestingError> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("admin"))
+        .ok_or(VestingError::Unauthorized)?;
+    if *admin != stored_admin {
+        return Err(VestingError::Unauthorized);
+    }
+    Ok(())
+}
+
 fn claimable_amount(
     env: &Env,
     grant_id: u64,
@@ -200,19 +215,7 @@ impl AcademyVestingContract {
         cliff: u64,
         duration: u64,
     ) -> Result<u64, VestingError> {
-        admin.require_auth();
-
-        // Verify caller is admin
-        let admin_key = symbol_short!("admin");
-        let stored_admin: Address = env
-            .storage()
-            .persistent()
-            .get(&admin_key)
-            .ok_or(VestingError::Unauthorized)?;
-
-        if admin != stored_admin {
-            return Err(VestingError::Unauthorized);
-        }
+        require_admin(&env, &admin)?;
 
         // Validate schedule
         if amount <= 0 {
@@ -428,19 +431,7 @@ impl AcademyVestingContract {
         admin: Address,
         revoke_delay: u64,
     ) -> Result<(), VestingError> {
-        admin.require_auth();
-
-        // Verify caller is admin
-        let admin_key = symbol_short!("admin");
-        let stored_admin: Address = env
-            .storage()
-            .persistent()
-            .get(&admin_key)
-            .ok_or(VestingError::Unauthorized)?;
-
-        if admin != stored_admin {
-            return Err(VestingError::Unauthorized);
-        }
+        require_admin(&env, &admin)?;
 
         // Get vesting schedule
         let mut schedules = load_schedules(&env)?;
@@ -540,6 +531,32 @@ impl AcademyVestingContract {
         Ok(vested_amount as i128)
     }
 
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), VestingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("admin"), &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), VestingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
     /// Get contract information
     pub fn get_info(env: Env) -> Result<(Address, Address, Address), VestingError> {
         let admin_key = symbol_short!("admin");
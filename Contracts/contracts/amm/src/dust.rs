@@ -0,0 +1,90 @@
+//! Dust policy
+//!
+//! Once configured, liquidity positions below `min_liquidity` shares cannot
+//! be created: deposits, withdrawals and share transfers must either leave
+//! a position at zero or at least the minimum. Tokens the pool holds beyond
+//! what it owes to pair reserves and flash loan fees (rounding residue,
+//! direct transfers) are swept to the treasury by the admin with
+//! `sweep_dust`.
+
+use shared::events::{DustSweptEvent, EventEmitter};
+use soroban_sdk::{contracttype, token, Address, Env};
+
+use crate::{flash, load_pair, AmmContract, AmmError, DataKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustPolicy {
+    /// Smallest non-zero share balance a provider may hold
+    pub min_liquidity: i128,
+    /// Receives swept dust
+    pub treasury: Address,
+}
+
+pub fn get(env: &Env) -> Option<DustPolicy> {
+    env.storage().instance().get(&DataKey::DustPolicy)
+}
+
+pub fn set(env: &Env, policy: Option<DustPolicy>) -> Result<(), AmmError> {
+    match policy {
+        Some(policy) => {
+            if policy.min_liquidity <= 0 {
+                return Err(AmmError::InvalidAmount);
+            }
+            env.storage().instance().set(&DataKey::DustPolicy, &policy);
+        }
+        None => env.storage().instance().remove(&DataKey::DustPolicy),
+    }
+    Ok(())
+}
+
+/// Reject a share balance that would end up between zero and the minimum
+pub fn check_position(env: &Env, shares: i128) -> Result<(), AmmError> {
+    match get(env) {
+        Some(policy) if shares > 0 && shares < policy.min_liquidity => {
+            Err(AmmError::BelowDustMinimum)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Amount of `token` the pool owes: pair reserves plus accrued flash fees
+pub fn owed(env: &Env, token: &Address) -> i128 {
+    let mut owed = flash::accrued_fees(env, token);
+    for pair in AmmContract::get_pairs(env.clone()).iter() {
+        let Ok(state) = load_pair(env, &pair) else {
+            continue;
+        };
+        if state.token_a == *token {
+            owed += state.reserve_a;
+        }
+        if state.token_b == *token {
+            owed += state.reserve_b;
+        }
+    }
+    owed
+}
+
+/// Transfer the pool's unowed balance of `token` to the treasury. Returns
+/// the amount swept.
+pub fn sweep(env: &Env, token: &Address) -> Result<i128, AmmError> {
+    let policy = get(env).ok_or(AmmError::NoDustPolicy)?;
+    let this = env.current_contract_address();
+    let client = token::Client::new(env, token);
+    let surplus = client.balance(&this) - owed(env, token);
+    if surplus <= 0 {
+        return Ok(0);
+    }
+    client.transfer(&this, &policy.treasury, &surplus);
+    EventEmitter::dust_swept(
+        env,
+        DustSweptEvent {
+            token: token.clone(),
+            treasury: policy.treasury,
+            count: 1,
+            total: surplus,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(surplus)
+}
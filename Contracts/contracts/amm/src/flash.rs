@@ -0,0 +1,98 @@
+//! Flash loans
+//!
+//! Any token held by the pool can be borrowed for the duration of a single
+//! invocation. The pool transfers `amount` to the receiver, calls its
+//! `on_flash_loan` hook and then requires its own balance to have grown by at
+//! least the fee. Fees are tracked separately from pair reserves and swept by
+//! the admin with `collect_flash_fees`.
+
+use shared::events::{EventEmitter, FlashLoanEvent};
+use soroban_sdk::{contractclient, token, Address, Bytes, Env};
+
+use crate::{AmmError, DataKey, BPS_DENOMINATOR};
+
+/// Flash loan fee, in basis points of the borrowed amount
+pub const FLASH_FEE_BPS: i128 = 9;
+
+/// Interface flash loan receivers must implement. The receiver has to
+/// transfer `amount + fee` of `token` back to the pool before returning.
+#[contractclient(name = "FlashLoanReceiverClient")]
+pub trait FlashLoanReceiver {
+    fn on_flash_loan(env: Env, pool: Address, token: Address, amount: i128, fee: i128, data: Bytes);
+}
+
+/// Fee owed on a loan of `amount`, rounded up so small loans are not free
+pub fn flash_fee(amount: i128) -> i128 {
+    (amount * FLASH_FEE_BPS + BPS_DENOMINATOR - 1) / BPS_DENOMINATOR
+}
+
+pub fn accrued_fees(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FlashFees(token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_accrued_fees(env: &Env, token: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FlashFees(token.clone()), &amount);
+}
+
+/// Lend `amount` of `token` to `receiver` and verify repayment plus fee.
+/// Any failure returns an error, which reverts the whole invocation
+/// including the receiver's callback.
+pub fn flash_loan(
+    env: &Env,
+    receiver: &Address,
+    token: &Address,
+    amount: i128,
+    data: &Bytes,
+) -> Result<i128, AmmError> {
+    if amount <= 0 {
+        return Err(AmmError::InvalidAmount);
+    }
+
+    let pool = env.current_contract_address();
+    let token_client = token::Client::new(env, token);
+    let balance_before = token_client.balance(&pool);
+    if amount > balance_before {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let fee = flash_fee(amount);
+    token_client.transfer(&pool, receiver, &amount);
+    FlashLoanReceiverClient::new(env, receiver).on_flash_loan(&pool, token, &amount, &fee, data);
+
+    let balance_after = token_client.balance(&pool);
+    if balance_after < balance_before + fee {
+        return Err(AmmError::FlashLoanNotRepaid);
+    }
+
+    // Anything paid above the fee is kept as fee revenue as well
+    let earned = balance_after - balance_before;
+    set_accrued_fees(env, token, accrued_fees(env, token) + earned);
+
+    EventEmitter::flash_loan(
+        env,
+        FlashLoanEvent {
+            receiver: receiver.clone(),
+            token: token.clone(),
+            amount,
+            fee: earned,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(earned)
+}
+
+/// Transfer all accrued flash loan fees in `token` to `to`
+pub fn collect_fees(env: &Env, token: &Address, to: &Address) -> i128 {
+    let amount = accrued_fees(env, token);
+    if amount > 0 {
+        set_accrued_fees(env, token, 0);
+        token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+    }
+    amount
+}
@@ -0,0 +1,628 @@
+//! Constant-product AMM
+//!
+//! Hosts any number of `x * y = k` pairs keyed by a pair symbol. Liquidity
+//! provider shares are tracked internally per pair. Every pair maintains a
+//! cumulative price accumulator so consumers can read manipulation-resistant
+//! time-weighted average prices via `get_twap`. Pool balances can also be
+//! borrowed atomically through `flash_loan`. An optional dust policy keeps
+//! liquidity positions above a minimum size. Swaps stop while the platform
+//! pause registry, if one is set, has `swap` paused.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{
+    namespaces, EventConfig, EventEmitter, LiquidityDepositedEvent, LiquidityWithdrawnEvent,
+    TradeExecutedEvent,
+};
+use shared::invariants::Invariants;
+use shared::math::mul_div;
+use shared::pause::{self, operations};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Bytes, Env, Symbol, Vec,
+};
+
+mod dust;
+mod flash;
+mod twap;
+
+pub use dust::DustPolicy;
+pub use flash::{FlashLoanReceiver, FlashLoanReceiverClient, FLASH_FEE_BPS};
+pub use twap::{Observation, OBSERVATION_PERIOD, PRICE_SCALE};
+
+/// Swap fee retained by liquidity providers, in basis points
+pub const SWAP_FEE_BPS: i128 = 30;
+pub(crate) const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AmmError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    PairExists = 4,
+    PairNotFound = 5,
+    InvalidAmount = 6,
+    InvalidToken = 7,
+    InsufficientLiquidity = 8,
+    SlippageExceeded = 9,
+    InsufficientShares = 10,
+    InsufficientHistory = 11,
+    FlashLoanNotRepaid = 12,
+    BelowDustMinimum = 13,
+    NoDustPolicy = 14,
+    Paused = 15,
+    Overflow = 16,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    SwapCount,
+    Pairs,
+    Pair(Symbol),
+    Shares(Symbol, Address),
+    Observations(Symbol),
+    FlashFees(Address),
+    DustPolicy,
+}
+
+/// Pool state for a single pair
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pair {
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub total_shares: i128,
+    /// Σ spot_price × seconds since pair creation
+    pub price_cumulative: i128,
+    /// Timestamp the accumulator was last advanced
+    pub last_update: u64,
+}
+
+#[contract]
+pub struct AmmContract;
+
+#[contractimpl]
+impl AmmContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), AmmError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AmmError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        EventEmitter::init(&env, namespaces::DEX);
+        Ok(())
+    }
+
+    /// Register a new pair (admin only)
+    pub fn create_pair(
+        env: Env,
+        admin: Address,
+        pair: Symbol,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+
+        if token_a == token_b {
+            return Err(AmmError::InvalidToken);
+        }
+        let key = DataKey::Pair(pair.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(AmmError::PairExists);
+        }
+
+        let state = Pair {
+            token_a,
+            token_b,
+            reserve_a: 0,
+            reserve_b: 0,
+            total_shares: 0,
+            price_cumulative: 0,
+            last_update: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &state);
+
+        let mut pairs = Self::get_pairs(env.clone());
+        pairs.push_back(pair);
+        env.storage().instance().set(&DataKey::Pairs, &pairs);
+
+        Ok(())
+    }
+
+    /// Deposit both sides of a pair. The first deposit sets the price; later
+    /// deposits are credited at the current ratio (excess is not refunded, so
+    /// callers should deposit in proportion). Returns the shares minted.
+    pub fn add_liquidity(
+        env: Env,
+        provider: Address,
+        pair: Symbol,
+        amount_a: i128,
+        amount_b: i128,
+        min_shares: i128,
+    ) -> Result<i128, AmmError> {
+        provider.require_auth();
+
+        if amount_a <= 0 || amount_b <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+
+        let mut state = load_pair(&env, &pair)?;
+        twap::accumulate(&env, &mut state);
+
+        let shares = if state.total_shares == 0 {
+            isqrt(amount_a.checked_mul(amount_b).ok_or(AmmError::Overflow)?)
+        } else {
+            let shares_a = mul_div(amount_a, state.total_shares, state.reserve_a);
+            let shares_b = mul_div(amount_b, state.total_shares, state.reserve_b);
+            shares_a
+                .zip(shares_b)
+                .map(|(a, b)| a.min(b))
+                .ok_or(AmmError::Overflow)?
+        };
+        if shares <= 0 {
+            return Err(AmmError::InsufficientLiquidity);
+        }
+        if shares < min_shares {
+            return Err(AmmError::SlippageExceeded);
+        }
+
+        let shares_key = DataKey::Shares(pair.clone(), provider.clone());
+        let balance: i128 = env.storage().persistent().get(&shares_key).unwrap_or(0);
+        dust::check_position(&env, balance + shares)?;
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &state.token_a).transfer(&provider, &this, &amount_a);
+        token::Client::new(&env, &state.token_b).transfer(&provider, &this, &amount_b);
+
+        state.reserve_a += amount_a;
+        state.reserve_b += amount_b;
+        state.total_shares += shares;
+        save_pair(&env, &pair, &state);
+        twap::record_observation(&env, &pair, &state);
+        env.storage()
+            .persistent()
+            .set(&shares_key, &(balance + shares));
+
+        EventEmitter::liquidity_deposited(
+            &env,
+            LiquidityDepositedEvent {
+                provider,
+                amount: shares,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        check_invariants(&env, &[&state.token_a, &state.token_b]);
+
+        Ok(shares)
+    }
+
+    /// Burn `shares` for a pro-rata share of both reserves.
+    pub fn remove_liquidity(
+        env: Env,
+        provider: Address,
+        pair: Symbol,
+        shares: i128,
+        min_a: i128,
+        min_b: i128,
+    ) -> Result<(i128, i128), AmmError> {
+        provider.require_auth();
+
+        if shares <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+
+        let shares_key = DataKey::Shares(pair.clone(), provider.clone());
+        let balance: i128 = env.storage().persistent().get(&shares_key).unwrap_or(0);
+        if balance < shares {
+            return Err(AmmError::InsufficientShares);
+        }
+        dust::check_position(&env, balance - shares)?;
+
+        let mut state = load_pair(&env, &pair)?;
+        twap::accumulate(&env, &mut state);
+
+        let amount_a =
+            mul_div(state.reserve_a, shares, state.total_shares).ok_or(AmmError::Overflow)?;
+        let amount_b =
+            mul_div(state.reserve_b, shares, state.total_shares).ok_or(AmmError::Overflow)?;
+        if amount_a < min_a || amount_b < min_b {
+            return Err(AmmError::SlippageExceeded);
+        }
+
+        state.reserve_a -= amount_a;
+        state.reserve_b -= amount_b;
+        state.total_shares -= shares;
+        save_pair(&env, &pair, &state);
+        twap::record_observation(&env, &pair, &state);
+        env.storage()
+            .persistent()
+            .set(&shares_key, &(balance - shares));
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &state.token_a).transfer(&this, &provider, &amount_a);
+        token::Client::new(&env, &state.token_b).transfer(&this, &provider, &amount_b);
+
+        EventEmitter::liquidity_withdrawn(
+            &env,
+            LiquidityWithdrawnEvent {
+                provider,
+                amount: shares,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        check_invariants(&env, &[&state.token_a, &state.token_b]);
+
+        Ok((amount_a, amount_b))
+    }
+
+    /// Swap an exact `amount_in` of `token_in` for at least `min_out` of the
+    /// other token in the pair. Returns the amount received.
+    pub fn swap(
+        env: Env,
+        trader: Address,
+        pair: Symbol,
+        token_in: Address,
+        amount_in: i128,
+        min_out: i128,
+    ) -> Result<i128, AmmError> {
+        trader.require_auth();
+
+        if amount_in <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+        if pause::is_paused(&env, &operations::SWAP) {
+            return Err(AmmError::Paused);
+        }
+
+        let mut state = load_pair(&env, &pair)?;
+        let a_to_b = if token_in == state.token_a {
+            true
+        } else if token_in == state.token_b {
+            false
+        } else {
+            return Err(AmmError::InvalidToken);
+        };
+
+        let (reserve_in, reserve_out) = if a_to_b {
+            (state.reserve_a, state.reserve_b)
+        } else {
+            (state.reserve_b, state.reserve_a)
+        };
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out)?;
+        if amount_out < min_out {
+            return Err(AmmError::SlippageExceeded);
+        }
+
+        twap::accumulate(&env, &mut state);
+
+        let token_out = if a_to_b {
+            state.reserve_a += amount_in;
+            state.reserve_b -= amount_out;
+            state.token_b.clone()
+        } else {
+            state.reserve_b += amount_in;
+            state.reserve_a -= amount_out;
+            state.token_a.clone()
+        };
+        save_pair(&env, &pair, &state);
+        twap::record_observation(&env, &pair, &state);
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &token_in).transfer(&trader, &this, &amount_in);
+        token::Client::new(&env, &token_out).transfer(&this, &trader, &amount_out);
+
+        let price = mul_div(amount_out, PRICE_SCALE, amount_in).ok_or(AmmError::Overflow)?;
+        let fee_amount =
+            mul_div(amount_in, SWAP_FEE_BPS, BPS_DENOMINATOR).ok_or(AmmError::Overflow)?;
+        let swap_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SwapCount)
+            .unwrap_or(0)
+            + 1;
+        env.storage().instance().set(&DataKey::SwapCount, &swap_id);
+
+        EventEmitter::trade_executed(
+            &env,
+            TradeExecutedEvent {
+                trade_id: swap_id,
+                trader,
+                pair,
+                amount: amount_in,
+                price,
+                is_buy: !a_to_b,
+                fee_amount,
+                fee_token: token_in,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        check_invariants(&env, &[&state.token_a, &state.token_b]);
+
+        Ok(amount_out)
+    }
+
+    /// Record a price observation without trading. Permissionless so keepers
+    /// can keep the TWAP history dense on quiet pairs.
+    pub fn observe(env: Env, pair: Symbol) -> Result<bool, AmmError> {
+        let mut state = load_pair(&env, &pair)?;
+        twap::accumulate(&env, &mut state);
+        save_pair(&env, &pair, &state);
+        Ok(twap::record_observation(&env, &pair, &state))
+    }
+
+    /// Time-weighted average price of `token_a` in `token_b` (scaled by
+    /// `PRICE_SCALE`) over at least the last `window` seconds.
+    pub fn get_twap(env: Env, pair: Symbol, window: u64) -> Result<i128, AmmError> {
+        let state = load_pair(&env, &pair)?;
+        twap::twap(&env, &pair, &state, window)
+    }
+
+    /// Current spot price of `token_a` in `token_b`, scaled by `PRICE_SCALE`
+    pub fn get_spot_price(env: Env, pair: Symbol) -> Result<i128, AmmError> {
+        Ok(twap::spot_price(&load_pair(&env, &pair)?))
+    }
+
+    pub fn get_observations(env: Env, pair: Symbol) -> Vec<Observation> {
+        twap::get_observations(&env, &pair)
+    }
+
+    /// Quote the output of swapping `amount_in` of `token_in`
+    pub fn quote(
+        env: Env,
+        pair: Symbol,
+        token_in: Address,
+        amount_in: i128,
+    ) -> Result<i128, AmmError> {
+        let state = load_pair(&env, &pair)?;
+        if token_in == state.token_a {
+            get_amount_out(amount_in, state.reserve_a, state.reserve_b)
+        } else if token_in == state.token_b {
+            get_amount_out(amount_in, state.reserve_b, state.reserve_a)
+        } else {
+            Err(AmmError::InvalidToken)
+        }
+    }
+
+    /// Borrow `amount` of `token` from the pool for the duration of this call.
+    /// The receiver's `on_flash_loan` hook must repay `amount` plus
+    /// `FLASH_FEE_BPS` before returning. Returns the fee paid.
+    pub fn flash_loan(
+        env: Env,
+        receiver: Address,
+        token: Address,
+        amount: i128,
+        data: Bytes,
+    ) -> Result<i128, AmmError> {
+        receiver.require_auth();
+        let fee = flash::flash_loan(&env, &receiver, &token, amount, &data)?;
+        check_invariants(&env, &[&token]);
+        Ok(fee)
+    }
+
+    /// Sweep accrued flash loan fees for `token` to `to` (admin only)
+    pub fn collect_flash_fees(
+        env: Env,
+        admin: Address,
+        token: Address,
+        to: Address,
+    ) -> Result<i128, AmmError> {
+        require_admin(&env, &admin)?;
+        let collected = flash::collect_fees(&env, &token, &to);
+        check_invariants(&env, &[&token]);
+        Ok(collected)
+    }
+
+    /// Install or clear (`None`) the minimum liquidity position and the
+    /// treasury that collects dust (admin only)
+    pub fn set_dust_policy(
+        env: Env,
+        admin: Address,
+        policy: Option<DustPolicy>,
+    ) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+        dust::set(&env, policy)
+    }
+
+    pub fn dust_policy(env: Env) -> Option<DustPolicy> {
+        dust::get(&env)
+    }
+
+    /// Follow the platform pause registry at `registry`, reusing its
+    /// answers for `cache_ttl` seconds (admin only)
+    pub fn set_pause_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        cache_ttl: u64,
+    ) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+        pause::set_pause_registry(&env, &registry, cache_ttl);
+        Ok(())
+    }
+
+    /// Sweep the pool's balance of `token` beyond what it owes to reserves
+    /// and flash loan fees to the treasury (admin only). Returns the amount
+    /// swept.
+    pub fn sweep_dust(env: Env, admin: Address, token: Address) -> Result<i128, AmmError> {
+        require_admin(&env, &admin)?;
+        let swept = dust::sweep(&env, &token)?;
+        check_invariants(&env, &[&token]);
+        Ok(swept)
+    }
+
+    /// Choose which event formats the pool publishes (admin only)
+    pub fn set_event_config(env: Env, admin: Address, config: EventConfig) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+        EventConfig::store(&env, &config);
+        Ok(())
+    }
+
+    pub fn event_config(env: Env) -> EventConfig {
+        EventConfig::load(&env)
+    }
+
+    pub fn get_flash_fees(env: Env, token: Address) -> i128 {
+        flash::accrued_fees(&env, &token)
+    }
+
+    pub fn get_pair(env: Env, pair: Symbol) -> Option<Pair> {
+        env.storage().persistent().get(&DataKey::Pair(pair))
+    }
+
+    /// `token_a` and `token_b` of `pair`
+    pub fn pair_tokens(env: Env, pair: Symbol) -> Result<(Address, Address), AmmError> {
+        let state = load_pair(&env, &pair)?;
+        Ok((state.token_a, state.token_b))
+    }
+
+    pub fn get_pairs(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pairs)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn shares_of(env: Env, pair: Symbol, provider: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(pair, provider))
+            .unwrap_or(0)
+    }
+
+    /// Move liquidity shares to another holder, e.g. to stake them
+    pub fn transfer_shares(
+        env: Env,
+        pair: Symbol,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), AmmError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(AmmError::InvalidAmount);
+        }
+        load_pair(&env, &pair)?;
+        let balance = Self::shares_of(env.clone(), pair.clone(), from.clone());
+        if balance < amount {
+            return Err(AmmError::InsufficientShares);
+        }
+        dust::check_position(&env, balance - amount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(pair.clone(), from), &(balance - amount));
+        // Read after the debit so a transfer to oneself nets to nothing
+        let to_balance = Self::shares_of(env.clone(), pair.clone(), to.clone());
+        dust::check_position(&env, to_balance + amount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(pair, to), &(to_balance + amount));
+        Ok(())
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), AmmError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), AmmError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(AmmError::NotInitialized)?;
+    if stored != *admin {
+        return Err(AmmError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Every token the pool owes, as pair reserves and accrued flash loan
+/// fees, must be held by the contract
+fn check_invariants(env: &Env, tokens: &[&Address]) {
+    for token in tokens {
+        Invariants::new(env, "amm").le("reserves and fees backed by balance", || {
+            let held = token::Client::new(env, token).balance(&env.current_contract_address());
+            (dust::owed(env, token), held)
+        });
+    }
+}
+
+fn load_pair(env: &Env, pair: &Symbol) -> Result<Pair, AmmError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Pair(pair.clone()))
+        .ok_or(AmmError::PairNotFound)
+}
+
+fn save_pair(env: &Env, pair: &Symbol, state: &Pair) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Pair(pair.clone()), state);
+}
+
+/// Constant-product output for an exact input, net of the swap fee
+fn get_amount_out(amount_in: i128, reserve_in: i128, reserve_out: i128) -> Result<i128, AmmError> {
+    if amount_in <= 0 {
+        return Err(AmmError::InvalidAmount);
+    }
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+    let amount_in_with_fee = amount_in
+        .checked_mul(BPS_DENOMINATOR - SWAP_FEE_BPS)
+        .ok_or(AmmError::Overflow)?;
+    let denominator = reserve_in
+        .checked_mul(BPS_DENOMINATOR)
+        .and_then(|scaled| scaled.checked_add(amount_in_with_fee))
+        .ok_or(AmmError::Overflow)?;
+    let amount_out =
+        mul_div(reserve_out, amount_in_with_fee, denominator).ok_or(AmmError::Overflow)?;
+    if amount_out <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+    Ok(amount_out)
+}
+
+/// Integer square root (floor) via Newton's method
+fn isqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod test;
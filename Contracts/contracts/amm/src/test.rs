@@ -0,0 +1,441 @@
+#![cfg(test)]
+
+use super::*;
+use pause_registry::{PauseRegistry, PauseRegistryClient};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Bytes, Env,
+};
+
+mod repaying {
+    use crate::FlashLoanReceiver;
+    use soroban_sdk::{contract, contractimpl, token::Client as TokenClient, Address, Bytes, Env};
+
+    /// Receiver that repays principal plus fee
+    #[contract]
+    pub struct RepayingReceiver;
+
+    #[contractimpl]
+    impl FlashLoanReceiver for RepayingReceiver {
+        fn on_flash_loan(
+            env: Env,
+            pool: Address,
+            token: Address,
+            amount: i128,
+            fee: i128,
+            _data: Bytes,
+        ) {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &pool,
+                &(amount + fee),
+            );
+        }
+    }
+}
+
+mod defaulting {
+    use crate::FlashLoanReceiver;
+    use soroban_sdk::{contract, contractimpl, token::Client as TokenClient, Address, Bytes, Env};
+
+    /// Receiver that only returns the principal
+    #[contract]
+    pub struct DefaultingReceiver;
+
+    #[contractimpl]
+    impl FlashLoanReceiver for DefaultingReceiver {
+        fn on_flash_loan(
+            env: Env,
+            pool: Address,
+            token: Address,
+            amount: i128,
+            _fee: i128,
+            _data: Bytes,
+        ) {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &pool,
+                &amount,
+            );
+        }
+    }
+}
+
+use defaulting::DefaultingReceiver;
+use repaying::RepayingReceiver;
+
+struct Setup {
+    env: Env,
+    client: AmmContractClient<'static>,
+    token_a: TokenClient<'static>,
+    token_b: TokenClient<'static>,
+    admin: Address,
+    lp: Address,
+    trader: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let lp = Address::generate(&env);
+    let trader = Address::generate(&env);
+
+    let token_a_id = env.register_stellar_asset_contract(admin.clone());
+    let token_b_id = env.register_stellar_asset_contract(admin.clone());
+    for user in [&lp, &trader] {
+        StellarAssetClient::new(&env, &token_a_id).mint(user, &10_000_000);
+        StellarAssetClient::new(&env, &token_b_id).mint(user, &10_000_000);
+    }
+
+    let contract_id = env.register_contract(None, AmmContract);
+    let client = AmmContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.create_pair(&admin, &symbol_short!("XLMUSDC"), &token_a_id, &token_b_id);
+
+    Setup {
+        client,
+        token_a: TokenClient::new(&env, &token_a_id),
+        token_b: TokenClient::new(&env, &token_b_id),
+        env,
+        admin,
+        lp,
+        trader,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+#[test]
+fn test_add_and_remove_liquidity() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+
+    let shares = s.client.add_liquidity(&s.lp, &pair, &100_000, &400_000, &0);
+    assert_eq!(shares, 200_000);
+    assert_eq!(s.client.get_spot_price(&pair), 4 * PRICE_SCALE);
+
+    let (a, b) = s.client.remove_liquidity(&s.lp, &pair, &100_000, &0, &0);
+    assert_eq!((a, b), (50_000, 200_000));
+    assert_eq!(s.client.shares_of(&pair, &s.lp), 100_000);
+
+    let result = s
+        .client
+        .try_remove_liquidity(&s.lp, &pair, &100_001, &0, &0);
+    assert_eq!(result, Err(Ok(AmmError::InsufficientShares)));
+}
+
+#[test]
+fn test_oversized_first_deposit_reports_overflow() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    let amount = i128::MAX / 2;
+
+    let result = s
+        .client
+        .try_add_liquidity(&s.lp, &pair, &amount, &amount, &0);
+    assert_eq!(result, Err(Ok(AmmError::Overflow)));
+    assert_eq!(s.client.shares_of(&pair, &s.lp), 0);
+}
+
+#[test]
+fn test_remove_liquidity_from_a_deep_pool() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    // shares * reserve_a is far beyond i128
+    let deep = 1i128 << 120;
+    StellarAssetClient::new(&s.env, &s.token_a.address).mint(&s.lp, &deep);
+    let shares = s.client.add_liquidity(&s.lp, &pair, &deep, &16, &0);
+    assert_eq!(shares, 1 << 62);
+
+    let (a, b) = s.client.remove_liquidity(&s.lp, &pair, &(1 << 61), &0, &0);
+    assert_eq!((a, b), (1 << 119, 8));
+}
+
+#[test]
+fn test_oversized_swap_reports_overflow() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    let amount = i128::MAX / 1_000;
+    let result = s.client.try_quote(&pair, &s.token_a.address, &amount);
+    assert_eq!(result, Err(Ok(AmmError::Overflow)));
+    let result = s
+        .client
+        .try_swap(&s.trader, &pair, &s.token_a.address, &amount, &0);
+    assert_eq!(result, Err(Ok(AmmError::Overflow)));
+}
+
+#[test]
+fn test_swap_respects_constant_product_and_slippage() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    let quoted = s.client.quote(&pair, &s.token_a.address, &10_000);
+    let result = s
+        .client
+        .try_swap(&s.trader, &pair, &s.token_a.address, &10_000, &(quoted + 1));
+    assert_eq!(result, Err(Ok(AmmError::SlippageExceeded)));
+
+    let out = s
+        .client
+        .swap(&s.trader, &pair, &s.token_a.address, &10_000, &quoted);
+    assert_eq!(out, quoted);
+    assert_eq!(s.token_b.balance(&s.trader), 10_000_000 + out);
+
+    let state = s.client.get_pair(&pair).unwrap();
+    assert!(state.reserve_a * state.reserve_b >= 1_000_000 * 1_000_000);
+}
+
+#[test]
+fn test_twap_over_window() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+
+    // Price 2.0 from t=1_000
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &2_000_000, &0);
+
+    // 30 minutes later the price moves
+    set_time(&s.env, 2_800);
+    assert_eq!(s.client.get_twap(&pair, &1_800), 2 * PRICE_SCALE);
+    s.client
+        .swap(&s.trader, &pair, &s.token_b.address, &1_000_000, &0);
+    let new_price = s.client.get_spot_price(&pair);
+    assert!(new_price > 2 * PRICE_SCALE);
+
+    // A 30 minute window anchored at the t=1_000 observation blends both prices
+    set_time(&s.env, 3_400);
+    let expected = (2 * PRICE_SCALE * 1_800 + new_price * 600) / 2_400;
+    assert_eq!(s.client.get_twap(&pair, &1_800), expected);
+
+    // Not enough history for a longer window
+    let result = s.client.try_get_twap(&pair, &3_000);
+    assert_eq!(result, Err(Ok(AmmError::InsufficientHistory)));
+}
+
+#[test]
+fn test_observations_are_rate_limited() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    assert!(!s.client.observe(&pair));
+    set_time(&s.env, 1_000 + OBSERVATION_PERIOD);
+    assert!(s.client.observe(&pair));
+    assert_eq!(s.client.get_observations(&pair).len(), 2);
+}
+
+#[test]
+fn test_create_pair_admin_only() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_create_pair(
+        &stranger,
+        &symbol_short!("OTHER"),
+        &s.token_a.address,
+        &s.token_b.address,
+    );
+    assert_eq!(result, Err(Ok(AmmError::Unauthorized)));
+
+    let result = s.client.try_create_pair(
+        &s.admin,
+        &symbol_short!("XLMUSDC"),
+        &s.token_a.address,
+        &s.token_b.address,
+    );
+    assert_eq!(result, Err(Ok(AmmError::PairExists)));
+}
+
+#[test]
+fn test_flash_loan_repaid_with_fee() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    let receiver = s.env.register_contract(None, RepayingReceiver);
+    StellarAssetClient::new(&s.env, &s.token_a.address).mint(&receiver, &1_000);
+
+    let fee = s
+        .client
+        .flash_loan(&receiver, &s.token_a.address, &500_000, &Bytes::new(&s.env));
+    assert_eq!(fee, 450);
+    assert_eq!(s.token_a.balance(&receiver), 550);
+    assert_eq!(s.token_a.balance(&s.client.address), 1_000_450);
+    assert_eq!(s.client.get_flash_fees(&s.token_a.address), 450);
+
+    // Reserves are untouched; fees are swept separately
+    assert_eq!(s.client.get_pair(&pair).unwrap().reserve_a, 1_000_000);
+    let collected = s
+        .client
+        .collect_flash_fees(&s.admin, &s.token_a.address, &s.admin);
+    assert_eq!(collected, 450);
+    assert_eq!(s.token_a.balance(&s.admin), 450);
+    assert_eq!(s.client.get_flash_fees(&s.token_a.address), 0);
+}
+
+#[test]
+fn test_flash_loan_without_fee_reverts() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    let receiver = s.env.register_contract(None, DefaultingReceiver);
+    let result =
+        s.client
+            .try_flash_loan(&receiver, &s.token_a.address, &500_000, &Bytes::new(&s.env));
+    assert_eq!(result, Err(Ok(AmmError::FlashLoanNotRepaid)));
+    assert_eq!(s.token_a.balance(&s.client.address), 1_000_000);
+
+    let result = s.client.try_flash_loan(
+        &receiver,
+        &s.token_a.address,
+        &2_000_000,
+        &Bytes::new(&s.env),
+    );
+    assert_eq!(result, Err(Ok(AmmError::InsufficientLiquidity)));
+}
+
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let s = setup();
+    let successor = Address::generate(&s.env);
+
+    s.client.propose_admin(&s.admin, &successor);
+    // Until accepted, the proposed admin has no power
+    let result = s
+        .client
+        .try_propose_admin(&successor, &Address::generate(&s.env));
+    assert_eq!(result, Err(Ok(AmmError::Unauthorized)));
+
+    s.client.accept_admin(&successor);
+    assert!(s.client.pending_admin().is_none());
+    let result = s.client.try_propose_admin(&s.admin, &successor);
+    assert_eq!(result, Err(Ok(AmmError::Unauthorized)));
+}
+
+#[test]
+fn test_dust_policy_keeps_positions_above_minimum() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    let treasury = Address::generate(&s.env);
+    s.client.set_dust_policy(
+        &s.admin,
+        &Some(DustPolicy {
+            min_liquidity: 1_000,
+            treasury,
+        }),
+    );
+
+    let result = s.client.try_add_liquidity(&s.lp, &pair, &100, &400, &0);
+    assert_eq!(result, Err(Ok(AmmError::BelowDustMinimum)));
+    let shares = s.client.add_liquidity(&s.lp, &pair, &10_000, &40_000, &0);
+    assert_eq!(shares, 20_000);
+
+    // Withdrawals and transfers must leave zero or at least the minimum
+    let result = s.client.try_remove_liquidity(&s.lp, &pair, &19_500, &0, &0);
+    assert_eq!(result, Err(Ok(AmmError::BelowDustMinimum)));
+    let result = s.client.try_transfer_shares(&pair, &s.lp, &s.trader, &999);
+    assert_eq!(result, Err(Ok(AmmError::BelowDustMinimum)));
+    s.client.transfer_shares(&pair, &s.lp, &s.trader, &1_000);
+    s.client.remove_liquidity(&s.lp, &pair, &19_000, &0, &0);
+    assert_eq!(s.client.shares_of(&pair, &s.lp), 0);
+}
+
+#[test]
+fn test_transfer_shares_to_self_mints_nothing() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    let shares = s.client.add_liquidity(&s.lp, &pair, &10_000, &40_000, &0);
+
+    s.client.transfer_shares(&pair, &s.lp, &s.lp, &shares);
+    s.client.transfer_shares(&pair, &s.lp, &s.lp, &shares);
+    assert_eq!(s.client.shares_of(&pair, &s.lp), shares);
+    let result = s
+        .client
+        .try_remove_liquidity(&s.lp, &pair, &(shares + 1), &0, &0);
+    assert_eq!(result, Err(Ok(AmmError::InsufficientShares)));
+}
+
+#[test]
+fn test_sweep_dust_collects_unowed_balance() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    let treasury = Address::generate(&s.env);
+    s.client.add_liquidity(&s.lp, &pair, &10_000, &40_000, &0);
+    // A direct transfer is not part of any reserve
+    s.token_a.transfer(&s.trader, &s.client.address, &7);
+
+    let result = s.client.try_sweep_dust(&s.admin, &s.token_a.address);
+    assert_eq!(result, Err(Ok(AmmError::NoDustPolicy)));
+
+    s.client.set_dust_policy(
+        &s.admin,
+        &Some(DustPolicy {
+            min_liquidity: 1,
+            treasury: treasury.clone(),
+        }),
+    );
+    assert_eq!(s.client.sweep_dust(&s.admin, &s.token_a.address), 7);
+    assert_eq!(s.token_a.balance(&treasury), 7);
+    assert_eq!(s.token_a.balance(&s.client.address), 10_000);
+    assert_eq!(s.client.sweep_dust(&s.admin, &s.token_b.address), 0);
+    assert_eq!(s.client.sweep_dust(&s.admin, &s.token_a.address), 0);
+}
+
+#[test]
+fn test_swaps_follow_the_pause_registry() {
+    let s = setup();
+    let pair = symbol_short!("XLMUSDC");
+    s.client
+        .add_liquidity(&s.lp, &pair, &1_000_000, &1_000_000, &0);
+
+    let governance = Address::generate(&s.env);
+    let registry_id = s.env.register_contract(None, PauseRegistry);
+    let registry = PauseRegistryClient::new(&s.env, &registry_id);
+    registry.initialize(&governance);
+    s.client.set_pause_registry(&s.admin, &registry_id, &60);
+
+    // A successful swap caches "not paused", so a pause takes effect once
+    // the answer expires
+    s.client
+        .swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+    registry.set_paused(&governance, &operations::SWAP, &true);
+    s.client
+        .swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+    set_time(&s.env, 1_060);
+    let result = s
+        .client
+        .try_swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+    assert_eq!(result, Err(Ok(AmmError::Paused)));
+
+    // Failed swaps cache nothing, so resuming is immediate
+    registry.set_paused(&governance, &operations::SWAP, &false);
+    s.client
+        .swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+
+    // Other operations being paused does not stop swaps
+    registry.set_paused(&governance, &operations::BORROW, &true);
+    set_time(&s.env, 1_120);
+    s.client
+        .swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+    registry.set_paused(&governance, &operations::ALL, &true);
+    set_time(&s.env, 1_180);
+    let result = s
+        .client
+        .try_swap(&s.trader, &pair, &s.token_a.address, &1_000, &0);
+    assert_eq!(result, Err(Ok(AmmError::Paused)));
+}
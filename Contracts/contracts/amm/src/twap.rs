@@ -0,0 +1,118 @@
+//! Time-weighted average price accumulator
+//!
+//! Each pair keeps a running `price_cumulative` (spot price × seconds) that is
+//! advanced before every reserve change. Snapshots of the accumulator are kept
+//! in a bounded list of observations, recorded at most once per
+//! `OBSERVATION_PERIOD`, so the average price over a window is
+//! `(cumulative_now - cumulative_then) / (now - then)`.
+
+use shared::events::{EventEmitter, PriceUpdatedEvent};
+use soroban_sdk::{contracttype, Env, Symbol, Vec};
+
+use crate::{AmmError, DataKey, Pair};
+
+/// Fixed-point scale for prices (7 decimals, matching Stellar assets)
+pub const PRICE_SCALE: i128 = 10_000_000;
+/// Minimum spacing between two recorded observations
+pub const OBSERVATION_PERIOD: u64 = 60;
+/// Maximum number of observations retained per pair
+pub const MAX_OBSERVATIONS: u32 = 64;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub price_cumulative: i128,
+}
+
+/// Spot price of `token_a` denominated in `token_b`, scaled by `PRICE_SCALE`
+pub fn spot_price(pair: &Pair) -> i128 {
+    if pair.reserve_a == 0 {
+        return 0;
+    }
+    pair.reserve_b * PRICE_SCALE / pair.reserve_a
+}
+
+/// Accumulator value extrapolated to `now` using the current reserves
+pub fn cumulative_at(pair: &Pair, now: u64) -> i128 {
+    let elapsed = now.saturating_sub(pair.last_update) as i128;
+    pair.price_cumulative + spot_price(pair) * elapsed
+}
+
+/// Advance the accumulator to the current ledger time. Must run before the
+/// reserves of `pair` change.
+pub fn accumulate(env: &Env, pair: &mut Pair) {
+    let now = env.ledger().timestamp();
+    pair.price_cumulative = cumulative_at(pair, now);
+    pair.last_update = now;
+}
+
+pub fn get_observations(env: &Env, pair_id: &Symbol) -> Vec<Observation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Observations(pair_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record an observation of the (already accumulated) pair if the last one is
+/// at least `OBSERVATION_PERIOD` old. Returns whether one was written.
+pub fn record_observation(env: &Env, pair_id: &Symbol, pair: &Pair) -> bool {
+    let now = env.ledger().timestamp();
+    let mut observations = get_observations(env, pair_id);
+
+    if let Some(last) = observations.last() {
+        if now < last.timestamp + OBSERVATION_PERIOD {
+            return false;
+        }
+    }
+
+    let price_cumulative = cumulative_at(pair, now);
+    observations.push_back(Observation {
+        timestamp: now,
+        price_cumulative,
+    });
+    while observations.len() > MAX_OBSERVATIONS {
+        observations.pop_front();
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Observations(pair_id.clone()), &observations);
+
+    EventEmitter::price_updated(
+        env,
+        PriceUpdatedEvent {
+            pair: pair_id.clone(),
+            price: spot_price(pair),
+            price_cumulative,
+            timestamp: now,
+        },
+    );
+
+    true
+}
+
+/// Average price over (at least) the last `window` seconds, measured from the
+/// newest observation that is at least `window` old.
+pub fn twap(env: &Env, pair_id: &Symbol, pair: &Pair, window: u64) -> Result<i128, AmmError> {
+    if window == 0 {
+        return Err(AmmError::InvalidAmount);
+    }
+
+    let now = env.ledger().timestamp();
+    let target = now
+        .checked_sub(window)
+        .ok_or(AmmError::InsufficientHistory)?;
+
+    let mut anchor: Option<Observation> = None;
+    for observation in get_observations(env, pair_id).iter() {
+        if observation.timestamp <= target {
+            anchor = Some(observation);
+        } else {
+            break;
+        }
+    }
+
+    let anchor = anchor.ok_or(AmmError::InsufficientHistory)?;
+    let elapsed = (now - anchor.timestamp) as i128;
+    Ok((cumulative_at(pair, now) - anchor.price_cumulative) / elapsed)
+}
@@ -0,0 +1,230 @@
+//! Attestation registry: revocable claims about addresses
+//!
+//! The admin approves attestors per claim name (a KYC provider for
+//! `kyc_tier2`, say). An approved attestor records the claim about a
+//! subject, optionally with an expiry, and can revoke it; the admin can
+//! revoke any claim. A claim only counts while its attestor is still
+//! approved for it, so dropping an attestor withdraws everything it
+//! vouched for at once.
+//!
+//! Compliance-gated contracts check claims through
+//! `shared::attestations`.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{AttestationEvent, EventEmitter};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AttestationError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    /// The expiry is not in the future
+    InvalidExpiry = 4,
+    ClaimNotFound = 5,
+}
+
+/// A claim recorded about a subject
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub attestor: Address,
+    pub issued_at: u64,
+    /// When the claim lapses, or 0 if it never does
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Attestor(Address, Symbol),
+    Claim(Address, Symbol),
+}
+
+#[contract]
+pub struct AttestationRegistry;
+
+#[contractimpl]
+impl AttestationRegistry {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), AttestationError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AttestationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Approve or drop `attestor` for `claim`. Dropping it invalidates the
+    /// claims it issued; approving it again restores those not revoked.
+    pub fn set_attestor(
+        env: Env,
+        admin: Address,
+        attestor: Address,
+        claim: Symbol,
+        approved: bool,
+    ) -> Result<(), AttestationError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::Attestor(attestor, claim);
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Record `claim` about `subject`, replacing any earlier one. An
+    /// `expires_at` of 0 never lapses.
+    pub fn issue(
+        env: Env,
+        attestor: Address,
+        subject: Address,
+        claim: Symbol,
+        expires_at: u64,
+    ) -> Result<(), AttestationError> {
+        attestor.require_auth();
+        if !Self::is_attestor(env.clone(), attestor.clone(), claim.clone()) {
+            return Err(AttestationError::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if expires_at != 0 && expires_at <= now {
+            return Err(AttestationError::InvalidExpiry);
+        }
+        let record = Claim {
+            attestor: attestor.clone(),
+            issued_at: now,
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(subject.clone(), claim.clone()), &record);
+
+        EventEmitter::attestation_issued(
+            &env,
+            AttestationEvent {
+                subject,
+                claim,
+                attestor: attestor.clone(),
+                expires_at,
+                changed_by: attestor,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Revoke `subject`'s `claim`. Allowed for the attestor that issued it
+    /// and for the admin.
+    pub fn revoke(
+        env: Env,
+        caller: Address,
+        subject: Address,
+        claim: Symbol,
+    ) -> Result<(), AttestationError> {
+        caller.require_auth();
+        let key = DataKey::Claim(subject.clone(), claim.clone());
+        let record: Claim = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AttestationError::ClaimNotFound)?;
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AttestationError::NotInitialized)?;
+        if caller != record.attestor && caller != admin {
+            return Err(AttestationError::Unauthorized);
+        }
+        env.storage().persistent().remove(&key);
+
+        EventEmitter::attestation_revoked(
+            &env,
+            AttestationEvent {
+                subject,
+                claim,
+                attestor: record.attestor,
+                expires_at: record.expires_at,
+                changed_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    // ---- views ----
+
+    /// Whether `subject` holds an unexpired, unrevoked `claim` from an
+    /// attestor still approved for it
+    pub fn has_claim(env: Env, subject: Address, claim: Symbol) -> bool {
+        let Some(record) = Self::claim(env.clone(), subject, claim.clone()) else {
+            return false;
+        };
+        let live = record.expires_at == 0 || env.ledger().timestamp() < record.expires_at;
+        live && Self::is_attestor(env, record.attestor, claim)
+    }
+
+    /// Stored claim, whether or not it still counts
+    pub fn claim(env: Env, subject: Address, claim: Symbol) -> Option<Claim> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claim(subject, claim))
+    }
+
+    pub fn is_attestor(env: Env, attestor: Address, claim: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Attestor(attestor, claim))
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), AttestationError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), AttestationError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), AttestationError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(AttestationError::NotInitialized)?;
+    if stored != *admin {
+        return Err(AttestationError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use super::*;
+use shared::attestations::{claims, AttestationClient, ClaimRequirement};
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::{assert_standard_event, payload};
+
+struct Setup {
+    env: Env,
+    client: AttestationRegistryClient<'static>,
+    admin: Address,
+    kyc: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let client =
+        AttestationRegistryClient::new(&env, &env.register_contract(None, AttestationRegistry));
+    let admin = Address::generate(&env);
+    let kyc = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_attestor(&admin, &kyc, &claims::KYC_TIER_2, &true);
+    Setup {
+        env,
+        client,
+        admin,
+        kyc,
+    }
+}
+
+#[test]
+fn test_claims_expire_and_are_revocable() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let tier = claims::KYC_TIER_2;
+
+    s.client.issue(&s.kyc, &alice, &tier, &2_000);
+    let captured = assert_standard_event(&s.env, topics::ATTESTATION_ISSUED, |_| true);
+    let event: AttestationEvent = payload(&captured);
+    assert_eq!((event.claim, event.expires_at), (tier.clone(), 2_000));
+
+    // Gated contracts read through the shared interface
+    let requirement = ClaimRequirement {
+        registry: s.client.address.clone(),
+        claim: tier.clone(),
+    };
+    assert!(requirement.is_met(&s.env, &alice));
+    let reader = AttestationClient::new(&s.env, &s.client.address);
+    assert!(!reader.has_claim(&alice, &claims::KYC_TIER_1));
+    s.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(!reader.has_claim(&alice, &tier));
+
+    // Reissuing replaces the lapsed claim; the admin can revoke it
+    s.client.issue(&s.kyc, &alice, &tier, &0);
+    assert!(reader.has_claim(&alice, &tier));
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_revoke(&stranger, &alice, &tier);
+    assert_eq!(result, Err(Ok(AttestationError::Unauthorized)));
+    s.client.revoke(&s.admin, &alice, &tier);
+    let captured = assert_standard_event(&s.env, topics::ATTESTATION_REVOKED, |_| true);
+    let event: AttestationEvent = payload(&captured);
+    assert_eq!((event.attestor, event.changed_by), (s.kyc.clone(), s.admin));
+    assert!(!reader.has_claim(&alice, &tier));
+    let result = s.client.try_revoke(&s.kyc, &alice, &tier);
+    assert_eq!(result, Err(Ok(AttestationError::ClaimNotFound)));
+}
+
+#[test]
+fn test_only_approved_attestors_vouch() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let tier = claims::KYC_TIER_2;
+
+    let result = s.client.try_issue(&s.kyc, &alice, &claims::ACCREDITED, &0);
+    assert_eq!(result, Err(Ok(AttestationError::Unauthorized)));
+    let result = s.client.try_issue(&s.kyc, &alice, &tier, &1_000);
+    assert_eq!(result, Err(Ok(AttestationError::InvalidExpiry)));
+
+    // Dropping the attestor withdraws its claims until it is approved again
+    s.client.issue(&s.kyc, &alice, &tier, &0);
+    s.client.set_attestor(&s.admin, &s.kyc, &tier, &false);
+    assert!(!s.client.has_claim(&alice, &tier));
+    assert!(s.client.claim(&alice, &tier).is_some());
+    s.client.set_attestor(&s.admin, &s.kyc, &tier, &true);
+    assert!(s.client.has_claim(&alice, &tier));
+
+    let result = s.client.try_set_attestor(&s.kyc, &s.kyc, &tier, &true);
+    assert_eq!(result, Err(Ok(AttestationError::Unauthorized)));
+}
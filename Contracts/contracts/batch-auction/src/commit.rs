@@ -0,0 +1,108 @@
+//! Commit-reveal order submission
+//!
+//! With commit-reveal enabled, orders cannot be submitted in the clear.
+//! A trader first commits to `sha256((trader, is_buy, amount,
+//! limit_price, salt))` with a bond in the quote token, which places the
+//! hidden order in the open batch. Within `reveal_ledgers` ledgers they
+//! reveal the order and salt, which escrows it like a plain submission and
+//! returns the bond. A batch cannot settle while one of its commitments
+//! can still be revealed; commitments left unrevealed past their deadline
+//! have their bonds slashed to the treasury at settlement.
+
+use shared::events::{EventEmitter, OrderCommitEvent};
+use shared::pagination::IndexedList;
+use soroban_sdk::{contracttype, symbol_short, token, xdr::ToXdr, Address, BytesN, Env, Vec};
+
+use crate::{AuctionError, DataKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitConfig {
+    /// Quote-token bond posted with each commitment
+    pub bond: i128,
+    /// Ledgers after the commitment within which it must be revealed
+    pub reveal_ledgers: u32,
+    /// Receiver of slashed bonds
+    pub treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitState {
+    Pending,
+    Revealed,
+    Slashed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Commitment {
+    pub trader: Address,
+    pub commitment: BytesN<32>,
+    pub bond: i128,
+    /// Last ledger the order can be revealed in
+    pub reveal_deadline: u32,
+    pub state: CommitState,
+}
+
+pub fn config(env: &Env) -> Option<CommitConfig> {
+    env.storage().instance().get(&DataKey::CommitConfig)
+}
+
+/// Hash a trader commits to for an order
+pub fn order_hash(
+    env: &Env,
+    trader: &Address,
+    is_buy: bool,
+    amount: i128,
+    limit_price: i128,
+    salt: &BytesN<32>,
+) -> BytesN<32> {
+    let payload = (trader.clone(), is_buy, amount, limit_price, salt.clone()).to_xdr(env);
+    env.crypto().sha256(&payload)
+}
+
+/// Order commitments of a batch; a commitment's id is its index
+pub fn commits(batch_id: u64) -> IndexedList<u64> {
+    IndexedList::new(symbol_short!("commits"), batch_id)
+}
+
+/// Slash the bonds of `batch_id`'s expired commitments, failing while any
+/// commitment can still be revealed
+pub fn close(env: &Env, quote_token: &Address, batch_id: u64) -> Result<(), AuctionError> {
+    let commits = commits(batch_id);
+    let stored: Vec<Commitment> = commits.all(env);
+    if stored.is_empty() {
+        return Ok(());
+    }
+    let ledger = env.ledger().sequence();
+    let treasury = config(env).map(|config| config.treasury);
+    let quote = token::Client::new(env, quote_token);
+    for (commit_id, mut commit) in stored.iter().enumerate() {
+        if commit.state != CommitState::Pending {
+            continue;
+        }
+        if ledger <= commit.reveal_deadline {
+            return Err(AuctionError::RevealPending);
+        }
+        // Commit-reveal may have been switched off since; the bond then
+        // goes back to the trader
+        let receiver = treasury.clone().unwrap_or(commit.trader.clone());
+        quote.transfer(&env.current_contract_address(), &receiver, &commit.bond);
+        commit.state = CommitState::Slashed;
+        commits.set(env, commit_id as u32, &commit);
+        EventEmitter::commit_slashed(
+            env,
+            OrderCommitEvent {
+                batch_id,
+                commit_id: commit_id as u32,
+                trader: commit.trader,
+                commitment: commit.commitment,
+                bond: commit.bond,
+                reveal_deadline: commit.reveal_deadline,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(())
+}
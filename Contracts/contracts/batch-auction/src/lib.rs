@@ -0,0 +1,664 @@
+//! Batch auction trading mode
+//!
+//! Instead of filling orders one by one, this contract collects limit
+//! orders for a base/quote market over fixed windows and matches each
+//! window's orders together at a single clearing price. Since every fill in
+//! a batch trades at the same price, placing an order ahead of (or around)
+//! another within the window gains nothing.
+//!
+//! Batch `n` accepts orders from `genesis + (n - 1) * batch_duration` until
+//! the next batch opens; after that anyone can settle it. Settlement picks
+//! the limit price that matches the most volume (ties go to the smallest
+//! imbalance between the two sides, then to the midpoint of the tied
+//! prices). Every buy at or above the clearing price and every sell at or
+//! below it is eligible; the side with less eligible volume fills in full
+//! and the other side fills pro rata.
+//!
+//! Orders escrow their worst case: sells the base amount, buys the quote
+//! cost at their limit price. Settlement pays out fills at the clearing
+//! price and refunds the rest. Buyers' costs round up and sellers'
+//! proceeds round down, so quote dust stays in the contract.
+//!
+//! The admin can require orders to be committed while hidden and revealed
+//! later ([`commit`]), so not even their limit prices are visible while
+//! the batch is open.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{
+    BatchSettledEvent, EventEmitter, OrderCommitEvent, OrderRevealEvent, TradeExecutedEvent,
+};
+use shared::math::mul_div;
+use shared::pagination::{IndexedList, Pagination};
+use shared::positions::{Position, Positions};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
+    Symbol, Vec,
+};
+
+pub mod commit;
+
+pub use commit::{CommitConfig, CommitState, Commitment};
+
+/// Fixed-point scale of prices (quote per base)
+pub const PRICE_SCALE: i128 = 10_000_000;
+/// Orders one batch can hold, bounding the settlement loop
+pub const MAX_ORDERS_PER_BATCH: u32 = 40;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    InvalidOrder = 4,
+    BatchFull = 5,
+    BatchClosed = 6,
+    BatchOpen = 7,
+    AlreadySettled = 8,
+    OrderNotFound = 9,
+    Unauthorized = 10,
+    CommitRequired = 11,
+    CommitDisabled = 12,
+    RevealPending = 13,
+    RevealExpired = 14,
+    InvalidReveal = 15,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    /// Market symbol reported in trade events
+    pub market: Symbol,
+    pub base_token: Address,
+    pub quote_token: Address,
+    /// Start of batch 1
+    pub genesis: u64,
+    /// Length of each batch's order window, in seconds
+    pub batch_duration: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchOrder {
+    pub trader: Address,
+    pub is_buy: bool,
+    /// Base amount to trade, zero once cancelled
+    pub amount: i128,
+    pub limit_price: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    /// Price every fill traded at, zero if the batch did not cross
+    pub clearing_price: i128,
+    /// Base amount matched
+    pub volume: i128,
+    pub settled_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Settlement(u64),
+    CommitConfig,
+}
+
+#[contract]
+pub struct BatchAuction;
+
+#[contractimpl]
+impl BatchAuction {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        market: Symbol,
+        base_token: Address,
+        quote_token: Address,
+        batch_duration: u64,
+    ) -> Result<(), AuctionError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(AuctionError::AlreadyInitialized);
+        }
+        if batch_duration == 0 || base_token == quote_token {
+            return Err(AuctionError::InvalidConfig);
+        }
+        let config = Config {
+            admin,
+            market,
+            base_token,
+            quote_token,
+            genesis: env.ledger().timestamp(),
+            batch_duration,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Add a limit order for `amount` base to the open batch, escrowing
+    /// its worst case. Returns `(batch_id, order_id)`.
+    pub fn submit_order(
+        env: Env,
+        trader: Address,
+        is_buy: bool,
+        amount: i128,
+        limit_price: i128,
+    ) -> Result<(u64, u32), AuctionError> {
+        trader.require_auth();
+        let config = load_config(&env)?;
+        if commit::config(&env).is_some() {
+            return Err(AuctionError::CommitRequired);
+        }
+        let batch_id = current_batch(&env, &config);
+        if orders(batch_id).len(&env) >= MAX_ORDERS_PER_BATCH {
+            return Err(AuctionError::BatchFull);
+        }
+        let order = BatchOrder {
+            trader,
+            is_buy,
+            amount,
+            limit_price,
+        };
+        Ok((batch_id, add_order(&env, &config, batch_id, order)?))
+    }
+
+    /// Require orders to go through commit-reveal, or with `None` accept
+    /// plain submissions again (admin only). Commitments already made can
+    /// still be revealed.
+    pub fn set_commit_reveal(
+        env: Env,
+        admin: Address,
+        commit_config: Option<CommitConfig>,
+    ) -> Result<(), AuctionError> {
+        require_admin(&env, &admin)?;
+        match commit_config {
+            Some(commit_config) => {
+                if commit_config.bond < 0 || commit_config.reveal_ledgers == 0 {
+                    return Err(AuctionError::InvalidConfig);
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::CommitConfig, &commit_config);
+            }
+            None => env.storage().instance().remove(&DataKey::CommitConfig),
+        }
+        Ok(())
+    }
+
+    /// Commit to a hidden order in the open batch, posting the bond.
+    /// `commitment` is [`Self::order_hash`] of the order. Returns
+    /// `(batch_id, commit_id)`.
+    pub fn commit_order(
+        env: Env,
+        trader: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(u64, u32), AuctionError> {
+        trader.require_auth();
+        let config = load_config(&env)?;
+        let commit_config = commit::config(&env).ok_or(AuctionError::CommitDisabled)?;
+        let batch_id = current_batch(&env, &config);
+        let commits = commit::commits(batch_id);
+        let pending = commits
+            .all::<Commitment>(&env)
+            .iter()
+            .filter(|commit| commit.state == CommitState::Pending)
+            .count() as u32;
+        if orders(batch_id).len(&env) + pending >= MAX_ORDERS_PER_BATCH {
+            return Err(AuctionError::BatchFull);
+        }
+
+        if commit_config.bond > 0 {
+            token::Client::new(&env, &config.quote_token).transfer(
+                &trader,
+                &env.current_contract_address(),
+                &commit_config.bond,
+            );
+        }
+        let reveal_deadline = env.ledger().sequence() + commit_config.reveal_ledgers;
+        let commit_id = commits.push(
+            &env,
+            &Commitment {
+                trader: trader.clone(),
+                commitment: commitment.clone(),
+                bond: commit_config.bond,
+                reveal_deadline,
+                state: CommitState::Pending,
+            },
+        );
+        EventEmitter::order_committed(
+            &env,
+            OrderCommitEvent {
+                batch_id,
+                commit_id,
+                trader,
+                commitment,
+                bond: commit_config.bond,
+                reveal_deadline,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok((batch_id, commit_id))
+    }
+
+    /// Open a commitment: escrow the order it hid, add it to the batch it
+    /// was committed in and return the bond. Returns the order id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reveal_order(
+        env: Env,
+        trader: Address,
+        batch_id: u64,
+        commit_id: u32,
+        is_buy: bool,
+        amount: i128,
+        limit_price: i128,
+        salt: BytesN<32>,
+    ) -> Result<u32, AuctionError> {
+        trader.require_auth();
+        let config = load_config(&env)?;
+        let commits = commit::commits(batch_id);
+        let mut commitment = commits
+            .get::<Commitment>(&env, commit_id)
+            .filter(|commit| commit.state == CommitState::Pending)
+            .ok_or(AuctionError::OrderNotFound)?;
+        if commitment.trader != trader {
+            return Err(AuctionError::Unauthorized);
+        }
+        if env.ledger().sequence() > commitment.reveal_deadline {
+            return Err(AuctionError::RevealExpired);
+        }
+        let hash = commit::order_hash(&env, &trader, is_buy, amount, limit_price, &salt);
+        if hash != commitment.commitment {
+            return Err(AuctionError::InvalidReveal);
+        }
+
+        let order = BatchOrder {
+            trader: trader.clone(),
+            is_buy,
+            amount,
+            limit_price,
+        };
+        let order_id = add_order(&env, &config, batch_id, order)?;
+        if commitment.bond > 0 {
+            token::Client::new(&env, &config.quote_token).transfer(
+                &env.current_contract_address(),
+                &trader,
+                &commitment.bond,
+            );
+        }
+        commitment.state = CommitState::Revealed;
+        commits.set(&env, commit_id, &commitment);
+        EventEmitter::order_revealed(
+            &env,
+            OrderRevealEvent {
+                batch_id,
+                commit_id,
+                order_id,
+                trader,
+                is_buy,
+                amount,
+                limit_price,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(order_id)
+    }
+
+    /// Withdraw an order from its batch while the batch is still open
+    pub fn cancel_order(
+        env: Env,
+        trader: Address,
+        batch_id: u64,
+        order_id: u32,
+    ) -> Result<(), AuctionError> {
+        trader.require_auth();
+        let config = load_config(&env)?;
+        if batch_id != current_batch(&env, &config) {
+            return Err(AuctionError::BatchClosed);
+        }
+        let orders = orders(batch_id);
+        let mut order = orders
+            .get::<BatchOrder>(&env, order_id)
+            .filter(|order| order.amount > 0)
+            .ok_or(AuctionError::OrderNotFound)?;
+        if order.trader != trader {
+            return Err(AuctionError::Unauthorized);
+        }
+        refund(&env, &config, &order, 0, 0);
+        order.amount = 0;
+        orders.set(&env, order_id, &order);
+        Ok(())
+    }
+
+    /// Match a closed batch at its clearing price and pay out every order.
+    /// Anyone can settle. Returns the clearing price.
+    pub fn settle(env: Env, caller: Address, batch_id: u64) -> Result<i128, AuctionError> {
+        caller.require_auth();
+        let config = load_config(&env)?;
+        if batch_id == 0 || batch_id >= current_batch(&env, &config) {
+            return Err(AuctionError::BatchOpen);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Settlement(batch_id))
+        {
+            return Err(AuctionError::AlreadySettled);
+        }
+
+        commit::close(&env, &config.quote_token, batch_id)?;
+
+        let orders: Vec<BatchOrder> = orders(batch_id).all(&env);
+        let (clearing_price, volume) = clearing_price(&orders);
+        let (bought, sold) = if volume > 0 {
+            (
+                eligible_volume(&orders, true, clearing_price),
+                eligible_volume(&orders, false, clearing_price),
+            )
+        } else {
+            (0, 0)
+        };
+        // Base still to hand out on each side's pro-rata fills; the last
+        // eligible order of a side takes the rounding remainder
+        let mut buys_left = volume;
+        let mut sells_left = volume;
+        let mut buys_remaining = eligible_count(&orders, true, clearing_price, volume);
+        let mut sells_remaining = eligible_count(&orders, false, clearing_price, volume);
+
+        let timestamp = env.ledger().timestamp();
+        let mut filled = 0;
+        for (order_id, order) in orders.iter().enumerate() {
+            if order.amount == 0 {
+                continue;
+            }
+            let fill = if volume > 0 && crosses(&order, clearing_price) {
+                let (left, remaining, side_total) = if order.is_buy {
+                    (&mut buys_left, &mut buys_remaining, bought)
+                } else {
+                    (&mut sells_left, &mut sells_remaining, sold)
+                };
+                *remaining -= 1;
+                let fill = if *remaining == 0 {
+                    *left
+                } else {
+                    mul_div(order.amount, volume, side_total).unwrap_or(0)
+                };
+                *left -= fill;
+                fill
+            } else {
+                0
+            };
+            let proceeds = if order.is_buy {
+                quote_cost(fill, clearing_price).unwrap_or(0)
+            } else {
+                mul_div(fill, clearing_price, PRICE_SCALE).unwrap_or(0)
+            };
+            refund(&env, &config, &order, fill, proceeds);
+
+            if fill > 0 {
+                filled += 1;
+                let signed_fill = if order.is_buy { fill } else { -fill };
+                Positions::record(
+                    &env,
+                    &order.trader,
+                    &config.market,
+                    signed_fill,
+                    clearing_price,
+                    PRICE_SCALE,
+                );
+                EventEmitter::batch_trade_executed(
+                    &env,
+                    TradeExecutedEvent {
+                        trade_id: order_id as u64,
+                        trader: order.trader,
+                        pair: config.market.clone(),
+                        amount: fill,
+                        price: clearing_price,
+                        is_buy: order.is_buy,
+                        fee_amount: 0,
+                        fee_token: config.quote_token.clone(),
+                        timestamp,
+                    },
+                    batch_id,
+                );
+            }
+        }
+
+        let settlement = Settlement {
+            clearing_price,
+            volume,
+            settled_at: timestamp,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Settlement(batch_id), &settlement);
+        EventEmitter::batch_settled(
+            &env,
+            BatchSettledEvent {
+                batch_id,
+                settled_by: caller,
+                clearing_price,
+                volume,
+                orders: orders.len(),
+                filled,
+                timestamp,
+            },
+        );
+        Ok(clearing_price)
+    }
+
+    /// Batch currently accepting orders
+    pub fn current_batch(env: Env) -> Result<u64, AuctionError> {
+        Ok(current_batch(&env, &load_config(&env)?))
+    }
+
+    /// One page of a batch's revealed orders, and the cursor of the next
+    pub fn orders(env: Env, batch_id: u64, page: Pagination) -> (Vec<BatchOrder>, Option<u32>) {
+        orders(batch_id).page(&env, &page)
+    }
+
+    /// Position and realized PnL `user` built up through batch fills
+    pub fn position_of(env: Env, user: Address) -> Result<Position, AuctionError> {
+        Ok(Positions::position_of(
+            &env,
+            &user,
+            &load_config(&env)?.market,
+        ))
+    }
+
+    /// One page of a batch's commitments, indexed by commit id, and the
+    /// cursor of the next
+    pub fn commitments(
+        env: Env,
+        batch_id: u64,
+        page: Pagination,
+    ) -> (Vec<Commitment>, Option<u32>) {
+        commit::commits(batch_id).page(&env, &page)
+    }
+
+    /// Commitment hash of an order, for clients preparing a commitment
+    pub fn order_hash(
+        env: Env,
+        trader: Address,
+        is_buy: bool,
+        amount: i128,
+        limit_price: i128,
+        salt: BytesN<32>,
+    ) -> BytesN<32> {
+        commit::order_hash(&env, &trader, is_buy, amount, limit_price, &salt)
+    }
+
+    pub fn commit_config(env: Env) -> Option<CommitConfig> {
+        commit::config(&env)
+    }
+
+    pub fn settlement(env: Env, batch_id: u64) -> Option<Settlement> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Settlement(batch_id))
+    }
+
+    pub fn config(env: Env) -> Result<Config, AuctionError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AuctionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), AuctionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Escrow `order`'s worst case and append it to `batch_id`. Returns its
+/// order id.
+fn add_order(
+    env: &Env,
+    config: &Config,
+    batch_id: u64,
+    order: BatchOrder,
+) -> Result<u32, AuctionError> {
+    if order.amount <= 0 || order.limit_price <= 0 {
+        return Err(AuctionError::InvalidOrder);
+    }
+    let this = env.current_contract_address();
+    if order.is_buy {
+        let cost = quote_cost(order.amount, order.limit_price).ok_or(AuctionError::InvalidOrder)?;
+        token::Client::new(env, &config.quote_token).transfer(&order.trader, &this, &cost);
+    } else {
+        token::Client::new(env, &config.base_token).transfer(&order.trader, &this, &order.amount);
+    }
+
+    Ok(orders(batch_id).push(env, &order))
+}
+
+/// Uniform price matching the most volume among `orders`' limit prices,
+/// and that volume
+fn clearing_price(orders: &Vec<BatchOrder>) -> (i128, i128) {
+    // (volume, imbalance, lowest price, highest price) of the best candidate
+    let mut best: Option<(i128, i128, i128, i128)> = None;
+    for candidate in orders.iter().filter(|order| order.amount > 0) {
+        let price = candidate.limit_price;
+        let demand = eligible_volume(orders, true, price);
+        let supply = eligible_volume(orders, false, price);
+        let volume = demand.min(supply);
+        if volume == 0 {
+            continue;
+        }
+        let imbalance = (demand - supply).abs();
+        best = match best {
+            Some((v, i, low, high)) if v == volume && i == imbalance => {
+                Some((v, i, low.min(price), high.max(price)))
+            }
+            Some((v, i, _, _)) if v > volume || (v == volume && i < imbalance) => best,
+            _ => Some((volume, imbalance, price, price)),
+        };
+    }
+    match best {
+        Some((volume, _, low, high)) => (low + (high - low) / 2, volume),
+        None => (0, 0),
+    }
+}
+
+fn crosses(order: &BatchOrder, price: i128) -> bool {
+    if order.is_buy {
+        order.limit_price >= price
+    } else {
+        order.limit_price <= price
+    }
+}
+
+/// Base amount one side would trade at `price`
+fn eligible_volume(orders: &Vec<BatchOrder>, is_buy: bool, price: i128) -> i128 {
+    orders
+        .iter()
+        .filter(|order| order.is_buy == is_buy && order.amount > 0 && crosses(order, price))
+        .fold(0, |total, order| total + order.amount)
+}
+
+fn eligible_count(orders: &Vec<BatchOrder>, is_buy: bool, price: i128, volume: i128) -> u32 {
+    if volume == 0 {
+        return 0;
+    }
+    orders
+        .iter()
+        .filter(|order| order.is_buy == is_buy && order.amount > 0 && crosses(order, price))
+        .count() as u32
+}
+
+/// Quote owed for `amount` base at `price`, rounded up
+fn quote_cost(amount: i128, price: i128) -> Option<i128> {
+    let product = amount.checked_mul(price)?;
+    Some((product + PRICE_SCALE - 1) / PRICE_SCALE)
+}
+
+/// Pay `order` its fill of `fill` base for `proceeds` quote and return the
+/// unused part of its escrow
+fn refund(env: &Env, config: &Config, order: &BatchOrder, fill: i128, proceeds: i128) {
+    let this = env.current_contract_address();
+    let base = token::Client::new(env, &config.base_token);
+    let quote = token::Client::new(env, &config.quote_token);
+    let (base_out, quote_out) = if order.is_buy {
+        let escrow = quote_cost(order.amount, order.limit_price).unwrap_or(0);
+        (fill, escrow - proceeds)
+    } else {
+        (order.amount - fill, proceeds)
+    };
+    if base_out > 0 {
+        base.transfer(&this, &order.trader, &base_out);
+    }
+    if quote_out > 0 {
+        quote.transfer(&this, &order.trader, &quote_out);
+    }
+}
+
+fn current_batch(env: &Env, config: &Config) -> u64 {
+    (env.ledger().timestamp() - config.genesis) / config.batch_duration + 1
+}
+
+/// Orders of a batch; an order's id is its index
+fn orders(batch_id: u64) -> IndexedList<u64> {
+    IndexedList::new(symbol_short!("orders"), batch_id)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, AuctionError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(AuctionError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, AuctionError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(AuctionError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,233 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::symbol_short;
+use soroban_sdk::testutils::BytesN as _;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload, standard_events_from};
+
+const DURATION: u64 = 60;
+const P: i128 = PRICE_SCALE;
+
+struct Setup {
+    env: Env,
+    client: BatchAuctionClient<'static>,
+    admin: Address,
+    base: TestToken<'static>,
+    quote: TestToken<'static>,
+    traders: [Address; 4],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let base = stellar_asset(&env);
+    let quote = stellar_asset(&env);
+    let client = BatchAuctionClient::new(&env, &env.register_contract(None, BatchAuction));
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &symbol_short!("XLMUSDC"),
+        &base.address,
+        &quote.address,
+        &DURATION,
+    );
+    let traders = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for trader in traders.iter() {
+        base.mint(trader, 10_000);
+        quote.mint(trader, 100_000);
+    }
+
+    Setup {
+        env,
+        client,
+        admin,
+        base,
+        quote,
+        traders,
+    }
+}
+
+fn close_batch(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += DURATION);
+}
+
+#[test]
+fn test_batch_clears_at_single_price() {
+    let s = setup();
+    let [b1, b2, s1, s2] = &s.traders;
+    // Demand: 100 up to 3.0, 100 more up to 2.0
+    // Supply: 150 from 1.0, 100 more from 2.5
+    s.client.submit_order(b1, &true, &100, &(3 * P));
+    s.client.submit_order(b2, &true, &100, &(2 * P));
+    s.client.submit_order(s1, &false, &150, &P);
+    let (batch, _) = s.client.submit_order(s2, &false, &100, &(5 * P / 2));
+    assert_eq!(s.quote.balance(b1), 100_000 - 300);
+
+    let result = s.client.try_settle(b1, &batch);
+    assert_eq!(result, Err(Ok(AuctionError::BatchOpen)));
+    close_batch(&s.env);
+
+    // Anywhere from 1.0 to 2.0 matches 150 of the 200 demanded, so the
+    // batch clears at the midpoint and buyers fill pro rata. Each pays
+    // 112.5 rounded up; the seller gets 225.
+    let price = s.client.settle(b1, &batch);
+    assert_eq!(price, 3 * P / 2);
+    assert_eq!(s.base.balance(b1), 10_000 + 75);
+    assert_eq!(s.base.balance(b2), 10_000 + 75);
+    assert_eq!(s.quote.balance(b1), 100_000 - 113);
+    assert_eq!(s.quote.balance(b2), 100_000 - 113);
+    assert_eq!(s.quote.balance(s1), 100_000 + 225);
+    assert_eq!(s.quote.balance(&s.client.address), 1);
+    // The 2.5 sell did not cross and is returned
+    assert_eq!(s.base.balance(s2), 10_000);
+    assert_eq!(s.quote.balance(s2), 100_000);
+
+    let settled = assert_standard_event(&s.env, topics::BATCH_SETTLED, |_| true);
+    let event: BatchSettledEvent = payload(&settled);
+    assert_eq!((event.volume, event.orders, event.filled), (150, 4, 3));
+    assert_eq!(
+        settled.metadata::<i128>(&s.env, metadata_keys::CLEARING_PRICE_KEY),
+        Some(3 * P / 2)
+    );
+    let events = standard_events_from(&s.env, &s.client.address);
+    let fills: std::vec::Vec<_> = events
+        .iter()
+        .filter(|e| e.event.topic == topics::TRADE_EXECUTED)
+        .collect();
+    assert_eq!(fills.len(), 3);
+    for fill in fills {
+        assert_eq!(fill.event.correlation_id, batch);
+        assert_eq!(payload::<TradeExecutedEvent>(fill).price, 3 * P / 2);
+    }
+
+    let position = s.client.position_of(s1);
+    assert_eq!((position.net, position.avg_entry_price), (-150, 3 * P / 2));
+
+    let result = s.client.try_settle(b1, &batch);
+    assert_eq!(result, Err(Ok(AuctionError::AlreadySettled)));
+}
+
+#[test]
+fn test_order_position_in_batch_does_not_matter() {
+    // The same orders submitted in opposite orders clear identically
+    let mut outcomes = std::vec::Vec::new();
+    for reversed in [false, true] {
+        let s = setup();
+        let [b1, _, s1, s2] = &s.traders;
+        let mut orders = [
+            (b1, true, 90, 2 * P),
+            (s1, false, 50, P),
+            (s2, false, 50, P),
+        ];
+        if reversed {
+            orders.reverse();
+        }
+        for (trader, is_buy, amount, price) in orders {
+            s.client.submit_order(trader, &is_buy, &amount, &price);
+        }
+        close_batch(&s.env);
+        let price = s.client.settle(b1, &1);
+        outcomes.push((
+            price,
+            s.base.balance(s1),
+            s.base.balance(s2),
+            s.quote.balance(b1),
+        ));
+    }
+    assert_eq!(outcomes[0], outcomes[1]);
+    // Tied prices between 1.0 and 2.0 settle at the midpoint
+    assert_eq!(outcomes[0].0, 3 * P / 2);
+}
+
+#[test]
+fn test_cancel_and_uncrossed_batch_refund() {
+    let s = setup();
+    let [b1, _, s1, _] = &s.traders;
+    let (batch, buy) = s.client.submit_order(b1, &true, &100, &P);
+    let (_, sell) = s.client.submit_order(s1, &false, &100, &(2 * P));
+    let result = s.client.try_cancel_order(s1, &batch, &buy);
+    assert_eq!(result, Err(Ok(AuctionError::Unauthorized)));
+    s.client.cancel_order(s1, &batch, &sell);
+    assert_eq!(s.base.balance(s1), 10_000);
+    let result = s.client.try_cancel_order(s1, &batch, &sell);
+    assert_eq!(result, Err(Ok(AuctionError::OrderNotFound)));
+
+    // Orders placed now belong to the next batch
+    close_batch(&s.env);
+    let result = s.client.try_cancel_order(b1, &batch, &buy);
+    assert_eq!(result, Err(Ok(AuctionError::BatchClosed)));
+    assert_eq!(s.client.current_batch(), batch + 1);
+    assert_eq!(s.client.settle(s1, &batch), 0);
+    assert_eq!(s.quote.balance(b1), 100_000);
+    assert_eq!(s.client.settlement(&batch).unwrap().volume, 0);
+}
+
+#[test]
+fn test_commit_reveal_hides_orders_until_revealed() {
+    let s = setup();
+    let [b1, _, s1, s2] = &s.traders;
+    let treasury = Address::generate(&s.env);
+    let config = CommitConfig {
+        bond: 500,
+        reveal_ledgers: 10,
+        treasury: treasury.clone(),
+    };
+    let result = s.client.try_set_commit_reveal(b1, &Some(config.clone()));
+    assert_eq!(result, Err(Ok(AuctionError::Unauthorized)));
+    s.client.set_commit_reveal(&s.admin, &Some(config));
+    let result = s.client.try_submit_order(b1, &true, &100, &P);
+    assert_eq!(result, Err(Ok(AuctionError::CommitRequired)));
+
+    let salt = BytesN::random(&s.env);
+    let buy_hash = s.client.order_hash(b1, &true, &100, &(2 * P), &salt);
+    let sell_hash = s.client.order_hash(s1, &false, &100, &P, &salt);
+    let (batch, buy) = s.client.commit_order(b1, &buy_hash);
+    let (_, sell) = s.client.commit_order(s1, &sell_hash);
+    let (_, ghost) = s.client.commit_order(s2, &BytesN::random(&s.env));
+    assert_eq!(s.quote.balance(b1), 100_000 - 500);
+    assert!(s.client.orders(&batch, &Pagination::first(10)).0.is_empty());
+    let committed = assert_standard_event(&s.env, topics::ORDER_COMMITTED, |_| true);
+    assert_eq!(payload::<OrderCommitEvent>(&committed).commit_id, ghost);
+
+    // Reveals may land after the window, but must match the commitment
+    close_batch(&s.env);
+    let result = s
+        .client
+        .try_reveal_order(b1, &batch, &buy, &true, &100, &(3 * P), &salt);
+    assert_eq!(result, Err(Ok(AuctionError::InvalidReveal)));
+    s.client
+        .reveal_order(b1, &batch, &buy, &true, &100, &(2 * P), &salt);
+    assert_eq!(s.quote.balance(b1), 100_000 - 200);
+    let revealed = assert_standard_event(&s.env, topics::ORDER_REVEALED, |_| true);
+    assert_eq!(payload::<OrderRevealEvent>(&revealed).limit_price, 2 * P);
+    s.client
+        .reveal_order(s1, &batch, &sell, &false, &100, &P, &salt);
+
+    // The unrevealed commitment holds settlement up until its deadline
+    let result = s.client.try_settle(b1, &batch);
+    assert_eq!(result, Err(Ok(AuctionError::RevealPending)));
+    s.env.ledger().with_mut(|li| li.sequence_number += 11);
+    let result = s
+        .client
+        .try_reveal_order(s2, &batch, &ghost, &false, &1, &P, &salt);
+    assert_eq!(result, Err(Ok(AuctionError::RevealExpired)));
+    assert_eq!(s.client.settle(b1, &batch), 3 * P / 2);
+    assert_eq!(s.quote.balance(&treasury), 500);
+    assert_eq!(s.quote.balance(s2), 100_000 - 500);
+    assert_eq!(s.base.balance(b1), 10_000 + 100);
+    let (commits, _) = s.client.commitments(&batch, &Pagination::first(10));
+    assert_eq!(commits.get(ghost).unwrap().state, CommitState::Slashed);
+    assert_standard_event(&s.env, topics::COMMIT_SLASHED, |_| true);
+}
@@ -0,0 +1,274 @@
+//! Incentive market for gauge votes
+//!
+//! Anyone can attach tokens to a gauge for a voting epoch that has not yet
+//! ended. Once the gauge controller finalizes that epoch, each voter who
+//! backed the gauge claims a share of every incentive token proportional to
+//! the voting power they gave it. Incentives on a gauge nobody voted for can
+//! be refunded to their depositors.
+
+#![no_std]
+
+use shared::epoch::EpochClock;
+use shared::events::{BribeEvent, EventEmitter};
+use shared::voting::GaugeControllerClient;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env, Vec};
+
+/// Distinct incentive tokens per gauge and epoch
+pub const MAX_TOKENS: u32 = 10;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BribeError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    EpochClosed = 4,
+    EpochNotFinalized = 5,
+    NothingToClaim = 6,
+    AlreadyClaimed = 7,
+    TooManyTokens = 8,
+    VotesCast = 9,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    GaugeController,
+    /// Total deposited for (gauge, epoch, token)
+    Incentive(u32, u64, Address),
+    /// Amount already paid out of (gauge, epoch, token)
+    Paid(u32, u64, Address),
+    Tokens(u32, u64),
+    Deposit(Address, u32, u64, Address),
+    Claimed(Address, u32, u64, Address),
+}
+
+#[contract]
+pub struct BribeMarket;
+
+#[contractimpl]
+impl BribeMarket {
+    /// `gauge_controller` supplies the vote tallies incentives are split by
+    pub fn initialize(env: Env, gauge_controller: Address) -> Result<(), BribeError> {
+        if env.storage().instance().has(&DataKey::GaugeController) {
+            return Err(BribeError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::GaugeController, &gauge_controller);
+        Ok(())
+    }
+
+    /// Attach `amount` of `token` to votes for `gauge_id` during `epoch`,
+    /// which must be the current epoch or a later one
+    pub fn deposit(
+        env: Env,
+        depositor: Address,
+        gauge_id: u32,
+        epoch: u64,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), BribeError> {
+        depositor.require_auth();
+        gauge_controller(&env)?;
+        if amount <= 0 {
+            return Err(BribeError::InvalidAmount);
+        }
+        if epoch < EpochClock::WEEKLY.current_epoch(&env) {
+            return Err(BribeError::EpochClosed);
+        }
+
+        let mut tokens = Self::incentive_tokens(env.clone(), gauge_id, epoch);
+        if !tokens.contains(&token) {
+            if tokens.len() >= MAX_TOKENS {
+                return Err(BribeError::TooManyTokens);
+            }
+            tokens.push_back(token.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Tokens(gauge_id, epoch), &tokens);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &depositor,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let total = add(
+            &env,
+            &DataKey::Incentive(gauge_id, epoch, token.clone()),
+            amount,
+        );
+        add(
+            &env,
+            &DataKey::Deposit(depositor.clone(), gauge_id, epoch, token.clone()),
+            amount,
+        );
+        let remaining = total - paid(&env, gauge_id, epoch, &token);
+
+        EventEmitter::bribe_deposited(
+            &env,
+            BribeEvent {
+                account: depositor,
+                gauge_id,
+                epoch,
+                token,
+                amount,
+                remaining,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Pay `user` their share of the `token` incentive on `gauge_id` for a
+    /// finalized `epoch`. Returns the amount paid.
+    pub fn claim(
+        env: Env,
+        user: Address,
+        gauge_id: u32,
+        epoch: u64,
+        token: Address,
+    ) -> Result<i128, BribeError> {
+        user.require_auth();
+        let claimed_key = DataKey::Claimed(user.clone(), gauge_id, epoch, token.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(BribeError::AlreadyClaimed);
+        }
+        let amount = Self::claimable(env.clone(), user.clone(), gauge_id, epoch, token.clone())?;
+        if amount <= 0 {
+            return Err(BribeError::NothingToClaim);
+        }
+
+        env.storage().persistent().set(&claimed_key, &true);
+        add(&env, &DataKey::Paid(gauge_id, epoch, token.clone()), amount);
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &user, &amount);
+
+        let remaining = Self::incentive(env.clone(), gauge_id, epoch, token.clone())
+            - paid(&env, gauge_id, epoch, &token);
+        EventEmitter::bribe_claimed(
+            &env,
+            BribeEvent {
+                account: user,
+                gauge_id,
+                epoch,
+                token,
+                amount,
+                remaining,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(amount)
+    }
+
+    /// Return a deposit on a finalized epoch in which the gauge received no
+    /// votes. Returns the amount refunded.
+    pub fn refund(
+        env: Env,
+        depositor: Address,
+        gauge_id: u32,
+        epoch: u64,
+        token: Address,
+    ) -> Result<i128, BribeError> {
+        depositor.require_auth();
+        let gauges = finalized(&env, epoch)?;
+        if gauges.gauge_votes(&epoch, &gauge_id) > 0 {
+            return Err(BribeError::VotesCast);
+        }
+        let key = DataKey::Deposit(depositor.clone(), gauge_id, epoch, token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(BribeError::NothingToClaim);
+        }
+
+        env.storage().persistent().remove(&key);
+        add(&env, &DataKey::Paid(gauge_id, epoch, token.clone()), amount);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &depositor,
+            &amount,
+        );
+        Ok(amount)
+    }
+
+    // ---- views ----
+
+    /// What `user` would receive from `claim`; 0 once claimed
+    pub fn claimable(
+        env: Env,
+        user: Address,
+        gauge_id: u32,
+        epoch: u64,
+        token: Address,
+    ) -> Result<i128, BribeError> {
+        let gauges = finalized(&env, epoch)?;
+        if env.storage().persistent().has(&DataKey::Claimed(
+            user.clone(),
+            gauge_id,
+            epoch,
+            token.clone(),
+        )) {
+            return Ok(0);
+        }
+        let power = gauges
+            .user_votes(&user, &epoch)
+            .iter()
+            .find(|vote| vote.gauge_id == gauge_id)
+            .map(|vote| vote.power)
+            .unwrap_or(0);
+        let total = gauges.gauge_votes(&epoch, &gauge_id);
+        if power <= 0 || total <= 0 {
+            return Ok(0);
+        }
+        let incentive = Self::incentive(env, gauge_id, epoch, token);
+        Ok(incentive * power / total)
+    }
+
+    /// Total `token` deposited on `gauge_id` for `epoch`
+    pub fn incentive(env: Env, gauge_id: u32, epoch: u64, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Incentive(gauge_id, epoch, token))
+            .unwrap_or(0)
+    }
+
+    pub fn incentive_tokens(env: Env, gauge_id: u32, epoch: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tokens(gauge_id, epoch))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+fn gauge_controller(env: &Env) -> Result<Address, BribeError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::GaugeController)
+        .ok_or(BribeError::NotInitialized)
+}
+
+/// Gauge controller client, once votes for `epoch` are final
+fn finalized(env: &Env, epoch: u64) -> Result<GaugeControllerClient<'_>, BribeError> {
+    let gauges = GaugeControllerClient::new(env, &gauge_controller(env)?);
+    if gauges.finalized_epoch() <= epoch {
+        return Err(BribeError::EpochNotFinalized);
+    }
+    Ok(gauges)
+}
+
+fn paid(env: &Env, gauge_id: u32, epoch: u64, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Paid(gauge_id, epoch, token.clone()))
+        .unwrap_or(0)
+}
+
+fn add(env: &Env, key: &DataKey, delta: i128) -> i128 {
+    let value: i128 = env.storage().persistent().get(key).unwrap_or(0);
+    env.storage().persistent().set(key, &(value + delta));
+    value + delta
+}
+
+#[cfg(test)]
+mod test;
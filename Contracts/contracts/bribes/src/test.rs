@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use super::*;
+use gauges::{GaugeController, GaugeControllerClient as ControllerClient};
+use shared::events::topics;
+use shared::voting::WEEK;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+use voting_escrow::{VotingEscrow, VotingEscrowClient};
+
+const START: u64 = 100 * WEEK;
+const LOCK: i128 = 1_000_000;
+const BRIBE: i128 = 10_000;
+
+struct Setup {
+    env: Env,
+    client: BribeMarketClient<'static>,
+    gauges: ControllerClient<'static>,
+    reward: TokenClient<'static>,
+    epoch: u64,
+    depositor: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let token_admin = Address::generate(&env);
+    let locked_id = env.register_stellar_asset_contract(token_admin.clone());
+    let ve_id = env.register_contract(None, VotingEscrow);
+    let ve = VotingEscrowClient::new(&env, &ve_id);
+    ve.initialize(&locked_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &locked_id).mint(&alice, &LOCK);
+    StellarAssetClient::new(&env, &locked_id).mint(&bob, &(3 * LOCK));
+    ve.create_lock(&alice, &LOCK, &(START + 52 * WEEK));
+    ve.create_lock(&bob, &(3 * LOCK), &(START + 52 * WEEK));
+
+    let gauges_id = env.register_contract(None, GaugeController);
+    let gauges = ControllerClient::new(&env, &gauges_id);
+    let admin = Address::generate(&env);
+    gauges.initialize(&admin, &ve_id);
+    let pools = Address::generate(&env);
+    gauges.add_gauge(&admin, &pools, &0);
+    gauges.add_gauge(&admin, &pools, &1);
+
+    let reward_id = env.register_stellar_asset_contract(token_admin);
+    let reward = TokenClient::new(&env, &reward_id);
+    let depositor = Address::generate(&env);
+    StellarAssetClient::new(&env, &reward_id).mint(&depositor, &(10 * BRIBE));
+
+    let contract_id = env.register_contract(None, BribeMarket);
+    let client = BribeMarketClient::new(&env, &contract_id);
+    client.initialize(&gauges_id);
+    let epoch = gauges.current_epoch();
+
+    Setup {
+        env,
+        client,
+        gauges,
+        reward,
+        epoch,
+        depositor,
+        alice,
+        bob,
+    }
+}
+
+fn finalize(s: &Setup) {
+    s.env.ledger().with_mut(|li| li.timestamp += WEEK);
+    s.gauges.finalize_epoch();
+}
+
+#[test]
+fn test_voters_claim_pro_rata() {
+    let s = setup();
+    s.client
+        .deposit(&s.depositor, &0, &s.epoch, &s.reward.address, &BRIBE);
+    let captured = assert_standard_event(&s.env, topics::BRIBE_DEPOSITED, |_| true);
+    let deposit: BribeEvent = payload(&captured);
+    assert_eq!(deposit.remaining, BRIBE);
+
+    s.gauges.vote(&s.alice, &0, &10_000);
+    s.gauges.vote(&s.bob, &0, &10_000);
+    assert_eq!(
+        s.client
+            .try_claim(&s.alice, &0, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::EpochNotFinalized))
+    );
+
+    finalize(&s);
+    let alice = s.client.claim(&s.alice, &0, &s.epoch, &s.reward.address);
+    let bob = s.client.claim(&s.bob, &0, &s.epoch, &s.reward.address);
+    // Bob locked three times as much for as long
+    assert!((bob - 3 * alice).abs() <= 3);
+    assert!(alice + bob <= BRIBE && alice + bob >= BRIBE - 1);
+    assert_eq!(s.reward.balance(&s.alice), alice);
+
+    let captured = assert_standard_event(&s.env, topics::BRIBE_CLAIMED, |_| true);
+    let claim: BribeEvent = payload(&captured);
+    assert_eq!(claim.account, s.bob);
+    assert_eq!(claim.remaining, BRIBE - alice - bob);
+}
+
+#[test]
+fn test_claims_are_per_gauge_and_once() {
+    let s = setup();
+    s.client
+        .deposit(&s.depositor, &0, &s.epoch, &s.reward.address, &BRIBE);
+    s.gauges.vote(&s.alice, &0, &5_000);
+    s.gauges.vote(&s.alice, &1, &5_000);
+    s.gauges.vote(&s.bob, &1, &10_000);
+    finalize(&s);
+
+    // Only Alice backed gauge 0, so she takes all of it
+    assert_eq!(
+        s.client
+            .claimable(&s.alice, &0, &s.epoch, &s.reward.address),
+        BRIBE
+    );
+    assert_eq!(
+        s.client.try_claim(&s.bob, &0, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::NothingToClaim))
+    );
+    s.client.claim(&s.alice, &0, &s.epoch, &s.reward.address);
+    assert_eq!(
+        s.client
+            .try_claim(&s.alice, &0, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::AlreadyClaimed))
+    );
+    assert_eq!(
+        s.client
+            .claimable(&s.alice, &0, &s.epoch, &s.reward.address),
+        0
+    );
+}
+
+#[test]
+fn test_deposits_only_for_open_epochs() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_deposit(&s.depositor, &0, &(s.epoch - 1), &s.reward.address, &BRIBE),
+        Err(Ok(BribeError::EpochClosed))
+    );
+    assert_eq!(
+        s.client
+            .try_deposit(&s.depositor, &0, &s.epoch, &s.reward.address, &0),
+        Err(Ok(BribeError::InvalidAmount))
+    );
+    s.client
+        .deposit(&s.depositor, &1, &(s.epoch + 2), &s.reward.address, &BRIBE);
+    s.client
+        .deposit(&s.depositor, &1, &(s.epoch + 2), &s.reward.address, &BRIBE);
+    assert_eq!(
+        s.client.incentive(&1, &(s.epoch + 2), &s.reward.address),
+        2 * BRIBE
+    );
+    assert_eq!(s.client.incentive_tokens(&1, &(s.epoch + 2)).len(), 1);
+}
+
+#[test]
+fn test_refund_when_gauge_gets_no_votes() {
+    let s = setup();
+    s.client
+        .deposit(&s.depositor, &1, &s.epoch, &s.reward.address, &BRIBE);
+    s.client
+        .deposit(&s.depositor, &0, &s.epoch, &s.reward.address, &BRIBE);
+    s.gauges.vote(&s.alice, &0, &10_000);
+    assert_eq!(
+        s.client
+            .try_refund(&s.depositor, &1, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::EpochNotFinalized))
+    );
+
+    finalize(&s);
+    assert_eq!(
+        s.client
+            .try_refund(&s.depositor, &0, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::VotesCast))
+    );
+    assert_eq!(
+        s.client
+            .refund(&s.depositor, &1, &s.epoch, &s.reward.address),
+        BRIBE
+    );
+    assert_eq!(s.reward.balance(&s.depositor), 9 * BRIBE);
+    assert_eq!(
+        s.client
+            .try_refund(&s.depositor, &1, &s.epoch, &s.reward.address),
+        Err(Ok(BribeError::NothingToClaim))
+    );
+}
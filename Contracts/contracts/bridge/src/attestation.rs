@@ -0,0 +1,65 @@
+//! Validator attestations
+//!
+//! Validators sign `release_digest` off-chain: the typed-data digest of a
+//! `Release` of this chain's id, the recipient, amount and inbound nonce.
+//! The digest's domain binds the bridge address and network, and the chain
+//! id the bridge's own chain numbering, so a signature cannot be replayed
+//! against another deployment or chain; the nonce is consumed on release
+//! so it cannot be replayed here either.
+
+use shared::typed_data::TypedData;
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Vec};
+
+use crate::BridgeError;
+
+/// One validator's signature over a release digest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// Digest validators sign to release `amount` to `recipient`
+pub fn release_digest(
+    env: &Env,
+    chain_id: u32,
+    recipient: &Address,
+    amount: i128,
+    nonce: u64,
+) -> BytesN<32> {
+    TypedData::new(env, &symbol_short!("bridge"), &symbol_short!("Release"))
+        .field(symbol_short!("chain_id"), chain_id)
+        .field(symbol_short!("recipient"), recipient.clone())
+        .field(symbol_short!("amount"), amount)
+        .field(symbol_short!("nonce"), nonce)
+        .digest()
+}
+
+/// Check that at least `threshold` distinct validators signed `digest`.
+/// Returns the number of signatures accepted. An invalid signature traps.
+pub fn verify(
+    env: &Env,
+    validators: &Vec<BytesN<32>>,
+    threshold: u32,
+    digest: &BytesN<32>,
+    attestations: &Vec<Attestation>,
+) -> Result<u32, BridgeError> {
+    let message = Bytes::from_array(env, &digest.to_array());
+    let mut seen: Vec<BytesN<32>> = Vec::new(env);
+    for attestation in attestations.iter() {
+        if !validators.contains(&attestation.public_key) {
+            return Err(BridgeError::UnknownValidator);
+        }
+        if seen.contains(&attestation.public_key) {
+            return Err(BridgeError::DuplicateSignature);
+        }
+        env.crypto()
+            .ed25519_verify(&attestation.public_key, &message, &attestation.signature);
+        seen.push_back(attestation.public_key);
+    }
+    if seen.len() < threshold {
+        return Err(BridgeError::InsufficientSignatures);
+    }
+    Ok(seen.len())
+}
@@ -0,0 +1,323 @@
+//! Token bridge escrow
+//!
+//! Outbound: `lock` escrows tokens and emits a canonical `BRIDGE_LOCK`
+//! event that relayers watch to mint on the destination chain.
+//! Inbound: `release` pays out escrowed tokens once a threshold of
+//! validators has signed the transfer (see `attestation`). Each inbound
+//! nonce releases at most once, and the admin can pause both directions.
+
+#![no_std]
+
+mod attestation;
+
+pub use attestation::Attestation;
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{
+    BridgeLockEvent, BridgeReleaseEvent, ContractPausedEvent, ContractUnpausedEvent, EventEmitter,
+};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Bytes, BytesN, Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BridgeError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    Paused = 4,
+    InvalidAmount = 5,
+    InvalidThreshold = 6,
+    UnknownValidator = 7,
+    DuplicateSignature = 8,
+    InsufficientSignatures = 9,
+    AlreadyProcessed = 10,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    ChainId,
+    Validators,
+    Threshold,
+    Paused,
+    OutboundNonce,
+    Processed(u64),
+}
+
+#[contract]
+pub struct BridgeContract;
+
+#[contractimpl]
+impl BridgeContract {
+    /// `chain_id` identifies this chain in validator signatures
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        chain_id: u32,
+        validators: Vec<BytesN<32>>,
+        threshold: u32,
+    ) -> Result<(), BridgeError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BridgeError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        store_validators(&env, &validators, threshold)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::ChainId, &chain_id);
+        Ok(())
+    }
+
+    /// Escrow `amount` for delivery to `recipient` on `dest_chain`.
+    /// Returns the outbound nonce.
+    pub fn lock(
+        env: Env,
+        sender: Address,
+        amount: i128,
+        dest_chain: u32,
+        recipient: Bytes,
+    ) -> Result<u64, BridgeError> {
+        sender.require_auth();
+        require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(BridgeError::InvalidAmount);
+        }
+        let token = bridged_token(&env)?;
+        token::Client::new(&env, &token).transfer(
+            &sender,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let nonce: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OutboundNonce)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::OutboundNonce, &(nonce + 1));
+
+        EventEmitter::bridge_lock(
+            &env,
+            BridgeLockEvent {
+                sender,
+                token,
+                amount,
+                dest_chain,
+                recipient,
+                nonce,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(nonce)
+    }
+
+    /// Release escrowed tokens attested by at least `threshold` validators
+    pub fn release(
+        env: Env,
+        recipient: Address,
+        amount: i128,
+        nonce: u64,
+        attestations: Vec<Attestation>,
+    ) -> Result<(), BridgeError> {
+        require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(BridgeError::InvalidAmount);
+        }
+        if Self::is_processed(env.clone(), nonce) {
+            return Err(BridgeError::AlreadyProcessed);
+        }
+
+        let digest = Self::release_digest(env.clone(), recipient.clone(), amount, nonce)?;
+        let signers = attestation::verify(
+            &env,
+            &Self::validators(env.clone()),
+            Self::threshold(env.clone()),
+            &digest,
+            &attestations,
+        )?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Processed(nonce), &true);
+
+        let token = bridged_token(&env)?;
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        EventEmitter::bridge_release(
+            &env,
+            BridgeReleaseEvent {
+                recipient,
+                token,
+                amount,
+                nonce,
+                signers,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Replace the validator set
+    pub fn set_validators(
+        env: Env,
+        admin: Address,
+        validators: Vec<BytesN<32>>,
+        threshold: u32,
+    ) -> Result<(), BridgeError> {
+        require_admin(&env, &admin)?;
+        store_validators(&env, &validators, threshold)
+    }
+
+    pub fn pause(env: Env, admin: Address) -> Result<(), BridgeError> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Paused, &true);
+        EventEmitter::contract_paused(
+            &env,
+            ContractPausedEvent {
+                paused_by: admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn unpause(env: Env, admin: Address) -> Result<(), BridgeError> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Paused, &false);
+        EventEmitter::contract_unpaused(
+            &env,
+            ContractUnpausedEvent {
+                unpaused_by: admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    // ---- views ----
+
+    /// Digest validators sign to release `amount` to `recipient`
+    pub fn release_digest(
+        env: Env,
+        recipient: Address,
+        amount: i128,
+        nonce: u64,
+    ) -> Result<BytesN<32>, BridgeError> {
+        let chain_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ChainId)
+            .ok_or(BridgeError::NotInitialized)?;
+        Ok(attestation::release_digest(
+            &env, chain_id, &recipient, amount, nonce,
+        ))
+    }
+
+    pub fn validators(env: Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Validators)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(0)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    pub fn is_processed(env: Env, nonce: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Processed(nonce))
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), BridgeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), BridgeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), BridgeError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(BridgeError::NotInitialized)?;
+    if stored != *admin {
+        return Err(BridgeError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_not_paused(env: &Env) -> Result<(), BridgeError> {
+    if BridgeContract::is_paused(env.clone()) {
+        return Err(BridgeError::Paused);
+    }
+    Ok(())
+}
+
+fn bridged_token(env: &Env) -> Result<Address, BridgeError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(BridgeError::NotInitialized)
+}
+
+fn store_validators(
+    env: &Env,
+    validators: &Vec<BytesN<32>>,
+    threshold: u32,
+) -> Result<(), BridgeError> {
+    if threshold == 0 || threshold > validators.len() {
+        return Err(BridgeError::InvalidThreshold);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::Validators, validators);
+    env.storage()
+        .instance()
+        .set(&DataKey::Threshold, &threshold);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
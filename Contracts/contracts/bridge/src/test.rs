@@ -0,0 +1,170 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use shared::events::{metadata_keys::NONCE_KEY, topics};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Bytes, BytesN, Env};
+use test_utils::{assert_standard_event, payload};
+
+const CHAIN_ID: u32 = 7;
+
+struct Setup {
+    env: Env,
+    client: BridgeContractClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+    user: Address,
+    keys: std::vec::Vec<SigningKey>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+
+    let keys: std::vec::Vec<SigningKey> = (1..=3u8)
+        .map(|seed| SigningKey::from_bytes(&[seed; 32]))
+        .collect();
+    let mut validators = Vec::new(&env);
+    for key in &keys {
+        validators.push_back(BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    }
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, BridgeContract);
+    let client = BridgeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_id, &CHAIN_ID, &validators, &2);
+
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&user, &10_000);
+
+    Setup {
+        env,
+        client,
+        token,
+        admin,
+        user,
+        keys,
+    }
+}
+
+fn attest(
+    s: &Setup,
+    signers: &[usize],
+    recipient: &Address,
+    amount: i128,
+    nonce: u64,
+) -> Vec<Attestation> {
+    let digest = s.client.release_digest(recipient, &amount, &nonce);
+    let mut attestations = Vec::new(&s.env);
+    for &i in signers {
+        let key = &s.keys[i];
+        attestations.push_back(Attestation {
+            public_key: BytesN::from_array(&s.env, &key.verifying_key().to_bytes()),
+            signature: BytesN::from_array(&s.env, &key.sign(&digest.to_array()).to_bytes()),
+        });
+    }
+    attestations
+}
+
+#[test]
+fn test_lock_escrows_and_emits_canonical_event() {
+    let s = setup();
+    let recipient = Bytes::from_array(&s.env, &[0xab; 20]);
+
+    assert_eq!(s.client.lock(&s.user, &1_000, &1, &recipient), 0);
+    assert_eq!(s.client.lock(&s.user, &500, &1, &recipient), 1);
+    assert_eq!(s.token.balance(&s.client.address), 1_500);
+
+    let captured = assert_standard_event(&s.env, topics::BRIDGE_LOCK, |_| true);
+    assert_eq!(captured.metadata::<u64>(&s.env, NONCE_KEY), Some(1));
+    let event: shared::events::BridgeLockEvent = payload(&captured);
+    assert_eq!(event.recipient, recipient);
+    assert_eq!(event.dest_chain, 1);
+
+    let result = s.client.try_lock(&s.user, &0, &1, &recipient);
+    assert_eq!(result, Err(Ok(BridgeError::InvalidAmount)));
+}
+
+#[test]
+fn test_release_requires_threshold_and_blocks_replay() {
+    let s = setup();
+    s.client
+        .lock(&s.user, &5_000, &1, &Bytes::from_array(&s.env, &[1; 20]));
+    let recipient = Address::generate(&s.env);
+
+    let one = attest(&s, &[0], &recipient, 1_000, 42);
+    let result = s.client.try_release(&recipient, &1_000, &42, &one);
+    assert_eq!(result, Err(Ok(BridgeError::InsufficientSignatures)));
+
+    let mut doubled = attest(&s, &[0], &recipient, 1_000, 42);
+    doubled.append(&one);
+    let result = s.client.try_release(&recipient, &1_000, &42, &doubled);
+    assert_eq!(result, Err(Ok(BridgeError::DuplicateSignature)));
+
+    let outsider = SigningKey::from_bytes(&[9; 32]);
+    let forged = vec![
+        &s.env,
+        Attestation {
+            public_key: BytesN::from_array(&s.env, &outsider.verifying_key().to_bytes()),
+            signature: BytesN::from_array(&s.env, &[0; 64]),
+        },
+    ];
+    let result = s.client.try_release(&recipient, &1_000, &42, &forged);
+    assert_eq!(result, Err(Ok(BridgeError::UnknownValidator)));
+
+    let two = attest(&s, &[0, 2], &recipient, 1_000, 42);
+    s.client.release(&recipient, &1_000, &42, &two);
+    assert_eq!(s.token.balance(&recipient), 1_000);
+    assert!(s.client.is_processed(&42));
+    let captured = assert_standard_event(&s.env, topics::BRIDGE_RELEASE, |_| true);
+    let event: shared::events::BridgeReleaseEvent = payload(&captured);
+    assert_eq!(event.signers, 2);
+
+    let result = s.client.try_release(&recipient, &1_000, &42, &two);
+    assert_eq!(result, Err(Ok(BridgeError::AlreadyProcessed)));
+}
+
+#[test]
+fn test_pause_blocks_both_directions() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+    s.client
+        .lock(&s.user, &1_000, &1, &Bytes::from_array(&s.env, &[1; 20]));
+
+    let result = s.client.try_pause(&s.user);
+    assert_eq!(result, Err(Ok(BridgeError::Unauthorized)));
+    s.client.pause(&s.admin);
+    assert!(s.client.is_paused());
+
+    let result = s
+        .client
+        .try_lock(&s.user, &1_000, &1, &Bytes::from_array(&s.env, &[1; 20]));
+    assert_eq!(result, Err(Ok(BridgeError::Paused)));
+    let attestations = attest(&s, &[0, 1], &recipient, 1_000, 1);
+    let result = s.client.try_release(&recipient, &1_000, &1, &attestations);
+    assert_eq!(result, Err(Ok(BridgeError::Paused)));
+
+    s.client.unpause(&s.admin);
+    s.client.release(&recipient, &1_000, &1, &attestations);
+    assert_eq!(s.token.balance(&recipient), 1_000);
+}
+
+#[test]
+fn test_validator_set_updates() {
+    let s = setup();
+    let validators = s.client.validators();
+    let result = s.client.try_set_validators(&s.admin, &validators, &4);
+    assert_eq!(result, Err(Ok(BridgeError::InvalidThreshold)));
+    let result = s.client.try_set_validators(&s.admin, &validators, &0);
+    assert_eq!(result, Err(Ok(BridgeError::InvalidThreshold)));
+
+    s.client.set_validators(&s.admin, &validators, &3);
+    assert_eq!(s.client.threshold(), 3);
+}
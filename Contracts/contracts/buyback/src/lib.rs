@@ -0,0 +1,256 @@
+//! Buyback and burn
+//!
+//! Protocol fees are paid to this contract. Keepers periodically sell an
+//! accumulated fee token through the swap router into the burn token (the
+//! governance token) and burn what comes out; the burn token itself is
+//! burned directly. The admin sets the path each fee token is sold along.
+//!
+//! The keeper chooses the minimum output, but it may sit at most
+//! `max_slippage_bps` below the router's current quote, so a keeper cannot
+//! sell the fees at an arbitrary price. A fee token can be bought back at
+//! most once per `cooldown` seconds.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::amm::RouterClient;
+use shared::events::{BurnEvent, EventEmitter};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
+    IntoVal, Vec,
+};
+
+/// Basis-point denominator for slippage bounds
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BuybackError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    InvalidRoute = 5,
+    RouteNotFound = 6,
+    InvalidAmount = 7,
+    CooldownActive = 8,
+    SlippageTooLoose = 9,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    /// Swap router fee tokens are sold through
+    pub router: Address,
+    /// Token bought back and burned
+    pub burn_token: Address,
+    /// Furthest a keeper's minimum output may sit below the quote
+    pub max_slippage_bps: u32,
+    /// Seconds between buybacks of the same fee token
+    pub cooldown: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    /// Path a fee token is sold along, ending in the burn token
+    Route(Address),
+    /// Time of a fee token's last buyback
+    LastBuyback(Address),
+}
+
+#[contract]
+pub struct BuybackBurn;
+
+#[contractimpl]
+impl BuybackBurn {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        router: Address,
+        burn_token: Address,
+        max_slippage_bps: u32,
+        cooldown: u64,
+    ) -> Result<(), BuybackError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(BuybackError::AlreadyInitialized);
+        }
+        if max_slippage_bps > BPS_DENOMINATOR {
+            return Err(BuybackError::InvalidConfig);
+        }
+        let config = Config {
+            admin,
+            router,
+            burn_token,
+            max_slippage_bps,
+            cooldown,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Change the slippage bound and cooldown (admin only)
+    pub fn set_params(
+        env: Env,
+        admin: Address,
+        max_slippage_bps: u32,
+        cooldown: u64,
+    ) -> Result<(), BuybackError> {
+        let mut config = require_admin(&env, &admin)?;
+        if max_slippage_bps > BPS_DENOMINATOR {
+            return Err(BuybackError::InvalidConfig);
+        }
+        config.max_slippage_bps = max_slippage_bps;
+        config.cooldown = cooldown;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Sell `path[0]` along `path`, which must end in the burn token
+    /// (admin only)
+    pub fn set_route(env: Env, admin: Address, path: Vec<Address>) -> Result<(), BuybackError> {
+        let config = require_admin(&env, &admin)?;
+        if path.len() < 2 || path.last() != Some(config.burn_token) {
+            return Err(BuybackError::InvalidRoute);
+        }
+        let token = path.get_unchecked(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Route(token), &path);
+        Ok(())
+    }
+
+    /// Sell `amount` of accumulated `token` for the burn token and burn
+    /// the proceeds, or burn `amount` directly if `token` is the burn
+    /// token. `min_out` bounds the swap and must be within the configured
+    /// slippage of the current quote. Returns the amount burned.
+    pub fn buyback(
+        env: Env,
+        keeper: Address,
+        token: Address,
+        amount: i128,
+        min_out: i128,
+        deadline: u64,
+    ) -> Result<i128, BuybackError> {
+        keeper.require_auth();
+        let config = load_config(&env)?;
+        let this = env.current_contract_address();
+        if amount <= 0 || amount > token::Client::new(&env, &token).balance(&this) {
+            return Err(BuybackError::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        if let Some(last) = Self::last_buyback(env.clone(), token.clone()) {
+            if now < last + config.cooldown {
+                return Err(BuybackError::CooldownActive);
+            }
+        }
+
+        let burned = if token == config.burn_token {
+            amount
+        } else {
+            let path =
+                Self::route(env.clone(), token.clone()).ok_or(BuybackError::RouteNotFound)?;
+            let router = RouterClient::new(&env, &config.router);
+            let quote = router.quote(&path, &amount);
+            let floor = quote * (BPS_DENOMINATOR - config.max_slippage_bps) as i128
+                / BPS_DENOMINATOR as i128;
+            if min_out < floor {
+                return Err(BuybackError::SlippageTooLoose);
+            }
+            // The router pulls the input from this contract
+            env.authorize_as_current_contract(vec![
+                &env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: token.clone(),
+                        fn_name: symbol_short!("transfer"),
+                        args: (this.clone(), config.router.clone(), amount).into_val(&env),
+                    },
+                    sub_invocations: vec![&env],
+                }),
+            ]);
+            router.swap_exact_in(&this, &path, &amount, &min_out, &deadline)
+        };
+
+        token::Client::new(&env, &config.burn_token).burn(&this, &burned);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastBuyback(token.clone()), &now);
+        EventEmitter::buyback_burn(
+            &env,
+            BurnEvent {
+                from: this,
+                amount: burned,
+                timestamp: now,
+            },
+            token,
+            amount,
+        );
+        Ok(burned)
+    }
+
+    // ---- views ----
+
+    pub fn config(env: Env) -> Result<Config, BuybackError> {
+        load_config(&env)
+    }
+
+    pub fn route(env: Env, token: Address) -> Option<Vec<Address>> {
+        env.storage().persistent().get(&DataKey::Route(token))
+    }
+
+    pub fn last_buyback(env: Env, token: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::LastBuyback(token))
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), BuybackError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), BuybackError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, BuybackError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(BuybackError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, BuybackError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(BuybackError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,149 @@
+#![cfg(test)]
+
+use super::*;
+use amm::{AmmContract, AmmContractClient};
+use router::{SwapRouter, SwapRouterClient};
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::Symbol;
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const AB: Symbol = symbol_short!("AB");
+const BC: Symbol = symbol_short!("BC");
+const COOLDOWN: u64 = 3_600;
+
+struct Setup {
+    env: Env,
+    client: BuybackBurnClient<'static>,
+    router: SwapRouterClient<'static>,
+    admin: Address,
+    keeper: Address,
+    /// Fee token, intermediate token and burn token
+    tokens: [TestToken<'static>; 3],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let lp = Address::generate(&env);
+    let tokens = [
+        stellar_asset(&env),
+        stellar_asset(&env),
+        stellar_asset(&env),
+    ];
+    let amm = AmmContractClient::new(&env, &env.register_contract(None, AmmContract));
+    amm.initialize(&admin);
+    let router = SwapRouterClient::new(&env, &env.register_contract(None, SwapRouter));
+    router.initialize(&admin, &amm.address);
+    for (pair, a, b) in [(AB, 0, 1), (BC, 1, 2)] {
+        amm.create_pair(&admin, &pair, &tokens[a].address, &tokens[b].address);
+        tokens[a].mint(&lp, 100_000);
+        tokens[b].mint(&lp, 100_000);
+        amm.add_liquidity(&lp, &pair, &100_000, &100_000, &0);
+        router.register_pool(&admin, &pair);
+    }
+
+    let client = BuybackBurnClient::new(&env, &env.register_contract(None, BuybackBurn));
+    client.initialize(&admin, &router.address, &tokens[2].address, &100, &COOLDOWN);
+    let mut path = Vec::new(&env);
+    for token in &tokens {
+        path.push_back(token.address.clone());
+    }
+    client.set_route(&admin, &path);
+    tokens[0].mint(&client.address, 1_000);
+
+    Setup {
+        keeper: Address::generate(&env),
+        env,
+        client,
+        router,
+        admin,
+        tokens,
+    }
+}
+
+#[test]
+fn test_buyback_swaps_fees_and_burns_proceeds() {
+    let s = setup();
+    let fee_token = s.tokens[0].address.clone();
+    let route = s.client.route(&fee_token).unwrap();
+    let quote = s.router.quote(&route, &1_000);
+
+    assert_eq!(
+        s.client
+            .try_buyback(&s.keeper, &fee_token, &1_000, &(quote * 98 / 100), &2_000),
+        Err(Ok(BuybackError::SlippageTooLoose))
+    );
+    assert_eq!(
+        s.client
+            .try_buyback(&s.keeper, &fee_token, &1_001, &quote, &2_000),
+        Err(Ok(BuybackError::InvalidAmount))
+    );
+
+    let burned = s
+        .client
+        .buyback(&s.keeper, &fee_token, &1_000, &quote, &2_000);
+    assert_eq!(burned, quote);
+    assert_eq!(s.tokens[0].balance(&s.client.address), 0);
+    assert_eq!(s.tokens[2].balance(&s.client.address), 0);
+
+    let burn = assert_standard_event(&s.env, topics::BURN, |_| true);
+    let reason: Symbol = burn.metadata(&s.env, metadata_keys::REASON_KEY).unwrap();
+    assert_eq!(reason, symbol_short!("buyback"));
+    let token_in: Address = burn.metadata(&s.env, metadata_keys::TOKEN_KEY).unwrap();
+    assert_eq!(token_in, fee_token);
+    let amount_in: i128 = burn.metadata(&s.env, metadata_keys::AMOUNT_IN_KEY).unwrap();
+    assert_eq!(amount_in, 1_000);
+    let event: BurnEvent = payload(&burn);
+    assert_eq!(event.amount, quote);
+
+    s.tokens[0].mint(&s.client.address, 500);
+    assert_eq!(
+        s.client
+            .try_buyback(&s.keeper, &fee_token, &500, &0, &9_000),
+        Err(Ok(BuybackError::CooldownActive))
+    );
+    s.env.ledger().with_mut(|li| li.timestamp += COOLDOWN);
+    let quote = s.router.quote(&route, &500);
+    assert_eq!(
+        s.client
+            .buyback(&s.keeper, &fee_token, &500, &quote, &9_000),
+        quote
+    );
+}
+
+#[test]
+fn test_burn_token_fees_are_burned_directly() {
+    let s = setup();
+    let burn_token = s.tokens[2].address.clone();
+    s.tokens[2].mint(&s.client.address, 300);
+    assert_eq!(s.client.buyback(&s.keeper, &burn_token, &300, &0, &0), 300);
+    assert_eq!(s.tokens[2].balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_routes_must_end_in_burn_token() {
+    let s = setup();
+    let mut path = Vec::new(&s.env);
+    path.push_back(s.tokens[1].address.clone());
+    path.push_back(s.tokens[0].address.clone());
+    assert_eq!(
+        s.client.try_set_route(&s.admin, &path),
+        Err(Ok(BuybackError::InvalidRoute))
+    );
+    assert_eq!(
+        s.client
+            .try_buyback(&s.keeper, &s.tokens[1].address, &1, &0, &2_000),
+        Err(Ok(BuybackError::InvalidAmount))
+    );
+    s.tokens[1].mint(&s.client.address, 10);
+    assert_eq!(
+        s.client
+            .try_buyback(&s.keeper, &s.tokens[1].address, &10, &0, &2_000),
+        Err(Ok(BuybackError::RouteNotFound))
+    );
+}
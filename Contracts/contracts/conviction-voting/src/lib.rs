@@ -0,0 +1,503 @@
+//! Conviction voting for continuous funding
+//!
+//! Proposals request an amount from the contract's funding pool. Supporters
+//! stake tokens behind the proposals they back, and each proposal's
+//! conviction grows toward `staked / (1 - decay)` the longer the stake
+//! stays, decaying the same way once it is withdrawn:
+//!
+//! `conviction(t + n) = conviction(t) * decay^n + staked * (1 - decay^n) / (1 - decay)`
+//!
+//! A proposal passes once its conviction reaches
+//! `weight * total_staked / (max_ratio - requested / funds)^2`, so asking
+//! for a larger share of the pool needs more support held for longer, and
+//! no proposal may ask for `max_ratio` of the pool or more. `decay`,
+//! `max_ratio` and `weight` are fixed point with [`SCALE`] as one; `decay`
+//! applies per second.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{ConvictionPassedEvent, ConvictionUpdatedEvent, EventEmitter};
+use shared::governance::ProposalStatus;
+use shared::math::mul_div;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol,
+};
+
+/// Fixed-point one for `decay`, `max_ratio` and `weight`
+pub const SCALE: i128 = 1_000_000_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConvictionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    InvalidAmount = 5,
+    ProposalNotFound = 6,
+    ProposalNotActive = 7,
+    InsufficientStake = 8,
+    RequestTooLarge = 9,
+    InsufficientConviction = 10,
+    Overflow = 11,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvictionConfig {
+    /// Token supporters stake
+    pub stake_token: Address,
+    /// Token proposals are paid in
+    pub funding_token: Address,
+    /// Share of conviction kept each second
+    pub decay: i128,
+    /// Largest share of the pool a proposal may request
+    pub max_ratio: i128,
+    /// Scales how much conviction a request needs
+    pub weight: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub beneficiary: Address,
+    pub requested: i128,
+    pub description: Symbol,
+    /// Tokens currently staked in support
+    pub staked: i128,
+    /// Conviction as of `last_update`
+    pub conviction: i128,
+    pub last_update: u64,
+    /// `Pending` while collecting support, then `Executed` or `Cancelled`
+    pub status: ProposalStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Config,
+    Funds,
+    TotalStaked,
+    NextProposalId,
+    Proposal(u64),
+    Stake(u64, Address),
+}
+
+#[contract]
+pub struct ConvictionVoting;
+
+#[contractimpl]
+impl ConvictionVoting {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        stake_token: Address,
+        funding_token: Address,
+        decay: i128,
+        max_ratio: i128,
+        weight: i128,
+    ) -> Result<(), ConvictionError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ConvictionError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if decay <= 0 || decay >= SCALE || max_ratio <= 0 || max_ratio > SCALE || weight <= 0 {
+            return Err(ConvictionError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &ConvictionConfig {
+                stake_token,
+                funding_token,
+                decay,
+                max_ratio,
+                weight,
+            },
+        );
+        Ok(())
+    }
+
+    /// Add `amount` of the funding token to the pool proposals draw from
+    pub fn fund(env: Env, from: Address, amount: i128) -> Result<(), ConvictionError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(ConvictionError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        token::Client::new(&env, &config.funding_token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let funds = Self::funds(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Funds, &(funds + amount));
+        Ok(())
+    }
+
+    /// Request `requested` of the pool for `beneficiary`. Returns the
+    /// proposal id.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        beneficiary: Address,
+        requested: i128,
+        description: Symbol,
+    ) -> Result<u64, ConvictionError> {
+        proposer.require_auth();
+        load_config(&env)?;
+        if requested <= 0 {
+            return Err(ConvictionError::InvalidAmount);
+        }
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(id + 1));
+
+        let proposal = FundingProposal {
+            id,
+            proposer,
+            beneficiary,
+            requested,
+            description,
+            staked: 0,
+            conviction: 0,
+            last_update: env.ledger().timestamp(),
+            status: ProposalStatus::Pending,
+        };
+        save_proposal(&env, &proposal);
+        Ok(id)
+    }
+
+    /// Stake `amount` behind an active proposal. Returns its conviction.
+    pub fn support(
+        env: Env,
+        supporter: Address,
+        proposal_id: u64,
+        amount: i128,
+    ) -> Result<i128, ConvictionError> {
+        supporter.require_auth();
+        if amount <= 0 {
+            return Err(ConvictionError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(ConvictionError::ProposalNotActive);
+        }
+        token::Client::new(&env, &config.stake_token).transfer(
+            &supporter,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        accrue(&env, &config, &mut proposal)?;
+        proposal.staked += amount;
+        save_proposal(&env, &proposal);
+        let key = DataKey::Stake(proposal_id, supporter.clone());
+        let stake: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(stake + amount));
+        add_total_staked(&env, amount);
+
+        emit_updated(&env, &proposal, supporter);
+        Ok(proposal.conviction)
+    }
+
+    /// Withdraw `amount` of the caller's stake from a proposal. Stake can be
+    /// withdrawn at any time, including after the proposal has passed.
+    pub fn withdraw_support(
+        env: Env,
+        supporter: Address,
+        proposal_id: u64,
+        amount: i128,
+    ) -> Result<(), ConvictionError> {
+        supporter.require_auth();
+        let config = load_config(&env)?;
+        let key = DataKey::Stake(proposal_id, supporter.clone());
+        let stake: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 || amount > stake {
+            return Err(ConvictionError::InsufficientStake);
+        }
+
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status == ProposalStatus::Pending {
+            accrue(&env, &config, &mut proposal)?;
+            add_total_staked(&env, -amount);
+        }
+        proposal.staked -= amount;
+        save_proposal(&env, &proposal);
+        env.storage().persistent().set(&key, &(stake - amount));
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &supporter,
+            &amount,
+        );
+        if proposal.status == ProposalStatus::Pending {
+            emit_updated(&env, &proposal, supporter);
+        }
+        Ok(())
+    }
+
+    /// Pay out a proposal whose conviction has reached its threshold.
+    /// Anyone can call this.
+    pub fn execute(env: Env, proposal_id: u64) -> Result<(), ConvictionError> {
+        let config = load_config(&env)?;
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(ConvictionError::ProposalNotActive);
+        }
+        accrue(&env, &config, &mut proposal)?;
+        let funds = Self::funds(env.clone());
+        let threshold = threshold(&config, proposal.requested, funds, total_staked(&env))?;
+        if proposal.conviction < threshold {
+            return Err(ConvictionError::InsufficientConviction);
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        save_proposal(&env, &proposal);
+        add_total_staked(&env, -proposal.staked);
+        env.storage()
+            .instance()
+            .set(&DataKey::Funds, &(funds - proposal.requested));
+        token::Client::new(&env, &config.funding_token).transfer(
+            &env.current_contract_address(),
+            &proposal.beneficiary,
+            &proposal.requested,
+        );
+
+        EventEmitter::conviction_passed(
+            &env,
+            ConvictionPassedEvent {
+                proposal_id,
+                beneficiary: proposal.beneficiary,
+                amount: proposal.requested,
+                conviction: proposal.conviction,
+                threshold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw an active proposal (proposer or admin). Supporters can then
+    /// reclaim their stake.
+    pub fn cancel(env: Env, caller: Address, proposal_id: u64) -> Result<(), ConvictionError> {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ConvictionError::NotInitialized)?;
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if caller != proposal.proposer && caller != admin {
+            return Err(ConvictionError::Unauthorized);
+        }
+        if proposal.status != ProposalStatus::Pending {
+            return Err(ConvictionError::ProposalNotActive);
+        }
+        proposal.status = ProposalStatus::Cancelled;
+        save_proposal(&env, &proposal);
+        add_total_staked(&env, -proposal.staked);
+        Ok(())
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<FundingProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+    }
+
+    /// Conviction of a proposal as of now
+    pub fn conviction(env: Env, proposal_id: u64) -> Result<i128, ConvictionError> {
+        let config = load_config(&env)?;
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status == ProposalStatus::Pending {
+            accrue(&env, &config, &mut proposal)?;
+        }
+        Ok(proposal.conviction)
+    }
+
+    /// Conviction a proposal needs to pass with the current pool and stake
+    pub fn threshold(env: Env, proposal_id: u64) -> Result<i128, ConvictionError> {
+        let config = load_config(&env)?;
+        let proposal = load_proposal(&env, proposal_id)?;
+        threshold(
+            &config,
+            proposal.requested,
+            Self::funds(env.clone()),
+            total_staked(&env),
+        )
+    }
+
+    pub fn stake_of(env: Env, proposal_id: u64, supporter: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stake(proposal_id, supporter))
+            .unwrap_or(0)
+    }
+
+    /// Funding token available to proposals
+    pub fn funds(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Funds).unwrap_or(0)
+    }
+
+    /// Stake behind active proposals
+    pub fn total_staked(env: Env) -> i128 {
+        total_staked(&env)
+    }
+
+    pub fn get_config(env: Env) -> Result<ConvictionConfig, ConvictionError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), ConvictionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), ConvictionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Bring a proposal's conviction up to the current time
+fn accrue(
+    env: &Env,
+    config: &ConvictionConfig,
+    proposal: &mut FundingProposal,
+) -> Result<(), ConvictionError> {
+    let now = env.ledger().timestamp();
+    let elapsed = now.saturating_sub(proposal.last_update);
+    if elapsed > 0 {
+        let kept = decay_pow(config.decay, elapsed);
+        let carried = mul_div(proposal.conviction, kept, SCALE).ok_or(ConvictionError::Overflow)?;
+        let gained = mul_div(proposal.staked, SCALE - kept, SCALE - config.decay)
+            .ok_or(ConvictionError::Overflow)?;
+        proposal.conviction = carried + gained;
+        proposal.last_update = now;
+    }
+    Ok(())
+}
+
+/// `decay^n` in fixed point, by repeated squaring
+fn decay_pow(decay: i128, mut n: u64) -> i128 {
+    let mut result = SCALE;
+    let mut base = decay;
+    while n > 0 && result > 0 {
+        if n & 1 == 1 {
+            result = result * base / SCALE;
+        }
+        base = base * base / SCALE;
+        n >>= 1;
+    }
+    result
+}
+
+fn threshold(
+    config: &ConvictionConfig,
+    requested: i128,
+    funds: i128,
+    total_staked: i128,
+) -> Result<i128, ConvictionError> {
+    let ratio = mul_div(requested, SCALE, funds).ok_or(ConvictionError::RequestTooLarge)?;
+    if ratio >= config.max_ratio {
+        return Err(ConvictionError::RequestTooLarge);
+    }
+    let gap = config.max_ratio - ratio;
+    mul_div(config.weight, SCALE, gap)
+        .and_then(|scaled| mul_div(scaled, total_staked, gap))
+        .ok_or(ConvictionError::Overflow)
+}
+
+fn emit_updated(env: &Env, proposal: &FundingProposal, supporter: Address) {
+    EventEmitter::conviction_updated(
+        env,
+        ConvictionUpdatedEvent {
+            proposal_id: proposal.id,
+            supporter,
+            staked: proposal.staked,
+            conviction: proposal.conviction,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), ConvictionError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(ConvictionError::NotInitialized)?;
+    if stored != *admin {
+        return Err(ConvictionError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_config(env: &Env) -> Result<ConvictionConfig, ConvictionError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(ConvictionError::NotInitialized)
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Result<FundingProposal, ConvictionError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .ok_or(ConvictionError::ProposalNotFound)
+}
+
+fn save_proposal(env: &Env, proposal: &FundingProposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(proposal.id), proposal);
+}
+
+fn total_staked(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalStaked)
+        .unwrap_or(0)
+}
+
+fn add_total_staked(env: &Env, delta: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalStaked, &(total_staked(env) + delta));
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,178 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::symbol_short;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const HOUR: u64 = 60 * 60;
+/// Conviction keeps 99.999% per second, so stake converges on
+/// `100_000 * staked`
+const DECAY: i128 = 999_990_000;
+/// No proposal may request 20% or more of the pool
+const MAX_RATIO: i128 = 200_000_000;
+/// Scales thresholds so a well-supported request passes in about a day
+const WEIGHT: i128 = 1_000 * SCALE;
+
+struct Setup {
+    env: Env,
+    client: ConvictionVotingClient<'static>,
+    stake: TokenClient<'static>,
+    funding: TokenClient<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+    grantee: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+    let admin = Address::generate(&env);
+    let stake_id = env.register_stellar_asset_contract(admin.clone());
+    let funding_id = env.register_stellar_asset_contract(admin.clone());
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let grantee = Address::generate(&env);
+    StellarAssetClient::new(&env, &stake_id).mint(&alice, &1_000);
+    StellarAssetClient::new(&env, &stake_id).mint(&bob, &1_000);
+    StellarAssetClient::new(&env, &funding_id).mint(&admin, &100_000);
+
+    let contract_id = env.register_contract(None, ConvictionVoting);
+    let client = ConvictionVotingClient::new(&env, &contract_id);
+    client.initialize(&admin, &stake_id, &funding_id, &DECAY, &MAX_RATIO, &WEIGHT);
+    client.fund(&admin, &100_000);
+
+    Setup {
+        client,
+        stake: TokenClient::new(&env, &stake_id),
+        funding: TokenClient::new(&env, &funding_id),
+        env,
+        admin,
+        alice,
+        bob,
+        grantee,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_proposal_passes_once_conviction_reaches_threshold() {
+    let s = setup();
+    let id = s
+        .client
+        .propose(&s.alice, &s.grantee, &5_000, &symbol_short!("grant"));
+    s.client.support(&s.alice, &id, &1_000);
+
+    // 5% of the pool with 1_000 staked needs 1_000 * 1_000 / 0.15^2
+    // conviction
+    assert_eq!(s.client.threshold(&id), 44_444_444);
+    let result = s.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(ConvictionError::InsufficientConviction)));
+
+    advance(&s.env, 12 * HOUR);
+    let result = s.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(ConvictionError::InsufficientConviction)));
+
+    advance(&s.env, 6 * HOUR);
+    s.client.execute(&id);
+    assert_eq!(s.funding.balance(&s.grantee), 5_000);
+    assert_eq!(s.client.funds(), 95_000);
+    assert_eq!(s.client.total_staked(), 0);
+    assert_eq!(
+        s.client.get_proposal(&id).unwrap().status,
+        ProposalStatus::Executed
+    );
+
+    let captured = assert_standard_event(&s.env, topics::CONVICTION_PASSED, |_| true);
+    let passed: ConvictionPassedEvent = payload(&captured);
+    assert_eq!(passed.threshold, 44_444_444);
+    assert!(passed.conviction >= passed.threshold);
+
+    // Supporters get their stake back after the payout
+    let result = s.client.try_support(&s.bob, &id, &1_000);
+    assert_eq!(result, Err(Ok(ConvictionError::ProposalNotActive)));
+    s.client.withdraw_support(&s.alice, &id, &1_000);
+    assert_eq!(s.stake.balance(&s.alice), 1_000);
+}
+
+#[test]
+fn test_larger_requests_need_more_conviction() {
+    let s = setup();
+    let small = s
+        .client
+        .propose(&s.alice, &s.grantee, &1_000, &symbol_short!("small"));
+    let large = s
+        .client
+        .propose(&s.alice, &s.grantee, &15_000, &symbol_short!("large"));
+    let too_large = s
+        .client
+        .propose(&s.alice, &s.grantee, &20_000, &symbol_short!("huge"));
+    s.client.support(&s.alice, &small, &1_000);
+    s.client.support(&s.bob, &large, &500);
+    s.client.support(&s.bob, &too_large, &500);
+
+    assert!(s.client.threshold(&large) > 4 * s.client.threshold(&small));
+    let result = s.client.try_execute(&too_large);
+    assert_eq!(result, Err(Ok(ConvictionError::RequestTooLarge)));
+
+    advance(&s.env, 24 * HOUR);
+    s.client.execute(&small);
+    let result = s.client.try_execute(&large);
+    assert_eq!(result, Err(Ok(ConvictionError::InsufficientConviction)));
+}
+
+#[test]
+fn test_conviction_decays_after_withdrawal() {
+    let s = setup();
+    let id = s
+        .client
+        .propose(&s.alice, &s.grantee, &5_000, &symbol_short!("grant"));
+    s.client.support(&s.alice, &id, &1_000);
+    advance(&s.env, 6 * HOUR);
+    let built = s.client.conviction(&id);
+    assert!(built > 0 && built < 100_000 * 1_000);
+
+    s.client.withdraw_support(&s.alice, &id, &1_000);
+    let captured = assert_standard_event(&s.env, topics::CONVICTION_UPDATED, |_| true);
+    let updated: ConvictionUpdatedEvent = payload(&captured);
+    assert_eq!(updated.staked, 0);
+    assert_eq!(updated.conviction, built);
+    assert_eq!(
+        captured.metadata::<i128>(&s.env, metadata_keys::CONVICTION_KEY),
+        Some(built)
+    );
+
+    advance(&s.env, 6 * HOUR);
+    let decayed = s.client.conviction(&id);
+    assert!(decayed < built);
+    let result = s.client.try_withdraw_support(&s.alice, &id, &1);
+    assert_eq!(result, Err(Ok(ConvictionError::InsufficientStake)));
+}
+
+#[test]
+fn test_cancel_releases_stake() {
+    let s = setup();
+    let id = s
+        .client
+        .propose(&s.alice, &s.grantee, &5_000, &symbol_short!("grant"));
+    s.client.support(&s.bob, &id, &400);
+    assert_eq!(s.client.total_staked(), 400);
+
+    let result = s.client.try_cancel(&s.bob, &id);
+    assert_eq!(result, Err(Ok(ConvictionError::Unauthorized)));
+    s.client.cancel(&s.admin, &id);
+    assert_eq!(s.client.total_staked(), 0);
+    let result = s.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(ConvictionError::ProposalNotActive)));
+
+    s.client.withdraw_support(&s.bob, &id, &400);
+    assert_eq!(s.stake.balance(&s.bob), 1_000);
+}
@@ -0,0 +1,357 @@
+//! Revenue-share distributor
+//!
+//! Holders deposit the share token here and earn a pro-rata cut of the
+//! protocol revenue sent in. Each revenue deposit becomes a distribution
+//! tied to the epoch it arrived in; once that epoch is over, holders claim
+//! `amount * shares / total_shares` using the balances snapshotted for it
+//! (see [`snapshot`]). Whatever is still unclaimed `claim_epochs` epochs
+//! after the distribution's epoch can be rolled over into a new
+//! distribution in the current epoch.
+
+#![no_std]
+
+mod snapshot;
+
+use shared::epoch::EpochClock;
+use shared::events::{DistributionCreatedEvent, DividendClaimedEvent, EventEmitter};
+use shared::math::mul_div;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+pub use snapshot::Snapshot;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DividendError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    InvalidAmount = 4,
+    InsufficientShares = 5,
+    DistributionNotFound = 6,
+    EpochNotOver = 7,
+    AlreadyClaimed = 8,
+    NothingToClaim = 9,
+    ClaimPeriodOver = 10,
+    ClaimPeriodOpen = 11,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendConfig {
+    /// Token holders deposit to earn revenue
+    pub share_token: Address,
+    /// Length of a distribution epoch in seconds
+    pub epoch_length: u64,
+    /// Epochs after a distribution's own during which it can be claimed
+    pub claim_epochs: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Distribution {
+    pub id: u64,
+    pub token: Address,
+    pub amount: i128,
+    /// Epoch whose share balances the distribution is split by
+    pub epoch: u64,
+    pub claimed: i128,
+    /// Set once the unclaimed remainder has been rolled over
+    pub rolled_over: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    NextDistributionId,
+    Distribution(u64),
+    Claimed(u64, Address),
+    Balances(Address),
+    TotalBalances,
+}
+
+#[contract]
+pub struct DividendDistributor;
+
+#[contractimpl]
+impl DividendDistributor {
+    pub fn initialize(
+        env: Env,
+        share_token: Address,
+        epoch_length: u64,
+        claim_epochs: u64,
+    ) -> Result<(), DividendError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(DividendError::AlreadyInitialized);
+        }
+        if epoch_length == 0 || claim_epochs == 0 {
+            return Err(DividendError::InvalidConfig);
+        }
+        env.storage().instance().set(
+            &DataKey::Config,
+            &DividendConfig {
+                share_token,
+                epoch_length,
+                claim_epochs,
+            },
+        );
+        Ok(())
+    }
+
+    /// Deposit share tokens. They count from the next epoch.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<(), DividendError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(DividendError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        token::Client::new(&env, &config.share_token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let epoch = current_epoch(&env, &config);
+        snapshot::deposit(&env, &user, epoch, oldest_claimable(&config, epoch), amount);
+        Ok(())
+    }
+
+    /// Withdraw share tokens. They stop counting from the current epoch.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<(), DividendError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(DividendError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        let epoch = current_epoch(&env, &config);
+        if !snapshot::withdraw(&env, &user, epoch, oldest_claimable(&config, epoch), amount) {
+            return Err(DividendError::InsufficientShares);
+        }
+        token::Client::new(&env, &config.share_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        Ok(())
+    }
+
+    /// Send `amount` of `token` in as revenue for the current epoch.
+    /// Returns the new distribution's id.
+    pub fn distribute(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, DividendError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(DividendError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        token::Client::new(&env, &token).transfer(&from, &env.current_contract_address(), &amount);
+        Ok(create_distribution(&env, &config, token, amount, None))
+    }
+
+    /// Claim `user`'s share of a distribution whose epoch is over
+    pub fn claim(env: Env, user: Address, distribution_id: u64) -> Result<i128, DividendError> {
+        user.require_auth();
+        let config = load_config(&env)?;
+        let mut distribution = load_distribution(&env, distribution_id)?;
+        let epoch = current_epoch(&env, &config);
+        if epoch <= distribution.epoch {
+            return Err(DividendError::EpochNotOver);
+        }
+        if distribution.rolled_over || epoch > distribution.epoch + config.claim_epochs {
+            return Err(DividendError::ClaimPeriodOver);
+        }
+        let claimed_key = DataKey::Claimed(distribution_id, user.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(DividendError::AlreadyClaimed);
+        }
+
+        let amount = share_of(&env, &distribution, &user);
+        if amount <= 0 {
+            return Err(DividendError::NothingToClaim);
+        }
+        env.storage().persistent().set(&claimed_key, &amount);
+        distribution.claimed += amount;
+        save_distribution(&env, &distribution);
+
+        token::Client::new(&env, &distribution.token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        EventEmitter::dividend_claimed(
+            &env,
+            DividendClaimedEvent {
+                distribution_id,
+                user,
+                token: distribution.token,
+                amount,
+                epoch: distribution.epoch,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(amount)
+    }
+
+    /// Move what is left of an expired distribution into a new one for the
+    /// current epoch. Anyone can call this. Returns the new distribution's
+    /// id.
+    pub fn rollover(env: Env, distribution_id: u64) -> Result<u64, DividendError> {
+        let config = load_config(&env)?;
+        let mut distribution = load_distribution(&env, distribution_id)?;
+        if distribution.rolled_over {
+            return Err(DividendError::ClaimPeriodOver);
+        }
+        if current_epoch(&env, &config) <= distribution.epoch + config.claim_epochs {
+            return Err(DividendError::ClaimPeriodOpen);
+        }
+        let unclaimed = distribution.amount - distribution.claimed;
+        if unclaimed <= 0 {
+            return Err(DividendError::NothingToClaim);
+        }
+        distribution.rolled_over = true;
+        save_distribution(&env, &distribution);
+        Ok(create_distribution(
+            &env,
+            &config,
+            distribution.token,
+            unclaimed,
+            Some(distribution_id),
+        ))
+    }
+
+    /// What `user` can claim from a distribution, ignoring whether its
+    /// epoch is over
+    pub fn claimable(env: Env, user: Address, distribution_id: u64) -> i128 {
+        let Ok(distribution) = load_distribution(&env, distribution_id) else {
+            return 0;
+        };
+        if distribution.rolled_over
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Claimed(distribution_id, user.clone()))
+        {
+            return 0;
+        }
+        share_of(&env, &distribution, &user)
+    }
+
+    pub fn get_distribution(env: Env, distribution_id: u64) -> Option<Distribution> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Distribution(distribution_id))
+    }
+
+    /// Shares `user` has deposited, including ones not counted yet
+    pub fn shares(env: Env, user: Address) -> i128 {
+        snapshot::latest_balance(&env, &user)
+    }
+
+    /// Shares `user` held for `epoch`. Epochs that can no longer be
+    /// claimed may have been pruned.
+    pub fn shares_at(env: Env, user: Address, epoch: u64) -> i128 {
+        snapshot::balance_at(&env, &user, epoch)
+    }
+
+    /// Shares held by everyone for `epoch`. Epochs that can no longer be
+    /// claimed may have been pruned.
+    pub fn total_shares_at(env: Env, epoch: u64) -> i128 {
+        snapshot::total_at(&env, epoch)
+    }
+
+    pub fn current_epoch(env: Env) -> Result<u64, DividendError> {
+        Ok(current_epoch(&env, &load_config(&env)?))
+    }
+
+    pub fn get_config(env: Env) -> Result<DividendConfig, DividendError> {
+        load_config(&env)
+    }
+}
+
+fn create_distribution(
+    env: &Env,
+    config: &DividendConfig,
+    token: Address,
+    amount: i128,
+    rolled_over_from: Option<u64>,
+) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextDistributionId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextDistributionId, &(id + 1));
+
+    let epoch = current_epoch(env, config);
+    let distribution = Distribution {
+        id,
+        token: token.clone(),
+        amount,
+        epoch,
+        claimed: 0,
+        rolled_over: false,
+    };
+    save_distribution(env, &distribution);
+
+    EventEmitter::distribution_created(
+        env,
+        DistributionCreatedEvent {
+            distribution_id: id,
+            token,
+            amount,
+            epoch,
+            rolled_over_from,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    id
+}
+
+fn share_of(env: &Env, distribution: &Distribution, user: &Address) -> i128 {
+    let shares = snapshot::balance_at(env, user, distribution.epoch);
+    let total = snapshot::total_at(env, distribution.epoch);
+    mul_div(distribution.amount, shares, total).unwrap_or(0)
+}
+
+fn current_epoch(env: &Env, config: &DividendConfig) -> u64 {
+    EpochClock {
+        genesis: 0,
+        length: config.epoch_length,
+    }
+    .current_epoch(env)
+}
+
+/// Oldest epoch whose balances a claim may still read in `epoch`, with one
+/// to spare
+fn oldest_claimable(config: &DividendConfig, epoch: u64) -> u64 {
+    epoch.saturating_sub(config.claim_epochs.saturating_add(1))
+}
+
+fn load_config(env: &Env) -> Result<DividendConfig, DividendError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(DividendError::NotInitialized)
+}
+
+fn load_distribution(env: &Env, distribution_id: u64) -> Result<Distribution, DividendError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Distribution(distribution_id))
+        .ok_or(DividendError::DistributionNotFound)
+}
+
+fn save_distribution(env: &Env, distribution: &Distribution) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Distribution(distribution.id), distribution);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,151 @@
+//! Per-epoch share balances
+//!
+//! Each holder, and the total, keeps a history of `(epoch, balance)`
+//! entries; the balance counted for an epoch is the latest entry at or
+//! before it. Deposits are written for the next epoch so shares only earn
+//! from the first full epoch they are held. Withdrawals also lower the
+//! current epoch's entry, so the balance counted for an epoch is the
+//! smallest one held during it and shares cannot be moved in and out
+//! around a distribution.
+//!
+//! Only epochs that can still be claimed are needed, so every write drops
+//! the entries superseded before the oldest of them. A history then holds
+//! at most a few entries more than the claim window.
+
+use crate::DataKey;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    pub epoch: u64,
+    pub balance: i128,
+}
+
+/// Record `amount` more shares for `user`, counted from `epoch + 1`.
+/// History from before `oldest` is dropped.
+pub fn deposit(env: &Env, user: &Address, epoch: u64, oldest: u64, amount: i128) {
+    let key = DataKey::Balances(user.clone());
+    let mut history = load(env, &key);
+    let current = latest(&history);
+    write(&mut history, epoch + 1, current + amount);
+    prune(&mut history, oldest);
+    env.storage().persistent().set(&key, &history);
+
+    let mut total = load(env, &DataKey::TotalBalances);
+    let latest_total = latest(&total);
+    write(&mut total, epoch + 1, latest_total + amount);
+    prune(&mut total, oldest);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TotalBalances, &total);
+}
+
+/// Remove `amount` of `user`'s shares, from `epoch` onwards. Returns
+/// `false` if they hold fewer. History from before `oldest` is dropped.
+pub fn withdraw(env: &Env, user: &Address, epoch: u64, oldest: u64, amount: i128) -> bool {
+    let key = DataKey::Balances(user.clone());
+    let mut history = load(env, &key);
+    let remaining = latest(&history) - amount;
+    if remaining < 0 {
+        return false;
+    }
+    // Only the part of the current epoch's balance that is withdrawn
+    // leaves the current epoch's total
+    let counted = balance_in(&history, epoch);
+    let removed_now = (counted - remaining).max(0);
+    write(&mut history, epoch, counted.min(remaining));
+    write(&mut history, epoch + 1, remaining);
+    prune(&mut history, oldest);
+    env.storage().persistent().set(&key, &history);
+
+    let mut total = load(env, &DataKey::TotalBalances);
+    let counted_total = balance_in(&total, epoch);
+    let latest_total = latest(&total);
+    write(&mut total, epoch, counted_total - removed_now);
+    write(&mut total, epoch + 1, latest_total - amount);
+    prune(&mut total, oldest);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TotalBalances, &total);
+    true
+}
+
+/// Shares `user` holds for `epoch`
+pub fn balance_at(env: &Env, user: &Address, epoch: u64) -> i128 {
+    balance_in(&load(env, &DataKey::Balances(user.clone())), epoch)
+}
+
+/// Shares held by everyone for `epoch`
+pub fn total_at(env: &Env, epoch: u64) -> i128 {
+    balance_in(&load(env, &DataKey::TotalBalances), epoch)
+}
+
+/// Latest recorded shares of `user`, including deposits not yet counted
+pub fn latest_balance(env: &Env, user: &Address) -> i128 {
+    latest(&load(env, &DataKey::Balances(user.clone())))
+}
+
+fn load(env: &Env, key: &DataKey) -> Vec<Snapshot> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn latest(history: &Vec<Snapshot>) -> i128 {
+    history.last().map_or(0, |s| s.balance)
+}
+
+/// Binary search for the latest entry at or before `epoch`
+fn balance_in(history: &Vec<Snapshot>, epoch: u64) -> i128 {
+    let (mut low, mut high) = (0u32, history.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if history.get_unchecked(mid).epoch <= epoch {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        history.get_unchecked(low - 1).balance
+    }
+}
+
+/// Drop the entries superseded at or before `oldest`, keeping the one that
+/// still holds for it
+fn prune(history: &mut Vec<Snapshot>, oldest: u64) {
+    while history.len() > 1 && history.get_unchecked(1).epoch <= oldest {
+        history.pop_front();
+    }
+}
+
+/// Set the balance from `epoch`, which is at most one epoch before the
+/// last entry
+fn write(history: &mut Vec<Snapshot>, epoch: u64, balance: i128) {
+    match history.last() {
+        Some(last) if last.epoch == epoch => {
+            history.set(history.len() - 1, Snapshot { epoch, balance });
+        }
+        Some(last) if last.epoch > epoch => {
+            // Lowering the current epoch while the next one is already
+            // recorded: the entry for `epoch` is second to last, or absent
+            let index = history.len() - 1;
+            let previous = if index > 0 {
+                history.get(index - 1)
+            } else {
+                None
+            };
+            match previous {
+                Some(prev) if prev.epoch == epoch => {
+                    history.set(index - 1, Snapshot { epoch, balance })
+                }
+                _ => history.insert(index, Snapshot { epoch, balance }),
+            }
+        }
+        _ => history.push_back(Snapshot { epoch, balance }),
+    }
+}
@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::Vec;
+use test_utils::{assert_standard_event, payload};
+
+const EPOCH: u64 = 7 * 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: DividendDistributorClient<'static>,
+    shares: TokenClient<'static>,
+    revenue: TokenClient<'static>,
+    treasury: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10 * EPOCH);
+
+    let admin = Address::generate(&env);
+    let shares_id = env.register_stellar_asset_contract(admin.clone());
+    let revenue_id = env.register_stellar_asset_contract(admin);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &shares_id).mint(&alice, &4_000);
+    StellarAssetClient::new(&env, &shares_id).mint(&bob, &1_000);
+    StellarAssetClient::new(&env, &revenue_id).mint(&treasury, &100_000);
+
+    let contract_id = env.register_contract(None, DividendDistributor);
+    let client = DividendDistributorClient::new(&env, &contract_id);
+    client.initialize(&shares_id, &EPOCH, &2);
+
+    Setup {
+        client,
+        shares: TokenClient::new(&env, &shares_id),
+        revenue: TokenClient::new(&env, &revenue_id),
+        env,
+        treasury,
+        alice,
+        bob,
+    }
+}
+
+fn next_epoch(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += EPOCH);
+}
+
+#[test]
+fn test_revenue_is_split_by_snapshotted_shares() {
+    let s = setup();
+    s.client.deposit(&s.alice, &3_000);
+    s.client.deposit(&s.bob, &1_000);
+
+    // Deposits only count from the next epoch
+    let early = s.client.distribute(&s.treasury, &s.revenue.address, &4_000);
+    assert_eq!(s.client.claimable(&s.alice, &early), 0);
+
+    next_epoch(&s.env);
+    let id = s
+        .client
+        .distribute(&s.treasury, &s.revenue.address, &10_000);
+    let result = s.client.try_claim(&s.alice, &id);
+    assert_eq!(result, Err(Ok(DividendError::EpochNotOver)));
+
+    // Shares deposited during the epoch do not change its split
+    s.client.deposit(&s.alice, &1_000);
+    next_epoch(&s.env);
+    assert_eq!(s.client.claim(&s.alice, &id), 7_500);
+    assert_eq!(s.client.claim(&s.bob, &id), 2_500);
+    assert_eq!(s.revenue.balance(&s.alice), 7_500);
+    let result = s.client.try_claim(&s.bob, &id);
+    assert_eq!(result, Err(Ok(DividendError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_withdrawal_forfeits_current_epoch() {
+    let s = setup();
+    s.client.deposit(&s.alice, &3_000);
+    s.client.deposit(&s.bob, &1_000);
+    next_epoch(&s.env);
+
+    let id = s
+        .client
+        .distribute(&s.treasury, &s.revenue.address, &10_000);
+    // Bob pulls out before the epoch ends and is left out of its split
+    s.client.withdraw(&s.bob, &1_000);
+    assert_eq!(s.shares.balance(&s.bob), 1_000);
+    let epoch = s.client.current_epoch();
+    assert_eq!(s.client.total_shares_at(&epoch), 3_000);
+
+    next_epoch(&s.env);
+    let result = s.client.try_claim(&s.bob, &id);
+    assert_eq!(result, Err(Ok(DividendError::NothingToClaim)));
+    assert_eq!(s.client.claim(&s.alice, &id), 10_000);
+
+    let result = s.client.try_withdraw(&s.bob, &1);
+    assert_eq!(result, Err(Ok(DividendError::InsufficientShares)));
+}
+
+#[test]
+fn test_unclaimed_funds_roll_over() {
+    let s = setup();
+    s.client.deposit(&s.alice, &3_000);
+    s.client.deposit(&s.bob, &1_000);
+    next_epoch(&s.env);
+    let first = s.client.distribute(&s.treasury, &s.revenue.address, &8_000);
+
+    next_epoch(&s.env);
+    s.client.claim(&s.alice, &first);
+    let result = s.client.try_rollover(&first);
+    assert_eq!(result, Err(Ok(DividendError::ClaimPeriodOpen)));
+
+    // Two epochs to claim, then Bob's share moves to a new distribution
+    next_epoch(&s.env);
+    next_epoch(&s.env);
+    let result = s.client.try_claim(&s.bob, &first);
+    assert_eq!(result, Err(Ok(DividendError::ClaimPeriodOver)));
+    let second = s.client.rollover(&first);
+    assert!(s.client.get_distribution(&first).unwrap().rolled_over);
+
+    let created = assert_standard_event(&s.env, topics::DISTRIBUTION_CREATED, |_| true);
+    let event: DistributionCreatedEvent = payload(&created);
+    assert_eq!(event.distribution_id, second);
+    assert_eq!(event.amount, 2_000);
+    assert_eq!(event.rolled_over_from, Some(first));
+    assert_eq!(
+        created.metadata::<u64>(&s.env, metadata_keys::EPOCH_KEY),
+        Some(s.client.current_epoch())
+    );
+
+    next_epoch(&s.env);
+    assert_eq!(s.client.claim(&s.bob, &second), 500);
+    let claimed = assert_standard_event(&s.env, topics::DIVIDEND_CLAIMED, |_| true);
+    let event: DividendClaimedEvent = payload(&claimed);
+    assert_eq!(event.user, s.bob);
+    assert_eq!(event.epoch, s.client.current_epoch() - 1);
+}
+
+#[test]
+fn test_share_history_is_pruned_past_the_claim_window() {
+    let s = setup();
+    s.client.deposit(&s.bob, &1_000);
+    for _ in 0..20 {
+        s.client.deposit(&s.alice, &100);
+        next_epoch(&s.env);
+    }
+    let id = s.client.distribute(&s.treasury, &s.revenue.address, &3_000);
+    s.client.withdraw(&s.alice, &1_000);
+    next_epoch(&s.env);
+    s.client.deposit(&s.alice, &100);
+
+    let (alice, total) = s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage().persistent();
+        let alice: Vec<Snapshot> = storage.get(&DataKey::Balances(s.alice.clone())).unwrap();
+        let total: Vec<Snapshot> = storage.get(&DataKey::TotalBalances).unwrap();
+        (alice.len(), total.len())
+    });
+    // Two claimable epochs, one spare, the current and the next
+    assert!(alice <= 5 && total <= 5);
+
+    // Alice kept 1_000 of her 2_000 through the distribution's epoch
+    assert_eq!(s.client.claim(&s.alice, &id), 1_500);
+    assert_eq!(s.client.claim(&s.bob, &id), 1_500);
+}
@@ -0,0 +1,347 @@
+//! Emission controller
+//!
+//! Mints the protocol token's emissions to the reward distributor, one
+//! amount per epoch. Each epoch emits `decay_bps` less than the previous
+//! one, down to a floor. Anyone (usually a keeper) calls
+//! [`EmissionController::distribute`] to mint the epochs that have started;
+//! epochs missed in the meantime are caught up, a bounded number per call.
+//!
+//! The admin is governance. It can adjust the curve, but only within
+//! bounds: the decay is capped at [`MAX_DECAY_BPS`], and the emission of
+//! the next epoch can move by at most [`MAX_ADJUSTMENT_BPS`] once per
+//! epoch. Changes apply from the next epoch not yet minted.
+//!
+//! The controller must be the token's admin (or minter) so it can mint.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::epoch::EpochClock;
+use shared::events::{EmissionCurveEvent, EmissionEvent, EventEmitter};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+/// Basis-point denominator for decay and adjustments
+pub const BPS_DENOMINATOR: u32 = 10_000;
+/// Highest decay per epoch governance can set (10%)
+pub const MAX_DECAY_BPS: u32 = 1_000;
+/// Largest change to the next epoch's emission in one adjustment (20%)
+pub const MAX_ADJUSTMENT_BPS: u32 = 2_000;
+/// Epochs minted by one `distribute` call
+pub const MAX_EPOCHS_PER_CALL: u32 = 52;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EmissionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    AdjustmentTooLarge = 5,
+    AdjustedThisEpoch = 6,
+    NothingToDistribute = 7,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Governance
+    pub admin: Address,
+    /// Token minted
+    pub token: Address,
+    /// Receiver of the emissions
+    pub distributor: Address,
+    pub clock: EpochClock,
+}
+
+/// The decay curve from the next epoch to be minted onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Curve {
+    /// Next epoch to be minted
+    pub next_epoch: u64,
+    /// Emission of `next_epoch`
+    pub emission: i128,
+    /// Decay per epoch in basis points
+    pub decay_bps: u32,
+    /// Emission never decays below this
+    pub floor: i128,
+    /// Emission minted so far
+    pub total_emitted: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Curve,
+    /// Amount minted for an epoch
+    Emitted(u64),
+    /// Epoch of the last emission adjustment
+    LastAdjustment,
+}
+
+#[contract]
+pub struct EmissionController;
+
+#[contractimpl]
+impl EmissionController {
+    /// Start emitting `initial_emission` in the epoch containing now, with
+    /// epochs of `epoch_length` seconds
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        distributor: Address,
+        epoch_length: u64,
+        initial_emission: i128,
+        decay_bps: u32,
+        floor: i128,
+    ) -> Result<(), EmissionError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(EmissionError::AlreadyInitialized);
+        }
+        let clock = EpochClock::new(env.ledger().timestamp(), epoch_length)
+            .ok_or(EmissionError::InvalidConfig)?;
+        if decay_bps > MAX_DECAY_BPS || floor < 0 || initial_emission < floor {
+            return Err(EmissionError::InvalidConfig);
+        }
+        let config = Config {
+            admin,
+            token,
+            distributor,
+            clock,
+        };
+        let curve = Curve {
+            next_epoch: 0,
+            emission: initial_emission,
+            decay_bps,
+            floor,
+            total_emitted: 0,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::Curve, &curve);
+        Ok(())
+    }
+
+    /// Mint the emissions of every epoch that has started but not been
+    /// minted, up to [`MAX_EPOCHS_PER_CALL`]. Permissionless, for keepers.
+    /// Returns the amount minted.
+    pub fn distribute(env: Env) -> Result<i128, EmissionError> {
+        let config = load_config(&env)?;
+        let mut curve = load_curve(&env)?;
+        let minted = catch_up(&env, &config, &mut curve);
+        if minted == 0 {
+            return Err(EmissionError::NothingToDistribute);
+        }
+        env.storage().instance().set(&DataKey::Curve, &curve);
+        Ok(minted)
+    }
+
+    /// Set the decay per epoch (governance only)
+    pub fn set_decay(env: Env, admin: Address, decay_bps: u32) -> Result<(), EmissionError> {
+        let (config, mut curve) = begin_adjustment(&env, &admin)?;
+        if decay_bps > MAX_DECAY_BPS {
+            return Err(EmissionError::InvalidConfig);
+        }
+        curve.decay_bps = decay_bps;
+        finish_adjustment(&env, &config, &curve);
+        Ok(())
+    }
+
+    /// Move the next epoch's emission to `emission`, at most
+    /// [`MAX_ADJUSTMENT_BPS`] away from the curve, once per epoch
+    /// (governance only)
+    pub fn set_emission(env: Env, admin: Address, emission: i128) -> Result<(), EmissionError> {
+        let (config, mut curve) = begin_adjustment(&env, &admin)?;
+        let epoch = config.clock.current_epoch(&env);
+        let last: Option<u64> = env.storage().instance().get(&DataKey::LastAdjustment);
+        if last == Some(epoch) {
+            return Err(EmissionError::AdjustedThisEpoch);
+        }
+        let max_change = curve.emission * MAX_ADJUSTMENT_BPS as i128 / BPS_DENOMINATOR as i128;
+        if (emission - curve.emission).abs() > max_change {
+            return Err(EmissionError::AdjustmentTooLarge);
+        }
+        if emission < curve.floor {
+            return Err(EmissionError::InvalidConfig);
+        }
+        curve.emission = emission;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastAdjustment, &epoch);
+        finish_adjustment(&env, &config, &curve);
+        Ok(())
+    }
+
+    /// Set the emission floor, which may not exceed the next epoch's
+    /// emission (governance only)
+    pub fn set_floor(env: Env, admin: Address, floor: i128) -> Result<(), EmissionError> {
+        let (config, mut curve) = begin_adjustment(&env, &admin)?;
+        if floor < 0 || floor > curve.emission {
+            return Err(EmissionError::InvalidConfig);
+        }
+        curve.floor = floor;
+        finish_adjustment(&env, &config, &curve);
+        Ok(())
+    }
+
+    /// Send future emissions to `distributor` (governance only)
+    pub fn set_distributor(
+        env: Env,
+        admin: Address,
+        distributor: Address,
+    ) -> Result<(), EmissionError> {
+        let (mut config, curve) = begin_adjustment(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Curve, &curve);
+        config.distributor = distributor;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ---- views ----
+
+    pub fn config(env: Env) -> Result<Config, EmissionError> {
+        load_config(&env)
+    }
+
+    pub fn curve(env: Env) -> Result<Curve, EmissionError> {
+        load_curve(&env)
+    }
+
+    pub fn current_epoch(env: Env) -> Result<u64, EmissionError> {
+        Ok(load_config(&env)?.clock.current_epoch(&env))
+    }
+
+    /// Amount minted for `epoch`, 0 if not minted yet
+    pub fn emitted(env: Env, epoch: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Emitted(epoch))
+            .unwrap_or(0)
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), EmissionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), EmissionError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Mint the started epochs, advancing `curve`. Returns the amount minted.
+fn catch_up(env: &Env, config: &Config, curve: &mut Curve) -> i128 {
+    let current = config.clock.current_epoch(env);
+    let token = token::StellarAssetClient::new(env, &config.token);
+    let mut minted = 0;
+    let mut epochs = 0;
+    while curve.next_epoch <= current && epochs < MAX_EPOCHS_PER_CALL {
+        let epoch = curve.next_epoch;
+        let amount = curve.emission;
+        if amount > 0 {
+            token.mint(&config.distributor, &amount);
+        }
+        curve.total_emitted += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Emitted(epoch), &amount);
+        EventEmitter::emission(
+            env,
+            EmissionEvent {
+                epoch,
+                amount,
+                distributor: config.distributor.clone(),
+                total_emitted: curve.total_emitted,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        let decayed =
+            amount * (BPS_DENOMINATOR - curve.decay_bps) as i128 / BPS_DENOMINATOR as i128;
+        curve.emission = decayed.max(curve.floor);
+        curve.next_epoch += 1;
+        minted += amount;
+        epochs += 1;
+    }
+    minted
+}
+
+/// Check governance and mint what is due under the old curve, so a change
+/// only affects epochs that have not started
+fn begin_adjustment(env: &Env, admin: &Address) -> Result<(Config, Curve), EmissionError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(EmissionError::Unauthorized);
+    }
+    let mut curve = load_curve(env)?;
+    catch_up(env, &config, &mut curve);
+    Ok((config, curve))
+}
+
+fn finish_adjustment(env: &Env, config: &Config, curve: &Curve) {
+    env.storage().instance().set(&DataKey::Curve, curve);
+    EventEmitter::emission_curve_changed(
+        env,
+        EmissionCurveEvent {
+            admin: config.admin.clone(),
+            epoch: curve.next_epoch,
+            emission: curve.emission,
+            decay_bps: curve.decay_bps,
+            floor: curve.floor,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, EmissionError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(EmissionError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, EmissionError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(EmissionError::NotInitialized)
+}
+
+fn load_curve(env: &Env) -> Result<Curve, EmissionError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Curve)
+        .ok_or(EmissionError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
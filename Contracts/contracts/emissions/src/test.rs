@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const EPOCH: u64 = 1_000;
+
+struct Setup {
+    env: Env,
+    client: EmissionControllerClient<'static>,
+    admin: Address,
+    distributor: Address,
+    token: TestToken<'static>,
+}
+
+fn setup(initial: i128, decay_bps: u32, floor: i128) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    let token = stellar_asset(&env);
+    let contract_id = env.register_contract(None, EmissionController);
+    token.admin_client.set_admin(&contract_id);
+    let client = EmissionControllerClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let distributor = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &token.address,
+        &distributor,
+        &EPOCH,
+        &initial,
+        &decay_bps,
+        &floor,
+    );
+    Setup {
+        env,
+        client,
+        admin,
+        distributor,
+        token,
+    }
+}
+
+fn advance_epochs(env: &Env, epochs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += epochs * EPOCH);
+}
+
+#[test]
+fn test_emissions_decay_per_epoch_and_catch_up() {
+    let s = setup(1_000, 200, 0);
+
+    assert_eq!(s.client.distribute(), 1_000);
+    assert_eq!(
+        s.client.try_distribute(),
+        Err(Ok(EmissionError::NothingToDistribute))
+    );
+
+    // Two missed epochs are minted by one call: 980, then 960
+    advance_epochs(&s.env, 2);
+    assert_eq!(s.client.distribute(), 1_940);
+    assert_eq!(s.token.balance(&s.distributor), 2_940);
+    assert_eq!(s.client.emitted(&1), 980);
+    assert_eq!(s.client.emitted(&2), 960);
+
+    let event: EmissionEvent = payload(&assert_standard_event(&s.env, topics::EMISSION, |_| true));
+    assert_eq!((event.epoch, event.amount), (2, 960));
+    assert_eq!(event.total_emitted, 2_940);
+    assert_eq!(s.client.curve().next_epoch, 3);
+}
+
+#[test]
+fn test_emission_stops_decaying_at_floor() {
+    let s = setup(1_000, 1_000, 850);
+    advance_epochs(&s.env, 3);
+    assert_eq!(s.client.distribute(), 1_000 + 900 + 850 + 850);
+    assert_eq!(s.client.curve().emission, 850);
+}
+
+#[test]
+fn test_governance_adjusts_curve_within_bounds() {
+    let s = setup(1_000, 200, 0);
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_set_decay(&outsider, &100),
+        Err(Ok(EmissionError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_set_decay(&s.admin, &(MAX_DECAY_BPS + 1)),
+        Err(Ok(EmissionError::InvalidConfig))
+    );
+
+    // Adjusting first mints the started epoch under the old curve
+    s.client.set_decay(&s.admin, &500);
+    assert_eq!(s.client.emitted(&0), 1_000);
+    assert_eq!(s.client.curve().emission, 980);
+
+    assert_eq!(
+        s.client.try_set_emission(&s.admin, &1_200),
+        Err(Ok(EmissionError::AdjustmentTooLarge))
+    );
+    s.client.set_emission(&s.admin, &1_176);
+    assert_eq!(
+        s.client.try_set_emission(&s.admin, &1_100),
+        Err(Ok(EmissionError::AdjustedThisEpoch))
+    );
+    let event: EmissionCurveEvent = payload(&assert_standard_event(
+        &s.env,
+        topics::EMISSION_CURVE,
+        |_| true,
+    ));
+    assert_eq!(
+        (event.epoch, event.emission, event.decay_bps),
+        (1, 1_176, 500)
+    );
+
+    advance_epochs(&s.env, 1);
+    assert_eq!(s.client.distribute(), 1_176);
+    assert_eq!(s.client.curve().emission, 1_117);
+    s.client.set_emission(&s.admin, &1_000);
+
+    let distributor = Address::generate(&s.env);
+    s.client.set_distributor(&s.admin, &distributor);
+    advance_epochs(&s.env, 1);
+    s.client.distribute();
+    assert_eq!(s.token.balance(&distributor), 1_000);
+}
@@ -0,0 +1,245 @@
+//! Deterministic contract factory
+//!
+//! Deploys AMM pools and vesting contracts from admin-registered wasm hashes.
+//! Addresses are derived from the deployer and a caller-chosen salt, so a
+//! frontend can compute a pool's address before it exists and nobody can
+//! squat another deployer's salt. Every deployment is recorded, letting
+//! clients enumerate pools on-chain.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{ContractDeployedEvent, EventEmitter};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Symbol, Val, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    WasmNotRegistered = 4,
+    AlreadyDeployed = 5,
+}
+
+/// Kinds of contract the factory can deploy
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContractKind {
+    AmmPool,
+    Vesting,
+}
+
+impl ContractKind {
+    pub fn name(&self) -> Symbol {
+        match self {
+            ContractKind::AmmPool => symbol_short!("amm_pool"),
+            ContractKind::Vesting => symbol_short!("vesting"),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    WasmHash(ContractKind),
+    Deployments,
+    ByKind(ContractKind),
+    Deployment(Address),
+}
+
+/// Registry entry for a deployed contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub address: Address,
+    pub kind: ContractKind,
+    pub deployer: Address,
+    pub salt: BytesN<32>,
+    pub wasm_hash: BytesN<32>,
+    pub deployed_at: u64,
+}
+
+#[contract]
+pub struct FactoryContract;
+
+#[contractimpl]
+impl FactoryContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), FactoryError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Register the wasm deployed for `kind` (admin only). Existing
+    /// deployments keep the code they were deployed with.
+    pub fn set_wasm_hash(
+        env: Env,
+        admin: Address,
+        kind: ContractKind,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::WasmHash(kind), &wasm_hash);
+        Ok(())
+    }
+
+    pub fn get_wasm_hash(env: Env, kind: ContractKind) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::WasmHash(kind))
+    }
+
+    /// Deploy a `kind` contract at the address derived from `deployer` and
+    /// `salt`, then call `init_fn(init_args)` on it.
+    pub fn deploy(
+        env: Env,
+        deployer: Address,
+        kind: ContractKind,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Result<Address, FactoryError> {
+        deployer.require_auth();
+        let wasm_hash =
+            Self::get_wasm_hash(env.clone(), kind).ok_or(FactoryError::WasmNotRegistered)?;
+
+        let predicted = Self::deployed_address(env.clone(), deployer.clone(), salt.clone());
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Deployment(predicted))
+        {
+            return Err(FactoryError::AlreadyDeployed);
+        }
+
+        let address = env
+            .deployer()
+            .with_current_contract(derive_salt(&env, &deployer, &salt))
+            .deploy(wasm_hash.clone());
+        let _: Val = env.invoke_contract(&address, &init_fn, init_args.clone());
+
+        let deployment = Deployment {
+            address: address.clone(),
+            kind,
+            deployer: deployer.clone(),
+            salt,
+            wasm_hash: wasm_hash.clone(),
+            deployed_at: env.ledger().timestamp(),
+        };
+        record(&env, &deployment);
+
+        EventEmitter::contract_deployed(
+            &env,
+            ContractDeployedEvent {
+                address: address.clone(),
+                kind: kind.name(),
+                deployer,
+                wasm_hash,
+                init_fn,
+                init_args,
+                timestamp: deployment.deployed_at,
+            },
+        );
+
+        Ok(address)
+    }
+
+    /// Address `deploy` will use for `deployer` and `salt`
+    pub fn deployed_address(env: Env, deployer: Address, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(derive_salt(&env, &deployer, &salt))
+            .deployed_address()
+    }
+
+    pub fn get_deployment(env: Env, address: Address) -> Option<Deployment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deployment(address))
+    }
+
+    /// Every contract deployed by the factory, oldest first
+    pub fn get_deployments(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deployments)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_deployments_by_kind(env: Env, kind: ContractKind) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ByKind(kind))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), FactoryError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), FactoryError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), FactoryError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(FactoryError::NotInitialized)?;
+    if stored != *admin {
+        return Err(FactoryError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Salt actually used for deployment: `sha256(deployer || salt)`, which
+/// namespaces salts per deployer
+fn derive_salt(env: &Env, deployer: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&deployer.clone().to_xdr(env));
+    preimage.append(&Bytes::from_array(env, &salt.to_array()));
+    env.crypto().sha256(&preimage)
+}
+
+fn record(env: &Env, deployment: &Deployment) {
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::Deployment(deployment.address.clone()), deployment);
+
+    let mut all = FactoryContract::get_deployments(env.clone());
+    all.push_back(deployment.address.clone());
+    storage.set(&DataKey::Deployments, &all);
+
+    let mut by_kind = FactoryContract::get_deployments_by_kind(env.clone(), deployment.kind);
+    by_kind.push_back(deployment.address.clone());
+    storage.set(&DataKey::ByKind(deployment.kind), &by_kind);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,59 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, Symbol};
+
+fn setup() -> (Env, FactoryContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FactoryContract);
+    let client = FactoryContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, client, admin)
+}
+
+#[test]
+fn test_wasm_hash_registration_is_admin_only() {
+    let (env, client, admin) = setup();
+    let hash = BytesN::from_array(&env, &[1; 32]);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_set_wasm_hash(&stranger, &ContractKind::AmmPool, &hash);
+    assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+    client.set_wasm_hash(&admin, &ContractKind::AmmPool, &hash);
+    assert_eq!(client.get_wasm_hash(&ContractKind::AmmPool), Some(hash));
+    assert_eq!(client.get_wasm_hash(&ContractKind::Vesting), None);
+}
+
+#[test]
+fn test_deployed_address_is_deterministic_per_deployer() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[7; 32]);
+
+    let first = client.deployed_address(&alice, &salt);
+    assert_eq!(client.deployed_address(&alice, &salt), first);
+    assert_ne!(client.deployed_address(&bob, &salt), first);
+    assert_ne!(
+        client.deployed_address(&alice, &BytesN::from_array(&env, &[8; 32])),
+        first
+    );
+}
+
+#[test]
+fn test_deploy_requires_registered_wasm() {
+    let (env, client, _) = setup();
+    let deployer = Address::generate(&env);
+    let result = client.try_deploy(
+        &deployer,
+        &ContractKind::Vesting,
+        &BytesN::from_array(&env, &[7; 32]),
+        &Symbol::new(&env, "initialize"),
+        &vec![&env],
+    );
+    assert_eq!(result, Err(Ok(FactoryError::WasmNotRegistered)));
+    assert!(client.get_deployments().is_empty());
+}
@@ -0,0 +1,329 @@
+//! Gauge controller
+//!
+//! Each gauge is a reward pool (a staking pool or an AMM pair) that
+//! receives a share of protocol emissions. Vote-escrow holders split their
+//! voting power across gauges once per weekly epoch; power is read at the
+//! start of the epoch so tokens locked mid-epoch only count from the next
+//! one. When an epoch ends anyone can finalize it, turning its tallies into
+//! the basis-point weights reward distributors apply for the following
+//! epoch. An epoch without votes keeps the previous weights.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::epoch::EpochClock;
+use shared::events::{EventEmitter, GaugeEpochEvent, GaugeVoteEvent};
+use shared::voting::VotingPowerClient;
+
+pub use shared::voting::UserVote;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec};
+
+/// Basis-point denominator for vote and gauge weights
+pub const BPS_DENOMINATOR: u32 = 10_000;
+/// Upper bound on gauges so finalization stays within one transaction
+pub const MAX_GAUGES: u32 = 50;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GaugeError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    GaugeExists = 4,
+    GaugeNotFound = 5,
+    TooManyGauges = 6,
+    InvalidWeight = 7,
+    NoVotingPower = 8,
+    AlreadyFinalized = 9,
+}
+
+/// A reward pool emissions can be directed to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GaugeInfo {
+    /// Contract distributing the rewards
+    pub target: Address,
+    /// Pool within `target`
+    pub pool_id: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    VotingEscrow,
+    Gauges,
+    GaugeId(Address, u32),
+    Votes(u64, u32),
+    TotalVotes(u64),
+    UserVotes(Address, u64),
+    Weights,
+    FinalizedEpoch,
+}
+
+#[contract]
+pub struct GaugeController;
+
+#[contractimpl]
+impl GaugeController {
+    /// `voting_escrow` supplies voting power
+    pub fn initialize(env: Env, admin: Address, voting_escrow: Address) -> Result<(), GaugeError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(GaugeError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::VotingEscrow, &voting_escrow);
+        env.storage()
+            .instance()
+            .set(&DataKey::FinalizedEpoch, &current_epoch(&env));
+        Ok(())
+    }
+
+    /// Register pool `pool_id` of `target` as a gauge. Returns its id.
+    pub fn add_gauge(
+        env: Env,
+        admin: Address,
+        target: Address,
+        pool_id: u32,
+    ) -> Result<u32, GaugeError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::GaugeId(target.clone(), pool_id);
+        if env.storage().persistent().has(&key) {
+            return Err(GaugeError::GaugeExists);
+        }
+        let mut gauges = Self::gauges(env.clone());
+        if gauges.len() >= MAX_GAUGES {
+            return Err(GaugeError::TooManyGauges);
+        }
+        let gauge_id = gauges.len();
+        gauges.push_back(GaugeInfo { target, pool_id });
+        env.storage().instance().set(&DataKey::Gauges, &gauges);
+        env.storage().persistent().set(&key, &gauge_id);
+        Ok(gauge_id)
+    }
+
+    /// Give `weight_bps` of the caller's voting power to a gauge for the
+    /// current epoch, replacing any earlier vote for it. A user's weights
+    /// across gauges may not exceed 100%.
+    pub fn vote(
+        env: Env,
+        user: Address,
+        gauge_id: u32,
+        weight_bps: u32,
+    ) -> Result<i128, GaugeError> {
+        user.require_auth();
+        if gauge_id >= Self::gauges(env.clone()).len() {
+            return Err(GaugeError::GaugeNotFound);
+        }
+        let epoch = current_epoch(&env);
+        let balance = VotingPowerClient::new(&env, &voting_escrow(&env)?)
+            .balance_of_at(&user, &EpochClock::WEEKLY.epoch_start(epoch));
+        if balance <= 0 {
+            return Err(GaugeError::NoVotingPower);
+        }
+
+        let mut votes = Self::user_votes(env.clone(), user.clone(), epoch);
+        let mut used = weight_bps;
+        let mut previous = None;
+        for (i, vote) in votes.iter().enumerate() {
+            if vote.gauge_id == gauge_id {
+                previous = Some((i as u32, vote.power));
+            } else {
+                used += vote.weight_bps;
+            }
+        }
+        if used > BPS_DENOMINATOR {
+            return Err(GaugeError::InvalidWeight);
+        }
+
+        let power = balance * weight_bps as i128 / BPS_DENOMINATOR as i128;
+        let old_power = match previous {
+            Some((i, old_power)) => {
+                votes.remove(i);
+                old_power
+            }
+            None => 0,
+        };
+        if weight_bps > 0 {
+            votes.push_back(UserVote {
+                gauge_id,
+                weight_bps,
+                power,
+            });
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserVotes(user.clone(), epoch), &votes);
+        add_votes(&env, &DataKey::Votes(epoch, gauge_id), power - old_power);
+        add_votes(&env, &DataKey::TotalVotes(epoch), power - old_power);
+
+        EventEmitter::gauge_voted(
+            &env,
+            GaugeVoteEvent {
+                user,
+                gauge_id,
+                epoch,
+                weight_bps,
+                power,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(power)
+    }
+
+    /// Turn the previous epoch's votes into the weights for the current
+    /// epoch. Permissionless, for keepers.
+    pub fn finalize_epoch(env: Env) -> Result<Vec<u32>, GaugeError> {
+        voting_escrow(&env)?;
+        let epoch = current_epoch(&env);
+        if Self::finalized_epoch(env.clone()) >= epoch {
+            return Err(GaugeError::AlreadyFinalized);
+        }
+
+        let tallied = epoch - 1;
+        let total = Self::total_votes(env.clone(), tallied);
+        let mut weights = Self::weights(env.clone());
+        if total > 0 {
+            weights = Vec::new(&env);
+            for gauge_id in 0..Self::gauges(env.clone()).len() {
+                let votes = Self::gauge_votes(env.clone(), tallied, gauge_id);
+                weights.push_back((votes * BPS_DENOMINATOR as i128 / total) as u32);
+            }
+            env.storage().instance().set(&DataKey::Weights, &weights);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FinalizedEpoch, &epoch);
+
+        EventEmitter::gauge_epoch(
+            &env,
+            GaugeEpochEvent {
+                epoch,
+                total_votes: total,
+                weights: weights.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(weights)
+    }
+
+    // ---- views ----
+
+    /// Weight of pool `pool_id` of `target` in the last finalized epoch
+    pub fn gauge_weight(env: Env, target: Address, pool_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::GaugeId(target, pool_id))
+            .and_then(|gauge_id| Self::weights(env).get(gauge_id))
+            .unwrap_or(0)
+    }
+
+    /// Finalized weights, indexed by gauge id
+    pub fn weights(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Weights)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn gauges(env: Env) -> Vec<GaugeInfo> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Gauges)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn current_epoch(env: Env) -> u64 {
+        current_epoch(&env)
+    }
+
+    pub fn finalized_epoch(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FinalizedEpoch)
+            .unwrap_or(0)
+    }
+
+    pub fn gauge_votes(env: Env, epoch: u64, gauge_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Votes(epoch, gauge_id))
+            .unwrap_or(0)
+    }
+
+    pub fn total_votes(env: Env, epoch: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalVotes(epoch))
+            .unwrap_or(0)
+    }
+
+    pub fn user_votes(env: Env, user: Address, epoch: u64) -> Vec<UserVote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserVotes(user, epoch))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), GaugeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), GaugeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), GaugeError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(GaugeError::NotInitialized)?;
+    if stored != *admin {
+        return Err(GaugeError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn voting_escrow(env: &Env) -> Result<Address, GaugeError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::VotingEscrow)
+        .ok_or(GaugeError::NotInitialized)
+}
+
+fn current_epoch(env: &Env) -> u64 {
+    EpochClock::WEEKLY.current_epoch(env)
+}
+
+fn add_votes(env: &Env, key: &DataKey, delta: i128) {
+    let votes: i128 = env.storage().persistent().get(key).unwrap_or(0);
+    env.storage().persistent().set(key, &(votes + delta));
+}
+
+#[cfg(test)]
+mod test;
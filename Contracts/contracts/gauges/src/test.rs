@@ -0,0 +1,168 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use shared::voting::WEEK;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::StellarAssetClient;
+use test_utils::{assert_standard_event, payload};
+use voting_escrow::{VotingEscrow, VotingEscrowClient};
+
+const START: u64 = 100 * WEEK;
+const LOCK: i128 = 1_000_000;
+
+struct Setup {
+    env: Env,
+    client: GaugeControllerClient<'static>,
+    ve: VotingEscrowClient<'static>,
+    minter: StellarAssetClient<'static>,
+    admin: Address,
+    pools: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let minter = StellarAssetClient::new(&env, &token_id);
+    let ve_id = env.register_contract(None, VotingEscrow);
+    let ve = VotingEscrowClient::new(&env, &ve_id);
+    ve.initialize(&token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    minter.mint(&alice, &LOCK);
+    minter.mint(&bob, &(3 * LOCK));
+    ve.create_lock(&alice, &LOCK, &(START + 52 * WEEK));
+    ve.create_lock(&bob, &(3 * LOCK), &(START + 52 * WEEK));
+
+    let contract_id = env.register_contract(None, GaugeController);
+    let client = GaugeControllerClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &ve_id);
+    let pools = Address::generate(&env);
+    client.add_gauge(&admin, &pools, &0);
+    client.add_gauge(&admin, &pools, &1);
+
+    Setup {
+        env,
+        client,
+        ve,
+        minter,
+        admin,
+        pools,
+        alice,
+        bob,
+    }
+}
+
+fn next_epoch(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += WEEK);
+}
+
+#[test]
+fn test_votes_finalize_into_weights() {
+    let s = setup();
+    s.client.vote(&s.alice, &0, &10_000);
+    s.client.vote(&s.bob, &0, &5_000);
+    s.client.vote(&s.bob, &1, &5_000);
+
+    let captured = assert_standard_event(&s.env, topics::GAUGE_VOTED, |_| true);
+    let vote: GaugeVoteEvent = payload(&captured);
+    assert_eq!(vote.gauge_id, 1);
+    assert_eq!(vote.epoch, START / WEEK);
+
+    // Weights only change once the voting epoch is over
+    assert_eq!(s.client.gauge_weight(&s.pools, &0), 0);
+    next_epoch(&s.env);
+    let weights = s.client.finalize_epoch();
+    // Alice has a quarter of the power: 25% + 75% / 2 to gauge 0
+    assert!((weights.get(0).unwrap() as i32 - 6_250).abs() <= 1);
+    assert!((weights.get(1).unwrap() as i32 - 3_750).abs() <= 1);
+    assert_eq!(s.client.gauge_weight(&s.pools, &0), weights.get(0).unwrap());
+    assert_eq!(s.client.gauge_weight(&Address::generate(&s.env), &0), 0);
+
+    let captured = assert_standard_event(&s.env, topics::GAUGE_EPOCH, |_| true);
+    let rollover: GaugeEpochEvent = payload(&captured);
+    assert_eq!(rollover.epoch, START / WEEK + 1);
+    assert_eq!(rollover.weights, weights);
+}
+
+#[test]
+fn test_revote_replaces_and_weights_are_capped() {
+    let s = setup();
+    let epoch = s.client.current_epoch();
+    let full = s.client.vote(&s.alice, &0, &6_000);
+    let reduced = s.client.vote(&s.alice, &0, &2_000);
+    assert!(reduced < full);
+    assert_eq!(s.client.gauge_votes(&epoch, &0), reduced);
+    assert_eq!(s.client.user_votes(&s.alice, &epoch).len(), 1);
+
+    s.client.vote(&s.alice, &1, &8_000);
+    assert_eq!(
+        s.client.try_vote(&s.alice, &0, &2_001),
+        Err(Ok(GaugeError::InvalidWeight))
+    );
+    assert_eq!(
+        s.client.try_vote(&s.alice, &2, &1_000),
+        Err(Ok(GaugeError::GaugeNotFound))
+    );
+    assert_eq!(
+        s.client.total_votes(&epoch),
+        s.client.gauge_votes(&epoch, &0) + s.client.gauge_votes(&epoch, &1)
+    );
+}
+
+#[test]
+fn test_epoch_without_votes_keeps_weights() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_finalize_epoch(),
+        Err(Ok(GaugeError::AlreadyFinalized))
+    );
+    s.client.vote(&s.alice, &1, &10_000);
+    next_epoch(&s.env);
+    s.client.finalize_epoch();
+    assert_eq!(s.client.gauge_weight(&s.pools, &1), 10_000);
+
+    next_epoch(&s.env);
+    s.client.finalize_epoch();
+    assert_eq!(s.client.gauge_weight(&s.pools, &1), 10_000);
+    assert_eq!(s.client.finalized_epoch(), START / WEEK + 2);
+}
+
+#[test]
+fn test_power_is_snapshotted_at_epoch_start() {
+    let s = setup();
+    let carol = Address::generate(&s.env);
+    s.env.ledger().with_mut(|li| li.timestamp += 1);
+    s.minter.mint(&carol, &LOCK);
+    s.ve.create_lock(&carol, &LOCK, &(START + 52 * WEEK));
+    assert_eq!(
+        s.client.try_vote(&carol, &0, &10_000),
+        Err(Ok(GaugeError::NoVotingPower))
+    );
+
+    next_epoch(&s.env);
+    assert!(s.client.vote(&carol, &0, &10_000) > 0);
+}
+
+#[test]
+fn test_only_admin_adds_gauges() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_add_gauge(&s.alice, &s.pools, &2),
+        Err(Ok(GaugeError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_add_gauge(&s.admin, &s.pools, &0),
+        Err(Ok(GaugeError::GaugeExists))
+    );
+    assert_eq!(s.client.add_gauge(&s.admin, &s.pools, &2), 2);
+    assert_eq!(s.client.gauges().len(), 3);
+}
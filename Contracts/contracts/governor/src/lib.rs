@@ -0,0 +1,423 @@
+//! Token-weighted governor
+//!
+//! Holders vote on proposals with whichever registered voting asset the
+//! proposal names: a plain governance token, a vote-escrow lock or a
+//! staking position. The admin registers each asset with a weight in basis
+//! points, so a vote's power is the raw balance the asset reports scaled by
+//! that weight; the weight is fixed on the proposal when it is created.
+//!
+//! Power is read as of the proposal's creation, so a balance moved between
+//! accounts afterwards can't vote again: vote-escrow power at its creation
+//! time, token balances and stakes at the end of the ledger before it.
+//! Token assets must keep balance checkpoints (`balance_at`).
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{namespaces, EventEmitter, VoteCastEvent};
+use shared::governance::ProposalStatus;
+use shared::pagination::Pagination;
+use shared::voting::{BalanceHistoryClient, StakedBalanceClient, VotingPowerClient, VotingSource};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+/// Basis-point denominator for asset weights
+pub const BPS_DENOMINATOR: u32 = 10_000;
+/// Upper bound on registered voting assets
+pub const MAX_ASSETS: u32 = 20;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernorError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidWeight = 4,
+    TooManyAssets = 5,
+    AssetNotFound = 6,
+    AssetDisabled = 7,
+    ProposalNotFound = 8,
+    VotingClosed = 9,
+    VotingOpen = 10,
+    AlreadyVoted = 11,
+    NoVotingPower = 12,
+    InvalidConfig = 13,
+    /// The token no longer keeps history as old as the proposal snapshot
+    SnapshotUnavailable = 14,
+}
+
+/// An asset proposals can count votes in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingAsset {
+    /// How voting power is read from `contract`
+    pub source: VotingSource,
+    /// Token, vote-escrow or staking contract
+    pub contract: Address,
+    /// Share of the raw balance counted as votes; zero disables the asset
+    /// for new proposals
+    pub weight_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernorConfig {
+    /// How long proposals are open for voting
+    pub voting_period: u64,
+    /// Weighted votes that must be cast for a proposal to pass
+    pub quorum: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub description: Symbol,
+    /// Registered asset this proposal counts
+    pub asset_id: u32,
+    /// Asset as registered when the proposal was created
+    pub asset: VotingAsset,
+    /// Time vote-escrow power is read at
+    pub snapshot: u64,
+    /// Ledger token balances and stakes are read at the end of
+    pub snapshot_ledger: u32,
+    pub vote_end: u64,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    /// `Pending` while voting, then `Approved` or `Rejected` once finalized
+    pub status: ProposalStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Config,
+    Assets,
+    NextProposalId,
+    Proposal(u64),
+    Voted(u64, Address),
+}
+
+#[contract]
+pub struct Governor;
+
+#[contractimpl]
+impl Governor {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        voting_period: u64,
+        quorum: i128,
+    ) -> Result<(), GovernorError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(GovernorError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if voting_period == 0 || quorum < 0 {
+            return Err(GovernorError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &GovernorConfig {
+                voting_period,
+                quorum,
+            },
+        );
+        EventEmitter::init(&env, namespaces::GOVERNANCE);
+        Ok(())
+    }
+
+    /// Register `contract` as a voting asset read through `source`, counted
+    /// at `weight_bps` (admin only). Returns its id.
+    pub fn register_asset(
+        env: Env,
+        admin: Address,
+        source: VotingSource,
+        contract: Address,
+        weight_bps: u32,
+    ) -> Result<u32, GovernorError> {
+        require_admin(&env, &admin)?;
+        if weight_bps == 0 || weight_bps > BPS_DENOMINATOR {
+            return Err(GovernorError::InvalidWeight);
+        }
+        let mut assets = Self::assets(env.clone());
+        if assets.len() >= MAX_ASSETS {
+            return Err(GovernorError::TooManyAssets);
+        }
+        let asset_id = assets.len();
+        assets.push_back(VotingAsset {
+            source,
+            contract,
+            weight_bps,
+        });
+        env.storage().instance().set(&DataKey::Assets, &assets);
+        Ok(asset_id)
+    }
+
+    /// Change an asset's weight for proposals created from now on; zero
+    /// stops new proposals from using it (admin only)
+    pub fn set_asset_weight(
+        env: Env,
+        admin: Address,
+        asset_id: u32,
+        weight_bps: u32,
+    ) -> Result<(), GovernorError> {
+        require_admin(&env, &admin)?;
+        if weight_bps > BPS_DENOMINATOR {
+            return Err(GovernorError::InvalidWeight);
+        }
+        let mut assets = Self::assets(env.clone());
+        let mut asset = assets.get(asset_id).ok_or(GovernorError::AssetNotFound)?;
+        asset.weight_bps = weight_bps;
+        assets.set(asset_id, asset);
+        env.storage().instance().set(&DataKey::Assets, &assets);
+        Ok(())
+    }
+
+    /// Open a proposal counting votes in asset `asset_id`. The proposer
+    /// must hold voting power in that asset. Returns the proposal id.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        description: Symbol,
+        asset_id: u32,
+    ) -> Result<u64, GovernorError> {
+        proposer.require_auth();
+        let config = load_config(&env)?;
+        let asset = Self::assets(env.clone())
+            .get(asset_id)
+            .ok_or(GovernorError::AssetNotFound)?;
+        if asset.weight_bps == 0 {
+            return Err(GovernorError::AssetDisabled);
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1);
+        let now = env.ledger().timestamp();
+        // The current ledger's balances can still change, so count the
+        // last closed one
+        let proposal = Proposal {
+            id,
+            proposer: proposer.clone(),
+            description,
+            asset_id,
+            asset,
+            snapshot: now,
+            snapshot_ledger: env.ledger().sequence().saturating_sub(1),
+            vote_end: now + config.voting_period,
+            for_votes: 0,
+            against_votes: 0,
+            status: ProposalStatus::Pending,
+        };
+        if raw_power(&env, &proposal, &proposer)? <= 0 {
+            return Err(GovernorError::NoVotingPower);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(id + 1));
+        save_proposal(&env, &proposal);
+        Ok(id)
+    }
+
+    /// Vote on an open proposal with the caller's power in the proposal's
+    /// asset. Returns the weighted power counted.
+    pub fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        support: bool,
+    ) -> Result<i128, GovernorError> {
+        voter.require_auth();
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        let now = env.ledger().timestamp();
+        if proposal.status != ProposalStatus::Pending || now >= proposal.vote_end {
+            return Err(GovernorError::VotingClosed);
+        }
+        let voted_key = DataKey::Voted(proposal_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            return Err(GovernorError::AlreadyVoted);
+        }
+
+        let raw_power = raw_power(&env, &proposal, &voter)?;
+        let power = raw_power * proposal.asset.weight_bps as i128 / BPS_DENOMINATOR as i128;
+        if power <= 0 {
+            return Err(GovernorError::NoVotingPower);
+        }
+
+        if support {
+            proposal.for_votes += power;
+        } else {
+            proposal.against_votes += power;
+        }
+        save_proposal(&env, &proposal);
+        env.storage().persistent().set(&voted_key, &support);
+
+        EventEmitter::vote_cast(
+            &env,
+            VoteCastEvent {
+                proposal_id,
+                voter,
+                support,
+                asset_id: proposal.asset_id,
+                asset: proposal.asset.contract,
+                source: proposal.asset.source,
+                raw_power,
+                power,
+                timestamp: now,
+            },
+        );
+        Ok(power)
+    }
+
+    /// Close voting once the period has ended. The proposal passes with a
+    /// majority in favour and at least the quorum cast.
+    pub fn finalize(env: Env, proposal_id: u64) -> Result<ProposalStatus, GovernorError> {
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Pending
+            || env.ledger().timestamp() < proposal.vote_end
+        {
+            return Err(GovernorError::VotingOpen);
+        }
+        let quorum = load_config(&env)?.quorum;
+        let cast = proposal.for_votes + proposal.against_votes;
+        proposal.status = if proposal.for_votes > proposal.against_votes && cast >= quorum {
+            ProposalStatus::Approved
+        } else {
+            ProposalStatus::Rejected
+        };
+        save_proposal(&env, &proposal);
+        Ok(proposal.status)
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+    }
+
+    /// One page of proposals in creation order, and the cursor of the next.
+    /// Cursor `n` is proposal id `n + 1`.
+    pub fn proposals(env: Env, page: Pagination) -> (Vec<Proposal>, Option<u32>) {
+        let next_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1);
+        let count = u32::try_from(next_id - 1).unwrap_or(u32::MAX);
+        let (start, end, next) = page.bounds(count);
+        let mut proposals = Vec::new(&env);
+        for index in start..end {
+            if let Ok(proposal) = load_proposal(&env, index as u64 + 1) {
+                proposals.push_back(proposal);
+            }
+        }
+        (proposals, next)
+    }
+
+    /// Registered voting assets, indexed by asset id
+    pub fn assets(env: Env) -> Vec<VotingAsset> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Assets)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Weighted power `voter` would cast on `proposal_id` now
+    pub fn voting_power(env: Env, voter: Address, proposal_id: u64) -> Result<i128, GovernorError> {
+        let proposal = load_proposal(&env, proposal_id)?;
+        let raw_power = raw_power(&env, &proposal, &voter)?;
+        Ok(raw_power * proposal.asset.weight_bps as i128 / BPS_DENOMINATOR as i128)
+    }
+
+    pub fn get_config(env: Env) -> Result<GovernorConfig, GovernorError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), GovernorError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), GovernorError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Unweighted power of `voter` in the proposal's asset as of its snapshot
+fn raw_power(env: &Env, proposal: &Proposal, voter: &Address) -> Result<i128, GovernorError> {
+    let contract = &proposal.asset.contract;
+    Ok(match proposal.asset.source {
+        VotingSource::Token => BalanceHistoryClient::new(env, contract)
+            .try_balance_at(voter, &proposal.snapshot_ledger)
+            .ok()
+            .and_then(Result::ok)
+            .ok_or(GovernorError::SnapshotUnavailable)?,
+        VotingSource::VoteEscrow => {
+            VotingPowerClient::new(env, contract).balance_of_at(voter, &proposal.snapshot)
+        }
+        VotingSource::Staking => StakedBalanceClient::new(env, contract)
+            .staked_balance_at(voter, &proposal.snapshot_ledger),
+    })
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), GovernorError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(GovernorError::NotInitialized)?;
+    if stored != *admin {
+        return Err(GovernorError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_config(env: &Env) -> Result<GovernorConfig, GovernorError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(GovernorError::NotInitialized)
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, GovernorError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .ok_or(GovernorError::ProposalNotFound)
+}
+
+fn save_proposal(env: &Env, proposal: &Proposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(proposal.id), proposal);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,271 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use shared::voting::WEEK;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, String, Symbol};
+use stellara_staking_rewards::{StakingRewardsContract, StakingRewardsContractClient};
+use test_utils::{assert_standard_event, payload};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+use voting_escrow::{VotingEscrow, VotingEscrowClient};
+
+const START: u64 = 100 * WEEK;
+const PERIOD: u64 = 3 * 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: GovernorClient<'static>,
+    admin: Address,
+    token: Address,
+    ve: Address,
+    staking: Address,
+    /// Holds only plain tokens
+    alice: Address,
+    /// Holds only a vote-escrow lock
+    bob: Address,
+    /// Holds only a stake
+    carol: Address,
+}
+
+const TOKEN_ASSET: u32 = 0;
+const VE_ASSET: u32 = 1;
+const STAKING_ASSET: u32 = 2;
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let token = env.register_contract(None, TokenContract);
+    let minter = TokenContractClient::new(&env, &token);
+    minter.initialize(
+        &Address::generate(&env),
+        &TokenMetadata {
+            name: String::from_str(&env, "Stellara"),
+            symbol: String::from_str(&env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(&env, ""),
+        },
+        &false,
+    );
+
+    let ve = env.register_contract(None, VotingEscrow);
+    VotingEscrowClient::new(&env, &ve).initialize(&token);
+    let staking = env.register_contract(None, StakingRewardsContract);
+    let staking_admin = Address::generate(&env);
+    StakingRewardsContractClient::new(&env, &staking).initialize(&staking_admin, &token, &token);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    minter.mint(&alice, &1_000);
+    minter.mint(&bob, &4_000);
+    minter.mint(&carol, &2_000);
+    // A four-year lock counts one vote per token
+    VotingEscrowClient::new(&env, &ve).create_lock(&bob, &4_000, &(START + 208 * WEEK));
+    StakingRewardsContractClient::new(&env, &staking).stake(&carol, &1_500, &0);
+    StakingRewardsContractClient::new(&env, &staking).stake_position(&carol, &500, &1);
+
+    let contract_id = env.register_contract(None, Governor);
+    let client = GovernorClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &PERIOD, &1_000);
+    client.register_asset(&admin, &VotingSource::Token, &token, &10_000);
+    client.register_asset(&admin, &VotingSource::VoteEscrow, &ve, &10_000);
+    client.register_asset(&admin, &VotingSource::Staking, &staking, &5_000);
+    // Proposals count balances as of the last closed ledger
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+
+    Setup {
+        env,
+        client,
+        admin,
+        token,
+        ve,
+        staking,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+#[test]
+fn test_proposal_counts_only_its_asset() {
+    let s = setup();
+
+    let result = s
+        .client
+        .try_propose(&s.alice, &symbol_short!("ve_only"), &VE_ASSET);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+
+    let id = s
+        .client
+        .propose(&s.bob, &symbol_short!("ve_only"), &VE_ASSET);
+    let result = s.client.try_cast_vote(&s.alice, &id, &true);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+    let power = s.client.cast_vote(&s.bob, &id, &true);
+    assert!(power > 3_900 && power <= 4_000);
+    let result = s.client.try_cast_vote(&s.bob, &id, &false);
+    assert_eq!(result, Err(Ok(GovernorError::AlreadyVoted)));
+
+    let id = s
+        .client
+        .propose(&s.alice, &symbol_short!("tok_only"), &TOKEN_ASSET);
+    assert_eq!(s.client.cast_vote(&s.alice, &id, &true), 1_000);
+    let result = s.client.try_cast_vote(&s.bob, &id, &true);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+
+    // Staked principal across the stake and position, at half weight
+    let id = s
+        .client
+        .propose(&s.carol, &symbol_short!("stk_only"), &STAKING_ASSET);
+    assert_eq!(s.client.cast_vote(&s.carol, &id, &false), 1_000);
+    assert_eq!(s.client.get_proposal(&id).unwrap().against_votes, 1_000);
+}
+
+#[test]
+fn test_proposals_are_paginated() {
+    let s = setup();
+    for _ in 0..3 {
+        s.client
+            .propose(&s.alice, &symbol_short!("prop"), &TOKEN_ASSET);
+    }
+
+    let (page, next) = s.client.proposals(&Pagination::first(2));
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).id, 1);
+    assert_eq!(next, Some(2));
+    let (page, next) = s.client.proposals(&Pagination {
+        cursor: 2,
+        limit: 2,
+    });
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).id, 3);
+    assert_eq!(next, None);
+}
+
+#[test]
+fn test_finalize_applies_majority_and_quorum() {
+    let s = setup();
+    TokenContractClient::new(&s.env, &s.token).mint(&s.carol, &999);
+    s.env.ledger().with_mut(|li| li.sequence_number += 1);
+    let passed = s.client.propose(&s.bob, &symbol_short!("pass"), &VE_ASSET);
+    let short = s
+        .client
+        .propose(&s.alice, &symbol_short!("short"), &TOKEN_ASSET);
+    s.client.cast_vote(&s.bob, &passed, &true);
+    // 999 votes in favour is below the 1_000 quorum
+    s.client.cast_vote(&s.carol, &short, &true);
+
+    let result = s.client.try_finalize(&passed);
+    assert_eq!(result, Err(Ok(GovernorError::VotingOpen)));
+
+    s.env.ledger().with_mut(|li| li.timestamp += PERIOD);
+    let result = s.client.try_cast_vote(&s.alice, &short, &true);
+    assert_eq!(result, Err(Ok(GovernorError::VotingClosed)));
+    assert_eq!(s.client.finalize(&passed), ProposalStatus::Approved);
+    assert_eq!(s.client.finalize(&short), ProposalStatus::Rejected);
+}
+
+#[test]
+fn test_weight_is_fixed_per_proposal() {
+    let s = setup();
+    let id = s
+        .client
+        .propose(&s.carol, &symbol_short!("stk"), &STAKING_ASSET);
+
+    s.client.set_asset_weight(&s.admin, &STAKING_ASSET, &0);
+    let result = s
+        .client
+        .try_propose(&s.carol, &symbol_short!("stk"), &STAKING_ASSET);
+    assert_eq!(result, Err(Ok(GovernorError::AssetDisabled)));
+    assert_eq!(s.client.cast_vote(&s.carol, &id, &true), 1_000);
+
+    let result = s
+        .client
+        .try_set_asset_weight(&s.admin, &STAKING_ASSET, &10_001);
+    assert_eq!(result, Err(Ok(GovernorError::InvalidWeight)));
+    let result = s
+        .client
+        .try_register_asset(&s.alice, &VotingSource::Token, &s.token, &10_000);
+    assert_eq!(result, Err(Ok(GovernorError::Unauthorized)));
+}
+
+#[test]
+fn test_vote_event_records_asset_and_source() {
+    let s = setup();
+    let id = s.client.propose(&s.bob, &symbol_short!("ve"), &VE_ASSET);
+    let power = s.client.cast_vote(&s.bob, &id, &true);
+
+    let captured = assert_standard_event(&s.env, topics::VOTE_CAST, |_| true);
+    assert_eq!(
+        captured.metadata::<u32>(&s.env, metadata_keys::ASSET_KEY),
+        Some(VE_ASSET)
+    );
+    assert_eq!(
+        captured.metadata::<Symbol>(&s.env, metadata_keys::SOURCE_KEY),
+        Some(symbol_short!("ve"))
+    );
+    assert_eq!(
+        captured.metadata::<Address>(&s.env, metadata_keys::TOKEN_KEY),
+        Some(s.ve.clone())
+    );
+    let vote: VoteCastEvent = payload(&captured);
+    assert_eq!(vote.source, VotingSource::VoteEscrow);
+    assert_eq!(vote.power, power);
+    assert_eq!(vote.raw_power, power);
+
+    let id = s
+        .client
+        .propose(&s.carol, &symbol_short!("stk"), &STAKING_ASSET);
+    s.client.cast_vote(&s.carol, &id, &true);
+    let captured = assert_standard_event(&s.env, topics::VOTE_CAST, |_| true);
+    let vote: VoteCastEvent = payload(&captured);
+    assert_eq!(vote.asset, s.staking);
+    assert_eq!(vote.raw_power, 2_000);
+    assert_eq!(vote.power, 1_000);
+}
+
+#[test]
+fn test_moved_balances_do_not_vote_twice() {
+    let s = setup();
+    let token = TokenContractClient::new(&s.env, &s.token);
+    let staking = StakingRewardsContractClient::new(&s.env, &s.staking);
+    let tokens = s
+        .client
+        .propose(&s.alice, &symbol_short!("tok"), &TOKEN_ASSET);
+    let stakes = s
+        .client
+        .propose(&s.carol, &symbol_short!("stk"), &STAKING_ASSET);
+    let dave = Address::generate(&s.env);
+
+    assert_eq!(s.client.cast_vote(&s.alice, &tokens, &true), 1_000);
+    token.transfer(&s.alice, &dave, &1_000);
+    s.env.ledger().with_mut(|li| li.sequence_number += 1);
+    let result = s.client.try_cast_vote(&dave, &tokens, &true);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+
+    // Moving a staked position doesn't move its votes either
+    assert_eq!(s.client.cast_vote(&s.carol, &stakes, &true), 1_000);
+    let position = staking
+        .positions_of(&s.carol, &Pagination::first(1))
+        .0
+        .get(0)
+        .unwrap();
+    staking.transfer_position(&s.carol, &dave, &position);
+    s.env.ledger().with_mut(|li| li.sequence_number += 1);
+    let result = s.client.try_cast_vote(&dave, &stakes, &true);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+
+    // Later proposals see the new holder
+    let later = s.client.propose(&dave, &symbol_short!("tok"), &TOKEN_ASSET);
+    assert_eq!(s.client.cast_vote(&dave, &later, &true), 1_000);
+    let result = s.client.try_cast_vote(&s.alice, &later, &true);
+    assert_eq!(result, Err(Ok(GovernorError::NoVotingPower)));
+    let later = s
+        .client
+        .propose(&dave, &symbol_short!("stk"), &STAKING_ASSET);
+    assert_eq!(s.client.cast_vote(&dave, &later, &true), 250);
+    assert_eq!(s.client.cast_vote(&s.carol, &later, &true), 750);
+}
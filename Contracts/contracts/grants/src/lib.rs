@@ -0,0 +1,439 @@
+//! Quadratic funding rounds
+//!
+//! During a round donors contribute to listed projects; after it ends a
+//! matching pool is split between projects in proportion to
+//! `(Σ √contribution)² − Σ contribution` over their donors, so broad
+//! support counts for more than a few large donations. Each project then
+//! receives its contributions plus its match.
+//!
+//! Quadratic matching rewards splitting one donation across many
+//! accounts, so a round can require donors to hold a badge from the
+//! multi-token contract or reach a reputation tier.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, GrantContributionEvent, GrantMatchEvent};
+use shared::math::{mul_div, sqrt};
+use shared::reputation::ReputationClient;
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, Env,
+};
+
+/// Most projects in one round, which bounds the payout loop
+pub const MAX_PROJECTS: u32 = 50;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GrantsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidRound = 5,
+    RoundNotFound = 6,
+    ProjectNotFound = 7,
+    TooManyProjects = 8,
+    /// Outside the round's contribution window
+    RoundNotActive = 9,
+    RoundNotEnded = 10,
+    AlreadyDistributed = 11,
+    /// The donor does not pass the round's sybil gate
+    NotEligible = 12,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeGate {
+    pub multitoken: Address,
+    pub id: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierGate {
+    pub reputation: Address,
+    pub min_tier: u32,
+}
+
+/// Who may contribute to a round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Gate {
+    Open,
+    /// Holders of a live badge
+    Badge(BadgeGate),
+    /// Accounts at or above a reputation tier
+    Tier(TierGate),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    /// Token of contributions and the matching pool
+    pub token: Address,
+    pub starts_at: u64,
+    /// Last second contributions are accepted
+    pub ends_at: u64,
+    pub gate: Gate,
+    pub pool: i128,
+    pub projects: u32,
+    /// Sum of the projects' quadratic weights
+    pub total_weight: i128,
+    pub distributed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Project {
+    pub recipient: Address,
+    pub contributions: i128,
+    /// Σ √contribution over the project's donors
+    pub sqrt_sum: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    NextRoundId,
+    Round(u64),
+    Project(u64, u32),
+    Contribution(u64, u32, Address),
+}
+
+/// The badge balance call of the multi-token contract
+#[contractclient(name = "BadgeHolderClient")]
+pub trait BadgeHolder {
+    fn balance_of(env: Env, owner: Address, id: u64) -> i128;
+}
+
+#[contract]
+pub struct Grants;
+
+#[contractimpl]
+impl Grants {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), GrantsError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(GrantsError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Open a round accepting contributions in `token` from `starts_at`
+    /// through `ends_at`. Returns its id.
+    pub fn create_round(
+        env: Env,
+        admin: Address,
+        token: Address,
+        starts_at: u64,
+        ends_at: u64,
+        gate: Gate,
+    ) -> Result<u64, GrantsError> {
+        require_admin(&env, &admin)?;
+        if ends_at <= starts_at || ends_at <= env.ledger().timestamp() {
+            return Err(GrantsError::InvalidRound);
+        }
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRoundId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRoundId, &(id + 1));
+        let round = Round {
+            token,
+            starts_at,
+            ends_at,
+            gate,
+            pool: 0,
+            projects: 0,
+            total_weight: 0,
+            distributed: false,
+        };
+        set_round(&env, id, &round);
+        Ok(id)
+    }
+
+    /// List a project paying out to `recipient`. Returns its id within the
+    /// round.
+    pub fn add_project(
+        env: Env,
+        admin: Address,
+        round_id: u64,
+        recipient: Address,
+    ) -> Result<u32, GrantsError> {
+        require_admin(&env, &admin)?;
+        let mut round = load_round(&env, round_id)?;
+        if env.ledger().timestamp() > round.ends_at {
+            return Err(GrantsError::RoundNotActive);
+        }
+        if round.projects >= MAX_PROJECTS {
+            return Err(GrantsError::TooManyProjects);
+        }
+        let project_id = round.projects;
+        round.projects += 1;
+        set_round(&env, round_id, &round);
+        let project = Project {
+            recipient,
+            contributions: 0,
+            sqrt_sum: 0,
+        };
+        set_project(&env, round_id, project_id, &project);
+        Ok(project_id)
+    }
+
+    /// Add `amount` to a round's matching pool. Anyone can fund it until
+    /// it is distributed.
+    pub fn fund_pool(
+        env: Env,
+        funder: Address,
+        round_id: u64,
+        amount: i128,
+    ) -> Result<i128, GrantsError> {
+        funder.require_auth();
+        let mut round = load_round(&env, round_id)?;
+        if round.distributed {
+            return Err(GrantsError::AlreadyDistributed);
+        }
+        if amount <= 0 {
+            return Err(GrantsError::InvalidAmount);
+        }
+        token::Client::new(&env, &round.token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        round.pool += amount;
+        set_round(&env, round_id, &round);
+        Ok(round.pool)
+    }
+
+    /// Contribute `amount` to a project. Returns `donor`'s total
+    /// contribution to it.
+    pub fn contribute(
+        env: Env,
+        donor: Address,
+        round_id: u64,
+        project_id: u32,
+        amount: i128,
+    ) -> Result<i128, GrantsError> {
+        donor.require_auth();
+        let mut round = load_round(&env, round_id)?;
+        let now = env.ledger().timestamp();
+        if now < round.starts_at || now > round.ends_at {
+            return Err(GrantsError::RoundNotActive);
+        }
+        if amount <= 0 {
+            return Err(GrantsError::InvalidAmount);
+        }
+        let mut project =
+            Self::project(env.clone(), round_id, project_id).ok_or(GrantsError::ProjectNotFound)?;
+        if !is_eligible(&env, &round.gate, &donor) {
+            return Err(GrantsError::NotEligible);
+        }
+        token::Client::new(&env, &round.token).transfer(
+            &donor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let key = DataKey::Contribution(round_id, project_id, donor.clone());
+        let previous: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let total = previous + amount;
+        env.storage().persistent().set(&key, &total);
+
+        let old_weight = weight(&project)?;
+        project.contributions += amount;
+        project.sqrt_sum += sqrt(total) - sqrt(previous);
+        let new_weight = weight(&project)?;
+        set_project(&env, round_id, project_id, &project);
+        round.total_weight += new_weight - old_weight;
+        set_round(&env, round_id, &round);
+
+        EventEmitter::grant_contributed(
+            &env,
+            GrantContributionEvent {
+                round_id,
+                project_id,
+                donor,
+                amount,
+                weight: new_weight,
+                timestamp: now,
+            },
+        );
+        Ok(total)
+    }
+
+    /// Pay every project its contributions and its share of the matching
+    /// pool once the round has ended. Permissionless. Rounding dust, or the
+    /// whole pool if no project has any weight, goes back to the admin.
+    /// Returns the total matched.
+    pub fn distribute(env: Env, round_id: u64) -> Result<i128, GrantsError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(GrantsError::NotInitialized)?;
+        let mut round = load_round(&env, round_id)?;
+        let now = env.ledger().timestamp();
+        if now <= round.ends_at {
+            return Err(GrantsError::RoundNotEnded);
+        }
+        if round.distributed {
+            return Err(GrantsError::AlreadyDistributed);
+        }
+        round.distributed = true;
+        set_round(&env, round_id, &round);
+
+        let token = token::Client::new(&env, &round.token);
+        let this = env.current_contract_address();
+        let mut matched_total = 0;
+        for project_id in 0..round.projects {
+            let Some(project) = Self::project(env.clone(), round_id, project_id) else {
+                continue;
+            };
+            let matched = if round.total_weight == 0 {
+                0
+            } else {
+                mul_div(round.pool, weight(&project)?, round.total_weight)
+                    .ok_or(GrantsError::InvalidAmount)?
+            };
+            let payout = project.contributions + matched;
+            if payout > 0 {
+                token.transfer(&this, &project.recipient, &payout);
+            }
+            matched_total += matched;
+            EventEmitter::grant_matched(
+                &env,
+                GrantMatchEvent {
+                    round_id,
+                    project_id,
+                    recipient: project.recipient,
+                    contributions: project.contributions,
+                    matched,
+                    timestamp: now,
+                },
+            );
+        }
+        let leftover = round.pool - matched_total;
+        if leftover > 0 {
+            token.transfer(&this, &admin, &leftover);
+        }
+        Ok(matched_total)
+    }
+
+    // ---- views ----
+
+    /// What a project would be matched if the round ended now
+    pub fn estimated_match(env: Env, round_id: u64, project_id: u32) -> Result<i128, GrantsError> {
+        let round = load_round(&env, round_id)?;
+        let project =
+            Self::project(env, round_id, project_id).ok_or(GrantsError::ProjectNotFound)?;
+        if round.total_weight == 0 {
+            return Ok(0);
+        }
+        mul_div(round.pool, weight(&project)?, round.total_weight).ok_or(GrantsError::InvalidAmount)
+    }
+
+    pub fn round(env: Env, round_id: u64) -> Option<Round> {
+        env.storage().persistent().get(&DataKey::Round(round_id))
+    }
+
+    pub fn project(env: Env, round_id: u64, project_id: u32) -> Option<Project> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Project(round_id, project_id))
+    }
+
+    pub fn contribution(env: Env, round_id: u64, project_id: u32, donor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contribution(round_id, project_id, donor))
+            .unwrap_or(0)
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), GrantsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), GrantsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Quadratic weight of a project: `(Σ √c)² − Σ c`. Rounded-down roots can
+/// take it slightly below zero, which counts as zero.
+fn weight(project: &Project) -> Result<i128, GrantsError> {
+    let squared = project
+        .sqrt_sum
+        .checked_mul(project.sqrt_sum)
+        .ok_or(GrantsError::InvalidAmount)?;
+    Ok((squared - project.contributions).max(0))
+}
+
+fn is_eligible(env: &Env, gate: &Gate, donor: &Address) -> bool {
+    match gate {
+        Gate::Open => true,
+        Gate::Badge(badge) => {
+            BadgeHolderClient::new(env, &badge.multitoken).balance_of(donor, &badge.id) > 0
+        }
+        Gate::Tier(tier) => {
+            ReputationClient::new(env, &tier.reputation).tier_of(donor) >= tier.min_tier
+        }
+    }
+}
+
+fn set_round(env: &Env, round_id: u64, round: &Round) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Round(round_id), round);
+}
+
+fn set_project(env: &Env, round_id: u64, project_id: u32, project: &Project) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Project(round_id, project_id), project);
+}
+
+fn load_round(env: &Env, round_id: u64) -> Result<Round, GrantsError> {
+    Grants::round(env.clone(), round_id).ok_or(GrantsError::RoundNotFound)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), GrantsError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(GrantsError::NotInitialized)?;
+    if stored != *admin {
+        return Err(GrantsError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
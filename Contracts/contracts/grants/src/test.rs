@@ -0,0 +1,174 @@
+#![cfg(test)]
+
+use super::*;
+use multitoken::{MultiToken, MultiTokenClient};
+use reputation::{Reputation, ReputationClient as ReputationContractClient, WEIGHT_SCALE};
+use shared::events::topics;
+use shared::reputation::activities;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{String, Vec};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+struct Setup {
+    env: Env,
+    client: GrantsClient<'static>,
+    admin: Address,
+    token: TestToken<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let client = GrantsClient::new(&env, &env.register_contract(None, Grants));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let token = stellar_asset(&env);
+    Setup {
+        env,
+        client,
+        admin,
+        token,
+    }
+}
+
+impl Setup {
+    fn round(&self, gate: &Gate) -> u64 {
+        self.client
+            .create_round(&self.admin, &self.token.address, &1_000, &2_000, gate)
+    }
+
+    fn donate(&self, round_id: u64, project_id: u32, amount: i128) -> Address {
+        let donor = Address::generate(&self.env);
+        self.token.mint(&donor, amount);
+        self.client
+            .contribute(&donor, &round_id, &project_id, &amount);
+        donor
+    }
+
+    fn end_round(&self) {
+        self.env.ledger().with_mut(|li| li.timestamp = 2_001);
+    }
+}
+
+#[test]
+fn test_matching_favours_broad_support() {
+    let s = setup();
+    let id = s.round(&Gate::Open);
+    let recipients: [Address; 3] = core::array::from_fn(|_| Address::generate(&s.env));
+    for recipient in recipients.iter() {
+        s.client.add_project(&s.admin, &id, recipient);
+    }
+    let funder = Address::generate(&s.env);
+    s.token.mint(&funder, 1_400);
+    s.client.fund_pool(&funder, &id, &1_400);
+
+    // Each project raises 400 or 200, from four donors, one donor and two
+    for _ in 0..4 {
+        s.donate(id, 0, 100);
+    }
+    let whale = s.donate(id, 1, 400);
+    s.donate(id, 2, 100);
+    s.donate(id, 2, 100);
+    let captured = assert_standard_event(&s.env, topics::GRANT_CONTRIBUTED, |_| true);
+    let event: GrantContributionEvent = payload(&captured);
+    assert_eq!(
+        (event.project_id, event.amount, event.weight),
+        (2, 100, 200)
+    );
+
+    // (40² − 400) : (20² − 400) : (20² − 200)
+    assert_eq!(s.client.estimated_match(&id, &0), 1_200);
+    assert_eq!(s.client.estimated_match(&id, &1), 0);
+    assert_eq!(s.client.estimated_match(&id, &2), 200);
+    assert_eq!(s.client.contribution(&id, &1, &whale), 400);
+
+    let result = s.client.try_distribute(&id);
+    assert_eq!(result, Err(Ok(GrantsError::RoundNotEnded)));
+    s.end_round();
+    assert_eq!(s.client.distribute(&id), 1_400);
+    assert_eq!(s.token.balance(&recipients[0]), 1_600);
+    assert_eq!(s.token.balance(&recipients[1]), 400);
+    assert_eq!(s.token.balance(&recipients[2]), 400);
+    let captured = assert_standard_event(&s.env, topics::GRANT_MATCHED, |_| true);
+    let event: GrantMatchEvent = payload(&captured);
+    assert_eq!((event.contributions, event.matched), (200, 200));
+
+    let result = s.client.try_distribute(&id);
+    assert_eq!(result, Err(Ok(GrantsError::AlreadyDistributed)));
+}
+
+#[test]
+fn test_rounds_gate_donors_on_badges_and_tiers() {
+    let s = setup();
+    let badges = MultiTokenClient::new(&s.env, &s.env.register_contract(None, MultiToken));
+    badges.initialize(&s.admin);
+    let uri = String::from_str(&s.env, "ipfs://badges/human");
+    let badge_id = badges.create_badge(&s.admin, &uri, &1, &0);
+    badges.set_badge_issuer(&s.admin, &badge_id, &s.admin, &true);
+    let gate = Gate::Badge(BadgeGate {
+        multitoken: badges.address.clone(),
+        id: badge_id,
+    });
+    let badge_round = s.round(&gate);
+    s.client.add_project(&s.admin, &badge_round, &s.admin);
+
+    let donor = Address::generate(&s.env);
+    s.token.mint(&donor, 20);
+    let result = s.client.try_contribute(&donor, &badge_round, &0, &10);
+    assert_eq!(result, Err(Ok(GrantsError::NotEligible)));
+    badges.issue_badge(&s.admin, &donor, &badge_id);
+    s.client.contribute(&donor, &badge_round, &0, &10);
+
+    let reputation =
+        ReputationContractClient::new(&s.env, &s.env.register_contract(None, Reputation));
+    reputation.initialize(&s.admin, &0);
+    reputation.set_weight(&s.admin, &activities::GOVERNANCE, &(WEIGHT_SCALE as u32));
+    reputation.set_reporter(&s.admin, &s.admin, &activities::GOVERNANCE, &true);
+    reputation.set_tiers(&s.admin, &Vec::from_array(&s.env, [5]));
+    let gate = Gate::Tier(TierGate {
+        reputation: reputation.address.clone(),
+        min_tier: 1,
+    });
+    let tier_round = s.round(&gate);
+    s.client.add_project(&s.admin, &tier_round, &s.admin);
+
+    let result = s.client.try_contribute(&donor, &tier_round, &0, &10);
+    assert_eq!(result, Err(Ok(GrantsError::NotEligible)));
+    reputation.record(&s.admin, &donor, &activities::GOVERNANCE, &5);
+    s.client.contribute(&donor, &tier_round, &0, &10);
+}
+
+#[test]
+fn test_round_window_and_unmatched_pool() {
+    let s = setup();
+    let result = s
+        .client
+        .try_create_round(&s.admin, &s.token.address, &1_000, &1_000, &Gate::Open);
+    assert_eq!(result, Err(Ok(GrantsError::InvalidRound)));
+    let id = s
+        .client
+        .create_round(&s.admin, &s.token.address, &1_500, &2_000, &Gate::Open);
+    let recipient = Address::generate(&s.env);
+    s.client.add_project(&s.admin, &id, &recipient);
+    s.token.mint(&s.admin, 300);
+    s.client.fund_pool(&s.admin, &id, &300);
+
+    let donor = Address::generate(&s.env);
+    s.token.mint(&donor, 50);
+    let result = s.client.try_contribute(&donor, &id, &0, &50);
+    assert_eq!(result, Err(Ok(GrantsError::RoundNotActive)));
+    s.env.ledger().with_mut(|li| li.timestamp = 1_500);
+    let result = s.client.try_contribute(&donor, &id, &1, &50);
+    assert_eq!(result, Err(Ok(GrantsError::ProjectNotFound)));
+    s.client.contribute(&donor, &id, &0, &50);
+
+    // A lone donor earns no match; the pool goes back to the admin
+    s.end_round();
+    let result = s.client.try_contribute(&donor, &id, &0, &1);
+    assert_eq!(result, Err(Ok(GrantsError::RoundNotActive)));
+    assert_eq!(s.client.distribute(&id), 0);
+    assert_eq!(s.token.balance(&recipient), 50);
+    assert_eq!(s.token.balance(&s.admin), 300);
+}
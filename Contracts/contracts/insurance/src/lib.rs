@@ -0,0 +1,389 @@
+//! Protocol insurance fund
+//!
+//! Protocol contracts route a configurable slice of the fees they collect
+//! into the fund via `contribute`. Anyone harmed by an incident can file a
+//! claim; the governance address approves or rejects it. Approved payouts
+//! are capped per incident and separated by a cooldown so a single exploit
+//! cannot drain the fund in one go.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{ClaimPaidEvent, EventEmitter, FeeCollectedEvent, InsuranceClaimEvent};
+use shared::fees::{FeeError, FeeManager};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol,
+};
+
+/// Basis-point denominator for the fee share
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InsuranceError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidConfig = 5,
+    ClaimNotFound = 6,
+    ClaimNotPending = 7,
+    CoolingDown = 8,
+    InsufficientFunds = 9,
+}
+
+impl From<FeeError> for InsuranceError {
+    fn from(error: FeeError) -> Self {
+        match error {
+            FeeError::InsufficientBalance => InsuranceError::InsufficientFunds,
+            FeeError::InvalidAmount => InsuranceError::InvalidAmount,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceConfig {
+    pub admin: Address,
+    /// Approves and rejects claims
+    pub governance: Address,
+    pub token: Address,
+    /// Share of each contributed fee kept by the fund
+    pub fee_share_bps: u32,
+    /// Most a single claim can pay out
+    pub coverage_cap: i128,
+    /// Seconds that must pass between payouts
+    pub cooldown: u64,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    Rejected,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub claimant: Address,
+    pub amount: i128,
+    /// Incident the claim is for
+    pub reason: Symbol,
+    pub status: ClaimStatus,
+    /// Amount actually paid, after the coverage cap
+    pub paid: i128,
+    pub filed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    NextClaimId,
+    Claim(u64),
+    LastPayout,
+}
+
+#[contract]
+pub struct InsuranceFund;
+
+#[contractimpl]
+impl InsuranceFund {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        governance: Address,
+        token: Address,
+        fee_share_bps: u32,
+        coverage_cap: i128,
+        cooldown: u64,
+    ) -> Result<(), InsuranceError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(InsuranceError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        let config = InsuranceConfig {
+            admin,
+            governance,
+            token,
+            fee_share_bps,
+            coverage_cap,
+            cooldown,
+        };
+        validate(&config)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Move the fund's share of `fee_amount` from `from` into the fund.
+    /// Returns the amount contributed.
+    pub fn contribute(env: Env, from: Address, fee_amount: i128) -> Result<i128, InsuranceError> {
+        from.require_auth();
+        if fee_amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        let config = load_config(&env)?;
+        let share = fee_amount * config.fee_share_bps as i128 / BPS_DENOMINATOR;
+        let fund = env.current_contract_address();
+        FeeManager::collect_fee_checked(&env, &config.token, &from, &fund, share)?;
+
+        EventEmitter::fee_collected(
+            &env,
+            FeeCollectedEvent {
+                payer: from,
+                recipient: fund,
+                amount: share,
+                token: config.token,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(share)
+    }
+
+    /// File a claim for losses from the incident `reason`. Returns the
+    /// claim id.
+    pub fn file_claim(
+        env: Env,
+        claimant: Address,
+        amount: i128,
+        reason: Symbol,
+    ) -> Result<u64, InsuranceError> {
+        claimant.require_auth();
+        load_config(&env)?;
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let claim_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextClaimId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextClaimId, &(claim_id + 1));
+
+        let now = env.ledger().timestamp();
+        let claim = Claim {
+            claimant: claimant.clone(),
+            amount,
+            reason: reason.clone(),
+            status: ClaimStatus::Pending,
+            paid: 0,
+            filed_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim_id), &claim);
+
+        EventEmitter::claim_filed(
+            &env,
+            InsuranceClaimEvent {
+                claim_id,
+                claimant,
+                amount,
+                reason,
+                timestamp: now,
+            },
+        );
+        Ok(claim_id)
+    }
+
+    /// Pay a pending claim, up to the coverage cap (governance only).
+    /// Returns the amount paid.
+    pub fn approve_claim(
+        env: Env,
+        governance: Address,
+        claim_id: u64,
+    ) -> Result<i128, InsuranceError> {
+        let config = require_governance(&env, &governance)?;
+        let mut claim = load_pending(&env, claim_id)?;
+
+        let now = env.ledger().timestamp();
+        if let Some(last) = env.storage().instance().get::<_, u64>(&DataKey::LastPayout) {
+            if now < last + config.cooldown {
+                return Err(InsuranceError::CoolingDown);
+            }
+        }
+        let payout = claim.amount.min(config.coverage_cap);
+        let token = token::Client::new(&env, &config.token);
+        let fund = env.current_contract_address();
+        if token.balance(&fund) < payout {
+            return Err(InsuranceError::InsufficientFunds);
+        }
+
+        claim.status = ClaimStatus::Paid;
+        claim.paid = payout;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim_id), &claim);
+        env.storage().instance().set(&DataKey::LastPayout, &now);
+        token.transfer(&fund, &claim.claimant, &payout);
+
+        EventEmitter::fund_claim_paid(
+            &env,
+            ClaimPaidEvent {
+                policy_id: claim_id,
+                policyholder: claim.claimant,
+                amount: payout,
+                timestamp: now,
+            },
+            claim.reason,
+        );
+        Ok(payout)
+    }
+
+    /// Reject a pending claim (governance only)
+    pub fn reject_claim(
+        env: Env,
+        governance: Address,
+        claim_id: u64,
+        reason: Symbol,
+    ) -> Result<(), InsuranceError> {
+        require_governance(&env, &governance)?;
+        let mut claim = load_pending(&env, claim_id)?;
+        claim.status = ClaimStatus::Rejected;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim_id), &claim);
+
+        EventEmitter::claim_rejected(
+            &env,
+            InsuranceClaimEvent {
+                claim_id,
+                claimant: claim.claimant,
+                amount: claim.amount,
+                reason,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Update the fee share, coverage cap and cooldown (admin only)
+    pub fn set_parameters(
+        env: Env,
+        admin: Address,
+        fee_share_bps: u32,
+        coverage_cap: i128,
+        cooldown: u64,
+    ) -> Result<(), InsuranceError> {
+        let mut config = require_admin(&env, &admin)?;
+        config.fee_share_bps = fee_share_bps;
+        config.coverage_cap = coverage_cap;
+        config.cooldown = cooldown;
+        validate(&config)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Hand claim approval to a new governance address (admin only)
+    pub fn set_governance(
+        env: Env,
+        admin: Address,
+        governance: Address,
+    ) -> Result<(), InsuranceError> {
+        let mut config = require_admin(&env, &admin)?;
+        config.governance = governance;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ---- views ----
+
+    pub fn config(env: Env) -> Result<InsuranceConfig, InsuranceError> {
+        load_config(&env)
+    }
+
+    pub fn get_claim(env: Env, claim_id: u64) -> Option<Claim> {
+        env.storage().persistent().get(&DataKey::Claim(claim_id))
+    }
+
+    /// Assets available to pay claims
+    pub fn fund_balance(env: Env) -> Result<i128, InsuranceError> {
+        let config = load_config(&env)?;
+        Ok(token::Client::new(&env, &config.token).balance(&env.current_contract_address()))
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), InsuranceError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), InsuranceError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn load_config(env: &Env) -> Result<InsuranceConfig, InsuranceError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(InsuranceError::NotInitialized)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<InsuranceConfig, InsuranceError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(InsuranceError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn require_governance(env: &Env, governance: &Address) -> Result<InsuranceConfig, InsuranceError> {
+    governance.require_auth();
+    let config = load_config(env)?;
+    if config.governance != *governance {
+        return Err(InsuranceError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_pending(env: &Env, claim_id: u64) -> Result<Claim, InsuranceError> {
+    let claim: Claim = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Claim(claim_id))
+        .ok_or(InsuranceError::ClaimNotFound)?;
+    if claim.status != ClaimStatus::Pending {
+        return Err(InsuranceError::ClaimNotPending);
+    }
+    Ok(claim)
+}
+
+fn validate(config: &InsuranceConfig) -> Result<(), InsuranceError> {
+    if config.fee_share_bps as i128 > BPS_DENOMINATOR || config.coverage_cap <= 0 {
+        return Err(InsuranceError::InvalidConfig);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
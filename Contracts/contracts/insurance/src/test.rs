@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::symbol_short;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const CAP: i128 = 500;
+const COOLDOWN: u64 = 3_600;
+
+struct Setup {
+    env: Env,
+    client: InsuranceFundClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+    governance: Address,
+    protocol: Address,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+    let protocol = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&protocol, &100_000);
+
+    let contract_id = env.register_contract(None, InsuranceFund);
+    let client = InsuranceFundClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    client.initialize(&admin, &governance, &token_id, &2_000, &CAP, &COOLDOWN);
+    let user = Address::generate(&env);
+
+    Setup {
+        env,
+        client,
+        token,
+        admin,
+        governance,
+        protocol,
+        user,
+    }
+}
+
+#[test]
+fn test_contribute_keeps_fee_share() {
+    let s = setup();
+    assert_eq!(s.client.contribute(&s.protocol, &10_000), 2_000);
+    assert_eq!(s.client.fund_balance(), 2_000);
+    assert_eq!(s.token.balance(&s.protocol), 98_000);
+    assert_standard_event(&s.env, topics::FEE_COLLECTED, |_| true);
+}
+
+#[test]
+fn test_approved_claim_is_capped() {
+    let s = setup();
+    s.client.contribute(&s.protocol, &10_000);
+    let claim_id = s.client.file_claim(&s.user, &800, &symbol_short!("oracle"));
+
+    let captured = assert_standard_event(&s.env, topics::CLAIM_FILED, |_| true);
+    let filed: InsuranceClaimEvent = payload(&captured);
+    assert_eq!(filed.claim_id, claim_id);
+    assert_eq!(filed.amount, 800);
+
+    assert_eq!(s.client.approve_claim(&s.governance, &claim_id), CAP);
+    assert_eq!(s.token.balance(&s.user), CAP);
+    let claim = s.client.get_claim(&claim_id).unwrap();
+    assert_eq!(claim.status, ClaimStatus::Paid);
+    assert_eq!(claim.paid, CAP);
+
+    let captured = assert_standard_event(&s.env, topics::CLAIM_PAID, |_| true);
+    let paid: ClaimPaidEvent = payload(&captured);
+    assert_eq!(paid.amount, CAP);
+    assert_eq!(
+        captured.metadata::<Symbol>(&s.env, metadata_keys::REASON_KEY),
+        Some(symbol_short!("oracle"))
+    );
+
+    assert_eq!(
+        s.client.try_approve_claim(&s.governance, &claim_id),
+        Err(Ok(InsuranceError::ClaimNotPending))
+    );
+}
+
+#[test]
+fn test_payouts_respect_cooldown() {
+    let s = setup();
+    s.client.contribute(&s.protocol, &10_000);
+    let first = s.client.file_claim(&s.user, &100, &symbol_short!("hack"));
+    let second = s.client.file_claim(&s.user, &100, &symbol_short!("hack"));
+
+    s.client.approve_claim(&s.governance, &first);
+    assert_eq!(
+        s.client.try_approve_claim(&s.governance, &second),
+        Err(Ok(InsuranceError::CoolingDown))
+    );
+    s.env.ledger().with_mut(|li| li.timestamp += COOLDOWN);
+    assert_eq!(s.client.approve_claim(&s.governance, &second), 100);
+}
+
+#[test]
+fn test_only_governance_decides_claims() {
+    let s = setup();
+    let claim_id = s.client.file_claim(&s.user, &100, &symbol_short!("hack"));
+    assert_eq!(
+        s.client.try_approve_claim(&s.admin, &claim_id),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_approve_claim(&s.governance, &claim_id),
+        Err(Ok(InsuranceError::InsufficientFunds))
+    );
+
+    s.client
+        .reject_claim(&s.governance, &claim_id, &symbol_short!("no_proof"));
+    let captured = assert_standard_event(&s.env, topics::CLAIM_REJECTED, |_| true);
+    let rejected: InsuranceClaimEvent = payload(&captured);
+    assert_eq!(rejected.reason, symbol_short!("no_proof"));
+    assert_eq!(
+        s.client.get_claim(&claim_id).unwrap().status,
+        ClaimStatus::Rejected
+    );
+}
+
+#[test]
+fn test_parameters_are_validated() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_set_parameters(&s.admin, &10_001, &CAP, &COOLDOWN),
+        Err(Ok(InsuranceError::InvalidConfig))
+    );
+    assert_eq!(
+        s.client
+            .try_set_parameters(&s.governance, &1_000, &CAP, &COOLDOWN),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    s.client.set_parameters(&s.admin, &1_000, &1_000, &0);
+    assert_eq!(s.client.config().coverage_cap, 1_000);
+}
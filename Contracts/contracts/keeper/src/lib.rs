@@ -0,0 +1,264 @@
+//! Keeper registry
+//!
+//! Contracts (or their operators) register recurring tasks: a target
+//! contract, a function and arguments, an interval and a bounty per
+//! execution, prepaid into the task's balance. Anyone can act as a keeper
+//! and call `perform` once a task is due; the registry invokes the target
+//! and pays the keeper the bounty from the task balance. Targets that
+//! should only be driven by keepers can `require_auth` the registry's
+//! address, which the registry authorizes implicitly when it calls them.
+
+#![no_std]
+
+use shared::events::{EventEmitter, TaskEvent, TaskPerformedEvent};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol, Val, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum KeeperError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidInterval = 4,
+    InvalidAmount = 5,
+    TaskNotFound = 6,
+    TaskNotDue = 7,
+    InsufficientBalance = 8,
+    TaskFailed = 9,
+}
+
+/// A recurring task. The first execution is due as soon as it is
+/// registered; each execution schedules the next one `interval` seconds
+/// later.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Task {
+    pub owner: Address,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub interval: u64,
+    pub payment: i128,
+    pub balance: i128,
+    pub next_run: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    PaymentToken,
+    NextTaskId,
+    Task(u64),
+}
+
+#[contract]
+pub struct KeeperContract;
+
+#[contractimpl]
+impl KeeperContract {
+    /// `payment_token` funds task balances and keeper bounties
+    pub fn initialize(env: Env, payment_token: Address) -> Result<(), KeeperError> {
+        if env.storage().instance().has(&DataKey::PaymentToken) {
+            return Err(KeeperError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentToken, &payment_token);
+        Ok(())
+    }
+
+    /// Register a task and prepay `deposit` towards its bounties. Returns
+    /// the task id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_task(
+        env: Env,
+        owner: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+        interval: u64,
+        payment: i128,
+        deposit: i128,
+    ) -> Result<u64, KeeperError> {
+        owner.require_auth();
+        if interval == 0 {
+            return Err(KeeperError::InvalidInterval);
+        }
+        if payment <= 0 || deposit < 0 {
+            return Err(KeeperError::InvalidAmount);
+        }
+        let token = payment_token(&env)?;
+        if deposit > 0 {
+            token::Client::new(&env, &token).transfer(
+                &owner,
+                &env.current_contract_address(),
+                &deposit,
+            );
+        }
+
+        let task_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTaskId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTaskId, &(task_id + 1));
+
+        let now = env.ledger().timestamp();
+        let task = Task {
+            owner,
+            target,
+            function,
+            args,
+            interval,
+            payment,
+            balance: deposit,
+            next_run: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Task(task_id), &task);
+
+        EventEmitter::task_registered(&env, task_event(task_id, &task, now));
+        Ok(task_id)
+    }
+
+    /// Top up a task's balance. Anyone can fund any task.
+    pub fn fund_task(
+        env: Env,
+        funder: Address,
+        task_id: u64,
+        amount: i128,
+    ) -> Result<i128, KeeperError> {
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(KeeperError::InvalidAmount);
+        }
+        let mut task = load_task(&env, task_id)?;
+        token::Client::new(&env, &payment_token(&env)?).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        task.balance += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Task(task_id), &task);
+        Ok(task.balance)
+    }
+
+    /// Remove a task and refund its remaining balance to the owner
+    pub fn cancel_task(env: Env, owner: Address, task_id: u64) -> Result<i128, KeeperError> {
+        owner.require_auth();
+        let task = load_task(&env, task_id)?;
+        if task.owner != owner {
+            return Err(KeeperError::Unauthorized);
+        }
+        env.storage().persistent().remove(&DataKey::Task(task_id));
+        if task.balance > 0 {
+            token::Client::new(&env, &payment_token(&env)?).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &task.balance,
+            );
+        }
+
+        EventEmitter::task_cancelled(&env, task_event(task_id, &task, env.ledger().timestamp()));
+        Ok(task.balance)
+    }
+
+    /// Execute a due task and pay the calling keeper its bounty. A failing
+    /// target call reverts the whole execution, so keepers are only paid
+    /// for work that landed.
+    pub fn perform(env: Env, keeper: Address, task_id: u64) -> Result<i128, KeeperError> {
+        keeper.require_auth();
+        let mut task = load_task(&env, task_id)?;
+        let now = env.ledger().timestamp();
+        if now < task.next_run {
+            return Err(KeeperError::TaskNotDue);
+        }
+        if task.balance < task.payment {
+            return Err(KeeperError::InsufficientBalance);
+        }
+
+        let result = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &task.target,
+            &task.function,
+            task.args.clone(),
+        );
+        if !matches!(result, Ok(Ok(_))) {
+            return Err(KeeperError::TaskFailed);
+        }
+
+        // Late executions do not accumulate a backlog of due runs
+        task.next_run = now + task.interval;
+        task.balance -= task.payment;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Task(task_id), &task);
+        token::Client::new(&env, &payment_token(&env)?).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &task.payment,
+        );
+
+        EventEmitter::task_performed(
+            &env,
+            TaskPerformedEvent {
+                task_id,
+                keeper,
+                payment: task.payment,
+                next_run: task.next_run,
+                timestamp: now,
+            },
+        );
+        Ok(task.payment)
+    }
+
+    // ---- views ----
+
+    pub fn get_task(env: Env, task_id: u64) -> Option<Task> {
+        env.storage().persistent().get(&DataKey::Task(task_id))
+    }
+
+    /// Whether `perform` would currently accept the task
+    pub fn is_due(env: Env, task_id: u64) -> bool {
+        match Self::get_task(env.clone(), task_id) {
+            Some(task) => env.ledger().timestamp() >= task.next_run && task.balance >= task.payment,
+            None => false,
+        }
+    }
+}
+
+fn payment_token(env: &Env) -> Result<Address, KeeperError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PaymentToken)
+        .ok_or(KeeperError::NotInitialized)
+}
+
+fn load_task(env: &Env, task_id: u64) -> Result<Task, KeeperError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Task(task_id))
+        .ok_or(KeeperError::TaskNotFound)
+}
+
+fn task_event(task_id: u64, task: &Task, timestamp: u64) -> TaskEvent {
+    TaskEvent {
+        task_id,
+        owner: task.owner.clone(),
+        target: task.target.clone(),
+        function: task.function.clone(),
+        interval: task.interval,
+        payment: task.payment,
+        timestamp,
+    }
+}
+
+#[cfg(test)]
+mod test;
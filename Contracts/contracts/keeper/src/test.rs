@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, IntoVal,
+};
+use test_utils::{assert_standard_event, payload};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CounterError {
+    Broken = 1,
+}
+
+/// Minimal automation target
+#[contract]
+pub struct Counter;
+
+#[contractimpl]
+impl Counter {
+    pub fn tick(env: Env, by: u32) -> u32 {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("n"))
+            .unwrap_or(0)
+            + by;
+        env.storage().instance().set(&symbol_short!("n"), &count);
+        count
+    }
+
+    pub fn count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("n"))
+            .unwrap_or(0)
+    }
+
+    pub fn broken(_env: Env) -> Result<(), CounterError> {
+        Err(CounterError::Broken)
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: KeeperContractClient<'static>,
+    counter: CounterClient<'static>,
+    token: TokenClient<'static>,
+    owner: Address,
+    keeper: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+    let owner = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&owner, &1_000);
+
+    let contract_id = env.register_contract(None, KeeperContract);
+    let client = KeeperContractClient::new(&env, &contract_id);
+    client.initialize(&token_id);
+
+    let counter_id = env.register_contract(None, Counter);
+    let counter = CounterClient::new(&env, &counter_id);
+    let keeper = Address::generate(&env);
+
+    Setup {
+        env,
+        client,
+        counter,
+        token,
+        owner,
+        keeper,
+    }
+}
+
+fn register_tick(s: &Setup, deposit: i128) -> u64 {
+    s.client.register_task(
+        &s.owner,
+        &s.counter.address,
+        &symbol_short!("tick"),
+        &vec![&s.env, 2u32.into_val(&s.env)],
+        &3_600,
+        &10,
+        &deposit,
+    )
+}
+
+#[test]
+fn test_perform_runs_due_task_and_pays_keeper() {
+    let s = setup();
+    let task_id = register_tick(&s, 25);
+    assert_eq!(s.token.balance(&s.client.address), 25);
+    assert_standard_event(&s.env, topics::TASK_REGISTERED, |_| true);
+
+    assert!(s.client.is_due(&task_id));
+    assert_eq!(s.client.perform(&s.keeper, &task_id), 10);
+    assert_eq!(s.counter.count(), 2);
+    assert_eq!(s.token.balance(&s.keeper), 10);
+
+    let captured = assert_standard_event(&s.env, topics::TASK_PERFORMED, |_| true);
+    let event: TaskPerformedEvent = payload(&captured);
+    assert_eq!(event.next_run, 3_600);
+
+    let result = s.client.try_perform(&s.keeper, &task_id);
+    assert_eq!(result, Err(Ok(KeeperError::TaskNotDue)));
+
+    s.env.ledger().with_mut(|li| li.timestamp = 3_600);
+    s.client.perform(&s.keeper, &task_id);
+    assert_eq!(s.counter.count(), 4);
+
+    // 5 left, bounty is 10
+    s.env.ledger().with_mut(|li| li.timestamp = 7_200);
+    assert!(!s.client.is_due(&task_id));
+    let result = s.client.try_perform(&s.keeper, &task_id);
+    assert_eq!(result, Err(Ok(KeeperError::InsufficientBalance)));
+
+    assert_eq!(s.client.fund_task(&s.owner, &task_id, &5), 10);
+    s.client.perform(&s.keeper, &task_id);
+    assert_eq!(s.token.balance(&s.keeper), 30);
+}
+
+#[test]
+fn test_failed_target_pays_nothing() {
+    let s = setup();
+    let task_id = s.client.register_task(
+        &s.owner,
+        &s.counter.address,
+        &symbol_short!("broken"),
+        &vec![&s.env],
+        &60,
+        &10,
+        &100,
+    );
+
+    let result = s.client.try_perform(&s.keeper, &task_id);
+    assert_eq!(result, Err(Ok(KeeperError::TaskFailed)));
+    assert_eq!(s.token.balance(&s.keeper), 0);
+    assert_eq!(s.client.get_task(&task_id).unwrap().balance, 100);
+}
+
+#[test]
+fn test_cancel_refunds_owner() {
+    let s = setup();
+    let task_id = register_tick(&s, 100);
+
+    let result = s.client.try_cancel_task(&s.keeper, &task_id);
+    assert_eq!(result, Err(Ok(KeeperError::Unauthorized)));
+
+    assert_eq!(s.client.cancel_task(&s.owner, &task_id), 100);
+    assert_eq!(s.token.balance(&s.owner), 1_000);
+    assert_eq!(s.client.get_task(&task_id), None);
+    assert_standard_event(&s.env, topics::TASK_CANCELLED, |_| true);
+
+    let result = s.client.try_perform(&s.keeper, &task_id);
+    assert_eq!(result, Err(Ok(KeeperError::TaskNotFound)));
+
+    let result = s.client.try_register_task(
+        &s.owner,
+        &s.counter.address,
+        &symbol_short!("tick"),
+        &vec![&s.env],
+        &0,
+        &10,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(KeeperError::InvalidInterval)));
+}
@@ -0,0 +1,343 @@
+//! Launch auction for the initial distribution of a token
+//!
+//! The admin deposits `sale_amount` of the sale token at initialization.
+//! While the window `[start, end)` is open, users commit quote tokens; each
+//! commitment bids for the sale tokens it buys at the current price (see
+//! [`Pricing`]). After the window anyone can settle the auction:
+//!
+//! - if the sale is oversubscribed, every bid is scaled down pro rata so
+//!   exactly `sale_amount` is sold, and each participant is refunded the
+//!   unused part of their commitment;
+//! - otherwise every bid fills in full and the unsold tokens go back to the
+//!   admin.
+//!
+//! Settlement sends the quote raised to the treasury; participants then
+//! claim their tokens and refund. Token amounts and refunds round down, so
+//! rounding dust stays in the contract.
+
+#![no_std]
+
+use shared::events::{EventEmitter, LaunchClaimEvent, LaunchCommitEvent, LaunchSettledEvent};
+use shared::math::mul_div;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+mod pricing;
+
+pub use pricing::{Declining, Pricing, Tranche, PRICE_SCALE};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LaunchError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    InvalidAmount = 4,
+    NotStarted = 5,
+    Ended = 6,
+    NotEnded = 7,
+    AlreadySettled = 8,
+    NotSettled = 9,
+    NothingToClaim = 10,
+    SoldOut = 11,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LaunchConfig {
+    pub admin: Address,
+    pub sale_token: Address,
+    pub quote_token: Address,
+    /// Receives the quote raised
+    pub treasury: Address,
+    /// Sale tokens on offer
+    pub sale_amount: i128,
+    /// First second commitments are accepted
+    pub start: u64,
+    /// First second commitments are refused and settlement is possible
+    pub end: u64,
+    pub pricing: Pricing,
+}
+
+/// Running totals of all commitments
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Totals {
+    /// Quote tokens committed
+    pub committed: i128,
+    /// Sale tokens bid for
+    pub demand: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Commitment {
+    pub committed: i128,
+    pub demand: i128,
+    pub claimed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    /// Sale tokens distributed
+    pub sold: i128,
+    /// Quote tokens sent to the treasury
+    pub raised: i128,
+    pub settled_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Totals,
+    Settlement,
+    Commitment(Address),
+}
+
+#[contract]
+pub struct LaunchAuction;
+
+#[contractimpl]
+impl LaunchAuction {
+    /// Set up the auction and take `sale_amount` of the sale token from the
+    /// admin
+    pub fn initialize(env: Env, config: LaunchConfig) -> Result<(), LaunchError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LaunchError::AlreadyInitialized);
+        }
+        config.admin.require_auth();
+        if config.sale_amount <= 0
+            || config.start >= config.end
+            || config.sale_token == config.quote_token
+            || !config.pricing.is_valid(config.sale_amount)
+        {
+            return Err(LaunchError::InvalidConfig);
+        }
+        token::Client::new(&env, &config.sale_token).transfer(
+            &config.admin,
+            &env.current_contract_address(),
+            &config.sale_amount,
+        );
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Commit `amount` of the quote token at the current price. Returns the
+    /// sale tokens bid for.
+    pub fn commit(env: Env, user: Address, amount: i128) -> Result<i128, LaunchError> {
+        user.require_auth();
+        let config = load_config(&env)?;
+        if amount <= 0 {
+            return Err(LaunchError::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        if now < config.start {
+            return Err(LaunchError::NotStarted);
+        }
+        if now >= config.end {
+            return Err(LaunchError::Ended);
+        }
+
+        let mut totals = Self::totals(env.clone());
+        let price = config
+            .pricing
+            .price(config.start, config.end, now, totals.demand)?;
+        let demand =
+            config
+                .pricing
+                .demand_for(config.start, config.end, now, totals.demand, amount)?;
+        if demand <= 0 {
+            return Err(LaunchError::InvalidAmount);
+        }
+
+        token::Client::new(&env, &config.quote_token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        totals.committed += amount;
+        totals.demand += demand;
+        env.storage().instance().set(&DataKey::Totals, &totals);
+        let mut commitment = Self::commitment(env.clone(), user.clone());
+        commitment.committed += amount;
+        commitment.demand += demand;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Commitment(user.clone()), &commitment);
+
+        EventEmitter::launch_committed(
+            &env,
+            LaunchCommitEvent {
+                user,
+                amount,
+                demand,
+                price,
+                timestamp: now,
+            },
+        );
+        Ok(demand)
+    }
+
+    /// Close the auction once the window has ended: send the quote raised
+    /// to the treasury and unsold tokens back to the admin. Permissionless.
+    pub fn settle(env: Env, caller: Address) -> Result<Settlement, LaunchError> {
+        caller.require_auth();
+        let config = load_config(&env)?;
+        let now = env.ledger().timestamp();
+        if now < config.end {
+            return Err(LaunchError::NotEnded);
+        }
+        if env.storage().instance().has(&DataKey::Settlement) {
+            return Err(LaunchError::AlreadySettled);
+        }
+
+        let totals = Self::totals(env.clone());
+        let sold = totals.demand.min(config.sale_amount);
+        // Refunds round down, so rounding the raise down still leaves
+        // enough to pay all of them
+        let raised = if totals.demand == 0 {
+            0
+        } else {
+            mul_div(totals.committed, sold, totals.demand).ok_or(LaunchError::InvalidAmount)?
+        };
+        let settlement = Settlement {
+            sold,
+            raised,
+            settled_at: now,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Settlement, &settlement);
+
+        let this = env.current_contract_address();
+        if raised > 0 {
+            token::Client::new(&env, &config.quote_token).transfer(
+                &this,
+                &config.treasury,
+                &raised,
+            );
+        }
+        if sold < config.sale_amount {
+            token::Client::new(&env, &config.sale_token).transfer(
+                &this,
+                &config.admin,
+                &(config.sale_amount - sold),
+            );
+        }
+
+        EventEmitter::launch_settled(
+            &env,
+            LaunchSettledEvent {
+                settled_by: caller,
+                committed: totals.committed,
+                demand: totals.demand,
+                sold,
+                raised,
+                timestamp: now,
+            },
+        );
+        Ok(settlement)
+    }
+
+    /// Claim `user`'s sale tokens and the unused part of their commitment.
+    /// Returns `(tokens, refund)`.
+    pub fn claim(env: Env, user: Address) -> Result<(i128, i128), LaunchError> {
+        user.require_auth();
+        let config = load_config(&env)?;
+        let settlement = Self::settlement(env.clone()).ok_or(LaunchError::NotSettled)?;
+        let mut commitment = Self::commitment(env.clone(), user.clone());
+        if commitment.committed == 0 || commitment.claimed {
+            return Err(LaunchError::NothingToClaim);
+        }
+
+        let (tokens, refund) = Self::claimable(&env, &commitment, settlement.sold)?;
+        commitment.claimed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Commitment(user.clone()), &commitment);
+
+        let this = env.current_contract_address();
+        if tokens > 0 {
+            token::Client::new(&env, &config.sale_token).transfer(&this, &user, &tokens);
+        }
+        if refund > 0 {
+            token::Client::new(&env, &config.quote_token).transfer(&this, &user, &refund);
+        }
+
+        EventEmitter::launch_claimed(
+            &env,
+            LaunchClaimEvent {
+                user,
+                tokens,
+                refund,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok((tokens, refund))
+    }
+
+    /// Price a commitment would pay now
+    pub fn current_price(env: Env) -> Result<i128, LaunchError> {
+        let config = load_config(&env)?;
+        let demand = Self::totals(env.clone()).demand;
+        config
+            .pricing
+            .price(config.start, config.end, env.ledger().timestamp(), demand)
+    }
+
+    pub fn config(env: Env) -> Result<LaunchConfig, LaunchError> {
+        load_config(&env)
+    }
+
+    pub fn totals(env: Env) -> Totals {
+        env.storage()
+            .instance()
+            .get(&DataKey::Totals)
+            .unwrap_or_default()
+    }
+
+    pub fn commitment(env: Env, user: Address) -> Commitment {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Commitment(user))
+            .unwrap_or_default()
+    }
+
+    pub fn settlement(env: Env) -> Option<Settlement> {
+        env.storage().instance().get(&DataKey::Settlement)
+    }
+}
+
+impl LaunchAuction {
+    /// Tokens and refund owed on `commitment` once `sold` tokens have been
+    /// split pro rata across all bids
+    fn claimable(
+        env: &Env,
+        commitment: &Commitment,
+        sold: i128,
+    ) -> Result<(i128, i128), LaunchError> {
+        let totals = Self::totals(env.clone());
+        if sold >= totals.demand {
+            return Ok((commitment.demand, 0));
+        }
+        let tokens =
+            mul_div(commitment.demand, sold, totals.demand).ok_or(LaunchError::InvalidAmount)?;
+        let unfilled = totals.demand - sold;
+        // Refund the unfilled share of the commitment, rounded down
+        let refund = mul_div(commitment.committed, unfilled, totals.demand)
+            .ok_or(LaunchError::InvalidAmount)?;
+        Ok((tokens, refund))
+    }
+}
+
+fn load_config(env: &Env) -> Result<LaunchConfig, LaunchError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(LaunchError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,139 @@
+//! Sale token pricing for the two auction modes
+//!
+//! Prices are quote per sale token, scaled by [`PRICE_SCALE`].
+
+use shared::math::mul_div;
+use soroban_sdk::{contracttype, Vec};
+
+use crate::LaunchError;
+
+/// Fixed-point scale of prices
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+/// A block of sale tokens offered at one price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tranche {
+    pub amount: i128,
+    pub price: i128,
+}
+
+/// Bounds of a [`Pricing::Declining`] sale
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Declining {
+    pub start_price: i128,
+    pub end_price: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Pricing {
+    /// The price falls linearly from `start_price` at the start of the
+    /// window to `end_price` at its end, so early commitments pay more and
+    /// waiting is a bet that others will not bid the sale out
+    Declining(Declining),
+    /// Tranches are sold in order, each at its own fixed price; the sale
+    /// amount is their total and commitments beyond it are refused
+    Tranches(Vec<Tranche>),
+}
+
+impl Pricing {
+    pub fn is_valid(&self, sale_amount: i128) -> bool {
+        match self {
+            Pricing::Declining(bounds) => {
+                bounds.end_price > 0 && bounds.start_price >= bounds.end_price
+            }
+            Pricing::Tranches(tranches) => {
+                let mut total = 0i128;
+                for tranche in tranches.iter() {
+                    if tranche.amount <= 0 || tranche.price <= 0 {
+                        return false;
+                    }
+                    total = match total.checked_add(tranche.amount) {
+                        Some(total) => total,
+                        None => return false,
+                    };
+                }
+                !tranches.is_empty() && total == sale_amount
+            }
+        }
+    }
+
+    /// Current price: by time for [`Pricing::Declining`], by the tokens
+    /// already bid for (`demand`) for [`Pricing::Tranches`]
+    pub fn price(&self, start: u64, end: u64, now: u64, demand: i128) -> Result<i128, LaunchError> {
+        match self {
+            Pricing::Declining(bounds) => {
+                let elapsed = now.clamp(start, end) - start;
+                let drop = mul_div(
+                    bounds.start_price - bounds.end_price,
+                    elapsed as i128,
+                    (end - start) as i128,
+                )
+                .ok_or(LaunchError::InvalidAmount)?;
+                Ok(bounds.start_price - drop)
+            }
+            Pricing::Tranches(tranches) => {
+                let mut sold_through = 0i128;
+                for tranche in tranches.iter() {
+                    sold_through += tranche.amount;
+                    if demand < sold_through {
+                        return Ok(tranche.price);
+                    }
+                }
+                Err(LaunchError::SoldOut)
+            }
+        }
+    }
+
+    /// Sale tokens `amount` of quote buys, given `demand` tokens already
+    /// bid for. Tranche purchases may span several tranches; quote left
+    /// over after the last one fails the purchase.
+    pub fn demand_for(
+        &self,
+        start: u64,
+        end: u64,
+        now: u64,
+        demand: i128,
+        amount: i128,
+    ) -> Result<i128, LaunchError> {
+        match self {
+            Pricing::Declining(_) => {
+                let price = self.price(start, end, now, demand)?;
+                mul_div(amount, PRICE_SCALE, price).ok_or(LaunchError::InvalidAmount)
+            }
+            Pricing::Tranches(tranches) => {
+                let mut remaining = amount;
+                let mut bought = 0i128;
+                let mut sold_through = 0i128;
+                for tranche in tranches.iter() {
+                    sold_through += tranche.amount;
+                    let taken = (demand + bought).max(sold_through - tranche.amount);
+                    let available = sold_through - taken;
+                    if available <= 0 {
+                        continue;
+                    }
+                    // Cost of the rest of the tranche, rounded up
+                    let cost = available
+                        .checked_mul(tranche.price)
+                        .map(|value| (value + PRICE_SCALE - 1) / PRICE_SCALE)
+                        .ok_or(LaunchError::InvalidAmount)?;
+                    if remaining >= cost {
+                        bought += available;
+                        remaining -= cost;
+                    } else {
+                        bought += mul_div(remaining, PRICE_SCALE, tranche.price)
+                            .ok_or(LaunchError::InvalidAmount)?;
+                        remaining = 0;
+                        break;
+                    }
+                }
+                if remaining > 0 {
+                    return Err(LaunchError::SoldOut);
+                }
+                Ok(bought)
+            }
+        }
+    }
+}
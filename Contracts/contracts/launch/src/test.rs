@@ -0,0 +1,219 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Vec};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const START: u64 = 1_000;
+const END: u64 = 2_000;
+const P: i128 = PRICE_SCALE;
+
+struct Setup {
+    env: Env,
+    client: LaunchAuctionClient<'static>,
+    admin: Address,
+    treasury: Address,
+    sale: TestToken<'static>,
+    quote: TestToken<'static>,
+    users: [Address; 2],
+}
+
+fn setup(pricing: impl FnOnce(&Env) -> Pricing) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let sale = stellar_asset(&env);
+    let quote = stellar_asset(&env);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    sale.mint(&admin, 1_000);
+    let users = [Address::generate(&env), Address::generate(&env)];
+    for user in users.iter() {
+        quote.mint(user, 10_000);
+    }
+
+    let client = LaunchAuctionClient::new(&env, &env.register_contract(None, LaunchAuction));
+    client.initialize(&LaunchConfig {
+        admin: admin.clone(),
+        sale_token: sale.address.clone(),
+        quote_token: quote.address.clone(),
+        treasury: treasury.clone(),
+        sale_amount: 1_000,
+        start: START,
+        end: END,
+        pricing: pricing(&env),
+    });
+
+    Setup {
+        env,
+        client,
+        admin,
+        treasury,
+        sale,
+        quote,
+        users,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn tranches(env: &Env) -> Pricing {
+    let tranches: Vec<Tranche> = vec![
+        env,
+        Tranche {
+            amount: 500,
+            price: P,
+        },
+        Tranche {
+            amount: 500,
+            price: 2 * P,
+        },
+    ];
+    Pricing::Tranches(tranches)
+}
+
+#[test]
+fn test_oversubscribed_declining_sale_fills_pro_rata() {
+    let s = setup(|_| {
+        Pricing::Declining(Declining {
+            start_price: 2 * P,
+            end_price: P,
+        })
+    });
+    let [alice, bob] = s.users.clone();
+    assert_eq!(s.sale.balance(&s.client.address), 1_000);
+
+    assert_eq!(s.client.commit(&alice, &1_200), 600);
+    set_time(&s.env, 1_500);
+    assert_eq!(s.client.current_price(), 3 * P / 2);
+    assert_eq!(s.client.commit(&bob, &900), 600);
+    let captured = assert_standard_event(&s.env, topics::LAUNCH_COMMIT, |_| true);
+    let event: LaunchCommitEvent = payload(&captured);
+    assert_eq!(
+        (event.amount, event.demand, event.price),
+        (900, 600, 3 * P / 2)
+    );
+
+    set_time(&s.env, END);
+    let result = s.client.try_commit(&alice, &100);
+    assert_eq!(result, Err(Ok(LaunchError::Ended)));
+    let settlement = s.client.settle(&bob);
+    assert_eq!((settlement.sold, settlement.raised), (1_000, 1_750));
+    assert_eq!(s.quote.balance(&s.treasury), 1_750);
+    let captured = assert_standard_event(&s.env, topics::LAUNCH_SETTLED, |_| true);
+    let event: LaunchSettledEvent = payload(&captured);
+    assert_eq!((event.committed, event.demand), (2_100, 1_200));
+
+    // 1_200 demand for 1_000 tokens: each bid fills 5/6
+    assert_eq!(s.client.claim(&alice), (500, 200));
+    assert_eq!(s.client.claim(&bob), (500, 150));
+    let captured = assert_standard_event(&s.env, topics::LAUNCH_CLAIM, |_| true);
+    let event: LaunchClaimEvent = payload(&captured);
+    assert_eq!((event.tokens, event.refund), (500, 150));
+    assert_eq!(s.sale.balance(&alice), 500);
+    assert_eq!(s.quote.balance(&alice), 10_000 - 1_200 + 200);
+    assert_eq!(s.sale.balance(&s.client.address), 0);
+    assert_eq!(s.quote.balance(&s.client.address), 0);
+
+    let result = s.client.try_claim(&alice);
+    assert_eq!(result, Err(Ok(LaunchError::NothingToClaim)));
+}
+
+#[test]
+fn test_tranches_sell_in_order_and_return_unsold() {
+    let s = setup(tranches);
+    let [alice, bob] = s.users.clone();
+
+    // 500 at 1, then 100 at 2
+    assert_eq!(s.client.commit(&alice, &700), 600);
+    assert_eq!(s.client.current_price(), 2 * P);
+    // Only 400 tokens (800 quote) remain
+    let result = s.client.try_commit(&bob, &1_000);
+    assert_eq!(result, Err(Ok(LaunchError::SoldOut)));
+
+    set_time(&s.env, END);
+    let settlement = s.client.settle(&bob);
+    assert_eq!((settlement.sold, settlement.raised), (600, 700));
+    assert_eq!(s.sale.balance(&s.admin), 400);
+    assert_eq!(s.quote.balance(&s.treasury), 700);
+    assert_eq!(s.client.claim(&alice), (600, 0));
+
+    let result = s.client.try_claim(&bob);
+    assert_eq!(result, Err(Ok(LaunchError::NothingToClaim)));
+}
+
+#[test]
+fn test_tranches_sell_out() {
+    let s = setup(tranches);
+    let [alice, bob] = s.users.clone();
+
+    s.client.commit(&alice, &700);
+    assert_eq!(s.client.commit(&bob, &800), 400);
+    let result = s.client.try_current_price();
+    assert_eq!(result, Err(Ok(LaunchError::SoldOut)));
+    let result = s.client.try_commit(&bob, &1);
+    assert_eq!(result, Err(Ok(LaunchError::SoldOut)));
+
+    set_time(&s.env, END);
+    let settlement = s.client.settle(&alice);
+    assert_eq!((settlement.sold, settlement.raised), (1_000, 1_500));
+    assert_eq!(s.client.claim(&bob), (400, 0));
+}
+
+#[test]
+fn test_window_and_settlement_order() {
+    let s = setup(|_| {
+        Pricing::Declining(Declining {
+            start_price: P,
+            end_price: P,
+        })
+    });
+    let [alice, _] = s.users.clone();
+
+    set_time(&s.env, START - 1);
+    let result = s.client.try_commit(&alice, &100);
+    assert_eq!(result, Err(Ok(LaunchError::NotStarted)));
+    set_time(&s.env, START);
+    s.client.commit(&alice, &100);
+
+    let result = s.client.try_settle(&alice);
+    assert_eq!(result, Err(Ok(LaunchError::NotEnded)));
+    let result = s.client.try_claim(&alice);
+    assert_eq!(result, Err(Ok(LaunchError::NotSettled)));
+
+    set_time(&s.env, END);
+    s.client.settle(&alice);
+    let result = s.client.try_settle(&alice);
+    assert_eq!(result, Err(Ok(LaunchError::AlreadySettled)));
+    assert_eq!(s.sale.balance(&s.admin), 900);
+}
+
+#[test]
+fn test_tranches_must_cover_sale_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let sale = stellar_asset(&env);
+    let quote = stellar_asset(&env);
+    let admin = Address::generate(&env);
+    sale.mint(&admin, 1_000);
+    let client = LaunchAuctionClient::new(&env, &env.register_contract(None, LaunchAuction));
+
+    let config = LaunchConfig {
+        admin,
+        sale_token: sale.address.clone(),
+        quote_token: quote.address.clone(),
+        treasury: Address::generate(&env),
+        sale_amount: 900,
+        start: START,
+        end: END,
+        pricing: tranches(&env),
+    };
+    let result = client.try_initialize(&config);
+    assert_eq!(result, Err(Ok(LaunchError::InvalidConfig)));
+}
@@ -0,0 +1,132 @@
+//! Utilization-based interest rate model
+//!
+//! Each market tracks deposits and borrows as scaled balances against a
+//! supply and a borrow index. Accrual grows the borrow index by the borrow
+//! rate for the elapsed time and passes the resulting interest on to
+//! depositors through the supply index, so individual positions never have
+//! to be touched to earn or owe interest.
+
+use soroban_sdk::{contracttype, Env};
+
+/// Fixed-point scale for the supply and borrow indices
+pub const INDEX_SCALE: i128 = 1_000_000_000;
+/// Basis point denominator
+pub const BPS_DENOMINATOR: i128 = 10_000;
+pub const SECONDS_PER_YEAR: i128 = 31_536_000;
+
+/// Per-asset risk and rate parameters. Rates are annual, in basis points.
+///
+/// Below `kink_bps` utilization the borrow rate is
+/// `base_rate_bps + utilization × slope_bps`; above it the excess
+/// utilization is charged at `jump_slope_bps` instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetConfig {
+    /// Share of the deposit value that counts towards borrowing power
+    pub collateral_factor_bps: u32,
+    pub base_rate_bps: u32,
+    pub slope_bps: u32,
+    pub jump_slope_bps: u32,
+    pub kink_bps: u32,
+}
+
+impl AssetConfig {
+    pub fn is_valid(&self) -> bool {
+        self.collateral_factor_bps < BPS_DENOMINATOR as u32
+            && self.kink_bps <= BPS_DENOMINATOR as u32
+    }
+}
+
+/// Aggregate state of a single asset market
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Market {
+    pub total_supply_scaled: i128,
+    pub total_borrow_scaled: i128,
+    pub supply_index: i128,
+    pub borrow_index: i128,
+    pub last_accrual: u64,
+}
+
+impl Market {
+    pub fn new(now: u64) -> Self {
+        Market {
+            total_supply_scaled: 0,
+            total_borrow_scaled: 0,
+            supply_index: INDEX_SCALE,
+            borrow_index: INDEX_SCALE,
+            last_accrual: now,
+        }
+    }
+
+    pub fn total_deposits(&self) -> i128 {
+        self.total_supply_scaled * self.supply_index / INDEX_SCALE
+    }
+
+    pub fn total_borrows(&self) -> i128 {
+        self.total_borrow_scaled * self.borrow_index / INDEX_SCALE
+    }
+
+    /// Share of deposits currently lent out, in basis points
+    pub fn utilization_bps(&self) -> i128 {
+        let deposits = self.total_deposits();
+        if deposits == 0 {
+            return 0;
+        }
+        (self.total_borrows() * BPS_DENOMINATOR / deposits).min(BPS_DENOMINATOR)
+    }
+}
+
+/// Annual borrow rate in basis points for the given utilization
+pub fn borrow_rate_bps(config: &AssetConfig, utilization_bps: i128) -> i128 {
+    let base = config.base_rate_bps as i128;
+    let slope = config.slope_bps as i128;
+    let kink = config.kink_bps as i128;
+
+    if utilization_bps <= kink {
+        base + utilization_bps * slope / BPS_DENOMINATOR
+    } else {
+        let excess = utilization_bps - kink;
+        base + kink * slope / BPS_DENOMINATOR
+            + excess * config.jump_slope_bps as i128 / BPS_DENOMINATOR
+    }
+}
+
+/// Bring `market` up to the current ledger time
+pub fn accrue(env: &Env, config: &AssetConfig, market: &mut Market) {
+    let now = env.ledger().timestamp();
+    let elapsed = now.saturating_sub(market.last_accrual) as i128;
+    market.last_accrual = now;
+    if elapsed == 0 || market.total_borrow_scaled == 0 {
+        return;
+    }
+
+    let rate = borrow_rate_bps(config, market.utilization_bps());
+    let growth = INDEX_SCALE * rate * elapsed / (BPS_DENOMINATOR * SECONDS_PER_YEAR);
+    if growth == 0 {
+        return;
+    }
+
+    let deposits_before = market.total_deposits();
+    let borrows_before = market.total_borrows();
+    market.borrow_index = market.borrow_index * (INDEX_SCALE + growth) / INDEX_SCALE;
+    let interest = market.total_borrows() - borrows_before;
+
+    if deposits_before > 0 {
+        market.supply_index = market.supply_index * (deposits_before + interest) / deposits_before;
+    }
+}
+
+/// Scaled units for `amount`, rounded down (used when crediting)
+pub fn to_scaled_down(amount: i128, index: i128) -> i128 {
+    amount * INDEX_SCALE / index
+}
+
+/// Scaled units for `amount`, rounded up (used when debiting)
+pub fn to_scaled_up(amount: i128, index: i128) -> i128 {
+    (amount * INDEX_SCALE + index - 1) / index
+}
+
+pub fn from_scaled(scaled: i128, index: i128) -> i128 {
+    scaled * index / INDEX_SCALE
+}
@@ -0,0 +1,654 @@
+//! Collateralized lending market
+//!
+//! Users deposit listed assets to earn interest and may borrow any listed
+//! asset against the collateral-factor-weighted value of their deposits.
+//! Prices are pushed by a designated oracle account; interest accrues per
+//! asset through a utilization-based rate model (see `interest`). The
+//! collateral of accounts whose health factor drops below one is sold off in
+//! descending-price auctions (see `liquidation`).
+//!
+//! Oracle prices pass through a circuit breaker. While an asset's breaker is
+//! tripped its price is treated as unavailable, so borrowing, withdrawing
+//! against debt and starting auctions that need it revert, while deposits,
+//! repayments and bids on running auctions carry on.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::auction::{Auction, AuctionParams};
+use shared::circuit_breaker::{BreakerConfig, BreakerState, CircuitBreaker, PriceFeed};
+use shared::events::{namespaces, EventEmitter, LendingActionEvent};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env, Vec};
+
+mod interest;
+mod liquidation;
+
+pub use interest::{AssetConfig, Market, INDEX_SCALE};
+
+/// Fixed-point scale for oracle prices and health factors
+pub const PRICE_SCALE: i128 = 10_000_000;
+/// Prices older than this are rejected
+pub const MAX_PRICE_AGE: u64 = 3_600;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LendingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    AssetNotListed = 4,
+    AssetAlreadyListed = 5,
+    InvalidAmount = 6,
+    InvalidConfig = 7,
+    PriceUnavailable = 8,
+    InsufficientLiquidity = 9,
+    InsufficientCollateral = 10,
+    InsufficientBalance = 11,
+    PositionHealthy = 12,
+    AuctionNotFound = 13,
+    AuctionExpired = 14,
+    AuctionActive = 15,
+    PriceTooHigh = 16,
+    CircuitBreakerTripped = 17,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Oracle,
+    Assets,
+    Config(Address),
+    Market(Address),
+    Price(Address),
+    Deposit(Address, Address),
+    Debt(Address, Address),
+    AuctionParams,
+    NextAuctionId,
+    Auction(u64),
+    /// Collateral of an asset held for running auctions
+    Reserved(Address),
+    /// Auction tabs in an asset left uncovered when their lot sold out
+    BadDebt(Address),
+}
+
+/// Last price reported by the oracle, scaled by `PRICE_SCALE`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Valuation of an account across all markets
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountHealth {
+    /// Σ deposit value × collateral factor
+    pub collateral_value: i128,
+    /// Σ debt value
+    pub debt_value: i128,
+    /// collateral_value / debt_value scaled by `PRICE_SCALE`; `i128::MAX`
+    /// without debt
+    pub health_factor: i128,
+}
+
+#[contract]
+pub struct LendingContract;
+
+#[contractimpl]
+impl LendingContract {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), LendingError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(LendingError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        EventEmitter::init(&env, namespaces::LENDING);
+        Ok(())
+    }
+
+    /// List a new asset with its risk and rate parameters (admin only)
+    pub fn list_asset(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        config: AssetConfig,
+    ) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        if !config.is_valid() {
+            return Err(LendingError::InvalidConfig);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Config(asset.clone()))
+        {
+            return Err(LendingError::AssetAlreadyListed);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Config(asset.clone()), &config);
+        env.storage().persistent().set(
+            &DataKey::Market(asset.clone()),
+            &Market::new(env.ledger().timestamp()),
+        );
+
+        let mut assets = Self::get_assets(env.clone());
+        assets.push_back(asset);
+        env.storage().instance().set(&DataKey::Assets, &assets);
+        Ok(())
+    }
+
+    /// Update the parameters of a listed asset (admin only). Interest is
+    /// accrued at the old rate first.
+    pub fn set_asset_config(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        config: AssetConfig,
+    ) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        if !config.is_valid() {
+            return Err(LendingError::InvalidConfig);
+        }
+        let market = load_market(&env, &asset)?;
+        save_market(&env, &asset, &market);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Config(asset), &config);
+        Ok(())
+    }
+
+    /// Report the price of `asset` (oracle only)
+    pub fn set_price(
+        env: Env,
+        oracle: Address,
+        asset: Address,
+        price: i128,
+    ) -> Result<(), LendingError> {
+        oracle.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .ok_or(LendingError::NotInitialized)?;
+        if stored != oracle {
+            return Err(LendingError::Unauthorized);
+        }
+        if price <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        load_config(&env, &asset)?;
+
+        let data = PriceData {
+            price,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Price(asset.clone()), &data);
+        CircuitBreaker::record(&env, &PriceFeed::Asset(asset), price);
+        Ok(())
+    }
+
+    /// Install or, with `None`, disable the price circuit breaker
+    pub fn set_circuit_breaker(
+        env: Env,
+        admin: Address,
+        config: Option<BreakerConfig>,
+    ) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        if config.as_ref().is_some_and(|c| !c.is_valid()) {
+            return Err(LendingError::InvalidConfig);
+        }
+        CircuitBreaker::set_config(&env, config);
+        Ok(())
+    }
+
+    /// Clear a tripped breaker on `asset` before its cooldown ends, taking
+    /// the last reported price as the new reference
+    pub fn reset_circuit_breaker(
+        env: Env,
+        admin: Address,
+        asset: Address,
+    ) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        let data =
+            Self::get_price(env.clone(), asset.clone()).ok_or(LendingError::PriceUnavailable)?;
+        CircuitBreaker::reset(&env, &PriceFeed::Asset(asset), data.price);
+        Ok(())
+    }
+
+    /// Supply `amount` of `asset`; deposits earn interest and count as
+    /// collateral
+    pub fn deposit(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        let mut market = load_market(&env, &asset)?;
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &amount);
+
+        let scaled = interest::to_scaled_down(amount, market.supply_index);
+        market.total_supply_scaled += scaled;
+        save_market(&env, &asset, &market);
+        let key = DataKey::Deposit(asset.clone(), user.clone());
+        set_scaled(&env, &key, get_scaled(&env, &key) + scaled);
+
+        let price = read_price(&env, &asset).unwrap_or(0);
+        EventEmitter::lending_deposited(&env, action_event(&env, user, asset, amount, price));
+        Ok(())
+    }
+
+    /// Withdraw `amount` of deposited `asset`. The account must remain
+    /// healthy afterwards.
+    pub fn withdraw(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        let mut market = load_market(&env, &asset)?;
+        let key = DataKey::Deposit(asset.clone(), user.clone());
+        let balance = get_scaled(&env, &key);
+        if interest::from_scaled(balance, market.supply_index) < amount {
+            return Err(LendingError::InsufficientBalance);
+        }
+        check_cash(&env, &asset, amount)?;
+
+        let scaled = interest::to_scaled_up(amount, market.supply_index).min(balance);
+        market.total_supply_scaled -= scaled;
+        save_market(&env, &asset, &market);
+        set_scaled(&env, &key, balance - scaled);
+
+        require_healthy(&env, &user)?;
+
+        token::Client::new(&env, &asset).transfer(&env.current_contract_address(), &user, &amount);
+
+        let price = read_price(&env, &asset).unwrap_or(0);
+        EventEmitter::lending_withdrawn(&env, action_event(&env, user, asset, amount, price));
+        Ok(())
+    }
+
+    /// Borrow `amount` of `asset` against the account's collateral
+    pub fn borrow(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        let mut market = load_market(&env, &asset)?;
+        check_cash(&env, &asset, amount)?;
+
+        let scaled = interest::to_scaled_up(amount, market.borrow_index);
+        market.total_borrow_scaled += scaled;
+        save_market(&env, &asset, &market);
+        let key = DataKey::Debt(asset.clone(), user.clone());
+        set_scaled(&env, &key, get_scaled(&env, &key) + scaled);
+
+        require_healthy(&env, &user)?;
+
+        token::Client::new(&env, &asset).transfer(&env.current_contract_address(), &user, &amount);
+
+        let price = read_price(&env, &asset)?;
+        EventEmitter::borrowed(&env, action_event(&env, user, asset, amount, price));
+        Ok(())
+    }
+
+    /// Repay up to `amount` of the caller's debt in `asset`. Returns the
+    /// amount actually repaid.
+    pub fn repay(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, LendingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        let mut market = load_market(&env, &asset)?;
+        let key = DataKey::Debt(asset.clone(), user.clone());
+        let debt_scaled = get_scaled(&env, &key);
+        let debt = interest::from_scaled(debt_scaled, market.borrow_index);
+        if debt == 0 {
+            return Err(LendingError::InsufficientBalance);
+        }
+
+        let (repaid, scaled) = if amount >= debt {
+            (debt, debt_scaled)
+        } else {
+            (
+                amount,
+                interest::to_scaled_down(amount, market.borrow_index),
+            )
+        };
+
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &repaid);
+        market.total_borrow_scaled -= scaled;
+        save_market(&env, &asset, &market);
+        set_scaled(&env, &key, debt_scaled - scaled);
+
+        let price = read_price(&env, &asset).unwrap_or(0);
+        EventEmitter::repaid(&env, action_event(&env, user, asset, repaid, price));
+        Ok(repaid)
+    }
+
+    /// Put an underwater borrower's whole `collateral_asset` deposit up for
+    /// auction against the `debt_asset` debt it covers. Returns the auction
+    /// id.
+    pub fn start_auction(
+        env: Env,
+        borrower: Address,
+        debt_asset: Address,
+        collateral_asset: Address,
+    ) -> Result<u64, LendingError> {
+        liquidation::start(&env, &borrower, &debt_asset, &collateral_asset)
+    }
+
+    /// Pay the debt asset for up to `max_collateral` of an auction's lot at
+    /// its current price, failing if that price exceeds `max_price`.
+    /// Returns `(collateral, paid)`.
+    pub fn take(
+        env: Env,
+        taker: Address,
+        auction_id: u64,
+        max_collateral: i128,
+        max_price: i128,
+    ) -> Result<(i128, i128), LendingError> {
+        taker.require_auth();
+        liquidation::take(&env, &taker, auction_id, max_collateral, max_price)
+    }
+
+    /// Restart an expired auction from current oracle prices
+    pub fn reset_auction(env: Env, auction_id: u64) -> Result<(), LendingError> {
+        liquidation::reset(&env, auction_id)
+    }
+
+    pub fn set_auction_params(
+        env: Env,
+        admin: Address,
+        params: AuctionParams,
+    ) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        if !params.is_valid() {
+            return Err(LendingError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuctionParams, &params);
+        Ok(())
+    }
+
+    /// Current deposit balance of `user` in `asset`, including interest
+    pub fn deposit_balance(env: Env, asset: Address, user: Address) -> i128 {
+        match load_market(&env, &asset) {
+            Ok(market) => interest::from_scaled(
+                get_scaled(&env, &DataKey::Deposit(asset, user)),
+                market.supply_index,
+            ),
+            Err(_) => 0,
+        }
+    }
+
+    /// Current debt of `user` in `asset`, including interest
+    pub fn debt_balance(env: Env, asset: Address, user: Address) -> i128 {
+        match load_market(&env, &asset) {
+            Ok(market) => interest::from_scaled(
+                get_scaled(&env, &DataKey::Debt(asset, user)),
+                market.borrow_index,
+            ),
+            Err(_) => 0,
+        }
+    }
+
+    pub fn get_account(env: Env, user: Address) -> Result<AccountHealth, LendingError> {
+        account_health(&env, &user)
+    }
+
+    /// Market state accrued to the current ledger time
+    pub fn get_market(env: Env, asset: Address) -> Result<Market, LendingError> {
+        load_market(&env, &asset)
+    }
+
+    pub fn get_asset_config(env: Env, asset: Address) -> Result<AssetConfig, LendingError> {
+        load_config(&env, &asset)
+    }
+
+    /// Current annual borrow rate for `asset`, in basis points
+    pub fn get_borrow_rate(env: Env, asset: Address) -> Result<i128, LendingError> {
+        let config = load_config(&env, &asset)?;
+        let market = load_market(&env, &asset)?;
+        Ok(interest::borrow_rate_bps(&config, market.utilization_bps()))
+    }
+
+    pub fn get_price(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().persistent().get(&DataKey::Price(asset))
+    }
+
+    pub fn get_auction(env: Env, auction_id: u64) -> Result<Auction, LendingError> {
+        liquidation::load(&env, auction_id)
+    }
+
+    /// Current price of an auction's collateral in units of its debt asset,
+    /// scaled by `PRICE_SCALE`
+    pub fn get_auction_price(env: Env, auction_id: u64) -> Result<i128, LendingError> {
+        let auction = liquidation::load(&env, auction_id)?;
+        Ok(auction.price(&liquidation::params(&env), env.ledger().timestamp()))
+    }
+
+    pub fn get_auction_params(env: Env) -> AuctionParams {
+        liquidation::params(&env)
+    }
+
+    /// Debt in `asset` that auctions failed to cover
+    pub fn get_bad_debt(env: Env, asset: Address) -> i128 {
+        get_scaled(&env, &DataKey::BadDebt(asset))
+    }
+
+    pub fn get_circuit_breaker(env: Env) -> Option<BreakerConfig> {
+        CircuitBreaker::config(&env)
+    }
+
+    pub fn get_breaker_state(env: Env, asset: Address) -> Option<BreakerState> {
+        CircuitBreaker::state(&env, &PriceFeed::Asset(asset))
+    }
+
+    pub fn get_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Assets)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), LendingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), LendingError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(LendingError::NotInitialized)?;
+    if stored != *admin {
+        return Err(LendingError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_config(env: &Env, asset: &Address) -> Result<AssetConfig, LendingError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Config(asset.clone()))
+        .ok_or(LendingError::AssetNotListed)
+}
+
+/// Load a market with interest accrued up to now. Callers that change the
+/// market must `save_market` it so the accrual is persisted.
+fn load_market(env: &Env, asset: &Address) -> Result<Market, LendingError> {
+    let config = load_config(env, asset)?;
+    let mut market: Market = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Market(asset.clone()))
+        .ok_or(LendingError::AssetNotListed)?;
+    interest::accrue(env, &config, &mut market);
+    Ok(market)
+}
+
+fn save_market(env: &Env, asset: &Address, market: &Market) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Market(asset.clone()), market);
+}
+
+fn get_scaled(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn set_scaled(env: &Env, key: &DataKey, value: i128) {
+    if value == 0 {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, &value);
+    }
+}
+
+/// Fresh oracle price for `asset`, unless its circuit breaker is tripped
+fn read_price(env: &Env, asset: &Address) -> Result<i128, LendingError> {
+    if !CircuitBreaker::check(env, &PriceFeed::Asset(asset.clone())) {
+        return Err(LendingError::CircuitBreakerTripped);
+    }
+    let data: PriceData = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Price(asset.clone()))
+        .ok_or(LendingError::PriceUnavailable)?;
+    if env.ledger().timestamp() > data.timestamp + MAX_PRICE_AGE {
+        return Err(LendingError::PriceUnavailable);
+    }
+    Ok(data.price)
+}
+
+fn check_cash(env: &Env, asset: &Address, amount: i128) -> Result<(), LendingError> {
+    let cash = token::Client::new(env, asset).balance(&env.current_contract_address())
+        - liquidation::reserved(env, asset);
+    if cash < amount {
+        return Err(LendingError::InsufficientLiquidity);
+    }
+    Ok(())
+}
+
+fn account_health(env: &Env, user: &Address) -> Result<AccountHealth, LendingError> {
+    let mut collateral_value = 0;
+    let mut debt_value = 0;
+
+    for asset in LendingContract::get_assets(env.clone()).iter() {
+        let deposit_scaled = get_scaled(env, &DataKey::Deposit(asset.clone(), user.clone()));
+        let debt_scaled = get_scaled(env, &DataKey::Debt(asset.clone(), user.clone()));
+        if deposit_scaled == 0 && debt_scaled == 0 {
+            continue;
+        }
+
+        let config = load_config(env, &asset)?;
+        let market = load_market(env, &asset)?;
+        let price = read_price(env, &asset)?;
+
+        let deposit = interest::from_scaled(deposit_scaled, market.supply_index);
+        let debt = interest::from_scaled(debt_scaled, market.borrow_index);
+        collateral_value += deposit * price / PRICE_SCALE * config.collateral_factor_bps as i128
+            / interest::BPS_DENOMINATOR;
+        debt_value += debt * price / PRICE_SCALE;
+    }
+
+    let health_factor = if debt_value == 0 {
+        i128::MAX
+    } else {
+        collateral_value * PRICE_SCALE / debt_value
+    };
+
+    Ok(AccountHealth {
+        collateral_value,
+        debt_value,
+        health_factor,
+    })
+}
+
+fn has_debt(env: &Env, user: &Address) -> bool {
+    LendingContract::get_assets(env.clone())
+        .iter()
+        .any(|asset| get_scaled(env, &DataKey::Debt(asset, user.clone())) > 0)
+}
+
+fn require_healthy(env: &Env, user: &Address) -> Result<(), LendingError> {
+    if !has_debt(env, user) {
+        return Ok(());
+    }
+    if account_health(env, user)?.health_factor < PRICE_SCALE {
+        return Err(LendingError::InsufficientCollateral);
+    }
+    Ok(())
+}
+
+fn action_event(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    price: i128,
+) -> LendingActionEvent {
+    LendingActionEvent {
+        user,
+        asset,
+        amount,
+        price,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+#[cfg(test)]
+mod test;
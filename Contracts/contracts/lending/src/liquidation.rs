@@ -0,0 +1,248 @@
+//! Liquidation of underwater positions by Dutch auction
+//!
+//! Once an account's health factor drops below one, anyone may put its
+//! deposit in one collateral asset up for auction against its debt in one
+//! borrowed asset. The lot leaves the account's deposits together with as
+//! much of the debt as the oracle says it covers (the tab); the lot stays
+//! reserved in the pool until sold. Keepers pay the debt asset to buy the
+//! lot, in partial fills, at a price that starts above the oracle price and
+//! decays along the curve in `AuctionParams`. When the tab is covered,
+//! unsold collateral is deposited back for the borrower; when the lot runs
+//! out first, the rest of the tab is recorded as bad debt of the market.
+
+use shared::auction::{Auction, AuctionParams};
+use shared::events::{AuctionSettleEvent, AuctionStartEvent, AuctionTakeEvent, EventEmitter};
+use soroban_sdk::{token, Address, Env};
+
+use crate::interest;
+use crate::{
+    account_health, get_scaled, load_market, read_price, save_market, set_scaled, DataKey,
+    LendingError, PRICE_SCALE,
+};
+
+/// Auction `borrower`'s whole `collateral_asset` deposit against their
+/// `debt_asset` debt. Returns the auction id.
+pub fn start(
+    env: &Env,
+    borrower: &Address,
+    debt_asset: &Address,
+    collateral_asset: &Address,
+) -> Result<u64, LendingError> {
+    let health = account_health(env, borrower)?;
+    if health.health_factor >= PRICE_SCALE {
+        return Err(LendingError::PositionHealthy);
+    }
+
+    let mut collateral_market = load_market(env, collateral_asset)?;
+    let collateral_key = DataKey::Deposit(collateral_asset.clone(), borrower.clone());
+    let collateral_scaled = get_scaled(env, &collateral_key);
+    let lot = interest::from_scaled(collateral_scaled, collateral_market.supply_index);
+    if lot == 0 {
+        return Err(LendingError::InsufficientCollateral);
+    }
+    // Collateral price in units of the debt asset
+    let price = read_price(env, collateral_asset)? * PRICE_SCALE / read_price(env, debt_asset)?;
+
+    collateral_market.total_supply_scaled -= collateral_scaled;
+    save_market(env, collateral_asset, &collateral_market);
+    set_scaled(env, &collateral_key, 0);
+    add_reserved(env, collateral_asset, lot);
+
+    // Loaded after saving in case debt and collateral are the same asset
+    let mut debt_market = load_market(env, debt_asset)?;
+    let debt_key = DataKey::Debt(debt_asset.clone(), borrower.clone());
+    let debt_scaled = get_scaled(env, &debt_key);
+    let debt = interest::from_scaled(debt_scaled, debt_market.borrow_index);
+    if debt == 0 {
+        return Err(LendingError::InsufficientBalance);
+    }
+    let tab = debt.min((lot * price / PRICE_SCALE).max(1));
+    let tab_scaled = if tab == debt {
+        debt_scaled
+    } else {
+        interest::to_scaled_down(tab, debt_market.borrow_index)
+    };
+    debt_market.total_borrow_scaled -= tab_scaled;
+    save_market(env, debt_asset, &debt_market);
+    set_scaled(env, &debt_key, debt_scaled - tab_scaled);
+
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextAuctionId)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextAuctionId, &(id + 1));
+
+    let params = params(env);
+    let auction = Auction {
+        borrower: borrower.clone(),
+        collateral_asset: collateral_asset.clone(),
+        debt_asset: debt_asset.clone(),
+        lot,
+        tab,
+        start_price: params.start_price(price),
+        started_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), &auction);
+    emit_start(env, id, &auction, &params);
+    Ok(id)
+}
+
+/// Restart an auction that ran past its tail from current oracle prices
+pub fn reset(env: &Env, id: u64) -> Result<(), LendingError> {
+    let mut auction = load(env, id)?;
+    let params = params(env);
+    let now = env.ledger().timestamp();
+    if !auction.expired(&params, now) {
+        return Err(LendingError::AuctionActive);
+    }
+    let price = read_price(env, &auction.collateral_asset)? * PRICE_SCALE
+        / read_price(env, &auction.debt_asset)?;
+    auction.start_price = params.start_price(price);
+    auction.started_at = now;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), &auction);
+    emit_start(env, id, &auction, &params);
+    Ok(())
+}
+
+/// Pay the debt asset for up to `max_collateral` of auction `id`'s lot,
+/// provided the current price is at most `max_price`. Returns
+/// `(collateral, paid)`.
+pub fn take(
+    env: &Env,
+    taker: &Address,
+    id: u64,
+    max_collateral: i128,
+    max_price: i128,
+) -> Result<(i128, i128), LendingError> {
+    if max_collateral <= 0 {
+        return Err(LendingError::InvalidAmount);
+    }
+    let mut auction = load(env, id)?;
+    let params = params(env);
+    let now = env.ledger().timestamp();
+    if auction.expired(&params, now) {
+        return Err(LendingError::AuctionExpired);
+    }
+    let price = auction.price(&params, now);
+    if price > max_price {
+        return Err(LendingError::PriceTooHigh);
+    }
+    let (collateral, paid) = auction.fill(price, PRICE_SCALE, max_collateral);
+    if collateral <= 0 || paid <= 0 {
+        return Err(LendingError::InvalidAmount);
+    }
+
+    let this = env.current_contract_address();
+    token::Client::new(env, &auction.debt_asset).transfer(taker, &this, &paid);
+    add_reserved(env, &auction.collateral_asset, -collateral);
+    token::Client::new(env, &auction.collateral_asset).transfer(&this, taker, &collateral);
+    auction.lot -= collateral;
+    auction.tab -= paid;
+
+    EventEmitter::auction_taken(
+        env,
+        AuctionTakeEvent {
+            auction_id: id,
+            taker: taker.clone(),
+            collateral,
+            paid,
+            price,
+            lot: auction.lot,
+            tab: auction.tab,
+            timestamp: now,
+        },
+    );
+
+    if auction.is_done() {
+        settle(env, id, &auction)?;
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(id), &auction);
+    }
+    Ok((collateral, paid))
+}
+
+pub fn load(env: &Env, id: u64) -> Result<Auction, LendingError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Auction(id))
+        .ok_or(LendingError::AuctionNotFound)
+}
+
+pub fn params(env: &Env) -> AuctionParams {
+    env.storage()
+        .instance()
+        .get(&DataKey::AuctionParams)
+        .unwrap_or_default()
+}
+
+/// Collateral held for running auctions, which does not count as cash
+pub fn reserved(env: &Env, asset: &Address) -> i128 {
+    get_scaled(env, &DataKey::Reserved(asset.clone()))
+}
+
+fn add_reserved(env: &Env, asset: &Address, delta: i128) {
+    let key = DataKey::Reserved(asset.clone());
+    set_scaled(env, &key, get_scaled(env, &key) + delta);
+}
+
+/// Close a finished auction: deposit unsold collateral back for the
+/// borrower and book any uncovered tab as bad debt
+fn settle(env: &Env, id: u64, auction: &Auction) -> Result<(), LendingError> {
+    let returned = auction.lot.max(0);
+    if returned > 0 {
+        let asset = &auction.collateral_asset;
+        let mut market = load_market(env, asset)?;
+        let scaled = interest::to_scaled_down(returned, market.supply_index);
+        market.total_supply_scaled += scaled;
+        save_market(env, asset, &market);
+        let key = DataKey::Deposit(asset.clone(), auction.borrower.clone());
+        set_scaled(env, &key, get_scaled(env, &key) + scaled);
+        add_reserved(env, asset, -returned);
+    }
+    let uncovered = auction.tab.max(0);
+    if uncovered > 0 {
+        let key = DataKey::BadDebt(auction.debt_asset.clone());
+        set_scaled(env, &key, get_scaled(env, &key) + uncovered);
+    }
+    env.storage().persistent().remove(&DataKey::Auction(id));
+
+    EventEmitter::auction_closed(
+        env,
+        AuctionSettleEvent {
+            auction_id: id,
+            borrower: auction.borrower.clone(),
+            returned,
+            bad_debt: uncovered,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+fn emit_start(env: &Env, id: u64, auction: &Auction, params: &AuctionParams) {
+    EventEmitter::auction_started(
+        env,
+        AuctionStartEvent {
+            auction_id: id,
+            borrower: auction.borrower.clone(),
+            collateral_asset: auction.collateral_asset.clone(),
+            debt_asset: auction.debt_asset.clone(),
+            lot: auction.lot,
+            tab: auction.tab,
+            start_price: auction.start_price,
+            step: params.step,
+            cut_bps: params.cut_bps,
+            tail: params.tail,
+            timestamp: auction.started_at,
+        },
+    );
+}
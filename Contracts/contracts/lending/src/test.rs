@@ -0,0 +1,397 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, Symbol, TryFromVal,
+};
+
+struct Setup {
+    env: Env,
+    client: LendingContractClient<'static>,
+    usdc: TokenClient<'static>,
+    xlm: TokenClient<'static>,
+    admin: Address,
+    oracle: Address,
+    lender: Address,
+    borrower: Address,
+}
+
+fn config(collateral_factor_bps: u32) -> AssetConfig {
+    AssetConfig {
+        collateral_factor_bps,
+        base_rate_bps: 200,
+        slope_bps: 1_000,
+        jump_slope_bps: 10_000,
+        kink_bps: 8_000,
+    }
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    let usdc_id = env.register_stellar_asset_contract(admin.clone());
+    let xlm_id = env.register_stellar_asset_contract(admin.clone());
+    StellarAssetClient::new(&env, &usdc_id).mint(&lender, &1_000_000);
+    StellarAssetClient::new(&env, &usdc_id).mint(&borrower, &100_000);
+    StellarAssetClient::new(&env, &xlm_id).mint(&borrower, &1_000_000);
+
+    let contract_id = env.register_contract(None, LendingContract);
+    let client = LendingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &oracle);
+    client.list_asset(&admin, &usdc_id, &config(8_000));
+    client.list_asset(&admin, &xlm_id, &config(5_000));
+
+    // 1 USDC = 1.0, 1 XLM = 0.1
+    client.set_price(&oracle, &usdc_id, &PRICE_SCALE);
+    client.set_price(&oracle, &xlm_id, &(PRICE_SCALE / 10));
+
+    Setup {
+        client,
+        usdc: TokenClient::new(&env, &usdc_id),
+        xlm: TokenClient::new(&env, &xlm_id),
+        env,
+        admin,
+        oracle,
+        lender,
+        borrower,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    assert_eq!(
+        s.client.deposit_balance(&s.usdc.address, &s.lender),
+        500_000
+    );
+    assert_eq!(s.usdc.balance(&s.client.address), 500_000);
+
+    s.client.withdraw(&s.lender, &s.usdc.address, &200_000);
+    assert_eq!(
+        s.client.deposit_balance(&s.usdc.address, &s.lender),
+        300_000
+    );
+    assert_eq!(s.usdc.balance(&s.lender), 700_000);
+
+    let result = s.client.try_withdraw(&s.lender, &s.usdc.address, &300_001);
+    assert_eq!(result, Err(Ok(LendingError::InsufficientBalance)));
+}
+
+#[test]
+fn test_borrow_limited_by_collateral_factor() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    // 1_000_000 XLM at 0.1 with a 50% collateral factor = 50_000 borrowing power
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+
+    let result = s.client.try_borrow(&s.borrower, &s.usdc.address, &50_001);
+    assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
+
+    s.client.borrow(&s.borrower, &s.usdc.address, &40_000);
+    let health = s.client.get_account(&s.borrower);
+    assert_eq!(health.collateral_value, 50_000);
+    assert_eq!(health.debt_value, 40_000);
+    assert_eq!(health.health_factor, PRICE_SCALE * 5 / 4);
+
+    // Withdrawing collateral that backs the loan is rejected
+    let result = s.client.try_withdraw(&s.borrower, &s.xlm.address, &300_000);
+    assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
+    s.client.withdraw(&s.borrower, &s.xlm.address, &200_000);
+}
+
+#[test]
+fn test_interest_accrues_to_depositors() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &100_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+    s.client.borrow(&s.borrower, &s.usdc.address, &40_000);
+
+    // 40% utilization: 2% + 40% × 10% = 6% per year
+    assert_eq!(s.client.get_borrow_rate(&s.usdc.address), 600);
+
+    set_time(&s.env, 1_000 + interest::SECONDS_PER_YEAR as u64);
+    s.client.set_price(&s.oracle, &s.usdc.address, &PRICE_SCALE);
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE / 10));
+
+    let debt = s.client.debt_balance(&s.usdc.address, &s.borrower);
+    assert_eq!(debt, 42_400);
+    let deposit = s.client.deposit_balance(&s.usdc.address, &s.lender);
+    assert_eq!(deposit, 102_400);
+
+    // Repaying more than owed only takes the outstanding debt
+    let repaid = s.client.repay(&s.borrower, &s.usdc.address, &50_000);
+    assert_eq!(repaid, 42_400);
+    assert_eq!(s.client.debt_balance(&s.usdc.address, &s.borrower), 0);
+    assert_eq!(s.usdc.balance(&s.borrower), 100_000 + 40_000 - 42_400);
+}
+
+#[test]
+fn test_rate_model_jumps_above_kink() {
+    let cfg = config(8_000);
+    assert_eq!(interest::borrow_rate_bps(&cfg, 0), 200);
+    assert_eq!(interest::borrow_rate_bps(&cfg, 8_000), 1_000);
+    // 90%: 2% + 8% + 10% × 100%
+    assert_eq!(interest::borrow_rate_bps(&cfg, 9_000), 2_000);
+}
+
+#[test]
+fn test_stale_price_blocks_borrowing() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+
+    set_time(&s.env, 1_000 + MAX_PRICE_AGE + 1);
+    let result = s.client.try_borrow(&s.borrower, &s.usdc.address, &1_000);
+    assert_eq!(result, Err(Ok(LendingError::PriceUnavailable)));
+
+    let stranger = Address::generate(&s.env);
+    let result = s
+        .client
+        .try_set_price(&stranger, &s.usdc.address, &PRICE_SCALE);
+    assert_eq!(result, Err(Ok(LendingError::Unauthorized)));
+}
+
+#[test]
+fn test_borrow_emits_standard_event() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+    s.client.borrow(&s.borrower, &s.usdc.address, &10_000);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let standard = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .last()
+        .unwrap();
+
+    assert_eq!(standard.topic, topics::BORROW);
+    assert_eq!(standard.user_address, s.borrower);
+    let amount = standard
+        .metadata
+        .get(shared::events::metadata_keys::AMOUNT_KEY)
+        .unwrap();
+    assert_eq!(i128::try_from_val(&s.env, &amount).unwrap(), 10_000);
+}
+
+#[test]
+fn test_auction_underwater_position() {
+    let s = setup();
+    let keeper = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.usdc.address).mint(&keeper, &100_000);
+
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+    s.client.borrow(&s.borrower, &s.usdc.address, &40_000);
+
+    let result = s
+        .client
+        .try_start_auction(&s.borrower, &s.usdc.address, &s.xlm.address);
+    assert_eq!(result, Err(Ok(LendingError::PositionHealthy)));
+
+    // XLM drops to 0.07: collateral value 35_000 < 40_000 debt
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE * 7 / 100));
+    assert!(s.client.get_account(&s.borrower).health_factor < PRICE_SCALE);
+
+    let id = s
+        .client
+        .start_auction(&s.borrower, &s.usdc.address, &s.xlm.address);
+    let auction = s.client.get_auction(&id);
+    assert_eq!(auction.lot, 1_000_000);
+    assert_eq!(auction.tab, 40_000);
+    // Opens 10% above the oracle price
+    assert_eq!(auction.start_price, PRICE_SCALE * 77 / 1_000);
+    assert_eq!(s.client.debt_balance(&s.usdc.address, &s.borrower), 0);
+    assert_eq!(s.client.deposit_balance(&s.xlm.address, &s.borrower), 0);
+
+    // A partial fill at the opening price
+    let (collateral, paid) = s.client.take(&keeper, &id, &200_000, &auction.start_price);
+    assert_eq!(collateral, 200_000);
+    assert_eq!(paid, 15_400);
+    assert_eq!(s.xlm.balance(&keeper), 200_000);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let standard = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .last()
+        .unwrap();
+    assert_eq!(standard.topic, topics::AUCTION_TAKEN);
+    assert_eq!(standard.user_address, keeper);
+    let seized_meta = standard
+        .metadata
+        .get(shared::events::metadata_keys::SEIZED_KEY)
+        .unwrap();
+    assert_eq!(i128::try_from_val(&s.env, &seized_meta).unwrap(), 200_000);
+
+    // Later fills are cheaper and stop once the tab is covered
+    s.env.ledger().with_mut(|l| l.timestamp += 600);
+    let price = s.client.get_auction_price(&id);
+    assert!(price < auction.start_price);
+    let (collateral, paid_rest) = s.client.take(&keeper, &id, &800_000, &price);
+    assert_eq!(paid + paid_rest, 40_000);
+    assert!(collateral < 800_000);
+    assert_eq!(s.client.get_bad_debt(&s.usdc.address), 0);
+
+    // Unsold collateral is deposited back for the borrower
+    let returned = 800_000 - collateral;
+    let deposit = s.client.deposit_balance(&s.xlm.address, &s.borrower);
+    assert!(deposit > 0 && deposit <= returned);
+    s.client.withdraw(&s.borrower, &s.xlm.address, &deposit);
+}
+
+#[test]
+fn test_expired_auction_reset_and_bad_debt() {
+    let s = setup();
+    let keeper = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.usdc.address).mint(&keeper, &100_000);
+
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+    s.client.borrow(&s.borrower, &s.usdc.address, &50_000);
+
+    // XLM crashes to 0.02: all 1_000_000 XLM is worth 20_000
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE / 50));
+    let id = s
+        .client
+        .start_auction(&s.borrower, &s.usdc.address, &s.xlm.address);
+    assert_eq!(s.client.get_auction(&id).tab, 20_000);
+    assert_eq!(s.client.debt_balance(&s.usdc.address, &s.borrower), 30_000);
+    assert_eq!(
+        s.client
+            .try_take(&keeper, &id, &1_000_000, &(PRICE_SCALE / 50)),
+        Err(Ok(LendingError::PriceTooHigh))
+    );
+    assert_eq!(
+        s.client.try_reset_auction(&id),
+        Err(Ok(LendingError::AuctionActive))
+    );
+
+    let tail = s.client.get_auction_params().tail;
+    s.env.ledger().with_mut(|l| l.timestamp += tail + 1);
+    assert_eq!(
+        s.client.try_take(&keeper, &id, &1_000_000, &i128::MAX),
+        Err(Ok(LendingError::AuctionExpired))
+    );
+
+    // Restarted from a lower price, the whole lot fails to cover the tab
+    s.client.set_price(&s.oracle, &s.usdc.address, &PRICE_SCALE);
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE / 100));
+    s.client.reset_auction(&id);
+    let (collateral, paid) = s.client.take(&keeper, &id, &1_000_000, &i128::MAX);
+    assert_eq!(collateral, 1_000_000);
+    assert_eq!(paid, 11_000);
+    assert_eq!(s.client.get_bad_debt(&s.usdc.address), 9_000);
+    assert_eq!(
+        s.client.try_get_auction(&id),
+        Err(Ok(LendingError::AuctionNotFound))
+    );
+}
+
+#[test]
+fn test_circuit_breaker_switches_asset_to_safe_mode() {
+    let s = setup();
+    s.client.deposit(&s.lender, &s.usdc.address, &500_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &1_000_000);
+    s.client.borrow(&s.borrower, &s.usdc.address, &10_000);
+
+    s.client.set_circuit_breaker(
+        &s.admin,
+        &Some(BreakerConfig {
+            max_move_bps: 2_000,
+            window: 600,
+            cooldown: 1_800,
+        }),
+    );
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE / 10));
+
+    // XLM falls 30% within five minutes
+    s.env.ledger().with_mut(|l| l.timestamp += 300);
+    s.client
+        .set_price(&s.oracle, &s.xlm.address, &(PRICE_SCALE * 7 / 100));
+    let state = s.client.get_breaker_state(&s.xlm.address).unwrap();
+    assert_eq!(state.tripped_price, PRICE_SCALE * 7 / 100);
+    assert_eq!(state.tripped_until, 1_000 + 300 + 1_800);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let standard = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .last()
+        .unwrap();
+    assert_eq!(standard.topic, topics::CIRCUIT_TRIPPED);
+    let reference = standard
+        .metadata
+        .get(shared::events::metadata_keys::REFERENCE_KEY)
+        .unwrap();
+    assert_eq!(
+        i128::try_from_val(&s.env, &reference).unwrap(),
+        PRICE_SCALE / 10
+    );
+
+    // Anything valuing XLM reverts; risk-reducing actions still work
+    assert_eq!(
+        s.client.try_borrow(&s.borrower, &s.usdc.address, &1_000),
+        Err(Ok(LendingError::CircuitBreakerTripped))
+    );
+    assert_eq!(
+        s.client
+            .try_start_auction(&s.borrower, &s.usdc.address, &s.xlm.address),
+        Err(Ok(LendingError::CircuitBreakerTripped))
+    );
+    s.client.repay(&s.borrower, &s.usdc.address, &5_000);
+    StellarAssetClient::new(&s.env, &s.xlm.address).mint(&s.borrower, &100_000);
+    s.client.deposit(&s.borrower, &s.xlm.address, &100_000);
+
+    // The admin reopens the market at the last reported price
+    s.client.reset_circuit_breaker(&s.admin, &s.xlm.address);
+    s.client.borrow(&s.borrower, &s.usdc.address, &1_000);
+    assert_eq!(
+        s.client
+            .get_breaker_state(&s.xlm.address)
+            .unwrap()
+            .reference_price,
+        PRICE_SCALE * 7 / 100
+    );
+}
@@ -0,0 +1,378 @@
+//! Limit and stop orders on AMM pairs
+//!
+//! Users park a sell order here: the tokens to sell, a trigger price and a
+//! minimum output. The order is escrowed until a keeper executes it, which
+//! is only possible once the pair's TWAP has crossed the trigger, or until
+//! the owner cancels it. Keepers swap through the AMM on the owner's
+//! behalf and keep an execution fee out of the proceeds.
+//!
+//! Prices are the price of the pair's `token_a` in `token_b`, scaled by
+//! [`PRICE_SCALE`]. Which way the price must cross depends on the side:
+//!
+//! | selling   | `Limit` executes when price is | `Stop` executes when price is |
+//! |-----------|--------------------------------|-------------------------------|
+//! | `token_a` | at or above the trigger        | at or below the trigger       |
+//! | `token_b` | at or below the trigger        | at or above the trigger       |
+//!
+//! Triggers read the TWAP rather than the spot price so a single swap
+//! cannot push a pair across a trigger and fill orders at a distorted
+//! price; `min_out` still bounds what the swap itself returns.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::amm::{AmmClient, PRICE_SCALE};
+use shared::deadline::{is_expired, require_future, require_not_expired};
+use shared::events::{EventEmitter, OrderTriggeredEvent, TradeExecutedEvent};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
+    IntoVal, Symbol,
+};
+
+/// Basis-point denominator for the execution fee
+pub const BPS_DENOMINATOR: i128 = 10_000;
+/// Highest execution fee the admin can set
+pub const MAX_EXECUTION_FEE_BPS: u32 = 100;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OrderError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidOrder = 4,
+    InvalidFee = 5,
+    OrderNotFound = 6,
+    NotTriggered = 7,
+    Expired = 8,
+    NoPrice = 9,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrderKind {
+    /// Sell at the trigger price or better
+    Limit,
+    /// Sell once the price moves past the trigger against the seller
+    Stop,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub amm: Address,
+    /// Share of the output paid to the executing keeper
+    pub execution_fee_bps: u32,
+    /// TWAP window triggers are checked against, in seconds
+    pub twap_window: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub owner: Address,
+    pub pair: Symbol,
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Whether `token_in` is the pair's `token_a`
+    pub sells_a: bool,
+    pub amount_in: i128,
+    /// Least the swap may return, before the execution fee
+    pub min_out: i128,
+    pub kind: OrderKind,
+    pub trigger_price: i128,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    NextOrderId,
+    Order(u64),
+}
+
+#[contract]
+pub struct OrderManager;
+
+#[contractimpl]
+impl OrderManager {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        amm: Address,
+        execution_fee_bps: u32,
+        twap_window: u64,
+    ) -> Result<(), OrderError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(OrderError::AlreadyInitialized);
+        }
+        if execution_fee_bps > MAX_EXECUTION_FEE_BPS || twap_window == 0 {
+            return Err(OrderError::InvalidFee);
+        }
+        let config = Config {
+            admin,
+            amm,
+            execution_fee_bps,
+            twap_window,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Change the keeper execution fee (admin only). Applies to open
+    /// orders too.
+    pub fn set_execution_fee(env: Env, admin: Address, fee_bps: u32) -> Result<(), OrderError> {
+        let mut config = require_admin(&env, &admin)?;
+        if fee_bps > MAX_EXECUTION_FEE_BPS {
+            return Err(OrderError::InvalidFee);
+        }
+        config.execution_fee_bps = fee_bps;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Escrow `amount_in` of `token_in` as an order on `pair`. Returns the
+    /// order id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_order(
+        env: Env,
+        owner: Address,
+        pair: Symbol,
+        token_in: Address,
+        amount_in: i128,
+        min_out: i128,
+        kind: OrderKind,
+        trigger_price: i128,
+        expires_at: u64,
+    ) -> Result<u64, OrderError> {
+        owner.require_auth();
+        if amount_in <= 0 || min_out < 0 || trigger_price <= 0 {
+            return Err(OrderError::InvalidOrder);
+        }
+        require_future(&env, expires_at).map_err(|_| OrderError::Expired)?;
+        let config = load_config(&env)?;
+        let (token_a, token_b) = AmmClient::new(&env, &config.amm).pair_tokens(&pair);
+        let (sells_a, token_out) = if token_in == token_a {
+            (true, token_b)
+        } else if token_in == token_b {
+            (false, token_a)
+        } else {
+            return Err(OrderError::InvalidOrder);
+        };
+
+        token::Client::new(&env, &token_in).transfer(
+            &owner,
+            &env.current_contract_address(),
+            &amount_in,
+        );
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextOrderId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextOrderId, &(id + 1));
+        let order = Order {
+            owner,
+            pair,
+            token_in,
+            token_out,
+            sells_a,
+            amount_in,
+            min_out,
+            kind,
+            trigger_price,
+            expires_at,
+        };
+        env.storage().persistent().set(&DataKey::Order(id), &order);
+        Ok(id)
+    }
+
+    /// Return an order's escrow to its owner. The owner can cancel at any
+    /// time; anyone can clear an expired order.
+    pub fn cancel_order(env: Env, caller: Address, order_id: u64) -> Result<(), OrderError> {
+        caller.require_auth();
+        let order = load_order(&env, order_id)?;
+        if caller != order.owner && !is_expired(&env, order.expires_at) {
+            return Err(OrderError::Unauthorized);
+        }
+        env.storage().persistent().remove(&DataKey::Order(order_id));
+        token::Client::new(&env, &order.token_in).transfer(
+            &env.current_contract_address(),
+            &order.owner,
+            &order.amount_in,
+        );
+        Ok(())
+    }
+
+    /// Execute a triggered order, paying `keeper` the execution fee out of
+    /// the proceeds. Returns what the owner received.
+    pub fn execute_order(env: Env, keeper: Address, order_id: u64) -> Result<i128, OrderError> {
+        keeper.require_auth();
+        let config = load_config(&env)?;
+        let order = load_order(&env, order_id)?;
+        require_not_expired(&env, order.expires_at).map_err(|_| OrderError::Expired)?;
+        let amm = AmmClient::new(&env, &config.amm);
+        let price = amm
+            .try_get_twap(&order.pair, &config.twap_window)
+            .ok()
+            .and_then(|price| price.ok())
+            .ok_or(OrderError::NoPrice)?;
+        if !triggered(&order, price) {
+            return Err(OrderError::NotTriggered);
+        }
+        env.storage().persistent().remove(&DataKey::Order(order_id));
+
+        let this = env.current_contract_address();
+        // The AMM pulls the input from this contract
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: order.token_in.clone(),
+                    fn_name: symbol_short!("transfer"),
+                    args: (this.clone(), config.amm.clone(), order.amount_in).into_val(&env),
+                },
+                sub_invocations: vec![&env],
+            }),
+        ]);
+        let amount_out = amm.swap(
+            &this,
+            &order.pair,
+            &order.token_in,
+            &order.amount_in,
+            &order.min_out,
+        );
+
+        let fee = amount_out * config.execution_fee_bps as i128 / BPS_DENOMINATOR;
+        let received = amount_out - fee;
+        let output = token::Client::new(&env, &order.token_out);
+        output.transfer(&this, &order.owner, &received);
+        if fee > 0 {
+            output.transfer(&this, &keeper, &fee);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        EventEmitter::order_triggered(
+            &env,
+            OrderTriggeredEvent {
+                order_id,
+                owner: order.owner.clone(),
+                keeper,
+                pair: order.pair.clone(),
+                trigger_price: order.trigger_price,
+                price,
+                amount_in: order.amount_in,
+                amount_out: received,
+                fee,
+                timestamp,
+            },
+        );
+        EventEmitter::order_trade_executed(
+            &env,
+            TradeExecutedEvent {
+                trade_id: order_id,
+                trader: order.owner,
+                pair: order.pair,
+                amount: order.amount_in,
+                price: amount_out * PRICE_SCALE / order.amount_in,
+                is_buy: !order.sells_a,
+                fee_amount: fee,
+                fee_token: order.token_out,
+                timestamp,
+            },
+            order.trigger_price,
+        );
+        Ok(received)
+    }
+
+    /// Whether `order_id` could be executed now
+    pub fn is_triggered(env: Env, order_id: u64) -> bool {
+        let (Ok(config), Ok(order)) = (load_config(&env), load_order(&env, order_id)) else {
+            return false;
+        };
+        if is_expired(&env, order.expires_at) {
+            return false;
+        }
+        AmmClient::new(&env, &config.amm)
+            .try_get_twap(&order.pair, &config.twap_window)
+            .ok()
+            .and_then(|price| price.ok())
+            .is_some_and(|price| triggered(&order, price))
+    }
+
+    pub fn get_order(env: Env, order_id: u64) -> Option<Order> {
+        env.storage().persistent().get(&DataKey::Order(order_id))
+    }
+
+    pub fn config(env: Env) -> Result<Config, OrderError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), OrderError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), OrderError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Whether `price` has crossed the order's trigger in its direction
+fn triggered(order: &Order, price: i128) -> bool {
+    let wants_higher = order.sells_a == (order.kind == OrderKind::Limit);
+    if wants_higher {
+        price >= order.trigger_price
+    } else {
+        price <= order.trigger_price
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, OrderError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(OrderError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, OrderError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(OrderError::NotInitialized)
+}
+
+fn load_order(env: &Env, order_id: u64) -> Result<Order, OrderError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Order(order_id))
+        .ok_or(OrderError::OrderNotFound)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,207 @@
+#![cfg(test)]
+
+use super::*;
+use amm::{AmmContract, AmmContractClient};
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const PAIR: Symbol = symbol_short!("XLMUSDC");
+const WINDOW: u64 = 600;
+
+struct Setup {
+    env: Env,
+    client: OrderManagerClient<'static>,
+    amm: AmmContractClient<'static>,
+    token_a: TestToken<'static>,
+    token_b: TestToken<'static>,
+    alice: Address,
+    keeper: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token_a = stellar_asset(&env);
+    let token_b = stellar_asset(&env);
+    let amm = AmmContractClient::new(&env, &env.register_contract(None, AmmContract));
+    amm.initialize(&admin);
+    amm.create_pair(&admin, &PAIR, &token_a.address, &token_b.address);
+    let lp = Address::generate(&env);
+    token_a.mint(&lp, 100_000);
+    token_b.mint(&lp, 100_000);
+    amm.add_liquidity(&lp, &PAIR, &100_000, &100_000, &0);
+
+    let client = OrderManagerClient::new(&env, &env.register_contract(None, OrderManager));
+    client.initialize(&admin, &amm.address, &50, &WINDOW);
+    token_a.mint(&alice, 1_000);
+    token_b.mint(&alice, 1_000);
+
+    Setup {
+        env,
+        client,
+        amm,
+        token_a,
+        token_b,
+        alice,
+        keeper,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+/// Swap `amount` of `token` into the pair and let the TWAP catch up
+fn move_price(s: &Setup, token: &TestToken, amount: i128) {
+    let trader = Address::generate(&s.env);
+    token.mint(&trader, amount);
+    s.amm.swap(&trader, &PAIR, &token.address, &amount, &0);
+    advance(&s.env, WINDOW);
+}
+
+#[test]
+fn test_limit_sell_executes_once_price_rises() {
+    let s = setup();
+    let expires = 1_000 + 10 * WINDOW;
+    let id = s.client.place_order(
+        &s.alice,
+        &PAIR,
+        &s.token_a.address,
+        &1_000,
+        &1_000,
+        &OrderKind::Limit,
+        &12_000_000,
+        &expires,
+    );
+    assert_eq!(s.token_a.balance(&s.alice), 0);
+    assert_eq!(
+        s.client.get_order(&id).unwrap().token_out,
+        s.token_b.address
+    );
+
+    advance(&s.env, WINDOW);
+    assert!(!s.client.is_triggered(&id));
+    let result = s.client.try_execute_order(&s.keeper, &id);
+    assert_eq!(result, Err(Ok(OrderError::NotTriggered)));
+
+    // Buying token A lifts its price above the trigger
+    move_price(&s, &s.token_b, 20_000);
+    assert!(s.client.is_triggered(&id));
+    let quoted = s.amm.quote(&PAIR, &s.token_a.address, &1_000);
+    let received = s.client.execute_order(&s.keeper, &id);
+    let fee = quoted * 50 / 10_000;
+    assert_eq!(received, quoted - fee);
+    assert_eq!(s.token_b.balance(&s.alice), 1_000 + received);
+    assert_eq!(s.token_b.balance(&s.keeper), fee);
+    assert!(s.client.get_order(&id).is_none());
+
+    let triggered = assert_standard_event(&s.env, topics::ORDER_TRIGGERED, |_| true);
+    let event: OrderTriggeredEvent = payload(&triggered);
+    assert_eq!(event.keeper, s.keeper);
+    assert!(event.price >= 12_000_000);
+    let traded = assert_standard_event(&s.env, topics::TRADE_EXECUTED, |_| true);
+    let trade: TradeExecutedEvent = payload(&traded);
+    assert_eq!(trade.trader, s.alice);
+    assert!(!trade.is_buy);
+    assert_eq!(trade.fee_amount, fee);
+    assert_eq!(
+        traded.metadata::<i128>(&s.env, metadata_keys::TRIGGER_KEY),
+        Some(12_000_000)
+    );
+}
+
+#[test]
+fn test_stop_order_triggers_on_falling_price() {
+    let s = setup();
+    // Stop-loss on token A: sell once it falls to 0.8
+    let stop = s.client.place_order(
+        &s.alice,
+        &PAIR,
+        &s.token_a.address,
+        &500,
+        &0,
+        &OrderKind::Stop,
+        &8_000_000,
+        &100_000,
+    );
+    // Buying token A with token B at 0.8 or better is a limit on the B side
+    let buy = s.client.place_order(
+        &s.alice,
+        &PAIR,
+        &s.token_b.address,
+        &500,
+        &0,
+        &OrderKind::Limit,
+        &8_000_000,
+        &100_000,
+    );
+    advance(&s.env, WINDOW);
+    assert!(!s.client.is_triggered(&stop));
+    assert!(!s.client.is_triggered(&buy));
+
+    move_price(&s, &s.token_a, 20_000);
+    assert!(s.client.is_triggered(&stop));
+    assert!(s.client.is_triggered(&buy));
+    s.client.execute_order(&s.keeper, &stop);
+    s.client.execute_order(&s.keeper, &buy);
+    assert!(s.token_a.balance(&s.alice) > 1_000);
+    let traded = assert_standard_event(&s.env, topics::TRADE_EXECUTED, |_| true);
+    assert!(payload::<TradeExecutedEvent>(&traded).is_buy);
+}
+
+#[test]
+fn test_cancel_and_expiry() {
+    let s = setup();
+    let result = s.client.try_place_order(
+        &s.alice,
+        &PAIR,
+        &Address::generate(&s.env),
+        &100,
+        &0,
+        &OrderKind::Limit,
+        &1,
+        &2_000,
+    );
+    assert_eq!(result, Err(Ok(OrderError::InvalidOrder)));
+
+    let first = s.client.place_order(
+        &s.alice,
+        &PAIR,
+        &s.token_a.address,
+        &400,
+        &0,
+        &OrderKind::Limit,
+        &1,
+        &2_000,
+    );
+    let second = s.client.place_order(
+        &s.alice,
+        &PAIR,
+        &s.token_a.address,
+        &600,
+        &0,
+        &OrderKind::Limit,
+        &1,
+        &2_000,
+    );
+    let result = s.client.try_cancel_order(&s.keeper, &first);
+    assert_eq!(result, Err(Ok(OrderError::Unauthorized)));
+    s.client.cancel_order(&s.alice, &first);
+    assert_eq!(s.token_a.balance(&s.alice), 400);
+
+    // Past expiry the order cannot fill, but anyone can refund it
+    advance(&s.env, 1_001);
+    let result = s.client.try_execute_order(&s.keeper, &second);
+    assert_eq!(result, Err(Ok(OrderError::Expired)));
+    s.client.cancel_order(&s.keeper, &second);
+    assert_eq!(s.token_a.balance(&s.alice), 1_000);
+    let result = s.client.try_cancel_order(&s.alice, &second);
+    assert_eq!(result, Err(Ok(OrderError::OrderNotFound)));
+}
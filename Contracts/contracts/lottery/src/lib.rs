@@ -0,0 +1,459 @@
+//! No-loss prize lottery
+//!
+//! Deposits are pooled into the yield vault and can be withdrawn in full at
+//! any time; only the yield is at stake. Every `draw_period` anyone can
+//! start a draw, which sets aside the yield earned so far as the prize and
+//! freezes deposits, then pick its winner in a later ledger. Each unit of
+//! principal is one ticket.
+//!
+//! The winner is picked with the host PRNG reseeded from its own output
+//! and the ledger the pick happens in. The seed and winning ticket are
+//! published and stored with the draw, so anyone can replay the pick
+//! against the frozen ticket list. The seed comes from consensus data, so
+//! it is public once the ledger closes and a corrupt validator could bias
+//! it; see the `soroban_sdk::prng` warnings.
+
+#![no_std]
+
+use shared::events::{DrawStartedEvent, DrawWinnerEvent, EventEmitter, PrizeClaimedEvent};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token, vec,
+    Address, Bytes, BytesN, Env, IntoVal, Vec,
+};
+
+/// Most depositors the lottery holds, which bounds the ticket walk of a
+/// draw
+pub const MAX_PARTICIPANTS: u32 = 100;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LotteryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientPrincipal = 4,
+    PoolFull = 5,
+    /// Deposits and withdrawals wait until the pending draw has a winner
+    DrawInProgress = 6,
+    DrawNotDue = 7,
+    NoDrawPending = 8,
+    /// The winner is picked in a later ledger than the draw started in
+    TooEarly = 9,
+    NoTickets = 10,
+    NoPrize = 11,
+    DrawNotFound = 12,
+    NotWinner = 13,
+    AlreadyClaimed = 14,
+}
+
+/// The subset of the vault interface the lottery relies on
+#[contractclient(name = "YieldVaultClient")]
+pub trait YieldVault {
+    fn deposit(env: Env, user: Address, assets: i128) -> i128;
+    fn withdraw(env: Env, user: Address, assets: i128) -> i128;
+    fn shares_of(env: Env, user: Address) -> i128;
+    fn convert_to_assets(env: Env, shares: i128) -> i128;
+    fn asset(env: Env) -> Address;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotteryConfig {
+    pub vault: Address,
+    /// The vault's underlying asset
+    pub asset: Address,
+    /// Minimum seconds between draws
+    pub draw_period: u64,
+}
+
+/// A draw whose prize and tickets are locked in, awaiting its winner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingDraw {
+    pub id: u64,
+    pub prize: i128,
+    pub tickets: i128,
+    pub start_ledger: u32,
+}
+
+/// Record of a finished draw, enough to replay the pick
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Draw {
+    pub id: u64,
+    pub prize: i128,
+    pub tickets: i128,
+    pub seed: BytesN<32>,
+    /// Winning ticket: the winner is the participant whose cumulative
+    /// principal first exceeds it, in participant order
+    pub ticket: u64,
+    pub winner: Address,
+    pub drawn_at: u64,
+    pub claimed: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Principal(Address),
+    Participants,
+    TotalPrincipal,
+    /// Prizes drawn and not yet claimed
+    Reserved,
+    NextDrawId,
+    LastDrawAt,
+    Pending,
+    Draw(u64),
+}
+
+#[contract]
+pub struct Lottery;
+
+#[contractimpl]
+impl Lottery {
+    pub fn initialize(env: Env, vault: Address, draw_period: u64) -> Result<(), LotteryError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LotteryError::AlreadyInitialized);
+        }
+        let asset = YieldVaultClient::new(&env, &vault).asset();
+        let config = LotteryConfig {
+            vault,
+            asset,
+            draw_period,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage()
+            .instance()
+            .set(&DataKey::LastDrawAt, &env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Deposit `amount` into the pool, earning that many tickets. Returns
+    /// `user`'s principal.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, LotteryError> {
+        user.require_auth();
+        let config = load_config(&env)?;
+        require_no_pending(&env)?;
+        if amount <= 0 {
+            return Err(LotteryError::InvalidAmount);
+        }
+        let principal = Self::principal_of(env.clone(), user.clone());
+        if principal == 0 {
+            let mut participants = Self::participants(env.clone());
+            if participants.len() >= MAX_PARTICIPANTS {
+                return Err(LotteryError::PoolFull);
+            }
+            participants.push_back(user.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Participants, &participants);
+        }
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &config.asset).transfer(&user, &this, &amount);
+        // The vault pulls the deposit from this contract
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: config.asset.clone(),
+                    fn_name: symbol_short!("transfer"),
+                    args: (this.clone(), config.vault.clone(), amount).into_val(&env),
+                },
+                sub_invocations: vec![&env],
+            }),
+        ]);
+        YieldVaultClient::new(&env, &config.vault).deposit(&this, &amount);
+
+        let principal = principal + amount;
+        set_principal(&env, &user, principal);
+        set_total_principal(&env, Self::total_principal(env.clone()) + amount);
+        Ok(principal)
+    }
+
+    /// Withdraw `amount` of `user`'s principal. Returns what remains.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<i128, LotteryError> {
+        user.require_auth();
+        let config = load_config(&env)?;
+        require_no_pending(&env)?;
+        let principal = Self::principal_of(env.clone(), user.clone());
+        if amount <= 0 {
+            return Err(LotteryError::InvalidAmount);
+        }
+        if amount > principal {
+            return Err(LotteryError::InsufficientPrincipal);
+        }
+        let remaining = principal - amount;
+        set_principal(&env, &user, remaining);
+        set_total_principal(&env, Self::total_principal(env.clone()) - amount);
+        if remaining == 0 {
+            let mut participants = Self::participants(env.clone());
+            if let Some(index) = participants.first_index_of(&user) {
+                participants.remove(index);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Participants, &participants);
+        }
+        pay_out(&env, &config, &user, amount);
+        Ok(remaining)
+    }
+
+    /// Lock in the yield earned so far as the prize of a new draw and
+    /// freeze the tickets. Permissionless once `draw_period` has passed
+    /// since the last draw. Returns the draw id.
+    pub fn start_draw(env: Env, caller: Address) -> Result<u64, LotteryError> {
+        caller.require_auth();
+        let config = load_config(&env)?;
+        require_no_pending(&env)?;
+        let now = env.ledger().timestamp();
+        let last: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastDrawAt)
+            .unwrap_or(0);
+        if now < last + config.draw_period {
+            return Err(LotteryError::DrawNotDue);
+        }
+        let tickets = Self::total_principal(env.clone());
+        if tickets == 0 {
+            return Err(LotteryError::NoTickets);
+        }
+        let prize = Self::prize_pool(env.clone())?;
+        if prize == 0 {
+            return Err(LotteryError::NoPrize);
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextDrawId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextDrawId, &(id + 1));
+        set_reserved(&env, reserved(&env) + prize);
+        let start_ledger = env.ledger().sequence();
+        let pending = PendingDraw {
+            id,
+            prize,
+            tickets,
+            start_ledger,
+        };
+        env.storage().instance().set(&DataKey::Pending, &pending);
+
+        EventEmitter::draw_started(
+            &env,
+            DrawStartedEvent {
+                draw_id: id,
+                prize,
+                tickets,
+                start_ledger,
+                started_by: caller,
+                timestamp: now,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Pick the pending draw's winner. Permissionless, in any ledger after
+    /// the one the draw started in. Returns the winner.
+    pub fn select_winner(env: Env, caller: Address) -> Result<Address, LotteryError> {
+        caller.require_auth();
+        let pending: PendingDraw = env
+            .storage()
+            .instance()
+            .get(&DataKey::Pending)
+            .ok_or(LotteryError::NoDrawPending)?;
+        let sequence = env.ledger().sequence();
+        if sequence <= pending.start_ledger {
+            return Err(LotteryError::TooEarly);
+        }
+
+        let entropy: BytesN<32> = env.prng().gen();
+        let mut material = Bytes::from_array(&env, &entropy.to_array());
+        material.extend_from_array(&pending.id.to_be_bytes());
+        material.extend_from_array(&sequence.to_be_bytes());
+        material.extend_from_array(&env.ledger().timestamp().to_be_bytes());
+        let seed = env.crypto().sha256(&material);
+        env.prng().seed(Bytes::from_array(&env, &seed.to_array()));
+        let ticket: u64 = env.prng().gen_range(0..pending.tickets as u64);
+        let winner = ticket_holder(&env, ticket)?;
+
+        let now = env.ledger().timestamp();
+        let draw = Draw {
+            id: pending.id,
+            prize: pending.prize,
+            tickets: pending.tickets,
+            seed: seed.clone(),
+            ticket,
+            winner: winner.clone(),
+            drawn_at: now,
+            claimed: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Draw(pending.id), &draw);
+        env.storage().instance().remove(&DataKey::Pending);
+        env.storage().instance().set(&DataKey::LastDrawAt, &now);
+
+        EventEmitter::draw_winner(
+            &env,
+            DrawWinnerEvent {
+                draw_id: pending.id,
+                winner: winner.clone(),
+                prize: pending.prize,
+                seed,
+                ticket,
+                timestamp: now,
+            },
+        );
+        Ok(winner)
+    }
+
+    /// Pay `winner` the prize of draw `draw_id`
+    pub fn claim_prize(env: Env, winner: Address, draw_id: u64) -> Result<i128, LotteryError> {
+        winner.require_auth();
+        let config = load_config(&env)?;
+        let mut draw = Self::draw(env.clone(), draw_id).ok_or(LotteryError::DrawNotFound)?;
+        if draw.winner != winner {
+            return Err(LotteryError::NotWinner);
+        }
+        if draw.claimed {
+            return Err(LotteryError::AlreadyClaimed);
+        }
+        draw.claimed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Draw(draw_id), &draw);
+        set_reserved(&env, reserved(&env) - draw.prize);
+        pay_out(&env, &config, &winner, draw.prize);
+
+        EventEmitter::prize_claimed(
+            &env,
+            PrizeClaimedEvent {
+                draw_id,
+                winner,
+                prize: draw.prize,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(draw.prize)
+    }
+
+    // ---- views ----
+
+    /// Yield available for the next draw: the pool's value in the vault
+    /// less principal and prizes not yet claimed
+    pub fn prize_pool(env: Env) -> Result<i128, LotteryError> {
+        let config = load_config(&env)?;
+        let vault = YieldVaultClient::new(&env, &config.vault);
+        let shares = vault.shares_of(&env.current_contract_address());
+        let value = if shares == 0 {
+            0
+        } else {
+            vault.convert_to_assets(&shares)
+        };
+        Ok((value - Self::total_principal(env.clone()) - reserved(&env)).max(0))
+    }
+
+    pub fn principal_of(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Principal(user))
+            .unwrap_or(0)
+    }
+
+    pub fn total_principal(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalPrincipal)
+            .unwrap_or(0)
+    }
+
+    /// Depositors in ticket order
+    pub fn participants(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Participants)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn pending_draw(env: Env) -> Option<PendingDraw> {
+        env.storage().instance().get(&DataKey::Pending)
+    }
+
+    pub fn draw(env: Env, draw_id: u64) -> Option<Draw> {
+        env.storage().persistent().get(&DataKey::Draw(draw_id))
+    }
+
+    pub fn config(env: Env) -> Result<LotteryConfig, LotteryError> {
+        load_config(&env)
+    }
+}
+
+/// Participant holding `ticket`, counting each participant's principal as
+/// a run of consecutive tickets
+fn ticket_holder(env: &Env, ticket: u64) -> Result<Address, LotteryError> {
+    let mut end = 0i128;
+    for participant in Lottery::participants(env.clone()).iter() {
+        end += Lottery::principal_of(env.clone(), participant.clone());
+        if (ticket as i128) < end {
+            return Ok(participant);
+        }
+    }
+    Err(LotteryError::NoTickets)
+}
+
+/// Withdraw `amount` from the vault and send it to `to`
+fn pay_out(env: &Env, config: &LotteryConfig, to: &Address, amount: i128) {
+    let this = env.current_contract_address();
+    YieldVaultClient::new(env, &config.vault).withdraw(&this, &amount);
+    token::Client::new(env, &config.asset).transfer(&this, to, &amount);
+}
+
+fn require_no_pending(env: &Env) -> Result<(), LotteryError> {
+    if env.storage().instance().has(&DataKey::Pending) {
+        return Err(LotteryError::DrawInProgress);
+    }
+    Ok(())
+}
+
+fn reserved(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Reserved)
+        .unwrap_or(0)
+}
+
+fn set_reserved(env: &Env, amount: i128) {
+    env.storage().instance().set(&DataKey::Reserved, &amount);
+}
+
+fn set_principal(env: &Env, user: &Address, amount: i128) {
+    let key = DataKey::Principal(user.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+fn set_total_principal(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalPrincipal, &amount);
+}
+
+fn load_config(env: &Env) -> Result<LotteryConfig, LotteryError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(LotteryError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
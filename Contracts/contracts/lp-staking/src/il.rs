@@ -0,0 +1,125 @@
+//! Impermanent-loss protection
+//!
+//! When enabled, stakes locked for at least `min_lock` seconds are
+//! compensated in the governance token when they unstake. The loss is
+//! measured from the pair's TWAP when the shares were staked to its TWAP
+//! at exit, as the value an LP position lost against simply holding the
+//! two tokens: `1 - 2·√r / (1 + r)` for a price ratio `r`. The program
+//! covers `coverage_bps` of that loss on the stake's shares, valued at the
+//! governance-set `share_value`.
+//!
+//! Payouts come from the contract's governance token balance and stop
+//! (without failing the unstake) when it runs dry. Stakes opened while the
+//! program was off have no entry price and are not covered.
+
+use crate::{DataKey, LpPool, LpStake, LpStakingError};
+use shared::amm::AmmClient;
+use shared::events::{EventEmitter, IlCompensationEvent};
+use shared::math::{mul_div, sqrt};
+use soroban_sdk::{contracttype, token, Address, Env, Symbol};
+
+pub const BPS: i128 = 10_000;
+/// Fixed-point scale of [`IlConfig::share_value`]
+pub const VALUE_SCALE: i128 = 10_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IlConfig {
+    /// Governance token compensation is paid in
+    pub token: Address,
+    /// Shortest lock that is covered, in seconds
+    pub min_lock: u64,
+    /// Share of the loss compensated
+    pub coverage_bps: u32,
+    /// TWAP window used for entry and exit prices
+    pub twap_window: u64,
+    /// Governance tokens one LP share is valued at, scaled by
+    /// [`VALUE_SCALE`]
+    pub share_value: i128,
+}
+
+pub fn config(env: &Env) -> Option<IlConfig> {
+    env.storage().instance().get(&DataKey::IlConfig)
+}
+
+/// Loss of an LP position against holding, in basis points, for a move
+/// from `entry` to `exit` price. Prices too large to multiply are an
+/// [`LpStakingError::Overflow`].
+pub fn loss_bps(entry: i128, exit: i128) -> Result<u32, LpStakingError> {
+    if entry <= 0 || exit <= 0 {
+        return Ok(0);
+    }
+    let product = entry.checked_mul(exit).ok_or(LpStakingError::Overflow)?;
+    let sum = entry.checked_add(exit).ok_or(LpStakingError::Overflow)?;
+    let lp_value = sqrt(product)
+        .checked_mul(2)
+        .and_then(|twice_mean| mul_div(twice_mean, BPS, sum))
+        .ok_or(LpStakingError::Overflow)?;
+    Ok((BPS - lp_value).max(0) as u32)
+}
+
+/// Pair TWAP to record as a new stake's entry price, or zero when the
+/// program is off or the pair lacks the history
+pub fn entry_price(env: &Env, amm: &Address, pair: &Symbol) -> i128 {
+    match config(env) {
+        Some(config) => twap(env, amm, pair, config.twap_window),
+        None => 0,
+    }
+}
+
+/// Compensate an unstaking stake for its impermanent loss. Returns the
+/// governance tokens paid.
+pub fn compensate(
+    env: &Env,
+    amm: &Address,
+    stake_id: u64,
+    stake: &LpStake,
+    pool: &LpPool,
+) -> Result<i128, LpStakingError> {
+    let Some(config) = config(env) else {
+        return Ok(0);
+    };
+    if stake.entry_price == 0 || stake.unlock_at - stake.staked_at < config.min_lock {
+        return Ok(0);
+    }
+    let exit_price = twap(env, amm, &pool.pair, config.twap_window);
+    let il_bps = loss_bps(stake.entry_price, exit_price)?;
+    let value =
+        mul_div(stake.amount, config.share_value, VALUE_SCALE).ok_or(LpStakingError::Overflow)?;
+    let owed = mul_div(
+        value,
+        il_bps as i128 * config.coverage_bps as i128,
+        BPS * BPS,
+    )
+    .ok_or(LpStakingError::Overflow)?;
+
+    let client = token::Client::new(env, &config.token);
+    let this = env.current_contract_address();
+    let amount = owed.min(client.balance(&this));
+    if amount <= 0 {
+        return Ok(0);
+    }
+    client.transfer(&this, &stake.owner, &amount);
+    EventEmitter::il_compensated(
+        env,
+        IlCompensationEvent {
+            user: stake.owner.clone(),
+            pair: pool.pair.clone(),
+            stake_id,
+            il_bps,
+            amount,
+            entry_price: stake.entry_price,
+            exit_price,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(amount)
+}
+
+fn twap(env: &Env, amm: &Address, pair: &Symbol, window: u64) -> i128 {
+    AmmClient::new(env, amm)
+        .try_get_twap(pair, &window)
+        .ok()
+        .and_then(|price| price.ok())
+        .unwrap_or(0)
+}
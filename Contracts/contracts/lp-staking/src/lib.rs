@@ -0,0 +1,506 @@
+//! LP staking
+//!
+//! Liquidity providers stake their AMM pair shares here to earn reward
+//! token emissions. Each pool stakes the shares of one pair and is a gauge
+//! target: `sync_rates` splits the contract's emission rate across pools by
+//! the gauge controller's weights, so votes decide which pairs are
+//! incentivised.
+//!
+//! Stakers can lock their shares for longer to weigh more in their pool
+//! (see [`LOCK_TIERS`]). Rewards accrue per second through the shared
+//! reward-per-share accounting and are paid from the contract's reward
+//! token balance, which the emissions treasury keeps funded.
+//!
+//! Governance can also cover long-locked stakes against impermanent loss
+//! (see [`il`]).
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::amm::AmmClient;
+use shared::events::{EventEmitter, LpStakeEvent, RewardsClaimedEvent};
+use shared::pagination::{IndexedList, Pagination};
+use shared::staking::{boosted_weight, RewardPool};
+use shared::voting::GaugeControllerClient;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+pub mod il;
+
+pub use il::IlConfig;
+
+pub const DAY: u64 = 24 * 60 * 60;
+/// Upper bound on pools so rate syncs stay within one transaction
+pub const MAX_POOLS: u32 = 20;
+/// Lock durations a stake can choose and the multiplier (in basis points)
+/// each gives its weight
+pub const LOCK_TIERS: [(u64, u32); 4] = [
+    (0, 10_000),
+    (30 * DAY, 12_500),
+    (90 * DAY, 15_000),
+    (180 * DAY, 20_000),
+];
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LpStakingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    PoolNotFound = 5,
+    TooManyPools = 6,
+    InvalidLock = 7,
+    StakeNotFound = 8,
+    StillLocked = 9,
+    NothingToClaim = 10,
+    InvalidConfig = 11,
+    Overflow = 12,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub amm: Address,
+    pub reward_token: Address,
+    pub gauge_controller: Address,
+    /// Reward tokens emitted per second across all pools
+    pub emission_rate: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LpPool {
+    pub pair: Symbol,
+    /// This pool's part of the emission rate, per second
+    pub rate: i128,
+    pub rewards: RewardPool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LpStake {
+    pub owner: Address,
+    pub pool_id: u32,
+    /// LP shares staked
+    pub amount: i128,
+    /// Shares counted for rewards, lock multiplier included
+    pub weight: i128,
+    pub multiplier_bps: u32,
+    pub staked_at: u64,
+    pub unlock_at: u64,
+    /// Pair TWAP at staking for impermanent-loss protection; zero when
+    /// not covered
+    pub entry_price: i128,
+    /// Pool reward per share the stake was last paid up to
+    pub reward_paid_per_share: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Pools,
+    NextStakeId,
+    Stake(u64),
+    /// Position of a stake in its owner's list
+    StakeIndex(u64),
+    IlConfig,
+}
+
+#[contract]
+pub struct LpStaking;
+
+#[contractimpl]
+impl LpStaking {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        amm: Address,
+        reward_token: Address,
+        gauge_controller: Address,
+        emission_rate: i128,
+    ) -> Result<(), LpStakingError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LpStakingError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if emission_rate < 0 {
+            return Err(LpStakingError::InvalidAmount);
+        }
+        let config = Config {
+            admin,
+            amm,
+            reward_token,
+            gauge_controller,
+            emission_rate,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Add a pool staking the shares of `pair` (admin only). Its id is its
+    /// gauge pool id. It earns nothing until the next `sync_rates`.
+    pub fn add_pool(env: Env, admin: Address, pair: Symbol) -> Result<u32, LpStakingError> {
+        require_admin(&env, &admin)?;
+        let mut pools = Self::pools(env.clone());
+        if pools.len() >= MAX_POOLS {
+            return Err(LpStakingError::TooManyPools);
+        }
+        pools.push_back(LpPool {
+            pair,
+            rate: 0,
+            rewards: RewardPool::new(env.ledger().timestamp()),
+        });
+        env.storage().instance().set(&DataKey::Pools, &pools);
+        Ok(pools.len() - 1)
+    }
+
+    /// Change the total emission rate (admin only). Takes effect on the
+    /// next `sync_rates`.
+    pub fn set_emission_rate(
+        env: Env,
+        admin: Address,
+        emission_rate: i128,
+    ) -> Result<(), LpStakingError> {
+        let mut config = require_admin(&env, &admin)?;
+        if emission_rate < 0 {
+            return Err(LpStakingError::InvalidAmount);
+        }
+        config.emission_rate = emission_rate;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Set each pool's rate to its gauge weight's share of the emission
+    /// rate, after accruing what the old rates emitted. Permissionless so
+    /// keepers can call it after each epoch rollover.
+    pub fn sync_rates(env: Env) -> Result<Vec<LpPool>, LpStakingError> {
+        let config = load_config(&env)?;
+        let gauges = GaugeControllerClient::new(&env, &config.gauge_controller);
+        let this = env.current_contract_address();
+        let now = env.ledger().timestamp();
+
+        let mut pools = Self::pools(env.clone());
+        for pool_id in 0..pools.len() {
+            let mut pool = pools.get(pool_id).unwrap();
+            pool.rewards
+                .accrue(&env, pool.rate, now)
+                .map_err(|_| LpStakingError::Overflow)?;
+            let weight = gauges.gauge_weight(&this, &pool_id);
+            pool.rate = config.emission_rate * weight as i128 / 10_000;
+            pools.set(pool_id, pool);
+        }
+        env.storage().instance().set(&DataKey::Pools, &pools);
+        Ok(pools)
+    }
+
+    /// Stake `amount` shares of the pool's pair, locked for
+    /// `lock_seconds`, which must be one of the [`LOCK_TIERS`]. Returns the
+    /// stake id.
+    pub fn stake(
+        env: Env,
+        user: Address,
+        pool_id: u32,
+        amount: i128,
+        lock_seconds: u64,
+    ) -> Result<u64, LpStakingError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(LpStakingError::InvalidAmount);
+        }
+        let multiplier_bps = LOCK_TIERS
+            .iter()
+            .find(|(duration, _)| *duration == lock_seconds)
+            .map(|(_, multiplier)| *multiplier)
+            .ok_or(LpStakingError::InvalidLock)?;
+        let config = load_config(&env)?;
+        let mut pool = accrued_pool(&env, pool_id)?;
+
+        AmmClient::new(&env, &config.amm).transfer_shares(
+            &pool.pair,
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let now = env.ledger().timestamp();
+        let stake = LpStake {
+            owner: user.clone(),
+            pool_id,
+            amount,
+            weight: boosted_weight(&env, amount, multiplier_bps)
+                .map_err(|_| LpStakingError::Overflow)?,
+            multiplier_bps,
+            staked_at: now,
+            unlock_at: now + lock_seconds,
+            entry_price: il::entry_price(&env, &config.amm, &pool.pair),
+            reward_paid_per_share: pool.rewards.reward_per_share,
+        };
+        pool.rewards.total_weight += stake.weight;
+        save_pool(&env, pool_id, &pool);
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextStakeId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextStakeId, &(id + 1));
+        env.storage().persistent().set(&DataKey::Stake(id), &stake);
+        let index = user_stakes(&stake.owner).push(&env, &id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StakeIndex(id), &index);
+
+        EventEmitter::lp_staked(&env, stake_event(&env, id, &stake, &pool));
+        Ok(id)
+    }
+
+    /// Pay out the rewards a stake has earned
+    pub fn claim(env: Env, user: Address, stake_id: u64) -> Result<i128, LpStakingError> {
+        user.require_auth();
+        let mut stake = load_stake(&env, &user, stake_id)?;
+        let pool = accrued_pool(&env, stake.pool_id)?;
+        let reward = pay_rewards(&env, stake_id, &mut stake, &pool)?;
+        if reward == 0 {
+            return Err(LpStakingError::NothingToClaim);
+        }
+        save_pool(&env, stake.pool_id, &pool);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(stake_id), &stake);
+        Ok(reward)
+    }
+
+    /// Return an unlocked stake's shares along with its unclaimed rewards
+    /// and any impermanent-loss compensation. Returns the rewards paid.
+    pub fn unstake(env: Env, user: Address, stake_id: u64) -> Result<i128, LpStakingError> {
+        user.require_auth();
+        let mut stake = load_stake(&env, &user, stake_id)?;
+        if env.ledger().timestamp() < stake.unlock_at {
+            return Err(LpStakingError::StillLocked);
+        }
+        let config = load_config(&env)?;
+        let mut pool = accrued_pool(&env, stake.pool_id)?;
+        let reward = pay_rewards(&env, stake_id, &mut stake, &pool)?;
+        pool.rewards.total_weight -= stake.weight;
+        save_pool(&env, stake.pool_id, &pool);
+
+        env.storage().persistent().remove(&DataKey::Stake(stake_id));
+        remove_user_stake(&env, &user, stake_id);
+
+        AmmClient::new(&env, &config.amm).transfer_shares(
+            &pool.pair,
+            &env.current_contract_address(),
+            &user,
+            &stake.amount,
+        );
+        il::compensate(&env, &config.amm, stake_id, &stake, &pool)?;
+        EventEmitter::lp_unstaked(&env, stake_event(&env, stake_id, &stake, &pool));
+        Ok(reward)
+    }
+
+    /// Turn impermanent-loss protection on, reconfigure it, or turn it off
+    /// with `None` (admin only). Stakes keep the entry price recorded when
+    /// they were opened.
+    pub fn set_il_protection(
+        env: Env,
+        admin: Address,
+        config: Option<IlConfig>,
+    ) -> Result<(), LpStakingError> {
+        require_admin(&env, &admin)?;
+        match config {
+            Some(config) => {
+                if config.coverage_bps > il::BPS as u32 || config.share_value < 0 {
+                    return Err(LpStakingError::InvalidConfig);
+                }
+                env.storage().instance().set(&DataKey::IlConfig, &config);
+            }
+            None => env.storage().instance().remove(&DataKey::IlConfig),
+        }
+        Ok(())
+    }
+
+    pub fn il_protection(env: Env) -> Option<IlConfig> {
+        il::config(&env)
+    }
+
+    /// Rewards a stake could claim now
+    pub fn pending_rewards(env: Env, stake_id: u64) -> Result<i128, LpStakingError> {
+        let Some(stake) = Self::get_stake(env.clone(), stake_id) else {
+            return Ok(0);
+        };
+        let pool = match accrued_pool(&env, stake.pool_id) {
+            Err(LpStakingError::PoolNotFound) => return Ok(0),
+            pool => pool?,
+        };
+        pool.rewards
+            .earned(&env, stake.weight, stake.reward_paid_per_share)
+            .map_err(|_| LpStakingError::Overflow)
+    }
+
+    pub fn get_stake(env: Env, stake_id: u64) -> Option<LpStake> {
+        env.storage().persistent().get(&DataKey::Stake(stake_id))
+    }
+
+    /// One page of the ids of `user`'s stakes, and the cursor of the next
+    pub fn stakes_of(env: Env, user: Address, page: Pagination) -> (Vec<u64>, Option<u32>) {
+        user_stakes(&user).page(&env, &page)
+    }
+
+    pub fn pools(env: Env) -> Vec<LpPool> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pools)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn config(env: Env) -> Result<Config, LpStakingError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), LpStakingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), LpStakingError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn user_stakes(user: &Address) -> IndexedList<Address> {
+    IndexedList::new(symbol_short!("lp_stakes"), user.clone())
+}
+
+fn remove_user_stake(env: &Env, user: &Address, stake_id: u64) {
+    let key = DataKey::StakeIndex(stake_id);
+    let Some(index) = env.storage().persistent().get::<_, u32>(&key) else {
+        return;
+    };
+    env.storage().persistent().remove(&key);
+    if let Some(moved) = user_stakes(user).swap_remove::<u64>(env, index) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::StakeIndex(moved), &index);
+    }
+}
+
+fn load_config(env: &Env) -> Result<Config, LpStakingError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(LpStakingError::NotInitialized)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, LpStakingError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(LpStakingError::Unauthorized);
+    }
+    Ok(config)
+}
+
+/// Pool `pool_id` with its emissions accrued up to now
+fn accrued_pool(env: &Env, pool_id: u32) -> Result<LpPool, LpStakingError> {
+    let mut pool = LpStaking::pools(env.clone())
+        .get(pool_id)
+        .ok_or(LpStakingError::PoolNotFound)?;
+    pool.rewards
+        .accrue(env, pool.rate, env.ledger().timestamp())
+        .map_err(|_| LpStakingError::Overflow)?;
+    Ok(pool)
+}
+
+fn save_pool(env: &Env, pool_id: u32, pool: &LpPool) {
+    let mut pools = LpStaking::pools(env.clone());
+    pools.set(pool_id, pool.clone());
+    env.storage().instance().set(&DataKey::Pools, &pools);
+}
+
+fn load_stake(env: &Env, owner: &Address, stake_id: u64) -> Result<LpStake, LpStakingError> {
+    let stake = LpStaking::get_stake(env.clone(), stake_id).ok_or(LpStakingError::StakeNotFound)?;
+    if stake.owner != *owner {
+        return Err(LpStakingError::Unauthorized);
+    }
+    Ok(stake)
+}
+
+/// Pay what `stake` earned up to `pool` and mark it paid. Emits nothing
+/// when there is nothing to pay.
+fn pay_rewards(
+    env: &Env,
+    stake_id: u64,
+    stake: &mut LpStake,
+    pool: &LpPool,
+) -> Result<i128, LpStakingError> {
+    let reward = pool
+        .rewards
+        .earned(env, stake.weight, stake.reward_paid_per_share)
+        .map_err(|_| LpStakingError::Overflow)?;
+    stake.reward_paid_per_share = pool.rewards.reward_per_share;
+    if reward == 0 {
+        return Ok(0);
+    }
+    let config = load_config(env)?;
+    token::Client::new(env, &config.reward_token).transfer(
+        &env.current_contract_address(),
+        &stake.owner,
+        &reward,
+    );
+    EventEmitter::pair_rewards_claimed(
+        env,
+        RewardsClaimedEvent {
+            user: stake.owner.clone(),
+            position_id: Some(stake_id),
+            amount: reward,
+            multiplier_bps: stake.multiplier_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+        pool.pair.clone(),
+    );
+    Ok(reward)
+}
+
+fn stake_event(env: &Env, stake_id: u64, stake: &LpStake, pool: &LpPool) -> LpStakeEvent {
+    LpStakeEvent {
+        user: stake.owner.clone(),
+        pair: pool.pair.clone(),
+        stake_id,
+        amount: stake.amount,
+        multiplier_bps: stake.multiplier_bps,
+        unlock_at: stake.unlock_at,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+#[cfg(test)]
+mod test;
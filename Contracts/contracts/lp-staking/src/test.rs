@@ -0,0 +1,240 @@
+#![cfg(test)]
+
+use super::*;
+use amm::{AmmContract, AmmContractClient};
+use shared::events::{metadata_keys, topics, IlCompensationEvent};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contract, contractimpl, symbol_short};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_no_standard_event, assert_standard_event, payload};
+
+/// Gauge controller that sends every emission to pool 0
+#[contract]
+pub struct MockGauges;
+
+#[contractimpl]
+impl MockGauges {
+    pub fn gauge_weight(_env: Env, _target: Address, pool_id: u32) -> u32 {
+        match pool_id {
+            0 => 10_000,
+            _ => 0,
+        }
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: LpStakingClient<'static>,
+    amm: AmmContractClient<'static>,
+    token_a: TestToken<'static>,
+    reward: TestToken<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+const PAIR: Symbol = symbol_short!("XLMUSDC");
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let token_a = stellar_asset(&env);
+    let token_b = stellar_asset(&env);
+    let amm = AmmContractClient::new(&env, &env.register_contract(None, AmmContract));
+    amm.initialize(&admin);
+    amm.create_pair(&admin, &PAIR, &token_a.address, &token_b.address);
+    for lp in [&alice, &bob] {
+        token_a.mint(lp, 1_000);
+        token_b.mint(lp, 1_000);
+        amm.add_liquidity(lp, &PAIR, &1_000, &1_000, &0);
+    }
+
+    let reward = stellar_asset(&env);
+    let gauges = env.register_contract(None, MockGauges);
+    let client = LpStakingClient::new(&env, &env.register_contract(None, LpStaking));
+    client.initialize(&admin, &amm.address, &reward.address, &gauges, &300);
+    reward.mint(&client.address, 1_000_000_000);
+    client.add_pool(&admin, &PAIR);
+    client.add_pool(&admin, &symbol_short!("OTHER"));
+    client.sync_rates();
+
+    Setup {
+        env,
+        client,
+        amm,
+        token_a,
+        reward,
+        admin,
+        alice,
+        bob,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_lock_multiplier_weighs_rewards() {
+    let s = setup();
+    let pools = s.client.pools();
+    assert_eq!(pools.get(0).unwrap().rate, 300);
+    assert_eq!(pools.get(1).unwrap().rate, 0);
+
+    let alice_stake = s.client.stake(&s.alice, &0, &1_000, &0);
+    let bob_stake = s.client.stake(&s.bob, &0, &1_000, &(180 * DAY));
+    let staked = assert_standard_event(&s.env, topics::STAKE, |_| true);
+    let event: LpStakeEvent = payload(&staked);
+    assert_eq!(event.multiplier_bps, 20_000);
+    assert_eq!(
+        staked.metadata::<Symbol>(&s.env, metadata_keys::PAIR_KEY),
+        Some(PAIR)
+    );
+    assert_eq!(s.amm.shares_of(&PAIR, &s.client.address), 2_000);
+
+    // The locked stake weighs double: 30_000 emitted splits 1:2
+    advance(&s.env, 100);
+    assert_eq!(s.client.pending_rewards(&bob_stake), 20_000);
+    assert_eq!(s.client.claim(&s.alice, &alice_stake), 10_000);
+    assert_eq!(s.reward.balance(&s.alice), 10_000);
+    let claimed = assert_standard_event(&s.env, topics::REWARDS_CLAIMED, |_| true);
+    assert_eq!(
+        claimed.metadata::<Symbol>(&s.env, metadata_keys::PAIR_KEY),
+        Some(PAIR)
+    );
+    assert_eq!(
+        payload::<RewardsClaimedEvent>(&claimed).position_id,
+        Some(alice_stake)
+    );
+
+    let result = s.client.try_claim(&s.alice, &alice_stake);
+    assert_eq!(result, Err(Ok(LpStakingError::NothingToClaim)));
+    let result = s.client.try_unstake(&s.bob, &bob_stake);
+    assert_eq!(result, Err(Ok(LpStakingError::StillLocked)));
+    let result = s.client.try_claim(&s.alice, &bob_stake);
+    assert_eq!(result, Err(Ok(LpStakingError::Unauthorized)));
+}
+
+#[test]
+fn test_unstake_returns_shares_with_rewards() {
+    let s = setup();
+    let result = s.client.try_stake(&s.alice, &0, &1_000, &DAY);
+    assert_eq!(result, Err(Ok(LpStakingError::InvalidLock)));
+    let id = s.client.stake(&s.alice, &0, &600, &(30 * DAY));
+    assert_eq!(s.amm.shares_of(&PAIR, &s.alice), 400);
+
+    advance(&s.env, 30 * DAY);
+    let paid = s.client.unstake(&s.alice, &id);
+    assert_eq!(paid, 300 * 30 * DAY as i128);
+    assert_eq!(s.reward.balance(&s.alice), paid);
+    assert_eq!(s.amm.shares_of(&PAIR, &s.alice), 1_000);
+    assert!(s.client.get_stake(&id).is_none());
+    let (stakes, next) = s.client.stakes_of(&s.alice, &Pagination::first(10));
+    assert!(stakes.is_empty());
+    assert_eq!(next, None);
+    let unstaked = assert_standard_event(&s.env, topics::UNSTAKE, |_| true);
+    assert_eq!(
+        unstaked.metadata::<i128>(&s.env, metadata_keys::AMOUNT_KEY),
+        Some(600)
+    );
+}
+
+#[test]
+fn test_rates_follow_emission_changes() {
+    let s = setup();
+    let id = s.client.stake(&s.alice, &0, &1_000, &0);
+    advance(&s.env, 10);
+
+    // Emissions before a rate change keep the old rate
+    s.client.set_emission_rate(&s.admin, &100);
+    s.client.sync_rates();
+    advance(&s.env, 10);
+    assert_eq!(s.client.pending_rewards(&id), 3_000 + 1_000);
+    assert_eq!(s.client.pools().get(1).unwrap().rate, 0);
+
+    let result = s.client.try_set_emission_rate(&s.alice, &1);
+    assert_eq!(result, Err(Ok(LpStakingError::Unauthorized)));
+    let result = s.client.try_add_pool(&s.alice, &PAIR);
+    assert_eq!(result, Err(Ok(LpStakingError::Unauthorized)));
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let s = setup();
+    let client = LpStakingClient::new(&s.env, &s.env.register_contract(None, LpStaking));
+    client.initialize(&s.admin, &s.amm.address, &s.reward.address, &s.admin, &0);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+}
+
+#[test]
+fn test_loss_formula() {
+    assert_eq!(il::loss_bps(10_000_000, 10_000_000), Ok(0));
+    // A doubling or halving costs an LP about 5.7% against holding
+    assert_eq!(il::loss_bps(10_000_000, 20_000_000), Ok(572));
+    assert_eq!(il::loss_bps(20_000_000, 10_000_000), Ok(572));
+    assert_eq!(il::loss_bps(0, 10_000_000), Ok(0));
+    // Prices whose product does not fit are reported, not trapped on
+    assert_eq!(
+        il::loss_bps(i128::MAX / 2, 4),
+        Err(LpStakingError::Overflow)
+    );
+}
+
+#[test]
+fn test_long_locks_compensated_for_impermanent_loss() {
+    let s = setup();
+    let gov = stellar_asset(&s.env);
+    gov.mint(&s.client.address, 1_000_000);
+    let mut config = IlConfig {
+        token: gov.address.clone(),
+        min_lock: 30 * DAY,
+        coverage_bps: 5_000,
+        twap_window: 3_600,
+        share_value: 2 * il::VALUE_SCALE,
+    };
+    config.coverage_bps = 10_001;
+    let result = s
+        .client
+        .try_set_il_protection(&s.admin, &Some(config.clone()));
+    assert_eq!(result, Err(Ok(LpStakingError::InvalidConfig)));
+    config.coverage_bps = 5_000;
+    s.client.set_il_protection(&s.admin, &Some(config));
+
+    advance(&s.env, 3_600);
+    let covered = s.client.stake(&s.alice, &0, &1_000, &(30 * DAY));
+    let uncovered = s.client.stake(&s.bob, &0, &1_000, &0);
+    assert_eq!(
+        s.client.get_stake(&covered).unwrap().entry_price,
+        10_000_000
+    );
+
+    // Dump token A into the pair so its price falls to about a quarter
+    let trader = Address::generate(&s.env);
+    s.token_a.mint(&trader, 2_000);
+    s.amm.swap(&trader, &PAIR, &s.token_a.address, &2_000, &0);
+    let exit_price = s.amm.get_spot_price(&PAIR);
+    let il_bps = il::loss_bps(10_000_000, exit_price).unwrap();
+    assert!(il_bps > 1_900);
+
+    advance(&s.env, 30 * DAY);
+    s.client.unstake(&s.bob, &uncovered);
+    assert_no_standard_event(&s.env, topics::IL_COMPENSATED);
+    assert_eq!(gov.balance(&s.bob), 0);
+
+    s.client.unstake(&s.alice, &covered);
+    let expected = 2_000 * il_bps as i128 * 5_000 / 100_000_000;
+    assert_eq!(gov.balance(&s.alice), expected);
+    let compensated = assert_standard_event(&s.env, topics::IL_COMPENSATED, |_| true);
+    let event: IlCompensationEvent = payload(&compensated);
+    assert_eq!(event.exit_price, exit_price);
+    assert_eq!(event.amount, expected);
+    assert_eq!(
+        compensated.metadata::<u32>(&s.env, metadata_keys::IL_BPS_KEY),
+        Some(il_bps)
+    );
+}
@@ -0,0 +1,287 @@
+//! Dutch and English auctions
+//!
+//! Auctioned tokens are escrowed in the marketplace for the auction's
+//! lifetime so settlement can never fail after bids have been taken.
+//!
+//! * Dutch: the price declines linearly from `start_price` to `floor_price`
+//!   between `start_time` and `end_time`; the first buyer settles at the
+//!   current price.
+//! * English: bids are escrowed and the previous high bidder is refunded
+//!   automatically. A bid inside the final `EXTENSION_WINDOW` pushes the end
+//!   out so the auction cannot be sniped. Anyone can settle after the end.
+
+use crate::{load_config, require_sellable, settle, DataKey, MarketConfig, MarketError};
+use shared::events::{AuctionSettledEvent, BidEvent, EventEmitter, ListingEvent};
+use soroban_sdk::{contracttype, token, Address, Env};
+
+/// Bids in the last five minutes extend the auction to five minutes from
+/// the bid
+pub const EXTENSION_WINDOW: u64 = 5 * 60;
+/// Each English bid must beat the previous one by 5%
+pub const MIN_BID_INCREMENT_BPS: i128 = 500;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AuctionKind {
+    Dutch,
+    English,
+}
+
+/// An auction for one escrowed token. For English auctions `start_price`
+/// is the reserve and `floor_price` equals it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub seller: Address,
+    pub kind: AuctionKind,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+/// Highest bid in an English auction, held in escrow
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bid {
+    pub bidder: Address,
+    pub amount: i128,
+}
+
+pub fn get_auction(env: &Env, id: u64) -> Option<Auction> {
+    env.storage().persistent().get(&DataKey::Auction(id))
+}
+
+pub fn get_bid(env: &Env, id: u64) -> Option<Bid> {
+    env.storage().persistent().get(&DataKey::Bid(id))
+}
+
+fn require_auction(env: &Env, id: u64) -> Result<Auction, MarketError> {
+    get_auction(env, id).ok_or(MarketError::AuctionNotFound)
+}
+
+/// Escrow the token and open an auction
+pub fn create(
+    env: &Env,
+    seller: &Address,
+    id: u64,
+    kind: AuctionKind,
+    start_price: i128,
+    floor_price: i128,
+    duration: u64,
+) -> Result<(), MarketError> {
+    let config = load_config(env)?;
+    seller.require_auth();
+    if floor_price <= 0 || start_price < floor_price {
+        return Err(MarketError::InvalidPrice);
+    }
+    if duration == 0 {
+        return Err(MarketError::InvalidDuration);
+    }
+    require_sellable(env, &config, seller, id)?;
+
+    let market = env.current_contract_address();
+    crate::NftClient::new(env, &config.nft).transfer_from(&market, seller, &market, &id);
+    // Any fixed-price listing is superseded by the auction
+    env.storage().persistent().remove(&DataKey::Listing(id));
+
+    let now = env.ledger().timestamp();
+    let auction = Auction {
+        seller: seller.clone(),
+        kind,
+        start_price,
+        floor_price,
+        start_time: now,
+        end_time: now + duration,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), &auction);
+
+    EventEmitter::auction_created(
+        env,
+        ListingEvent {
+            nft: config.nft,
+            id,
+            seller: seller.clone(),
+            price: start_price,
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Current Dutch price, declining linearly to the floor
+pub fn current_price(env: &Env, auction: &Auction) -> i128 {
+    let now = env.ledger().timestamp();
+    if auction.kind == AuctionKind::English || now >= auction.end_time {
+        return auction.floor_price;
+    }
+    let elapsed = (now - auction.start_time) as i128;
+    let duration = (auction.end_time - auction.start_time) as i128;
+    auction.start_price - (auction.start_price - auction.floor_price) * elapsed / duration
+}
+
+/// Buy a Dutch auction at the current price
+pub fn buy(env: &Env, buyer: &Address, id: u64) -> Result<i128, MarketError> {
+    let config = load_config(env)?;
+    buyer.require_auth();
+    let auction = require_auction(env, id)?;
+    if auction.kind != AuctionKind::Dutch {
+        return Err(MarketError::WrongAuctionKind);
+    }
+    if env.ledger().timestamp() >= auction.end_time {
+        return Err(MarketError::AuctionEnded);
+    }
+    if auction.seller == *buyer {
+        return Err(MarketError::CannotBuyOwn);
+    }
+
+    let price = current_price(env, &auction);
+    env.storage().persistent().remove(&DataKey::Auction(id));
+    settle(env, &config, buyer, &auction.seller, buyer, id, price)?;
+    emit_settled(env, &config, &auction, id, buyer, price);
+    Ok(price)
+}
+
+/// Place an escrowed bid on an English auction, refunding the previous
+/// high bidder
+pub fn bid(env: &Env, bidder: &Address, id: u64, amount: i128) -> Result<(), MarketError> {
+    let config = load_config(env)?;
+    bidder.require_auth();
+    let mut auction = require_auction(env, id)?;
+    if auction.kind != AuctionKind::English {
+        return Err(MarketError::WrongAuctionKind);
+    }
+    let now = env.ledger().timestamp();
+    if now >= auction.end_time {
+        return Err(MarketError::AuctionEnded);
+    }
+    if auction.seller == *bidder {
+        return Err(MarketError::CannotBuyOwn);
+    }
+
+    let previous = get_bid(env, id);
+    let min_bid = match &previous {
+        Some(previous) => {
+            let increment =
+                (previous.amount * MIN_BID_INCREMENT_BPS / crate::BPS_DENOMINATOR).max(1);
+            previous.amount + increment
+        }
+        None => auction.start_price,
+    };
+    if amount < min_bid {
+        return Err(MarketError::BidTooLow);
+    }
+
+    let token = token::Client::new(env, &config.payment_token);
+    let market = env.current_contract_address();
+    token.transfer(bidder, &market, &amount);
+    if let Some(previous) = previous {
+        token.transfer(&market, &previous.bidder, &previous.amount);
+    }
+    env.storage().persistent().set(
+        &DataKey::Bid(id),
+        &Bid {
+            bidder: bidder.clone(),
+            amount,
+        },
+    );
+
+    if auction.end_time - now < EXTENSION_WINDOW {
+        auction.end_time = now + EXTENSION_WINDOW;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(id), &auction);
+    }
+
+    EventEmitter::bid_placed(
+        env,
+        BidEvent {
+            nft: config.nft,
+            id,
+            bidder: bidder.clone(),
+            amount,
+            end_time: auction.end_time,
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Close an English auction after its end. The winner pays out of escrow;
+/// without bids the token returns to the seller.
+pub fn settle_english(env: &Env, id: u64) -> Result<(), MarketError> {
+    let config = load_config(env)?;
+    let auction = require_auction(env, id)?;
+    if auction.kind != AuctionKind::English {
+        return Err(MarketError::WrongAuctionKind);
+    }
+    if env.ledger().timestamp() < auction.end_time {
+        return Err(MarketError::AuctionActive);
+    }
+
+    env.storage().persistent().remove(&DataKey::Auction(id));
+    match get_bid(env, id) {
+        Some(winning) => {
+            env.storage().persistent().remove(&DataKey::Bid(id));
+            let market = env.current_contract_address();
+            settle(
+                env,
+                &config,
+                &market,
+                &auction.seller,
+                &winning.bidder,
+                id,
+                winning.amount,
+            )?;
+            emit_settled(env, &config, &auction, id, &winning.bidder, winning.amount);
+        }
+        None => return_to_seller(env, &config, &auction, id),
+    }
+    Ok(())
+}
+
+/// Withdraw an auction that has no bids and return the token
+pub fn cancel(env: &Env, seller: &Address, id: u64) -> Result<(), MarketError> {
+    let config = load_config(env)?;
+    seller.require_auth();
+    let auction = require_auction(env, id)?;
+    if auction.seller != *seller {
+        return Err(MarketError::Unauthorized);
+    }
+    if get_bid(env, id).is_some() {
+        return Err(MarketError::AuctionActive);
+    }
+
+    env.storage().persistent().remove(&DataKey::Auction(id));
+    return_to_seller(env, &config, &auction, id);
+    Ok(())
+}
+
+fn return_to_seller(env: &Env, config: &MarketConfig, auction: &Auction, id: u64) {
+    let market = env.current_contract_address();
+    crate::NftClient::new(env, &config.nft).transfer(&market, &auction.seller, &id);
+    emit_settled(env, config, auction, id, &auction.seller, 0);
+}
+
+fn emit_settled(
+    env: &Env,
+    config: &MarketConfig,
+    auction: &Auction,
+    id: u64,
+    winner: &Address,
+    amount: i128,
+) {
+    EventEmitter::auction_settled(
+        env,
+        AuctionSettledEvent {
+            nft: config.nft.clone(),
+            id,
+            seller: auction.seller.clone(),
+            winner: winner.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
@@ -0,0 +1,564 @@
+//! NFT marketplace
+//!
+//! Fixed-price listings and escrowed offers for one NFT collection. Sellers
+//! approve the marketplace for a token before listing it; settlement pays
+//! the marketplace fee through the shared `FeeManager`, the creator royalty
+//! reported by the collection's `royalty_info`, and the remainder to the
+//! seller, then moves the token to the buyer. Dutch and English auctions
+//! live in `auction` and settle through the same path.
+
+#![no_std]
+
+mod auction;
+
+pub use auction::{Auction, AuctionKind, Bid, EXTENSION_WINDOW, MIN_BID_INCREMENT_BPS};
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, ListingEvent, OfferEvent, RoyaltyPaidEvent, SaleEvent};
+use shared::fees::FeeManager;
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, Env,
+};
+
+/// Basis-point denominator for fees
+pub const BPS_DENOMINATOR: i128 = 10_000;
+/// Highest marketplace fee the admin can set (10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MarketError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidPrice = 4,
+    InvalidFee = 5,
+    NotOwner = 6,
+    NotApproved = 7,
+    ListingNotFound = 8,
+    OfferNotFound = 9,
+    TokenNotFound = 10,
+    CannotBuyOwn = 11,
+    AuctionNotFound = 12,
+    AuctionActive = 13,
+    AuctionEnded = 14,
+    BidTooLow = 15,
+    InvalidDuration = 16,
+    WrongAuctionKind = 17,
+}
+
+/// The subset of the NFT contract interface the marketplace relies on
+#[contractclient(name = "NftClient")]
+pub trait NftInterface {
+    fn owner_of(env: Env, id: u64) -> Result<Address, soroban_sdk::Error>;
+    fn get_approved(env: Env, id: u64) -> Option<Address>;
+    fn transfer(env: Env, from: Address, to: Address, id: u64) -> Result<(), soroban_sdk::Error>;
+    fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<(), soroban_sdk::Error>;
+    fn royalty_info(env: Env, id: u64, sale_price: i128) -> Option<(Address, i128)>;
+}
+
+/// Marketplace settings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketConfig {
+    pub admin: Address,
+    pub nft: Address,
+    pub payment_token: Address,
+    pub fee_recipient: Address,
+    pub fee_bps: u32,
+}
+
+/// A fixed-price listing
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub seller: Address,
+    pub price: i128,
+    pub listed_at: u64,
+}
+
+/// An offer, escrowed in the marketplace until accepted or cancelled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offer {
+    pub amount: i128,
+    pub made_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Listing(u64),
+    Offer(u64, Address),
+    Auction(u64),
+    Bid(u64),
+}
+
+#[contract]
+pub struct MarketplaceContract;
+
+#[contractimpl]
+impl MarketplaceContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        nft: Address,
+        payment_token: Address,
+        fee_recipient: Address,
+        fee_bps: u32,
+    ) -> Result<(), MarketError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(MarketError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if fee_bps > MAX_FEE_BPS {
+            return Err(MarketError::InvalidFee);
+        }
+        let config = MarketConfig {
+            admin,
+            nft,
+            payment_token,
+            fee_recipient,
+            fee_bps,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn set_fee(
+        env: Env,
+        admin: Address,
+        fee_recipient: Address,
+        fee_bps: u32,
+    ) -> Result<(), MarketError> {
+        let mut config = require_admin(&env, &admin)?;
+        if fee_bps > MAX_FEE_BPS {
+            return Err(MarketError::InvalidFee);
+        }
+        config.fee_recipient = fee_recipient;
+        config.fee_bps = fee_bps;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// List token `id` at a fixed price. The seller must own the token and
+    /// have approved the marketplace for it.
+    pub fn list(env: Env, seller: Address, id: u64, price: i128) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        seller.require_auth();
+        if price <= 0 {
+            return Err(MarketError::InvalidPrice);
+        }
+        require_sellable(&env, &config, &seller, id)?;
+
+        let listing = Listing {
+            seller: seller.clone(),
+            price,
+            listed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Listing(id), &listing);
+
+        EventEmitter::listed(
+            &env,
+            ListingEvent {
+                nft: config.nft,
+                id,
+                seller,
+                price,
+                timestamp: listing.listed_at,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn cancel_listing(env: Env, seller: Address, id: u64) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        seller.require_auth();
+        let listing = get_listing(&env, id)?;
+        if listing.seller != seller {
+            return Err(MarketError::Unauthorized);
+        }
+        env.storage().persistent().remove(&DataKey::Listing(id));
+
+        EventEmitter::unlisted(
+            &env,
+            ListingEvent {
+                nft: config.nft,
+                id,
+                seller,
+                price: listing.price,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Buy a listed token at its asking price
+    pub fn buy(env: Env, buyer: Address, id: u64) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        buyer.require_auth();
+        let listing = get_listing(&env, id)?;
+        if listing.seller == buyer {
+            return Err(MarketError::CannotBuyOwn);
+        }
+        // A listing goes stale if the seller moved the token or revoked the
+        // approval after listing
+        require_sellable(&env, &config, &listing.seller, id)?;
+
+        env.storage().persistent().remove(&DataKey::Listing(id));
+        settle(
+            &env,
+            &config,
+            &buyer,
+            &listing.seller,
+            &buyer,
+            id,
+            listing.price,
+        )
+    }
+
+    /// Offer `amount` for token `id`. The amount is escrowed; a second offer
+    /// from the same buyer replaces the first and refunds it.
+    pub fn make_offer(env: Env, buyer: Address, id: u64, amount: i128) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        buyer.require_auth();
+        if amount <= 0 {
+            return Err(MarketError::InvalidPrice);
+        }
+        let owner = owner_of(&env, &config, id)?;
+        if owner == buyer {
+            return Err(MarketError::CannotBuyOwn);
+        }
+
+        let key = DataKey::Offer(id, buyer.clone());
+        let token = token::Client::new(&env, &config.payment_token);
+        let market = env.current_contract_address();
+        if let Some(previous) = env.storage().persistent().get::<_, Offer>(&key) {
+            token.transfer(&market, &buyer, &previous.amount);
+        }
+        token.transfer(&buyer, &market, &amount);
+
+        let offer = Offer {
+            amount,
+            made_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &offer);
+
+        EventEmitter::offer_made(
+            &env,
+            OfferEvent {
+                nft: config.nft,
+                id,
+                buyer,
+                amount,
+                timestamp: offer.made_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw an offer and refund the escrow
+    pub fn cancel_offer(env: Env, buyer: Address, id: u64) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        buyer.require_auth();
+        let offer = take_offer(&env, id, &buyer)?;
+        token::Client::new(&env, &config.payment_token).transfer(
+            &env.current_contract_address(),
+            &buyer,
+            &offer.amount,
+        );
+
+        EventEmitter::offer_cancelled(
+            &env,
+            OfferEvent {
+                nft: config.nft,
+                id,
+                buyer,
+                amount: offer.amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Sell token `id` to `buyer` at their escrowed offer
+    pub fn accept_offer(
+        env: Env,
+        seller: Address,
+        id: u64,
+        buyer: Address,
+    ) -> Result<(), MarketError> {
+        let config = load_config(&env)?;
+        seller.require_auth();
+        require_sellable(&env, &config, &seller, id)?;
+        let offer = take_offer(&env, id, &buyer)?;
+
+        env.storage().persistent().remove(&DataKey::Listing(id));
+        let market = env.current_contract_address();
+        settle(&env, &config, &market, &seller, &buyer, id, offer.amount)
+    }
+
+    // ---- auctions ----
+
+    /// Auction token `id` with a price declining from `start_price` to
+    /// `floor_price` over `duration` seconds
+    pub fn create_dutch_auction(
+        env: Env,
+        seller: Address,
+        id: u64,
+        start_price: i128,
+        floor_price: i128,
+        duration: u64,
+    ) -> Result<(), MarketError> {
+        auction::create(
+            &env,
+            &seller,
+            id,
+            AuctionKind::Dutch,
+            start_price,
+            floor_price,
+            duration,
+        )
+    }
+
+    /// Auction token `id` to the highest bidder at or above `reserve_price`
+    pub fn create_english_auction(
+        env: Env,
+        seller: Address,
+        id: u64,
+        reserve_price: i128,
+        duration: u64,
+    ) -> Result<(), MarketError> {
+        auction::create(
+            &env,
+            &seller,
+            id,
+            AuctionKind::English,
+            reserve_price,
+            reserve_price,
+            duration,
+        )
+    }
+
+    /// Buy a Dutch auction at its current price. Returns the price paid.
+    pub fn buy_dutch(env: Env, buyer: Address, id: u64) -> Result<i128, MarketError> {
+        auction::buy(&env, &buyer, id)
+    }
+
+    pub fn bid(env: Env, bidder: Address, id: u64, amount: i128) -> Result<(), MarketError> {
+        auction::bid(&env, &bidder, id, amount)
+    }
+
+    /// Settle an ended English auction. Callable by anyone.
+    pub fn settle_auction(env: Env, id: u64) -> Result<(), MarketError> {
+        auction::settle_english(&env, id)
+    }
+
+    /// Withdraw an auction without bids and reclaim the token
+    pub fn cancel_auction(env: Env, seller: Address, id: u64) -> Result<(), MarketError> {
+        auction::cancel(&env, &seller, id)
+    }
+
+    // ---- views ----
+
+    pub fn config(env: Env) -> Result<MarketConfig, MarketError> {
+        load_config(&env)
+    }
+
+    pub fn listing(env: Env, id: u64) -> Option<Listing> {
+        env.storage().persistent().get(&DataKey::Listing(id))
+    }
+
+    pub fn offer(env: Env, id: u64, buyer: Address) -> Option<Offer> {
+        env.storage().persistent().get(&DataKey::Offer(id, buyer))
+    }
+
+    pub fn auction(env: Env, id: u64) -> Option<Auction> {
+        auction::get_auction(&env, id)
+    }
+
+    pub fn highest_bid(env: Env, id: u64) -> Option<Bid> {
+        auction::get_bid(&env, id)
+    }
+
+    /// Current Dutch price, or the reserve for an English auction
+    pub fn auction_price(env: Env, id: u64) -> Result<i128, MarketError> {
+        let auction = auction::get_auction(&env, id).ok_or(MarketError::AuctionNotFound)?;
+        Ok(auction::current_price(&env, &auction))
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), MarketError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<MarketConfig, MarketError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(MarketError::Unauthorized);
+    }
+    Ok(config)
+}
+
+pub(crate) fn load_config(env: &Env) -> Result<MarketConfig, MarketError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(MarketError::NotInitialized)
+}
+
+fn get_listing(env: &Env, id: u64) -> Result<Listing, MarketError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Listing(id))
+        .ok_or(MarketError::ListingNotFound)
+}
+
+fn take_offer(env: &Env, id: u64, buyer: &Address) -> Result<Offer, MarketError> {
+    let key = DataKey::Offer(id, buyer.clone());
+    let offer = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(MarketError::OfferNotFound)?;
+    env.storage().persistent().remove(&key);
+    Ok(offer)
+}
+
+fn owner_of(env: &Env, config: &MarketConfig, id: u64) -> Result<Address, MarketError> {
+    match NftClient::new(env, &config.nft).try_owner_of(&id) {
+        Ok(Ok(owner)) => Ok(owner),
+        _ => Err(MarketError::TokenNotFound),
+    }
+}
+
+/// Check `seller` owns token `id` and the marketplace may move it
+pub(crate) fn require_sellable(
+    env: &Env,
+    config: &MarketConfig,
+    seller: &Address,
+    id: u64,
+) -> Result<(), MarketError> {
+    if owner_of(env, config, id)? != *seller {
+        return Err(MarketError::NotOwner);
+    }
+    let approved = NftClient::new(env, &config.nft).get_approved(&id);
+    if approved != Some(env.current_contract_address()) {
+        return Err(MarketError::NotApproved);
+    }
+    Ok(())
+}
+
+/// Pay out a sale from `payer` and deliver the token, either from the
+/// seller under approval or out of auction escrow. The fee and royalty come
+/// out of `price`; the seller receives the rest.
+pub(crate) fn settle(
+    env: &Env,
+    config: &MarketConfig,
+    payer: &Address,
+    seller: &Address,
+    buyer: &Address,
+    id: u64,
+    price: i128,
+) -> Result<(), MarketError> {
+    let nft = NftClient::new(env, &config.nft);
+    let timestamp = env.ledger().timestamp();
+
+    let fee = price * config.fee_bps as i128 / BPS_DENOMINATOR;
+    FeeManager::collect_fee(
+        env,
+        &config.payment_token,
+        payer,
+        &config.fee_recipient,
+        fee,
+    )
+    .map_err(|_| MarketError::InvalidPrice)?;
+
+    let mut royalty = 0;
+    if let Some((receiver, amount)) = nft.royalty_info(&id, &price) {
+        if amount < 0 || fee + amount > price {
+            return Err(MarketError::InvalidPrice);
+        }
+        FeeManager::collect_fee(env, &config.payment_token, payer, &receiver, amount)
+            .map_err(|_| MarketError::InvalidPrice)?;
+        royalty = amount;
+        EventEmitter::royalty_paid(
+            env,
+            RoyaltyPaidEvent {
+                nft: config.nft.clone(),
+                id,
+                receiver,
+                amount,
+                sale_price: price,
+                timestamp,
+            },
+        );
+    }
+
+    token::Client::new(env, &config.payment_token).transfer(
+        payer,
+        seller,
+        &(price - fee - royalty),
+    );
+    let market = env.current_contract_address();
+    let delivered = if owner_of(env, config, id)? == market {
+        nft.try_transfer(&market, buyer, &id)
+    } else {
+        nft.try_transfer_from(&market, seller, buyer, &id)
+    };
+    if !matches!(delivered, Ok(Ok(()))) {
+        return Err(MarketError::NotApproved);
+    }
+
+    EventEmitter::sale(
+        env,
+        SaleEvent {
+            nft: config.nft.clone(),
+            id,
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            price,
+            fee,
+            royalty,
+            timestamp,
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
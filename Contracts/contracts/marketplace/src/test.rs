@@ -0,0 +1,269 @@
+#![cfg(test)]
+
+use super::*;
+use nft::{NftContract, NftContractClient};
+use shared::events::metadata_keys::{AMOUNT_KEY, BIDDER_KEY, FEE_KEY, PRICE_KEY};
+use shared::events::topics;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use test_utils::{assert_standard_event, payload};
+
+struct Setup {
+    env: Env,
+    market: MarketplaceContractClient<'static>,
+    nft: NftContractClient<'static>,
+    token: TokenClient<'static>,
+    nft_admin: Address,
+    fee_recipient: Address,
+    seller: Address,
+    buyer: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let nft_admin = Address::generate(&env);
+    let nft_id = env.register_contract(None, NftContract);
+    let nft = NftContractClient::new(&env, &nft_id);
+    nft.initialize(
+        &nft_admin,
+        &String::from_str(&env, "Stellara Collectibles"),
+        &String::from_str(&env, "SCOL"),
+    );
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+
+    let fee_recipient = Address::generate(&env);
+    let market_id = env.register_contract(None, MarketplaceContract);
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    // 2.5% marketplace fee
+    market.initialize(&nft_admin, &nft_id, &token_id, &fee_recipient, &250);
+
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&buyer, &100_000);
+
+    Setup {
+        env,
+        market,
+        nft,
+        token,
+        nft_admin,
+        fee_recipient,
+        seller,
+        buyer,
+    }
+}
+
+fn mint_approved(s: &Setup) -> u64 {
+    let id = s.nft.mint(
+        &s.nft_admin,
+        &s.seller,
+        &String::from_str(&s.env, "ipfs://1"),
+    );
+    s.nft.approve(&s.seller, &s.market.address, &id);
+    id
+}
+
+#[test]
+fn test_buy_pays_fee_royalty_and_seller() {
+    let s = setup();
+    let creator = Address::generate(&s.env);
+    let id = s.nft.mint(
+        &s.nft_admin,
+        &s.seller,
+        &String::from_str(&s.env, "ipfs://1"),
+    );
+    // 5% creator royalty
+    s.nft.set_royalty(&s.nft_admin, &id, &creator, &500);
+
+    let result = s.market.try_list(&s.seller, &id, &10_000);
+    assert_eq!(result, Err(Ok(MarketError::NotApproved)));
+    s.nft.approve(&s.seller, &s.market.address, &id);
+    s.market.list(&s.seller, &id, &10_000);
+    assert_standard_event(&s.env, topics::LISTED, |_| true);
+
+    s.market.buy(&s.buyer, &id);
+    assert_eq!(s.nft.owner_of(&id), s.buyer);
+    assert_eq!(s.market.listing(&id), None);
+    assert_eq!(s.token.balance(&s.fee_recipient), 250);
+    assert_eq!(s.token.balance(&creator), 500);
+    assert_eq!(s.token.balance(&s.seller), 9_250);
+    assert_eq!(s.token.balance(&s.buyer), 90_000);
+
+    let royalty = assert_standard_event(&s.env, topics::ROYALTY_PAID, |_| true);
+    let royalty: RoyaltyPaidEvent = payload(&royalty);
+    assert_eq!((royalty.receiver, royalty.amount), (creator, 500));
+
+    let sale = assert_standard_event(&s.env, topics::SALE, |_| true);
+    assert_eq!(sale.metadata::<i128>(&s.env, PRICE_KEY), Some(10_000));
+    assert_eq!(sale.metadata::<i128>(&s.env, FEE_KEY), Some(250));
+    let sale: SaleEvent = payload(&sale);
+    assert_eq!(sale.royalty, 500);
+}
+
+#[test]
+fn test_stale_and_cancelled_listings() {
+    let s = setup();
+    let id = mint_approved(&s);
+    s.market.list(&s.seller, &id, &1_000);
+
+    let result = s.market.try_cancel_listing(&s.buyer, &id);
+    assert_eq!(result, Err(Ok(MarketError::Unauthorized)));
+    let result = s.market.try_buy(&s.seller, &id);
+    assert_eq!(result, Err(Ok(MarketError::CannotBuyOwn)));
+
+    // Seller moves the token away: the listing can no longer settle
+    let other = Address::generate(&s.env);
+    s.nft.transfer(&s.seller, &other, &id);
+    let result = s.market.try_buy(&s.buyer, &id);
+    assert_eq!(result, Err(Ok(MarketError::NotOwner)));
+
+    s.market.cancel_listing(&s.seller, &id);
+    assert_standard_event(&s.env, topics::UNLISTED, |_| true);
+    let result = s.market.try_buy(&s.buyer, &id);
+    assert_eq!(result, Err(Ok(MarketError::ListingNotFound)));
+}
+
+#[test]
+fn test_offers_are_escrowed_and_settle_on_accept() {
+    let s = setup();
+    let id = mint_approved(&s);
+    let bidder = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&bidder, &1_000);
+
+    s.market.make_offer(&s.buyer, &id, &5_000);
+    // A second offer replaces the first
+    s.market.make_offer(&s.buyer, &id, &8_000);
+    assert_eq!(s.token.balance(&s.buyer), 92_000);
+    assert_eq!(s.token.balance(&s.market.address), 8_000);
+
+    s.market.make_offer(&bidder, &id, &1_000);
+    s.market.cancel_offer(&bidder, &id);
+    assert_eq!(s.token.balance(&bidder), 1_000);
+    assert_standard_event(&s.env, topics::OFFER_CANCELLED, |_| true);
+
+    let result = s.market.try_accept_offer(&s.seller, &id, &bidder);
+    assert_eq!(result, Err(Ok(MarketError::OfferNotFound)));
+
+    s.market.accept_offer(&s.seller, &id, &s.buyer);
+    assert_eq!(s.nft.owner_of(&id), s.buyer);
+    assert_eq!(s.market.offer(&id, &s.buyer), None);
+    assert_eq!(s.token.balance(&s.market.address), 0);
+    assert_eq!(s.token.balance(&s.fee_recipient), 200);
+    assert_eq!(s.token.balance(&s.seller), 7_800);
+}
+
+#[test]
+fn test_fee_is_capped_and_admin_only() {
+    let s = setup();
+    let result = s.market.try_set_fee(&s.seller, &s.fee_recipient, &100);
+    assert_eq!(result, Err(Ok(MarketError::Unauthorized)));
+    let result = s
+        .market
+        .try_set_fee(&s.nft_admin, &s.fee_recipient, &(MAX_FEE_BPS + 1));
+    assert_eq!(result, Err(Ok(MarketError::InvalidFee)));
+
+    s.market.set_fee(&s.nft_admin, &s.fee_recipient, &0);
+    assert_eq!(s.market.config().fee_bps, 0);
+}
+
+#[test]
+fn test_dutch_auction_price_declines() {
+    let s = setup();
+    let id = mint_approved(&s);
+    s.market
+        .create_dutch_auction(&s.seller, &id, &10_000, &4_000, &1_000);
+    // Token is escrowed for the auction
+    assert_eq!(s.nft.owner_of(&id), s.market.address);
+    assert_standard_event(&s.env, topics::AUCTION_CREATED, |_| true);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(s.market.auction_price(&id), 7_000);
+    assert_eq!(s.market.buy_dutch(&s.buyer, &id), 7_000);
+    assert_eq!(s.nft.owner_of(&id), s.buyer);
+    // 7_000 minus 2.5% fee
+    assert_eq!(s.token.balance(&s.seller), 6_825);
+
+    let settled = assert_standard_event(&s.env, topics::AUCTION_SETTLED, |_| true);
+    assert_eq!(
+        settled.metadata::<Address>(&s.env, BIDDER_KEY),
+        Some(s.buyer.clone())
+    );
+    assert_eq!(settled.metadata::<i128>(&s.env, AMOUNT_KEY), Some(7_000));
+    assert_eq!(s.market.auction(&id), None);
+}
+
+#[test]
+fn test_english_auction_refunds_and_extends() {
+    let s = setup();
+    let id = mint_approved(&s);
+    let rival = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&rival, &100_000);
+    s.market
+        .create_english_auction(&s.seller, &id, &1_000, &3_600);
+
+    let result = s.market.try_bid(&s.buyer, &id, &999);
+    assert_eq!(result, Err(Ok(MarketError::BidTooLow)));
+    s.market.bid(&s.buyer, &id, &1_000);
+
+    // Needs a 5% increment over the standing bid
+    let result = s.market.try_bid(&rival, &id, &1_049);
+    assert_eq!(result, Err(Ok(MarketError::BidTooLow)));
+
+    // A late bid pushes the end out and refunds the outbid bidder
+    s.env.ledger().with_mut(|li| li.timestamp = 3_500);
+    s.market.bid(&rival, &id, &2_000);
+    assert_eq!(s.token.balance(&s.buyer), 100_000);
+    assert_eq!(
+        s.market.auction(&id).unwrap().end_time,
+        3_500 + EXTENSION_WINDOW
+    );
+    let bid = assert_standard_event(&s.env, topics::BID_PLACED, |_| true);
+    assert_eq!(
+        bid.metadata::<Address>(&s.env, BIDDER_KEY),
+        Some(rival.clone())
+    );
+    assert_eq!(bid.metadata::<i128>(&s.env, AMOUNT_KEY), Some(2_000));
+
+    s.env.ledger().with_mut(|li| li.timestamp = 3_700);
+    let result = s.market.try_settle_auction(&id);
+    assert_eq!(result, Err(Ok(MarketError::AuctionActive)));
+    let result = s.market.try_cancel_auction(&s.seller, &id);
+    assert_eq!(result, Err(Ok(MarketError::AuctionActive)));
+
+    s.env.ledger().with_mut(|li| li.timestamp = 3_800);
+    s.market.settle_auction(&id);
+    assert_eq!(s.nft.owner_of(&id), rival);
+    assert_eq!(s.token.balance(&s.seller), 1_950);
+    assert_eq!(s.token.balance(&s.market.address), 0);
+}
+
+#[test]
+fn test_unsold_auctions_return_token() {
+    let s = setup();
+    let id = mint_approved(&s);
+    s.market.create_english_auction(&s.seller, &id, &1_000, &60);
+    let result = s.market.try_buy_dutch(&s.buyer, &id);
+    assert_eq!(result, Err(Ok(MarketError::WrongAuctionKind)));
+
+    s.env.ledger().with_mut(|li| li.timestamp = 60);
+    let result = s.market.try_bid(&s.buyer, &id, &1_000);
+    assert_eq!(result, Err(Ok(MarketError::AuctionEnded)));
+    s.market.settle_auction(&id);
+    assert_eq!(s.nft.owner_of(&id), s.seller);
+
+    s.nft.approve(&s.seller, &s.market.address, &id);
+    s.market
+        .create_dutch_auction(&s.seller, &id, &5_000, &1_000, &60);
+    let result = s.market.try_cancel_auction(&s.buyer, &id);
+    assert_eq!(result, Err(Ok(MarketError::Unauthorized)));
+    s.market.cancel_auction(&s.seller, &id);
+    assert_eq!(s.nft.owner_of(&id), s.seller);
+}
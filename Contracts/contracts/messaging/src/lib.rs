@@ -1,7 +1,9 @@
 #![no_std]
 
 use shared::acl::ACL;
-use shared::governance::{GovernanceManager, GovernanceRole, UpgradeProposal};
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::governance::{GovernanceManager, GovernanceRole, ProposalSummary, UpgradeProposal};
+use shared::pagination::Pagination;
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec,
 };
@@ -619,7 +621,6 @@ impl UpgradeableMessagingContract {
         Ok(ACL::has_permission(&env, &user, &permission))
     }
 
-    #[allow(dead_code)]
     fn require_admin_role(env: &Env, admin: &Address) -> Result<(), MessagingError> {
         let roles: Map<Address, GovernanceRole> = env
             .storage()
@@ -638,6 +639,54 @@ impl UpgradeableMessagingContract {
         Ok(())
     }
 
+    /// Propose `new_admin` to take over the admin role; takes effect once
+    /// they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), MessagingError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        Self::require_admin_role(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending proposal, taking the governance and ACL admin roles
+    /// from the address that proposed it
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let previous = PendingAdmin::get(&env).map(|pending| pending.current_admin);
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let roles_key = symbol_short!("roles");
+        let admin_role = Symbol::new(&env, "admin");
+        let mut roles: Map<Address, GovernanceRole> = env
+            .storage()
+            .persistent()
+            .get(&roles_key)
+            .unwrap_or_else(|| Map::new(&env));
+        if let Some(previous) = previous {
+            roles.remove(previous.clone());
+            ACL::revoke_role(&env, &previous, &admin_role);
+        }
+        roles.set(admin.clone(), GovernanceRole::Admin);
+        ACL::assign_role(&env, &admin, &admin_role);
+        env.storage().persistent().set(&roles_key, &roles);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), MessagingError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        Self::require_admin_role(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
     pub fn propose_upgrade(
         env: Env,
         admin: Address,
@@ -718,6 +767,40 @@ impl UpgradeableMessagingContract {
         GovernanceManager::cancel_proposal(&env, proposal_id, admin)
             .map_err(|_| MessagingError::Unauthorized)
     }
+
+    /// Archive an upgrade proposal that has been closed long enough,
+    /// keeping only its summary. Anyone can archive.
+    pub fn archive_upgrade(
+        env: Env,
+        proposal_id: u64,
+        archiver: Address,
+    ) -> Result<ProposalSummary, MessagingError> {
+        archiver.require_auth();
+        require_initialized(&env)?;
+
+        GovernanceManager::archive_proposal(&env, proposal_id, archiver)
+            .map_err(|_| MessagingError::Unauthorized)
+    }
+
+    /// Outcome of an upgrade proposal, archived or not
+    pub fn get_proposal_summary(
+        env: Env,
+        proposal_id: u64,
+    ) -> Result<ProposalSummary, MessagingError> {
+        require_initialized(&env)?;
+        GovernanceManager::get_proposal_summary(&env, proposal_id)
+            .map_err(|_| MessagingError::Unauthorized)
+    }
+
+    /// One page of upgrade proposal summaries, oldest first, and the
+    /// cursor of the next
+    pub fn get_upgrade_proposals(
+        env: Env,
+        page: Pagination,
+    ) -> Result<(Vec<ProposalSummary>, Option<u32>), MessagingError> {
+        require_initialized(&env)?;
+        Ok(GovernanceManager::proposals(&env, &page))
+    }
 }
 
 #[cfg(test)]
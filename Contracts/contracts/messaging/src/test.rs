@@ -354,6 +354,17 @@ fn test_upgrade_rejection_and_cancellation_flow() {
         client.get_upgrade_proposal(&cancel_id).status,
         ProposalStatus::Cancelled
     );
+
+    let (first, next) = client.get_upgrade_proposals(&Pagination::first(1));
+    assert_eq!(first.len(), 1);
+    assert_eq!(first.get(0).unwrap().status, ProposalStatus::Rejected);
+    assert_eq!(next, Some(1));
+    let (second, next) = client.get_upgrade_proposals(&Pagination {
+        cursor: 1,
+        limit: 1,
+    });
+    assert_eq!(second.get(0).unwrap().id, cancel_id);
+    assert_eq!(next, None);
 }
 
 #[test]
@@ -0,0 +1,416 @@
+//! Protocol metrics aggregation
+//!
+//! Contracts registered as reporters push counters here as they operate:
+//! traded volume per pair, fees collected per token and changes in value
+//! locked per token. Volume and fees are summed per epoch; TVL is a level,
+//! so each token keeps a history of the level at the end of every epoch it
+//! changed in, and epochs without changes read the previous level.
+//!
+//! Dashboards and gauge weights read the per-epoch values on-chain. Only
+//! the last `horizon` epochs are kept. TVL histories trim themselves as
+//! they grow; for volume and fees anyone can call
+//! [`ProtocolMetrics::prune`] to delete older epochs, a bounded number per
+//! call.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::epoch::EpochClock;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+/// Distinct metrics a single epoch can track, bounding pruning work
+pub const MAX_METRICS_PER_EPOCH: u32 = 100;
+/// Longest horizon, bounding the length of TVL histories
+pub const MAX_HORIZON: u32 = 366;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MetricsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    InvalidAmount = 5,
+    EpochFull = 6,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    /// Epoch length in seconds
+    pub epoch_length: u64,
+    /// Number of most recent epochs kept
+    pub horizon: u32,
+    /// Start of epoch 0
+    pub genesis: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Metric {
+    /// Volume traded on a pair
+    PairVolume(Symbol),
+    /// Fees collected in a token
+    TokenFees(Address),
+    /// Value locked in a token
+    Tvl(Address),
+}
+
+/// TVL of a token at the end of an epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TvlPoint {
+    pub epoch: u64,
+    pub tvl: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Reporter(Address),
+    /// Volume or fees of an epoch
+    Value(u64, Metric),
+    /// Metrics recorded in an epoch, for pruning
+    EpochMetrics(u64),
+    /// TVL points of a token, oldest first
+    TvlHistory(Address),
+    /// Oldest epoch not yet pruned
+    OldestEpoch,
+    /// Latest epoch a metric was recorded in
+    LastEpoch,
+}
+
+#[contract]
+pub struct ProtocolMetrics;
+
+#[contractimpl]
+impl ProtocolMetrics {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        epoch_length: u64,
+        horizon: u32,
+    ) -> Result<(), MetricsError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(MetricsError::AlreadyInitialized);
+        }
+        if epoch_length == 0 || horizon == 0 || horizon > MAX_HORIZON {
+            return Err(MetricsError::InvalidConfig);
+        }
+        let config = Config {
+            admin,
+            epoch_length,
+            horizon,
+            genesis: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Allow or revoke a contract pushing metrics (admin only)
+    pub fn set_reporter(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        allowed: bool,
+    ) -> Result<(), MetricsError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::Reporter(reporter);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Change how many epochs are kept (admin only). Shrinking it makes
+    /// more epochs prunable.
+    pub fn set_horizon(env: Env, admin: Address, horizon: u32) -> Result<(), MetricsError> {
+        let mut config = require_admin(&env, &admin)?;
+        if horizon == 0 || horizon > MAX_HORIZON {
+            return Err(MetricsError::InvalidConfig);
+        }
+        config.horizon = horizon;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn record_volume(
+        env: Env,
+        reporter: Address,
+        pair: Symbol,
+        amount: i128,
+    ) -> Result<(), MetricsError> {
+        let config = require_reporter(&env, &reporter)?;
+        if amount < 0 {
+            return Err(MetricsError::InvalidAmount);
+        }
+        add(&env, &config, Metric::PairVolume(pair), amount)
+    }
+
+    pub fn record_fees(
+        env: Env,
+        reporter: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), MetricsError> {
+        let config = require_reporter(&env, &reporter)?;
+        if amount < 0 {
+            return Err(MetricsError::InvalidAmount);
+        }
+        add(&env, &config, Metric::TokenFees(token), amount)
+    }
+
+    /// Report `delta` more (or, negative, less) of `token` locked
+    pub fn record_tvl_delta(
+        env: Env,
+        reporter: Address,
+        token: Address,
+        delta: i128,
+    ) -> Result<(), MetricsError> {
+        let config = require_reporter(&env, &reporter)?;
+        let mut history = tvl_history(&env, &token);
+        let tvl = history
+            .last()
+            .map_or(0, |point| point.tvl)
+            .checked_add(delta)
+            .filter(|tvl| *tvl >= 0)
+            .ok_or(MetricsError::InvalidAmount)?;
+
+        let epoch = advance_epoch(&env, &config);
+        match history.last() {
+            Some(point) if point.epoch == epoch => {
+                history.set(history.len() - 1, TvlPoint { epoch, tvl });
+            }
+            _ => history.push_back(TvlPoint { epoch, tvl }),
+        }
+        // Keep the last point before the horizon as the level it starts at
+        let cutoff = horizon_start(&config, epoch);
+        while history.len() > 1 && history.get_unchecked(1).epoch <= cutoff {
+            history.pop_front();
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::TvlHistory(token), &history);
+        Ok(())
+    }
+
+    /// Delete the volume and fees of up to `max_epochs` epochs that fell
+    /// out of the horizon. Returns the number of epochs pruned.
+    pub fn prune(env: Env, max_epochs: u32) -> Result<u32, MetricsError> {
+        let config = load_config(&env)?;
+        let cutoff = horizon_start(&config, current_epoch(&env, &config));
+        let storage = env.storage().persistent();
+        let mut oldest: u64 = storage.get(&DataKey::OldestEpoch).unwrap_or(0);
+        let mut pruned = 0;
+        while oldest < cutoff && pruned < max_epochs {
+            let metrics: Vec<Metric> = storage
+                .get(&DataKey::EpochMetrics(oldest))
+                .unwrap_or(Vec::new(&env));
+            for metric in metrics.iter() {
+                storage.remove(&DataKey::Value(oldest, metric));
+            }
+            storage.remove(&DataKey::EpochMetrics(oldest));
+            oldest += 1;
+            pruned += 1;
+        }
+        storage.set(&DataKey::OldestEpoch, &oldest);
+        Ok(pruned)
+    }
+
+    pub fn current_epoch(env: Env) -> Result<u64, MetricsError> {
+        Ok(current_epoch(&env, &load_config(&env)?))
+    }
+
+    /// Value of `metric` in `epoch`: the epoch's volume or fees, or the
+    /// TVL at its end. Epochs before the horizon may already read as 0.
+    pub fn value(env: Env, metric: Metric, epoch: u64) -> i128 {
+        match metric {
+            Metric::Tvl(token) => tvl_history(&env, &token)
+                .iter()
+                .take_while(|point| point.epoch <= epoch)
+                .last()
+                .map_or(0, |point| point.tvl),
+            metric => env
+                .storage()
+                .persistent()
+                .get(&DataKey::Value(epoch, metric))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Volume or fees summed over the `epochs` epochs ending with the
+    /// current one, e.g. recent pair volume for gauge weights
+    pub fn total_over(env: Env, metric: Metric, epochs: u32) -> Result<i128, MetricsError> {
+        let config = load_config(&env)?;
+        if matches!(metric, Metric::Tvl(_)) {
+            return Err(MetricsError::InvalidConfig);
+        }
+        let current = current_epoch(&env, &config);
+        let epochs = epochs.min(config.horizon) as u64;
+        let first = (current + 1).saturating_sub(epochs);
+        let storage = env.storage().persistent();
+        Ok((first..=current)
+            .filter_map(|epoch| storage.get::<_, i128>(&DataKey::Value(epoch, metric.clone())))
+            .sum())
+    }
+
+    /// Current value locked in `token`
+    pub fn tvl(env: Env, token: Address) -> i128 {
+        tvl_history(&env, &token)
+            .last()
+            .map_or(0, |point| point.tvl)
+    }
+
+    /// TVL of `token` at the end of each epoch it changed in, oldest first
+    pub fn tvl_history(env: Env, token: Address) -> Vec<TvlPoint> {
+        tvl_history(&env, &token)
+    }
+
+    /// Metrics recorded in `epoch`
+    pub fn epoch_metrics(env: Env, epoch: u64) -> Vec<Metric> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EpochMetrics(epoch))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Reporter(reporter))
+    }
+
+    pub fn config(env: Env) -> Result<Config, MetricsError> {
+        load_config(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), MetricsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), MetricsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn add(env: &Env, config: &Config, metric: Metric, amount: i128) -> Result<(), MetricsError> {
+    let epoch = advance_epoch(env, config);
+    track(env, epoch, &metric)?;
+    let key = DataKey::Value(epoch, metric);
+    let value: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &value.saturating_add(amount));
+    Ok(())
+}
+
+/// Remember that `metric` has a value in `epoch`
+fn track(env: &Env, epoch: u64, metric: &Metric) -> Result<(), MetricsError> {
+    let key = DataKey::EpochMetrics(epoch);
+    let mut metrics: Vec<Metric> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if metrics.contains(metric) {
+        return Ok(());
+    }
+    if metrics.len() >= MAX_METRICS_PER_EPOCH {
+        return Err(MetricsError::EpochFull);
+    }
+    metrics.push_back(metric.clone());
+    env.storage().persistent().set(&key, &metrics);
+    Ok(())
+}
+
+fn tvl_history(env: &Env, token: &Address) -> Vec<TvlPoint> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TvlHistory(token.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// First epoch inside the horizon when `current` is the current epoch
+fn horizon_start(config: &Config, current: u64) -> u64 {
+    (current + 1).saturating_sub(config.horizon as u64)
+}
+
+fn require_reporter(env: &Env, reporter: &Address) -> Result<Config, MetricsError> {
+    reporter.require_auth();
+    let config = load_config(env)?;
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Reporter(reporter.clone()))
+    {
+        return Err(MetricsError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn clock(config: &Config) -> EpochClock {
+    EpochClock {
+        genesis: config.genesis,
+        length: config.epoch_length,
+    }
+}
+
+fn current_epoch(env: &Env, config: &Config) -> u64 {
+    clock(config).current_epoch(env)
+}
+
+/// Current epoch, announcing it if this is the first record in it
+fn advance_epoch(env: &Env, config: &Config) -> u64 {
+    let last: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastEpoch)
+        .unwrap_or(0);
+    match clock(config).rollover(env, last) {
+        Some(epoch) => {
+            env.storage().instance().set(&DataKey::LastEpoch, &epoch);
+            epoch
+        }
+        None => last,
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, MetricsError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(MetricsError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, MetricsError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(MetricsError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
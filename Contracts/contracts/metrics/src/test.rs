@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{topics, EpochRolloverEvent};
+use soroban_sdk::symbol_short;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::{assert_standard_event, payload};
+
+const EPOCH: u64 = 100;
+
+fn setup(horizon: u32) -> (Env, ProtocolMetricsClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let client = ProtocolMetricsClient::new(&env, &env.register_contract(None, ProtocolMetrics));
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    client.initialize(&admin, &EPOCH, &horizon);
+    client.set_reporter(&admin, &reporter, &true);
+    (env, client, admin, reporter)
+}
+
+fn advance_epochs(env: &Env, epochs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += epochs * EPOCH);
+}
+
+#[test]
+fn test_volume_and_fees_sum_per_epoch() {
+    let (env, client, admin, reporter) = setup(10);
+    let pair = symbol_short!("XLMUSDC");
+    let token = Address::generate(&env);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_record_volume(&outsider, &pair, &100),
+        Err(Ok(MetricsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_record_volume(&reporter, &pair, &-1),
+        Err(Ok(MetricsError::InvalidAmount))
+    );
+
+    client.record_volume(&reporter, &pair, &100);
+    client.record_volume(&reporter, &pair, &50);
+    client.record_fees(&reporter, &token, &3);
+    advance_epochs(&env, 1);
+    client.record_volume(&reporter, &pair, &70);
+    let rollover: EpochRolloverEvent =
+        payload(&assert_standard_event(&env, topics::EPOCH_ROLLOVER, |_| {
+            true
+        }));
+    assert_eq!((rollover.previous_epoch, rollover.epoch), (0, 1));
+    assert_eq!(rollover.epoch_start, 1_000 + EPOCH);
+
+    let volume = Metric::PairVolume(pair.clone());
+    assert_eq!(client.current_epoch(), 1);
+    assert_eq!(client.value(&volume, &0), 150);
+    assert_eq!(client.value(&volume, &1), 70);
+    assert_eq!(client.value(&Metric::TokenFees(token.clone()), &0), 3);
+    assert_eq!(client.total_over(&volume, &2), 220);
+    assert_eq!(client.total_over(&volume, &1), 70);
+    assert_eq!(client.epoch_metrics(&0).len(), 2);
+
+    client.set_reporter(&admin, &reporter, &false);
+    assert!(!client.is_reporter(&reporter));
+    assert_eq!(
+        client.try_record_fees(&reporter, &token, &1),
+        Err(Ok(MetricsError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_tvl_carries_forward_between_changes() {
+    let (env, client, _, reporter) = setup(3);
+    let token = Address::generate(&env);
+    let tvl = Metric::Tvl(token.clone());
+
+    client.record_tvl_delta(&reporter, &token, &1_000);
+    client.record_tvl_delta(&reporter, &token, &-200);
+    assert_eq!(
+        client.try_record_tvl_delta(&reporter, &token, &-801),
+        Err(Ok(MetricsError::InvalidAmount))
+    );
+    advance_epochs(&env, 2);
+    client.record_tvl_delta(&reporter, &token, &500);
+
+    assert_eq!(client.tvl(&token), 1_300);
+    assert_eq!(client.value(&tvl, &0), 800);
+    assert_eq!(client.value(&tvl, &1), 800);
+    assert_eq!(client.value(&tvl, &2), 1_300);
+    assert_eq!(
+        client.try_total_over(&tvl, &2),
+        Err(Ok(MetricsError::InvalidConfig))
+    );
+
+    // Long after the horizon the history keeps only the level it starts at
+    advance_epochs(&env, 10);
+    client.record_tvl_delta(&reporter, &token, &-300);
+    assert_eq!(client.tvl_history(&token).len(), 2);
+    assert_eq!(client.value(&tvl, &11), 1_300);
+    assert_eq!(client.value(&tvl, &12), 1_000);
+}
+
+#[test]
+fn test_prune_drops_epochs_past_horizon() {
+    let (env, client, admin, reporter) = setup(2);
+    let pair = symbol_short!("XLMUSDC");
+    let volume = Metric::PairVolume(pair.clone());
+
+    for _ in 0..4 {
+        client.record_volume(&reporter, &pair, &10);
+        advance_epochs(&env, 1);
+    }
+    // Epochs 3 and 4 are inside the horizon
+    assert_eq!(client.current_epoch(), 4);
+    assert_eq!(client.total_over(&volume, &10), 10);
+
+    assert_eq!(client.prune(&2), 2);
+    assert_eq!(client.value(&volume, &0), 0);
+    assert_eq!(client.value(&volume, &1), 0);
+    assert_eq!(client.value(&volume, &2), 10);
+    assert_eq!(client.prune(&10), 1);
+    assert_eq!(client.prune(&10), 0);
+    assert!(client.epoch_metrics(&2).is_empty());
+    assert_eq!(client.value(&volume, &3), 10);
+
+    assert_eq!(
+        client.try_set_horizon(&admin, &0),
+        Err(Ok(MetricsError::InvalidConfig))
+    );
+    client.set_horizon(&admin, &1);
+    assert_eq!(client.prune(&10), 1);
+    assert_eq!(client.value(&volume, &3), 0);
+}
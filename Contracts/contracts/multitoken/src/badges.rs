@@ -0,0 +1,109 @@
+//! Soulbound badge ids
+//!
+//! A badge is a token id that cannot be transferred, minted or burned by
+//! the usual paths. Designated issuers hand out one per holder and can
+//! revoke it; a badge created with a validity period lapses that long
+//! after it is issued unless reissued. Each badge carries a weight, and an
+//! account's reputation is the sum of the weights of its live badges.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::DataKey;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    /// Reputation a live badge adds to its holder
+    pub weight: u32,
+    /// Seconds a badge stays live after issue, or 0 for no expiry
+    pub validity: u64,
+}
+
+pub fn get(env: &Env, id: u64) -> Option<Badge> {
+    env.storage().persistent().get(&DataKey::Badge(id))
+}
+
+pub fn is_badge(env: &Env, id: u64) -> bool {
+    env.storage().persistent().has(&DataKey::Badge(id))
+}
+
+pub fn set(env: &Env, id: u64, badge: &Badge) {
+    env.storage().persistent().set(&DataKey::Badge(id), badge);
+}
+
+pub fn is_issuer(env: &Env, id: u64, issuer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Issuer(id, issuer.clone()))
+}
+
+pub fn set_issuer(env: &Env, id: u64, issuer: &Address, allowed: bool) {
+    let key = DataKey::Issuer(id, issuer.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// When `holder`'s badge `id` lapses (0 for never), or `None` if they do
+/// not hold it
+pub fn expiry(env: &Env, holder: &Address, id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BadgeExpiry(holder.clone(), id))
+}
+
+pub fn is_live(env: &Env, holder: &Address, id: u64) -> bool {
+    match expiry(env, holder, id) {
+        Some(0) => true,
+        Some(expires_at) => env.ledger().timestamp() < expires_at,
+        None => false,
+    }
+}
+
+/// Record that `holder` holds badge `id` until `expires_at`
+pub fn hold(env: &Env, holder: &Address, id: u64, expires_at: u64) {
+    if expiry(env, holder, id).is_none() {
+        let mut held = held(env, holder);
+        held.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BadgesOf(holder.clone()), &held);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::BadgeExpiry(holder.clone(), id), &expires_at);
+}
+
+pub fn release(env: &Env, holder: &Address, id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BadgeExpiry(holder.clone(), id));
+    let mut held = held(env, holder);
+    if let Some(index) = held.first_index_of(id) {
+        held.remove(index);
+    }
+    let key = DataKey::BadgesOf(holder.clone());
+    if held.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &held);
+    }
+}
+
+/// Every badge `holder` has been issued and not had revoked, live or not
+pub fn held(env: &Env, holder: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BadgesOf(holder.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn reputation(env: &Env, holder: &Address) -> u32 {
+    held(env, holder)
+        .iter()
+        .filter(|id| is_live(env, holder, *id))
+        .filter_map(|id| get(env, id))
+        .fold(0u32, |total, badge| total.saturating_add(badge.weight))
+}
@@ -0,0 +1,621 @@
+//! Multi-token (semi-fungible) contract for game items and tickets
+//!
+//! Many token ids live under one contract. The admin creates each id with
+//! a metadata URI and an optional supply cap, then mints it to players;
+//! holders transfer and burn any number of ids in one call, or let an
+//! operator such as a game server or marketplace move them.
+//!
+//! Every mint, transfer and burn emits a single event carrying the ids and
+//! amounts it covered, so minting 50 items in one transaction costs one
+//! event rather than 50.
+//!
+//! Ids created with `create_badge` are soulbound badges instead: see the
+//! `badges` module.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{
+    BadgeEvent, EventEmitter, MultiTokenSupplyEvent, MultiTokenTransferEvent, MultiTokenUriEvent,
+};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, vec, Address, Env, String, Vec,
+};
+
+mod badges;
+
+pub use badges::Badge;
+
+/// Most ids a single batch call may touch
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MultiTokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    TokenNotFound = 4,
+    InvalidAmount = 5,
+    /// Empty batch, or ids and amounts of different lengths
+    InvalidBatch = 6,
+    BatchTooLarge = 7,
+    SupplyCapExceeded = 8,
+    InsufficientBalance = 9,
+    NotApproved = 10,
+    /// Badges cannot be minted, transferred or burned, only issued and
+    /// revoked
+    Soulbound = 11,
+    NotBadge = 12,
+    BadgeHeld = 13,
+    BadgeNotHeld = 14,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenInfo {
+    pub uri: String,
+    /// Most that can be in circulation at once, or 0 for no cap
+    pub max_supply: i128,
+    /// Minted minus burned
+    pub supply: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    NextId,
+    Token(u64),
+    Balance(Address, u64),
+    Operator(Address, Address),
+    Badge(u64),
+    Issuer(u64, Address),
+    BadgeExpiry(Address, u64),
+    BadgesOf(Address),
+}
+
+#[contract]
+pub struct MultiToken;
+
+#[contractimpl]
+impl MultiToken {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MultiTokenError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MultiTokenError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Create a token id with its metadata URI. A `max_supply` of 0 leaves
+    /// the supply uncapped. Returns the new id.
+    pub fn create_token(
+        env: Env,
+        admin: Address,
+        uri: String,
+        max_supply: i128,
+    ) -> Result<u64, MultiTokenError> {
+        require_admin(&env, &admin)?;
+        if max_supply < 0 {
+            return Err(MultiTokenError::InvalidAmount);
+        }
+        Ok(new_token(&env, admin, uri, max_supply))
+    }
+
+    /// Create a soulbound badge id worth `weight` reputation. Issued
+    /// badges lapse after `validity` seconds, or never if it is 0. Returns
+    /// the new id.
+    pub fn create_badge(
+        env: Env,
+        admin: Address,
+        uri: String,
+        weight: u32,
+        validity: u64,
+    ) -> Result<u64, MultiTokenError> {
+        require_admin(&env, &admin)?;
+        let id = new_token(&env, admin, uri, 0);
+        badges::set(&env, id, &Badge { weight, validity });
+        Ok(id)
+    }
+
+    /// Allow or disallow `issuer` to issue and revoke badge `id`
+    pub fn set_badge_issuer(
+        env: Env,
+        admin: Address,
+        id: u64,
+        issuer: Address,
+        allowed: bool,
+    ) -> Result<(), MultiTokenError> {
+        require_admin(&env, &admin)?;
+        load_badge(&env, id)?;
+        badges::set_issuer(&env, id, &issuer, allowed);
+        Ok(())
+    }
+
+    /// Issue badge `id` to `to`. Reissuing a lapsed badge renews it.
+    /// Returns when it lapses, or 0 if it never does.
+    pub fn issue_badge(
+        env: Env,
+        issuer: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<u64, MultiTokenError> {
+        require_issuer(&env, &issuer, id)?;
+        let badge = load_badge(&env, id)?;
+        if badges::is_live(&env, &to, id) {
+            return Err(MultiTokenError::BadgeHeld);
+        }
+        if badges::expiry(&env, &to, id).is_none() {
+            let mut info = load_token(&env, id)?;
+            info.supply += 1;
+            env.storage().persistent().set(&DataKey::Token(id), &info);
+            set_balance(&env, &to, id, 1);
+        }
+        let expires_at = match badge.validity {
+            0 => 0,
+            validity => env.ledger().timestamp() + validity,
+        };
+        badges::hold(&env, &to, id, expires_at);
+
+        EventEmitter::badge_issued(
+            &env,
+            BadgeEvent {
+                id,
+                holder: to,
+                issuer,
+                expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(expires_at)
+    }
+
+    /// Take badge `id` back from `holder`, live or lapsed. The admin can
+    /// revoke any badge.
+    pub fn revoke_badge(
+        env: Env,
+        issuer: Address,
+        holder: Address,
+        id: u64,
+    ) -> Result<(), MultiTokenError> {
+        require_issuer(&env, &issuer, id)?;
+        if badges::expiry(&env, &holder, id).is_none() {
+            return Err(MultiTokenError::BadgeNotHeld);
+        }
+        badges::release(&env, &holder, id);
+        set_balance(&env, &holder, id, 0);
+        let mut info = load_token(&env, id)?;
+        info.supply -= 1;
+        env.storage().persistent().set(&DataKey::Token(id), &info);
+
+        EventEmitter::badge_revoked(
+            &env,
+            BadgeEvent {
+                id,
+                holder,
+                issuer,
+                expires_at: 0,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn set_uri(env: Env, admin: Address, id: u64, uri: String) -> Result<(), MultiTokenError> {
+        require_admin(&env, &admin)?;
+        let mut info = load_token(&env, id)?;
+        info.uri = uri.clone();
+        env.storage().persistent().set(&DataKey::Token(id), &info);
+        emit_uri(&env, admin, id, uri);
+        Ok(())
+    }
+
+    pub fn mint(
+        env: Env,
+        admin: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        Self::batch_mint(env.clone(), admin, to, vec![&env, id], vec![&env, amount])
+    }
+
+    /// Mint `amounts[i]` of `ids[i]` to `to` for every i, failing the
+    /// whole batch if any id would exceed its supply cap
+    pub fn batch_mint(
+        env: Env,
+        admin: Address,
+        to: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        require_admin(&env, &admin)?;
+        check_batch(&ids, &amounts)?;
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            let mut info = load_fungible(&env, id)?;
+            info.supply = info
+                .supply
+                .checked_add(amount)
+                .ok_or(MultiTokenError::SupplyCapExceeded)?;
+            if info.max_supply > 0 && info.supply > info.max_supply {
+                return Err(MultiTokenError::SupplyCapExceeded);
+            }
+            env.storage().persistent().set(&DataKey::Token(id), &info);
+            credit(&env, &to, id, amount)?;
+        }
+        EventEmitter::multi_token_minted(
+            &env,
+            MultiTokenSupplyEvent {
+                account: to,
+                ids,
+                amounts,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        let (ids, amounts) = (vec![&env, id], vec![&env, amount]);
+        move_batch(&env, from.clone(), from, to, ids, amounts)
+    }
+
+    pub fn batch_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        move_batch(&env, from.clone(), from, to, ids, amounts)
+    }
+
+    /// Transfer on behalf of `from` as an operator it approved
+    pub fn transfer_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<(), MultiTokenError> {
+        let (ids, amounts) = (vec![&env, id], vec![&env, amount]);
+        move_batch(&env, operator, from, to, ids, amounts)
+    }
+
+    pub fn batch_transfer_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        move_batch(&env, operator, from, to, ids, amounts)
+    }
+
+    pub fn burn(env: Env, owner: Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+        Self::batch_burn(env.clone(), owner, vec![&env, id], vec![&env, amount])
+    }
+
+    /// Burn `amounts[i]` of `ids[i]` from `owner`. Burning frees room
+    /// under the supply cap.
+    pub fn batch_burn(
+        env: Env,
+        owner: Address,
+        ids: Vec<u64>,
+        amounts: Vec<i128>,
+    ) -> Result<(), MultiTokenError> {
+        owner.require_auth();
+        check_batch(&ids, &amounts)?;
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            let mut info = load_fungible(&env, id)?;
+            debit(&env, &owner, id, amount)?;
+            info.supply -= amount;
+            env.storage().persistent().set(&DataKey::Token(id), &info);
+        }
+        EventEmitter::multi_token_burned(
+            &env,
+            MultiTokenSupplyEvent {
+                account: owner,
+                ids,
+                amounts,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Let `operator` move all of `owner`'s tokens, or revoke it
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        let key = DataKey::Operator(owner, operator);
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    // ---- views ----
+
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Operator(owner, operator))
+    }
+
+    /// `owner`'s balance of `id`; a lapsed badge counts as 0
+    pub fn balance_of(env: Env, owner: Address, id: u64) -> i128 {
+        live_balance(&env, &owner, id)
+    }
+
+    /// Balance of `owners[i]` in `ids[i]` for every i
+    pub fn balance_of_batch(
+        env: Env,
+        owners: Vec<Address>,
+        ids: Vec<u64>,
+    ) -> Result<Vec<i128>, MultiTokenError> {
+        if owners.len() != ids.len() {
+            return Err(MultiTokenError::InvalidBatch);
+        }
+        let mut balances = Vec::new(&env);
+        for (owner, id) in owners.iter().zip(ids.iter()) {
+            balances.push_back(live_balance(&env, &owner, id));
+        }
+        Ok(balances)
+    }
+
+    pub fn token_info(env: Env, id: u64) -> Option<TokenInfo> {
+        env.storage().persistent().get(&DataKey::Token(id))
+    }
+
+    pub fn uri(env: Env, id: u64) -> Result<String, MultiTokenError> {
+        Ok(load_token(&env, id)?.uri)
+    }
+
+    /// Minted minus burned; for a badge, issued minus revoked, lapsed
+    /// badges included
+    pub fn total_supply(env: Env, id: u64) -> i128 {
+        Self::token_info(env, id).map_or(0, |info| info.supply)
+    }
+
+    pub fn badge(env: Env, id: u64) -> Option<Badge> {
+        badges::get(&env, id)
+    }
+
+    pub fn is_badge_issuer(env: Env, id: u64, issuer: Address) -> bool {
+        badges::is_issuer(&env, id, &issuer)
+    }
+
+    /// When `holder`'s badge `id` lapses (0 for never), or `None` if they
+    /// do not hold it
+    pub fn badge_expiry(env: Env, holder: Address, id: u64) -> Option<u64> {
+        badges::expiry(&env, &holder, id)
+    }
+
+    /// Badges `holder` holds and that have not lapsed
+    pub fn badges_of(env: Env, holder: Address) -> Vec<u64> {
+        let mut live = Vec::new(&env);
+        for id in badges::held(&env, &holder).iter() {
+            if badges::is_live(&env, &holder, id) {
+                live.push_back(id);
+            }
+        }
+        live
+    }
+
+    /// Sum of the weights of `holder`'s live badges, for modules that
+    /// grant fee discounts or voting thresholds by reputation
+    pub fn reputation_of(env: Env, holder: Address) -> u32 {
+        badges::reputation(&env, &holder)
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), MultiTokenError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), MultiTokenError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Move a batch from `from` to `to`, authorized by `from` itself or an
+/// operator it approved
+fn move_batch(
+    env: &Env,
+    operator: Address,
+    from: Address,
+    to: Address,
+    ids: Vec<u64>,
+    amounts: Vec<i128>,
+) -> Result<(), MultiTokenError> {
+    operator.require_auth();
+    if operator != from
+        && !MultiToken::is_approved_for_all(env.clone(), from.clone(), operator.clone())
+    {
+        return Err(MultiTokenError::NotApproved);
+    }
+    check_batch(&ids, &amounts)?;
+    for (id, amount) in ids.iter().zip(amounts.iter()) {
+        load_fungible(env, id)?;
+        debit(env, &from, id, amount)?;
+        credit(env, &to, id, amount)?;
+    }
+    EventEmitter::multi_token_transferred(
+        env,
+        MultiTokenTransferEvent {
+            operator,
+            from,
+            to,
+            ids,
+            amounts,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+fn check_batch(ids: &Vec<u64>, amounts: &Vec<i128>) -> Result<(), MultiTokenError> {
+    if ids.is_empty() || ids.len() != amounts.len() {
+        return Err(MultiTokenError::InvalidBatch);
+    }
+    if ids.len() > MAX_BATCH_SIZE {
+        return Err(MultiTokenError::BatchTooLarge);
+    }
+    if amounts.iter().any(|amount| amount <= 0) {
+        return Err(MultiTokenError::InvalidAmount);
+    }
+    Ok(())
+}
+
+fn live_balance(env: &Env, owner: &Address, id: u64) -> i128 {
+    if badges::is_badge(env, id) && !badges::is_live(env, owner, id) {
+        return 0;
+    }
+    balance(env, owner, id)
+}
+
+fn balance(env: &Env, owner: &Address, id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(owner.clone(), id))
+        .unwrap_or(0)
+}
+
+fn set_balance(env: &Env, owner: &Address, id: u64, amount: i128) {
+    let key = DataKey::Balance(owner.clone(), id);
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+fn credit(env: &Env, owner: &Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+    let updated = balance(env, owner, id)
+        .checked_add(amount)
+        .ok_or(MultiTokenError::InvalidAmount)?;
+    set_balance(env, owner, id, updated);
+    Ok(())
+}
+
+fn debit(env: &Env, owner: &Address, id: u64, amount: i128) -> Result<(), MultiTokenError> {
+    let current = balance(env, owner, id);
+    if current < amount {
+        return Err(MultiTokenError::InsufficientBalance);
+    }
+    set_balance(env, owner, id, current - amount);
+    Ok(())
+}
+
+fn load_token(env: &Env, id: u64) -> Result<TokenInfo, MultiTokenError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(id))
+        .ok_or(MultiTokenError::TokenNotFound)
+}
+
+/// Load a token id that moves through mint, transfer and burn
+fn load_fungible(env: &Env, id: u64) -> Result<TokenInfo, MultiTokenError> {
+    let info = load_token(env, id)?;
+    if badges::is_badge(env, id) {
+        return Err(MultiTokenError::Soulbound);
+    }
+    Ok(info)
+}
+
+fn load_badge(env: &Env, id: u64) -> Result<Badge, MultiTokenError> {
+    match badges::get(env, id) {
+        Some(badge) => Ok(badge),
+        None => {
+            load_token(env, id)?;
+            Err(MultiTokenError::NotBadge)
+        }
+    }
+}
+
+fn new_token(env: &Env, admin: Address, uri: String, max_supply: i128) -> u64 {
+    let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(1);
+    env.storage().instance().set(&DataKey::NextId, &(id + 1));
+    let info = TokenInfo {
+        uri: uri.clone(),
+        max_supply,
+        supply: 0,
+    };
+    env.storage().persistent().set(&DataKey::Token(id), &info);
+    emit_uri(env, admin, id, uri);
+    id
+}
+
+/// Authorize `issuer` for badge `id`: a designated issuer or the admin
+fn require_issuer(env: &Env, issuer: &Address, id: u64) -> Result<(), MultiTokenError> {
+    issuer.require_auth();
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(MultiTokenError::NotInitialized)?;
+    if *issuer != admin && !badges::is_issuer(env, id, issuer) {
+        return Err(MultiTokenError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), MultiTokenError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(MultiTokenError::NotInitialized)?;
+    if stored != *admin {
+        return Err(MultiTokenError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn emit_uri(env: &Env, updated_by: Address, id: u64, uri: String) {
+    EventEmitter::multi_token_uri(
+        env,
+        MultiTokenUriEvent {
+            id,
+            uri,
+            updated_by,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,243 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys::AMOUNT_KEY, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::{assert_standard_event, payload, standard_events};
+
+struct Setup {
+    env: Env,
+    client: MultiTokenClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = MultiTokenClient::new(&env, &env.register_contract(None, MultiToken));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    Setup { env, client, admin }
+}
+
+fn create(s: &Setup, uri: &str, max_supply: i128) -> u64 {
+    s.client
+        .create_token(&s.admin, &String::from_str(&s.env, uri), &max_supply)
+}
+
+#[test]
+fn test_batch_mint_emits_one_event() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    let mut ids = Vec::new(&s.env);
+    let mut amounts = Vec::new(&s.env);
+    for i in 0..MAX_BATCH_SIZE {
+        ids.push_back(create(&s, "ipfs://items/sword", 0));
+        amounts.push_back(i as i128 + 1);
+    }
+
+    s.client.batch_mint(&s.admin, &player, &ids, &amounts);
+    let mints = standard_events(&s.env)
+        .into_iter()
+        .filter(|captured| captured.event.topic == topics::MT_MINTED)
+        .count();
+    assert_eq!(mints, 1);
+    let captured = assert_standard_event(&s.env, topics::MT_MINTED, |_| true);
+    let event: MultiTokenSupplyEvent = payload(&captured);
+    assert_eq!(
+        (event.account, event.ids, event.amounts),
+        (player.clone(), ids.clone(), amounts)
+    );
+    let total: i128 = (1..=MAX_BATCH_SIZE as i128).sum();
+    assert_eq!(captured.metadata::<i128>(&s.env, AMOUNT_KEY), Some(total));
+
+    let owners = Vec::from_array(&s.env, [player.clone(), player.clone()]);
+    let pair = Vec::from_array(&s.env, [ids.get(0).unwrap(), ids.get(49).unwrap()]);
+    assert_eq!(
+        s.client.balance_of_batch(&owners, &pair),
+        Vec::from_array(&s.env, [1, 50])
+    );
+
+    ids.push_back(ids.get(0).unwrap());
+    let mut amounts = Vec::new(&s.env);
+    for _ in 0..ids.len() {
+        amounts.push_back(1);
+    }
+    let result = s.client.try_batch_mint(&s.admin, &player, &ids, &amounts);
+    assert_eq!(result, Err(Ok(MultiTokenError::BatchTooLarge)));
+}
+
+#[test]
+fn test_supply_cap_and_uri() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    let ticket = create(&s, "ipfs://tickets/finals", 100);
+    let captured = assert_standard_event(&s.env, topics::MT_URI, |_| true);
+    let event: MultiTokenUriEvent = payload(&captured);
+    assert_eq!(event.id, ticket);
+
+    s.client.mint(&s.admin, &player, &ticket, &70);
+    // The batch fails as a whole once the second entry crosses the cap
+    let ids = Vec::from_array(&s.env, [ticket, ticket]);
+    let amounts = Vec::from_array(&s.env, [30, 11]);
+    let result = s.client.try_batch_mint(&s.admin, &player, &ids, &amounts);
+    assert_eq!(result, Err(Ok(MultiTokenError::SupplyCapExceeded)));
+    assert_eq!(s.client.total_supply(&ticket), 70);
+
+    // Burning frees room under the cap
+    s.client.burn(&player, &ticket, &11);
+    s.client.batch_mint(&s.admin, &player, &ids, &amounts);
+    assert_eq!(s.client.total_supply(&ticket), 100);
+    assert_eq!(s.client.balance_of(&player, &ticket), 100);
+
+    let uri = String::from_str(&s.env, "ipfs://tickets/finals-v2");
+    s.client.set_uri(&s.admin, &ticket, &uri);
+    assert_eq!(s.client.uri(&ticket), uri);
+
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_set_uri(&stranger, &ticket, &uri);
+    assert_eq!(result, Err(Ok(MultiTokenError::Unauthorized)));
+    let result = s.client.try_mint(&s.admin, &player, &(ticket + 1), &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::TokenNotFound)));
+}
+
+#[test]
+fn test_batch_transfer_and_operators() {
+    let s = setup();
+    let [alice, bob, market] = [0; 3].map(|_| Address::generate(&s.env));
+    let sword = create(&s, "ipfs://items/sword", 0);
+    let potion = create(&s, "ipfs://items/potion", 0);
+    let ids = Vec::from_array(&s.env, [sword, potion]);
+    s.client
+        .batch_mint(&s.admin, &alice, &ids, &Vec::from_array(&s.env, [1, 20]));
+
+    let amounts = Vec::from_array(&s.env, [1, 5]);
+    s.client.batch_transfer(&alice, &bob, &ids, &amounts);
+    let captured = assert_standard_event(&s.env, topics::MT_TRANSFERRED, |_| true);
+    let event: MultiTokenTransferEvent = payload(&captured);
+    assert_eq!((event.operator, event.to), (alice.clone(), bob.clone()));
+    assert_eq!((event.ids, event.amounts), (ids.clone(), amounts));
+    assert_eq!(s.client.balance_of(&alice, &potion), 15);
+    assert_eq!(s.client.balance_of(&bob, &sword), 1);
+
+    let result = s.client.try_transfer(&alice, &bob, &sword, &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::InsufficientBalance)));
+    let result = s
+        .client
+        .try_transfer_from(&market, &alice, &market, &potion, &5);
+    assert_eq!(result, Err(Ok(MultiTokenError::NotApproved)));
+
+    s.client.set_approval_for_all(&alice, &market, &true);
+    assert!(s.client.is_approved_for_all(&alice, &market));
+    s.client
+        .transfer_from(&market, &alice, &market, &potion, &5);
+    assert_eq!(s.client.balance_of(&market, &potion), 5);
+
+    s.client.set_approval_for_all(&alice, &market, &false);
+    let result = s
+        .client
+        .try_transfer_from(&market, &alice, &market, &potion, &5);
+    assert_eq!(result, Err(Ok(MultiTokenError::NotApproved)));
+
+    let result = s
+        .client
+        .try_batch_transfer(&alice, &bob, &ids, &Vec::from_array(&s.env, [1]));
+    assert_eq!(result, Err(Ok(MultiTokenError::InvalidBatch)));
+    let result = s.client.try_transfer(&alice, &bob, &potion, &0);
+    assert_eq!(result, Err(Ok(MultiTokenError::InvalidAmount)));
+}
+
+#[test]
+fn test_batch_burn_reduces_supply() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    let gem = create(&s, "ipfs://items/gem", 0);
+    let coin = create(&s, "ipfs://items/coin", 0);
+    let ids = Vec::from_array(&s.env, [gem, coin]);
+    s.client
+        .batch_mint(&s.admin, &player, &ids, &Vec::from_array(&s.env, [3, 300]));
+
+    let amounts = Vec::from_array(&s.env, [3, 100]);
+    s.client.batch_burn(&player, &ids, &amounts);
+    let captured = assert_standard_event(&s.env, topics::MT_BURNED, |_| true);
+    let event: MultiTokenSupplyEvent = payload(&captured);
+    assert_eq!((event.account, event.amounts), (player.clone(), amounts));
+    assert_eq!(s.client.total_supply(&gem), 0);
+    assert_eq!(s.client.balance_of(&player, &coin), 200);
+
+    let result = s.client.try_burn(&player, &gem, &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::InsufficientBalance)));
+}
+
+#[test]
+fn test_badges_are_soulbound_and_lapse() {
+    let s = setup();
+    let [issuer, alice, bob] = [0; 3].map(|_| Address::generate(&s.env));
+    let uri = String::from_str(&s.env, "ipfs://badges/voter");
+    let voter = s.client.create_badge(&s.admin, &uri, &10, &1_000);
+    let founder = s.client.create_badge(&s.admin, &uri, &25, &0);
+
+    let result = s.client.try_issue_badge(&issuer, &alice, &voter);
+    assert_eq!(result, Err(Ok(MultiTokenError::Unauthorized)));
+    s.client.set_badge_issuer(&s.admin, &voter, &issuer, &true);
+    assert_eq!(s.client.issue_badge(&issuer, &alice, &voter), 1_000);
+    let captured = assert_standard_event(&s.env, topics::BADGE_ISSUED, |_| true);
+    let event: BadgeEvent = payload(&captured);
+    assert_eq!((event.id, event.holder), (voter, alice.clone()));
+    s.client.issue_badge(&s.admin, &alice, &founder);
+
+    let result = s.client.try_issue_badge(&issuer, &alice, &voter);
+    assert_eq!(result, Err(Ok(MultiTokenError::BadgeHeld)));
+    let result = s.client.try_transfer(&alice, &bob, &voter, &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::Soulbound)));
+    let result = s.client.try_mint(&s.admin, &bob, &voter, &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::Soulbound)));
+    let result = s.client.try_burn(&alice, &voter, &1);
+    assert_eq!(result, Err(Ok(MultiTokenError::Soulbound)));
+    assert_eq!(s.client.reputation_of(&alice), 35);
+
+    // The voter badge lapses; reissuing renews it without new supply
+    s.env.ledger().with_mut(|li| li.timestamp += 1_000);
+    assert_eq!(s.client.balance_of(&alice, &voter), 0);
+    assert_eq!(
+        s.client.badges_of(&alice),
+        Vec::from_array(&s.env, [founder])
+    );
+    assert_eq!(s.client.reputation_of(&alice), 25);
+    assert_eq!(s.client.issue_badge(&issuer, &alice, &voter), 2_000);
+    assert_eq!(s.client.total_supply(&voter), 1);
+    assert_eq!(s.client.reputation_of(&alice), 35);
+
+    s.client.revoke_badge(&issuer, &alice, &voter);
+    let captured = assert_standard_event(&s.env, topics::BADGE_REVOKED, |_| true);
+    let event: BadgeEvent = payload(&captured);
+    assert_eq!(event.issuer, issuer);
+    assert_eq!(s.client.badge_expiry(&alice, &voter), None);
+    assert_eq!(s.client.total_supply(&voter), 0);
+    assert_eq!(s.client.reputation_of(&alice), 25);
+    let result = s.client.try_revoke_badge(&issuer, &alice, &voter);
+    assert_eq!(result, Err(Ok(MultiTokenError::BadgeNotHeld)));
+
+    let item = create(&s, "ipfs://items/sword", 0);
+    let result = s
+        .client
+        .try_set_badge_issuer(&s.admin, &item, &issuer, &true);
+    assert_eq!(result, Err(Ok(MultiTokenError::NotBadge)));
+}
+
+#[test]
+fn test_admin_transfer_can_be_cancelled() {
+    let s = setup();
+    let successor = Address::generate(&s.env);
+
+    s.client.propose_admin(&s.admin, &successor);
+    let pending = s.client.pending_admin().unwrap();
+    assert_eq!(pending.proposed_admin, successor);
+
+    let result = s.client.try_cancel_admin_transfer(&successor);
+    assert_eq!(result, Err(Ok(MultiTokenError::Unauthorized)));
+    s.client.cancel_admin_transfer(&s.admin);
+    assert!(s.client.pending_admin().is_none());
+    assert!(s.client.try_accept_admin(&successor).is_err());
+    create(&s, "ipfs://items/shield", 0);
+}
@@ -0,0 +1,318 @@
+//! Name service: short human-readable names for Stellara addresses
+//!
+//! A name is a `Symbol` registered for a whole number of years against a
+//! yearly fee paid to the treasury. Its owner points it at any address
+//! (itself by default) and can transfer it. Anyone may renew a name; after
+//! it expires the owner keeps an exclusive grace period to renew before it
+//! can be registered afresh. An account can also claim one of the names
+//! pointing at it as its primary name for reverse lookup.
+//!
+//! Other contracts resolve names at call time through
+//! `shared::names::NameServiceClient`.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, NameEvent, NameTransferEvent};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol,
+};
+
+pub const YEAR: u64 = 365 * 24 * 60 * 60;
+/// Longest a name can be paid up for, counted from now
+pub const MAX_YEARS: u32 = 10;
+/// How long after expiry only a renewal, not a new registration, is
+/// accepted
+pub const GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NameError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidFee = 4,
+    /// Zero years, or paid up beyond `MAX_YEARS`
+    InvalidDuration = 5,
+    /// Registered, or expired but still in its grace period
+    NameTaken = 6,
+    NameNotFound = 7,
+    NameExpired = 8,
+    /// The name does not point at the account claiming it
+    NotResolved = 9,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub token: Address,
+    /// Receives registration and renewal fees
+    pub treasury: Address,
+    pub fee_per_year: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameRecord {
+    pub owner: Address,
+    /// Address the name resolves to
+    pub target: Address,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Fees,
+    Name(Symbol),
+    Primary(Address),
+}
+
+#[contract]
+pub struct NameService;
+
+#[contractimpl]
+impl NameService {
+    pub fn initialize(env: Env, admin: Address, fees: FeeConfig) -> Result<(), NameError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NameError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        set_fees(&env, &fees)
+    }
+
+    /// Change the fee token, treasury or yearly fee. Applies to later
+    /// registrations and renewals only.
+    pub fn set_fees(env: Env, admin: Address, fees: FeeConfig) -> Result<(), NameError> {
+        require_admin(&env, &admin)?;
+        set_fees(&env, &fees)
+    }
+
+    /// Register `name` to `owner` for `years`, paying the fee from
+    /// `owner`. Returns the expiry.
+    pub fn register(env: Env, owner: Address, name: Symbol, years: u32) -> Result<u64, NameError> {
+        owner.require_auth();
+        let now = env.ledger().timestamp();
+        if let Some(record) = Self::record(env.clone(), name.clone()) {
+            if now <= record.expires_at + GRACE_PERIOD {
+                return Err(NameError::NameTaken);
+            }
+        }
+        if years == 0 || years > MAX_YEARS {
+            return Err(NameError::InvalidDuration);
+        }
+        let fee = charge(&env, &owner, years)?;
+        let expires_at = now + years as u64 * YEAR;
+        let record = NameRecord {
+            owner: owner.clone(),
+            target: owner.clone(),
+            expires_at,
+        };
+        set_record(&env, &name, &record);
+
+        EventEmitter::name_registered(
+            &env,
+            NameEvent {
+                name,
+                owner: owner.clone(),
+                payer: owner,
+                fee,
+                expires_at,
+                timestamp: now,
+            },
+        );
+        Ok(expires_at)
+    }
+
+    /// Extend `name` by `years`, paid by `payer`. Accepted until the end of
+    /// the grace period. Returns the new expiry.
+    pub fn renew(env: Env, payer: Address, name: Symbol, years: u32) -> Result<u64, NameError> {
+        payer.require_auth();
+        let mut record = Self::record(env.clone(), name.clone()).ok_or(NameError::NameNotFound)?;
+        let now = env.ledger().timestamp();
+        if now > record.expires_at + GRACE_PERIOD {
+            return Err(NameError::NameExpired);
+        }
+        let expires_at = record.expires_at + years as u64 * YEAR;
+        if years == 0 || expires_at > now + MAX_YEARS as u64 * YEAR {
+            return Err(NameError::InvalidDuration);
+        }
+        let fee = charge(&env, &payer, years)?;
+        record.expires_at = expires_at;
+        set_record(&env, &name, &record);
+
+        EventEmitter::name_renewed(
+            &env,
+            NameEvent {
+                name,
+                owner: record.owner,
+                payer,
+                fee,
+                expires_at,
+                timestamp: now,
+            },
+        );
+        Ok(expires_at)
+    }
+
+    /// Hand `name` to `to`, pointing it at them
+    pub fn transfer(env: Env, owner: Address, name: Symbol, to: Address) -> Result<(), NameError> {
+        let mut record = load_owned(&env, &owner, &name)?;
+        record.owner = to.clone();
+        record.target = to.clone();
+        set_record(&env, &name, &record);
+
+        EventEmitter::name_transferred(
+            &env,
+            NameTransferEvent {
+                name,
+                from: owner,
+                to,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Point `name` at `target`
+    pub fn set_target(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+        target: Address,
+    ) -> Result<(), NameError> {
+        let mut record = load_owned(&env, &owner, &name)?;
+        record.target = target;
+        set_record(&env, &name, &record);
+        Ok(())
+    }
+
+    /// Claim `name`, which must point at `account`, as its primary name
+    pub fn set_primary(env: Env, account: Address, name: Symbol) -> Result<(), NameError> {
+        account.require_auth();
+        if Self::resolve(env.clone(), name.clone()) != Some(account.clone()) {
+            return Err(NameError::NotResolved);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Primary(account), &name);
+        Ok(())
+    }
+
+    // ---- views ----
+
+    /// Address `name` points at, or `None` if it is unregistered or expired
+    pub fn resolve(env: Env, name: Symbol) -> Option<Address> {
+        let record = Self::record(env.clone(), name)?;
+        if env.ledger().timestamp() > record.expires_at {
+            return None;
+        }
+        Some(record.target)
+    }
+
+    /// Primary name of `account`. A name that has since expired or been
+    /// pointed elsewhere no longer counts.
+    pub fn name_of(env: Env, account: Address) -> Option<Symbol> {
+        let name: Symbol = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Primary(account.clone()))?;
+        if Self::resolve(env, name.clone()) != Some(account) {
+            return None;
+        }
+        Some(name)
+    }
+
+    /// Stored record of `name`, expired or not
+    pub fn record(env: Env, name: Symbol) -> Option<NameRecord> {
+        env.storage().persistent().get(&DataKey::Name(name))
+    }
+
+    pub fn fees(env: Env) -> Result<FeeConfig, NameError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Fees)
+            .ok_or(NameError::NotInitialized)
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), NameError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), NameError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Take the fee for `years` from `payer`. Returns the fee.
+fn charge(env: &Env, payer: &Address, years: u32) -> Result<i128, NameError> {
+    let fees = NameService::fees(env.clone())?;
+    let fee = fees.fee_per_year * years as i128;
+    if fee > 0 {
+        token::Client::new(env, &fees.token).transfer(payer, &fees.treasury, &fee);
+    }
+    Ok(fee)
+}
+
+/// Record of a live `name` owned by `owner`, who must authorize
+fn load_owned(env: &Env, owner: &Address, name: &Symbol) -> Result<NameRecord, NameError> {
+    owner.require_auth();
+    let record = NameService::record(env.clone(), name.clone()).ok_or(NameError::NameNotFound)?;
+    if record.owner != *owner {
+        return Err(NameError::Unauthorized);
+    }
+    if env.ledger().timestamp() > record.expires_at {
+        return Err(NameError::NameExpired);
+    }
+    Ok(record)
+}
+
+fn set_record(env: &Env, name: &Symbol, record: &NameRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Name(name.clone()), record);
+}
+
+fn set_fees(env: &Env, fees: &FeeConfig) -> Result<(), NameError> {
+    if fees.fee_per_year < 0 {
+        return Err(NameError::InvalidFee);
+    }
+    env.storage().instance().set(&DataKey::Fees, fees);
+    Ok(())
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), NameError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(NameError::NotInitialized)?;
+    if stored != *admin {
+        return Err(NameError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use shared::names::NameServiceClient as SharedNameServiceClient;
+use soroban_sdk::symbol_short;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const FEE: i128 = 50;
+
+struct Setup {
+    env: Env,
+    client: NameServiceClient<'static>,
+    admin: Address,
+    token: TestToken<'static>,
+    treasury: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let client = NameServiceClient::new(&env, &env.register_contract(None, NameService));
+    let admin = Address::generate(&env);
+    let token = stellar_asset(&env);
+    let treasury = Address::generate(&env);
+    let fees = FeeConfig {
+        token: token.address.clone(),
+        treasury: treasury.clone(),
+        fee_per_year: FEE,
+    };
+    client.initialize(&admin, &fees);
+    Setup {
+        env,
+        client,
+        admin,
+        token,
+        treasury,
+    }
+}
+
+impl Setup {
+    fn account(&self) -> Address {
+        let account = Address::generate(&self.env);
+        self.token.mint(&account, 1_000);
+        account
+    }
+
+    fn warp(&self, timestamp: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp = timestamp);
+    }
+}
+
+#[test]
+fn test_register_resolve_and_reverse() {
+    let s = setup();
+    let alice = s.account();
+    let name = symbol_short!("alice");
+
+    let expires_at = s.client.register(&alice, &name, &2);
+    assert_eq!(expires_at, 1_000 + 2 * YEAR);
+    assert_eq!(s.token.balance(&s.treasury), 2 * FEE);
+    let captured = assert_standard_event(&s.env, topics::NAME_REGISTERED, |_| true);
+    let event: NameEvent = payload(&captured);
+    assert_eq!((event.name, event.fee), (name.clone(), 2 * FEE));
+
+    // Consumers resolve through the shared interface
+    let reader = SharedNameServiceClient::new(&s.env, &s.client.address);
+    assert_eq!(reader.resolve(&name), Some(alice.clone()));
+    assert_eq!(reader.name_of(&alice), None);
+    s.client.set_primary(&alice, &name);
+    assert_eq!(reader.name_of(&alice), Some(name.clone()));
+
+    // Pointing the name elsewhere drops the reverse record
+    let vault = Address::generate(&s.env);
+    s.client.set_target(&alice, &name, &vault);
+    assert_eq!(reader.resolve(&name), Some(vault.clone()));
+    assert_eq!(reader.name_of(&alice), None);
+    let result = s.client.try_set_primary(&alice, &name);
+    assert_eq!(result, Err(Ok(NameError::NotResolved)));
+
+    let bob = s.account();
+    let result = s.client.try_register(&bob, &name, &1);
+    assert_eq!(result, Err(Ok(NameError::NameTaken)));
+}
+
+#[test]
+fn test_expiry_grace_and_renewal() {
+    let s = setup();
+    let alice = s.account();
+    let bob = s.account();
+    let name = symbol_short!("stellara");
+    let expires_at = s.client.register(&alice, &name, &1);
+
+    // Expired names stop resolving but stay reserved through the grace
+    s.warp(expires_at + 1);
+    assert_eq!(s.client.resolve(&name), None);
+    let result = s.client.try_register(&bob, &name, &1);
+    assert_eq!(result, Err(Ok(NameError::NameTaken)));
+    let result = s.client.try_transfer(&alice, &name, &bob);
+    assert_eq!(result, Err(Ok(NameError::NameExpired)));
+
+    // Anyone can pay a renewal; it extends from the old expiry
+    let renewed = s.client.renew(&bob, &name, &1);
+    assert_eq!(renewed, expires_at + YEAR);
+    assert_eq!(s.client.resolve(&name), Some(alice.clone()));
+    let captured = assert_standard_event(&s.env, topics::NAME_RENEWED, |_| true);
+    let event: NameEvent = payload(&captured);
+    assert_eq!((event.owner, event.payer), (alice.clone(), bob.clone()));
+    let result = s.client.try_renew(&bob, &name, &MAX_YEARS);
+    assert_eq!(result, Err(Ok(NameError::InvalidDuration)));
+
+    // Past the grace period the name is up for grabs
+    s.warp(renewed + GRACE_PERIOD + 1);
+    let result = s.client.try_renew(&alice, &name, &1);
+    assert_eq!(result, Err(Ok(NameError::NameExpired)));
+    s.client.register(&bob, &name, &1);
+    assert_eq!(s.client.resolve(&name), Some(bob));
+}
+
+#[test]
+fn test_transfer_and_admin_fees() {
+    let s = setup();
+    let alice = s.account();
+    let bob = s.account();
+    let name = symbol_short!("shop");
+    s.client.register(&alice, &name, &1);
+
+    let result = s.client.try_transfer(&bob, &name, &bob);
+    assert_eq!(result, Err(Ok(NameError::Unauthorized)));
+    s.client.transfer(&alice, &name, &bob);
+    assert_eq!(s.client.resolve(&name), Some(bob.clone()));
+    assert_eq!(s.client.record(&name).unwrap().owner, bob);
+    assert_standard_event(&s.env, topics::NAME_TRANSFERRED, |_| true);
+
+    let mut fees = s.client.fees();
+    fees.fee_per_year = -1;
+    let result = s.client.try_set_fees(&s.admin, &fees);
+    assert_eq!(result, Err(Ok(NameError::InvalidFee)));
+    fees.fee_per_year = 0;
+    let result = s.client.try_set_fees(&alice, &fees);
+    assert_eq!(result, Err(Ok(NameError::Unauthorized)));
+    s.client.set_fees(&s.admin, &fees);
+    s.client.register(&alice, &symbol_short!("free"), &1);
+    assert_eq!(s.token.balance(&s.treasury), FEE);
+    let result = s.client.try_register(&alice, &symbol_short!("long"), &0);
+    assert_eq!(result, Err(Ok(NameError::InvalidDuration)));
+}
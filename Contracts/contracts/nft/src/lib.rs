@@ -0,0 +1,316 @@
+//! Non-fungible token collection
+//!
+//! One collection per contract instance. The admin mints tokens with a
+//! per-token metadata URI; holders transfer, approve and burn through the
+//! shared `NonFungible` ownership module. Every state change emits a
+//! standardized event, and `tokens_of_owner` pages through a holder's
+//! tokens so wallets never need to load an unbounded list. The admin can
+//! attach a creator royalty to each token, which marketplaces read through
+//! `royalty_info` at settlement.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, NftApprovalEvent, NftEvent, NftTransferEvent};
+use shared::nft::{NftError, NonFungible};
+use shared::pagination::Pagination;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String, Vec};
+
+/// Basis-point denominator for royalties
+pub const BPS_DENOMINATOR: i128 = 10_000;
+/// Highest royalty a token can carry (25%)
+pub const MAX_ROYALTY_BPS: u32 = 2_500;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NftContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    TokenNotFound = 4,
+    NotOwner = 5,
+    NotApproved = 6,
+    InvalidRoyalty = 7,
+}
+
+impl From<NftError> for NftContractError {
+    fn from(err: NftError) -> Self {
+        match err {
+            NftError::NotFound => NftContractError::TokenNotFound,
+            NftError::NotOwner => NftContractError::NotOwner,
+            NftError::NotApproved => NftContractError::NotApproved,
+        }
+    }
+}
+
+/// Collection-level metadata
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionMetadata {
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Creator royalty attached to a token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Royalty {
+    pub receiver: Address,
+    pub bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Metadata,
+    Supply,
+    TokenUri(u64),
+    Royalty(u64),
+}
+
+#[contract]
+pub struct NftContract;
+
+#[contractimpl]
+impl NftContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+    ) -> Result<(), NftContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NftContractError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Metadata, &CollectionMetadata { name, symbol });
+        env.storage().instance().set(&DataKey::Supply, &0u64);
+        Ok(())
+    }
+
+    /// Mint a new token to `to` with its metadata URI. Returns the token id.
+    pub fn mint(
+        env: Env,
+        admin: Address,
+        to: Address,
+        uri: String,
+    ) -> Result<u64, NftContractError> {
+        require_admin(&env, &admin)?;
+
+        let id = NonFungible::mint(&env, &to);
+        env.storage().persistent().set(&DataKey::TokenUri(id), &uri);
+        env.storage()
+            .instance()
+            .set(&DataKey::Supply, &(Self::total_supply(env.clone()) + 1));
+
+        EventEmitter::nft_minted(
+            &env,
+            NftEvent {
+                id,
+                owner: to,
+                uri,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(id)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, id: u64) -> Result<(), NftContractError> {
+        NonFungible::transfer(&env, &from, &to, id)?;
+        emit_transfer(&env, from, to, id);
+        Ok(())
+    }
+
+    /// Allow `spender` to move token `id` once. The approval is cleared on
+    /// the next transfer.
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        id: u64,
+    ) -> Result<(), NftContractError> {
+        NonFungible::approve(&env, &owner, &spender, id)?;
+        EventEmitter::nft_approved(
+            &env,
+            NftApprovalEvent {
+                id,
+                owner,
+                spender,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<(), NftContractError> {
+        NonFungible::transfer_from(&env, &spender, &from, &to, id)?;
+        emit_transfer(&env, from, to, id);
+        Ok(())
+    }
+
+    pub fn burn(env: Env, owner: Address, id: u64) -> Result<(), NftContractError> {
+        let uri = Self::token_uri(env.clone(), id)?;
+        NonFungible::burn(&env, &owner, id)?;
+        env.storage().persistent().remove(&DataKey::TokenUri(id));
+        env.storage().persistent().remove(&DataKey::Royalty(id));
+        env.storage()
+            .instance()
+            .set(&DataKey::Supply, &(Self::total_supply(env.clone()) - 1));
+
+        EventEmitter::nft_burned(
+            &env,
+            NftEvent {
+                id,
+                owner,
+                uri,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Set the creator royalty paid on secondary sales of token `id`
+    pub fn set_royalty(
+        env: Env,
+        admin: Address,
+        id: u64,
+        receiver: Address,
+        bps: u32,
+    ) -> Result<(), NftContractError> {
+        require_admin(&env, &admin)?;
+        if bps > MAX_ROYALTY_BPS {
+            return Err(NftContractError::InvalidRoyalty);
+        }
+        Self::owner_of(env.clone(), id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Royalty(id), &Royalty { receiver, bps });
+        Ok(())
+    }
+
+    pub fn royalty(env: Env, id: u64) -> Option<Royalty> {
+        env.storage().persistent().get(&DataKey::Royalty(id))
+    }
+
+    /// Royalty receiver and amount owed on a sale of token `id` at
+    /// `sale_price`, or `None` when the token carries no royalty
+    pub fn royalty_info(env: Env, id: u64, sale_price: i128) -> Option<(Address, i128)> {
+        let royalty = Self::royalty(env, id)?;
+        let amount = sale_price * royalty.bps as i128 / BPS_DENOMINATOR;
+        Some((royalty.receiver, amount))
+    }
+
+    // ---- views ----
+
+    pub fn name(env: Env) -> Result<String, NftContractError> {
+        Ok(metadata(&env)?.name)
+    }
+
+    pub fn symbol(env: Env) -> Result<String, NftContractError> {
+        Ok(metadata(&env)?.symbol)
+    }
+
+    pub fn token_uri(env: Env, id: u64) -> Result<String, NftContractError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenUri(id))
+            .ok_or(NftContractError::TokenNotFound)
+    }
+
+    pub fn owner_of(env: Env, id: u64) -> Result<Address, NftContractError> {
+        NonFungible::owner_of(&env, id).ok_or(NftContractError::TokenNotFound)
+    }
+
+    pub fn get_approved(env: Env, id: u64) -> Option<Address> {
+        NonFungible::approved(&env, id)
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        NonFungible::balance_of(&env, &owner)
+    }
+
+    /// Tokens currently in circulation (minted minus burned)
+    pub fn total_supply(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Supply).unwrap_or(0)
+    }
+
+    /// One page of the tokens held by `owner`, and the cursor of the next
+    pub fn tokens_of_owner(env: Env, owner: Address, page: Pagination) -> (Vec<u64>, Option<u32>) {
+        NonFungible::tokens_page(&env, &owner, &page)
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), NftContractError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), NftContractError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), NftContractError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(NftContractError::NotInitialized)?;
+    if stored != *admin {
+        return Err(NftContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn metadata(env: &Env) -> Result<CollectionMetadata, NftContractError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Metadata)
+        .ok_or(NftContractError::NotInitialized)
+}
+
+fn emit_transfer(env: &Env, from: Address, to: Address, id: u64) {
+    EventEmitter::nft_transferred(
+        env,
+        NftTransferEvent {
+            id,
+            from,
+            to,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod test;
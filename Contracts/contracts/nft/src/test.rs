@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+use test_utils::{assert_standard_event, payload};
+
+fn setup() -> (Env, NftContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, NftContract);
+    let client = NftContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Stellara Collectibles"),
+        &String::from_str(&env, "SCOL"),
+    );
+    (env, client, admin)
+}
+
+#[test]
+fn test_mint_sets_owner_uri_and_emits_event() {
+    let (env, client, admin) = setup();
+    let alice = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://token/1");
+
+    let stranger = Address::generate(&env);
+    let result = client.try_mint(&stranger, &alice, &uri);
+    assert_eq!(result, Err(Ok(NftContractError::Unauthorized)));
+
+    let id = client.mint(&admin, &alice, &uri);
+    assert_eq!(id, 1);
+    assert_eq!(client.owner_of(&id), alice);
+    assert_eq!(client.token_uri(&id), uri);
+    assert_eq!(client.balance_of(&alice), 1);
+    assert_eq!(client.total_supply(), 1);
+    assert_eq!(client.symbol(), String::from_str(&env, "SCOL"));
+
+    let captured = assert_standard_event(&env, topics::NFT_MINTED, |_| true);
+    let event: NftEvent = payload(&captured);
+    assert_eq!(event.id, id);
+    assert_eq!(event.owner, alice);
+    assert_eq!(event.uri, uri);
+}
+
+#[test]
+fn test_transfer_and_approval_flow() {
+    let (env, client, admin) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let market = Address::generate(&env);
+    let id = client.mint(&admin, &alice, &String::from_str(&env, "ipfs://1"));
+
+    let result = client.try_transfer(&bob, &alice, &id);
+    assert_eq!(result, Err(Ok(NftContractError::NotOwner)));
+
+    let result = client.try_transfer_from(&market, &alice, &bob, &id);
+    assert_eq!(result, Err(Ok(NftContractError::NotApproved)));
+
+    client.approve(&alice, &market, &id);
+    assert_eq!(client.get_approved(&id), Some(market.clone()));
+    client.transfer_from(&market, &alice, &bob, &id);
+    assert_eq!(client.owner_of(&id), bob);
+    assert_eq!(client.get_approved(&id), None);
+
+    let captured = assert_standard_event(&env, topics::NFT_TRANSFERRED, |_| true);
+    let event: NftTransferEvent = payload(&captured);
+    assert_eq!((event.from, event.to), (alice.clone(), bob.clone()));
+
+    client.transfer(&bob, &alice, &id);
+    assert_eq!(client.balance_of(&alice), 1);
+    assert_eq!(client.balance_of(&bob), 0);
+}
+
+#[test]
+fn test_burn_removes_token() {
+    let (env, client, admin) = setup();
+    let alice = Address::generate(&env);
+    let id = client.mint(&admin, &alice, &String::from_str(&env, "ipfs://1"));
+
+    client.burn(&alice, &id);
+    assert_eq!(client.total_supply(), 0);
+    assert_eq!(client.balance_of(&alice), 0);
+    assert_eq!(
+        client.try_owner_of(&id),
+        Err(Ok(NftContractError::TokenNotFound))
+    );
+    assert_eq!(
+        client.try_token_uri(&id),
+        Err(Ok(NftContractError::TokenNotFound))
+    );
+    assert_standard_event(&env, topics::NFT_BURNED, |_| true);
+}
+
+#[test]
+fn test_tokens_of_owner_paginates() {
+    let (env, client, admin) = setup();
+    let alice = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://x");
+    for _ in 0..5 {
+        client.mint(&admin, &alice, &uri);
+    }
+
+    let page = |cursor, limit| client.tokens_of_owner(&alice, &Pagination { cursor, limit });
+    assert_eq!(page(0, 2), (vec![&env, 1, 2], Some(2)));
+    assert_eq!(page(2, 2), (vec![&env, 3, 4], Some(4)));
+    assert_eq!(page(4, 2), (vec![&env, 5], None));
+    assert_eq!(page(9, 2), (Vec::new(&env), None));
+    assert_eq!(page(0, u32::MAX), (vec![&env, 1, 2, 3, 4, 5], None));
+
+    // Sending a token away moves the holder's last token into its slot
+    let bob = Address::generate(&env);
+    client.transfer(&alice, &bob, &2);
+    assert_eq!(page(0, 10), (vec![&env, 1, 5, 3, 4], None));
+    client.burn(&alice, &4);
+    assert_eq!(page(0, 10), (vec![&env, 1, 5, 3], None));
+    assert_eq!(client.balance_of(&alice), 3);
+    assert_eq!(
+        client.tokens_of_owner(&bob, &Pagination::first(10)).0,
+        vec![&env, 2]
+    );
+}
+
+#[test]
+fn test_royalty_info() {
+    let (env, client, admin) = setup();
+    let alice = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let id = client.mint(&admin, &alice, &String::from_str(&env, "ipfs://1"));
+    assert_eq!(client.royalty_info(&id, &1_000), None);
+
+    let result = client.try_set_royalty(&admin, &id, &creator, &(MAX_ROYALTY_BPS + 1));
+    assert_eq!(result, Err(Ok(NftContractError::InvalidRoyalty)));
+    let result = client.try_set_royalty(&admin, &99, &creator, &500);
+    assert_eq!(result, Err(Ok(NftContractError::TokenNotFound)));
+
+    client.set_royalty(&admin, &id, &creator, &500);
+    assert_eq!(client.royalty_info(&id, &1_000), Some((creator, 50)));
+}
@@ -0,0 +1,261 @@
+//! OTC deals for large token-for-token trades
+//!
+//! A maker opens a deal naming the taker, the amount of each token changing
+//! hands and an expiry; the two amounts fix the price. Each party then
+//! funds their leg by escrowing it here, in either order. Funding the
+//! second leg settles the deal in the same call: the maker's tokens go to
+//! the taker and the taker's to the maker, so neither side is ever exposed
+//! to the other failing to deliver.
+//!
+//! A deal not fully funded by `expires_at` can be expired by anyone, which
+//! refunds whichever leg was escrowed.
+
+#![no_std]
+
+use shared::deadline::{is_expired, require_future, require_not_expired};
+use shared::events::{EventEmitter, OtcDealClosedEvent, OtcDealCreatedEvent, OtcDealFundedEvent};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OtcError {
+    InvalidDeal = 1,
+    DealNotFound = 2,
+    Unauthorized = 3,
+    AlreadyFunded = 4,
+    Expired = 5,
+    NotExpired = 6,
+    DealClosed = 7,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DealStatus {
+    /// Waiting for one or both legs
+    Open,
+    /// Both legs were funded and exchanged
+    Settled,
+    /// Expired before both legs were funded; escrow was refunded
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deal {
+    pub maker: Address,
+    pub taker: Address,
+    /// Token the maker gives
+    pub maker_token: Address,
+    pub maker_amount: i128,
+    /// Token the taker gives
+    pub taker_token: Address,
+    pub taker_amount: i128,
+    /// Last second either leg can be funded
+    pub expires_at: u64,
+    pub maker_funded: bool,
+    pub taker_funded: bool,
+    pub status: DealStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextDealId,
+    Deal(u64),
+}
+
+#[contract]
+pub struct OtcDesk;
+
+#[contractimpl]
+impl OtcDesk {
+    /// Open a deal in which `maker` gives `maker_amount` of `maker_token`
+    /// for `taker_amount` of `taker_token` from `taker`. Nothing is escrowed
+    /// yet. Returns the deal id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_deal(
+        env: Env,
+        maker: Address,
+        taker: Address,
+        maker_token: Address,
+        maker_amount: i128,
+        taker_token: Address,
+        taker_amount: i128,
+        expires_at: u64,
+    ) -> Result<u64, OtcError> {
+        maker.require_auth();
+        if maker == taker || maker_token == taker_token || maker_amount <= 0 || taker_amount <= 0 {
+            return Err(OtcError::InvalidDeal);
+        }
+        require_future(&env, expires_at).map_err(|_| OtcError::Expired)?;
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextDealId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextDealId, &(id + 1));
+        let deal = Deal {
+            maker: maker.clone(),
+            taker: taker.clone(),
+            maker_token: maker_token.clone(),
+            maker_amount,
+            taker_token: taker_token.clone(),
+            taker_amount,
+            expires_at,
+            maker_funded: false,
+            taker_funded: false,
+            status: DealStatus::Open,
+        };
+        env.storage().persistent().set(&DataKey::Deal(id), &deal);
+
+        EventEmitter::otc_deal_created(
+            &env,
+            OtcDealCreatedEvent {
+                deal_id: id,
+                maker,
+                taker,
+                maker_token,
+                maker_amount,
+                taker_token,
+                taker_amount,
+                expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(id)
+    }
+
+    /// Escrow `party`'s leg of the deal. If the other leg is already in,
+    /// the deal settles immediately. Returns the deal's new status.
+    pub fn fund(env: Env, party: Address, deal_id: u64) -> Result<DealStatus, OtcError> {
+        party.require_auth();
+        let mut deal = load_open_deal(&env, deal_id)?;
+        require_not_expired(&env, deal.expires_at).map_err(|_| OtcError::Expired)?;
+
+        let (token, amount) = if party == deal.maker {
+            if deal.maker_funded {
+                return Err(OtcError::AlreadyFunded);
+            }
+            deal.maker_funded = true;
+            (deal.maker_token.clone(), deal.maker_amount)
+        } else if party == deal.taker {
+            if deal.taker_funded {
+                return Err(OtcError::AlreadyFunded);
+            }
+            deal.taker_funded = true;
+            (deal.taker_token.clone(), deal.taker_amount)
+        } else {
+            return Err(OtcError::Unauthorized);
+        };
+        token::Client::new(&env, &token).transfer(&party, &env.current_contract_address(), &amount);
+        let timestamp = env.ledger().timestamp();
+        EventEmitter::otc_deal_funded(
+            &env,
+            OtcDealFundedEvent {
+                deal_id,
+                party,
+                token,
+                amount,
+                timestamp,
+            },
+        );
+
+        if deal.maker_funded && deal.taker_funded {
+            deal.status = DealStatus::Settled;
+            let this = env.current_contract_address();
+            token::Client::new(&env, &deal.maker_token).transfer(
+                &this,
+                &deal.taker,
+                &deal.maker_amount,
+            );
+            token::Client::new(&env, &deal.taker_token).transfer(
+                &this,
+                &deal.maker,
+                &deal.taker_amount,
+            );
+            EventEmitter::otc_deal_settled(
+                &env,
+                OtcDealClosedEvent {
+                    deal_id,
+                    maker: deal.maker.clone(),
+                    taker: deal.taker.clone(),
+                    maker_amount: deal.maker_amount,
+                    taker_amount: deal.taker_amount,
+                    timestamp,
+                },
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Deal(deal_id), &deal);
+        Ok(deal.status)
+    }
+
+    /// Close a deal that passed its expiry without settling, refunding
+    /// any escrowed leg. Permissionless.
+    pub fn expire(env: Env, caller: Address, deal_id: u64) -> Result<(), OtcError> {
+        caller.require_auth();
+        let mut deal = load_open_deal(&env, deal_id)?;
+        if !is_expired(&env, deal.expires_at) {
+            return Err(OtcError::NotExpired);
+        }
+        deal.status = DealStatus::Expired;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Deal(deal_id), &deal);
+
+        let this = env.current_contract_address();
+        let maker_refund = if deal.maker_funded {
+            deal.maker_amount
+        } else {
+            0
+        };
+        let taker_refund = if deal.taker_funded {
+            deal.taker_amount
+        } else {
+            0
+        };
+        if maker_refund > 0 {
+            token::Client::new(&env, &deal.maker_token).transfer(&this, &deal.maker, &maker_refund);
+        }
+        if taker_refund > 0 {
+            token::Client::new(&env, &deal.taker_token).transfer(&this, &deal.taker, &taker_refund);
+        }
+
+        EventEmitter::otc_deal_expired(
+            &env,
+            OtcDealClosedEvent {
+                deal_id,
+                maker: deal.maker,
+                taker: deal.taker,
+                maker_amount: maker_refund,
+                taker_amount: taker_refund,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_deal(env: Env, deal_id: u64) -> Option<Deal> {
+        env.storage().persistent().get(&DataKey::Deal(deal_id))
+    }
+}
+
+fn load_open_deal(env: &Env, deal_id: u64) -> Result<Deal, OtcError> {
+    let deal: Deal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Deal(deal_id))
+        .ok_or(OtcError::DealNotFound)?;
+    if deal.status != DealStatus::Open {
+        return Err(OtcError::DealClosed);
+    }
+    Ok(deal)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const EXPIRY: u64 = 5_000;
+
+struct Setup {
+    env: Env,
+    client: OtcDeskClient<'static>,
+    maker: Address,
+    taker: Address,
+    base: TestToken<'static>,
+    quote: TestToken<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let base = stellar_asset(&env);
+    let quote = stellar_asset(&env);
+    let maker = Address::generate(&env);
+    let taker = Address::generate(&env);
+    base.mint(&maker, 1_000_000);
+    quote.mint(&taker, 5_000_000);
+    let client = OtcDeskClient::new(&env, &env.register_contract(None, OtcDesk));
+
+    Setup {
+        env,
+        client,
+        maker,
+        taker,
+        base,
+        quote,
+    }
+}
+
+fn create(s: &Setup) -> u64 {
+    s.client.create_deal(
+        &s.maker,
+        &s.taker,
+        &s.base.address,
+        &1_000_000,
+        &s.quote.address,
+        &5_000_000,
+        &EXPIRY,
+    )
+}
+
+#[test]
+fn test_deal_settles_when_both_legs_are_funded() {
+    let s = setup();
+    let id = create(&s);
+    let captured = assert_standard_event(&s.env, topics::OTC_CREATED, |_| true);
+    let event: OtcDealCreatedEvent = payload(&captured);
+    assert_eq!((event.deal_id, event.expires_at), (id, EXPIRY));
+
+    // Taker funds first; nothing moves to the maker yet
+    assert_eq!(s.client.fund(&s.taker, &id), DealStatus::Open);
+    assert_eq!(s.quote.balance(&s.client.address), 5_000_000);
+    assert_eq!(s.quote.balance(&s.maker), 0);
+    let captured = assert_standard_event(&s.env, topics::OTC_FUNDED, |_| true);
+    let event: OtcDealFundedEvent = payload(&captured);
+    assert_eq!(
+        (event.token, event.amount),
+        (s.quote.address.clone(), 5_000_000)
+    );
+
+    let result = s.client.try_fund(&s.taker, &id);
+    assert_eq!(result, Err(Ok(OtcError::AlreadyFunded)));
+
+    assert_eq!(s.client.fund(&s.maker, &id), DealStatus::Settled);
+    assert_eq!(s.base.balance(&s.taker), 1_000_000);
+    assert_eq!(s.quote.balance(&s.maker), 5_000_000);
+    assert_eq!(s.base.balance(&s.client.address), 0);
+    assert_eq!(s.quote.balance(&s.client.address), 0);
+    let captured = assert_standard_event(&s.env, topics::OTC_SETTLED, |_| true);
+    let event: OtcDealClosedEvent = payload(&captured);
+    assert_eq!(
+        (event.maker_amount, event.taker_amount),
+        (1_000_000, 5_000_000)
+    );
+
+    let deal = s.client.get_deal(&id).unwrap();
+    assert_eq!(deal.status, DealStatus::Settled);
+    let result = s.client.try_expire(&s.taker, &id);
+    assert_eq!(result, Err(Ok(OtcError::DealClosed)));
+}
+
+#[test]
+fn test_expired_deal_refunds_funded_leg() {
+    let s = setup();
+    let id = create(&s);
+    s.client.fund(&s.maker, &id);
+    assert_eq!(s.base.balance(&s.maker), 0);
+
+    let result = s.client.try_expire(&s.taker, &id);
+    assert_eq!(result, Err(Ok(OtcError::NotExpired)));
+
+    s.env.ledger().with_mut(|li| li.timestamp = EXPIRY + 1);
+    let result = s.client.try_fund(&s.taker, &id);
+    assert_eq!(result, Err(Ok(OtcError::Expired)));
+
+    let keeper = Address::generate(&s.env);
+    s.client.expire(&keeper, &id);
+    assert_eq!(s.base.balance(&s.maker), 1_000_000);
+    assert_eq!(s.quote.balance(&s.taker), 5_000_000);
+    let captured = assert_standard_event(&s.env, topics::OTC_EXPIRED, |_| true);
+    let event: OtcDealClosedEvent = payload(&captured);
+    assert_eq!((event.maker_amount, event.taker_amount), (1_000_000, 0));
+    assert_eq!(s.client.get_deal(&id).unwrap().status, DealStatus::Expired);
+
+    let result = s.client.try_expire(&keeper, &id);
+    assert_eq!(result, Err(Ok(OtcError::DealClosed)));
+}
+
+#[test]
+fn test_only_parties_can_fund() {
+    let s = setup();
+    let id = create(&s);
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_fund(&stranger, &id);
+    assert_eq!(result, Err(Ok(OtcError::Unauthorized)));
+    let result = s.client.try_fund(&s.maker, &(id + 1));
+    assert_eq!(result, Err(Ok(OtcError::DealNotFound)));
+}
+
+#[test]
+fn test_invalid_deals_are_rejected() {
+    let s = setup();
+    let result = s.client.try_create_deal(
+        &s.maker,
+        &s.maker,
+        &s.base.address,
+        &1,
+        &s.quote.address,
+        &1,
+        &EXPIRY,
+    );
+    assert_eq!(result, Err(Ok(OtcError::InvalidDeal)));
+    let result = s.client.try_create_deal(
+        &s.maker,
+        &s.taker,
+        &s.base.address,
+        &0,
+        &s.quote.address,
+        &1,
+        &EXPIRY,
+    );
+    assert_eq!(result, Err(Ok(OtcError::InvalidDeal)));
+    let result = s.client.try_create_deal(
+        &s.maker,
+        &s.taker,
+        &s.base.address,
+        &1,
+        &s.quote.address,
+        &1,
+        &1_000,
+    );
+    assert_eq!(result, Err(Ok(OtcError::Expired)));
+}
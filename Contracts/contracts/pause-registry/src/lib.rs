@@ -0,0 +1,146 @@
+//! Platform-wide pause switches
+//!
+//! Governance pauses and resumes named operations (`swap`, `borrow`,
+//! `mint`, ...) here instead of in each contract. Contracts ask whether an
+//! operation is paused through `shared::pause::require_not_paused`, which
+//! caches the answer. Pausing `all` pauses every operation without
+//! touching the individual flags, so resuming it restores the previous
+//! state.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, PauseChangedEvent};
+use shared::pause::operations;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PauseError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    Unchanged = 4,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Governance,
+    Paused,
+}
+
+#[contract]
+pub struct PauseRegistry;
+
+#[contractimpl]
+impl PauseRegistry {
+    pub fn initialize(env: Env, governance: Address) -> Result<(), PauseError> {
+        if env.storage().instance().has(&DataKey::Governance) {
+            return Err(PauseError::AlreadyInitialized);
+        }
+        governance.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+        Ok(())
+    }
+
+    /// Propose handing pause control to `new_governance`, who must accept
+    /// before the change takes effect
+    pub fn propose_governance(
+        env: Env,
+        governance: Address,
+        new_governance: Address,
+    ) -> Result<(), PauseError> {
+        require_governance(&env, &governance)?;
+        PendingAdmin::propose(&env, &governance, &new_governance);
+        Ok(())
+    }
+
+    /// Take over pause control by accepting a pending proposal
+    pub fn accept_governance(env: Env, new_governance: Address) -> Result<(), AdminTransferError> {
+        let governance = PendingAdmin::accept(&env, &new_governance)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+        Ok(())
+    }
+
+    pub fn cancel_governance_transfer(env: Env, governance: Address) -> Result<(), PauseError> {
+        require_governance(&env, &governance)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_governance(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
+    pub fn governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Governance)
+    }
+
+    /// Pause or resume `operation` platform-wide
+    pub fn set_paused(
+        env: Env,
+        governance: Address,
+        operation: Symbol,
+        paused: bool,
+    ) -> Result<(), PauseError> {
+        require_governance(&env, &governance)?;
+
+        let mut list = Self::paused_operations(env.clone());
+        let index = list.first_index_of(&operation);
+        match (index, paused) {
+            (None, true) => list.push_back(operation.clone()),
+            (Some(i), false) => {
+                list.remove(i);
+            }
+            _ => return Err(PauseError::Unchanged),
+        }
+        env.storage().instance().set(&DataKey::Paused, &list);
+
+        EventEmitter::pause_changed(
+            &env,
+            PauseChangedEvent {
+                operation,
+                paused,
+                changed_by: governance,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether `operation` is paused, on its own or through `all`
+    pub fn is_paused(env: Env, operation: Symbol) -> bool {
+        let list = Self::paused_operations(env);
+        list.contains(&operation) || list.contains(&operations::ALL)
+    }
+
+    /// Operations currently paused, in the order they were paused
+    pub fn paused_operations(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+fn require_governance(env: &Env, governance: &Address) -> Result<(), PauseError> {
+    governance.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Governance)
+        .ok_or(PauseError::NotInitialized)?;
+    if stored != *governance {
+        return Err(PauseError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
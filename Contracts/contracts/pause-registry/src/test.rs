@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::vec;
+use test_utils::{assert_standard_event, payload};
+
+fn setup() -> (Env, PauseRegistryClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = PauseRegistryClient::new(&env, &env.register_contract(None, PauseRegistry));
+    let governance = Address::generate(&env);
+    client.initialize(&governance);
+    (env, client, governance)
+}
+
+#[test]
+fn test_operations_pause_independently() {
+    let (env, client, governance) = setup();
+    client.set_paused(&governance, &operations::SWAP, &true);
+    let captured = assert_standard_event(&env, topics::PAUSE_CHANGED, |_| true);
+    let event: PauseChangedEvent = payload(&captured);
+    assert_eq!((event.operation, event.paused), (operations::SWAP, true));
+    assert_eq!(
+        captured.metadata::<Symbol>(&env, metadata_keys::OPERATION_KEY),
+        Some(operations::SWAP)
+    );
+
+    client.set_paused(&governance, &operations::MINT, &true);
+    assert!(client.is_paused(&operations::SWAP));
+    assert!(!client.is_paused(&operations::BORROW));
+    assert_eq!(
+        client.paused_operations(),
+        vec![&env, operations::SWAP, operations::MINT]
+    );
+
+    client.set_paused(&governance, &operations::SWAP, &false);
+    let captured = assert_standard_event(&env, topics::PAUSE_CHANGED, |_| true);
+    let event: PauseChangedEvent = payload(&captured);
+    assert!(!event.paused);
+    assert!(!client.is_paused(&operations::SWAP));
+    assert!(client.is_paused(&operations::MINT));
+
+    let result = client.try_set_paused(&governance, &operations::SWAP, &false);
+    assert_eq!(result, Err(Ok(PauseError::Unchanged)));
+}
+
+#[test]
+fn test_all_pauses_everything_without_losing_flags() {
+    let (_, client, governance) = setup();
+    client.set_paused(&governance, &operations::MINT, &true);
+    client.set_paused(&governance, &operations::ALL, &true);
+    assert!(client.is_paused(&operations::SWAP));
+    assert!(client.is_paused(&operations::BORROW));
+
+    client.set_paused(&governance, &operations::ALL, &false);
+    assert!(!client.is_paused(&operations::SWAP));
+    assert!(client.is_paused(&operations::MINT));
+}
+
+#[test]
+fn test_only_governance_can_pause() {
+    let (env, client, governance) = setup();
+    let stranger = Address::generate(&env);
+    let result = client.try_set_paused(&stranger, &operations::SWAP, &true);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+
+    let new_governance = Address::generate(&env);
+    client.propose_governance(&governance, &new_governance);
+    client.accept_governance(&new_governance);
+    let result = client.try_set_paused(&governance, &operations::SWAP, &true);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+    client.set_paused(&new_governance, &operations::SWAP, &true);
+    assert_eq!(client.governance(), Some(new_governance));
+}
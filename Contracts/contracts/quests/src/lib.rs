@@ -0,0 +1,417 @@
+//! Quests: admin-defined tasks that pay out a reward once completed
+//!
+//! Each quest pairs a [`Task`] with a [`Reward`]. A task is verified either
+//! from on-chain state (holding a stake for a period, read from the staking
+//! contract) or by the quest's attestor signing off on it for tasks the
+//! chain cannot check directly, such as voting on a number of proposals.
+//! An account completes a quest once and then claims its reward: tokens
+//! paid from this contract's balance, which the admin funds, or a badge
+//! issued through the multi-token contract, where this contract must be a
+//! designated issuer.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, QuestCompletedEvent, QuestRewardEvent};
+use shared::typed_data::TypedData;
+use shared::voting::StakedBalanceClient;
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Bytes, BytesN, Env,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum QuestError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    QuestNotFound = 4,
+    InvalidQuest = 5,
+    /// Deactivated by the admin or past its end
+    QuestClosed = 6,
+    /// The quest's task is verified another way
+    WrongTask = 7,
+    NotStarted = 8,
+    AlreadyStarted = 9,
+    StakeTooLow = 10,
+    StillRunning = 11,
+    AlreadyCompleted = 12,
+    NotCompleted = 13,
+    AlreadyClaimed = 14,
+}
+
+/// Hold at least `amount` staked in `staking` from `start` until
+/// `duration` seconds later. The stake is checked at both ends only.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeTask {
+    pub staking: Address,
+    pub amount: i128,
+    pub duration: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Task {
+    Stake(StakeTask),
+    /// Completed by a signature of this ed25519 key over
+    /// `completion_digest`
+    Attested(BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenReward {
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeReward {
+    /// Multi-token contract that holds the badge
+    pub multitoken: Address,
+    pub id: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Reward {
+    Token(TokenReward),
+    Badge(BadgeReward),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Quest {
+    pub task: Task,
+    pub reward: Reward,
+    /// Last second the quest can be completed, or 0 for no end
+    pub ends_at: u64,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuestStatus {
+    /// A stake task is running
+    Started,
+    Completed,
+    Claimed,
+}
+
+/// One account's progress on one quest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Progress {
+    pub status: QuestStatus,
+    pub started_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    NextQuestId,
+    Quest(u64),
+    Progress(u64, Address),
+}
+
+/// The badge-issuing call of the multi-token contract
+#[contractclient(name = "BadgeIssuerClient")]
+pub trait BadgeIssuer {
+    fn issue_badge(env: Env, issuer: Address, to: Address, id: u64) -> u64;
+}
+
+#[contract]
+pub struct Quests;
+
+#[contractimpl]
+impl Quests {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), QuestError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(QuestError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Define a quest. Returns its id.
+    pub fn create_quest(
+        env: Env,
+        admin: Address,
+        task: Task,
+        reward: Reward,
+        ends_at: u64,
+    ) -> Result<u64, QuestError> {
+        require_admin(&env, &admin)?;
+        let valid_task = match &task {
+            Task::Stake(stake) => stake.amount > 0 && stake.duration > 0,
+            Task::Attested(_) => true,
+        };
+        let valid_reward = match &reward {
+            Reward::Token(paid) => paid.amount > 0,
+            Reward::Badge(_) => true,
+        };
+        if !valid_task || !valid_reward {
+            return Err(QuestError::InvalidQuest);
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextQuestId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextQuestId, &(id + 1));
+        let quest = Quest {
+            task,
+            reward,
+            ends_at,
+            active: true,
+        };
+        env.storage().persistent().set(&DataKey::Quest(id), &quest);
+        Ok(id)
+    }
+
+    /// Open or close a quest. Completed quests can still be claimed while
+    /// it is closed.
+    pub fn set_active(
+        env: Env,
+        admin: Address,
+        quest_id: u64,
+        active: bool,
+    ) -> Result<(), QuestError> {
+        require_admin(&env, &admin)?;
+        let mut quest = load_quest(&env, quest_id)?;
+        quest.active = active;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Quest(quest_id), &quest);
+        Ok(())
+    }
+
+    /// Start a stake quest, checking the stake is already in place
+    pub fn start(env: Env, account: Address, quest_id: u64) -> Result<(), QuestError> {
+        account.require_auth();
+        let quest = load_open_quest(&env, quest_id)?;
+        let Task::Stake(task) = quest.task else {
+            return Err(QuestError::WrongTask);
+        };
+        if Self::progress(env.clone(), quest_id, account.clone()).is_some() {
+            return Err(QuestError::AlreadyStarted);
+        }
+        require_stake(&env, &task, &account)?;
+        set_progress(
+            &env,
+            quest_id,
+            &account,
+            &Progress {
+                status: QuestStatus::Started,
+                started_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Complete a started stake quest once its duration has passed, if the
+    /// stake is still in place
+    pub fn complete(env: Env, account: Address, quest_id: u64) -> Result<(), QuestError> {
+        account.require_auth();
+        let quest = load_open_quest(&env, quest_id)?;
+        let Task::Stake(task) = quest.task else {
+            return Err(QuestError::WrongTask);
+        };
+        let progress =
+            Self::progress(env.clone(), quest_id, account.clone()).ok_or(QuestError::NotStarted)?;
+        if progress.status != QuestStatus::Started {
+            return Err(QuestError::AlreadyCompleted);
+        }
+        if env.ledger().timestamp() < progress.started_at + task.duration {
+            return Err(QuestError::StillRunning);
+        }
+        require_stake(&env, &task, &account)?;
+        mark_completed(&env, quest_id, account, progress.started_at);
+        Ok(())
+    }
+
+    /// Complete an attested quest with the attestor's `signature` over
+    /// `completion_digest(quest_id, account)`. An invalid signature traps.
+    pub fn complete_attested(
+        env: Env,
+        account: Address,
+        quest_id: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), QuestError> {
+        account.require_auth();
+        let quest = load_open_quest(&env, quest_id)?;
+        let Task::Attested(attestor) = quest.task else {
+            return Err(QuestError::WrongTask);
+        };
+        if Self::progress(env.clone(), quest_id, account.clone()).is_some() {
+            return Err(QuestError::AlreadyCompleted);
+        }
+        let digest = Self::completion_digest(env.clone(), quest_id, account.clone());
+        let message = Bytes::from_array(&env, &digest.to_array());
+        env.crypto().ed25519_verify(&attestor, &message, &signature);
+        let now = env.ledger().timestamp();
+        mark_completed(&env, quest_id, account, now);
+        Ok(())
+    }
+
+    /// Pay out a completed quest's reward
+    pub fn claim(env: Env, account: Address, quest_id: u64) -> Result<(), QuestError> {
+        account.require_auth();
+        let quest = load_quest(&env, quest_id)?;
+        let mut progress = Self::progress(env.clone(), quest_id, account.clone())
+            .ok_or(QuestError::NotCompleted)?;
+        match progress.status {
+            QuestStatus::Started => return Err(QuestError::NotCompleted),
+            QuestStatus::Claimed => return Err(QuestError::AlreadyClaimed),
+            QuestStatus::Completed => {}
+        }
+        progress.status = QuestStatus::Claimed;
+        set_progress(&env, quest_id, &account, &progress);
+
+        let this = env.current_contract_address();
+        let (asset, amount, badge_id) = match quest.reward {
+            Reward::Token(paid) => {
+                token::Client::new(&env, &paid.token).transfer(&this, &account, &paid.amount);
+                (paid.token, paid.amount, 0)
+            }
+            Reward::Badge(badge) => {
+                BadgeIssuerClient::new(&env, &badge.multitoken)
+                    .issue_badge(&this, &account, &badge.id);
+                (badge.multitoken, 1, badge.id)
+            }
+        };
+        EventEmitter::quest_reward_claimed(
+            &env,
+            QuestRewardEvent {
+                quest_id,
+                account,
+                asset,
+                amount,
+                badge_id,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    // ---- views ----
+
+    /// Digest an attested quest's attestor signs to complete it for
+    /// `account`
+    pub fn completion_digest(env: Env, quest_id: u64, account: Address) -> BytesN<32> {
+        TypedData::new(&env, &symbol_short!("quests"), &symbol_short!("Complete"))
+            .field(symbol_short!("quest_id"), quest_id)
+            .field(symbol_short!("account"), account)
+            .digest()
+    }
+
+    pub fn quest(env: Env, quest_id: u64) -> Option<Quest> {
+        env.storage().persistent().get(&DataKey::Quest(quest_id))
+    }
+
+    pub fn progress(env: Env, quest_id: u64, account: Address) -> Option<Progress> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Progress(quest_id, account))
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), QuestError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), QuestError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_stake(env: &Env, task: &StakeTask, account: &Address) -> Result<(), QuestError> {
+    if StakedBalanceClient::new(env, &task.staking).staked_balance(account) < task.amount {
+        return Err(QuestError::StakeTooLow);
+    }
+    Ok(())
+}
+
+fn mark_completed(env: &Env, quest_id: u64, account: Address, started_at: u64) {
+    set_progress(
+        env,
+        quest_id,
+        &account,
+        &Progress {
+            status: QuestStatus::Completed,
+            started_at,
+        },
+    );
+    EventEmitter::quest_completed(
+        env,
+        QuestCompletedEvent {
+            quest_id,
+            account,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+fn set_progress(env: &Env, quest_id: u64, account: &Address, progress: &Progress) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Progress(quest_id, account.clone()), progress);
+}
+
+fn load_quest(env: &Env, quest_id: u64) -> Result<Quest, QuestError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Quest(quest_id))
+        .ok_or(QuestError::QuestNotFound)
+}
+
+fn load_open_quest(env: &Env, quest_id: u64) -> Result<Quest, QuestError> {
+    let quest = load_quest(env, quest_id)?;
+    let ended = quest.ends_at != 0 && env.ledger().timestamp() > quest.ends_at;
+    if !quest.active || ended {
+        return Err(QuestError::QuestClosed);
+    }
+    Ok(quest)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), QuestError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(QuestError::NotInitialized)?;
+    if stored != *admin {
+        return Err(QuestError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
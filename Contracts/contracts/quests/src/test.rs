@@ -0,0 +1,169 @@
+#![cfg(test)]
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use multitoken::{MultiToken, MultiTokenClient};
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::String;
+use test_utils::fixtures::stellar_asset;
+use test_utils::{assert_standard_event, payload};
+
+const DAY: u64 = 24 * 60 * 60;
+
+mod staking {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Staking contract reporting whatever balance the test sets
+    #[contract]
+    pub struct MockStaking;
+
+    #[contractimpl]
+    impl MockStaking {
+        pub fn set_staked(env: Env, user: Address, amount: i128) {
+            env.storage().persistent().set(&user, &amount);
+        }
+
+        pub fn staked_balance(env: Env, user: Address) -> i128 {
+            env.storage().persistent().get(&user).unwrap_or(0)
+        }
+    }
+}
+
+use staking::{MockStaking, MockStakingClient};
+
+struct Setup {
+    env: Env,
+    client: QuestsClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let client = QuestsClient::new(&env, &env.register_contract(None, Quests));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    Setup { env, client, admin }
+}
+
+#[test]
+fn test_stake_quest_pays_tokens() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let staking = MockStakingClient::new(&s.env, &s.env.register_contract(None, MockStaking));
+    let reward = stellar_asset(&s.env);
+    reward.mint(&s.client.address, 500);
+
+    let task = Task::Stake(StakeTask {
+        staking: staking.address.clone(),
+        amount: 1_000,
+        duration: 7 * DAY,
+    });
+    let paid = Reward::Token(TokenReward {
+        token: reward.address.clone(),
+        amount: 500,
+    });
+    let id = s.client.create_quest(&s.admin, &task, &paid, &0);
+
+    let result = s.client.try_start(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::StakeTooLow)));
+    staking.set_staked(&alice, &1_000);
+    s.client.start(&alice, &id);
+    let result = s.client.try_claim(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::NotCompleted)));
+
+    s.env.ledger().with_mut(|li| li.timestamp += 7 * DAY - 1);
+    let result = s.client.try_complete(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::StillRunning)));
+
+    // Unstaking before the end forfeits the quest until the stake is back
+    s.env.ledger().with_mut(|li| li.timestamp += 1);
+    staking.set_staked(&alice, &999);
+    let result = s.client.try_complete(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::StakeTooLow)));
+    staking.set_staked(&alice, &1_000);
+    s.client.complete(&alice, &id);
+    let captured = assert_standard_event(&s.env, topics::QUEST_COMPLETED, |_| true);
+    let event: QuestCompletedEvent = payload(&captured);
+    assert_eq!((event.quest_id, event.account), (id, alice.clone()));
+
+    s.client.claim(&alice, &id);
+    assert_eq!(reward.balance(&alice), 500);
+    let captured = assert_standard_event(&s.env, topics::QUEST_CLAIMED, |_| true);
+    let event: QuestRewardEvent = payload(&captured);
+    assert_eq!((event.asset, event.amount), (reward.address.clone(), 500));
+    let result = s.client.try_claim(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::AlreadyClaimed)));
+    let result = s.client.try_complete(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::AlreadyCompleted)));
+}
+
+#[test]
+fn test_attested_quest_issues_badge() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let attestor = SigningKey::from_bytes(&[7; 32]);
+    let badges = MultiTokenClient::new(&s.env, &s.env.register_contract(None, MultiToken));
+    badges.initialize(&s.admin);
+    let uri = String::from_str(&s.env, "ipfs://badges/voter");
+    let badge_id = badges.create_badge(&s.admin, &uri, &10, &0);
+    badges.set_badge_issuer(&s.admin, &badge_id, &s.client.address, &true);
+
+    let task = Task::Attested(BytesN::from_array(
+        &s.env,
+        &attestor.verifying_key().to_bytes(),
+    ));
+    let reward = Reward::Badge(BadgeReward {
+        multitoken: badges.address.clone(),
+        id: badge_id,
+    });
+    let id = s.client.create_quest(&s.admin, &task, &reward, &2_000);
+
+    let result = s.client.try_start(&alice, &id);
+    assert_eq!(result, Err(Ok(QuestError::WrongTask)));
+    let digest = s.client.completion_digest(&id, &alice);
+    let signature = BytesN::from_array(&s.env, &attestor.sign(&digest.to_array()).to_bytes());
+
+    s.client.complete_attested(&alice, &id, &signature);
+    let result = s.client.try_complete_attested(&alice, &id, &signature);
+    assert_eq!(result, Err(Ok(QuestError::AlreadyCompleted)));
+    s.client.claim(&alice, &id);
+    assert_eq!(badges.reputation_of(&alice), 10);
+    let captured = assert_standard_event(&s.env, topics::QUEST_CLAIMED, |_| true);
+    let event: QuestRewardEvent = payload(&captured);
+    assert_eq!(event.badge_id, badge_id);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 2_001);
+    let bob = Address::generate(&s.env);
+    let result = s.client.try_complete_attested(&bob, &id, &signature);
+    assert_eq!(result, Err(Ok(QuestError::QuestClosed)));
+}
+
+#[test]
+fn test_admin_controls_quests() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let reward = Reward::Token(TokenReward {
+        token: Address::generate(&s.env),
+        amount: 0,
+    });
+    let task = Task::Attested(BytesN::from_array(&s.env, &[1; 32]));
+    let result = s.client.try_create_quest(&s.admin, &task, &reward, &0);
+    assert_eq!(result, Err(Ok(QuestError::InvalidQuest)));
+
+    let reward = Reward::Token(TokenReward {
+        token: Address::generate(&s.env),
+        amount: 1,
+    });
+    let result = s.client.try_create_quest(&stranger, &task, &reward, &0);
+    assert_eq!(result, Err(Ok(QuestError::Unauthorized)));
+    let id = s.client.create_quest(&s.admin, &task, &reward, &0);
+    s.client.set_active(&s.admin, &id, &false);
+    let signature = BytesN::from_array(&s.env, &[0; 64]);
+    let result = s.client.try_complete_attested(&stranger, &id, &signature);
+    assert_eq!(result, Err(Ok(QuestError::QuestClosed)));
+    let result = s.client.try_claim(&stranger, &(id + 1));
+    assert_eq!(result, Err(Ok(QuestError::QuestNotFound)));
+}
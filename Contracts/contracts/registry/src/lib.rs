@@ -0,0 +1,184 @@
+//! Stellara contract registry
+//!
+//! Maps well-known names (`token`, `staking`, `governance`, ...) to the
+//! addresses of the official Stellara contracts. Updates are gated on the
+//! governance account, and every change is kept as a new version so the
+//! history of a name can be audited. Other contracts resolve names through
+//! `shared::registry::resolve` rather than hardcoding addresses.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, RegistryUpdatedEvent};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    NotFound = 4,
+    Unchanged = 5,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Governance,
+    Names,
+    History(Symbol),
+}
+
+/// One version of a registry entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryEntry {
+    pub address: Address,
+    pub version: u32,
+    pub updated_by: Address,
+    pub updated_at: u64,
+}
+
+#[contract]
+pub struct RegistryContract;
+
+#[contractimpl]
+impl RegistryContract {
+    pub fn initialize(env: Env, governance: Address) -> Result<(), RegistryError> {
+        if env.storage().instance().has(&DataKey::Governance) {
+            return Err(RegistryError::AlreadyInitialized);
+        }
+        governance.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+        Ok(())
+    }
+
+    /// Propose handing registry control to `new_governance`, who must
+    /// accept before the change takes effect
+    pub fn propose_governance(
+        env: Env,
+        governance: Address,
+        new_governance: Address,
+    ) -> Result<(), RegistryError> {
+        require_governance(&env, &governance)?;
+        PendingAdmin::propose(&env, &governance, &new_governance);
+        Ok(())
+    }
+
+    /// Take over registry control by accepting a pending proposal
+    pub fn accept_governance(env: Env, new_governance: Address) -> Result<(), AdminTransferError> {
+        let governance = PendingAdmin::accept(&env, &new_governance)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+        Ok(())
+    }
+
+    pub fn cancel_governance_transfer(env: Env, governance: Address) -> Result<(), RegistryError> {
+        require_governance(&env, &governance)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_governance(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
+    pub fn governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Governance)
+    }
+
+    /// Point `name` at `address`, recording a new version. Returns the
+    /// version number.
+    pub fn set(
+        env: Env,
+        governance: Address,
+        name: Symbol,
+        address: Address,
+    ) -> Result<u32, RegistryError> {
+        require_governance(&env, &governance)?;
+
+        let mut history = Self::history(env.clone(), name.clone());
+        if history.last().map(|entry| entry.address) == Some(address.clone()) {
+            return Err(RegistryError::Unchanged);
+        }
+        if history.is_empty() {
+            let mut names = Self::names(env.clone());
+            names.push_back(name.clone());
+            env.storage().persistent().set(&DataKey::Names, &names);
+        }
+
+        let version = history.len() + 1;
+        let timestamp = env.ledger().timestamp();
+        history.push_back(RegistryEntry {
+            address: address.clone(),
+            version,
+            updated_by: governance.clone(),
+            updated_at: timestamp,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::History(name.clone()), &history);
+
+        EventEmitter::registry_updated(
+            &env,
+            RegistryUpdatedEvent {
+                name,
+                address,
+                version,
+                updated_by: governance,
+                timestamp,
+            },
+        );
+
+        Ok(version)
+    }
+
+    /// Current address for `name`
+    pub fn resolve(env: Env, name: Symbol) -> Option<Address> {
+        Self::history(env, name).last().map(|entry| entry.address)
+    }
+
+    /// Current entry for `name`
+    pub fn get_entry(env: Env, name: Symbol) -> Result<RegistryEntry, RegistryError> {
+        Self::history(env, name)
+            .last()
+            .ok_or(RegistryError::NotFound)
+    }
+
+    /// Every version of `name`, oldest first
+    pub fn history(env: Env, name: Symbol) -> Vec<RegistryEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::History(name))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Every name that has been registered
+    pub fn names(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Names)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+fn require_governance(env: &Env, governance: &Address) -> Result<(), RegistryError> {
+    governance.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Governance)
+        .ok_or(RegistryError::NotInitialized)?;
+    if stored != *governance {
+        return Err(RegistryError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,86 @@
+#![cfg(test)]
+
+use super::*;
+use shared::errors::ContractError;
+use shared::registry::{self, names};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
+
+fn setup() -> (Env, RegistryContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RegistryContract);
+    let client = RegistryContractClient::new(&env, &contract_id);
+    let governance = Address::generate(&env);
+    client.initialize(&governance);
+    (env, client, governance)
+}
+
+/// Stand-in for a contract that finds its peers through the registry
+#[contract]
+pub struct Consumer;
+
+#[contractimpl]
+impl Consumer {
+    pub fn init(env: Env, registry: Address) {
+        registry::set_registry(&env, &registry);
+    }
+
+    pub fn token(env: Env) -> Result<Address, ContractError> {
+        registry::resolve(&env, &names::TOKEN)
+    }
+}
+
+#[test]
+fn test_set_records_version_history() {
+    let (env, client, governance) = setup();
+    let v1 = Address::generate(&env);
+    let v2 = Address::generate(&env);
+
+    assert_eq!(client.set(&governance, &names::TOKEN, &v1), 1);
+    assert_eq!(client.set(&governance, &names::TOKEN, &v2), 2);
+
+    assert_eq!(client.resolve(&names::TOKEN), Some(v2.clone()));
+    let history = client.history(&names::TOKEN);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().address, v1);
+    assert_eq!(client.get_entry(&names::TOKEN).version, 2);
+    assert_eq!(client.names().len(), 1);
+
+    let result = client.try_set(&governance, &names::TOKEN, &v2);
+    assert_eq!(result, Err(Ok(RegistryError::Unchanged)));
+}
+
+#[test]
+fn test_updates_are_governance_gated() {
+    let (env, client, governance) = setup();
+    let stranger = Address::generate(&env);
+    let target = Address::generate(&env);
+
+    let result = client.try_set(&stranger, &names::STAKING, &target);
+    assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+    assert_eq!(client.resolve(&names::STAKING), None);
+
+    let new_governance = Address::generate(&env);
+    client.propose_governance(&governance, &new_governance);
+    client.accept_governance(&new_governance);
+    let result = client.try_set(&governance, &names::STAKING, &target);
+    assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+    client.set(&new_governance, &names::STAKING, &target);
+    assert_eq!(client.resolve(&names::STAKING), Some(target));
+}
+
+#[test]
+fn test_shared_resolve_helper() {
+    let (env, client, governance) = setup();
+    let consumer_id = env.register_contract(None, Consumer);
+    let consumer = ConsumerClient::new(&env, &consumer_id);
+
+    assert_eq!(consumer.try_token(), Err(Ok(ContractError::NotInitialized)));
+
+    consumer.init(&client.address);
+    assert_eq!(consumer.try_token(), Err(Ok(ContractError::NotFound)));
+
+    let token = Address::generate(&env);
+    client.set(&governance, &names::TOKEN, &token);
+    assert_eq!(consumer.token(), token);
+}
@@ -0,0 +1,328 @@
+//! Sponsored relay registry
+//!
+//! Lets users act without holding fees: they sign a meta-transaction
+//! (a permit, a vote by signature, a claim on their behalf) and a
+//! whitelisted relayer submits it through [`RelayRegistry::relay`]. The
+//! registry calls the target and reimburses the relayer's fee out of a
+//! sponsorship budget the protocol funds in advance.
+//!
+//! Only calls the admin allowed, as a (contract, function) pair, can be
+//! relayed. Each epoch has a fixed budget, and each call is reimbursed at
+//! most `max_fee`. A failing target call reverts the whole relay, so
+//! relayers are only reimbursed for calls that landed.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::epoch::EpochClock;
+use shared::events::{EventEmitter, RelayReimbursedEvent};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol, Val, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RelayError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    InvalidAmount = 5,
+    UnknownRelayer = 6,
+    CallNotAllowed = 7,
+    FeeTooHigh = 8,
+    BudgetExhausted = 9,
+    RelayFailed = 10,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    /// Token relayers are reimbursed in
+    pub token: Address,
+    pub clock: EpochClock,
+    /// Total reimbursements allowed per epoch
+    pub epoch_budget: i128,
+    /// Largest reimbursement of a single call
+    pub max_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Relayer(Address),
+    /// A (contract, function) pair that may be relayed
+    AllowedCall(Address, Symbol),
+    /// Reimbursed in an epoch
+    EpochSpent(u64),
+    /// Reimbursed to a relayer in an epoch
+    RelayerSpent(Address, u64),
+    /// Reimbursed to a relayer over all epochs
+    RelayerTotal(Address),
+}
+
+#[contract]
+pub struct RelayRegistry;
+
+#[contractimpl]
+impl RelayRegistry {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        epoch_length: u64,
+        epoch_budget: i128,
+        max_fee: i128,
+    ) -> Result<(), RelayError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(RelayError::AlreadyInitialized);
+        }
+        let clock = EpochClock::new(env.ledger().timestamp(), epoch_length)
+            .ok_or(RelayError::InvalidConfig)?;
+        if epoch_budget < 0 || max_fee < 0 {
+            return Err(RelayError::InvalidConfig);
+        }
+        let config = Config {
+            admin,
+            token,
+            clock,
+            epoch_budget,
+            max_fee,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Add `amount` to the sponsorship pool
+    pub fn fund(env: Env, from: Address, amount: i128) -> Result<(), RelayError> {
+        from.require_auth();
+        let config = load_config(&env)?;
+        if amount <= 0 {
+            return Err(RelayError::InvalidAmount);
+        }
+        token::Client::new(&env, &config.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Ok(())
+    }
+
+    /// Whitelist or remove a relayer (admin only)
+    pub fn set_relayer(
+        env: Env,
+        admin: Address,
+        relayer: Address,
+        allowed: bool,
+    ) -> Result<(), RelayError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::Relayer(relayer);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Allow or disallow relaying calls to `function` of `target` (admin
+    /// only)
+    pub fn set_allowed_call(
+        env: Env,
+        admin: Address,
+        target: Address,
+        function: Symbol,
+        allowed: bool,
+    ) -> Result<(), RelayError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::AllowedCall(target, function);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Change the per-epoch budget and per-call fee cap (admin only). A
+    /// lower budget applies to the current epoch immediately.
+    pub fn set_budget(
+        env: Env,
+        admin: Address,
+        epoch_budget: i128,
+        max_fee: i128,
+    ) -> Result<(), RelayError> {
+        let mut config = require_admin(&env, &admin)?;
+        if epoch_budget < 0 || max_fee < 0 {
+            return Err(RelayError::InvalidConfig);
+        }
+        config.epoch_budget = epoch_budget;
+        config.max_fee = max_fee;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Call `function` of `target` with `args` for a user and reimburse
+    /// the relayer `fee`. Returns the target's return value.
+    pub fn relay(
+        env: Env,
+        relayer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+        fee: i128,
+    ) -> Result<Val, RelayError> {
+        relayer.require_auth();
+        let config = load_config(&env)?;
+        if !Self::is_relayer(env.clone(), relayer.clone()) {
+            return Err(RelayError::UnknownRelayer);
+        }
+        if !Self::is_allowed_call(env.clone(), target.clone(), function.clone()) {
+            return Err(RelayError::CallNotAllowed);
+        }
+        if fee < 0 {
+            return Err(RelayError::InvalidAmount);
+        }
+        if fee > config.max_fee {
+            return Err(RelayError::FeeTooHigh);
+        }
+        let epoch = config.clock.current_epoch(&env);
+        let epoch_spent = read_amount(&env, &DataKey::EpochSpent(epoch)) + fee;
+        if epoch_spent > config.epoch_budget {
+            return Err(RelayError::BudgetExhausted);
+        }
+
+        let result = env.try_invoke_contract::<Val, soroban_sdk::Error>(&target, &function, args);
+        let value = match result {
+            Ok(Ok(value)) => value,
+            _ => return Err(RelayError::RelayFailed),
+        };
+
+        let relayer_spent = read_amount(&env, &DataKey::RelayerSpent(relayer.clone(), epoch)) + fee;
+        let relayer_total = read_amount(&env, &DataKey::RelayerTotal(relayer.clone())) + fee;
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::EpochSpent(epoch), &epoch_spent);
+        storage.set(
+            &DataKey::RelayerSpent(relayer.clone(), epoch),
+            &relayer_spent,
+        );
+        storage.set(&DataKey::RelayerTotal(relayer.clone()), &relayer_total);
+        if fee > 0 {
+            token::Client::new(&env, &config.token).transfer(
+                &env.current_contract_address(),
+                &relayer,
+                &fee,
+            );
+        }
+
+        EventEmitter::relay_reimbursed(
+            &env,
+            RelayReimbursedEvent {
+                relayer,
+                target,
+                function,
+                amount: fee,
+                epoch,
+                relayer_spent,
+                budget_remaining: config.epoch_budget - epoch_spent,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(value)
+    }
+
+    // ---- views ----
+
+    pub fn is_relayer(env: Env, relayer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Relayer(relayer))
+    }
+
+    pub fn is_allowed_call(env: Env, target: Address, function: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::AllowedCall(target, function))
+    }
+
+    /// Budget left in the current epoch
+    pub fn remaining_budget(env: Env) -> Result<i128, RelayError> {
+        let config = load_config(&env)?;
+        let epoch = config.clock.current_epoch(&env);
+        Ok((config.epoch_budget - Self::epoch_spent(env, epoch)).max(0))
+    }
+
+    pub fn epoch_spent(env: Env, epoch: u64) -> i128 {
+        read_amount(&env, &DataKey::EpochSpent(epoch))
+    }
+
+    /// Reimbursed to `relayer` in `epoch`
+    pub fn relayer_spent(env: Env, relayer: Address, epoch: u64) -> i128 {
+        read_amount(&env, &DataKey::RelayerSpent(relayer, epoch))
+    }
+
+    /// Reimbursed to `relayer` over all epochs
+    pub fn relayer_total(env: Env, relayer: Address) -> i128 {
+        read_amount(&env, &DataKey::RelayerTotal(relayer))
+    }
+
+    pub fn current_epoch(env: Env) -> Result<u64, RelayError> {
+        Ok(load_config(&env)?.clock.current_epoch(&env))
+    }
+
+    pub fn config(env: Env) -> Result<Config, RelayError> {
+        load_config(&env)
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), RelayError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut config = load_config(&env).unwrap();
+        config.admin = admin;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), RelayError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn read_amount(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<Config, RelayError> {
+    admin.require_auth();
+    let config = load_config(env)?;
+    if config.admin != *admin {
+        return Err(RelayError::Unauthorized);
+    }
+    Ok(config)
+}
+
+fn load_config(env: &Env) -> Result<Config, RelayError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(RelayError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contract, contracterror, contractimpl, symbol_short, vec, IntoVal, TryFromVal};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const EPOCH: u64 = 86_400;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RewardsError {
+    Rejected = 1,
+}
+
+/// Stand-in for a contract with a signature-authorized entry point
+#[contract]
+struct Rewards;
+
+#[contractimpl]
+impl Rewards {
+    pub fn claim_for(env: Env, user: Address) -> u32 {
+        let claims: u32 = env.storage().persistent().get(&user).unwrap_or(0) + 1;
+        env.storage().persistent().set(&user, &claims);
+        claims
+    }
+
+    pub fn fail(_env: Env) -> Result<(), RewardsError> {
+        Err(RewardsError::Rejected)
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: RelayRegistryClient<'static>,
+    admin: Address,
+    relayer: Address,
+    target: Address,
+    token: TestToken<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let token = stellar_asset(&env);
+    let client = RelayRegistryClient::new(&env, &env.register_contract(None, RelayRegistry));
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let target = env.register_contract(None, Rewards);
+    client.initialize(&admin, &token.address, &EPOCH, &100, &40);
+    token.mint(&admin, 1_000);
+    client.fund(&admin, &1_000);
+    client.set_relayer(&admin, &relayer, &true);
+    client.set_allowed_call(&admin, &target, &symbol_short!("claim_for"), &true);
+
+    Setup {
+        env,
+        client,
+        admin,
+        relayer,
+        target,
+        token,
+    }
+}
+
+fn claim_args(s: &Setup, user: &Address) -> Vec<Val> {
+    vec![&s.env, user.into_val(&s.env)]
+}
+
+#[test]
+fn test_relay_calls_target_and_reimburses_relayer() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    let value = s.client.relay(
+        &s.relayer,
+        &s.target,
+        &symbol_short!("claim_for"),
+        &claim_args(&s, &user),
+        &30,
+    );
+    assert_eq!(u32::try_from_val(&s.env, &value), Ok(1));
+    assert_eq!(s.token.balance(&s.relayer), 30);
+    assert_eq!(s.client.relayer_spent(&s.relayer, &0), 30);
+    assert_eq!(s.client.remaining_budget(), 70);
+
+    let event: RelayReimbursedEvent = payload(&assert_standard_event(
+        &s.env,
+        topics::RELAY_REIMBURSED,
+        |_| true,
+    ));
+    assert_eq!(event.amount, 30);
+    assert_eq!(event.function, symbol_short!("claim_for"));
+    assert_eq!(event.budget_remaining, 70);
+}
+
+#[test]
+fn test_only_whitelisted_relayers_and_calls() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    let outsider = Address::generate(&s.env);
+    let claim = symbol_short!("claim_for");
+
+    assert_eq!(
+        s.client
+            .try_relay(&outsider, &s.target, &claim, &claim_args(&s, &user), &0)
+            .err(),
+        Some(Ok(RelayError::UnknownRelayer))
+    );
+    assert_eq!(
+        s.client
+            .try_relay(
+                &s.relayer,
+                &s.target,
+                &symbol_short!("fail"),
+                &Vec::new(&s.env),
+                &0
+            )
+            .err(),
+        Some(Ok(RelayError::CallNotAllowed))
+    );
+    assert_eq!(
+        s.client
+            .try_relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &41)
+            .err(),
+        Some(Ok(RelayError::FeeTooHigh))
+    );
+
+    s.client
+        .set_allowed_call(&s.admin, &s.target, &symbol_short!("fail"), &true);
+    assert_eq!(
+        s.client
+            .try_relay(
+                &s.relayer,
+                &s.target,
+                &symbol_short!("fail"),
+                &Vec::new(&s.env),
+                &10
+            )
+            .err(),
+        Some(Ok(RelayError::RelayFailed))
+    );
+    assert_eq!(s.token.balance(&s.relayer), 0);
+
+    s.client.set_relayer(&s.admin, &s.relayer, &false);
+    assert_eq!(
+        s.client
+            .try_relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &0)
+            .err(),
+        Some(Ok(RelayError::UnknownRelayer))
+    );
+}
+
+#[test]
+fn test_budget_resets_each_epoch() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    let claim = symbol_short!("claim_for");
+
+    for _ in 0..2 {
+        s.client
+            .relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &40);
+    }
+    assert_eq!(
+        s.client
+            .try_relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &40)
+            .err(),
+        Some(Ok(RelayError::BudgetExhausted))
+    );
+    s.client
+        .relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &20);
+    assert_eq!(s.client.remaining_budget(), 0);
+
+    s.env.ledger().with_mut(|li| li.timestamp += EPOCH);
+    s.client
+        .relay(&s.relayer, &s.target, &claim, &claim_args(&s, &user), &40);
+    assert_eq!(s.client.epoch_spent(&0), 100);
+    assert_eq!(s.client.relayer_spent(&s.relayer, &1), 40);
+    assert_eq!(s.client.relayer_total(&s.relayer), 140);
+    assert_eq!(s.token.balance(&s.relayer), 140);
+}
@@ -0,0 +1,296 @@
+//! Reputation scores aggregated from on-chain activity
+//!
+//! Staking, governance and trading contracts registered as reporters push
+//! activity here (stake-seconds, votes cast, traded volume); each activity
+//! earns points at an admin-set weight. A score decays over time, halving
+//! every `half_life` seconds, so reputation reflects recent participation.
+//! Admin-set thresholds split scores into tiers that fee schedules and
+//! governance thresholds read through `shared::reputation`.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, ReputationEvent};
+use shared::math::mul_div;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+/// Denominator of activity weights: a weight of `WEIGHT_SCALE` earns one
+/// point per unit of activity
+pub const WEIGHT_SCALE: i128 = 10_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ReputationError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    UnknownActivity = 5,
+    /// Thresholds must be positive and strictly increasing
+    InvalidTiers = 6,
+}
+
+/// Points as of `updated_at`, before decay since then
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Score {
+    pub points: i128,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    HalfLife,
+    Tiers,
+    Weight(Symbol),
+    Reporter(Address, Symbol),
+    Score(Address),
+}
+
+#[contract]
+pub struct Reputation;
+
+#[contractimpl]
+impl Reputation {
+    /// Set up with scores halving every `half_life` seconds (0 disables
+    /// decay)
+    pub fn initialize(env: Env, admin: Address, half_life: u64) -> Result<(), ReputationError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ReputationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::HalfLife, &half_life);
+        Ok(())
+    }
+
+    /// Change the decay rate. Scores are decayed lazily, so the new rate
+    /// also applies to the time since each score was last updated.
+    pub fn set_half_life(env: Env, admin: Address, half_life: u64) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::HalfLife, &half_life);
+        Ok(())
+    }
+
+    /// Points earned per unit of `activity`, scaled by [`WEIGHT_SCALE`].
+    /// A weight of 0 stops the activity from being recorded.
+    pub fn set_weight(
+        env: Env,
+        admin: Address,
+        activity: Symbol,
+        weight: u32,
+    ) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::Weight(activity);
+        if weight == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &weight);
+        }
+        Ok(())
+    }
+
+    /// Allow or disallow `reporter` to record `activity`
+    pub fn set_reporter(
+        env: Env,
+        admin: Address,
+        reporter: Address,
+        activity: Symbol,
+        allowed: bool,
+    ) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::Reporter(reporter, activity);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Score thresholds of tiers 1, 2, ...; scores below the first are
+    /// tier 0
+    pub fn set_tiers(
+        env: Env,
+        admin: Address,
+        thresholds: Vec<i128>,
+    ) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        let mut previous = 0;
+        for threshold in thresholds.iter() {
+            if threshold <= previous {
+                return Err(ReputationError::InvalidTiers);
+            }
+            previous = threshold;
+        }
+        env.storage().instance().set(&DataKey::Tiers, &thresholds);
+        Ok(())
+    }
+
+    /// Add `amount` of `activity` by `account`. Called by the reporting
+    /// contract. Returns the account's new score.
+    pub fn record(
+        env: Env,
+        reporter: Address,
+        account: Address,
+        activity: Symbol,
+        amount: i128,
+    ) -> Result<i128, ReputationError> {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone(), activity.clone()) {
+            return Err(ReputationError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(ReputationError::InvalidAmount);
+        }
+        let weight = Self::weight(env.clone(), activity.clone());
+        if weight == 0 {
+            return Err(ReputationError::UnknownActivity);
+        }
+        let points =
+            mul_div(amount, weight as i128, WEIGHT_SCALE).ok_or(ReputationError::InvalidAmount)?;
+
+        let now = env.ledger().timestamp();
+        let score = Self::score_of(env.clone(), account.clone())
+            .checked_add(points)
+            .ok_or(ReputationError::InvalidAmount)?;
+        env.storage().persistent().set(
+            &DataKey::Score(account.clone()),
+            &Score {
+                points: score,
+                updated_at: now,
+            },
+        );
+
+        EventEmitter::reputation_updated(
+            &env,
+            ReputationEvent {
+                account,
+                activity,
+                reporter,
+                points,
+                score,
+                tier: tier(&env, score),
+                timestamp: now,
+            },
+        );
+        Ok(score)
+    }
+
+    // ---- views ----
+
+    /// Current score of `account`, decayed to now
+    pub fn score_of(env: Env, account: Address) -> i128 {
+        let score: Score = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Score(account))
+            .unwrap_or_default();
+        let elapsed = env.ledger().timestamp().saturating_sub(score.updated_at);
+        decay(score.points, elapsed, Self::half_life(env))
+    }
+
+    pub fn tier_of(env: Env, account: Address) -> u32 {
+        let score = Self::score_of(env.clone(), account);
+        tier(&env, score)
+    }
+
+    pub fn tiers(env: Env) -> Vec<i128> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tiers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn half_life(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::HalfLife)
+            .unwrap_or(0)
+    }
+
+    pub fn weight(env: Env, activity: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Weight(activity))
+            .unwrap_or(0)
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address, activity: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Reporter(reporter, activity))
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), ReputationError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// `points` after `elapsed` seconds of halving every `half_life`. Within a
+/// half-life the decay is linear between the two halvings, which slightly
+/// overstates the exact exponential but never increases a score.
+fn decay(points: i128, elapsed: u64, half_life: u64) -> i128 {
+    if half_life == 0 || points <= 0 {
+        return points;
+    }
+    let halvings = elapsed / half_life;
+    if halvings >= 127 {
+        return 0;
+    }
+    let halved = points >> halvings;
+    let partial =
+        mul_div(halved, (elapsed % half_life) as i128, 2 * half_life as i128).unwrap_or(0);
+    halved - partial
+}
+
+fn tier(env: &Env, score: i128) -> u32 {
+    Reputation::tiers(env.clone())
+        .iter()
+        .take_while(|threshold| score >= *threshold)
+        .count() as u32
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), ReputationError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(ReputationError::NotInitialized)?;
+    if stored != *admin {
+        return Err(ReputationError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,122 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use shared::reputation::{activities, ReputationClient as SharedReputationClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::{assert_standard_event, payload};
+
+const HALF_LIFE: u64 = 1_000;
+
+struct Setup {
+    env: Env,
+    client: ReputationClient<'static>,
+    admin: Address,
+    staking: Address,
+    governor: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ReputationClient::new(&env, &env.register_contract(None, Reputation));
+    let admin = Address::generate(&env);
+    let staking = Address::generate(&env);
+    let governor = Address::generate(&env);
+    client.initialize(&admin, &HALF_LIFE);
+    client.set_weight(&admin, &activities::STAKING, &1);
+    client.set_weight(&admin, &activities::GOVERNANCE, &(50 * WEIGHT_SCALE as u32));
+    client.set_reporter(&admin, &staking, &activities::STAKING, &true);
+    client.set_reporter(&admin, &governor, &activities::GOVERNANCE, &true);
+    client.set_tiers(&admin, &Vec::from_array(&env, [100, 1_000]));
+    Setup {
+        env,
+        client,
+        admin,
+        staking,
+        governor,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_activity_accumulates_into_tiers() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    // 1_000_000 stake-seconds at 1/10_000 of a point each
+    let score = s
+        .client
+        .record(&s.staking, &alice, &activities::STAKING, &1_000_000);
+    assert_eq!(score, 100);
+    assert_eq!(s.client.tier_of(&alice), 1);
+
+    s.client
+        .record(&s.governor, &alice, &activities::GOVERNANCE, &18);
+    let captured = assert_standard_event(&s.env, topics::REPUTATION_UPDATED, |_| true);
+    let event: ReputationEvent = payload(&captured);
+    assert_eq!((event.points, event.score, event.tier), (900, 1_000, 2));
+    assert_eq!(event.activity, activities::GOVERNANCE);
+
+    // Consumers read through the shared interface
+    let reader = SharedReputationClient::new(&s.env, &s.client.address);
+    assert_eq!(reader.score_of(&alice), 1_000);
+    assert_eq!(reader.tier_of(&alice), 2);
+    assert_eq!(reader.tier_of(&Address::generate(&s.env)), 0);
+}
+
+#[test]
+fn test_scores_decay_by_half_life() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    s.client
+        .record(&s.governor, &alice, &activities::GOVERNANCE, &20);
+
+    advance(&s.env, HALF_LIFE);
+    assert_eq!(s.client.score_of(&alice), 500);
+    advance(&s.env, HALF_LIFE / 2);
+    assert_eq!(s.client.score_of(&alice), 375);
+    assert_eq!(s.client.tier_of(&alice), 1);
+
+    // New activity builds on the decayed score
+    assert_eq!(
+        s.client
+            .record(&s.governor, &alice, &activities::GOVERNANCE, &1),
+        425
+    );
+    advance(&s.env, 200 * HALF_LIFE);
+    assert_eq!(s.client.score_of(&alice), 0);
+
+    s.client.set_half_life(&s.admin, &0);
+    assert_eq!(s.client.score_of(&alice), 425);
+}
+
+#[test]
+fn test_only_reporters_record_known_activities() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_record(&s.staking, &alice, &activities::GOVERNANCE, &1);
+    assert_eq!(result, Err(Ok(ReputationError::Unauthorized)));
+
+    s.client
+        .set_reporter(&s.admin, &s.staking, &activities::TRADING, &true);
+    let result = s
+        .client
+        .try_record(&s.staking, &alice, &activities::TRADING, &1);
+    assert_eq!(result, Err(Ok(ReputationError::UnknownActivity)));
+    let result = s
+        .client
+        .try_record(&s.staking, &alice, &activities::STAKING, &0);
+    assert_eq!(result, Err(Ok(ReputationError::InvalidAmount)));
+
+    let result = s
+        .client
+        .try_set_tiers(&s.admin, &Vec::from_array(&s.env, [100, 100]));
+    assert_eq!(result, Err(Ok(ReputationError::InvalidTiers)));
+}
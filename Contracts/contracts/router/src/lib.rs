@@ -0,0 +1,279 @@
+//! Multi-hop swap router
+//!
+//! The admin registers AMM pairs with the router, which indexes them by
+//! their two tokens. A swap names a path of tokens, `[A, B, C]`, and the
+//! router swaps through the registered `A/B` and `B/C` pairs in turn,
+//! holding the intermediate tokens itself. The whole route reverts when it
+//! misses its deadline or returns less than `amount_out_min`; individual
+//! hops carry no slippage bound of their own.
+//!
+//! Every hop publishes a `TRADE_EXECUTED` event and the route closes with a
+//! `ROUTE_EXECUTED` summary, all carrying the route id as correlation id.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::amm::{AmmClient, PRICE_SCALE};
+use shared::deadline::require_not_expired;
+use shared::events::{EventEmitter, RouteExecutedEvent, TradeExecutedEvent};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
+    IntoVal, Symbol, Vec,
+};
+
+/// Longest path a route can take, in hops
+pub const MAX_HOPS: u32 = 4;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RouterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidPath = 4,
+    PoolNotFound = 5,
+    PoolExists = 6,
+    InvalidAmount = 7,
+    DeadlineExpired = 8,
+    SlippageExceeded = 9,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Amm,
+    NextRouteId,
+    Pairs,
+    /// Pair trading the two tokens, stored under both orders
+    Pool(Address, Address),
+}
+
+#[contract]
+pub struct SwapRouter;
+
+#[contractimpl]
+impl SwapRouter {
+    pub fn initialize(env: Env, admin: Address, amm: Address) -> Result<(), RouterError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(RouterError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Amm, &amm);
+        Ok(())
+    }
+
+    /// Make an AMM pair available to routes (admin only)
+    pub fn register_pool(env: Env, admin: Address, pair: Symbol) -> Result<(), RouterError> {
+        admin.require_auth();
+        require_admin(&env, &admin)?;
+        let (token_a, token_b) = AmmClient::new(&env, &load_amm(&env)?).pair_tokens(&pair);
+        let storage = env.storage().persistent();
+        if storage.has(&DataKey::Pool(token_a.clone(), token_b.clone())) {
+            return Err(RouterError::PoolExists);
+        }
+        storage.set(&DataKey::Pool(token_a.clone(), token_b.clone()), &pair);
+        storage.set(&DataKey::Pool(token_b, token_a), &pair);
+
+        let mut pairs = Self::pools(env.clone());
+        pairs.push_back(pair);
+        env.storage().instance().set(&DataKey::Pairs, &pairs);
+        Ok(())
+    }
+
+    /// Sell exactly `amount_in` of `path[0]` for as much of the last token
+    /// of `path` as the route yields, reverting if that is less than
+    /// `amount_out_min` or the ledger is past `deadline`. Returns the
+    /// amount received.
+    pub fn swap_exact_in(
+        env: Env,
+        trader: Address,
+        path: Vec<Address>,
+        amount_in: i128,
+        amount_out_min: i128,
+        deadline: u64,
+    ) -> Result<i128, RouterError> {
+        trader.require_auth();
+        require_not_expired(&env, deadline).map_err(|_| RouterError::DeadlineExpired)?;
+        if amount_in <= 0 || amount_out_min < 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+        let pairs = resolve(&env, &path)?;
+        let amm_address = load_amm(&env)?;
+        let amm = AmmClient::new(&env, &amm_address);
+        let this = env.current_contract_address();
+        let route_id = next_route_id(&env);
+        let timestamp = env.ledger().timestamp();
+
+        token::Client::new(&env, &path.get_unchecked(0)).transfer(&trader, &this, &amount_in);
+        let mut amount = amount_in;
+        for (hop, pair) in pairs.iter().enumerate() {
+            let token_in = path.get_unchecked(hop as u32);
+            // The AMM pulls each hop's input from this contract
+            env.authorize_as_current_contract(vec![
+                &env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: token_in.clone(),
+                        fn_name: symbol_short!("transfer"),
+                        args: (this.clone(), amm_address.clone(), amount).into_val(&env),
+                    },
+                    sub_invocations: vec![&env],
+                }),
+            ]);
+            let amount_out = amm.swap(&this, &pair, &token_in, &amount, &0);
+            let (token_a, _) = amm.pair_tokens(&pair);
+            EventEmitter::route_trade_executed(
+                &env,
+                TradeExecutedEvent {
+                    trade_id: route_id,
+                    trader: trader.clone(),
+                    pair,
+                    amount,
+                    price: amount_out * PRICE_SCALE / amount,
+                    is_buy: token_in != token_a,
+                    fee_amount: 0,
+                    fee_token: token_in,
+                    timestamp,
+                },
+                route_id,
+                hop as u32,
+            );
+            amount = amount_out;
+        }
+        if amount < amount_out_min {
+            return Err(RouterError::SlippageExceeded);
+        }
+
+        token::Client::new(&env, &path.get_unchecked(path.len() - 1))
+            .transfer(&this, &trader, &amount);
+        EventEmitter::route_executed(
+            &env,
+            RouteExecutedEvent {
+                route_id,
+                trader,
+                path,
+                amount_in,
+                amount_out: amount,
+                timestamp,
+            },
+        );
+        Ok(amount)
+    }
+
+    /// What selling `amount_in` along `path` would return at current
+    /// reserves. Only a best-effort estimate: reserves can move before the
+    /// swap lands, so swaps should still set `amount_out_min`.
+    pub fn quote(env: Env, path: Vec<Address>, amount_in: i128) -> Result<i128, RouterError> {
+        if amount_in <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+        let pairs = resolve(&env, &path)?;
+        let amm = AmmClient::new(&env, &load_amm(&env)?);
+        let mut amount = amount_in;
+        for (hop, pair) in pairs.iter().enumerate() {
+            amount = amm
+                .try_quote(&pair, &path.get_unchecked(hop as u32), &amount)
+                .ok()
+                .and_then(|quoted| quoted.ok())
+                .ok_or(RouterError::InvalidAmount)?;
+        }
+        Ok(amount)
+    }
+
+    /// Pair routing between `token_a` and `token_b`, if registered
+    pub fn pool_for(env: Env, token_a: Address, token_b: Address) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pool(token_a, token_b))
+    }
+
+    pub fn pools(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pairs)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), RouterError> {
+        admin.require_auth();
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), RouterError> {
+        admin.require_auth();
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Registered pairs for each hop of `path`
+fn resolve(env: &Env, path: &Vec<Address>) -> Result<Vec<Symbol>, RouterError> {
+    if path.len() < 2 || path.len() > MAX_HOPS + 1 {
+        return Err(RouterError::InvalidPath);
+    }
+    let mut pairs = Vec::new(env);
+    for hop in 0..path.len() - 1 {
+        let pair = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(
+                path.get_unchecked(hop),
+                path.get_unchecked(hop + 1),
+            ))
+            .ok_or(RouterError::PoolNotFound)?;
+        pairs.push_back(pair);
+    }
+    Ok(pairs)
+}
+
+fn next_route_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextRouteId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextRouteId, &(id + 1));
+    id
+}
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), RouterError> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(RouterError::NotInitialized)?;
+    if admin != *caller {
+        return Err(RouterError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_amm(env: &Env) -> Result<Address, RouterError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Amm)
+        .ok_or(RouterError::NotInitialized)
+}
+
+#[cfg(test)]
+mod test;
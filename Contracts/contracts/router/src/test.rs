@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use super::*;
+use amm::{AmmContract, AmmContractClient};
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{payload, standard_events_from};
+
+struct Setup {
+    env: Env,
+    client: SwapRouterClient<'static>,
+    amm: AmmContractClient<'static>,
+    tokens: [TestToken<'static>; 3],
+    admin: Address,
+    trader: Address,
+}
+
+const AB: Symbol = symbol_short!("AB");
+const BC: Symbol = symbol_short!("BC");
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let trader = Address::generate(&env);
+    let lp = Address::generate(&env);
+    let tokens = [
+        stellar_asset(&env),
+        stellar_asset(&env),
+        stellar_asset(&env),
+    ];
+    let amm = AmmContractClient::new(&env, &env.register_contract(None, AmmContract));
+    amm.initialize(&admin);
+    for (pair, a, b) in [(AB, 0, 1), (BC, 1, 2)] {
+        amm.create_pair(&admin, &pair, &tokens[a].address, &tokens[b].address);
+        tokens[a].mint(&lp, 100_000);
+        tokens[b].mint(&lp, 100_000);
+        amm.add_liquidity(&lp, &pair, &100_000, &100_000, &0);
+    }
+
+    let client = SwapRouterClient::new(&env, &env.register_contract(None, SwapRouter));
+    client.initialize(&admin, &amm.address);
+    client.register_pool(&admin, &AB);
+    client.register_pool(&admin, &BC);
+    tokens[0].mint(&trader, 1_000);
+
+    Setup {
+        env,
+        client,
+        amm,
+        tokens,
+        admin,
+        trader,
+    }
+}
+
+fn path(s: &Setup, indices: &[usize]) -> Vec<Address> {
+    let mut path = Vec::new(&s.env);
+    for i in indices {
+        path.push_back(s.tokens[*i].address.clone());
+    }
+    path
+}
+
+#[test]
+fn test_two_hop_route_matches_quote() {
+    let s = setup();
+    let route = path(&s, &[0, 1, 2]);
+    let first = s.amm.quote(&AB, &s.tokens[0].address, &1_000);
+    let quoted = s.client.quote(&route, &1_000);
+    assert_eq!(quoted, s.amm.quote(&BC, &s.tokens[1].address, &first));
+
+    let received = s
+        .client
+        .swap_exact_in(&s.trader, &route, &1_000, &quoted, &1_000);
+    assert_eq!(received, quoted);
+    assert_eq!(s.tokens[0].balance(&s.trader), 0);
+    assert_eq!(s.tokens[2].balance(&s.trader), received);
+    assert_eq!(s.tokens[1].balance(&s.client.address), 0);
+
+    // One trade per hop, then the summary, all under the route's id
+    let events = standard_events_from(&s.env, &s.client.address);
+    assert_eq!(events.len(), 3);
+    let summary = &events[2];
+    assert_eq!(summary.event.topic, topics::ROUTE_EXECUTED);
+    let route_event: RouteExecutedEvent = payload(summary);
+    assert_eq!(route_event.amount_out, received);
+    for (hop, captured) in events[..2].iter().enumerate() {
+        assert_eq!(captured.event.topic, topics::TRADE_EXECUTED);
+        assert_eq!(captured.event.correlation_id, route_event.route_id);
+        assert_eq!(
+            captured.metadata::<u32>(&s.env, metadata_keys::HOP_KEY),
+            Some(hop as u32)
+        );
+    }
+    let second: TradeExecutedEvent = payload(&events[1]);
+    assert_eq!(second.pair, BC);
+    assert_eq!(second.amount, first);
+}
+
+#[test]
+fn test_slippage_and_deadline_revert_the_route() {
+    let s = setup();
+    let route = path(&s, &[0, 1, 2]);
+    let quoted = s.client.quote(&route, &1_000);
+
+    let result = s
+        .client
+        .try_swap_exact_in(&s.trader, &route, &1_000, &(quoted + 1), &1_000);
+    assert_eq!(result, Err(Ok(RouterError::SlippageExceeded)));
+    assert_eq!(s.tokens[0].balance(&s.trader), 1_000);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 1_001);
+    let result = s
+        .client
+        .try_swap_exact_in(&s.trader, &route, &1_000, &0, &1_000);
+    assert_eq!(result, Err(Ok(RouterError::DeadlineExpired)));
+}
+
+#[test]
+fn test_paths_need_registered_pools() {
+    let s = setup();
+    let result = s.client.try_quote(&path(&s, &[0, 2]), &1_000);
+    assert_eq!(result, Err(Ok(RouterError::PoolNotFound)));
+    let result = s.client.try_quote(&path(&s, &[0]), &1_000);
+    assert_eq!(result, Err(Ok(RouterError::InvalidPath)));
+    let result = s.client.try_register_pool(&s.admin, &AB);
+    assert_eq!(result, Err(Ok(RouterError::PoolExists)));
+    let result = s.client.try_register_pool(&s.trader, &BC);
+    assert_eq!(result, Err(Ok(RouterError::Unauthorized)));
+
+    // Reverse direction routes through the same pools
+    assert_eq!(
+        s.client
+            .pool_for(&s.tokens[2].address, &s.tokens[1].address),
+        Some(BC)
+    );
+    assert!(s.client.quote(&path(&s, &[2, 1, 0]), &1_000) > 0);
+    assert_eq!(s.client.pools().len(), 2);
+}
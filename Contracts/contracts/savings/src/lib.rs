@@ -0,0 +1,372 @@
+//! Savings rate for the stable asset
+//!
+//! Users deposit the stable asset and earn interest at an annual rate set
+//! by governance. Balances are stored scaled by a global savings index that
+//! grows at the rate, so every account accrues without being touched; a rate
+//! change applies the old rate up to that moment.
+//!
+//! Interest is not minted: it is paid out of whatever this contract holds
+//! beyond the deposited principal, which the treasury tops up by sending the
+//! stable asset here (e.g. a spend with this contract as recipient).
+//! Withdrawals take principal first, so savers can always get their deposit
+//! back even while the reserve is short of the interest owed.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, SavingsEvent, SavingsRateEvent};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+/// Fixed-point scale of the savings index
+pub const INDEX_SCALE: i128 = 1_000_000_000;
+pub const BPS_DENOMINATOR: i128 = 10_000;
+pub const SECONDS_PER_YEAR: i128 = 31_536_000;
+/// Highest annual rate governance may set
+pub const MAX_RATE_BPS: u32 = 2_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SavingsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidRate = 5,
+    InsufficientBalance = 6,
+    InsufficientReserve = 7,
+}
+
+/// Savings index and when the rate was last applied to it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsIndex {
+    pub index: i128,
+    pub last_accrual: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SavingsAccount {
+    /// Balance divided by the index at deposit time
+    pub scaled: i128,
+    /// Deposits not yet withdrawn
+    pub principal: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Asset,
+    Rate,
+    Index,
+    Account(Address),
+    TotalScaled,
+    TotalPrincipal,
+}
+
+#[contract]
+pub struct SavingsContract;
+
+#[contractimpl]
+impl SavingsContract {
+    /// `admin` is the governance account allowed to set the rate;
+    /// `rate_bps` is the annual savings rate.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        rate_bps: u32,
+    ) -> Result<(), SavingsError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(SavingsError::AlreadyInitialized);
+        }
+        if rate_bps > MAX_RATE_BPS {
+            return Err(SavingsError::InvalidRate);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        env.storage().instance().set(&DataKey::Rate, &rate_bps);
+        env.storage().instance().set(
+            &DataKey::Index,
+            &SavingsIndex {
+                index: INDEX_SCALE,
+                last_accrual: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Change the annual savings rate (admin only). Balances accrue at the
+    /// old rate up to now.
+    pub fn set_rate(env: Env, admin: Address, rate_bps: u32) -> Result<(), SavingsError> {
+        require_admin(&env, &admin)?;
+        if rate_bps > MAX_RATE_BPS {
+            return Err(SavingsError::InvalidRate);
+        }
+        let index = accrue(&env);
+        let old_rate_bps = Self::rate(env.clone());
+        env.storage().instance().set(&DataKey::Rate, &rate_bps);
+        EventEmitter::savings_rate_changed(
+            &env,
+            SavingsRateEvent {
+                admin,
+                old_rate_bps,
+                new_rate_bps: rate_bps,
+                index,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Deposit `amount` of the stable asset. Returns the new balance.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, SavingsError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        let asset = load_asset(&env)?;
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &amount);
+
+        let index = accrue(&env);
+        let scaled = amount * INDEX_SCALE / index;
+        let mut account = load_account(&env, &user);
+        account.scaled += scaled;
+        account.principal += amount;
+        save_account(&env, &user, &account);
+        add_totals(&env, scaled, amount);
+
+        let balance = from_scaled(account.scaled, index);
+        EventEmitter::savings_deposit(
+            &env,
+            SavingsEvent {
+                user,
+                asset,
+                amount,
+                interest: 0,
+                balance,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(balance)
+    }
+
+    /// Withdraw `amount`, principal first and then accrued interest.
+    /// Returns the part paid out of interest.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<i128, SavingsError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        let asset = load_asset(&env)?;
+        let index = accrue(&env);
+        let mut account = load_account(&env, &user);
+        let balance = from_scaled(account.scaled, index);
+        if amount > balance {
+            return Err(SavingsError::InsufficientBalance);
+        }
+
+        let from_principal = amount.min(account.principal);
+        let interest = amount - from_principal;
+        if interest > reserve(&env, &asset) {
+            return Err(SavingsError::InsufficientReserve);
+        }
+        let burned = if amount == balance {
+            account.scaled
+        } else {
+            to_scaled_up(amount, index).min(account.scaled)
+        };
+        // Emptying the account also drops principal lost to rounding
+        let principal_removed = if burned == account.scaled {
+            account.principal
+        } else {
+            from_principal
+        };
+        account.scaled -= burned;
+        account.principal -= principal_removed;
+        save_account(&env, &user, &account);
+        add_totals(&env, -burned, -principal_removed);
+
+        token::Client::new(&env, &asset).transfer(&env.current_contract_address(), &user, &amount);
+        EventEmitter::savings_withdraw(
+            &env,
+            SavingsEvent {
+                user,
+                asset,
+                amount,
+                interest,
+                balance: from_scaled(account.scaled, index),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(interest)
+    }
+
+    // ---- views ----
+
+    /// Balance of `user` including accrued interest
+    pub fn balance(env: Env, user: Address) -> i128 {
+        from_scaled(load_account(&env, &user).scaled, current_index(&env))
+    }
+
+    /// Interest `user` has earned and not yet withdrawn
+    pub fn accrued_interest(env: Env, user: Address) -> i128 {
+        let account = load_account(&env, &user);
+        (from_scaled(account.scaled, current_index(&env)) - account.principal).max(0)
+    }
+
+    /// Deposits across all accounts, excluding interest
+    pub fn total_principal(env: Env) -> i128 {
+        get_total(&env, &DataKey::TotalPrincipal)
+    }
+
+    /// Interest owed across all accounts
+    pub fn total_interest(env: Env) -> i128 {
+        let owed = from_scaled(get_total(&env, &DataKey::TotalScaled), current_index(&env));
+        (owed - get_total(&env, &DataKey::TotalPrincipal)).max(0)
+    }
+
+    /// Stable asset held beyond the principal, available to pay interest
+    pub fn reserve(env: Env) -> Result<i128, SavingsError> {
+        Ok(reserve(&env, &load_asset(&env)?))
+    }
+
+    /// Annual savings rate in basis points
+    pub fn rate(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Rate).unwrap_or(0)
+    }
+
+    /// Savings index grown by the rate up to now
+    pub fn index(env: Env) -> i128 {
+        current_index(&env)
+    }
+
+    pub fn asset(env: Env) -> Result<Address, SavingsError> {
+        load_asset(&env)
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), SavingsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), SavingsError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Savings index grown by the rate up to now, without storing it
+fn current_index(env: &Env) -> i128 {
+    let Some(state) = env
+        .storage()
+        .instance()
+        .get::<_, SavingsIndex>(&DataKey::Index)
+    else {
+        return INDEX_SCALE;
+    };
+    let elapsed = env.ledger().timestamp().saturating_sub(state.last_accrual) as i128;
+    let rate = SavingsContract::rate(env.clone()) as i128;
+    let growth = INDEX_SCALE * rate * elapsed / (BPS_DENOMINATOR * SECONDS_PER_YEAR);
+    state.index * (INDEX_SCALE + growth) / INDEX_SCALE
+}
+
+/// Apply the rate up to now and return the new index
+fn accrue(env: &Env) -> i128 {
+    let index = current_index(env);
+    env.storage().instance().set(
+        &DataKey::Index,
+        &SavingsIndex {
+            index,
+            last_accrual: env.ledger().timestamp(),
+        },
+    );
+    index
+}
+
+/// Scaled units for `amount`, rounded up (used when removing balance)
+fn to_scaled_up(amount: i128, index: i128) -> i128 {
+    (amount * INDEX_SCALE + index - 1) / index
+}
+
+fn from_scaled(scaled: i128, index: i128) -> i128 {
+    scaled * index / INDEX_SCALE
+}
+
+fn reserve(env: &Env, asset: &Address) -> i128 {
+    let held = token::Client::new(env, asset).balance(&env.current_contract_address());
+    held - get_total(env, &DataKey::TotalPrincipal)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), SavingsError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SavingsError::NotInitialized)?;
+    if stored != *admin {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_asset(env: &Env) -> Result<Address, SavingsError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Asset)
+        .ok_or(SavingsError::NotInitialized)
+}
+
+fn load_account(env: &Env, user: &Address) -> SavingsAccount {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Account(user.clone()))
+        .unwrap_or_default()
+}
+
+fn save_account(env: &Env, user: &Address, account: &SavingsAccount) {
+    let key = DataKey::Account(user.clone());
+    if account.scaled == 0 && account.principal == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, account);
+    }
+}
+
+fn get_total(env: &Env, key: &DataKey) -> i128 {
+    env.storage().instance().get(key).unwrap_or(0)
+}
+
+fn add_totals(env: &Env, scaled: i128, principal: i128) {
+    let total_scaled = get_total(env, &DataKey::TotalScaled) + scaled;
+    let total_principal = get_total(env, &DataKey::TotalPrincipal) + principal;
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalScaled, &total_scaled);
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalPrincipal, &total_principal);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,126 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const YEAR: u64 = SECONDS_PER_YEAR as u64;
+
+struct Setup {
+    env: Env,
+    client: SavingsContractClient<'static>,
+    stable: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    admin: Address,
+    alice: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+    let admin = Address::generate(&env);
+    let stable_id = env.register_stellar_asset_contract(admin.clone());
+    let alice = Address::generate(&env);
+    let minter = StellarAssetClient::new(&env, &stable_id);
+    minter.mint(&alice, &10_000);
+
+    let contract_id = env.register_contract(None, SavingsContract);
+    let client = SavingsContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &stable_id, &500);
+
+    Setup {
+        client,
+        stable: TokenClient::new(&env, &stable_id),
+        minter,
+        env,
+        admin,
+        alice,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_deposit_earns_interest_paid_from_reserve() {
+    let s = setup();
+    assert_eq!(s.client.deposit(&s.alice, &10_000), 10_000);
+    let deposited = assert_standard_event(&s.env, topics::SAVINGS_DEPOSIT, |_| true);
+    let event: SavingsEvent = payload(&deposited);
+    assert_eq!(event.amount, 10_000);
+    assert_eq!(event.balance, 10_000);
+
+    advance(&s.env, YEAR);
+    assert_eq!(s.client.balance(&s.alice), 10_500);
+    assert_eq!(s.client.accrued_interest(&s.alice), 500);
+    assert_eq!(s.client.total_interest(), 500);
+
+    // The treasury funds the interest by sending the stable asset in
+    s.minter.mint(&s.client.address, &800);
+    assert_eq!(s.client.reserve(), 800);
+    assert_eq!(s.client.withdraw(&s.alice, &10_500), 500);
+    assert_eq!(s.stable.balance(&s.alice), 10_500);
+    assert_eq!(s.client.balance(&s.alice), 0);
+    assert_eq!(s.client.total_principal(), 0);
+    assert_eq!(s.client.reserve(), 300);
+
+    let withdrawn = assert_standard_event(&s.env, topics::SAVINGS_WITHDRAW, |_| true);
+    let event: SavingsEvent = payload(&withdrawn);
+    assert_eq!(event.user, s.alice);
+    assert_eq!(event.interest, 500);
+    assert_eq!(event.balance, 0);
+    assert_eq!(
+        withdrawn.metadata::<i128>(&s.env, metadata_keys::AMOUNT_KEY),
+        Some(10_500)
+    );
+}
+
+#[test]
+fn test_principal_is_withdrawable_without_reserve() {
+    let s = setup();
+    s.client.deposit(&s.alice, &10_000);
+    advance(&s.env, YEAR);
+
+    let result = s.client.try_withdraw(&s.alice, &10_500);
+    assert_eq!(result, Err(Ok(SavingsError::InsufficientReserve)));
+    assert_eq!(s.client.withdraw(&s.alice, &4_200), 0);
+    assert_eq!(s.client.accrued_interest(&s.alice), 500);
+    assert_eq!(s.client.total_principal(), 5_800);
+
+    let result = s.client.try_withdraw(&s.alice, &6_301);
+    assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+}
+
+#[test]
+fn test_rate_change_applies_from_now() {
+    let s = setup();
+    s.client.deposit(&s.alice, &10_000);
+    advance(&s.env, YEAR / 2);
+
+    s.client.set_rate(&s.admin, &0);
+    let changed = assert_standard_event(&s.env, topics::SAVINGS_RATE, |_| true);
+    let event: SavingsRateEvent = payload(&changed);
+    assert_eq!(event.old_rate_bps, 500);
+    assert_eq!(event.new_rate_bps, 0);
+    assert_eq!(event.index, s.client.index());
+
+    advance(&s.env, YEAR / 2);
+    assert_eq!(s.client.accrued_interest(&s.alice), 250);
+    assert_eq!(s.client.rate(), 0);
+}
+
+#[test]
+fn test_only_governance_sets_rate() {
+    let s = setup();
+    let result = s.client.try_set_rate(&s.alice, &1_000);
+    assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+    let result = s.client.try_set_rate(&s.admin, &(MAX_RATE_BPS + 1));
+    assert_eq!(result, Err(Ok(SavingsError::InvalidRate)));
+    let result = s.client.try_deposit(&s.alice, &0);
+    assert_eq!(result, Err(Ok(SavingsError::InvalidAmount)));
+}
@@ -0,0 +1,251 @@
+//! Session-key smart account
+//!
+//! A custom Soroban account controlled by an ed25519 owner key. The owner
+//! can register session keys, e.g. one kept by the mobile app, that may only
+//! authorize calls allowed by their [`SessionPolicy`]: listed contracts and
+//! functions, daily spend caps per token, and an expiry. A lost or leaked
+//! session key therefore cannot reach beyond its policy, and the owner key
+//! never has to leave cold storage for day-to-day use.
+//!
+//! Every authorization the account gives goes through `__check_auth`.
+//! Owner signatures are accepted for anything; session signatures are
+//! checked against their policy for each call in the authorized tree.
+//!
+//! If the owner key is lost, guardians can rotate it to a new one (see
+//! [`recovery`]). Session keys stay registered across a rotation; the new
+//! owner revokes any they no longer trust.
+
+#![no_std]
+
+mod policy;
+pub mod recovery;
+
+use shared::deadline::require_future;
+use shared::events::{EventEmitter, RecoveryEvent, SessionKeyEvent};
+use soroban_sdk::auth::{Context, CustomAccountInterface};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+pub use policy::{SessionPolicy, SpendLimit, DAY};
+pub use recovery::{GuardianConfig, RecoveryRequest};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SessionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    UnknownSigner = 3,
+    SessionExpired = 4,
+    ContractNotAllowed = 5,
+    FunctionNotAllowed = 6,
+    SpendLimitExceeded = 7,
+    InvalidPolicy = 8,
+    InvalidGuardians = 9,
+    NotGuardian = 10,
+    RecoveryPending = 11,
+    NoRecovery = 12,
+    RecoveryNotReady = 13,
+}
+
+/// Signature passed to `__check_auth`, by the owner or a session key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountSignature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Owner,
+    Session(BytesN<32>),
+    /// Amount of a token a session moved on a day
+    Spent(BytesN<32>, Address, u64),
+    Guardians,
+    Recovery,
+}
+
+#[contract]
+pub struct SessionAccount;
+
+#[contractimpl]
+impl SessionAccount {
+    pub fn initialize(env: Env, owner_key: BytesN<32>) -> Result<(), SessionError> {
+        if env.storage().instance().has(&DataKey::Owner) {
+            return Err(SessionError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Owner, &owner_key);
+        Ok(())
+    }
+
+    /// Register or replace the policy of `session_key`. Must be authorized
+    /// by the owner key.
+    pub fn add_session(
+        env: Env,
+        session_key: BytesN<32>,
+        policy: SessionPolicy,
+    ) -> Result<(), SessionError> {
+        env.current_contract_address().require_auth();
+        if require_future(&env, policy.expires_at).is_err()
+            || policy.contracts.contains(env.current_contract_address())
+            || policy
+                .spend_limits
+                .iter()
+                .any(|limit| limit.daily_limit < 0)
+        {
+            return Err(SessionError::InvalidPolicy);
+        }
+        let expires_at = policy.expires_at;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Session(session_key.clone()), &policy);
+        EventEmitter::session_added(&env, session_event(&env, session_key, expires_at));
+        Ok(())
+    }
+
+    /// Remove `session_key`. Must be authorized by the owner key.
+    pub fn revoke_session(env: Env, session_key: BytesN<32>) -> Result<(), SessionError> {
+        env.current_contract_address().require_auth();
+        let key = DataKey::Session(session_key.clone());
+        let policy: SessionPolicy = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(SessionError::UnknownSigner)?;
+        env.storage().persistent().remove(&key);
+        EventEmitter::session_revoked(&env, session_event(&env, session_key, policy.expires_at));
+        Ok(())
+    }
+
+    pub fn get_session(env: Env, session_key: BytesN<32>) -> Option<SessionPolicy> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Session(session_key))
+    }
+
+    /// Amount of `token` the session has moved today
+    pub fn spent_today(env: Env, session_key: BytesN<32>, token: Address) -> i128 {
+        policy::spent_today(&env, &session_key, &token)
+    }
+
+    pub fn owner_key(env: Env) -> Result<BytesN<32>, SessionError> {
+        load_owner(&env)
+    }
+
+    /// Name the guardians that can recover the owner key. Must be
+    /// authorized by the owner key.
+    pub fn set_guardians(env: Env, config: GuardianConfig) -> Result<(), SessionError> {
+        env.current_contract_address().require_auth();
+        recovery::set_config(&env, &config)
+    }
+
+    /// Propose rotating the owner key to `new_owner_key`, counting as
+    /// `guardian`'s approval
+    pub fn initiate_recovery(
+        env: Env,
+        guardian: Address,
+        new_owner_key: BytesN<32>,
+    ) -> Result<(), SessionError> {
+        let request = recovery::initiate(&env, &guardian, new_owner_key)?;
+        EventEmitter::recovery_initiated(&env, recovery_event(&env, request));
+        Ok(())
+    }
+
+    pub fn approve_recovery(env: Env, guardian: Address) -> Result<(), SessionError> {
+        recovery::approve(&env, &guardian)?;
+        Ok(())
+    }
+
+    /// Drop the pending recovery. Must be authorized by the owner key.
+    pub fn cancel_recovery(env: Env) -> Result<(), SessionError> {
+        env.current_contract_address().require_auth();
+        let request = recovery::cancel(&env)?;
+        EventEmitter::recovery_cancelled(&env, recovery_event(&env, request));
+        Ok(())
+    }
+
+    /// Rotate the owner key once the pending recovery has enough guardian
+    /// approvals and its delay has passed. Anyone can call this.
+    pub fn complete_recovery(env: Env) -> Result<(), SessionError> {
+        let request = recovery::complete(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Owner, &request.new_owner_key);
+        EventEmitter::recovery_completed(&env, recovery_event(&env, request));
+        Ok(())
+    }
+
+    pub fn guardians(env: Env) -> Option<GuardianConfig> {
+        recovery::config(&env)
+    }
+
+    pub fn pending_recovery(env: Env) -> Option<RecoveryRequest> {
+        recovery::request(&env)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for SessionAccount {
+    type Signature = AccountSignature;
+    type Error = SessionError;
+
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: BytesN<32>,
+        signature: AccountSignature,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), SessionError> {
+        let owner = load_owner(&env)?;
+        let session = if signature.public_key == owner {
+            None
+        } else {
+            let policy: SessionPolicy = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Session(signature.public_key.clone()))
+                .ok_or(SessionError::UnknownSigner)?;
+            Some(policy)
+        };
+
+        env.crypto().ed25519_verify(
+            &signature.public_key,
+            &signature_payload.into(),
+            &signature.signature,
+        );
+        match session {
+            Some(policy) => policy::authorize(&env, &signature.public_key, &policy, &auth_contexts),
+            None => Ok(()),
+        }
+    }
+}
+
+fn load_owner(env: &Env) -> Result<BytesN<32>, SessionError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Owner)
+        .ok_or(SessionError::NotInitialized)
+}
+
+fn session_event(env: &Env, session_key: BytesN<32>, expires_at: u64) -> SessionKeyEvent {
+    SessionKeyEvent {
+        account: env.current_contract_address(),
+        session_key,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+fn recovery_event(env: &Env, request: RecoveryRequest) -> RecoveryEvent {
+    RecoveryEvent {
+        account: env.current_contract_address(),
+        new_owner_key: request.new_owner_key,
+        approvals: request.approvals.len(),
+        executable_at: request.executable_at,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+#[cfg(test)]
+mod test;
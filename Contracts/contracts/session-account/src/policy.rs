@@ -0,0 +1,125 @@
+//! What a session key may authorize
+//!
+//! A session may call the listed contracts, and only the listed functions
+//! on them when any are given. Tokens with a spend limit may additionally
+//! be moved with `transfer`, `approve` or `burn`, and every such call
+//! counts towards the session's allowance for that token for the current
+//! UTC day. Calls into the account itself, e.g. to add sessions, are never
+//! allowed.
+
+use crate::{DataKey, SessionError};
+use shared::deadline::require_not_expired;
+use soroban_sdk::auth::{Context, ContractContext};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, TryFromVal, Vec};
+
+pub const DAY: u64 = 24 * 60 * 60;
+/// Two days of 5 second ledgers, so a day's spending record cannot expire
+/// before the day is over
+const SPENT_TTL_LEDGERS: u32 = 2 * 17_280;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendLimit {
+    pub token: Address,
+    /// Most of `token` the session may move per day
+    pub daily_limit: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionPolicy {
+    /// Contracts the session may call
+    pub contracts: Vec<Address>,
+    /// Functions the session may call on them; empty allows any
+    pub functions: Vec<Symbol>,
+    pub spend_limits: Vec<SpendLimit>,
+    /// Timestamp after which the session key is rejected
+    pub expires_at: u64,
+}
+
+/// Check every call in `contexts` against the policy of `session_key` and
+/// record the spending they add
+pub fn authorize(
+    env: &Env,
+    session_key: &BytesN<32>,
+    policy: &SessionPolicy,
+    contexts: &Vec<Context>,
+) -> Result<(), SessionError> {
+    require_not_expired(env, policy.expires_at).map_err(|_| SessionError::SessionExpired)?;
+    for context in contexts.iter() {
+        let Context::Contract(call) = context else {
+            return Err(SessionError::ContractNotAllowed);
+        };
+        if call.contract == env.current_contract_address() {
+            return Err(SessionError::ContractNotAllowed);
+        }
+        match policy
+            .spend_limits
+            .iter()
+            .find(|limit| limit.token == call.contract)
+        {
+            Some(limit) => spend(env, session_key, &limit, &call)?,
+            None => check_call(policy, &call)?,
+        }
+    }
+    Ok(())
+}
+
+/// Amount of the session's `token` allowance used today
+pub fn spent_today(env: &Env, session_key: &BytesN<32>, token: &Address) -> i128 {
+    env.storage()
+        .temporary()
+        .get(&spent_key(env, session_key, token))
+        .unwrap_or(0)
+}
+
+fn check_call(policy: &SessionPolicy, call: &ContractContext) -> Result<(), SessionError> {
+    if !policy.contracts.contains(&call.contract) {
+        return Err(SessionError::ContractNotAllowed);
+    }
+    if !policy.functions.is_empty() && !policy.functions.contains(&call.fn_name) {
+        return Err(SessionError::FunctionNotAllowed);
+    }
+    Ok(())
+}
+
+fn spend(
+    env: &Env,
+    session_key: &BytesN<32>,
+    limit: &SpendLimit,
+    call: &ContractContext,
+) -> Result<(), SessionError> {
+    let amount_index =
+        if call.fn_name == symbol_short!("transfer") || call.fn_name == symbol_short!("approve") {
+            2
+        } else if call.fn_name == symbol_short!("burn") {
+            1
+        } else {
+            return Err(SessionError::FunctionNotAllowed);
+        };
+    let amount = call
+        .args
+        .get(amount_index)
+        .and_then(|arg| i128::try_from_val(env, &arg).ok())
+        .filter(|amount| *amount >= 0)
+        .ok_or(SessionError::FunctionNotAllowed)?;
+
+    let spent = spent_today(env, session_key, &limit.token) + amount;
+    if spent > limit.daily_limit {
+        return Err(SessionError::SpendLimitExceeded);
+    }
+    let key = spent_key(env, session_key, &limit.token);
+    env.storage().temporary().set(&key, &spent);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, SPENT_TTL_LEDGERS, SPENT_TTL_LEDGERS);
+    Ok(())
+}
+
+fn spent_key(env: &Env, session_key: &BytesN<32>, token: &Address) -> DataKey {
+    DataKey::Spent(
+        session_key.clone(),
+        token.clone(),
+        env.ledger().timestamp() / DAY,
+    )
+}
@@ -0,0 +1,119 @@
+//! Social recovery of the owner key
+//!
+//! The owner names guardian accounts and how many of them must agree. If
+//! the owner key is lost, one guardian proposes a new key and the others
+//! approve it; once enough have and `delay` seconds have passed since the
+//! request, anyone can complete it and the account's owner key is rotated.
+//! Until then the owner can cancel the request, which is what stops
+//! guardians from taking over an account whose owner still has their key.
+
+use crate::{DataKey, SessionError};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianConfig {
+    pub guardians: Vec<Address>,
+    /// Guardian approvals needed to rotate the key
+    pub threshold: u32,
+    /// Seconds the owner has to cancel a request
+    pub delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_owner_key: BytesN<32>,
+    /// Guardians that have approved, the initiator first
+    pub approvals: Vec<Address>,
+    pub initiated_at: u64,
+    /// Earliest completion time
+    pub executable_at: u64,
+}
+
+pub fn config(env: &Env) -> Option<GuardianConfig> {
+    env.storage().instance().get(&DataKey::Guardians)
+}
+
+pub fn set_config(env: &Env, config: &GuardianConfig) -> Result<(), SessionError> {
+    let unique = config
+        .guardians
+        .iter()
+        .enumerate()
+        .all(|(i, g)| config.guardians.first_index_of(&g) == Some(i as u32));
+    if config.threshold == 0 || config.threshold > config.guardians.len() || !unique {
+        return Err(SessionError::InvalidGuardians);
+    }
+    env.storage().instance().set(&DataKey::Guardians, config);
+    Ok(())
+}
+
+pub fn request(env: &Env) -> Option<RecoveryRequest> {
+    env.storage().instance().get(&DataKey::Recovery)
+}
+
+/// Start a recovery to `new_owner_key`, approved by `guardian`
+pub fn initiate(
+    env: &Env,
+    guardian: &Address,
+    new_owner_key: BytesN<32>,
+) -> Result<RecoveryRequest, SessionError> {
+    let config = require_guardian(env, guardian)?;
+    if request(env).is_some() {
+        return Err(SessionError::RecoveryPending);
+    }
+    let now = env.ledger().timestamp();
+    let request = RecoveryRequest {
+        new_owner_key,
+        approvals: Vec::from_array(env, [guardian.clone()]),
+        initiated_at: now,
+        executable_at: now + config.delay,
+    };
+    env.storage().instance().set(&DataKey::Recovery, &request);
+    Ok(request)
+}
+
+/// Add `guardian`'s approval to the pending request
+pub fn approve(env: &Env, guardian: &Address) -> Result<RecoveryRequest, SessionError> {
+    require_guardian(env, guardian)?;
+    let mut request = request(env).ok_or(SessionError::NoRecovery)?;
+    if !request.approvals.contains(guardian) {
+        request.approvals.push_back(guardian.clone());
+        env.storage().instance().set(&DataKey::Recovery, &request);
+    }
+    Ok(request)
+}
+
+/// Drop the pending request
+pub fn cancel(env: &Env) -> Result<RecoveryRequest, SessionError> {
+    let request = request(env).ok_or(SessionError::NoRecovery)?;
+    env.storage().instance().remove(&DataKey::Recovery);
+    Ok(request)
+}
+
+/// Take the pending request if it has enough approvals and its delay has
+/// passed
+pub fn complete(env: &Env) -> Result<RecoveryRequest, SessionError> {
+    let config = config(env).ok_or(SessionError::NotGuardian)?;
+    let request = request(env).ok_or(SessionError::NoRecovery)?;
+    // Guardians removed since they approved no longer count
+    let approvals = request
+        .approvals
+        .iter()
+        .filter(|g| config.guardians.contains(g))
+        .count() as u32;
+    if approvals < config.threshold || env.ledger().timestamp() < request.executable_at {
+        return Err(SessionError::RecoveryNotReady);
+    }
+    env.storage().instance().remove(&DataKey::Recovery);
+    Ok(request)
+}
+
+fn require_guardian(env: &Env, guardian: &Address) -> Result<GuardianConfig, SessionError> {
+    guardian.require_auth();
+    let config = config(env).ok_or(SessionError::NotGuardian)?;
+    if !config.guardians.contains(guardian) {
+        return Err(SessionError::NotGuardian);
+    }
+    Ok(config)
+}
@@ -0,0 +1,278 @@
+#![cfg(test)]
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::auth::ContractContext;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, vec, IntoVal, Symbol, Val};
+use test_utils::{assert_standard_event, payload};
+
+struct Setup {
+    env: Env,
+    client: SessionAccountClient<'static>,
+    owner: SigningKey,
+    session: SigningKey,
+    app: Address,
+    token: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+
+    let owner = SigningKey::from_bytes(&[1; 32]);
+    let session = SigningKey::from_bytes(&[2; 32]);
+    let app = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, SessionAccount);
+    let client = SessionAccountClient::new(&env, &contract_id);
+    client.initialize(&public_key(&env, &owner));
+    client.add_session(
+        &public_key(&env, &session),
+        &SessionPolicy {
+            contracts: vec![&env, app.clone()],
+            functions: vec![&env, symbol_short!("stake"), symbol_short!("claim")],
+            spend_limits: vec![
+                &env,
+                SpendLimit {
+                    token: token.clone(),
+                    daily_limit: 1_000,
+                },
+            ],
+            expires_at: 12 * DAY,
+        },
+    );
+
+    Setup {
+        env,
+        client,
+        owner,
+        session,
+        app,
+        token,
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn call(contract: &Address, fn_name: Symbol, args: Vec<Val>) -> Context {
+    Context::Contract(ContractContext {
+        contract: contract.clone(),
+        fn_name,
+        args,
+    })
+}
+
+fn transfer(env: &Env, token: &Address, amount: i128) -> Context {
+    let from = Address::generate(env);
+    let to = Address::generate(env);
+    let args = vec![
+        env,
+        from.into_val(env),
+        to.into_val(env),
+        amount.into_val(env),
+    ];
+    call(token, symbol_short!("transfer"), args)
+}
+
+fn check_auth(
+    s: &Setup,
+    key: &SigningKey,
+    contexts: Vec<Context>,
+) -> Result<(), Result<SessionError, soroban_sdk::InvokeError>> {
+    let payload = BytesN::from_array(&s.env, &[9; 32]);
+    let signature = AccountSignature {
+        public_key: public_key(&s.env, key),
+        signature: BytesN::from_array(&s.env, &key.sign(&payload.to_array()).to_bytes()),
+    };
+    s.env.try_invoke_contract_check_auth::<SessionError>(
+        &s.client.address,
+        &payload,
+        signature.into_val(&s.env),
+        &contexts,
+    )
+}
+
+#[test]
+fn test_session_limited_to_listed_calls() {
+    let s = setup();
+    let stake = call(&s.app, symbol_short!("stake"), vec![&s.env]);
+    assert_eq!(check_auth(&s, &s.session, vec![&s.env, stake]), Ok(()));
+
+    let other_fn = call(&s.app, symbol_short!("unstake"), vec![&s.env]);
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, other_fn.clone()]),
+        Err(Ok(SessionError::FunctionNotAllowed))
+    );
+    let other_contract = call(
+        &Address::generate(&s.env),
+        symbol_short!("stake"),
+        vec![&s.env],
+    );
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, other_contract]),
+        Err(Ok(SessionError::ContractNotAllowed))
+    );
+    // The session cannot manage sessions on the account itself
+    let own = call(&s.client.address, symbol_short!("add_sess"), vec![&s.env]);
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, own]),
+        Err(Ok(SessionError::ContractNotAllowed))
+    );
+
+    // The owner key is not limited
+    assert_eq!(check_auth(&s, &s.owner, vec![&s.env, other_fn]), Ok(()));
+}
+
+#[test]
+fn test_session_daily_spend_cap() {
+    let s = setup();
+    let session_key = public_key(&s.env, &s.session);
+    let first = transfer(&s.env, &s.token, 600);
+    assert_eq!(check_auth(&s, &s.session, vec![&s.env, first]), Ok(()));
+    assert_eq!(s.client.spent_today(&session_key, &s.token), 600);
+
+    // A stake that pulls tokens counts the nested transfer too
+    let stake = call(&s.app, symbol_short!("stake"), vec![&s.env]);
+    let second = transfer(&s.env, &s.token, 500);
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, stake, second.clone()]),
+        Err(Ok(SessionError::SpendLimitExceeded))
+    );
+    assert_eq!(s.client.spent_today(&session_key, &s.token), 600);
+    let mint = call(&s.token, symbol_short!("mint"), vec![&s.env]);
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, mint]),
+        Err(Ok(SessionError::FunctionNotAllowed))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp += DAY);
+    assert_eq!(check_auth(&s, &s.session, vec![&s.env, second]), Ok(()));
+    assert_eq!(s.client.spent_today(&session_key, &s.token), 500);
+}
+
+#[test]
+fn test_session_expiry_and_revocation() {
+    let s = setup();
+    let session_key = public_key(&s.env, &s.session);
+    let added = assert_standard_event(&s.env, topics::SESSION_ADDED, |_| true);
+    let event: SessionKeyEvent = payload(&added);
+    assert_eq!(event.session_key, session_key);
+    assert_eq!(
+        added.metadata::<u64>(&s.env, metadata_keys::EXPIRY_KEY),
+        Some(12 * DAY)
+    );
+
+    let claim = call(&s.app, symbol_short!("claim"), vec![&s.env]);
+    s.env.ledger().with_mut(|li| li.timestamp = 12 * DAY + 1);
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, claim.clone()]),
+        Err(Ok(SessionError::SessionExpired))
+    );
+
+    s.client.revoke_session(&session_key);
+    assert!(s.client.get_session(&session_key).is_none());
+    assert_eq!(
+        check_auth(&s, &s.session, vec![&s.env, claim]),
+        Err(Ok(SessionError::UnknownSigner))
+    );
+
+    let mut policy = SessionPolicy {
+        contracts: vec![&s.env, s.client.address.clone()],
+        functions: Vec::new(&s.env),
+        spend_limits: Vec::new(&s.env),
+        expires_at: 20 * DAY,
+    };
+    let result = s.client.try_add_session(&session_key, &policy);
+    assert_eq!(result, Err(Ok(SessionError::InvalidPolicy)));
+    policy.contracts = vec![&s.env, s.app.clone()];
+    policy.expires_at = 12 * DAY;
+    let result = s.client.try_add_session(&session_key, &policy);
+    assert_eq!(result, Err(Ok(SessionError::InvalidPolicy)));
+}
+
+fn set_guardians(s: &Setup) -> [Address; 3] {
+    let guardians = [
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    ];
+    s.client.set_guardians(&GuardianConfig {
+        guardians: Vec::from_array(&s.env, guardians.clone()),
+        threshold: 2,
+        delay: 2 * DAY,
+    });
+    guardians
+}
+
+#[test]
+fn test_guardians_rotate_owner_after_delay() {
+    let s = setup();
+    let [first, second, _] = set_guardians(&s);
+    let new_owner = SigningKey::from_bytes(&[3; 32]);
+    let new_key = public_key(&s.env, &new_owner);
+
+    s.client.initiate_recovery(&first, &new_key);
+    let initiated = assert_standard_event(&s.env, topics::RECOVERY_INITIATED, |_| true);
+    let event: RecoveryEvent = payload(&initiated);
+    assert_eq!(event.new_owner_key, new_key);
+    assert_eq!(event.executable_at, 12 * DAY);
+    let result = s.client.try_complete_recovery();
+    assert_eq!(result, Err(Ok(SessionError::RecoveryNotReady)));
+
+    s.client.approve_recovery(&second);
+    let result = s.client.try_complete_recovery();
+    assert_eq!(result, Err(Ok(SessionError::RecoveryNotReady)));
+
+    s.env.ledger().with_mut(|li| li.timestamp += 2 * DAY);
+    s.client.complete_recovery();
+    assert_eq!(s.client.owner_key(), new_key);
+    assert!(s.client.pending_recovery().is_none());
+    let completed = assert_standard_event(&s.env, topics::RECOVERY_COMPLETED, |_| true);
+    let event: RecoveryEvent = payload(&completed);
+    assert_eq!(event.approvals, 2);
+    assert_eq!(
+        completed.metadata::<u64>(&s.env, metadata_keys::READY_AT_KEY),
+        Some(12 * DAY)
+    );
+
+    // The lost key no longer signs for the account
+    let call = call(&s.app, symbol_short!("unstake"), vec![&s.env]);
+    assert_eq!(
+        check_auth(&s, &s.owner, vec![&s.env, call.clone()]),
+        Err(Ok(SessionError::UnknownSigner))
+    );
+    assert_eq!(check_auth(&s, &new_owner, vec![&s.env, call]), Ok(()));
+}
+
+#[test]
+fn test_owner_cancels_recovery() {
+    let s = setup();
+    let [first, second, _] = set_guardians(&s);
+    let stranger = Address::generate(&s.env);
+    let new_key = BytesN::from_array(&s.env, &[3; 32]);
+
+    let result = s.client.try_initiate_recovery(&stranger, &new_key);
+    assert_eq!(result, Err(Ok(SessionError::NotGuardian)));
+    s.client.initiate_recovery(&first, &new_key);
+    let result = s.client.try_initiate_recovery(&second, &new_key);
+    assert_eq!(result, Err(Ok(SessionError::RecoveryPending)));
+
+    s.client.cancel_recovery();
+    assert_standard_event(&s.env, topics::RECOVERY_CANCELLED, |_| true);
+    let result = s.client.try_approve_recovery(&second);
+    assert_eq!(result, Err(Ok(SessionError::NoRecovery)));
+    assert_eq!(s.client.owner_key(), public_key(&s.env, &s.owner));
+
+    let result = s.client.try_set_guardians(&GuardianConfig {
+        guardians: vec![&s.env, first.clone(), first],
+        threshold: 2,
+        delay: DAY,
+    });
+    assert_eq!(result, Err(Ok(SessionError::InvalidGuardians)));
+}
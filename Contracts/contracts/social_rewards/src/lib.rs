@@ -1,5 +1,14 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::epoch::EpochClock;
+use shared::events::{EventEmitter, MintEvent};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+mod welcome;
+
+pub use welcome::{Member, WelcomeBonusConfig};
 
 #[contract]
 pub struct SocialRewardsContract;
@@ -44,6 +53,10 @@ pub enum RewardError {
     Unauthorized = 5003,
     InsufficientBalance = 5004,
     EngagementNotFound = 5005,
+    BonusNotConfigured = 5006,
+    AlreadyClaimed = 5007,
+    AccountTooNew = 5008,
+    EpochCapReached = 5009,
 }
 
 impl From<RewardError> for soroban_sdk::Error {
@@ -59,8 +72,21 @@ impl From<&RewardError> for soroban_sdk::Error {
 }
 
 impl From<soroban_sdk::Error> for RewardError {
-    fn from(_error: soroban_sdk::Error) -> Self {
-        RewardError::Unauthorized
+    fn from(error: soroban_sdk::Error) -> Self {
+        if !error.is_type(soroban_sdk::xdr::ScErrorType::Contract) {
+            return RewardError::Unauthorized;
+        }
+        match error.get_code() {
+            5001 => RewardError::InvalidAmount,
+            5002 => RewardError::NotInitialized,
+            5004 => RewardError::InsufficientBalance,
+            5005 => RewardError::EngagementNotFound,
+            5006 => RewardError::BonusNotConfigured,
+            5007 => RewardError::AlreadyClaimed,
+            5008 => RewardError::AccountTooNew,
+            5009 => RewardError::EpochCapReached,
+            _ => RewardError::Unauthorized,
+        }
     }
 }
 
@@ -116,6 +142,9 @@ impl SocialRewardsContract {
             metadata,
         };
 
+        // First engagement starts the address's welcome bonus clock
+        welcome::register(&env, &user);
+
         // Store engagement
         let engagement_key = (symbol_short!("eng"), next_id);
         env.storage().persistent().set(&engagement_key, &engagement);
@@ -210,4 +239,119 @@ impl SocialRewardsContract {
 
         Ok(())
     }
+
+    /// Configure the welcome bonus (admin only). The contract pays it out of
+    /// its own balance of `config.token`.
+    pub fn set_welcome_bonus(
+        env: Env,
+        admin: Address,
+        config: WelcomeBonusConfig,
+    ) -> Result<(), RewardError> {
+        require_admin(&env, &admin)?;
+        if config.amount <= 0 || config.epoch_cap < config.amount || config.epoch_length == 0 {
+            return Err(RewardError::InvalidAmount);
+        }
+        welcome::set_config(&env, &config);
+        Ok(())
+    }
+
+    /// Claim the one-time welcome bonus. `user` must have been known for at
+    /// least `min_age_ledgers` ledgers. Returns the amount paid.
+    pub fn claim_welcome_bonus(env: Env, user: Address) -> Result<i128, RewardError> {
+        user.require_auth();
+        let config = welcome::config(&env).ok_or(RewardError::BonusNotConfigured)?;
+        let member = welcome::member(&env, &user).ok_or(RewardError::AccountTooNew)?;
+        if welcome::is_claimed(&env, member.index) {
+            return Err(RewardError::AlreadyClaimed);
+        }
+        if env.ledger().sequence() - member.first_seen < config.min_age_ledgers {
+            return Err(RewardError::AccountTooNew);
+        }
+        let clock = EpochClock {
+            genesis: 0,
+            length: config.epoch_length,
+        };
+        let epoch = clock.current_epoch(&env);
+        if welcome::paid_in(&env, epoch) + config.amount > config.epoch_cap {
+            return Err(RewardError::EpochCapReached);
+        }
+        let client = token::Client::new(&env, &config.token);
+        if client.balance(&env.current_contract_address()) < config.amount {
+            return Err(RewardError::InsufficientBalance);
+        }
+
+        welcome::mark_claimed(&env, member.index);
+        welcome::add_paid(&env, epoch, config.amount);
+        client.transfer(&env.current_contract_address(), &user, &config.amount);
+        EventEmitter::mint(
+            &env,
+            MintEvent {
+                to: user,
+                amount: config.amount,
+                timestamp: env.ledger().timestamp(),
+            },
+            Symbol::new(&env, "welcome_bonus"),
+        );
+        Ok(config.amount)
+    }
+
+    pub fn welcome_bonus_claimed(env: Env, user: Address) -> bool {
+        welcome::member(&env, &user).is_some_and(|m| welcome::is_claimed(&env, m.index))
+    }
+
+    pub fn welcome_bonus_config(env: Env) -> Option<WelcomeBonusConfig> {
+        welcome::config(&env)
+    }
+
+    /// When `user` was first seen and their claim bitmap index
+    pub fn member(env: Env, user: Address) -> Option<Member> {
+        welcome::member(&env, &user)
+    }
+
+    /// Welcome bonus paid out during `epoch`
+    pub fn welcome_bonus_paid(env: Env, epoch: u64) -> i128 {
+        welcome::paid_in(&env, epoch)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), RewardError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("admin"), &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), RewardError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
 }
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), RewardError> {
+    admin.require_auth();
+    let stored_admin: Address = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("admin"))
+        .ok_or(RewardError::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(RewardError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
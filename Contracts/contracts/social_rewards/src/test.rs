@@ -0,0 +1,128 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const EPOCH: u64 = 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: SocialRewardsContractClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1_000;
+        li.timestamp = 10 * EPOCH;
+    });
+
+    let admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(admin.clone());
+    let contract_id = env.register_contract(None, SocialRewardsContract);
+    let client = SocialRewardsContractClient::new(&env, &contract_id);
+    client.init(&admin);
+    // Treasury funding
+    StellarAssetClient::new(&env, &token_id).mint(&contract_id, &1_000);
+    client.set_welcome_bonus(
+        &admin,
+        &WelcomeBonusConfig {
+            token: token_id.clone(),
+            amount: 100,
+            min_age_ledgers: 500,
+            epoch_length: EPOCH,
+            epoch_cap: 200,
+        },
+    );
+
+    Setup {
+        client,
+        token: TokenClient::new(&env, &token_id),
+        env,
+        admin,
+    }
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_welcome_bonus_needs_account_age() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    let result = s.client.try_claim_welcome_bonus(&user);
+    assert_eq!(result, Err(Ok(RewardError::AccountTooNew)));
+
+    s.client
+        .record_engagement(&user, &symbol_short!("post"), &0);
+    assert_eq!(s.client.member(&user).unwrap().first_seen, 1_000);
+    advance_ledgers(&s.env, 499);
+    let result = s.client.try_claim_welcome_bonus(&user);
+    assert_eq!(result, Err(Ok(RewardError::AccountTooNew)));
+
+    advance_ledgers(&s.env, 1);
+    assert_eq!(s.client.claim_welcome_bonus(&user), 100);
+    assert_eq!(s.token.balance(&user), 100);
+    assert!(s.client.welcome_bonus_claimed(&user));
+
+    let captured = assert_standard_event(&s.env, topics::MINT, |_| true);
+    let event: MintEvent = payload(&captured);
+    assert_eq!(event.to, user);
+    assert_eq!(
+        captured.metadata::<Symbol>(&s.env, metadata_keys::REASON_KEY),
+        Some(Symbol::new(&s.env, "welcome_bonus"))
+    );
+
+    // Later engagements keep the original first-seen ledger
+    s.client
+        .record_engagement(&user, &symbol_short!("post"), &0);
+    assert_eq!(s.client.member(&user).unwrap().first_seen, 1_000);
+    let result = s.client.try_claim_welcome_bonus(&user);
+    assert_eq!(result, Err(Ok(RewardError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_welcome_bonus_capped_per_epoch() {
+    let s = setup();
+    let users = [
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    ];
+    for user in users.iter() {
+        s.client.record_engagement(user, &symbol_short!("join"), &0);
+    }
+    advance_ledgers(&s.env, 500);
+
+    s.client.claim_welcome_bonus(&users[0]);
+    s.client.claim_welcome_bonus(&users[1]);
+    let result = s.client.try_claim_welcome_bonus(&users[2]);
+    assert_eq!(result, Err(Ok(RewardError::EpochCapReached)));
+    assert_eq!(s.client.welcome_bonus_paid(&10), 200);
+    assert!(!s.client.welcome_bonus_claimed(&users[2]));
+
+    s.env.ledger().with_mut(|li| li.timestamp += EPOCH);
+    s.client.claim_welcome_bonus(&users[2]);
+    assert_eq!(s.client.member(&users[2]).unwrap().index, 2);
+}
+
+#[test]
+fn test_welcome_bonus_config_is_admin_only() {
+    let s = setup();
+    let mut config = s.client.welcome_bonus_config().unwrap();
+    let result = s
+        .client
+        .try_set_welcome_bonus(&Address::generate(&s.env), &config);
+    assert_eq!(result, Err(Ok(RewardError::Unauthorized)));
+
+    config.epoch_cap = config.amount - 1;
+    let result = s.client.try_set_welcome_bonus(&s.admin, &config);
+    assert_eq!(result, Err(Ok(RewardError::InvalidAmount)));
+}
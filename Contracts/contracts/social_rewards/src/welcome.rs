@@ -0,0 +1,109 @@
+//! One-time welcome bonus
+//!
+//! Soroban cannot see when an account was created, so account age is taken
+//! from the ledger sequence at which the address first showed up here (its
+//! first recorded engagement). Each new address also gets a sequential
+//! member index, and claims are tracked in a bitmap over those indexes, 128
+//! members per storage entry. Payouts come out of the bonus token this
+//! contract holds, which the treasury funds by transfer, and are capped per
+//! epoch so a burst of fresh accounts cannot drain it.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+const BITS_PER_WORD: u32 = 128;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WelcomeBonusConfig {
+    /// Token paid out
+    pub token: Address,
+    /// Bonus per address
+    pub amount: i128,
+    /// Ledgers an address must have been known for before claiming
+    pub min_age_ledgers: u32,
+    /// Length of a cap epoch in seconds
+    pub epoch_length: u64,
+    /// Most bonus paid out per epoch across all addresses
+    pub epoch_cap: i128,
+}
+
+/// When an address first showed up and its position in the claim bitmap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Member {
+    pub index: u32,
+    pub first_seen: u32,
+}
+
+pub fn config(env: &Env) -> Option<WelcomeBonusConfig> {
+    env.storage().persistent().get(&symbol_short!("wb_cfg"))
+}
+
+pub fn set_config(env: &Env, config: &WelcomeBonusConfig) {
+    env.storage()
+        .persistent()
+        .set(&symbol_short!("wb_cfg"), config);
+}
+
+/// Record `user` at the current ledger unless already known
+pub fn register(env: &Env, user: &Address) -> Member {
+    if let Some(member) = member(env, user) {
+        return member;
+    }
+    let next_key = symbol_short!("wb_next");
+    let index: u32 = env.storage().persistent().get(&next_key).unwrap_or(0);
+    env.storage().persistent().set(&next_key, &(index + 1));
+
+    let member = Member {
+        index,
+        first_seen: env.ledger().sequence(),
+    };
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("wb_mem"), user.clone()), &member);
+    member
+}
+
+pub fn member(env: &Env, user: &Address) -> Option<Member> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("wb_mem"), user.clone()))
+}
+
+pub fn is_claimed(env: &Env, index: u32) -> bool {
+    word(env, index / BITS_PER_WORD) & bit(index) != 0
+}
+
+pub fn mark_claimed(env: &Env, index: u32) {
+    let word_index = index / BITS_PER_WORD;
+    let updated = word(env, word_index) | bit(index);
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("wb_bits"), word_index), &updated);
+}
+
+/// Bonus paid out during `epoch`
+pub fn paid_in(env: &Env, epoch: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("wb_paid"), epoch))
+        .unwrap_or(0)
+}
+
+pub fn add_paid(env: &Env, epoch: u64, amount: i128) {
+    let total = paid_in(env, epoch) + amount;
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("wb_paid"), epoch), &total);
+}
+
+fn word(env: &Env, word_index: u32) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("wb_bits"), word_index))
+        .unwrap_or(0)
+}
+
+fn bit(index: u32) -> u128 {
+    1u128 << (index % BITS_PER_WORD)
+}
@@ -0,0 +1,398 @@
+//! Payment splitter
+//!
+//! Tokens sent to the contract are split between payees by weight, and
+//! payees pull their part whenever they like. Any token can be split: the
+//! first release of a token starts tracking it, and from then on every
+//! increase in the contract's balance is credited to the payees holding
+//! shares at the time it is noticed, through a per-token
+//! amount-per-share accumulator.
+//!
+//! Weights are fixed when the splitter is created without an admin.
+//! Otherwise the admin (typically governance) can replace them; what every
+//! payee earned under the old weights in tracked tokens is settled first
+//! and stays claimable. Balances of tokens that were never released before
+//! a change are split by the new weights.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, SplitReleasedEvent, TransferEvent};
+use shared::names::NameServiceClient;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol, Vec,
+};
+
+/// Fixed-point scale of the amount-per-share accumulators
+pub const ACC_SCALE: i128 = 1_000_000_000_000;
+pub const MAX_PAYEES: u32 = 50;
+pub const MAX_TOKENS: u32 = 20;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SplitterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    Immutable = 4,
+    InvalidShares = 5,
+    TooManyTokens = 6,
+    NothingToRelease = 7,
+    /// The payee name is unregistered or expired
+    NameNotFound = 8,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payee {
+    pub address: Address,
+    pub shares: u32,
+}
+
+/// Split accounting of one token
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenState {
+    /// Amount credited per share, scaled by `ACC_SCALE`
+    pub acc_per_share: i128,
+    /// Part of the contract's balance already credited to payees
+    pub accounted: i128,
+}
+
+/// A payee's position in one token
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PayeeAccount {
+    /// Accumulator value the payee was last credited up to
+    pub acc_snapshot: i128,
+    /// Credited and not yet released
+    pub owed: i128,
+    pub released: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Payees,
+    TotalShares,
+    Tokens,
+    Token(Address),
+    Account(Address, Address),
+}
+
+#[contract]
+pub struct PaymentSplitter;
+
+#[contractimpl]
+impl PaymentSplitter {
+    /// Create the splitter. Without an `admin` the weights can never change.
+    pub fn initialize(
+        env: Env,
+        admin: Option<Address>,
+        payees: Vec<Payee>,
+    ) -> Result<(), SplitterError> {
+        if env.storage().instance().has(&DataKey::Payees) {
+            return Err(SplitterError::AlreadyInitialized);
+        }
+        if let Some(admin) = admin {
+            env.storage().instance().set(&DataKey::Admin, &admin);
+        }
+        save_payees(&env, &payees)
+    }
+
+    /// Replace the payees and their weights (admin only)
+    pub fn set_payees(env: Env, admin: Address, payees: Vec<Payee>) -> Result<(), SplitterError> {
+        require_admin(&env, &admin)?;
+
+        // Credit everyone up to now under the old weights. Incoming payees
+        // are settled at zero shares so they only earn from here on.
+        let old = load_payees(&env)?;
+        for token in Self::tokens(env.clone()).iter() {
+            let state = sync(&env, &token)?;
+            for payee in old.iter() {
+                settle(&env, &token, &state, &payee.address, payee.shares);
+            }
+            for payee in payees.iter() {
+                settle(
+                    &env,
+                    &token,
+                    &state,
+                    &payee.address,
+                    shares_in(&old, &payee.address),
+                );
+            }
+        }
+        save_payees(&env, &payees)
+    }
+
+    /// Send `payee` everything it is owed in `token`. Anyone can call this;
+    /// funds only ever go to the payee.
+    pub fn release(env: Env, token: Address, payee: Address) -> Result<i128, SplitterError> {
+        Self::release_batch(env.clone(), token, Vec::from_array(&env, [payee]))
+    }
+
+    /// Release to the payee `name` currently resolves to in the name
+    /// service `names`
+    pub fn release_to_name(
+        env: Env,
+        token: Address,
+        names: Address,
+        name: Symbol,
+    ) -> Result<i128, SplitterError> {
+        let payee = NameServiceClient::new(&env, &names)
+            .resolve(&name)
+            .ok_or(SplitterError::NameNotFound)?;
+        Self::release(env, token, payee)
+    }
+
+    /// Send each of `payees` what it is owed in `token`. Returns the total
+    /// paid.
+    pub fn release_batch(
+        env: Env,
+        token: Address,
+        payees: Vec<Address>,
+    ) -> Result<i128, SplitterError> {
+        let current = load_payees(&env)?;
+        let mut state = sync(&env, &token)?;
+        let client = token::Client::new(&env, &token);
+        let this = env.current_contract_address();
+
+        let mut total = 0;
+        let mut paid = 0;
+        for payee in payees.iter() {
+            let mut account = settle(&env, &token, &state, &payee, shares_in(&current, &payee));
+            let amount = account.owed;
+            if amount <= 0 {
+                continue;
+            }
+            account.owed = 0;
+            account.released += amount;
+            save_account(&env, &token, &payee, &account);
+            client.transfer(&this, &payee, &amount);
+            EventEmitter::transfer(
+                &env,
+                TransferEvent {
+                    from: this.clone(),
+                    to: payee,
+                    token: token.clone(),
+                    amount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+            total += amount;
+            paid += 1;
+        }
+        if total == 0 {
+            return Err(SplitterError::NothingToRelease);
+        }
+        state.accounted -= total;
+        save_state(&env, &token, &state);
+
+        EventEmitter::split_released(
+            &env,
+            SplitReleasedEvent {
+                token,
+                payees: paid,
+                total,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(total)
+    }
+
+    /// What `payee` could release in `token` now
+    pub fn pending(env: Env, token: Address, payee: Address) -> i128 {
+        let Ok(payees) = load_payees(&env) else {
+            return 0;
+        };
+        let state = credited(&env, &token, load_state(&env, &token));
+        let account = load_account(&env, &token, &payee);
+        account.owed + accrued(&state, &account, shares_in(&payees, &payee))
+    }
+
+    /// Total `payee` has been paid in `token`
+    pub fn released(env: Env, token: Address, payee: Address) -> i128 {
+        load_account(&env, &token, &payee).released
+    }
+
+    pub fn payees(env: Env) -> Vec<Payee> {
+        load_payees(&env).unwrap_or(Vec::new(&env))
+    }
+
+    pub fn total_shares(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    /// Tokens whose incoming balance is being tracked
+    pub fn tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tokens)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// `None` when the weights are immutable
+    pub fn admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), SplitterError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), SplitterError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+/// Credit whatever `token` arrived since the last sync and store the
+/// result. Starts tracking the token on first use.
+fn sync(env: &Env, token: &Address) -> Result<TokenState, SplitterError> {
+    let key = DataKey::Token(token.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut tokens = PaymentSplitter::tokens(env.clone());
+        if tokens.len() >= MAX_TOKENS {
+            return Err(SplitterError::TooManyTokens);
+        }
+        tokens.push_back(token.clone());
+        env.storage().instance().set(&DataKey::Tokens, &tokens);
+    }
+    let state = credited(env, token, load_state(env, token));
+    save_state(env, token, &state);
+    Ok(state)
+}
+
+/// `state` with the balance received since it was stored credited per
+/// share. Whatever does not divide evenly is left for the next sync.
+fn credited(env: &Env, token: &Address, mut state: TokenState) -> TokenState {
+    let total = PaymentSplitter::total_shares(env.clone()) as i128;
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    let income = balance - state.accounted;
+    if income > 0 && total > 0 {
+        let per_share = income * ACC_SCALE / total;
+        state.acc_per_share += per_share;
+        state.accounted += per_share * total / ACC_SCALE;
+    }
+    state
+}
+
+/// Credit `payee` up to `state` at `shares` and store its account
+fn settle(
+    env: &Env,
+    token: &Address,
+    state: &TokenState,
+    payee: &Address,
+    shares: u32,
+) -> PayeeAccount {
+    let mut account = load_account(env, token, payee);
+    account.owed += accrued(state, &account, shares);
+    account.acc_snapshot = state.acc_per_share;
+    save_account(env, token, payee, &account);
+    account
+}
+
+fn accrued(state: &TokenState, account: &PayeeAccount, shares: u32) -> i128 {
+    shares as i128 * (state.acc_per_share - account.acc_snapshot) / ACC_SCALE
+}
+
+fn shares_in(payees: &Vec<Payee>, address: &Address) -> u32 {
+    payees
+        .iter()
+        .find(|p| p.address == *address)
+        .map_or(0, |p| p.shares)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), SplitterError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SplitterError::Immutable)?;
+    if stored != *admin {
+        return Err(SplitterError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn save_payees(env: &Env, payees: &Vec<Payee>) -> Result<(), SplitterError> {
+    if payees.is_empty() || payees.len() > MAX_PAYEES {
+        return Err(SplitterError::InvalidShares);
+    }
+    let mut total: u32 = 0;
+    for (i, payee) in payees.iter().enumerate() {
+        let duplicate = payees
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.address == payee.address);
+        if payee.shares == 0 || duplicate {
+            return Err(SplitterError::InvalidShares);
+        }
+        total = total
+            .checked_add(payee.shares)
+            .ok_or(SplitterError::InvalidShares)?;
+    }
+    env.storage().instance().set(&DataKey::Payees, payees);
+    env.storage().instance().set(&DataKey::TotalShares, &total);
+    Ok(())
+}
+
+fn load_payees(env: &Env) -> Result<Vec<Payee>, SplitterError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Payees)
+        .ok_or(SplitterError::NotInitialized)
+}
+
+fn load_state(env: &Env, token: &Address) -> TokenState {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(token.clone()))
+        .unwrap_or_default()
+}
+
+fn save_state(env: &Env, token: &Address, state: &TokenState) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Token(token.clone()), state);
+}
+
+fn load_account(env: &Env, token: &Address, payee: &Address) -> PayeeAccount {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Account(token.clone(), payee.clone()))
+        .unwrap_or_default()
+}
+
+fn save_account(env: &Env, token: &Address, payee: &Address, account: &PayeeAccount) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Account(token.clone(), payee.clone()), account);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::Address as _;
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload, standard_events};
+
+struct Setup {
+    env: Env,
+    client: PaymentSplitterClient<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup(governed: bool) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contract_id = env.register_contract(None, PaymentSplitter);
+    let client = PaymentSplitterClient::new(&env, &contract_id);
+    client.initialize(
+        &governed.then(|| admin.clone()),
+        &payees(&env, &[(&alice, 3), (&bob, 1)]),
+    );
+
+    Setup {
+        env,
+        client,
+        admin,
+        alice,
+        bob,
+    }
+}
+
+fn payees(env: &Env, weights: &[(&Address, u32)]) -> Vec<Payee> {
+    let mut payees = Vec::new(env);
+    for (address, shares) in weights {
+        payees.push_back(Payee {
+            address: (*address).clone(),
+            shares: *shares,
+        });
+    }
+    payees
+}
+
+fn fund(s: &Setup, token: &TestToken, amount: i128) {
+    token.mint(&s.client.address, amount);
+}
+
+#[test]
+fn test_split_across_tokens() {
+    let s = setup(false);
+    let usdc = stellar_asset(&s.env);
+    let xlm = stellar_asset(&s.env);
+    fund(&s, &usdc, 1_000);
+    fund(&s, &xlm, 400);
+
+    assert_eq!(s.client.pending(&usdc.address, &s.alice), 750);
+    assert_eq!(s.client.release(&usdc.address, &s.alice), 750);
+    assert_eq!(usdc.balance(&s.alice), 750);
+
+    // Income after a release is split on top of what is still owed
+    fund(&s, &usdc, 2_000);
+    assert_eq!(s.client.pending(&usdc.address, &s.alice), 1_500);
+    assert_eq!(s.client.pending(&usdc.address, &s.bob), 750);
+    assert_eq!(s.client.release(&usdc.address, &s.bob), 750);
+    assert_eq!(s.client.released(&usdc.address, &s.bob), 750);
+
+    assert_eq!(s.client.release(&xlm.address, &s.bob), 100);
+    assert_eq!(xlm.balance(&s.bob), 100);
+    assert_eq!(s.client.pending(&xlm.address, &s.alice), 300);
+    assert_eq!(s.client.tokens().len(), 2);
+
+    let result = s.client.try_release(&xlm.address, &s.bob);
+    assert_eq!(result, Err(Ok(SplitterError::NothingToRelease)));
+}
+
+#[test]
+fn test_batch_release_emits_transfers_and_summary() {
+    let s = setup(false);
+    let usdc = stellar_asset(&s.env);
+    fund(&s, &usdc, 801);
+
+    let batch = Vec::from_array(&s.env, [s.alice.clone(), s.bob.clone()]);
+    assert_eq!(s.client.release_batch(&usdc.address, &batch), 800);
+    assert_eq!(usdc.balance(&s.alice), 600);
+    assert_eq!(usdc.balance(&s.bob), 200);
+
+    let transfers = standard_events(&s.env)
+        .into_iter()
+        .filter(|captured| captured.event.topic == topics::TRANSFER)
+        .count();
+    assert_eq!(transfers, 2);
+    let released = assert_standard_event(&s.env, topics::SPLIT_RELEASED, |_| true);
+    let event: SplitReleasedEvent = payload(&released);
+    assert_eq!(event.payees, 2);
+    assert_eq!(event.total, 800);
+    assert_eq!(
+        released.metadata::<i128>(&s.env, metadata_keys::AMOUNT_KEY),
+        Some(800)
+    );
+
+    // Rounding dust stays in the contract
+    assert_eq!(usdc.balance(&s.client.address), 1);
+    let result = s.client.try_release_batch(&usdc.address, &batch);
+    assert_eq!(result, Err(Ok(SplitterError::NothingToRelease)));
+}
+
+#[test]
+fn test_governed_weights_settle_before_change() {
+    let s = setup(true);
+    let usdc = stellar_asset(&s.env);
+    let carol = Address::generate(&s.env);
+    fund(&s, &usdc, 400);
+    s.client.release(&usdc.address, &s.alice);
+    fund(&s, &usdc, 400);
+
+    let stranger = Address::generate(&s.env);
+    let next = payees(&s.env, &[(&s.alice, 1), (&carol, 1)]);
+    let result = s.client.try_set_payees(&stranger, &next);
+    assert_eq!(result, Err(Ok(SplitterError::Unauthorized)));
+    s.client.set_payees(&s.admin, &next);
+    assert_eq!(s.client.total_shares(), 2);
+
+    // Earlier income keeps its old split; bob can still collect its part
+    assert_eq!(s.client.pending(&usdc.address, &s.alice), 300);
+    assert_eq!(s.client.pending(&usdc.address, &s.bob), 200);
+    assert_eq!(s.client.pending(&usdc.address, &carol), 0);
+
+    fund(&s, &usdc, 600);
+    assert_eq!(s.client.pending(&usdc.address, &s.alice), 600);
+    assert_eq!(s.client.pending(&usdc.address, &s.bob), 200);
+    assert_eq!(s.client.release(&usdc.address, &carol), 300);
+
+    let immutable = setup(false);
+    let next = payees(&immutable.env, &[(&immutable.alice, 1)]);
+    let result = immutable.client.try_set_payees(&immutable.admin, &next);
+    assert_eq!(result, Err(Ok(SplitterError::Immutable)));
+    let result = s
+        .client
+        .try_set_payees(&s.admin, &payees(&s.env, &[(&s.alice, 1), (&s.alice, 1)]));
+    assert_eq!(result, Err(Ok(SplitterError::InvalidShares)));
+}
+
+#[test]
+fn test_release_to_name() {
+    let s = setup(false);
+    let usdc = stellar_asset(&s.env);
+    fund(&s, &usdc, 400);
+    let names =
+        names::NameServiceClient::new(&s.env, &s.env.register_contract(None, names::NameService));
+    let fees = names::FeeConfig {
+        token: usdc.address.clone(),
+        treasury: s.admin.clone(),
+        fee_per_year: 0,
+    };
+    names.initialize(&s.admin, &fees);
+    let name = soroban_sdk::symbol_short!("alice");
+
+    let result = s
+        .client
+        .try_release_to_name(&usdc.address, &names.address, &name);
+    assert_eq!(result, Err(Ok(SplitterError::NameNotFound)));
+    names.register(&s.alice, &name, &1);
+    assert_eq!(
+        s.client
+            .release_to_name(&usdc.address, &names.address, &name),
+        300
+    );
+    assert_eq!(usdc.balance(&s.alice), 300);
+}
@@ -0,0 +1,695 @@
+//! Collateral-backed synthetic stablecoin
+//!
+//! Users lock any mix of listed collateral assets in a personal vault and
+//! mint the stable asset against it. Each collateral asset has its own
+//! minimum collateral ratio: a vault may hold at most
+//! `Σ value / min_ratio` of debt. Prices are pushed by a designated oracle
+//! account, with the stable asset valued at exactly one unit.
+//!
+//! Debt is stored scaled by a global index that grows at the annual
+//! stability fee, so outstanding debt accrues the fee without touching each
+//! vault. Burning repays debt; `redeem` burns and hands back collateral of
+//! equal value in one step. Collateral of vaults under their minimum ratio
+//! is sold off in descending-price auctions (see `liquidation`).
+//!
+//! The contract must be the admin of the stable asset's token contract.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::auction::{Auction, AuctionParams};
+use shared::events::{BurnEvent, EventEmitter, MintEvent};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
+};
+
+mod liquidation;
+
+/// Fixed-point scale for oracle prices and health factors
+pub const PRICE_SCALE: i128 = 10_000_000;
+/// Fixed-point scale of the debt index
+pub const INDEX_SCALE: i128 = 1_000_000_000;
+/// Prices older than this are rejected
+pub const MAX_PRICE_AGE: u64 = 3_600;
+/// Upper bound on listed collateral assets, which every health check walks
+pub const MAX_COLLATERAL_ASSETS: u32 = 10;
+pub const BPS_DENOMINATOR: i128 = 10_000;
+pub const SECONDS_PER_YEAR: i128 = 31_536_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StablecoinError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    AssetNotListed = 4,
+    AssetAlreadyListed = 5,
+    InvalidAmount = 6,
+    InvalidConfig = 7,
+    PriceUnavailable = 8,
+    InsufficientCollateral = 9,
+    InsufficientBalance = 10,
+    PositionHealthy = 11,
+    TooManyAssets = 12,
+    AuctionNotFound = 13,
+    AuctionExpired = 14,
+    AuctionActive = 15,
+    PriceTooHigh = 16,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Oracle,
+    StableToken,
+    StabilityFee,
+    DebtIndex,
+    TotalDebtScaled,
+    Assets,
+    Config(Address),
+    Price(Address),
+    Collateral(Address, Address),
+    Debt(Address),
+    AuctionParams,
+    NextAuctionId,
+    Auction(u64),
+    /// Auction tabs left uncovered when their lot sold out
+    BadDebt,
+}
+
+/// Risk parameters of one collateral asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralConfig {
+    /// Collateral value required per unit of debt, e.g. 15 000 for 150%
+    pub min_ratio_bps: u32,
+}
+
+impl CollateralConfig {
+    pub fn is_valid(&self) -> bool {
+        self.min_ratio_bps as i128 > BPS_DENOMINATOR
+    }
+}
+
+/// Last price reported by the oracle, scaled by `PRICE_SCALE`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Debt index and when the stability fee was last applied to it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebtIndex {
+    pub index: i128,
+    pub last_accrual: u64,
+}
+
+/// Valuation of a vault
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultHealth {
+    /// Σ collateral value
+    pub collateral_value: i128,
+    /// Σ collateral value / min ratio: the most debt the vault may carry
+    pub debt_limit: i128,
+    /// Debt including accrued stability fees
+    pub debt: i128,
+    /// debt_limit / debt scaled by `PRICE_SCALE`; `i128::MAX` without debt
+    pub health_factor: i128,
+}
+
+#[contract]
+pub struct StablecoinContract;
+
+#[contractimpl]
+impl StablecoinContract {
+    /// `stable_token` is the asset minted against collateral; this contract
+    /// must be its admin. `stability_fee_bps` is charged annually on debt.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        stable_token: Address,
+        stability_fee_bps: u32,
+    ) -> Result<(), StablecoinError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(StablecoinError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::StableToken, &stable_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::StabilityFee, &stability_fee_bps);
+        env.storage().instance().set(
+            &DataKey::DebtIndex,
+            &DebtIndex {
+                index: INDEX_SCALE,
+                last_accrual: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Accept `asset` as collateral (admin only)
+    pub fn list_collateral(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        config: CollateralConfig,
+    ) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        if !config.is_valid() {
+            return Err(StablecoinError::InvalidConfig);
+        }
+        let key = DataKey::Config(asset.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(StablecoinError::AssetAlreadyListed);
+        }
+        let mut assets = Self::collateral_assets(env.clone());
+        if assets.len() >= MAX_COLLATERAL_ASSETS {
+            return Err(StablecoinError::TooManyAssets);
+        }
+
+        env.storage().persistent().set(&key, &config);
+        assets.push_back(asset);
+        env.storage().instance().set(&DataKey::Assets, &assets);
+        Ok(())
+    }
+
+    /// Update a listed asset's risk parameters (admin only)
+    pub fn set_collateral_config(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        config: CollateralConfig,
+    ) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        if !config.is_valid() {
+            return Err(StablecoinError::InvalidConfig);
+        }
+        load_config(&env, &asset)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Config(asset), &config);
+        Ok(())
+    }
+
+    /// Change the annual stability fee (admin only). Debt accrues at the old
+    /// fee up to now.
+    pub fn set_stability_fee(
+        env: Env,
+        admin: Address,
+        stability_fee_bps: u32,
+    ) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        accrue(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::StabilityFee, &stability_fee_bps);
+        Ok(())
+    }
+
+    /// Report the price of `asset` (oracle only)
+    pub fn set_price(
+        env: Env,
+        oracle: Address,
+        asset: Address,
+        price: i128,
+    ) -> Result<(), StablecoinError> {
+        oracle.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .ok_or(StablecoinError::NotInitialized)?;
+        if stored != oracle {
+            return Err(StablecoinError::Unauthorized);
+        }
+        if price <= 0 {
+            return Err(StablecoinError::InvalidAmount);
+        }
+        load_config(&env, &asset)?;
+
+        let data = PriceData {
+            price,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Price(asset), &data);
+        Ok(())
+    }
+
+    /// Lock `amount` of `asset` in the caller's vault
+    pub fn deposit_collateral(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), StablecoinError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(StablecoinError::InvalidAmount);
+        }
+        load_config(&env, &asset)?;
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &amount);
+        let key = DataKey::Collateral(user, asset);
+        set_balance(&env, &key, get_balance(&env, &key) + amount);
+        Ok(())
+    }
+
+    /// Take `amount` of `asset` out of the caller's vault, which must stay
+    /// above its minimum ratio
+    pub fn withdraw_collateral(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), StablecoinError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(StablecoinError::InvalidAmount);
+        }
+        release_collateral(&env, &user, &asset, amount, &user)?;
+        require_healthy(&env, &user)
+    }
+
+    /// Mint `amount` of the stable asset against the caller's vault
+    pub fn mint(env: Env, user: Address, amount: i128) -> Result<(), StablecoinError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(StablecoinError::InvalidAmount);
+        }
+        let index = accrue(&env);
+        let scaled = to_scaled_up(amount, index);
+        let key = DataKey::Debt(user.clone());
+        set_balance(&env, &key, get_balance(&env, &key) + scaled);
+        add_total_debt(&env, scaled);
+        require_healthy(&env, &user)?;
+
+        token::StellarAssetClient::new(&env, &stable_token(&env)?).mint(&user, &amount);
+        EventEmitter::mint(
+            &env,
+            MintEvent {
+                to: user,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+            symbol_short!("borrow"),
+        );
+        Ok(())
+    }
+
+    /// Burn up to `amount` of the caller's stable asset against their debt.
+    /// Returns the amount burned.
+    pub fn burn(env: Env, user: Address, amount: i128) -> Result<i128, StablecoinError> {
+        user.require_auth();
+        let burned = repay(&env, &user, &user, amount)?;
+        EventEmitter::burn(
+            &env,
+            BurnEvent {
+                from: user,
+                amount: burned,
+                timestamp: env.ledger().timestamp(),
+            },
+            symbol_short!("repay"),
+        );
+        Ok(burned)
+    }
+
+    /// Burn up to `amount` of the caller's stable asset against their debt
+    /// and take collateral of the same value in `asset` out of their vault.
+    /// Returns the collateral released.
+    pub fn redeem(
+        env: Env,
+        user: Address,
+        amount: i128,
+        asset: Address,
+    ) -> Result<i128, StablecoinError> {
+        user.require_auth();
+        let burned = repay(&env, &user, &user, amount)?;
+        let released = burned * PRICE_SCALE / read_price(&env, &asset)?;
+        if released <= 0 {
+            return Err(StablecoinError::InvalidAmount);
+        }
+        release_collateral(&env, &user, &asset, released, &user)?;
+        require_healthy(&env, &user)?;
+
+        EventEmitter::burn(
+            &env,
+            BurnEvent {
+                from: user,
+                amount: burned,
+                timestamp: env.ledger().timestamp(),
+            },
+            symbol_short!("redeem"),
+        );
+        Ok(released)
+    }
+
+    /// Put an under-collateralized vault's whole `asset` collateral up for
+    /// auction against the debt it covers. Returns the auction id.
+    pub fn start_auction(env: Env, user: Address, asset: Address) -> Result<u64, StablecoinError> {
+        liquidation::start(&env, &user, &asset)
+    }
+
+    /// Burn stable tokens for up to `max_collateral` of an auction's lot at
+    /// its current price, failing if that price exceeds `max_price`.
+    /// Returns `(collateral, paid)`.
+    pub fn take(
+        env: Env,
+        taker: Address,
+        auction_id: u64,
+        max_collateral: i128,
+        max_price: i128,
+    ) -> Result<(i128, i128), StablecoinError> {
+        taker.require_auth();
+        liquidation::take(&env, &taker, auction_id, max_collateral, max_price)
+    }
+
+    /// Restart an expired auction from the current oracle price
+    pub fn reset_auction(env: Env, auction_id: u64) -> Result<(), StablecoinError> {
+        liquidation::reset(&env, auction_id)
+    }
+
+    pub fn set_auction_params(
+        env: Env,
+        admin: Address,
+        params: AuctionParams,
+    ) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        if !params.is_valid() {
+            return Err(StablecoinError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuctionParams, &params);
+        Ok(())
+    }
+
+    // ---- views ----
+
+    /// Debt of `user` including accrued stability fees
+    pub fn debt_of(env: Env, user: Address) -> i128 {
+        let scaled = get_balance(&env, &DataKey::Debt(user));
+        from_scaled(scaled, current_index(&env))
+    }
+
+    /// Debt across all vaults; the excess over the stable supply is fee
+    /// revenue
+    pub fn total_debt(env: Env) -> i128 {
+        let scaled: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalDebtScaled)
+            .unwrap_or(0);
+        from_scaled(scaled, current_index(&env))
+    }
+
+    pub fn collateral_of(env: Env, user: Address, asset: Address) -> i128 {
+        get_balance(&env, &DataKey::Collateral(user, asset))
+    }
+
+    pub fn vault_health(env: Env, user: Address) -> Result<VaultHealth, StablecoinError> {
+        vault_health(&env, &user)
+    }
+
+    pub fn collateral_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Assets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn collateral_config(
+        env: Env,
+        asset: Address,
+    ) -> Result<CollateralConfig, StablecoinError> {
+        load_config(&env, &asset)
+    }
+
+    pub fn stability_fee(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StabilityFee)
+            .unwrap_or(0)
+    }
+
+    pub fn get_price(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().persistent().get(&DataKey::Price(asset))
+    }
+
+    pub fn auction(env: Env, auction_id: u64) -> Result<Auction, StablecoinError> {
+        liquidation::load(&env, auction_id)
+    }
+
+    /// Current price per unit of an auction's collateral
+    pub fn auction_price(env: Env, auction_id: u64) -> Result<i128, StablecoinError> {
+        let auction = liquidation::load(&env, auction_id)?;
+        Ok(auction.price(&liquidation::params(&env), env.ledger().timestamp()))
+    }
+
+    pub fn auction_params(env: Env) -> AuctionParams {
+        liquidation::params(&env)
+    }
+
+    /// Debt auctions failed to cover
+    pub fn bad_debt(env: Env) -> i128 {
+        liquidation::bad_debt(&env)
+    }
+
+    // ---- admin transfer ----
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), StablecoinError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), StablecoinError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(StablecoinError::NotInitialized)?;
+    if stored != *admin {
+        return Err(StablecoinError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn stable_token(env: &Env) -> Result<Address, StablecoinError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::StableToken)
+        .ok_or(StablecoinError::NotInitialized)
+}
+
+fn load_config(env: &Env, asset: &Address) -> Result<CollateralConfig, StablecoinError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Config(asset.clone()))
+        .ok_or(StablecoinError::AssetNotListed)
+}
+
+/// Fresh oracle price for `asset`
+fn read_price(env: &Env, asset: &Address) -> Result<i128, StablecoinError> {
+    let data: PriceData = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Price(asset.clone()))
+        .ok_or(StablecoinError::PriceUnavailable)?;
+    if env.ledger().timestamp() > data.timestamp + MAX_PRICE_AGE {
+        return Err(StablecoinError::PriceUnavailable);
+    }
+    Ok(data.price)
+}
+
+fn get_balance(env: &Env, key: &DataKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn set_balance(env: &Env, key: &DataKey, value: i128) {
+    if value == 0 {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, &value);
+    }
+}
+
+fn add_total_debt(env: &Env, delta: i128) {
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalDebtScaled)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalDebtScaled, &(total + delta));
+}
+
+/// Debt index grown by the stability fee up to now, without storing it
+fn current_index(env: &Env) -> i128 {
+    let Some(state) = env
+        .storage()
+        .instance()
+        .get::<_, DebtIndex>(&DataKey::DebtIndex)
+    else {
+        return INDEX_SCALE;
+    };
+    let elapsed = env.ledger().timestamp().saturating_sub(state.last_accrual) as i128;
+    let fee = StablecoinContract::stability_fee(env.clone()) as i128;
+    let growth = INDEX_SCALE * fee * elapsed / (BPS_DENOMINATOR * SECONDS_PER_YEAR);
+    state.index * (INDEX_SCALE + growth) / INDEX_SCALE
+}
+
+/// Apply the stability fee up to now and return the new index
+fn accrue(env: &Env) -> i128 {
+    let index = current_index(env);
+    env.storage().instance().set(
+        &DataKey::DebtIndex,
+        &DebtIndex {
+            index,
+            last_accrual: env.ledger().timestamp(),
+        },
+    );
+    index
+}
+
+/// Scaled units for `amount`, rounded up (used when adding debt)
+fn to_scaled_up(amount: i128, index: i128) -> i128 {
+    (amount * INDEX_SCALE + index - 1) / index
+}
+
+fn from_scaled(scaled: i128, index: i128) -> i128 {
+    (scaled * index + INDEX_SCALE - 1) / INDEX_SCALE
+}
+
+/// Burn up to `amount` of `payer`'s stable asset against `user`'s debt.
+/// Returns the amount burned.
+fn repay(
+    env: &Env,
+    payer: &Address,
+    user: &Address,
+    amount: i128,
+) -> Result<i128, StablecoinError> {
+    let burned = reduce_debt(env, user, amount)?;
+    token::Client::new(env, &stable_token(env)?).burn(payer, &burned);
+    Ok(burned)
+}
+
+/// Remove up to `amount` of `user`'s debt. Returns the amount removed.
+fn reduce_debt(env: &Env, user: &Address, amount: i128) -> Result<i128, StablecoinError> {
+    if amount <= 0 {
+        return Err(StablecoinError::InvalidAmount);
+    }
+    let index = accrue(env);
+    let key = DataKey::Debt(user.clone());
+    let scaled = get_balance(env, &key);
+    let debt = from_scaled(scaled, index);
+    if debt == 0 {
+        return Err(StablecoinError::InsufficientBalance);
+    }
+
+    let reduced = amount.min(debt);
+    let reduced_scaled = if reduced == debt {
+        scaled
+    } else {
+        reduced * INDEX_SCALE / index
+    };
+    set_balance(env, &key, scaled - reduced_scaled);
+    add_total_debt(env, -reduced_scaled);
+    Ok(reduced)
+}
+
+/// Move `amount` of `asset` out of `user`'s vault to `to`
+fn release_collateral(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+    to: &Address,
+) -> Result<(), StablecoinError> {
+    let key = DataKey::Collateral(user.clone(), asset.clone());
+    let balance = get_balance(env, &key);
+    if balance < amount {
+        return Err(StablecoinError::InsufficientBalance);
+    }
+    set_balance(env, &key, balance - amount);
+    token::Client::new(env, asset).transfer(&env.current_contract_address(), to, &amount);
+    Ok(())
+}
+
+fn vault_health(env: &Env, user: &Address) -> Result<VaultHealth, StablecoinError> {
+    let mut collateral_value = 0;
+    let mut debt_limit = 0;
+    for asset in StablecoinContract::collateral_assets(env.clone()).iter() {
+        let amount = get_balance(env, &DataKey::Collateral(user.clone(), asset.clone()));
+        if amount == 0 {
+            continue;
+        }
+        let config = load_config(env, &asset)?;
+        let value = amount * read_price(env, &asset)? / PRICE_SCALE;
+        collateral_value += value;
+        debt_limit += value * BPS_DENOMINATOR / config.min_ratio_bps as i128;
+    }
+
+    let debt = StablecoinContract::debt_of(env.clone(), user.clone());
+    let health_factor = if debt == 0 {
+        i128::MAX
+    } else {
+        debt_limit * PRICE_SCALE / debt
+    };
+    Ok(VaultHealth {
+        collateral_value,
+        debt_limit,
+        debt,
+        health_factor,
+    })
+}
+
+fn require_healthy(env: &Env, user: &Address) -> Result<(), StablecoinError> {
+    if get_balance(env, &DataKey::Debt(user.clone())) == 0 {
+        return Ok(());
+    }
+    if vault_health(env, user)?.health_factor < PRICE_SCALE {
+        return Err(StablecoinError::InsufficientCollateral);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,204 @@
+//! Liquidation of under-collateralized vaults by Dutch auction
+//!
+//! Once a vault's debt exceeds its limit, anyone may put its holding of one
+//! collateral asset up for auction. The lot leaves the vault together with
+//! as much debt as the oracle says it covers (the tab). Keepers then burn
+//! stable tokens to buy the lot, in partial fills, at a price that starts
+//! above the oracle price and decays along the curve in `AuctionParams`.
+//! When the tab is covered, unsold collateral goes back to the vault; when
+//! the lot runs out first, the rest of the tab is recorded as bad debt.
+//! Auctions that pass their tail are restarted from a fresh oracle price.
+
+use shared::auction::{Auction, AuctionParams};
+use shared::events::{AuctionSettleEvent, AuctionStartEvent, AuctionTakeEvent, EventEmitter};
+use soroban_sdk::{token, Address, Env};
+
+use crate::{
+    get_balance, read_price, reduce_debt, set_balance, stable_token, vault_health, DataKey,
+    StablecoinError, PRICE_SCALE,
+};
+
+/// Auction `user`'s whole `asset` collateral. Returns the auction id.
+pub fn start(env: &Env, user: &Address, asset: &Address) -> Result<u64, StablecoinError> {
+    let health = vault_health(env, user)?;
+    if health.health_factor >= PRICE_SCALE {
+        return Err(StablecoinError::PositionHealthy);
+    }
+    let collateral_key = DataKey::Collateral(user.clone(), asset.clone());
+    let lot = get_balance(env, &collateral_key);
+    if lot == 0 {
+        return Err(StablecoinError::InsufficientCollateral);
+    }
+
+    let price = read_price(env, asset)?;
+    let tab = reduce_debt(env, user, (lot * price / PRICE_SCALE).max(1))?;
+    set_balance(env, &collateral_key, 0);
+
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextAuctionId)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextAuctionId, &(id + 1));
+
+    let params = params(env);
+    let auction = Auction {
+        borrower: user.clone(),
+        collateral_asset: asset.clone(),
+        debt_asset: stable_token(env)?,
+        lot,
+        tab,
+        start_price: params.start_price(price),
+        started_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), &auction);
+    emit_start(env, id, &auction, &params);
+    Ok(id)
+}
+
+/// Restart an auction that ran past its tail from the current oracle price
+pub fn reset(env: &Env, id: u64) -> Result<(), StablecoinError> {
+    let mut auction = load(env, id)?;
+    let params = params(env);
+    let now = env.ledger().timestamp();
+    if !auction.expired(&params, now) {
+        return Err(StablecoinError::AuctionActive);
+    }
+    auction.start_price = params.start_price(read_price(env, &auction.collateral_asset)?);
+    auction.started_at = now;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), &auction);
+    emit_start(env, id, &auction, &params);
+    Ok(())
+}
+
+/// Buy up to `max_collateral` of auction `id`'s lot for stable tokens,
+/// provided the current price is at most `max_price`. Returns
+/// `(collateral, paid)`.
+pub fn take(
+    env: &Env,
+    taker: &Address,
+    id: u64,
+    max_collateral: i128,
+    max_price: i128,
+) -> Result<(i128, i128), StablecoinError> {
+    if max_collateral <= 0 {
+        return Err(StablecoinError::InvalidAmount);
+    }
+    let mut auction = load(env, id)?;
+    let params = params(env);
+    let now = env.ledger().timestamp();
+    if auction.expired(&params, now) {
+        return Err(StablecoinError::AuctionExpired);
+    }
+    let price = auction.price(&params, now);
+    if price > max_price {
+        return Err(StablecoinError::PriceTooHigh);
+    }
+    let (collateral, paid) = auction.fill(price, PRICE_SCALE, max_collateral);
+    if collateral <= 0 || paid <= 0 {
+        return Err(StablecoinError::InvalidAmount);
+    }
+
+    token::Client::new(env, &auction.debt_asset).burn(taker, &paid);
+    token::Client::new(env, &auction.collateral_asset).transfer(
+        &env.current_contract_address(),
+        taker,
+        &collateral,
+    );
+    auction.lot -= collateral;
+    auction.tab -= paid;
+
+    EventEmitter::auction_taken(
+        env,
+        AuctionTakeEvent {
+            auction_id: id,
+            taker: taker.clone(),
+            collateral,
+            paid,
+            price,
+            lot: auction.lot,
+            tab: auction.tab,
+            timestamp: now,
+        },
+    );
+
+    if auction.is_done() {
+        settle(env, id, &auction);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(id), &auction);
+    }
+    Ok((collateral, paid))
+}
+
+pub fn load(env: &Env, id: u64) -> Result<Auction, StablecoinError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Auction(id))
+        .ok_or(StablecoinError::AuctionNotFound)
+}
+
+pub fn params(env: &Env) -> AuctionParams {
+    env.storage()
+        .instance()
+        .get(&DataKey::AuctionParams)
+        .unwrap_or_default()
+}
+
+pub fn bad_debt(env: &Env) -> i128 {
+    env.storage().instance().get(&DataKey::BadDebt).unwrap_or(0)
+}
+
+/// Close a finished auction: hand unsold collateral back to the vault and
+/// book any uncovered tab as bad debt
+fn settle(env: &Env, id: u64, auction: &Auction) {
+    let returned = auction.lot.max(0);
+    if returned > 0 {
+        let key = DataKey::Collateral(auction.borrower.clone(), auction.collateral_asset.clone());
+        set_balance(env, &key, get_balance(env, &key) + returned);
+    }
+    let uncovered = auction.tab.max(0);
+    if uncovered > 0 {
+        env.storage()
+            .instance()
+            .set(&DataKey::BadDebt, &(bad_debt(env) + uncovered));
+    }
+    env.storage().persistent().remove(&DataKey::Auction(id));
+
+    EventEmitter::auction_closed(
+        env,
+        AuctionSettleEvent {
+            auction_id: id,
+            borrower: auction.borrower.clone(),
+            returned,
+            bad_debt: uncovered,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+fn emit_start(env: &Env, id: u64, auction: &Auction, params: &AuctionParams) {
+    EventEmitter::auction_started(
+        env,
+        AuctionStartEvent {
+            auction_id: id,
+            borrower: auction.borrower.clone(),
+            collateral_asset: auction.collateral_asset.clone(),
+            debt_asset: auction.debt_asset.clone(),
+            lot: auction.lot,
+            tab: auction.tab,
+            start_price: auction.start_price,
+            step: params.step,
+            cut_bps: params.cut_bps,
+            tail: params.tail,
+            timestamp: auction.started_at,
+        },
+    );
+}
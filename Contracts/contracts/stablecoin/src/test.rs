@@ -0,0 +1,271 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const YEAR: u64 = SECONDS_PER_YEAR as u64;
+
+struct Setup {
+    env: Env,
+    client: StablecoinContractClient<'static>,
+    stable: TokenClient<'static>,
+    xlm: TokenClient<'static>,
+    btc: TokenClient<'static>,
+    admin: Address,
+    oracle: Address,
+    user: Address,
+}
+
+fn config(min_ratio_bps: u32) -> CollateralConfig {
+    CollateralConfig { min_ratio_bps }
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, StablecoinContract);
+    let client = StablecoinContractClient::new(&env, &contract_id);
+    let stable_id = env.register_stellar_asset_contract(contract_id.clone());
+    let xlm_id = env.register_stellar_asset_contract(admin.clone());
+    let btc_id = env.register_stellar_asset_contract(admin.clone());
+    StellarAssetClient::new(&env, &xlm_id).mint(&user, &1_000_000);
+    StellarAssetClient::new(&env, &btc_id).mint(&user, &1_000);
+
+    client.initialize(&admin, &oracle, &stable_id, &500);
+    client.list_collateral(&admin, &xlm_id, &config(20_000));
+    client.list_collateral(&admin, &btc_id, &config(12_500));
+    // 1 XLM = 0.1, 1 BTC = 100
+    client.set_price(&oracle, &xlm_id, &(PRICE_SCALE / 10));
+    client.set_price(&oracle, &btc_id, &(100 * PRICE_SCALE));
+
+    Setup {
+        client,
+        stable: TokenClient::new(&env, &stable_id),
+        xlm: TokenClient::new(&env, &xlm_id),
+        btc: TokenClient::new(&env, &btc_id),
+        env,
+        admin,
+        oracle,
+        user,
+    }
+}
+
+#[test]
+fn test_mint_against_collateral_basket() {
+    let s = setup();
+    s.client
+        .deposit_collateral(&s.user, &s.xlm.address, &100_000);
+    s.client.deposit_collateral(&s.user, &s.btc.address, &100);
+
+    // 10 000 of XLM at 200% and 10 000 of BTC at 125%
+    let health = s.client.vault_health(&s.user);
+    assert_eq!(health.collateral_value, 20_000);
+    assert_eq!(health.debt_limit, 13_000);
+
+    s.client.mint(&s.user, &13_000);
+    assert_eq!(s.stable.balance(&s.user), 13_000);
+    assert_eq!(s.client.debt_of(&s.user), 13_000);
+    assert_eq!(
+        s.client.try_mint(&s.user, &1),
+        Err(Ok(StablecoinError::InsufficientCollateral))
+    );
+    assert_eq!(
+        s.client
+            .try_withdraw_collateral(&s.user, &s.btc.address, &1),
+        Err(Ok(StablecoinError::InsufficientCollateral))
+    );
+
+    let captured = assert_standard_event(&s.env, topics::MINT, |_| true);
+    let event: shared::events::MintEvent = payload(&captured);
+    assert_eq!(event.amount, 13_000);
+}
+
+#[test]
+fn test_stability_fee_accrues_on_debt() {
+    let s = setup();
+    s.client.deposit_collateral(&s.user, &s.btc.address, &100);
+    s.client.mint(&s.user, &4_000);
+
+    s.env.ledger().with_mut(|l| l.timestamp += YEAR);
+    // 5% a year
+    assert_eq!(s.client.debt_of(&s.user), 4_200);
+    assert_eq!(s.client.total_debt(), 4_200);
+
+    // The fee cannot be repaid from the minted tokens alone
+    assert_eq!(s.client.burn(&s.user, &4_000), 4_000);
+    // Partial repayments round in the protocol's favour
+    let remaining = s.client.debt_of(&s.user);
+    assert!((200..=201).contains(&remaining));
+    assert_eq!(
+        s.client
+            .try_withdraw_collateral(&s.user, &s.btc.address, &100),
+        Err(Ok(StablecoinError::InsufficientCollateral))
+    );
+    assert_standard_event(&s.env, topics::BURN, |_| true);
+}
+
+#[test]
+fn test_redeem_returns_equal_value_collateral() {
+    let s = setup();
+    s.client
+        .deposit_collateral(&s.user, &s.xlm.address, &100_000);
+    s.client.mint(&s.user, &5_000);
+
+    let released = s.client.redeem(&s.user, &2_000, &s.xlm.address);
+    assert_eq!(released, 20_000);
+    assert_eq!(s.stable.balance(&s.user), 3_000);
+    assert_eq!(s.client.debt_of(&s.user), 3_000);
+    assert_eq!(s.client.collateral_of(&s.user, &s.xlm.address), 80_000);
+    assert_eq!(s.xlm.balance(&s.user), 920_000);
+
+    // Fully repaid vaults can withdraw everything
+    s.client.burn(&s.user, &3_000);
+    s.client
+        .withdraw_collateral(&s.user, &s.xlm.address, &80_000);
+    assert_eq!(s.xlm.balance(&s.user), 1_000_000);
+}
+
+#[test]
+fn test_auction_after_price_drop() {
+    let s = setup();
+    s.client.deposit_collateral(&s.user, &s.btc.address, &100);
+    s.client.mint(&s.user, &8_000);
+
+    let keeper = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_start_auction(&s.user, &s.btc.address),
+        Err(Ok(StablecoinError::PositionHealthy))
+    );
+
+    // BTC falls to 90: the limit drops to 7 200 against 8 000 of debt
+    s.client
+        .set_price(&s.oracle, &s.btc.address, &(90 * PRICE_SCALE));
+    let id = s.client.start_auction(&s.user, &s.btc.address);
+    let auction = s.client.auction(&id);
+    assert_eq!(auction.lot, 100);
+    assert_eq!(auction.tab, 8_000);
+    // Opens 10% above the oracle price
+    assert_eq!(auction.start_price, 99 * PRICE_SCALE);
+    assert_eq!(s.client.debt_of(&s.user), 0);
+    assert_eq!(s.client.collateral_of(&s.user, &s.btc.address), 0);
+
+    let captured = assert_standard_event(&s.env, topics::AUCTION_STARTED, |_| true);
+    let event: shared::events::AuctionStartEvent = payload(&captured);
+    assert_eq!(event.lot, 100);
+    assert_eq!(event.step, AuctionParams::default().step);
+    assert_eq!(event.cut_bps, AuctionParams::default().cut_bps);
+
+    // Ten minutes of 1% cuts
+    s.env.ledger().with_mut(|l| l.timestamp += 600);
+    let price = s.client.auction_price(&id);
+    assert!(price < 90 * PRICE_SCALE && price > 89 * PRICE_SCALE);
+    assert_eq!(
+        s.client.try_take(&keeper, &id, &40, &(89 * PRICE_SCALE)),
+        Err(Ok(StablecoinError::PriceTooHigh))
+    );
+
+    s.stable.transfer(&s.user, &keeper, &8_000);
+    let (collateral, paid) = s.client.take(&keeper, &id, &40, &price);
+    assert_eq!(collateral, 40);
+    assert_eq!(paid, 40 * price / PRICE_SCALE);
+    assert_eq!(s.btc.balance(&keeper), 40);
+    let auction = s.client.auction(&id);
+    assert_eq!(auction.lot, 60);
+    assert_eq!(auction.tab, 8_000 - paid);
+
+    // Taking everything only buys what covers the tab; the rest goes home
+    let (collateral, paid_rest) = s.client.take(&keeper, &id, &100, &price);
+    assert_eq!(paid + paid_rest, 8_000);
+    let returned = 60 - collateral;
+    assert!(returned > 0);
+    assert_eq!(s.client.collateral_of(&s.user, &s.btc.address), returned);
+    assert_eq!(s.stable.balance(&keeper), 0);
+    assert_eq!(s.client.bad_debt(), 0);
+    assert_eq!(
+        s.client.try_auction(&id),
+        Err(Ok(StablecoinError::AuctionNotFound))
+    );
+
+    let captured = assert_standard_event(&s.env, topics::AUCTION_CLOSED, |_| true);
+    let event: shared::events::AuctionSettleEvent = payload(&captured);
+    assert_eq!(event.returned, returned);
+    assert_eq!(event.bad_debt, 0);
+}
+
+#[test]
+fn test_expired_auction_reset_and_bad_debt() {
+    let s = setup();
+    s.client.deposit_collateral(&s.user, &s.btc.address, &100);
+    s.client.mint(&s.user, &8_000);
+    s.client
+        .set_price(&s.oracle, &s.btc.address, &(50 * PRICE_SCALE));
+    let id = s.client.start_auction(&s.user, &s.btc.address);
+    // Only the 5 000 the lot is worth leaves the vault as the tab
+    assert_eq!(s.client.auction(&id).tab, 5_000);
+    assert_eq!(s.client.debt_of(&s.user), 3_000);
+
+    let keeper = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_reset_auction(&id),
+        Err(Ok(StablecoinError::AuctionActive))
+    );
+    let params = s.client.auction_params();
+    s.env.ledger().with_mut(|l| l.timestamp += params.tail + 1);
+    assert_eq!(
+        s.client.try_take(&keeper, &id, &100, &i128::MAX),
+        Err(Ok(StablecoinError::AuctionExpired))
+    );
+
+    // BTC keeps falling; the restart opens from the new price
+    s.client
+        .set_price(&s.oracle, &s.btc.address, &(40 * PRICE_SCALE));
+    s.client.reset_auction(&id);
+    assert_eq!(s.client.auction(&id).start_price, 44 * PRICE_SCALE);
+
+    // The whole lot sells for less than the tab
+    s.stable.transfer(&s.user, &keeper, &5_000);
+    let (collateral, paid) = s.client.take(&keeper, &id, &100, &i128::MAX);
+    assert_eq!(collateral, 100);
+    assert_eq!(paid, 4_400);
+    assert_eq!(s.client.bad_debt(), 600);
+    assert_eq!(s.client.collateral_of(&s.user, &s.btc.address), 0);
+}
+
+#[test]
+fn test_admin_and_oracle_checks() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_list_collateral(&other, &other, &config(15_000)),
+        Err(Ok(StablecoinError::Unauthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_list_collateral(&s.admin, &other, &config(10_000)),
+        Err(Ok(StablecoinError::InvalidConfig))
+    );
+    assert_eq!(
+        s.client.try_set_price(&other, &s.xlm.address, &1),
+        Err(Ok(StablecoinError::Unauthorized))
+    );
+
+    // Stale prices block minting
+    s.client.deposit_collateral(&s.user, &s.btc.address, &10);
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp += MAX_PRICE_AGE + 1);
+    assert_eq!(
+        s.client.try_mint(&s.user, &1),
+        Err(Ok(StablecoinError::PriceUnavailable))
+    );
+}
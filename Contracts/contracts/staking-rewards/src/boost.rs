@@ -0,0 +1,151 @@
+//! Rewards boost for holders of listed NFT collections
+//!
+//! Each listed collection carries a multiplier. A staker holding at least
+//! one token of a collection has their claimed rewards multiplied by it,
+//! by the highest one if they hold several. Holdings are read from the
+//! collections at claim time and the result is cached per staker for
+//! `cache_ttl` seconds, so frequent claims don't cost one cross-contract
+//! call per collection each time. Changing the list invalidates every
+//! cached multiplier.
+
+use crate::math::BPS_DENOMINATOR;
+use crate::{storage_keys, ContractError};
+use shared::math::Checked;
+use shared::nft::NftBalanceClient;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Multiplier without any boost
+pub const NO_BOOST_BPS: u32 = BPS_DENOMINATOR as u32;
+/// Highest multiplier a collection may carry (3x)
+pub const MAX_BOOST_BPS: u32 = 30_000;
+pub const DEFAULT_CACHE_TTL: u64 = 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoostCollection {
+    pub collection: Address,
+    /// Rewards multiplier in basis points, above `NO_BOOST_BPS`
+    pub multiplier_bps: u32,
+}
+
+/// Multiplier last read for a staker
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedBoost {
+    pub multiplier_bps: u32,
+    pub checked_at: u64,
+    /// List version the multiplier was read against
+    pub version: u32,
+}
+
+pub fn collections(env: &Env) -> Vec<BoostCollection> {
+    env.storage()
+        .instance()
+        .get(&storage_keys::BOOSTS)
+        .unwrap_or(Vec::new(env))
+}
+
+/// List `collection` with `multiplier_bps`, replacing any earlier entry
+pub fn set_collection(env: &Env, collection: Address, multiplier_bps: u32) {
+    let mut list = collections(env);
+    let entry = BoostCollection {
+        collection,
+        multiplier_bps,
+    };
+    match list.iter().position(|b| b.collection == entry.collection) {
+        Some(i) => list.set(i as u32, entry),
+        None => list.push_back(entry),
+    }
+    save(env, &list);
+}
+
+/// Unlist `collection`. Returns `false` if it was not listed.
+pub fn remove_collection(env: &Env, collection: &Address) -> bool {
+    let mut list = collections(env);
+    let Some(i) = list.iter().position(|b| b.collection == *collection) else {
+        return false;
+    };
+    list.remove(i as u32);
+    save(env, &list);
+    true
+}
+
+pub fn cache_ttl(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&storage_keys::BOOST_TTL)
+        .unwrap_or(DEFAULT_CACHE_TTL)
+}
+
+pub fn set_cache_ttl(env: &Env, seconds: u64) {
+    env.storage()
+        .instance()
+        .set(&storage_keys::BOOST_TTL, &seconds);
+}
+
+/// Multiplier for `user`, from the cache while it is fresh and otherwise
+/// read from the collections and cached
+pub fn multiplier(env: &Env, user: &Address) -> u32 {
+    if let Some(cached) = fresh(env, user) {
+        return cached;
+    }
+    let multiplier_bps = read(env, user);
+    env.storage().temporary().set(
+        &(storage_keys::BOOST_CACHE, user.clone()),
+        &CachedBoost {
+            multiplier_bps,
+            checked_at: env.ledger().timestamp(),
+            version: version(env),
+        },
+    );
+    multiplier_bps
+}
+
+/// Multiplier for `user` without touching the cache, for views
+pub fn peek(env: &Env, user: &Address) -> u32 {
+    fresh(env, user).unwrap_or_else(|| read(env, user))
+}
+
+/// `amount` scaled by `multiplier_bps`, rounded down
+pub fn apply(env: &Env, amount: i128, multiplier_bps: u32) -> Result<i128, ContractError> {
+    Checked(amount)
+        .mul_div(env, multiplier_bps as i128, BPS_DENOMINATOR)
+        .map(Checked::get)
+        .map_err(|_| ContractError::Overflow)
+}
+
+fn fresh(env: &Env, user: &Address) -> Option<u32> {
+    let cached: CachedBoost = env
+        .storage()
+        .temporary()
+        .get(&(storage_keys::BOOST_CACHE, user.clone()))?;
+    let age = env.ledger().timestamp().saturating_sub(cached.checked_at);
+    (cached.version == version(env) && age < cache_ttl(env)).then_some(cached.multiplier_bps)
+}
+
+/// Highest multiplier among the collections `user` holds a token of
+fn read(env: &Env, user: &Address) -> u32 {
+    let mut best = NO_BOOST_BPS;
+    for boost in collections(env).iter() {
+        if boost.multiplier_bps > best
+            && NftBalanceClient::new(env, &boost.collection).balance_of(user) > 0
+        {
+            best = boost.multiplier_bps;
+        }
+    }
+    best
+}
+
+fn version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&storage_keys::BOOST_VERSION)
+        .unwrap_or(0)
+}
+
+fn save(env: &Env, list: &Vec<BoostCollection>) {
+    env.storage().instance().set(&storage_keys::BOOSTS, list);
+    env.storage()
+        .instance()
+        .set(&storage_keys::BOOST_VERSION, &(version(env) + 1));
+}
@@ -0,0 +1,59 @@
+//! Staked balance history for governance snapshots
+//!
+//! Before a staker's balance first changes in a ledger, the balance they
+//! held until then is recorded against that ledger. Their balance at the
+//! end of ledger `L` is then the one recorded by the first change after
+//! `L`, or the current balance if nothing changed since. Stakes older than
+//! this history need no backfill: they have not changed since.
+
+use crate::{storage_keys, StakingRewardsContract};
+use shared::pagination::IndexedList;
+use soroban_sdk::{contracttype, Address, Env};
+
+/// `balance` was held up to, but not including, `ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeCheckpoint {
+    pub ledger: u32,
+    pub balance: i128,
+}
+
+fn checkpoints(user: &Address) -> IndexedList<Address> {
+    IndexedList::new(storage_keys::STAKE_HISTORY, user.clone())
+}
+
+/// Call before changing `user`'s staked balance
+pub fn record(env: &Env, user: &Address) {
+    let ledger = env.ledger().sequence();
+    let list = checkpoints(user);
+    let len = list.len(env);
+    if len > 0 {
+        let last: StakeCheckpoint = list.get(env, len - 1).unwrap();
+        if last.ledger == ledger {
+            return;
+        }
+    }
+    let balance = StakingRewardsContract::staked_balance(env.clone(), user.clone());
+    list.push(env, &StakeCheckpoint { ledger, balance });
+}
+
+/// `user`'s staked balance at the end of `ledger`. For the current or a
+/// future ledger this is the balance now.
+pub fn balance_at(env: &Env, user: &Address, ledger: u32) -> i128 {
+    let list = checkpoints(user);
+    // First checkpoint recorded after `ledger`
+    let (mut low, mut high) = (0, list.len(env));
+    while low < high {
+        let mid = (low + high) / 2;
+        let checkpoint: StakeCheckpoint = list.get(env, mid).unwrap();
+        if checkpoint.ledger > ledger {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    match list.get::<StakeCheckpoint>(env, low) {
+        Some(checkpoint) => checkpoint.balance,
+        None => StakingRewardsContract::staked_balance(env.clone(), user.clone()),
+    }
+}
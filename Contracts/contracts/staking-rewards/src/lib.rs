@@ -1,9 +1,24 @@
 #![no_std]
 
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{
+    namespaces, EventEmitter, PositionEvent, PositionStakeEvent, PositionTransferEvent,
+    RewardsClaimedEvent, UnstakeRequestEvent,
+};
+use shared::nft::{NftError, NonFungible};
+use shared::pagination::Pagination;
+use shared::voting::GaugeControllerClient;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Vec,
 };
 
+mod boost;
+mod history;
+mod math;
+
+pub use boost::{BoostCollection, CachedBoost};
+pub use history::StakeCheckpoint;
+
 mod storage_keys {
     use soroban_sdk::{symbol_short, Symbol};
 
@@ -12,6 +27,19 @@ mod storage_keys {
     pub const REWARD_TOKEN: Symbol = symbol_short!("r_token");
     pub const POOL_CONFIG: Symbol = symbol_short!("p_cfg");
     pub const USER_STAKE: Symbol = symbol_short!("u_stake");
+    pub const USER_REWARDS: Symbol = symbol_short!("u_rewards");
+    pub const POSITION: Symbol = symbol_short!("position");
+    pub const GAUGE: Symbol = symbol_short!("gauge");
+    pub const APY_BUDGET: Symbol = symbol_short!("apy_bud");
+    pub const BOOSTS: Symbol = symbol_short!("boosts");
+    pub const BOOST_TTL: Symbol = symbol_short!("boost_ttl");
+    pub const BOOST_VERSION: Symbol = symbol_short!("boost_ver");
+    pub const BOOST_CACHE: Symbol = symbol_short!("boost_c");
+    pub const POOL_REWARDS: Symbol = symbol_short!("p_rewards");
+    pub const LOCK_POLICY: Symbol = symbol_short!("lock_pol");
+    pub const UNSTAKE_COOLDOWN: Symbol = symbol_short!("unstk_cd");
+    pub const UNSTAKE_REQUEST: Symbol = symbol_short!("unstk_req");
+    pub const STAKE_HISTORY: Symbol = symbol_short!("stk_hist");
 }
 
 #[contracterror]
@@ -26,6 +54,25 @@ pub enum ContractError {
     InsufficientBalance = 6,
     StillLocked = 7,
     NothingToClaim = 8,
+    PositionNotFound = 9,
+    NotPositionOwner = 10,
+    NotApproved = 11,
+    InvalidBoost = 12,
+    Overflow = 13,
+    CooldownActive = 14,
+    UnstakePending = 15,
+    NoUnstakeRequest = 16,
+    CooldownRequired = 17,
+}
+
+impl From<NftError> for ContractError {
+    fn from(error: NftError) -> Self {
+        match error {
+            NftError::NotFound => ContractError::PositionNotFound,
+            NftError::NotOwner => ContractError::NotPositionOwner,
+            NftError::NotApproved => ContractError::NotApproved,
+        }
+    }
 }
 
 #[contracttype]
@@ -44,6 +91,85 @@ pub struct UserStake {
     pub last_claim_timestamp: u64,
 }
 
+/// Reward accrual of a user's stake, kept under its own key so stakes
+/// stored before the pool accumulators existed still decode
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StakeRewards {
+    /// Pool accumulator value `rewards` was last brought up to
+    pub reward_per_token_paid: i128,
+    /// Rewards accrued and not yet claimed, before any boost
+    pub rewards: i128,
+}
+
+/// Reward accumulator of one pool. Rewards accrue to every staker in the
+/// pool continuously, at the pool rate in force at the time, so how often
+/// a staker claims or tops up never changes what they earn.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PoolRewards {
+    /// Rewards one staked unit has earned since the pool opened, scaled by
+    /// [`math::REWARD_SCALE`]
+    pub reward_per_token_stored: i128,
+    /// When the accumulator was last brought up to date
+    pub last_update: u64,
+    /// Principal staked in the pool through `stake`
+    pub total_staked: i128,
+    /// Rewards accrued to all stakers, scaled by
+    /// [`math::REWARD_SCALE`] so rounding never hides any
+    pub emitted_scaled: i128,
+    /// Rewards paid out or compounded by the pool's stakers, before
+    /// boosts. Includes rewards a stake earned in another pool before
+    /// moving here.
+    pub paid: i128,
+}
+
+/// A stake taken out of its pool and waiting out the unstake cooldown
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnstakeRequest {
+    /// The stake as it was when the request was made, restored if the
+    /// request is cancelled
+    pub stake: UserStake,
+    /// Unclaimed rewards of the stake, restored with it
+    pub rewards: i128,
+    /// Principal paid out by `withdraw`, after any early withdrawal penalty
+    pub amount: i128,
+    /// When `withdraw` becomes possible
+    pub available_at: u64,
+}
+
+/// A transferable locked stake. Ownership lives in the shared NFT module;
+/// pending rewards follow the position to its new owner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub id: u64,
+    pub amount: i128,
+    pub pool_id: u32,
+    pub start: u64,
+    pub lock_end: u64,
+    /// Annual reward rate in basis points, fixed when the position is
+    /// minted and recomputed on top-ups
+    pub multiplier_bps: u32,
+    pub last_claim: u64,
+    /// Rewards earned at an earlier amount or rate and not yet claimed
+    pub accrued: i128,
+}
+
+/// How topping up a position recomputes its lock and rate
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LockPolicy {
+    /// Blend the remaining lock and the rate of the existing principal
+    /// with a full lock at the pool's current rate for the new principal,
+    /// weighted by amount
+    Weighted,
+    /// Restart the whole position on a full lock at the pool's current
+    /// rate
+    Reset,
+}
+
 #[contract]
 pub struct StakingRewardsContract;
 
@@ -67,6 +193,7 @@ impl StakingRewardsContract {
         env.storage()
             .instance()
             .set(&storage_keys::REWARD_TOKEN, &reward_token);
+        EventEmitter::init(&env, namespaces::STAKING);
 
         // Define default pools: 30, 60, 90 days
         let pools = soroban_sdk::vec![
@@ -91,6 +218,144 @@ impl StakingRewardsContract {
         Ok(())
     }
 
+    /// Let a gauge controller split `apy_budget_bps` across the pools by
+    /// vote (admin only). Rates change on the next `sync_pool_rates`.
+    pub fn set_gauge_controller(
+        env: Env,
+        admin: Address,
+        controller: Address,
+        apy_budget_bps: u32,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::ADMIN)
+            .ok_or(ContractError::NotInitialized)?;
+        if stored != admin {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&storage_keys::GAUGE, &controller);
+        env.storage()
+            .instance()
+            .set(&storage_keys::APY_BUDGET, &apy_budget_bps);
+        Ok(())
+    }
+
+    /// Set each pool's rate to its gauge weight's share of the APY budget.
+    /// Permissionless so keepers can call it after each epoch rollover.
+    pub fn sync_pool_rates(env: Env) -> Result<Vec<PoolConfig>, ContractError> {
+        let controller: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::GAUGE)
+            .ok_or(ContractError::NotInitialized)?;
+        let budget: u32 = env
+            .storage()
+            .instance()
+            .get(&storage_keys::APY_BUDGET)
+            .unwrap_or(0);
+        let gauges = GaugeControllerClient::new(&env, &controller);
+        let this = env.current_contract_address();
+
+        let mut pools = Self::get_pools(env.clone());
+        for pool_id in 0..pools.len() {
+            let mut pool = pools.get(pool_id).unwrap();
+            // Accrue at the old rate up to now before the new one applies
+            let rewards = update_pool(&env, pool_id, &pool)?;
+            set_pool_rewards(&env, pool_id, &rewards);
+            let weight = gauges.gauge_weight(&this, &pool_id);
+            pool.apy_bps = (budget as u64 * weight as u64 / 10_000) as u32;
+            pools.set(pool_id, pool);
+        }
+        env.storage()
+            .instance()
+            .set(&storage_keys::POOL_CONFIG, &pools);
+        Ok(pools)
+    }
+
+    pub fn get_pools(env: Env) -> Vec<PoolConfig> {
+        env.storage()
+            .instance()
+            .get(&storage_keys::POOL_CONFIG)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Boost the rewards of holders of `collection` by `multiplier_bps`
+    /// (admin only). Replaces the collection's earlier multiplier.
+    pub fn set_boost_collection(
+        env: Env,
+        admin: Address,
+        collection: Address,
+        multiplier_bps: u32,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if multiplier_bps <= boost::NO_BOOST_BPS || multiplier_bps > boost::MAX_BOOST_BPS {
+            return Err(ContractError::InvalidBoost);
+        }
+        boost::set_collection(&env, collection, multiplier_bps);
+        Ok(())
+    }
+
+    pub fn remove_boost_collection(
+        env: Env,
+        admin: Address,
+        collection: Address,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        if !boost::remove_collection(&env, &collection) {
+            return Err(ContractError::InvalidBoost);
+        }
+        Ok(())
+    }
+
+    /// How long a staker's boost is reused before their NFT holdings are
+    /// checked again (admin only)
+    pub fn set_boost_cache_ttl(
+        env: Env,
+        admin: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        boost::set_cache_ttl(&env, seconds);
+        Ok(())
+    }
+
+    /// Choose how `add_to_stake` recomputes a position's lock and rate
+    /// (admin only)
+    pub fn set_lock_policy(
+        env: Env,
+        admin: Address,
+        policy: LockPolicy,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&storage_keys::LOCK_POLICY, &policy);
+        Ok(())
+    }
+
+    /// Top-up policy, [`LockPolicy::Weighted`] unless the admin chose
+    /// otherwise
+    pub fn lock_policy(env: Env) -> LockPolicy {
+        env.storage()
+            .instance()
+            .get(&storage_keys::LOCK_POLICY)
+            .unwrap_or(LockPolicy::Weighted)
+    }
+
+    pub fn boost_collections(env: Env) -> Vec<BoostCollection> {
+        boost::collections(&env)
+    }
+
+    /// Rewards multiplier `user` would get if they claimed now, in basis
+    /// points
+    pub fn boost_multiplier(env: Env, user: Address) -> u32 {
+        boost::peek(&env, &user)
+    }
+
     /// Stake tokens in a specific pool
     pub fn stake(env: Env, user: Address, amount: i128, pool_id: u32) -> Result<(), ContractError> {
         user.require_auth();
@@ -98,6 +363,9 @@ impl StakingRewardsContract {
         if amount <= 0 {
             return Err(ContractError::InvalidAmount);
         }
+        if Self::unstake_request(env.clone(), user.clone()).is_some() {
+            return Err(ContractError::UnstakePending);
+        }
 
         let pools: soroban_sdk::Vec<PoolConfig> = env
             .storage()
@@ -119,6 +387,7 @@ impl StakingRewardsContract {
         let client = soroban_sdk::token::Client::new(&env, &staking_token);
         client.transfer(&user, &env.current_contract_address(), &amount);
 
+        history::record(&env, &user);
         let key = (storage_keys::USER_STAKE, user.clone());
         let mut user_stake = env
             .storage()
@@ -131,14 +400,26 @@ impl StakingRewardsContract {
                 last_claim_timestamp: env.ledger().timestamp(),
             });
 
-        // For simplicity, if they already have a stake, they must unstake first or we just update
-        // In this implementation, we allow adding to stake but reset the timer for the whole amount
+        // Settle what the existing stake earned in its pool, then move the
+        // whole stake to `pool_id`. Accrued rewards are kept, but the
+        // lockup restarts for the whole amount.
+        let mut accrual = StakeRewards::default();
+        if user_stake.amount > 0 {
+            let (mut old_pool, settled) = checkpoint(&env, &user, &user_stake)?;
+            old_pool.total_staked -= user_stake.amount;
+            set_pool_rewards(&env, user_stake.pool_id, &old_pool);
+            accrual = settled;
+        }
+        let mut pool = update_pool(&env, pool_id, &pools.get_unchecked(pool_id))?;
         user_stake.amount += amount;
         user_stake.pool_id = pool_id;
         user_stake.start_timestamp = env.ledger().timestamp();
-        user_stake.last_claim_timestamp = env.ledger().timestamp();
+        accrual.reward_per_token_paid = pool.reward_per_token_stored;
+        pool.total_staked += user_stake.amount;
+        set_pool_rewards(&env, pool_id, &pool);
 
         env.storage().persistent().set(&key, &user_stake);
+        set_stake_rewards(&env, &user, &accrual);
 
         env.events().publish(
             (symbol_short!("stake"), user),
@@ -159,10 +440,17 @@ impl StakingRewardsContract {
             .get(&key)
             .ok_or(ContractError::NothingToClaim)?;
 
-        let reward_amount = calculate_rewards(&env, &user_stake)?;
+        let (mut pool, mut accrual) = checkpoint(&env, &user, &user_stake)?;
+        let reward_amount = accrual.rewards;
         if reward_amount <= 0 {
             return Err(ContractError::NothingToClaim);
         }
+        accrual.rewards = 0;
+        pool.paid += reward_amount;
+        set_pool_rewards(&env, user_stake.pool_id, &pool);
+        set_stake_rewards(&env, &user, &accrual);
+        let multiplier_bps = boost::multiplier(&env, &user);
+        let reward_amount = boost::apply(&env, reward_amount, multiplier_bps)?;
 
         let reward_token: Address = env
             .storage()
@@ -176,64 +464,137 @@ impl StakingRewardsContract {
         user_stake.last_claim_timestamp = env.ledger().timestamp();
         env.storage().persistent().set(&key, &user_stake);
 
-        env.events().publish(
-            (symbol_short!("claim"), user),
-            (reward_amount, env.ledger().timestamp()),
-        );
-
+        emit_rewards_claimed(&env, user, None, reward_amount, multiplier_bps);
         Ok(reward_amount)
     }
 
-    /// Unstake principal and any pending rewards
+    /// Unstake principal. Rewards not yet claimed are forfeited, so claim
+    /// first. Only available while no unstake cooldown is set; otherwise
+    /// use `request_unstake` and `withdraw`.
     pub fn unstake(env: Env, user: Address) -> Result<i128, ContractError> {
         user.require_auth();
+        if Self::unstake_cooldown(env.clone()) > 0 {
+            return Err(ContractError::CooldownRequired);
+        }
 
-        let key = (storage_keys::USER_STAKE, user.clone());
-        let user_stake: UserStake = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(ContractError::NothingToClaim)?;
+        let (_, _, principal_to_return) = remove_stake(&env, &user)?;
+        transfer_stake_token(&env, &user, principal_to_return);
 
-        let pools: soroban_sdk::Vec<PoolConfig> = env
-            .storage()
-            .instance()
-            .get(&storage_keys::POOL_CONFIG)
-            .unwrap();
+        env.events().publish(
+            (symbol_short!("unstake"), user),
+            (principal_to_return, env.ledger().timestamp()),
+        );
 
-        let pool = pools.get_unchecked(user_stake.pool_id);
-        let now = env.ledger().timestamp();
-        let elapsed = now - user_stake.start_timestamp;
+        Ok(principal_to_return)
+    }
 
-        let mut principal_to_return = user_stake.amount;
+    /// How long `request_unstake` makes a staker wait before `withdraw`
+    /// (admin only). Zero, the default, disables the cooldown and
+    /// re-enables `unstake`.
+    pub fn set_unstake_cooldown(
+        env: Env,
+        admin: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&storage_keys::UNSTAKE_COOLDOWN, &seconds);
+        Ok(())
+    }
 
-        // Apply early withdrawal penalty if lockup hasn't expired
-        if elapsed < pool.lockup_seconds {
-            let penalty_bps = 1000; // 10% penalty
-            let penalty_amount = (principal_to_return * penalty_bps as i128) / 10000;
-            principal_to_return -= penalty_amount;
+    pub fn unstake_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&storage_keys::UNSTAKE_COOLDOWN)
+            .unwrap_or(0)
+    }
 
-            // Penalty stays in the contract (could be sent to a treasury)
+    /// Take `user`'s stake out of its pool and start the cooldown. The
+    /// stake stops earning immediately and the early withdrawal penalty is
+    /// fixed now. Returns when `withdraw` becomes possible.
+    pub fn request_unstake(env: Env, user: Address) -> Result<u64, ContractError> {
+        user.require_auth();
+        let key = (storage_keys::UNSTAKE_REQUEST, user.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(ContractError::UnstakePending);
         }
 
-        let staking_token: Address = env
+        let (stake, rewards, amount) = remove_stake(&env, &user)?;
+        let now = env.ledger().timestamp();
+        let request = UnstakeRequest {
+            stake,
+            rewards,
+            amount,
+            available_at: now + Self::unstake_cooldown(env.clone()),
+        };
+        env.storage().persistent().set(&key, &request);
+
+        EventEmitter::unstake_requested(&env, unstake_request_event(&request, &user, now));
+        Ok(request.available_at)
+    }
+
+    /// Pay out a request whose cooldown has elapsed. Returns the principal
+    /// returned.
+    pub fn withdraw(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+        let key = (storage_keys::UNSTAKE_REQUEST, user.clone());
+        let request: UnstakeRequest = env
             .storage()
-            .instance()
-            .get(&storage_keys::STAKE_TOKEN)
-            .unwrap();
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::NoUnstakeRequest)?;
+        let now = env.ledger().timestamp();
+        if now < request.available_at {
+            return Err(ContractError::CooldownActive);
+        }
 
-        let client = soroban_sdk::token::Client::new(&env, &staking_token);
-        client.transfer(&env.current_contract_address(), &user, &principal_to_return);
+        env.storage().persistent().remove(&key);
+        transfer_stake_token(&env, &user, request.amount);
 
-        // Remove stake
+        EventEmitter::unstake_withdrawn(&env, unstake_request_event(&request, &user, now));
+        Ok(request.amount)
+    }
+
+    /// Put a requested stake back in its pool, penalty waived and
+    /// unclaimed rewards kept. Nothing accrues for the time spent in the
+    /// cooldown.
+    pub fn cancel_unstake(env: Env, user: Address) -> Result<(), ContractError> {
+        user.require_auth();
+        let key = (storage_keys::UNSTAKE_REQUEST, user.clone());
+        let request: UnstakeRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::NoUnstakeRequest)?;
         env.storage().persistent().remove(&key);
 
-        env.events().publish(
-            (symbol_short!("unstake"), user),
-            (principal_to_return, env.ledger().timestamp()),
-        );
+        let stake = request.stake.clone();
+        let pools = Self::get_pools(env.clone());
+        let config = pools.get(stake.pool_id).ok_or(ContractError::InvalidPool)?;
+        let mut pool = update_pool(&env, stake.pool_id, &config)?;
+        let accrual = StakeRewards {
+            reward_per_token_paid: pool.reward_per_token_stored,
+            rewards: request.rewards,
+        };
+        pool.total_staked += stake.amount;
+        set_pool_rewards(&env, stake.pool_id, &pool);
+        history::record(&env, &user);
+        env.storage()
+            .persistent()
+            .set(&(storage_keys::USER_STAKE, user.clone()), &stake);
+        set_stake_rewards(&env, &user, &accrual);
 
-        Ok(principal_to_return)
+        let now = env.ledger().timestamp();
+        EventEmitter::unstake_cancelled(&env, unstake_request_event(&request, &user, now));
+        Ok(())
+    }
+
+    /// `user`'s pending unstake request, if any
+    pub fn unstake_request(env: Env, user: Address) -> Option<UnstakeRequest> {
+        env.storage()
+            .persistent()
+            .get(&(storage_keys::UNSTAKE_REQUEST, user))
     }
 
     /// Re-stake pending rewards (Auto-compounding)
@@ -247,7 +608,8 @@ impl StakingRewardsContract {
             .get(&key)
             .ok_or(ContractError::NothingToClaim)?;
 
-        let reward_amount = calculate_rewards(&env, &user_stake)?;
+        let (mut pool, mut accrual) = checkpoint(&env, &user, &user_stake)?;
+        let reward_amount = accrual.rewards;
         if reward_amount <= 0 {
             return Err(ContractError::NothingToClaim);
         }
@@ -267,10 +629,17 @@ impl StakingRewardsContract {
         if staking_token != reward_token {
             return Err(ContractError::Unauthorized); // Or a more specific error
         }
+        accrual.rewards = 0;
+        pool.paid += reward_amount;
+        let reward_amount = boost::apply(&env, reward_amount, boost::multiplier(&env, &user))?;
 
+        history::record(&env, &user);
         user_stake.amount += reward_amount;
         user_stake.last_claim_timestamp = env.ledger().timestamp();
+        pool.total_staked += reward_amount;
+        set_pool_rewards(&env, user_stake.pool_id, &pool);
         env.storage().persistent().set(&key, &user_stake);
+        set_stake_rewards(&env, &user, &accrual);
 
         env.events().publish(
             (symbol_short!("compound"), user),
@@ -280,52 +649,599 @@ impl StakingRewardsContract {
         Ok(())
     }
 
+    /// Stake `amount` in `pool_id` as a new transferable position owned by
+    /// `user`. Returns the position id.
+    pub fn stake_position(
+        env: Env,
+        user: Address,
+        amount: i128,
+        pool_id: u32,
+    ) -> Result<u64, ContractError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let pools: soroban_sdk::Vec<PoolConfig> = env
+            .storage()
+            .instance()
+            .get(&storage_keys::POOL_CONFIG)
+            .ok_or(ContractError::NotInitialized)?;
+        let pool = pools.get(pool_id).ok_or(ContractError::InvalidPool)?;
+
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::STAKE_TOKEN)
+            .unwrap();
+        let client = soroban_sdk::token::Client::new(&env, &staking_token);
+        client.transfer(&user, &env.current_contract_address(), &amount);
+
+        history::record(&env, &user);
+        let now = env.ledger().timestamp();
+        let id = NonFungible::mint(&env, &user);
+        let position = Position {
+            id,
+            amount,
+            pool_id,
+            start: now,
+            lock_end: now + pool.lockup_seconds,
+            multiplier_bps: pool.apy_bps,
+            last_claim: now,
+            accrued: 0,
+        };
+        set_position(&env, &position);
+
+        EventEmitter::position_minted(&env, position_event(&position, &user, now));
+        Ok(id)
+    }
+
+    pub fn position_of(env: Env, id: u64) -> Option<Position> {
+        env.storage()
+            .persistent()
+            .get(&(storage_keys::POSITION, id))
+    }
+
+    pub fn position_owner(env: Env, id: u64) -> Option<Address> {
+        NonFungible::owner_of(&env, id)
+    }
+
+    /// One page of the ids of the positions held by `owner`, and the
+    /// cursor of the next
+    pub fn positions_of(env: Env, owner: Address, page: Pagination) -> (Vec<u64>, Option<u32>) {
+        NonFungible::tokens_page(&env, &owner, &page)
+    }
+
+    pub fn transfer_position(
+        env: Env,
+        from: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<(), ContractError> {
+        history::record(&env, &from);
+        history::record(&env, &to);
+        NonFungible::transfer(&env, &from, &to, id)?;
+        emit_position_transfer(&env, id, from, to);
+        Ok(())
+    }
+
+    /// Let `spender` (e.g. a lending market holding it as collateral) move
+    /// position `id`
+    pub fn approve_position(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        id: u64,
+    ) -> Result<(), ContractError> {
+        NonFungible::approve(&env, &owner, &spender, id)?;
+        Ok(())
+    }
+
+    pub fn transfer_position_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u64,
+    ) -> Result<(), ContractError> {
+        history::record(&env, &from);
+        history::record(&env, &to);
+        NonFungible::transfer_from(&env, &spender, &from, &to, id)?;
+        emit_position_transfer(&env, id, from, to);
+        Ok(())
+    }
+
+    /// Rewards position `id` would pay its owner now, boost included
+    pub fn get_position_rewards(env: Env, id: u64) -> Result<i128, ContractError> {
+        let (Some(position), Some(owner)) = (
+            Self::position_of(env.clone(), id),
+            NonFungible::owner_of(&env, id),
+        ) else {
+            return Ok(0);
+        };
+        let pending = position_rewards(&env, &position)?;
+        boost::apply(&env, pending, boost::peek(&env, &owner))
+    }
+
+    /// Claim the rewards accrued by position `id` to its owner
+    pub fn claim_position(env: Env, owner: Address, id: u64) -> Result<i128, ContractError> {
+        owner.require_auth();
+        NonFungible::require_owner(&env, &owner, id)?;
+        let mut position =
+            Self::position_of(env.clone(), id).ok_or(ContractError::PositionNotFound)?;
+
+        let reward_amount = position_rewards(&env, &position)?;
+        if reward_amount <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+        let multiplier_bps = boost::multiplier(&env, &owner);
+        let reward_amount = boost::apply(&env, reward_amount, multiplier_bps)?;
+        let reward_token: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::REWARD_TOKEN)
+            .unwrap();
+        let client = soroban_sdk::token::Client::new(&env, &reward_token);
+        client.transfer(&env.current_contract_address(), &owner, &reward_amount);
+
+        position.last_claim = env.ledger().timestamp();
+        position.accrued = 0;
+        set_position(&env, &position);
+        emit_rewards_claimed(&env, owner, Some(id), reward_amount, multiplier_bps);
+        Ok(reward_amount)
+    }
+
+    /// Add `amount` to position `id`. Rewards earned so far are kept, and
+    /// the lock and rate are recomputed under the [`LockPolicy`].
+    pub fn add_to_stake(
+        env: Env,
+        owner: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<Position, ContractError> {
+        owner.require_auth();
+        NonFungible::require_owner(&env, &owner, id)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let mut position =
+            Self::position_of(env.clone(), id).ok_or(ContractError::PositionNotFound)?;
+        let pool = Self::get_pools(env.clone())
+            .get(position.pool_id)
+            .ok_or(ContractError::InvalidPool)?;
+
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::STAKE_TOKEN)
+            .unwrap();
+        let client = soroban_sdk::token::Client::new(&env, &staking_token);
+        client.transfer(&owner, &env.current_contract_address(), &amount);
+
+        let now = env.ledger().timestamp();
+        accrue_position(&env, &mut position)?;
+        history::record(&env, &owner);
+        match Self::lock_policy(env.clone()) {
+            LockPolicy::Reset => {
+                position.start = now;
+                position.lock_end = now + pool.lockup_seconds;
+                position.multiplier_bps = pool.apy_bps;
+            }
+            LockPolicy::Weighted => {
+                let remaining = position.lock_end.saturating_sub(now);
+                let lock = math::weighted_average(
+                    &env,
+                    remaining as i128,
+                    position.amount,
+                    pool.lockup_seconds as i128,
+                    amount,
+                )
+                .map_err(|_| ContractError::Overflow)?;
+                let rate = math::weighted_average(
+                    &env,
+                    position.multiplier_bps as i128,
+                    position.amount,
+                    pool.apy_bps as i128,
+                    amount,
+                )
+                .map_err(|_| ContractError::Overflow)?;
+                position.lock_end = now + lock as u64;
+                position.multiplier_bps = rate as u32;
+            }
+        }
+        position.amount += amount;
+        set_position(&env, &position);
+
+        EventEmitter::position_staked(
+            &env,
+            position_stake_event(&position, &owner, amount, 0, now),
+        );
+        Ok(position)
+    }
+
+    /// Withdraw `amount` of position `id`'s principal, less the early
+    /// withdrawal penalty before the lock ends. The position keeps its
+    /// lock, rate and rewards; withdrawing everything goes through
+    /// `unstake_position`. Returns the principal returned.
+    pub fn unstake_partial(
+        env: Env,
+        owner: Address,
+        id: u64,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        owner.require_auth();
+        NonFungible::require_owner(&env, &owner, id)?;
+        let mut position =
+            Self::position_of(env.clone(), id).ok_or(ContractError::PositionNotFound)?;
+        if amount <= 0 || amount >= position.amount {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        accrue_position(&env, &mut position)?;
+        let (principal_to_return, penalty) = math::split_penalty(
+            &env,
+            amount,
+            now - position.start,
+            position.lock_end - position.start,
+        )
+        .map_err(|_| ContractError::Overflow)?;
+        history::record(&env, &owner);
+        position.amount -= amount;
+        set_position(&env, &position);
+
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::STAKE_TOKEN)
+            .unwrap();
+        let client = soroban_sdk::token::Client::new(&env, &staking_token);
+        client.transfer(
+            &env.current_contract_address(),
+            &owner,
+            &principal_to_return,
+        );
+
+        EventEmitter::position_unstaked(
+            &env,
+            position_stake_event(&position, &owner, amount, penalty, now),
+        );
+        Ok(principal_to_return)
+    }
+
+    /// Burn position `id` and return its principal to the owner, less the
+    /// early withdrawal penalty before the lock ends. Unclaimed rewards are
+    /// forfeited, so claim first.
+    pub fn unstake_position(env: Env, owner: Address, id: u64) -> Result<i128, ContractError> {
+        let position = Self::position_of(env.clone(), id).ok_or(ContractError::PositionNotFound)?;
+        history::record(&env, &owner);
+        NonFungible::burn(&env, &owner, id)?;
+
+        let now = env.ledger().timestamp();
+        let (principal_to_return, _penalty) = math::split_penalty(
+            &env,
+            position.amount,
+            now - position.start,
+            position.lock_end - position.start,
+        )
+        .map_err(|_| ContractError::Overflow)?;
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&storage_keys::STAKE_TOKEN)
+            .unwrap();
+        let client = soroban_sdk::token::Client::new(&env, &staking_token);
+        client.transfer(
+            &env.current_contract_address(),
+            &owner,
+            &principal_to_return,
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&(storage_keys::POSITION, id));
+        EventEmitter::position_burned(&env, position_event(&position, &owner, now));
+        Ok(principal_to_return)
+    }
+
     /// Get user's current stake info
     pub fn get_stake(env: Env, user: Address) -> Option<UserStake> {
         let key = (storage_keys::USER_STAKE, user);
         env.storage().persistent().get(&key)
     }
 
-    /// Get pending rewards for a user
-    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
-        let key = (storage_keys::USER_STAKE, user);
+    /// Principal `user` has staked, in their stake and the positions they
+    /// hold
+    pub fn staked_balance(env: Env, user: Address) -> i128 {
+        let mut total = Self::get_stake(env.clone(), user.clone()).map_or(0, |s| s.amount);
+        for id in NonFungible::tokens_of(&env, &user).iter() {
+            if let Some(position) = Self::position_of(env.clone(), id) {
+                total += position.amount;
+            }
+        }
+        total
+    }
+
+    /// `staked_balance` of `user` at the end of `ledger`, so a stake moved
+    /// after a proposal's snapshot can't vote twice
+    pub fn staked_balance_at(env: Env, user: Address, ledger: u32) -> i128 {
+        history::balance_at(&env, &user, ledger)
+    }
+
+    /// Get pending rewards for a user, boost included
+    pub fn get_pending_rewards(env: Env, user: Address) -> Result<i128, ContractError> {
+        let key = (storage_keys::USER_STAKE, user.clone());
         if let Some(user_stake) = env.storage().persistent().get::<_, UserStake>(&key) {
-            return calculate_rewards(&env, &user_stake).unwrap_or(0);
+            let (_, accrual) = checkpoint(&env, &user, &user_stake)?;
+            return boost::apply(&env, accrual.rewards, boost::peek(&env, &user));
         }
-        0
+        Ok(0)
+    }
+
+    /// Reward accumulator of `pool_id` as of its last update
+    pub fn pool_rewards(env: Env, pool_id: u32) -> PoolRewards {
+        env.storage()
+            .persistent()
+            .get(&(storage_keys::POOL_REWARDS, pool_id))
+            .unwrap_or_default()
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&storage_keys::ADMIN, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), ContractError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
     }
 }
 
-fn calculate_rewards(env: &Env, user_stake: &UserStake) -> Result<i128, ContractError> {
-    let pools: soroban_sdk::Vec<PoolConfig> = env
+fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
+    admin.require_auth();
+    let stored: Address = env
         .storage()
         .instance()
-        .get(&storage_keys::POOL_CONFIG)
+        .get(&storage_keys::ADMIN)
         .ok_or(ContractError::NotInitialized)?;
+    if stored != *admin {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Take `user`'s stake out of its pool and delete it, after crediting what
+/// it earned. Returns the stake, its unclaimed rewards and the principal
+/// owed after the early withdrawal penalty, which stays in the contract.
+fn remove_stake(env: &Env, user: &Address) -> Result<(UserStake, i128, i128), ContractError> {
+    let key = (storage_keys::USER_STAKE, user.clone());
+    let user_stake: UserStake = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(ContractError::NothingToClaim)?;
 
-    if user_stake.pool_id >= pools.len() {
-        return Err(ContractError::InvalidPool);
+    let pools = StakingRewardsContract::get_pools(env.clone());
+    let pool = pools.get_unchecked(user_stake.pool_id);
+    let elapsed = env.ledger().timestamp() - user_stake.start_timestamp;
+    let (principal, _penalty) =
+        math::split_penalty(env, user_stake.amount, elapsed, pool.lockup_seconds)
+            .map_err(|_| ContractError::Overflow)?;
+
+    let (mut rewards, accrual) = checkpoint(env, user, &user_stake)?;
+    history::record(env, user);
+    rewards.total_staked -= user_stake.amount;
+    set_pool_rewards(env, user_stake.pool_id, &rewards);
+    env.storage().persistent().remove(&key);
+    env.storage()
+        .persistent()
+        .remove(&(storage_keys::USER_REWARDS, user.clone()));
+    Ok((user_stake, accrual.rewards, principal))
+}
+
+fn transfer_stake_token(env: &Env, to: &Address, amount: i128) {
+    let staking_token: Address = env
+        .storage()
+        .instance()
+        .get(&storage_keys::STAKE_TOKEN)
+        .unwrap();
+    soroban_sdk::token::Client::new(env, &staking_token).transfer(
+        &env.current_contract_address(),
+        to,
+        &amount,
+    );
+}
+
+fn unstake_request_event(
+    request: &UnstakeRequest,
+    user: &Address,
+    timestamp: u64,
+) -> UnstakeRequestEvent {
+    UnstakeRequestEvent {
+        user: user.clone(),
+        amount: request.amount,
+        available_at: request.available_at,
+        timestamp,
     }
+}
 
-    let pool = pools.get(user_stake.pool_id).unwrap();
-    let now = env.ledger().timestamp();
-    let elapsed_seconds = now - user_stake.last_claim_timestamp;
+fn set_position(env: &Env, position: &Position) {
+    env.storage()
+        .persistent()
+        .set(&(storage_keys::POSITION, position.id), position);
+}
 
-    if elapsed_seconds == 0 {
-        return Ok(0);
+fn position_rewards(env: &Env, position: &Position) -> Result<i128, ContractError> {
+    let elapsed = env.ledger().timestamp() - position.last_claim;
+    let earned = math::reward(env, position.amount, position.multiplier_bps, elapsed)
+        .map_err(|_| ContractError::Overflow)?;
+    Ok(position.accrued + earned)
+}
+
+/// Fold what `position` earned since its last claim into `accrued`, before
+/// its amount or rate changes
+fn accrue_position(env: &Env, position: &mut Position) -> Result<(), ContractError> {
+    position.accrued = position_rewards(env, position)?;
+    position.last_claim = env.ledger().timestamp();
+    Ok(())
+}
+
+fn position_stake_event(
+    position: &Position,
+    owner: &Address,
+    amount: i128,
+    penalty: i128,
+    timestamp: u64,
+) -> PositionStakeEvent {
+    PositionStakeEvent {
+        id: position.id,
+        owner: owner.clone(),
+        amount,
+        total: position.amount,
+        penalty,
+        lock_end: position.lock_end,
+        multiplier_bps: position.multiplier_bps,
+        timestamp,
     }
+}
 
-    // Reward = Principal * APY * (elapsed / seconds_in_year)
-    // APY is in basis points
-    let seconds_in_year: u64 = 365 * 24 * 60 * 60;
+fn position_event(position: &Position, owner: &Address, timestamp: u64) -> PositionEvent {
+    PositionEvent {
+        id: position.id,
+        owner: owner.clone(),
+        amount: position.amount,
+        lock_end: position.lock_end,
+        multiplier_bps: position.multiplier_bps,
+        timestamp,
+    }
+}
 
-    // Scale precision for calculation: (amount * apy * seconds) / (10000 * seconds_in_year)
-    // Using i128 to prevent overflow in Intermediate calculation
-    let reward = (user_stake.amount * pool.apy_bps as i128 * elapsed_seconds as i128)
-        / (10000i128 * seconds_in_year as i128);
+fn emit_rewards_claimed(
+    env: &Env,
+    user: Address,
+    position_id: Option<u64>,
+    amount: i128,
+    multiplier_bps: u32,
+) {
+    EventEmitter::rewards_claimed(
+        env,
+        RewardsClaimedEvent {
+            user,
+            position_id,
+            amount,
+            multiplier_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
 
-    Ok(reward)
+fn emit_position_transfer(env: &Env, id: u64, from: Address, to: Address) {
+    EventEmitter::position_transferred(
+        env,
+        PositionTransferEvent {
+            id,
+            from,
+            to,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
 }
 
+fn set_pool_rewards(env: &Env, pool_id: u32, rewards: &PoolRewards) {
+    env.storage()
+        .persistent()
+        .set(&(storage_keys::POOL_REWARDS, pool_id), rewards);
+}
+
+/// `pool_id`'s accumulator brought up to now at `pool`'s rate. Not saved.
+fn update_pool(env: &Env, pool_id: u32, pool: &PoolConfig) -> Result<PoolRewards, ContractError> {
+    let mut rewards = StakingRewardsContract::pool_rewards(env.clone(), pool_id);
+    let now = env.ledger().timestamp();
+    if now > rewards.last_update {
+        let delta = math::reward_per_token(env, pool.apy_bps, now - rewards.last_update)
+            .map_err(|_| ContractError::Overflow)?;
+        rewards.reward_per_token_stored = rewards
+            .reward_per_token_stored
+            .checked_add(delta)
+            .ok_or(ContractError::Overflow)?;
+        rewards.emitted_scaled = rewards
+            .total_staked
+            .checked_mul(delta)
+            .and_then(|emitted| emitted.checked_add(rewards.emitted_scaled))
+            .ok_or(ContractError::Overflow)?;
+        rewards.last_update = now;
+    }
+    Ok(rewards)
+}
+
+fn set_stake_rewards(env: &Env, user: &Address, accrual: &StakeRewards) {
+    env.storage()
+        .persistent()
+        .set(&(storage_keys::USER_REWARDS, user.clone()), accrual);
+}
+
+/// Credit `user`'s stake with what it earned since its last checkpoint.
+/// Returns its pool's updated accumulator and the stake's accrual for the
+/// caller to save.
+///
+/// A stake stored before the pool accumulators has no accrual yet. It is
+/// credited what it earned since its last claim at its pool's rate, the
+/// way rewards used to accrue, and joins the pool's `total_staked`.
+fn checkpoint(
+    env: &Env,
+    user: &Address,
+    user_stake: &UserStake,
+) -> Result<(PoolRewards, StakeRewards), ContractError> {
+    let pools = StakingRewardsContract::get_pools(env.clone());
+    let pool = pools
+        .get(user_stake.pool_id)
+        .ok_or(ContractError::InvalidPool)?;
+    let mut rewards = update_pool(env, user_stake.pool_id, &pool)?;
+    let stored: Option<StakeRewards> = env
+        .storage()
+        .persistent()
+        .get(&(storage_keys::USER_REWARDS, user.clone()));
+    let mut accrual = match stored {
+        Some(accrual) => accrual,
+        None => {
+            let elapsed = env.ledger().timestamp() - user_stake.last_claim_timestamp;
+            rewards.total_staked += user_stake.amount;
+            StakeRewards {
+                reward_per_token_paid: rewards.reward_per_token_stored,
+                rewards: math::reward(env, user_stake.amount, pool.apy_bps, elapsed)
+                    .map_err(|_| ContractError::Overflow)?,
+            }
+        }
+    };
+    let earned = math::earned(
+        env,
+        user_stake.amount,
+        rewards.reward_per_token_stored - accrual.reward_per_token_paid,
+    )
+    .map_err(|_| ContractError::Overflow)?;
+    accrual.rewards += earned;
+    accrual.reward_per_token_paid = rewards.reward_per_token_stored;
+    Ok((rewards, accrual))
+}
+
+#[cfg(test)]
+mod proptests;
 #[cfg(test)]
 mod test;
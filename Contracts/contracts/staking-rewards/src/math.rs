@@ -0,0 +1,91 @@
+//! Pure reward and penalty arithmetic, kept free of storage so it can be
+//! checked in isolation. Overflow is reported rather than wrapped; the env
+//! is only used for the debug diagnostics of [`Checked`].
+
+use shared::errors::ContractError;
+use shared::math::Checked;
+use soroban_sdk::Env;
+
+pub const BPS_DENOMINATOR: i128 = 10_000;
+pub const SECONDS_IN_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Share of principal forfeited when unstaking before the lockup ends
+pub const EARLY_WITHDRAWAL_PENALTY_BPS: i128 = 1000; // 10%
+
+/// Scale of the reward-per-token accumulator. Keeping it in basis-point
+/// seconds makes each increment exact, so the only rounding is when
+/// rewards are credited.
+pub const REWARD_SCALE: i128 = BPS_DENOMINATOR * SECONDS_IN_YEAR as i128;
+
+/// Reward = principal * APY * (elapsed / seconds_in_year), with APY in
+/// basis points. Computed in a single division so rounding never favours
+/// the staker.
+pub fn reward(
+    env: &Env,
+    amount: i128,
+    apy_bps: u32,
+    elapsed_seconds: u64,
+) -> Result<i128, ContractError> {
+    Ok(Checked(amount)
+        .mul(env, apy_bps as i128)?
+        .mul(env, elapsed_seconds as i128)?
+        .div(env, BPS_DENOMINATOR * SECONDS_IN_YEAR as i128)?
+        .get())
+}
+
+/// Growth of a pool's reward-per-token accumulator over `elapsed_seconds`
+/// at `apy_bps`: what one staked unit earns in that time, scaled by
+/// [`REWARD_SCALE`]
+pub fn reward_per_token(
+    env: &Env,
+    apy_bps: u32,
+    elapsed_seconds: u64,
+) -> Result<i128, ContractError> {
+    Ok(Checked(apy_bps as i128)
+        .mul(env, elapsed_seconds as i128)?
+        .get())
+}
+
+/// Rewards earned by `amount` staked while the accumulator grew by
+/// `reward_per_token_delta`, rounded down
+pub fn earned(
+    env: &Env,
+    amount: i128,
+    reward_per_token_delta: i128,
+) -> Result<i128, ContractError> {
+    Ok(Checked(amount)
+        .mul_div(env, reward_per_token_delta, REWARD_SCALE)?
+        .get())
+}
+
+/// Split `amount` into the principal returned on unstake and the penalty
+/// withheld. The two parts always sum to `amount`.
+pub fn split_penalty(
+    env: &Env,
+    amount: i128,
+    elapsed: u64,
+    lockup_seconds: u64,
+) -> Result<(i128, i128), ContractError> {
+    if elapsed >= lockup_seconds {
+        return Ok((amount, 0));
+    }
+    let penalty = Checked(amount)
+        .mul_div(env, EARLY_WITHDRAWAL_PENALTY_BPS, BPS_DENOMINATOR)?
+        .get();
+    Ok((amount - penalty, penalty))
+}
+
+/// Average of `a` and `b` weighted by `a_weight` and `b_weight`, rounded
+/// down. Used to blend lock times and rates when a position is topped up.
+pub fn weighted_average(
+    env: &Env,
+    a: i128,
+    a_weight: i128,
+    b: i128,
+    b_weight: i128,
+) -> Result<i128, ContractError> {
+    let total = Checked(a)
+        .mul(env, a_weight)?
+        .add(env, Checked(b).mul(env, b_weight)?.get())?;
+    Ok(total.div(env, a_weight + b_weight)?.get())
+}
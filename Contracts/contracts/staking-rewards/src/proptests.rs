@@ -0,0 +1,85 @@
+extern crate std;
+
+use crate::math::{
+    earned, reward, reward_per_token, split_penalty, BPS_DENOMINATOR, SECONDS_IN_YEAR,
+};
+use proptest::prelude::*;
+use soroban_sdk::Env;
+use std::format;
+use test_utils::strategies::{amount, bps, checkpoints, duration};
+
+proptest! {
+    #[test]
+    fn rewards_never_exceed_emissions(
+        principal in amount(),
+        apy in bps(),
+        elapsed in duration(),
+    ) {
+        // Exact (unrounded) emissions for the period, scaled by the
+        // denominator to stay in integers
+        let env = Env::default();
+        let emitted_scaled = principal * apy as i128 * elapsed as i128;
+        let paid = reward(&env, principal, apy, elapsed).unwrap();
+        prop_assert!(paid >= 0);
+        prop_assert!(paid * BPS_DENOMINATOR * SECONDS_IN_YEAR as i128 <= emitted_scaled);
+    }
+
+    #[test]
+    fn claiming_in_pieces_never_pays_more(
+        principal in amount(),
+        apy in bps(),
+        total in duration(),
+        points in checkpoints(10 * SECONDS_IN_YEAR, 8),
+    ) {
+        let env = Env::default();
+        let reward = |elapsed| reward(&env, principal, apy, elapsed).unwrap();
+        let mut last = 0u64;
+        let mut paid = 0i128;
+        for point in points.into_iter().filter(|p| *p <= total) {
+            paid += reward(point - last);
+            last = point;
+        }
+        paid += reward(total - last);
+        prop_assert!(paid <= reward(total));
+    }
+
+    #[test]
+    fn accumulating_in_pieces_never_pays_more(
+        principal in amount(),
+        apy in bps(),
+        total in duration(),
+        points in checkpoints(10 * SECONDS_IN_YEAR, 8),
+    ) {
+        let env = Env::default();
+        let mut stored = 0i128;
+        let mut last = 0u64;
+        for point in points.into_iter().filter(|p| *p <= total).chain([total]) {
+            stored += reward_per_token(&env, apy, point - last).unwrap();
+            last = point;
+        }
+        let paid = earned(&env, principal, stored).unwrap();
+        prop_assert!(paid <= reward(&env, principal, apy, total).unwrap());
+    }
+
+    #[test]
+    fn unstake_returned_plus_penalty_equals_principal(
+        principal in amount(),
+        elapsed in duration(),
+        lockup in duration(),
+    ) {
+        let env = Env::default();
+        let (returned, penalty) = split_penalty(&env, principal, elapsed, lockup).unwrap();
+        prop_assert_eq!(returned + penalty, principal);
+        prop_assert!(returned > 0 && penalty >= 0);
+        if elapsed >= lockup {
+            prop_assert_eq!(penalty, 0);
+        }
+    }
+}
+
+#[test]
+fn overflowing_reward_is_an_error() {
+    let env = Env::default();
+    let result = reward(&env, i128::MAX / 2, 10_000, SECONDS_IN_YEAR);
+    assert_eq!(result, Err(shared::errors::ContractError::Overflow));
+}
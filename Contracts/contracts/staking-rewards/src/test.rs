@@ -1,11 +1,17 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
+use shared::events::{metadata_keys, topics};
 use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::InvokeError;
 use soroban_sdk::{
+    contract, contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Env, Vec,
+    Address, Env,
 };
+use std::string::ToString;
+use test_utils::{assert_standard_event, payload, Scenario};
 
 fn create_token(
     env: &Env,
@@ -110,7 +116,7 @@ fn test_early_withdrawal_penalty() {
 
     // Jump 1 day (Early)
     env.ledger().set(soroban_sdk::testutils::LedgerInfo {
-        timestamp: 1 * 24 * 60 * 60,
+        timestamp: 24 * 60 * 60,
         protocol_version: 20,
         sequence_number: 10,
         network_id: [0u8; 32],
@@ -167,3 +173,650 @@ fn test_compounding() {
     assert_eq!(stake_info.amount, 1073);
     assert_eq!(token.balance(&user), 0);
 }
+
+fn setup_positions(
+    env: &Env,
+) -> (
+    StakingRewardsContractClient<'static>,
+    TokenClient<'static>,
+    Address,
+) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+    let (token_address, token, token_admin) = create_token(env, &admin);
+
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(env, &contract_id);
+    // Same token for stake and rewards keeps the fixture small
+    client.initialize(&admin, &token_address, &token_address);
+    token_admin.mint(&user, &1_000_000);
+    token_admin.mint(&contract_id, &100_000);
+    (client, token, user)
+}
+
+#[test]
+fn test_position_mint_and_views() {
+    let env = Env::default();
+    let (client, token, user) = setup_positions(&env);
+
+    let id = client.stake_position(&user, &1000, &2);
+    let position = client.position_of(&id).unwrap();
+    assert_eq!(position.amount, 1000);
+    assert_eq!(position.lock_end, 90 * 24 * 60 * 60);
+    assert_eq!(position.multiplier_bps, 1500);
+    assert_eq!(client.position_owner(&id), Some(user.clone()));
+    assert_eq!(
+        client.positions_of(&user, &Pagination::first(10)),
+        (soroban_sdk::vec![&env, id], None)
+    );
+    assert_eq!(token.balance(&user), 999_000);
+
+    let second = client.stake_position(&user, &500, &0);
+    assert_eq!(second, id + 1);
+    assert_eq!(
+        client.positions_of(&user, &Pagination::first(1)),
+        (soroban_sdk::vec![&env, id], Some(1))
+    );
+}
+
+#[test]
+fn test_position_transfer_moves_rewards_and_principal() {
+    let env = Env::default();
+    let (client, token, user) = setup_positions(&env);
+    let buyer = Address::generate(&env);
+
+    let id = client.stake_position(&user, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 24 * 60 * 60);
+    client.transfer_position(&user, &buyer, &id);
+    assert!(client
+        .positions_of(&user, &Pagination::first(10))
+        .0
+        .is_empty());
+
+    let result = client.try_claim_position(&user, &id);
+    assert_eq!(result, Err(Ok(ContractError::NotPositionOwner)));
+    // 365_000 * 5% * 10/365 days
+    assert_eq!(client.claim_position(&buyer, &id), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 31 * 24 * 60 * 60);
+    assert_eq!(client.unstake_position(&buyer, &id), 365_000);
+    assert_eq!(token.balance(&buyer), 365_500);
+    assert!(client.position_of(&id).is_none());
+    assert_eq!(client.position_owner(&id), None);
+}
+
+#[test]
+fn test_staked_balance_history() {
+    let env = Env::default();
+    let (client, _, user) = setup_positions(&env);
+    let buyer = Address::generate(&env);
+    // Staked before history was kept
+    let legacy = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        let stake = UserStake {
+            amount: 700,
+            pool_id: 0,
+            start_timestamp: 0,
+            last_claim_timestamp: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&(storage_keys::USER_STAKE, legacy.clone()), &stake);
+    });
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    client.stake(&user, &1_000, &0);
+    let id = client.stake_position(&user, &500, &0);
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    client.transfer_position(&user, &buyer, &id);
+    client.stake(&user, &200, &0);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 30;
+        li.timestamp = 31 * 24 * 60 * 60;
+    });
+    client.unstake(&legacy);
+
+    assert_eq!(client.staked_balance_at(&user, &9), 0);
+    assert_eq!(client.staked_balance_at(&user, &10), 1_500);
+    assert_eq!(client.staked_balance_at(&user, &19), 1_500);
+    assert_eq!(client.staked_balance_at(&user, &20), 1_200);
+    assert_eq!(client.staked_balance_at(&buyer, &19), 0);
+    assert_eq!(client.staked_balance_at(&buyer, &25), 500);
+    assert_eq!(client.staked_balance_at(&legacy, &0), 700);
+    assert_eq!(client.staked_balance_at(&legacy, &29), 700);
+    assert_eq!(client.staked_balance_at(&legacy, &30), 0);
+}
+
+#[test]
+fn test_position_approval_and_early_unstake() {
+    let env = Env::default();
+    let (client, _, user) = setup_positions(&env);
+    let market = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+
+    let id = client.stake_position(&user, &1000, &1);
+    let result = client.try_transfer_position_from(&market, &user, &liquidator, &id);
+    assert_eq!(result, Err(Ok(ContractError::NotApproved)));
+
+    client.approve_position(&user, &market, &id);
+    client.transfer_position_from(&market, &user, &liquidator, &id);
+    assert_eq!(client.position_owner(&id), Some(liquidator.clone()));
+
+    // Approval does not survive the transfer
+    let result = client.try_transfer_position_from(&market, &liquidator, &user, &id);
+    assert_eq!(result, Err(Ok(ContractError::NotApproved)));
+
+    // Still locked: 10% penalty
+    assert_eq!(client.unstake_position(&liquidator, &id), 900);
+}
+
+/// Gauge controller that gives pool 1 a quarter and pool 2 the rest
+#[contract]
+pub struct MockGauges;
+
+#[contractimpl]
+impl MockGauges {
+    pub fn gauge_weight(_env: Env, _target: Address, pool_id: u32) -> u32 {
+        match pool_id {
+            1 => 2_500,
+            2 => 7_500,
+            _ => 0,
+        }
+    }
+}
+
+#[test]
+fn test_pool_rates_follow_gauge_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (staking_token, _, _) = create_token(&env, &admin);
+    let (reward_token, _, _) = create_token(&env, &admin);
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &staking_token, &reward_token);
+
+    let gauges = env.register_contract(None, MockGauges);
+    assert_eq!(
+        client.try_sync_pool_rates(),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_set_gauge_controller(&Address::generate(&env), &gauges, &2_000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_gauge_controller(&admin, &gauges, &2_000);
+
+    let pools = client.sync_pool_rates();
+    assert_eq!(pools.get(0).unwrap().apy_bps, 0);
+    assert_eq!(pools.get(1).unwrap().apy_bps, 500);
+    assert_eq!(pools.get(2).unwrap().apy_bps, 1_500);
+    // Lockups are untouched
+    assert_eq!(pools.get(2).unwrap().lockup_seconds, 90 * 24 * 60 * 60);
+    assert_eq!(client.get_pools(), pools);
+}
+
+/// Staking contract with one token for stake and rewards, and two
+/// funded stakers
+fn setup_stakers(env: &Env) -> (StakingRewardsContractClient<'static>, Address, [Address; 2]) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let users = [Address::generate(env), Address::generate(env)];
+    let (token_address, _, token_admin) = create_token(env, &admin);
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token_address, &token_address);
+    for user in users.iter() {
+        token_admin.mint(user, &1_000_000);
+    }
+    token_admin.mint(&contract_id, &100_000);
+    (client, admin, users)
+}
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn test_rewards_accrue_regardless_of_claim_frequency() {
+    let env = Env::default();
+    let (client, _, [alice, bob]) = setup_stakers(&env);
+    // 365_000 at 5% earns 50 a day
+    client.stake(&alice, &365_000, &0);
+    client.stake(&bob, &365_000, &0);
+
+    let mut alice_total = 0;
+    for day in 1..=10 {
+        env.ledger().with_mut(|li| li.timestamp = day * DAY);
+        alice_total += client.claim(&alice);
+    }
+    assert_eq!(alice_total, 500);
+    assert_eq!(client.claim(&bob), 500);
+
+    let pool = client.pool_rewards(&0);
+    assert_eq!(pool.total_staked, 730_000);
+    assert_eq!(pool.paid, 1_000);
+    assert!(pool.paid * math::REWARD_SCALE <= pool.emitted_scaled);
+}
+
+#[test]
+fn test_top_up_keeps_accrued_rewards() {
+    let env = Env::default();
+    let (client, _, [alice, _]) = setup_stakers(&env);
+    client.stake(&alice, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    assert_eq!(client.get_pending_rewards(&alice), 500);
+
+    client.stake(&alice, &365_000, &0);
+    assert_eq!(client.get_pending_rewards(&alice), 500);
+    env.ledger().with_mut(|li| li.timestamp = 20 * DAY);
+    assert_eq!(client.get_pending_rewards(&alice), 1_500);
+
+    // Moving pools settles the old pool first
+    client.stake(&alice, &1, &1);
+    assert_eq!(client.pool_rewards(&0).total_staked, 0);
+    assert_eq!(client.pool_rewards(&1).total_staked, 730_001);
+    assert_eq!(client.claim(&alice), 1_500);
+}
+
+#[test]
+fn test_rate_change_only_applies_from_sync() {
+    let env = Env::default();
+    let (client, admin, [alice, _]) = setup_stakers(&env);
+    client.stake(&alice, &365_000, &2);
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    // 15% for ten days
+    assert_eq!(client.get_pending_rewards(&alice), 1_500);
+
+    let gauges = env.register_contract(None, MockGauges);
+    client.set_gauge_controller(&admin, &gauges, &4_000);
+    assert_eq!(client.sync_pool_rates().get(2).unwrap().apy_bps, 3_000);
+    env.ledger().with_mut(|li| li.timestamp = 20 * DAY);
+    // Then 30% for ten more
+    assert_eq!(client.claim(&alice), 4_500);
+}
+
+#[test]
+fn test_total_paid_never_exceeds_total_emitted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (stake_token, _, stake_admin) = create_token(&env, &admin);
+    let (reward_token, reward, reward_admin) = create_token(&env, &admin);
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &stake_token, &reward_token);
+    reward_admin.mint(&contract_id, &1_000_000_000_000);
+    let users = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for user in users.iter() {
+        stake_admin.mint(user, &1_000_000_000_000);
+    }
+
+    // Fixed pseudo-random mix of stakes, top-ups, pool moves, claims and
+    // unstakes at uneven intervals
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let mut next = |bound: u64| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed % bound
+    };
+    for _ in 0..24 {
+        env.ledger().with_mut(|li| li.timestamp += next(20 * DAY));
+        let user = &users[next(3) as usize];
+        match next(4) {
+            0 | 1 => {
+                let amount = 1 + next(1_000_000_000) as i128;
+                client.stake(user, &amount, &(next(3) as u32));
+            }
+            2 => {
+                let _ = client.try_claim(user);
+            }
+            _ => {
+                let _ = client.try_unstake(user);
+            }
+        }
+
+        // Rewards follow a stake that moves pools, so the bound holds
+        // across pools rather than per pool
+        let (mut paid, mut emitted, mut staked) = (0i128, 0i128, 0i128);
+        for pool in 0..3 {
+            let rewards = client.pool_rewards(&pool);
+            paid += rewards.paid;
+            emitted += rewards.emitted_scaled;
+            staked += rewards.total_staked;
+        }
+        assert!(paid * math::REWARD_SCALE <= emitted);
+        let claimed: i128 = users.iter().map(|user| reward.balance(user)).sum();
+        assert_eq!(paid, claimed);
+        let held: i128 = users.iter().map(|user| client.staked_balance(user)).sum();
+        assert_eq!(staked, held);
+    }
+    assert!(client.pool_rewards(&0).paid + client.pool_rewards(&2).paid > 0);
+}
+
+#[test]
+fn test_add_to_stake_blends_lock_and_rate() {
+    let env = Env::default();
+    let (client, admin, [alice, _]) = setup_stakers(&env);
+    let id = client.stake_position(&alice, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 15 * DAY);
+    assert_eq!(client.get_position_rewards(&id), 750);
+
+    // Pool 0 drops to 0%; the top-up joins at that rate
+    let gauges = env.register_contract(None, MockGauges);
+    client.set_gauge_controller(&admin, &gauges, &2_000);
+    client.sync_pool_rates();
+    assert_eq!(client.lock_policy(), LockPolicy::Weighted);
+    let position = client.add_to_stake(&alice, &id, &365_000);
+    assert_eq!(position.amount, 730_000);
+    assert_eq!(position.multiplier_bps, 250);
+    // Half the principal has 15 days left, half a fresh 30
+    assert_eq!(position.lock_end, 15 * DAY + 45 * DAY / 2);
+    assert_eq!(client.get_position_rewards(&id), 750);
+
+    let captured = assert_standard_event(&env, topics::STAKE, |_| true);
+    let event: PositionStakeEvent = payload(&captured);
+    assert_eq!((event.amount, event.total), (365_000, 730_000));
+    assert_eq!(
+        captured.metadata::<u64>(&env, metadata_keys::ID_KEY),
+        Some(id)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 25 * DAY);
+    // 730_000 at 2.5% for ten days on top
+    assert_eq!(client.get_position_rewards(&id), 1_250);
+    assert_eq!(client.unstake_partial(&alice, &id, &100_000), 90_000);
+    let captured = assert_standard_event(&env, topics::UNSTAKE, |_| true);
+    let event: PositionStakeEvent = payload(&captured);
+    assert_eq!((event.total, event.penalty), (630_000, 10_000));
+    assert_eq!(
+        captured.metadata::<u64>(&env, metadata_keys::ID_KEY),
+        Some(id)
+    );
+
+    assert_eq!(client.claim_position(&alice, &id), 1_250);
+    let result = client.try_unstake_partial(&alice, &id, &630_000);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    let result = client.try_add_to_stake(&admin, &id, &1);
+    assert_eq!(result, Err(Ok(ContractError::NotPositionOwner)));
+}
+
+#[test]
+fn test_reset_policy_restarts_the_lock() {
+    let env = Env::default();
+    let (client, admin, [alice, bob]) = setup_stakers(&env);
+    let result = client.try_set_lock_policy(&bob, &LockPolicy::Reset);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    client.set_lock_policy(&admin, &LockPolicy::Reset);
+
+    let id = client.stake_position(&alice, &365_000, &1);
+    env.ledger().with_mut(|li| li.timestamp = 50 * DAY);
+    let position = client.add_to_stake(&alice, &id, &1);
+    assert_eq!((position.start, position.lock_end), (50 * DAY, 110 * DAY));
+    assert_eq!(position.multiplier_bps, 1_000);
+
+    // Past the original lock, but inside the restarted one
+    env.ledger().with_mut(|li| li.timestamp = 70 * DAY);
+    assert_eq!(client.unstake_partial(&alice, &id, &1_000), 900);
+    env.ledger().with_mut(|li| li.timestamp = 110 * DAY);
+    assert_eq!(client.unstake_partial(&alice, &id, &1_000), 1_000);
+}
+
+#[test]
+fn test_unstake_cooldown_request_cancel_and_withdraw() {
+    let env = Env::default();
+    let (client, admin, [alice, bob]) = setup_stakers(&env);
+    let result = client.try_set_unstake_cooldown(&bob, &(7 * DAY));
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    client.set_unstake_cooldown(&admin, &(7 * DAY));
+
+    client.stake(&alice, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    let result = client.try_unstake(&alice);
+    assert_eq!(result, Err(Ok(ContractError::CooldownRequired)));
+
+    // Inside the 30 day lockup, so the penalty applies
+    assert_eq!(client.request_unstake(&alice), 17 * DAY);
+    let captured = assert_standard_event(&env, topics::UNSTAKE_REQUESTED, |_| true);
+    let event: UnstakeRequestEvent = payload(&captured);
+    assert_eq!((event.amount, event.available_at), (328_500, 17 * DAY));
+    assert_eq!(client.pool_rewards(&0).total_staked, 0);
+    assert_eq!(client.get_stake(&alice), None);
+    let result = client.try_request_unstake(&alice);
+    assert_eq!(result, Err(Ok(ContractError::UnstakePending)));
+    let result = client.try_stake(&alice, &1, &0);
+    assert_eq!(result, Err(Ok(ContractError::UnstakePending)));
+    let result = client.try_withdraw(&alice);
+    assert_eq!(result, Err(Ok(ContractError::CooldownActive)));
+
+    // Cancelling restores the stake and its rewards; the cooldown earned
+    // nothing
+    env.ledger().with_mut(|li| li.timestamp = 12 * DAY);
+    client.cancel_unstake(&alice);
+    assert_standard_event(&env, topics::UNSTAKE_CANCELLED, |_| true);
+    assert_eq!(client.unstake_request(&alice), None);
+    assert_eq!(client.pool_rewards(&0).total_staked, 365_000);
+    assert_eq!(client.get_pending_rewards(&alice), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 40 * DAY);
+    assert_eq!(client.request_unstake(&alice), 47 * DAY);
+    assert_eq!(client.unstake_request(&alice).unwrap().amount, 365_000);
+    env.ledger().with_mut(|li| li.timestamp = 47 * DAY);
+    assert_eq!(client.withdraw(&alice), 365_000);
+    let captured = assert_standard_event(&env, topics::UNSTAKE, |_| true);
+    let event: UnstakeRequestEvent = payload(&captured);
+    assert_eq!(event.amount, 365_000);
+    let result = client.try_withdraw(&alice);
+    assert_eq!(result, Err(Ok(ContractError::NoUnstakeRequest)));
+
+    // Turning the cooldown off brings back the one-step unstake
+    client.set_unstake_cooldown(&admin, &0);
+    client.stake(&bob, &1_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 80 * DAY);
+    assert_eq!(client.unstake(&bob), 1_000);
+}
+
+mod baseline {
+    use soroban_sdk::contracttype;
+
+    /// `UserStake` as stored by the first deployed release
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct UserStake {
+        pub amount: i128,
+        pub pool_id: u32,
+        pub start_timestamp: u64,
+        pub last_claim_timestamp: u64,
+    }
+}
+
+#[test]
+fn test_baseline_stored_stakes_keep_accruing() {
+    let env = Env::default();
+    let (client, _, [alice, bob]) = setup_stakers(&env);
+    env.ledger().with_mut(|li| li.timestamp = 5 * DAY);
+    // A stake written by the first release, last claimed on day 2
+    let stored = baseline::UserStake {
+        amount: 73_000,
+        pool_id: 0,
+        start_timestamp: 0,
+        last_claim_timestamp: 2 * DAY,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&(storage_keys::USER_STAKE, alice.clone()), &stored);
+    });
+    assert_eq!(client.get_stake(&alice).unwrap().amount, 73_000);
+
+    // 73_000 at 5% earns 10 a day, counted from the last claim
+    assert_eq!(client.get_pending_rewards(&alice), 30);
+    env.ledger().with_mut(|li| li.timestamp = 10 * DAY);
+    assert_eq!(client.claim(&alice), 80);
+    assert_eq!(client.pool_rewards(&0).total_staked, 73_000);
+
+    client.stake(&bob, &73_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 20 * DAY);
+    client.compound(&alice);
+    assert_eq!(client.get_stake(&alice).unwrap().amount, 73_100);
+    assert_eq!(client.claim(&bob), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 40 * DAY);
+    assert_eq!(client.unstake(&alice), 73_100);
+    assert_eq!(client.pool_rewards(&0).total_staked, 73_000);
+}
+
+/// Staking contract plus a boost collection with one token minted to the
+/// returned holder
+fn setup_boost(
+    env: &Env,
+) -> (
+    StakingRewardsContractClient<'static>,
+    nft::NftContractClient<'static>,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let holder = Address::generate(env);
+    let (token_address, _, token_admin) = create_token(env, &admin);
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token_address, &token_address);
+    token_admin.mint(&holder, &1_000_000);
+    token_admin.mint(&contract_id, &100_000);
+
+    let collection = env.register_contract(None, nft::NftContract);
+    let nfts = nft::NftContractClient::new(env, &collection);
+    let name = soroban_sdk::String::from_str(env, "Boost");
+    nfts.initialize(&admin, &name, &name);
+    nfts.mint(
+        &admin,
+        &holder,
+        &soroban_sdk::String::from_str(env, "ipfs://boost"),
+    );
+    (client, nfts, admin, holder)
+}
+
+#[test]
+fn test_nft_holders_get_boosted_rewards() {
+    let env = Env::default();
+    let (client, nfts, admin, holder) = setup_boost(&env);
+    let other = Address::generate(&env);
+
+    let result = client.try_set_boost_collection(&admin, &nfts.address, &10_000);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBoost)));
+    let result = client.try_set_boost_collection(&holder, &nfts.address, &15_000);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    client.set_boost_collection(&admin, &nfts.address, &15_000);
+    assert_eq!(client.boost_multiplier(&holder), 15_000);
+    assert_eq!(client.boost_multiplier(&other), 10_000);
+
+    let id = client.stake_position(&holder, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 24 * 60 * 60);
+    // 365_000 * 5% * 10/365 days, times 1.5
+    assert_eq!(client.get_position_rewards(&id), 750);
+    assert_eq!(client.claim_position(&holder, &id), 750);
+
+    let captured = assert_standard_event(&env, topics::REWARDS_CLAIMED, |_| true);
+    let event: RewardsClaimedEvent = payload(&captured);
+    assert_eq!(event.position_id, Some(id));
+    assert_eq!(event.multiplier_bps, 15_000);
+    assert_eq!(
+        captured.metadata::<u32>(&env, metadata_keys::MULTIPLIER_KEY),
+        Some(15_000)
+    );
+}
+
+#[test]
+fn test_boost_is_cached_between_claims() {
+    let env = Env::default();
+    let (client, nfts, admin, holder) = setup_boost(&env);
+    let buyer = Address::generate(&env);
+    client.set_boost_collection(&admin, &nfts.address, &20_000);
+
+    let id = client.stake_position(&holder, &365_000, &0);
+    env.ledger().with_mut(|li| li.timestamp = 24 * 60 * 60);
+    assert_eq!(client.claim_position(&holder, &id), 100);
+
+    // Selling the NFT does not drop the boost until the cache expires
+    nfts.transfer(&holder, &buyer, &1);
+    env.ledger().with_mut(|li| li.timestamp += 30 * 60);
+    assert_eq!(client.boost_multiplier(&holder), 20_000);
+    env.ledger().with_mut(|li| li.timestamp += 30 * 60);
+    assert_eq!(client.boost_multiplier(&holder), 10_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 2 * 24 * 60 * 60);
+    assert_eq!(client.claim_position(&holder, &id), 50);
+
+    // Relisting invalidates cached multipliers straight away
+    client.set_boost_cache_ttl(&admin, &(7 * 24 * 60 * 60));
+    nfts.transfer(&buyer, &holder, &1);
+    assert_eq!(client.boost_multiplier(&holder), 10_000);
+    client.set_boost_collection(&admin, &nfts.address, &20_000);
+    assert_eq!(client.boost_multiplier(&holder), 20_000);
+
+    client.remove_boost_collection(&admin, &nfts.address);
+    assert!(client.boost_collections().is_empty());
+    let result = client.try_remove_boost_collection(&admin, &nfts.address);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBoost)));
+}
+
+/// Stake, claim and unstake sequence kept as a replayable scenario; the
+/// staking token held by the contract must cover every open stake
+const STAKE_CLAIM_UNSTAKE: &str = "
+@0 stake i10000 u0
+@1 stake i5000 u0
++86400
+@0 claim
+@1 unstake      # early, pays the penalty
+@1 unstake      # nothing left
++2592000
+@0 stake i250 u0
+@0 unstake
+@0 claim
+";
+
+#[test]
+fn test_replay_stake_claim_unstake_scenario() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let actors = [Address::generate(&env), Address::generate(&env)];
+    let (staking_token_address, staking_token, staking_token_admin) = create_token(&env, &admin);
+    let (reward_token_address, _reward_token, reward_token_admin) = create_token(&env, &admin);
+    let contract_id = env.register_contract(None, StakingRewardsContract);
+    let client = StakingRewardsContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &staking_token_address, &reward_token_address);
+    for actor in &actors {
+        staking_token_admin.mint(actor, &20_000);
+    }
+    reward_token_admin.mint(&contract_id, &100_000);
+
+    let scenario: Scenario = STAKE_CLAIM_UNSTAKE.parse().unwrap();
+    assert_eq!(
+        scenario.to_string().parse::<Scenario>(),
+        Ok(scenario.clone())
+    );
+    let outcomes = scenario.replay(&env, &contract_id, &actors);
+
+    let nothing_to_claim = InvokeError::Contract(ContractError::NothingToClaim as u32);
+    assert_eq!(outcomes.len(), 8);
+    assert_eq!(outcomes[4].err(), Some(nothing_to_claim));
+    assert_eq!(outcomes[7].err(), Some(nothing_to_claim));
+    assert_eq!(outcomes.iter().filter(|outcome| outcome.is_ok()).count(), 6);
+
+    let staked: i128 = actors
+        .iter()
+        .map(|actor| client.staked_balance(actor))
+        .sum();
+    assert_eq!(staked, 0);
+    // Both early unstake penalties stay behind
+    assert_eq!(staking_token.balance(&contract_id), 500 + 1025);
+}
@@ -0,0 +1,296 @@
+//! Streaming Payments
+//!
+//! A sender escrows `rate_per_second * (stop_time - start_time)` tokens and
+//! the recipient can withdraw whatever has accrued at any moment. Either party
+//! may cancel: the recipient receives everything streamed so far and the
+//! sender is refunded the remainder.
+
+#![no_std]
+
+use shared::events::{
+    EventEmitter, StreamCancelledEvent, StreamCreatedEvent, StreamWithdrawnEvent,
+};
+use shared::names::NameServiceClient;
+use shared::pagination::{IndexedList, Pagination};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StreamError {
+    InvalidAmount = 1,
+    InvalidTimeRange = 2,
+    StreamNotFound = 3,
+    Unauthorized = 4,
+    InsufficientBalance = 5,
+    Overflow = 6,
+    /// The recipient name is unregistered or expired
+    NameNotFound = 7,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    Stream(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    /// Total amount escrowed when the stream was created
+    pub deposit: i128,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub stop_time: u64,
+    /// Amount already withdrawn by the recipient
+    pub withdrawn: i128,
+}
+
+impl Stream {
+    /// Amount released to the recipient as of `now`, including withdrawals
+    pub fn streamed_amount(&self, now: u64) -> i128 {
+        if now <= self.start_time {
+            return 0;
+        }
+        let elapsed = now.min(self.stop_time) - self.start_time;
+        self.rate_per_second * elapsed as i128
+    }
+}
+
+#[contract]
+pub struct StreamsContract;
+
+#[contractimpl]
+impl StreamsContract {
+    /// Open a stream of `rate_per_second` tokens from `sender` to `recipient`
+    /// between `start_time` and `stop_time`. The full deposit is escrowed up
+    /// front. Returns the stream ID.
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        rate_per_second: i128,
+        start_time: u64,
+        stop_time: u64,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        if rate_per_second <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if stop_time <= start_time || start_time < env.ledger().timestamp() {
+            return Err(StreamError::InvalidTimeRange);
+        }
+        if sender == recipient {
+            return Err(StreamError::Unauthorized);
+        }
+
+        let deposit = rate_per_second
+            .checked_mul((stop_time - start_time) as i128)
+            .ok_or(StreamError::Overflow)?;
+
+        token::Client::new(&env, &token).transfer(
+            &sender,
+            &env.current_contract_address(),
+            &deposit,
+        );
+
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::NextId, &id);
+
+        let stream = Stream {
+            id,
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            token: token.clone(),
+            deposit,
+            rate_per_second,
+            start_time,
+            stop_time,
+            withdrawn: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(id), &stream);
+        add_user_stream(&env, &sender, id);
+        add_user_stream(&env, &recipient, id);
+
+        EventEmitter::stream_created(
+            &env,
+            StreamCreatedEvent {
+                stream_id: id,
+                sender,
+                recipient,
+                token,
+                deposit,
+                rate_per_second,
+                start_time,
+                stop_time,
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Open a stream to whichever address `name` resolves to in the name
+    /// service `names` right now. The stream is fixed to that address.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_to_name(
+        env: Env,
+        sender: Address,
+        names: Address,
+        name: Symbol,
+        token: Address,
+        rate_per_second: i128,
+        start_time: u64,
+        stop_time: u64,
+    ) -> Result<u64, StreamError> {
+        let recipient = NameServiceClient::new(&env, &names)
+            .resolve(&name)
+            .ok_or(StreamError::NameNotFound)?;
+        Self::create_stream(
+            env,
+            sender,
+            recipient,
+            token,
+            rate_per_second,
+            start_time,
+            stop_time,
+        )
+    }
+
+    /// Withdraw `amount` of the accrued balance to the recipient.
+    pub fn withdraw_from_stream(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        let available = stream.streamed_amount(now) - stream.withdrawn;
+        if amount > available {
+            return Err(StreamError::InsufficientBalance);
+        }
+
+        stream.withdrawn += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+
+        token::Client::new(&env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &stream.recipient,
+            &amount,
+        );
+
+        EventEmitter::stream_withdrawn(
+            &env,
+            StreamWithdrawnEvent {
+                stream_id,
+                recipient: stream.recipient,
+                amount,
+                timestamp: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a stream. The recipient receives everything streamed but not
+    /// yet withdrawn and the sender is refunded the unstreamed remainder.
+    /// Callable by either party.
+    pub fn cancel_stream(env: Env, caller: Address, stream_id: u64) -> Result<(), StreamError> {
+        caller.require_auth();
+
+        let stream = load_stream(&env, stream_id)?;
+        if caller != stream.sender && caller != stream.recipient {
+            return Err(StreamError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let streamed = stream.streamed_amount(now);
+        let recipient_amount = streamed - stream.withdrawn;
+        let sender_amount = stream.deposit - streamed;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Stream(stream_id));
+
+        let client = token::Client::new(&env, &stream.token);
+        let this = env.current_contract_address();
+        if recipient_amount > 0 {
+            client.transfer(&this, &stream.recipient, &recipient_amount);
+        }
+        if sender_amount > 0 {
+            client.transfer(&this, &stream.sender, &sender_amount);
+        }
+
+        EventEmitter::stream_cancelled(
+            &env,
+            StreamCancelledEvent {
+                stream_id,
+                cancelled_by: caller,
+                recipient_amount,
+                sender_amount,
+                timestamp: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Get a stream by ID. Cancelled streams are removed.
+    pub fn get_stream(env: Env, stream_id: u64) -> Option<Stream> {
+        env.storage().persistent().get(&DataKey::Stream(stream_id))
+    }
+
+    /// Current withdrawable (recipient) or refundable (sender) balance of `who`.
+    pub fn balance_of(env: Env, stream_id: u64, who: Address) -> Result<i128, StreamError> {
+        let stream = load_stream(&env, stream_id)?;
+        let streamed = stream.streamed_amount(env.ledger().timestamp());
+
+        if who == stream.recipient {
+            Ok(streamed - stream.withdrawn)
+        } else if who == stream.sender {
+            Ok(stream.deposit - streamed)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// One page of the IDs of the streams `user` has sent or received,
+    /// and the cursor of the next page.
+    pub fn get_user_streams(env: Env, user: Address, page: Pagination) -> (Vec<u64>, Option<u32>) {
+        user_streams(&user).page(&env, &page)
+    }
+}
+
+fn load_stream(env: &Env, stream_id: u64) -> Result<Stream, StreamError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stream(stream_id))
+        .ok_or(StreamError::StreamNotFound)
+}
+
+/// Ids of the streams `user` has sent or received
+fn user_streams(user: &Address) -> IndexedList<Address> {
+    IndexedList::new(symbol_short!("u_streams"), user.clone())
+}
+
+fn add_user_stream(env: &Env, user: &Address, stream_id: u64) {
+    user_streams(user).push(env, &stream_id);
+}
+
+#[cfg(test)]
+mod test;
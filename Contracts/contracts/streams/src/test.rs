@@ -0,0 +1,231 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{topics, StandardEventV2};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, Symbol, TryFromVal,
+};
+
+struct Setup {
+    env: Env,
+    client: StreamsContractClient<'static>,
+    token: TokenClient<'static>,
+    sender: Address,
+    recipient: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let token_id = env.register_stellar_asset_contract(admin);
+    StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000);
+
+    let contract_id = env.register_contract(None, StreamsContract);
+    let client = StreamsContractClient::new(&env, &contract_id);
+    let token = TokenClient::new(&env, &token_id);
+
+    Setup {
+        env,
+        client,
+        token,
+        sender,
+        recipient,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+#[test]
+fn test_create_stream_escrows_deposit() {
+    let s = setup();
+    let id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+
+    let stream = s.client.get_stream(&id).unwrap();
+    assert_eq!(stream.deposit, 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+    assert_eq!(s.token.balance(&s.sender), 999_000);
+    assert_eq!(
+        s.client
+            .get_user_streams(&s.recipient, &Pagination::first(10)),
+        (soroban_sdk::vec![&s.env, id], None)
+    );
+}
+
+#[test]
+fn test_create_stream_rejects_invalid_range() {
+    let s = setup();
+    let result = s.client.try_create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_100,
+        &1_100,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidTimeRange)));
+
+    let result = s.client.try_create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &0,
+        &1_000,
+        &1_100,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidAmount)));
+}
+
+#[test]
+fn test_withdraw_accrued_balance() {
+    let s = setup();
+    let id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+
+    set_time(&s.env, 1_040);
+    assert_eq!(s.client.balance_of(&id, &s.recipient), 400);
+    assert_eq!(s.client.balance_of(&id, &s.sender), 600);
+
+    s.client.withdraw_from_stream(&id, &300);
+    assert_eq!(s.token.balance(&s.recipient), 300);
+    assert_eq!(s.client.balance_of(&id, &s.recipient), 100);
+
+    let result = s.client.try_withdraw_from_stream(&id, &101);
+    assert_eq!(result, Err(Ok(StreamError::InsufficientBalance)));
+
+    // Accrual stops at stop_time
+    set_time(&s.env, 5_000);
+    assert_eq!(s.client.balance_of(&id, &s.recipient), 700);
+}
+
+#[test]
+fn test_cancel_splits_remaining_balance() {
+    let s = setup();
+    let id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+
+    set_time(&s.env, 1_025);
+    s.client.withdraw_from_stream(&id, &50);
+    s.client.cancel_stream(&s.sender, &id);
+
+    assert_eq!(s.token.balance(&s.recipient), 250);
+    assert_eq!(s.token.balance(&s.sender), 999_750);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert!(s.client.get_stream(&id).is_none());
+}
+
+#[test]
+fn test_cancel_by_third_party_rejected() {
+    let s = setup();
+    let id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_cancel_stream(&stranger, &id);
+    assert_eq!(result, Err(Ok(StreamError::Unauthorized)));
+}
+
+#[test]
+fn test_create_stream_emits_standard_event() {
+    let s = setup();
+    s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+
+    let prefix = Symbol::new(&s.env, shared::events::EVENT_PREFIX);
+    let standard = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .unwrap();
+
+    assert_eq!(standard.topic, topics::STREAM_CREATED);
+    assert_eq!(standard.user_address, s.sender);
+    let rate = standard
+        .metadata
+        .get(shared::events::metadata_keys::RATE_KEY)
+        .unwrap();
+    assert_eq!(i128::try_from_val(&s.env, &rate).unwrap(), 10);
+}
+
+#[test]
+fn test_create_stream_to_name_resolves_recipient() {
+    let s = setup();
+    let names =
+        names::NameServiceClient::new(&s.env, &s.env.register_contract(None, names::NameService));
+    let fees = names::FeeConfig {
+        token: s.token.address.clone(),
+        treasury: Address::generate(&s.env),
+        fee_per_year: 0,
+    };
+    names.initialize(&Address::generate(&s.env), &fees);
+    let name = Symbol::new(&s.env, "bob");
+
+    let result = s.client.try_create_stream_to_name(
+        &s.sender,
+        &names.address,
+        &name,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+    assert_eq!(result, Err(Ok(StreamError::NameNotFound)));
+
+    names.register(&s.recipient, &name, &1);
+    let id = s.client.create_stream_to_name(
+        &s.sender,
+        &names.address,
+        &name,
+        &s.token.address,
+        &10,
+        &1_000,
+        &1_100,
+    );
+    assert_eq!(s.client.get_stream(&id).unwrap().recipient, s.recipient);
+}
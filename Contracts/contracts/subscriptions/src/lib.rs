@@ -0,0 +1,376 @@
+//! Subscription billing
+//!
+//! Merchants publish plans: a token, an amount charged every `interval`
+//! seconds, a grace period and how many failed charges they tolerate.
+//! Subscribers join a plan with a cap on the total they will ever pay
+//! under it; joining raises their token allowance to this contract by the
+//! cap and pays the first period.
+//!
+//! Later periods are pulled by keepers: anyone can call `charge` once a
+//! period is due. A charge that fails (balance or allowance too low) marks
+//! the subscription past due and is retried, spread evenly over the grace
+//! period. The subscription is cancelled automatically after the plan's
+//! last tolerated failure, when the grace period runs out, or when the
+//! next charge would exceed the cap. Subscribers and merchants can cancel
+//! at any time. Cancelling does not shrink the token allowance; the
+//! contract just never draws on it for a cancelled subscription.
+
+#![no_std]
+
+use shared::events::{BillingChargeEvent, BillingEvent, EventEmitter};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BillingError {
+    PlanNotFound = 1,
+    PlanInactive = 2,
+    SubscriptionNotFound = 3,
+    Unauthorized = 4,
+    InvalidPlan = 5,
+    InvalidCap = 6,
+    NotDue = 7,
+    Cancelled = 8,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub merchant: Address,
+    pub token: Address,
+    /// Charged once per interval
+    pub amount: i128,
+    pub interval: u64,
+    /// Seconds a due charge may stay unpaid before the subscription lapses
+    pub grace_period: u64,
+    /// Failed charges in a row that cancel the subscription
+    pub max_failures: u32,
+    /// Inactive plans take no new subscribers
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStatus {
+    Active,
+    PastDue,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub subscriber: Address,
+    pub plan_id: u64,
+    /// Most the subscriber will pay under this subscription
+    pub cap: i128,
+    /// Paid so far
+    pub charged: i128,
+    /// Start of the unpaid period
+    pub next_charge: u64,
+    /// Earliest time a keeper may attempt the next charge
+    pub retry_at: u64,
+    pub failures: u32,
+    pub status: SubscriptionStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextPlanId,
+    Plan(u64),
+    NextSubscriptionId,
+    Subscription(u64),
+}
+
+#[contract]
+pub struct SubscriptionBilling;
+
+#[contractimpl]
+impl SubscriptionBilling {
+    /// Publish a plan. Returns its id.
+    pub fn create_plan(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        amount: i128,
+        interval: u64,
+        grace_period: u64,
+        max_failures: u32,
+    ) -> Result<u64, BillingError> {
+        merchant.require_auth();
+        if amount <= 0 || interval == 0 || max_failures == 0 {
+            return Err(BillingError::InvalidPlan);
+        }
+        let id = next_id(&env, &DataKey::NextPlanId);
+        let plan = Plan {
+            merchant,
+            token,
+            amount,
+            interval,
+            grace_period,
+            max_failures,
+            active: true,
+        };
+        env.storage().persistent().set(&DataKey::Plan(id), &plan);
+        Ok(id)
+    }
+
+    /// Open or close a plan to new subscribers. Existing subscriptions
+    /// keep being charged.
+    pub fn set_plan_active(
+        env: Env,
+        merchant: Address,
+        plan_id: u64,
+        active: bool,
+    ) -> Result<(), BillingError> {
+        merchant.require_auth();
+        let mut plan = load_plan(&env, plan_id)?;
+        if plan.merchant != merchant {
+            return Err(BillingError::Unauthorized);
+        }
+        plan.active = active;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(plan_id), &plan);
+        Ok(())
+    }
+
+    /// Subscribe to `plan_id`, paying at most `cap` over its lifetime. The
+    /// subscriber's allowance to this contract grows by `cap` and expires
+    /// at `expiration_ledger`. The first period is charged right away.
+    pub fn subscribe(
+        env: Env,
+        subscriber: Address,
+        plan_id: u64,
+        cap: i128,
+        expiration_ledger: u32,
+    ) -> Result<u64, BillingError> {
+        subscriber.require_auth();
+        let plan = load_plan(&env, plan_id)?;
+        if !plan.active {
+            return Err(BillingError::PlanInactive);
+        }
+        if cap < plan.amount {
+            return Err(BillingError::InvalidCap);
+        }
+
+        let client = token::Client::new(&env, &plan.token);
+        let this = env.current_contract_address();
+        let allowance = client.allowance(&subscriber, &this);
+        client.approve(&subscriber, &this, &(allowance + cap), &expiration_ledger);
+        client.transfer_from(&this, &subscriber, &plan.merchant, &plan.amount);
+
+        let now = env.ledger().timestamp();
+        let id = next_id(&env, &DataKey::NextSubscriptionId);
+        let subscription = Subscription {
+            subscriber,
+            plan_id,
+            cap,
+            charged: plan.amount,
+            next_charge: now + plan.interval,
+            retry_at: now + plan.interval,
+            failures: 0,
+            status: SubscriptionStatus::Active,
+        };
+        save_subscription(&env, id, &subscription);
+        EventEmitter::billing_started(
+            &env,
+            billing_event(&env, id, &subscription, &plan, symbol_short!("subscribe")),
+        );
+        EventEmitter::billing_charged(&env, charge_event(&env, id, &subscription, &plan));
+        Ok(id)
+    }
+
+    /// Charge the due period of `subscription_id`. Anyone can call this.
+    /// Returns whether the charge went through; a failed or impossible
+    /// charge is recorded rather than reverted.
+    pub fn charge(env: Env, subscription_id: u64) -> Result<bool, BillingError> {
+        let mut subscription = load_subscription(&env, subscription_id)?;
+        if subscription.status == SubscriptionStatus::Cancelled {
+            return Err(BillingError::Cancelled);
+        }
+        let now = env.ledger().timestamp();
+        if now < subscription.retry_at {
+            return Err(BillingError::NotDue);
+        }
+        let plan = load_plan(&env, subscription.plan_id)?;
+        if subscription.charged + plan.amount > subscription.cap {
+            end(
+                &env,
+                subscription_id,
+                &mut subscription,
+                &plan,
+                symbol_short!("cap"),
+            );
+            return Ok(false);
+        }
+        if now > subscription.next_charge + plan.grace_period {
+            end(
+                &env,
+                subscription_id,
+                &mut subscription,
+                &plan,
+                symbol_short!("lapsed"),
+            );
+            return Ok(false);
+        }
+
+        let this = env.current_contract_address();
+        let paid = token::Client::new(&env, &plan.token)
+            .try_transfer_from(
+                &this,
+                &subscription.subscriber,
+                &plan.merchant,
+                &plan.amount,
+            )
+            .is_ok();
+        if paid {
+            subscription.charged += plan.amount;
+            subscription.next_charge += plan.interval;
+            subscription.retry_at = subscription.next_charge;
+            subscription.failures = 0;
+            subscription.status = SubscriptionStatus::Active;
+            save_subscription(&env, subscription_id, &subscription);
+            EventEmitter::billing_charged(
+                &env,
+                charge_event(&env, subscription_id, &subscription, &plan),
+            );
+            return Ok(true);
+        }
+
+        subscription.failures += 1;
+        subscription.status = SubscriptionStatus::PastDue;
+        subscription.retry_at = now + plan.grace_period / plan.max_failures as u64;
+        save_subscription(&env, subscription_id, &subscription);
+        EventEmitter::billing_failed(
+            &env,
+            charge_event(&env, subscription_id, &subscription, &plan),
+        );
+        if subscription.failures >= plan.max_failures {
+            end(
+                &env,
+                subscription_id,
+                &mut subscription,
+                &plan,
+                symbol_short!("failed"),
+            );
+        }
+        Ok(false)
+    }
+
+    /// Cancel a subscription, as its subscriber or the plan's merchant
+    pub fn cancel(env: Env, caller: Address, subscription_id: u64) -> Result<(), BillingError> {
+        caller.require_auth();
+        let mut subscription = load_subscription(&env, subscription_id)?;
+        let plan = load_plan(&env, subscription.plan_id)?;
+        let reason = if caller == subscription.subscriber {
+            symbol_short!("subscribr")
+        } else if caller == plan.merchant {
+            symbol_short!("merchant")
+        } else {
+            return Err(BillingError::Unauthorized);
+        };
+        if subscription.status == SubscriptionStatus::Cancelled {
+            return Err(BillingError::Cancelled);
+        }
+        end(&env, subscription_id, &mut subscription, &plan, reason);
+        Ok(())
+    }
+
+    pub fn get_plan(env: Env, plan_id: u64) -> Option<Plan> {
+        env.storage().persistent().get(&DataKey::Plan(plan_id))
+    }
+
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Option<Subscription> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscription(subscription_id))
+    }
+
+    /// Whether the subscriber is entitled to the service: not cancelled
+    /// and paid up, or within the grace period of an unpaid period
+    pub fn is_active(env: Env, subscription_id: u64) -> bool {
+        let Ok(subscription) = load_subscription(&env, subscription_id) else {
+            return false;
+        };
+        let Ok(plan) = load_plan(&env, subscription.plan_id) else {
+            return false;
+        };
+        subscription.status != SubscriptionStatus::Cancelled
+            && env.ledger().timestamp() <= subscription.next_charge + plan.grace_period
+    }
+}
+
+fn end(env: &Env, id: u64, subscription: &mut Subscription, plan: &Plan, reason: Symbol) {
+    subscription.status = SubscriptionStatus::Cancelled;
+    save_subscription(env, id, subscription);
+    EventEmitter::billing_cancelled(env, billing_event(env, id, subscription, plan, reason));
+}
+
+fn next_id(env: &Env, key: &DataKey) -> u64 {
+    let id: u64 = env.storage().instance().get(key).unwrap_or(1);
+    env.storage().instance().set(key, &(id + 1));
+    id
+}
+
+fn load_plan(env: &Env, plan_id: u64) -> Result<Plan, BillingError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Plan(plan_id))
+        .ok_or(BillingError::PlanNotFound)
+}
+
+fn load_subscription(env: &Env, subscription_id: u64) -> Result<Subscription, BillingError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Subscription(subscription_id))
+        .ok_or(BillingError::SubscriptionNotFound)
+}
+
+fn save_subscription(env: &Env, subscription_id: u64, subscription: &Subscription) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Subscription(subscription_id), subscription);
+}
+
+fn billing_event(
+    env: &Env,
+    id: u64,
+    subscription: &Subscription,
+    plan: &Plan,
+    reason: Symbol,
+) -> BillingEvent {
+    BillingEvent {
+        subscription_id: id,
+        subscriber: subscription.subscriber.clone(),
+        merchant: plan.merchant.clone(),
+        plan_id: subscription.plan_id,
+        reason,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+fn charge_event(
+    env: &Env,
+    id: u64,
+    subscription: &Subscription,
+    plan: &Plan,
+) -> BillingChargeEvent {
+    BillingChargeEvent {
+        subscription_id: id,
+        subscriber: subscription.subscriber.clone(),
+        merchant: plan.merchant.clone(),
+        token: plan.token.clone(),
+        amount: plan.amount,
+        failures: subscription.failures,
+        next_charge: subscription.next_charge,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+#[cfg(test)]
+mod test;
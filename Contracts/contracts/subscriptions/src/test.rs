@@ -0,0 +1,158 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use test_utils::fixtures::{stellar_asset, TestToken};
+use test_utils::{assert_standard_event, payload};
+
+const DAY: u64 = 24 * 60 * 60;
+const MONTH: u64 = 30 * DAY;
+
+struct Setup {
+    env: Env,
+    client: SubscriptionBillingClient<'static>,
+    token: TestToken<'static>,
+    merchant: Address,
+    subscriber: Address,
+    plan_id: u64,
+}
+
+fn setup(balance: i128) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+    let token = stellar_asset(&env);
+    let merchant = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    token.mint(&subscriber, balance);
+
+    let contract_id = env.register_contract(None, SubscriptionBilling);
+    let client = SubscriptionBillingClient::new(&env, &contract_id);
+    let plan_id = client.create_plan(&merchant, &token.address, &100, &MONTH, &(3 * DAY), &3);
+
+    Setup {
+        env,
+        client,
+        token,
+        merchant,
+        subscriber,
+        plan_id,
+    }
+}
+
+fn subscribe(s: &Setup, cap: i128) -> u64 {
+    s.client
+        .subscribe(&s.subscriber, &s.plan_id, &cap, &100_000)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+fn cancel_reason(s: &Setup) -> Symbol {
+    let cancelled = assert_standard_event(&s.env, topics::BILLING_CANCELLED, |_| true);
+    payload::<BillingEvent>(&cancelled).reason
+}
+
+#[test]
+fn test_keeper_charges_each_period_up_to_cap() {
+    let s = setup(1_000);
+    let id = subscribe(&s, 250);
+    assert_eq!(s.token.balance(&s.merchant), 100);
+    assert_standard_event(&s.env, topics::BILLING_STARTED, |_| true);
+    assert_eq!(
+        s.token.client.allowance(&s.subscriber, &s.client.address),
+        150
+    );
+
+    let result = s.client.try_charge(&id);
+    assert_eq!(result, Err(Ok(BillingError::NotDue)));
+    advance(&s.env, MONTH);
+    assert!(s.client.charge(&id));
+    assert_eq!(s.token.balance(&s.merchant), 200);
+    let charged = assert_standard_event(&s.env, topics::BILLING_CHARGED, |_| true);
+    let event: BillingChargeEvent = payload(&charged);
+    assert_eq!(event.next_charge, 1_000_000 + 2 * MONTH);
+    assert_eq!(
+        charged.metadata::<u64>(&s.env, metadata_keys::ID_KEY),
+        Some(id)
+    );
+
+    // A third period would exceed the cap
+    advance(&s.env, MONTH);
+    assert!(!s.client.charge(&id));
+    assert_eq!(cancel_reason(&s), symbol_short!("cap"));
+    assert_eq!(s.token.balance(&s.merchant), 200);
+    assert!(!s.client.is_active(&id));
+    let result = s.client.try_charge(&id);
+    assert_eq!(result, Err(Ok(BillingError::Cancelled)));
+}
+
+#[test]
+fn test_failed_charges_retry_through_grace_then_cancel() {
+    let s = setup(150);
+    let id = subscribe(&s, 1_000);
+
+    advance(&s.env, MONTH);
+    assert!(!s.client.charge(&id));
+    let failed = assert_standard_event(&s.env, topics::BILLING_FAILED, |_| true);
+    assert_eq!(payload::<BillingChargeEvent>(&failed).failures, 1);
+    let subscription = s.client.get_subscription(&id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+    assert!(s.client.is_active(&id));
+    let result = s.client.try_charge(&id);
+    assert_eq!(result, Err(Ok(BillingError::NotDue)));
+
+    // Topping up before the next retry recovers the subscription
+    s.token.mint(&s.subscriber, 100);
+    advance(&s.env, DAY);
+    assert!(s.client.charge(&id));
+    let subscription = s.client.get_subscription(&id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.failures, 0);
+    assert_eq!(subscription.next_charge, 1_000_000 + 2 * MONTH);
+
+    advance(&s.env, MONTH - DAY);
+    for _ in 0..2 {
+        assert!(!s.client.charge(&id));
+        advance(&s.env, DAY);
+    }
+    assert!(s.client.is_active(&id));
+    assert!(!s.client.charge(&id));
+    assert_eq!(cancel_reason(&s), symbol_short!("failed"));
+    assert!(!s.client.is_active(&id));
+    assert_eq!(s.token.balance(&s.merchant), 200);
+}
+
+#[test]
+fn test_cancellation_and_plan_closing() {
+    let s = setup(1_000);
+    let id = subscribe(&s, 1_000);
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_cancel(&stranger, &id);
+    assert_eq!(result, Err(Ok(BillingError::Unauthorized)));
+
+    s.client.cancel(&s.merchant, &id);
+    assert_eq!(cancel_reason(&s), symbol_short!("merchant"));
+    let result = s.client.try_cancel(&s.subscriber, &id);
+    assert_eq!(result, Err(Ok(BillingError::Cancelled)));
+
+    // A subscription left unpaid past its grace period lapses
+    let id = subscribe(&s, 1_000);
+    advance(&s.env, MONTH + 4 * DAY);
+    assert!(!s.client.is_active(&id));
+    assert!(!s.client.charge(&id));
+    assert_eq!(cancel_reason(&s), symbol_short!("lapsed"));
+
+    s.client.set_plan_active(&s.merchant, &s.plan_id, &false);
+    let result = s
+        .client
+        .try_subscribe(&s.subscriber, &s.plan_id, &1_000, &100_000);
+    assert_eq!(result, Err(Ok(BillingError::PlanInactive)));
+    let result = s
+        .client
+        .try_set_plan_active(&s.subscriber, &s.plan_id, &true);
+    assert_eq!(result, Err(Ok(BillingError::Unauthorized)));
+}
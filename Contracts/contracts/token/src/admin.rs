@@ -1,7 +1,13 @@
-use soroban_sdk::{Env, Address};
+use crate::error::TokenError;
 use crate::storage::get_admin;
+use shared::recovery::Recovery;
+use soroban_sdk::{Address, Env};
 
-pub fn require_admin(env: &Env) {
-    let admin = get_admin(env);
+/// Authenticate the admin. Every admin action counts as activity for
+/// guardian recovery.
+pub fn require_admin(env: &Env) -> Result<Address, TokenError> {
+    let admin = get_admin(env)?;
     admin.require_auth();
+    Recovery::record_activity(env);
+    Ok(admin)
 }
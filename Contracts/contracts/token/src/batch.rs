@@ -0,0 +1,49 @@
+//! Batched transfers and multicall
+//!
+//! Payroll and airdrops send many transfers at once. Batching them into a
+//! single invocation saves per-transaction fees and makes the batch atomic:
+//! returning an error from any leg reverts every leg before it.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+const BATCH_COUNTER_KEY: Symbol = symbol_short!("BATCH_CNT");
+
+/// A single operation inside `multicall`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FuncCall {
+    /// (from, to, amount)
+    Transfer(Address, Address, i128),
+    /// (owner, spender, amount)
+    Approve(Address, Address, i128),
+    /// (spender, from, to, amount)
+    TransferFrom(Address, Address, Address, i128),
+}
+
+/// Allocate the correlation id shared by every event of one batch
+pub fn next_batch_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&BATCH_COUNTER_KEY)
+        .unwrap_or(0)
+        + 1;
+    env.storage().instance().set(&BATCH_COUNTER_KEY, &id);
+    id
+}
+
+/// Distinct addresses whose authorization the calls need
+pub fn actors(env: &Env, calls: &Vec<FuncCall>) -> Vec<Address> {
+    let mut actors: Vec<Address> = Vec::new(env);
+    for call in calls.iter() {
+        let actor = match call {
+            FuncCall::Transfer(from, _, _) => from,
+            FuncCall::Approve(owner, _, _) => owner,
+            FuncCall::TransferFrom(spender, _, _, _) => spender,
+        };
+        if !actors.contains(&actor) {
+            actors.push_back(actor);
+        }
+    }
+    actors
+}
@@ -0,0 +1,121 @@
+//! Balance checkpoints
+//!
+//! Every balance change records `(ledger, balance)` for the account, so
+//! `balance_at` can answer what an account held at the end of any past
+//! ledger with a binary search. Dividends, snapshot voting and airdrops
+//! read balances this way so that moving tokens after the snapshot ledger
+//! changes nothing.
+//!
+//! Without a horizon the history grows with every change. Once the admin
+//! sets one, each write drops the checkpoints that are no longer needed to
+//! answer queries within `horizon` ledgers of the current one, and older
+//! queries are refused. Lengthening or removing the horizon later does not
+//! bring back history already dropped, so queries before the last cutoff
+//! in force stay refused.
+//!
+//! Checkpoints hold the stored balance, so holder reflections from a
+//! transfer fee appear once they are credited at the account's next
+//! balance change, and a rebasing token's history is in shares.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::error::TokenError;
+use crate::storage::{self, DataKey};
+
+const HORIZON_KEY: Symbol = symbol_short!("CP_HORIZ");
+const PRUNED_KEY: Symbol = symbol_short!("CP_PRUNED");
+
+/// An account's balance as of the end of `ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub balance: i128,
+}
+
+pub fn horizon(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&HORIZON_KEY)
+}
+
+pub fn set_horizon(env: &Env, horizon: Option<u32>) -> Result<(), TokenError> {
+    if let Some(cutoff) = cutoff(env) {
+        let pruned = pruned_until(env).max(cutoff);
+        env.storage().instance().set(&PRUNED_KEY, &pruned);
+    }
+    match horizon {
+        Some(0) => return Err(TokenError::InvalidAmount),
+        Some(horizon) => env.storage().instance().set(&HORIZON_KEY, &horizon),
+        None => env.storage().instance().remove(&HORIZON_KEY),
+    }
+    Ok(())
+}
+
+pub fn get(env: &Env, addr: &Address) -> Vec<Checkpoint> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Checkpoints(addr.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record `addr`'s new balance for the current ledger
+pub fn record(env: &Env, addr: &Address, balance: i128) {
+    let ledger = env.ledger().sequence();
+    let mut list = get(env, addr);
+    let checkpoint = Checkpoint { ledger, balance };
+    match list.last() {
+        Some(last) if last.ledger == ledger => list.set(list.len() - 1, checkpoint),
+        _ => list.push_back(checkpoint),
+    }
+    if let Some(horizon) = horizon(env) {
+        // Keep the last checkpoint at or before the cutoff: it is the
+        // balance for every ledger from the cutoff to the next one
+        let keep_from = count_until(&list, ledger.saturating_sub(horizon)).saturating_sub(1);
+        if keep_from > 0 {
+            list = list.slice(keep_from..);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Checkpoints(addr.clone()), &list);
+}
+
+/// `addr`'s balance at the end of `ledger`
+pub fn balance_at(env: &Env, addr: &Address, ledger: u32) -> Result<i128, TokenError> {
+    let oldest = cutoff(env).unwrap_or(0).max(pruned_until(env));
+    if ledger > env.ledger().sequence() || ledger < oldest {
+        return Err(TokenError::SnapshotUnavailable);
+    }
+    let list = get(env, addr);
+    if list.is_empty() {
+        // Never changed since checkpoints were introduced
+        return Ok(storage::balance_of(env, addr));
+    }
+    Ok(match count_until(&list, ledger) {
+        0 => 0,
+        n => list.get_unchecked(n - 1).balance,
+    })
+}
+
+/// Oldest ledger the current horizon keeps history for
+fn cutoff(env: &Env) -> Option<u32> {
+    horizon(env).map(|horizon| env.ledger().sequence().saturating_sub(horizon))
+}
+
+/// Oldest ledger an earlier horizon may have kept history for
+fn pruned_until(env: &Env) -> u32 {
+    env.storage().instance().get(&PRUNED_KEY).unwrap_or(0)
+}
+
+/// Number of checkpoints at or before `ledger`
+fn count_until(list: &Vec<Checkpoint>, ledger: u32) -> u32 {
+    let (mut low, mut high) = (0, list.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if list.get_unchecked(mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
@@ -0,0 +1,117 @@
+//! Compliance controls for regulated assets
+//!
+//! When enabled at initialization, a compliance officer (initially the
+//! admin) can freeze accounts and claw back balances. Tokens initialized
+//! without compliance never store an officer, and since the role can only be
+//! handed over, not created, those powers can never be switched on later.
+//!
+//! The officer can also require both sides of every transfer to hold a
+//! claim, such as a KYC tier, in an attestation registry.
+
+use shared::attestations::ClaimRequirement;
+use shared::events::{AuthorizationChangedEvent, BurnEvent, EventEmitter};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::error::TokenError;
+use crate::storage::{self, DataKey};
+use crate::supply;
+use crate::{rebase, reflection};
+
+const OFFICER_KEY: Symbol = symbol_short!("COMPLY");
+const CLAIM_KEY: Symbol = symbol_short!("CLAIM");
+
+pub fn enable(env: &Env, officer: &Address) {
+    env.storage().instance().set(&OFFICER_KEY, officer);
+}
+
+pub fn officer(env: &Env) -> Result<Address, TokenError> {
+    env.storage()
+        .instance()
+        .get(&OFFICER_KEY)
+        .ok_or(TokenError::ComplianceDisabled)
+}
+
+pub fn require_officer(env: &Env) -> Result<Address, TokenError> {
+    let officer = officer(env)?;
+    officer.require_auth();
+    Ok(officer)
+}
+
+pub fn is_frozen(env: &Env, addr: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Frozen(addr.clone()))
+        .unwrap_or(false)
+}
+
+pub fn set_frozen(env: &Env, officer: &Address, addr: &Address, frozen: bool) {
+    let key = DataKey::Frozen(addr.clone());
+    if frozen {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    let reason = if frozen {
+        symbol_short!("freeze")
+    } else {
+        symbol_short!("unfreeze")
+    };
+    EventEmitter::authorization_changed(
+        env,
+        AuthorizationChangedEvent {
+            account: addr.clone(),
+            changed_by: officer.clone(),
+            authorized: !frozen,
+            timestamp: env.ledger().timestamp(),
+        },
+        reason,
+    );
+}
+
+pub fn claim_requirement(env: &Env) -> Option<ClaimRequirement> {
+    env.storage().instance().get(&CLAIM_KEY)
+}
+
+pub fn set_claim_requirement(env: &Env, requirement: Option<ClaimRequirement>) {
+    match requirement {
+        Some(requirement) => env.storage().instance().set(&CLAIM_KEY, &requirement),
+        None => env.storage().instance().remove(&CLAIM_KEY),
+    }
+}
+
+/// Check `from` and `to` both hold the required claim, if any
+pub fn check_claims(env: &Env, from: &Address, to: &Address) -> Result<(), TokenError> {
+    if let Some(requirement) = claim_requirement(env) {
+        if !requirement.is_met(env, from) || !requirement.is_met(env, to) {
+            return Err(TokenError::MissingClaim);
+        }
+    }
+    Ok(())
+}
+
+/// Burn `amount` from `from` regardless of its frozen state
+pub fn clawback(env: &Env, from: &Address, amount: i128) -> Result<(), TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    reflection::settle(env, from);
+    let shares = rebase::to_shares(env, amount, true)?;
+    let balance = storage::balance_of(env, from);
+    if balance < shares {
+        return Err(TokenError::InsufficientBalance);
+    }
+    storage::set_balance(env, from, &(balance - shares));
+    supply::record_burn(env, shares);
+
+    EventEmitter::burn(
+        env,
+        BurnEvent {
+            from: from.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+        symbol_short!("clawback"),
+    );
+    Ok(())
+}
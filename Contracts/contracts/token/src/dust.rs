@@ -0,0 +1,96 @@
+//! Dust policy
+//!
+//! Once configured, transfers below `min_transfer` are rejected, and the
+//! admin can sweep balances that have fallen below it to the treasury with
+//! `sweep_dust`. Swept accounts lose their balance entry, so near-zero
+//! holders stop taking up ledger storage. The contract cannot enumerate
+//! holders, so the sweep takes the accounts to check from the caller (an
+//! indexer knows them). Frozen accounts are left alone.
+
+use shared::events::{DustSweptEvent, EventEmitter};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::error::TokenError;
+use crate::storage::{self, DataKey};
+use crate::{checkpoints, compliance, rebase, reflection};
+
+const DUST_KEY: Symbol = symbol_short!("DUST");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustPolicy {
+    /// Smallest amount a transfer may move, and the balance below which an
+    /// account counts as dust
+    pub min_transfer: i128,
+    /// Receives swept dust
+    pub treasury: Address,
+}
+
+pub fn get(env: &Env) -> Option<DustPolicy> {
+    env.storage().instance().get(&DUST_KEY)
+}
+
+pub fn set(env: &Env, policy: Option<DustPolicy>) -> Result<(), TokenError> {
+    match policy {
+        Some(policy) => {
+            if policy.min_transfer <= 0 {
+                return Err(TokenError::InvalidAmount);
+            }
+            env.storage().instance().set(&DUST_KEY, &policy);
+        }
+        None => env.storage().instance().remove(&DUST_KEY),
+    }
+    Ok(())
+}
+
+/// Reject a transfer of `amount` below the minimum
+pub fn check(env: &Env, amount: i128) -> Result<(), TokenError> {
+    match get(env) {
+        Some(policy) if amount < policy.min_transfer => Err(TokenError::BelowDustMinimum),
+        _ => Ok(()),
+    }
+}
+
+/// Move every non-zero balance under the minimum among `holders` to the
+/// treasury. Returns the total swept.
+pub fn sweep(env: &Env, holders: &Vec<Address>) -> Result<i128, TokenError> {
+    let policy = get(env).ok_or(TokenError::NoDustPolicy)?;
+    let mut count = 0u32;
+    let mut total = 0i128;
+    for holder in holders.iter() {
+        if holder == policy.treasury || compliance::is_frozen(env, &holder) {
+            continue;
+        }
+        reflection::settle(env, &holder);
+        let balance = storage::balance_of(env, &holder);
+        if balance <= 0 || rebase::to_amount(env, balance)? >= policy.min_transfer {
+            continue;
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Balance(holder.clone()));
+        checkpoints::record(env, &holder, 0);
+        count += 1;
+        total += balance;
+    }
+    if count == 0 {
+        return Ok(0);
+    }
+
+    reflection::settle(env, &policy.treasury);
+    let balance = storage::balance_of(env, &policy.treasury)
+        .checked_add(total)
+        .ok_or(TokenError::Overflow)?;
+    storage::set_balance(env, &policy.treasury, &balance);
+    EventEmitter::dust_swept(
+        env,
+        DustSweptEvent {
+            token: env.current_contract_address(),
+            treasury: policy.treasury,
+            count,
+            total: rebase::to_amount(env, total)?,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(total)
+}
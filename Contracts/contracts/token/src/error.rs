@@ -0,0 +1,34 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Overflow = 3,
+    InvalidMetadata = 4,
+    InvalidAmount = 5,
+    InsufficientBalance = 6,
+    InsufficientAllowance = 7,
+    PermitExpired = 8,
+    PermitKeyNotSet = 9,
+    ComplianceDisabled = 10,
+    AccountFrozen = 11,
+    InvalidFee = 12,
+    BelowDustMinimum = 13,
+    NoDustPolicy = 14,
+    OperatorCapExceeded = 15,
+    NotOperator = 16,
+    TransferVetoed = 17,
+    HookReentrancy = 18,
+    SnapshotUnavailable = 19,
+    RebaseConflict = 20,
+    RebaseDisabled = 21,
+    InvalidIndex = 22,
+    MissingClaim = 23,
+    // Transfer limit rejections reuse the shared `ContractError` codes
+    TransferLimitExceeded = 4010,
+    BalanceLimitExceeded = 4011,
+    TransferCooldown = 4012,
+}
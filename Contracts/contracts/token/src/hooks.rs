@@ -0,0 +1,84 @@
+//! Transfer hook
+//!
+//! The admin may install a hook contract that is asked about every
+//! transfer before any balance moves and can veto it, e.g. to enforce an
+//! allow or deny list, keep vesting balances locked or apply game rules.
+//! Mints, clawbacks and dust sweeps are not hooked.
+//!
+//! The hook is called after the token's own checks and before its effects,
+//! so it sees the balances as they were and nothing is left half-applied
+//! if it vetoes. It is invoked with `try_`, so a hook that fails or panics
+//! vetoes the transfer rather than aborting with its own error. A lock is
+//! held for the duration of the call and any transfer attempted while it
+//! is held is refused, so a hook can never move funds from inside its own
+//! decision.
+
+use shared::events::{EventEmitter, TransferHookChangedEvent};
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+use crate::error::TokenError;
+
+const HOOK_KEY: Symbol = symbol_short!("HOOK");
+const LOCK_KEY: Symbol = symbol_short!("HOOK_LOCK");
+
+/// Interface transfer hooks must implement. Returning `false` vetoes the
+/// transfer.
+#[contractclient(name = "TransferHookClient")]
+pub trait TransferHook {
+    fn before_transfer(env: Env, token: Address, from: Address, to: Address, amount: i128) -> bool;
+}
+
+pub fn get(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&HOOK_KEY)
+}
+
+/// Install, replace or remove (`None`) the hook
+pub fn set(env: &Env, admin: &Address, hook: Option<Address>) {
+    let (hook, installed) = match hook {
+        Some(hook) => {
+            env.storage().instance().set(&HOOK_KEY, &hook);
+            (hook, true)
+        }
+        None => {
+            let Some(previous) = get(env) else {
+                return;
+            };
+            env.storage().instance().remove(&HOOK_KEY);
+            (previous, false)
+        }
+    };
+    EventEmitter::transfer_hook_changed(
+        env,
+        TransferHookChangedEvent {
+            token: env.current_contract_address(),
+            hook,
+            installed,
+            changed_by: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Ask the hook, if any, whether `from` may send `amount` to `to`
+pub fn check(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), TokenError> {
+    if env.storage().instance().has(&LOCK_KEY) {
+        return Err(TokenError::HookReentrancy);
+    }
+    let Some(hook) = get(env) else {
+        return Ok(());
+    };
+
+    env.storage().instance().set(&LOCK_KEY, &true);
+    let verdict = TransferHookClient::new(env, &hook).try_before_transfer(
+        &env.current_contract_address(),
+        from,
+        to,
+        &amount,
+    );
+    env.storage().instance().remove(&LOCK_KEY);
+
+    match verdict {
+        Ok(Ok(true)) => Ok(()),
+        _ => Err(TokenError::TransferVetoed),
+    }
+}
@@ -0,0 +1,703 @@
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::attestations::ClaimRequirement;
+use shared::errors::ContractError;
+use shared::events::{
+    namespaces, ApprovalEvent, EventConfig, EventEmitter, EventLog, EventLogConfig,
+    EventSubscription, MetadataUpdatedEvent, StandardEventV2, TransferEvent,
+};
+use shared::nonce::NonceManager;
+use shared::recovery::{Recovery, RecoveryConfig, RecoveryError};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+
+mod admin;
+mod batch;
+mod checkpoints;
+mod compliance;
+mod dust;
+mod error;
+mod hooks;
+mod limits;
+mod operators;
+mod permit;
+mod rebase;
+mod reflection;
+mod storage;
+mod supply;
+
+pub use batch::FuncCall;
+pub use checkpoints::Checkpoint;
+pub use dust::DustPolicy;
+pub use error::TokenError;
+pub use hooks::{TransferHook, TransferHookClient};
+pub use limits::TransferLimits;
+pub use operators::OperatorApproval;
+pub use rebase::{Rebasing, INDEX_SCALE};
+pub use reflection::{TransferFee, MAX_TRANSFER_FEE_BPS};
+pub use storage::TokenMetadata;
+pub use supply::EmissionSchedule;
+
+/// Upper bound on `decimals`, matching what wallets can display sensibly
+pub const MAX_DECIMALS: u32 = 18;
+
+#[contract]
+pub struct TokenContract;
+
+#[contractimpl]
+impl TokenContract {
+    /// Set up the token. With `compliance_enabled` the admin acts as
+    /// compliance officer and may freeze accounts and claw back balances;
+    /// passing `false` disables those powers permanently.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        metadata: TokenMetadata,
+        compliance_enabled: bool,
+    ) -> Result<(), TokenError> {
+        if storage::has_admin(&env) {
+            return Err(TokenError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        validate_metadata(&metadata)?;
+        storage::set_admin(&env, &admin);
+        storage::set_metadata(&env, &metadata);
+        EventEmitter::init(&env, namespaces::TOKEN);
+        if compliance_enabled {
+            compliance::enable(&env, &admin);
+        }
+        Ok(())
+    }
+
+    /// Mint `amount` to `to` (admin only), subject to the supply cap and
+    /// emission schedule
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+        admin::require_admin(&env).map_err(|_| ContractError::NotInitialized)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let shares = rebase::to_shares(&env, amount, false).map_err(|_| ContractError::Overflow)?;
+        if shares <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // checked arithmetic
+        reflection::settle(&env, &to);
+        let balance = storage::balance_of(&env, &to);
+        let new_balance = balance.checked_add(shares).ok_or(ContractError::Overflow)?;
+        supply::record_mint(&env, shares)?;
+
+        storage::set_balance(&env, &to, &new_balance);
+        Ok(())
+    }
+
+    /// Fix the hard supply cap (admin only). An existing cap can only be
+    /// lowered.
+    pub fn set_max_supply(env: Env, max_supply: i128) -> Result<(), ContractError> {
+        admin::require_admin(&env).map_err(|_| ContractError::NotInitialized)?;
+        supply::set_max_supply(&env, max_supply)
+    }
+
+    /// Install the emission schedule (admin only, once)
+    pub fn set_emission_schedule(
+        env: Env,
+        schedule: EmissionSchedule,
+    ) -> Result<(), ContractError> {
+        admin::require_admin(&env).map_err(|_| ContractError::NotInitialized)?;
+        supply::set_schedule(&env, &schedule)
+    }
+
+    pub fn total_supply(env: Env) -> Result<i128, TokenError> {
+        rebase::to_amount(&env, supply::total_supply(&env))
+    }
+
+    pub fn max_supply(env: Env) -> Option<i128> {
+        supply::max_supply(&env)
+    }
+
+    pub fn emission_schedule(env: Env) -> Option<EmissionSchedule> {
+        supply::schedule(&env)
+    }
+
+    /// Amount that can be minted right now under the cap and schedule
+    pub fn remaining_mintable(env: Env) -> Result<i128, TokenError> {
+        rebase::to_amount(&env, supply::remaining_mintable(&env))
+    }
+
+    pub fn balance(env: Env, id: Address) -> Result<i128, TokenError> {
+        rebase::to_amount(&env, reflection::balance_of(&env, &id))
+    }
+
+    /// `id`'s raw balance. Equal to `balance` unless rebasing is on, in
+    /// which case it is the share amount the balance is scaled from.
+    pub fn shares_of(env: Env, id: Address) -> i128 {
+        reflection::balance_of(&env, &id)
+    }
+
+    /// Turn on rebasing, with `updater` (the savings or lending module)
+    /// moving the index, or hand the index to a new updater (admin only).
+    /// Cannot be undone.
+    pub fn enable_rebasing(env: Env, updater: Address) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        rebase::enable(&env, updater)
+    }
+
+    /// Raise the rebasing index, scaling every balance by
+    /// `index / INDEX_SCALE` (updater only)
+    pub fn set_rebase_index(env: Env, index: i128) -> Result<(), TokenError> {
+        rebase::set_index(&env, index)
+    }
+
+    pub fn rebasing(env: Env) -> Option<Rebasing> {
+        rebase::get(&env)
+    }
+
+    /// `id`'s balance at the end of ledger `ledger`, in shares if the
+    /// token rebases. Fails for future ledgers and for ledgers beyond the
+    /// checkpoint horizon.
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, TokenError> {
+        checkpoints::balance_at(&env, &id, ledger)
+    }
+
+    /// Balance history of `id`, oldest first
+    pub fn checkpoints(env: Env, id: Address) -> Vec<Checkpoint> {
+        checkpoints::get(&env, &id)
+    }
+
+    /// Keep only enough balance history to answer queries `horizon`
+    /// ledgers back, or keep all of it with `None` (admin only)
+    pub fn set_checkpoint_horizon(env: Env, horizon: Option<u32>) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        checkpoints::set_horizon(&env, horizon)
+    }
+
+    pub fn checkpoint_horizon(env: Env) -> Option<u32> {
+        checkpoints::horizon(&env)
+    }
+
+    /// Charge a fee on every transfer, split between burn, treasury and
+    /// holders, or turn the fee off with `None` (admin only)
+    pub fn set_transfer_fee(env: Env, fee: Option<TransferFee>) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        reflection::set_config(&env, fee)
+    }
+
+    pub fn transfer_fee(env: Env) -> Option<TransferFee> {
+        reflection::config(&env)
+    }
+
+    /// Part of `id`'s balance that came from the holders' share of transfer
+    /// fees, over the account's lifetime
+    pub fn reflection_balance_of(env: Env, id: Address) -> i128 {
+        reflection::reflected(&env, &id)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount, None)
+    }
+
+    /// Send a different amount to each recipient in one call. Either every
+    /// leg succeeds or none does. Returns the batch correlation id carried by
+    /// each leg's transfer event.
+    pub fn transfer_batch(
+        env: Env,
+        from: Address,
+        transfers: Vec<(Address, i128)>,
+    ) -> Result<u64, TokenError> {
+        from.require_auth();
+        let batch_id = batch::next_batch_id(&env);
+        for (to, amount) in transfers.iter() {
+            move_balance(&env, &from, &to, amount, Some(batch_id))?;
+        }
+        Ok(batch_id)
+    }
+
+    /// Execute several token operations atomically. Every distinct actor
+    /// (sender, owner or spender) authorizes the multicall once; the first
+    /// failure aborts the whole batch. Returns
+    /// the batch correlation id.
+    pub fn multicall(env: Env, calls: Vec<FuncCall>) -> Result<u64, TokenError> {
+        if calls.is_empty() {
+            return Err(TokenError::InvalidAmount);
+        }
+        // An address may only be authorized once per invocation
+        for actor in batch::actors(&env, &calls).iter() {
+            actor.require_auth();
+        }
+
+        let batch_id = batch::next_batch_id(&env);
+        for call in calls.iter() {
+            match call {
+                FuncCall::Transfer(from, to, amount) => {
+                    move_balance(&env, &from, &to, amount, Some(batch_id))?;
+                }
+                FuncCall::Approve(owner, spender, amount) => {
+                    set_approval(&env, &owner, &spender, amount, false)?;
+                }
+                FuncCall::TransferFrom(spender, from, to, amount) => {
+                    spend_allowance(&env, &from, &spender, amount)?;
+                    move_balance(&env, &from, &to, amount, Some(batch_id))?;
+                }
+            }
+        }
+        Ok(batch_id)
+    }
+
+    /// Set the amount `spender` may transfer out of `owner`'s balance
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), TokenError> {
+        owner.require_auth();
+        set_approval(&env, &owner, &spender, amount, false)
+    }
+
+    pub fn allowance(env: Env, owner: Address, spender: Address) -> i128 {
+        storage::allowance(&env, &owner, &spender)
+    }
+
+    /// Move `amount` from `from` to `to` on `from`'s behalf. `spender`
+    /// needs an allowance, unless it is an operator for `from`.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), TokenError> {
+        spender.require_auth();
+        spend_allowance(&env, &from, &spender, amount)?;
+        move_balance(&env, &from, &to, amount, None)
+    }
+
+    /// Approve or revoke `operator` to move any of `owner`'s balance
+    /// without an allowance. Approving an existing operator keeps its cap;
+    /// revoking drops it.
+    pub fn set_operator(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        operators::set(&env, &owner, &operator, approved);
+    }
+
+    /// Limit each transfer `operator` makes from `owner` to `cap`, or lift
+    /// the limit with `None`
+    pub fn set_operator_cap(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        cap: Option<i128>,
+    ) -> Result<(), TokenError> {
+        owner.require_auth();
+        operators::set_cap(&env, &owner, &operator, cap)
+    }
+
+    pub fn operator(env: Env, owner: Address, operator: Address) -> Option<OperatorApproval> {
+        operators::get(&env, &owner, &operator)
+    }
+
+    /// Register the ed25519 key `owner` signs permits with
+    pub fn set_permit_key(env: Env, owner: Address, public_key: BytesN<32>) {
+        owner.require_auth();
+        storage::set_permit_key(&env, &owner, &public_key);
+    }
+
+    /// Approve `spender` with an ed25519 signature from `owner`'s permit key
+    /// instead of the owner's own authorization. See `permit` module docs for
+    /// the signed payload.
+    pub fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        deadline: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), TokenError> {
+        permit::verify(&env, &owner, &spender, amount, deadline, &signature)?;
+        set_approval(&env, &owner, &spender, amount, true)
+    }
+
+    /// Next permit nonce for `owner`
+    pub fn nonce(env: Env, owner: Address) -> u64 {
+        NonceManager::peek_nonce(&env, &owner, &permit::PERMIT_DOMAIN)
+    }
+
+    /// Digest `owner` must sign to permit `spender` for `amount`
+    pub fn permit_digest(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        deadline: u64,
+    ) -> BytesN<32> {
+        let nonce = Self::nonce(env.clone(), owner.clone());
+        permit::permit_digest(&env, &owner, &spender, amount, nonce, deadline)
+    }
+
+    /// Hand the compliance role to `officer` (admin only)
+    pub fn set_compliance_officer(env: Env, officer: Address) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        compliance::officer(&env)?;
+        compliance::enable(&env, &officer);
+        Ok(())
+    }
+
+    /// Choose which event formats the token publishes (admin only)
+    pub fn set_event_config(env: Env, config: EventConfig) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        EventConfig::store(&env, &config);
+        Ok(())
+    }
+
+    pub fn event_config(env: Env) -> EventConfig {
+        EventConfig::load(&env)
+    }
+
+    /// Keep the most recent standard events on-chain for light clients
+    /// (admin only). A capacity of zero turns the log off.
+    pub fn set_event_log(env: Env, config: EventLogConfig) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        EventLog::configure(&env, &config);
+        Ok(())
+    }
+
+    pub fn event_log_config(env: Env) -> Option<EventLogConfig> {
+        EventLog::config(&env)
+    }
+
+    /// Logged events from sequence `from_seq` on, at most `limit`
+    pub fn get_events(env: Env, from_seq: u64, limit: u32) -> Vec<StandardEventV2> {
+        EventLog::get_events(&env, from_seq, limit)
+    }
+
+    /// Ask for notify hints on this token's events about `user`, limited to
+    /// `topics` (empty for all)
+    pub fn subscribe(env: Env, user: Address, topics: Vec<Symbol>) {
+        EventSubscription::set(&env, &user, topics);
+    }
+
+    pub fn unsubscribe(env: Env, user: Address) -> bool {
+        EventSubscription::clear(&env, &user)
+    }
+
+    pub fn subscription(env: Env, user: Address) -> Option<Vec<Symbol>> {
+        EventSubscription::get(&env, &user)
+    }
+
+    pub fn admin(env: Env) -> Result<Address, TokenError> {
+        storage::get_admin(&env)
+    }
+
+    /// Propose `new_admin` as the next admin; takes effect once they accept
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        let admin = admin::require_admin(&env)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        storage::set_admin(&env, &admin);
+        Recovery::record_activity(&env);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
+    /// Name the guardians who may replace the admin after `timeout`
+    /// seconds of admin inactivity, with `threshold` approvals (admin only)
+    pub fn set_recovery(
+        env: Env,
+        guardians: Vec<Address>,
+        threshold: u32,
+        timeout: u64,
+    ) -> Result<(), RecoveryError> {
+        admin::require_admin(&env).map_err(|_| RecoveryError::NotConfigured)?;
+        Recovery::configure(&env, guardians, threshold, timeout)
+    }
+
+    pub fn recovery_config(env: Env) -> Option<RecoveryConfig> {
+        Recovery::config(&env)
+    }
+
+    /// Prove the admin is still active, cancelling any pending recovery
+    pub fn heartbeat(env: Env) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        Ok(())
+    }
+
+    /// Approve `new_admin` as the replacement for an inactive admin.
+    /// Returns `true` once the approval completed the rotation.
+    pub fn recover_admin(
+        env: Env,
+        guardian: Address,
+        new_admin: Address,
+    ) -> Result<bool, RecoveryError> {
+        let current = storage::get_admin(&env).map_err(|_| RecoveryError::NotConfigured)?;
+        match Recovery::approve(&env, &guardian, &current, &new_admin)? {
+            Some(admin) => {
+                storage::set_admin(&env, &admin);
+                // A transfer proposed by the lost admin must not survive
+                PendingAdmin::cancel(&env);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Install or clear (`None`) the anti-whale transfer limits (admin
+    /// only)
+    pub fn set_transfer_limits(env: Env, limits: Option<TransferLimits>) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        limits::set(&env, limits)
+    }
+
+    pub fn transfer_limits(env: Env) -> Option<TransferLimits> {
+        limits::get(&env)
+    }
+
+    /// Exempt `addr` from, or subject it to, the transfer limits (admin
+    /// only). Meant for pools, the treasury and other protocol accounts.
+    pub fn set_limit_exempt(env: Env, addr: Address, exempt: bool) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        limits::set_exempt(&env, &addr, exempt);
+        Ok(())
+    }
+
+    pub fn is_limit_exempt(env: Env, addr: Address) -> bool {
+        limits::is_exempt(&env, &addr)
+    }
+
+    /// Install or clear (`None`) the minimum transfer amount and the
+    /// treasury that collects dust (admin only)
+    pub fn set_dust_policy(env: Env, policy: Option<DustPolicy>) -> Result<(), TokenError> {
+        admin::require_admin(&env)?;
+        dust::set(&env, policy)
+    }
+
+    pub fn dust_policy(env: Env) -> Option<DustPolicy> {
+        dust::get(&env)
+    }
+
+    /// Move the balances of `holders` that are below the minimum transfer
+    /// to the treasury (admin only). Returns the total swept.
+    pub fn sweep_dust(env: Env, holders: Vec<Address>) -> Result<i128, TokenError> {
+        admin::require_admin(&env)?;
+        dust::sweep(&env, &holders)
+    }
+
+    /// Install, replace or remove (`None`) the contract consulted before
+    /// every transfer (admin only). Proposing governance as admin puts the
+    /// hook under its control.
+    pub fn set_transfer_hook(env: Env, hook: Option<Address>) -> Result<(), TokenError> {
+        let admin = admin::require_admin(&env)?;
+        hooks::set(&env, &admin, hook);
+        Ok(())
+    }
+
+    pub fn transfer_hook(env: Env) -> Option<Address> {
+        hooks::get(&env)
+    }
+
+    pub fn compliance_enabled(env: Env) -> bool {
+        compliance::officer(&env).is_ok()
+    }
+
+    /// Burn `amount` from `from` (compliance officer only)
+    pub fn clawback(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        compliance::require_officer(&env)?;
+        compliance::clawback(&env, &from, amount)
+    }
+
+    /// Freeze or unfreeze `addr` (compliance officer only). Frozen accounts
+    /// can neither send nor receive.
+    pub fn set_frozen(env: Env, addr: Address, frozen: bool) -> Result<(), TokenError> {
+        let officer = compliance::require_officer(&env)?;
+        compliance::set_frozen(&env, &officer, &addr, frozen);
+        Ok(())
+    }
+
+    pub fn is_frozen(env: Env, addr: Address) -> bool {
+        compliance::is_frozen(&env, &addr)
+    }
+
+    /// Require both sides of every transfer to hold a claim in an
+    /// attestation registry, or lift the requirement with `None`
+    /// (compliance officer only). Mints and clawbacks are not checked.
+    pub fn set_claim_requirement(
+        env: Env,
+        requirement: Option<ClaimRequirement>,
+    ) -> Result<(), TokenError> {
+        compliance::require_officer(&env)?;
+        compliance::set_claim_requirement(&env, requirement);
+        Ok(())
+    }
+
+    pub fn claim_requirement(env: Env) -> Option<ClaimRequirement> {
+        compliance::claim_requirement(&env)
+    }
+
+    /// Replace the token metadata (admin only)
+    pub fn update_metadata(env: Env, metadata: TokenMetadata) -> Result<(), TokenError> {
+        let admin = admin::require_admin(&env)?;
+        validate_metadata(&metadata)?;
+        storage::set_metadata(&env, &metadata);
+
+        EventEmitter::metadata_updated(
+            &env,
+            MetadataUpdatedEvent {
+                admin,
+                name: metadata.name,
+                symbol: metadata.symbol,
+                decimals: metadata.decimals,
+                uri: metadata.uri,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn metadata(env: Env) -> Result<TokenMetadata, TokenError> {
+        storage::get_metadata(&env)
+    }
+
+    pub fn name(env: Env) -> Result<String, TokenError> {
+        Ok(storage::get_metadata(&env)?.name)
+    }
+
+    pub fn symbol(env: Env) -> Result<String, TokenError> {
+        Ok(storage::get_metadata(&env)?.symbol)
+    }
+
+    pub fn decimals(env: Env) -> Result<u32, TokenError> {
+        Ok(storage::get_metadata(&env)?.decimals)
+    }
+
+    pub fn uri(env: Env) -> Result<String, TokenError> {
+        Ok(storage::get_metadata(&env)?.uri)
+    }
+}
+
+/// Move `amount` from `from` to `to` and emit a transfer event, tagged with
+/// `batch_id` when the transfer is one leg of a batch
+fn move_balance(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    batch_id: Option<u64>,
+) -> Result<(), TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    dust::check(env, amount)?;
+    if compliance::is_frozen(env, from) || compliance::is_frozen(env, to) {
+        return Err(TokenError::AccountFrozen);
+    }
+    compliance::check_claims(env, from, to)?;
+    hooks::check(env, from, to, amount)?;
+    // The recipient is credited the shares taken from the sender, so
+    // rounding them up leaves it at least `amount` better off
+    let shares = rebase::to_shares(env, amount, true)?;
+    reflection::settle(env, from);
+    reflection::settle(env, to);
+    let from_balance = storage::balance_of(env, from);
+    if from_balance < shares {
+        return Err(TokenError::InsufficientBalance);
+    }
+    storage::set_balance(env, from, &(from_balance - shares));
+    let fee = reflection::split(env, shares)?;
+    let received = shares - fee.as_ref().map_or(0, |(split, _)| split.total());
+    let to_balance = storage::balance_of(env, to)
+        .checked_add(received)
+        .ok_or(TokenError::Overflow)?;
+    limits::check(env, from, to, amount, rebase::to_amount(env, to_balance)?)?;
+    storage::set_balance(env, to, &to_balance);
+    if let Some((split, treasury)) = &fee {
+        if split.treasury > 0 {
+            reflection::settle(env, treasury);
+            let balance = storage::balance_of(env, treasury) + split.treasury;
+            storage::set_balance(env, treasury, &balance);
+        }
+        reflection::distribute(env, split)?;
+    }
+
+    let event = TransferEvent {
+        from: from.clone(),
+        to: to.clone(),
+        token: env.current_contract_address(),
+        amount,
+        timestamp: env.ledger().timestamp(),
+    };
+    match (fee, rebase::get(env), batch_id) {
+        (Some((split, _)), _, _) => {
+            EventEmitter::fee_transfer(env, event, received, split.total(), batch_id)
+        }
+        (None, Some(rebasing), _) => {
+            EventEmitter::rebasing_transfer(env, event, shares, rebasing.index, batch_id)
+        }
+        (None, None, Some(batch_id)) => EventEmitter::batch_transfer(env, event, batch_id),
+        (None, None, None) => EventEmitter::transfer(env, event),
+    }
+    Ok(())
+}
+
+fn spend_allowance(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+) -> Result<(), TokenError> {
+    // Operators bypass the allowance, within their cap
+    if let Some(result) = operators::check(env, owner, spender, amount) {
+        return result;
+    }
+    let allowance = storage::allowance(env, owner, spender);
+    if allowance < amount {
+        return Err(TokenError::InsufficientAllowance);
+    }
+    storage::set_allowance(env, owner, spender, allowance - amount);
+    Ok(())
+}
+
+fn set_approval(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    via_permit: bool,
+) -> Result<(), TokenError> {
+    if amount < 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    storage::set_allowance(env, owner, spender, amount);
+    EventEmitter::approval(
+        env,
+        ApprovalEvent {
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+        via_permit,
+    );
+    Ok(())
+}
+
+fn validate_metadata(metadata: &TokenMetadata) -> Result<(), TokenError> {
+    if metadata.decimals > MAX_DECIMALS || metadata.name.len() == 0 || metadata.symbol.len() == 0 {
+        return Err(TokenError::InvalidMetadata);
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Anti-whale transfer limits
+//!
+//! Once configured, every transfer is checked against an optional cap on
+//! the amount sent, an optional cap on the recipient's resulting balance and
+//! a cooldown between transfers from the same sender. Exempt addresses
+//! (pools, the treasury) skip the checks on their side of a transfer. Mints
+//! and clawbacks are not limited.
+//!
+//! Rejections use the shared `ContractError` codes. Debug builds also log
+//! a `LIMIT_HIT` diagnostic event naming the limit that tripped. Unlike a
+//! contract event it is kept when the rejected transfer is rolled back.
+
+use soroban_sdk::{contracttype, log, symbol_short, Address, Env, Symbol};
+
+use crate::error::TokenError;
+use crate::storage::DataKey;
+
+const LIMITS_KEY: Symbol = symbol_short!("LIMITS");
+
+/// Limits applied to every non-exempt address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferLimits {
+    /// Largest single transfer a sender may make
+    pub max_transfer: Option<i128>,
+    /// Largest balance a recipient may end up with
+    pub max_balance: Option<i128>,
+    /// Seconds a sender must wait between transfers; 0 for none
+    pub cooldown: u64,
+}
+
+pub fn get(env: &Env) -> Option<TransferLimits> {
+    env.storage().instance().get(&LIMITS_KEY)
+}
+
+pub fn set(env: &Env, limits: Option<TransferLimits>) -> Result<(), TokenError> {
+    match limits {
+        Some(limits) => {
+            let positive = |cap: Option<i128>| cap.is_none_or(|cap| cap > 0);
+            if !positive(limits.max_transfer) || !positive(limits.max_balance) {
+                return Err(TokenError::InvalidAmount);
+            }
+            env.storage().instance().set(&LIMITS_KEY, &limits);
+        }
+        None => env.storage().instance().remove(&LIMITS_KEY),
+    }
+    Ok(())
+}
+
+pub fn is_exempt(env: &Env, addr: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::LimitExempt(addr.clone()))
+}
+
+pub fn set_exempt(env: &Env, addr: &Address, exempt: bool) {
+    let key = DataKey::LimitExempt(addr.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Check a transfer of `amount` leaving `to` with `to_balance`, and start
+/// the sender's cooldown
+pub fn check(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    to_balance: i128,
+) -> Result<(), TokenError> {
+    let Some(limits) = get(env) else {
+        return Ok(());
+    };
+
+    if !is_exempt(env, from) {
+        if let Some(max) = limits.max_transfer {
+            if amount > max {
+                return Err(hit(env, from, TokenError::TransferLimitExceeded, max));
+            }
+        }
+        if limits.cooldown > 0 {
+            let now = env.ledger().timestamp();
+            let key = DataKey::LastTransfer(from.clone());
+            if let Some(last) = env.storage().persistent().get::<_, u64>(&key) {
+                let ready = last.saturating_add(limits.cooldown);
+                if now < ready {
+                    return Err(hit(env, from, TokenError::TransferCooldown, ready as i128));
+                }
+            }
+            env.storage().persistent().set(&key, &now);
+        }
+    }
+
+    if !is_exempt(env, to) {
+        if let Some(max) = limits.max_balance {
+            if to_balance > max {
+                return Err(hit(env, to, TokenError::BalanceLimitExceeded, max));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Report `error` for `account`, whose limit was `limit`
+fn hit(env: &Env, account: &Address, error: TokenError, limit: i128) -> TokenError {
+    log!(env, "LIMIT_HIT", account.clone(), error as u32, limit);
+    error
+}
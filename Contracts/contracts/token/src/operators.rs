@@ -0,0 +1,87 @@
+//! Operator approvals
+//!
+//! An owner can approve an operator, such as a vault, router or the
+//! staking contract, to move any amount of their balance through
+//! `transfer_from` without a per-amount allowance. An optional cap bounds
+//! what a single transfer by that operator may move. Approvals and
+//! revocations are announced as `AUTHORIZATION_CHANGED` events with reason
+//! `operator`, naming the operator as the account and the owner as the
+//! changer.
+
+use shared::events::{AuthorizationChangedEvent, EventEmitter};
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+use crate::error::TokenError;
+use crate::storage::DataKey;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorApproval {
+    /// Most a single transfer by the operator may move, if limited
+    pub cap: Option<i128>,
+}
+
+pub fn get(env: &Env, owner: &Address, operator: &Address) -> Option<OperatorApproval> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Operator(owner.clone(), operator.clone()))
+}
+
+pub fn set(env: &Env, owner: &Address, operator: &Address, approved: bool) {
+    let key = DataKey::Operator(owner.clone(), operator.clone());
+    if approved {
+        if env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &OperatorApproval { cap: None });
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    EventEmitter::authorization_changed(
+        env,
+        AuthorizationChangedEvent {
+            account: operator.clone(),
+            changed_by: owner.clone(),
+            authorized: approved,
+            timestamp: env.ledger().timestamp(),
+        },
+        symbol_short!("operator"),
+    );
+}
+
+/// Limit each transfer by an approved operator to `cap`, or lift the limit
+pub fn set_cap(
+    env: &Env,
+    owner: &Address,
+    operator: &Address,
+    cap: Option<i128>,
+) -> Result<(), TokenError> {
+    let mut approval = get(env, owner, operator).ok_or(TokenError::NotOperator)?;
+    if cap.is_some_and(|cap| cap <= 0) {
+        return Err(TokenError::InvalidAmount);
+    }
+    approval.cap = cap;
+    env.storage().persistent().set(
+        &DataKey::Operator(owner.clone(), operator.clone()),
+        &approval,
+    );
+    Ok(())
+}
+
+/// Whether `operator` may move `amount` of `owner`'s balance. `None` when
+/// it is not an operator for `owner` at all.
+pub fn check(
+    env: &Env,
+    owner: &Address,
+    operator: &Address,
+    amount: i128,
+) -> Option<Result<(), TokenError>> {
+    let approval = get(env, owner, operator)?;
+    Some(match approval.cap {
+        Some(cap) if amount > cap => Err(TokenError::OperatorCapExceeded),
+        _ => Ok(()),
+    })
+}
@@ -0,0 +1,61 @@
+//! Signed approvals
+//!
+//! Owners register an ed25519 key once with `set_permit_key`. Afterwards
+//! anyone can submit an approval signed with that key, so the owner never
+//! has to send a transaction. The signature covers the owner, spender,
+//! amount, the owner's current permit nonce and a deadline, under the
+//! `permit` domain of this token; the nonce is consumed on use so each
+//! signature works only once.
+
+use shared::deadline::require_not_expired;
+use shared::nonce::NonceManager;
+use shared::typed_data::TypedData;
+use soroban_sdk::{symbol_short, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::error::TokenError;
+use crate::storage;
+
+/// Nonce and signing domain of permits
+pub const PERMIT_DOMAIN: Symbol = symbol_short!("permit");
+
+/// Digest an owner signs to approve `spender` for `amount`
+pub fn permit_digest(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    nonce: u64,
+    deadline: u64,
+) -> BytesN<32> {
+    TypedData::new(env, &PERMIT_DOMAIN, &symbol_short!("Permit"))
+        .field(symbol_short!("owner"), owner.clone())
+        .field(symbol_short!("spender"), spender.clone())
+        .field(symbol_short!("amount"), amount)
+        .field(symbol_short!("nonce"), nonce)
+        .field(symbol_short!("deadline"), deadline)
+        .digest()
+}
+
+/// Verify a permit and consume the owner's nonce. An invalid signature traps.
+pub fn verify(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    deadline: u64,
+    signature: &BytesN<64>,
+) -> Result<(), TokenError> {
+    require_not_expired(env, deadline).map_err(|_| TokenError::PermitExpired)?;
+    let public_key = storage::permit_key(env, owner).ok_or(TokenError::PermitKeyNotSet)?;
+
+    let nonce = NonceManager::peek_nonce(env, owner, &PERMIT_DOMAIN);
+    let digest = permit_digest(env, owner, spender, amount, nonce, deadline);
+    env.crypto().ed25519_verify(
+        &public_key,
+        &Bytes::from_array(env, &digest.to_array()),
+        signature,
+    );
+
+    NonceManager::consume_nonce(env, owner, &PERMIT_DOMAIN);
+    Ok(())
+}
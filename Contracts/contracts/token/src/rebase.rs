@@ -0,0 +1,102 @@
+//! Interest-bearing rebasing mode
+//!
+//! Off by default. Once the admin enables it, stored balances are shares
+//! and every balance the token reports is `shares * index / INDEX_SCALE`.
+//! The index starts at `INDEX_SCALE` and only the appointed updater (the
+//! savings or lending module paying the interest) can move it, never
+//! down. Raising it grows every holder's balance at once without touching
+//! their entries.
+//!
+//! Amounts passed in (transfers, mints, allowances) stay in balance units
+//! and are converted at the current index. Debits round the shares up and
+//! credits round them down, so conversions never create value. The supply
+//! cap, emission schedule and balance checkpoints count shares.
+//!
+//! Rebasing cannot be combined with the transfer fee, and once enabled it
+//! cannot be turned off.
+
+use shared::events::{EventEmitter, RebaseEvent};
+use shared::math::mul_div;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::error::TokenError;
+use crate::reflection;
+
+/// Fixed-point scale of the rebasing index
+pub const INDEX_SCALE: i128 = 1_000_000_000;
+
+const REBASE_KEY: Symbol = symbol_short!("REBASE");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rebasing {
+    /// Only address allowed to move the index
+    pub updater: Address,
+    pub index: i128,
+}
+
+pub fn get(env: &Env) -> Option<Rebasing> {
+    env.storage().instance().get(&REBASE_KEY)
+}
+
+/// Turn rebasing on with `updater` moving the index, or hand the index to
+/// a new updater if it is already on
+pub fn enable(env: &Env, updater: Address) -> Result<(), TokenError> {
+    if reflection::config(env).is_some() {
+        return Err(TokenError::RebaseConflict);
+    }
+    let index = get(env).map_or(INDEX_SCALE, |rebasing| rebasing.index);
+    env.storage()
+        .instance()
+        .set(&REBASE_KEY, &Rebasing { updater, index });
+    Ok(())
+}
+
+/// Move the index to `index`, on the updater's authority
+pub fn set_index(env: &Env, index: i128) -> Result<(), TokenError> {
+    let mut rebasing = get(env).ok_or(TokenError::RebaseDisabled)?;
+    rebasing.updater.require_auth();
+    if index < rebasing.index {
+        return Err(TokenError::InvalidIndex);
+    }
+    let previous_index = rebasing.index;
+    rebasing.index = index;
+    env.storage().instance().set(&REBASE_KEY, &rebasing);
+
+    EventEmitter::rebased(
+        env,
+        RebaseEvent {
+            token: env.current_contract_address(),
+            previous_index,
+            index,
+            updated_by: rebasing.updater,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Balance units `shares` are worth, rounded down
+pub fn to_amount(env: &Env, shares: i128) -> Result<i128, TokenError> {
+    match get(env) {
+        Some(rebasing) => mul_div(shares, rebasing.index, INDEX_SCALE).ok_or(TokenError::Overflow),
+        None => Ok(shares),
+    }
+}
+
+/// Shares worth `amount` balance units, rounded up for debits and down for
+/// credits
+pub fn to_shares(env: &Env, amount: i128, round_up: bool) -> Result<i128, TokenError> {
+    let Some(rebasing) = get(env) else {
+        return Ok(amount);
+    };
+    let scaled = amount
+        .checked_mul(INDEX_SCALE)
+        .ok_or(TokenError::Overflow)?;
+    let shares = scaled / rebasing.index;
+    Ok(if round_up && shares * rebasing.index < scaled {
+        shares + 1
+    } else {
+        shares
+    })
+}
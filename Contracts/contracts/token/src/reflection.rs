@@ -0,0 +1,178 @@
+//! Fee-on-transfer and reflection mode
+//!
+//! Off by default. When the admin installs a `TransferFee`, every transfer
+//! is charged `fee_bps` of its amount and the recipient receives the rest.
+//! The fee is split between a burn, the treasury and all holders. The
+//! holders' part is spread pro rata over the stored balances with a
+//! reward-per-token accumulator: each account remembers the accumulator at
+//! its last touch and is credited `balance * (acc - snapshot)` before its
+//! balance next changes. `balance` already includes those pending credits,
+//! and `reflected` reports the lifetime total an account has been credited.
+//!
+//! Reflections not yet credited are part of the supply but of no stored
+//! balance, so they are tracked as the reflected pool and left out of the
+//! balance each distribution is spread over.
+
+use shared::math::mul_div;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::error::TokenError;
+use crate::storage::{self, DataKey};
+use crate::{rebase, supply};
+
+const FEE_KEY: Symbol = symbol_short!("XFER_FEE");
+const ACC_KEY: Symbol = symbol_short!("REFL_ACC");
+const POOL_KEY: Symbol = symbol_short!("REFL_POOL");
+
+/// Fixed-point scale of the per-token accumulator
+const SCALE: i128 = 1_000_000_000_000;
+const BPS_DENOMINATOR: u32 = 10_000;
+/// Highest fee the admin may charge
+pub const MAX_TRANSFER_FEE_BPS: u32 = 1_000;
+
+/// Transfer fee and how it is split. The three shares are fractions of the
+/// fee in basis points and must add up to 10 000.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferFee {
+    pub fee_bps: u32,
+    pub burn_bps: u32,
+    pub treasury_bps: u32,
+    pub holders_bps: u32,
+    pub treasury: Address,
+}
+
+/// How one transfer's fee was split
+pub struct FeeSplit {
+    pub burn: i128,
+    pub treasury: i128,
+    pub holders: i128,
+}
+
+impl FeeSplit {
+    pub fn total(&self) -> i128 {
+        self.burn + self.treasury + self.holders
+    }
+}
+
+pub fn config(env: &Env) -> Option<TransferFee> {
+    env.storage().instance().get(&FEE_KEY)
+}
+
+pub fn set_config(env: &Env, fee: Option<TransferFee>) -> Result<(), TokenError> {
+    match fee {
+        Some(_) if rebase::get(env).is_some() => return Err(TokenError::RebaseConflict),
+        Some(fee) => {
+            if fee.fee_bps > MAX_TRANSFER_FEE_BPS
+                || fee.burn_bps + fee.treasury_bps + fee.holders_bps != BPS_DENOMINATOR
+            {
+                return Err(TokenError::InvalidFee);
+            }
+            env.storage().instance().set(&FEE_KEY, &fee);
+        }
+        None => env.storage().instance().remove(&FEE_KEY),
+    }
+    Ok(())
+}
+
+/// Fee owed on a transfer of `amount`, if fee mode is on
+pub fn split(env: &Env, amount: i128) -> Result<Option<(FeeSplit, Address)>, TokenError> {
+    let Some(fee) = config(env) else {
+        return Ok(None);
+    };
+    let total = bps(amount, fee.fee_bps)?;
+    let burn = bps(total, fee.burn_bps)?;
+    let treasury = bps(total, fee.treasury_bps)?;
+    let split = FeeSplit {
+        burn,
+        treasury,
+        holders: total - burn - treasury,
+    };
+    Ok(Some((split, fee.treasury)))
+}
+
+/// Burn the fee's burn share and spread its holder share over the stored
+/// balances. The treasury share is credited by the caller.
+pub fn distribute(env: &Env, split: &FeeSplit) -> Result<(), TokenError> {
+    if split.burn > 0 {
+        supply::record_burn(env, split.burn);
+    }
+    if split.holders <= 0 {
+        return Ok(());
+    }
+    let pool = pool(env)
+        .checked_add(split.holders)
+        .ok_or(TokenError::Overflow)?;
+    let circulating = supply::total_supply(env) - pool;
+    if circulating <= 0 {
+        return Ok(());
+    }
+    let acc = mul_div(split.holders, SCALE, circulating)
+        .and_then(|step| accumulator(env).checked_add(step))
+        .ok_or(TokenError::Overflow)?;
+    env.storage().instance().set(&ACC_KEY, &acc);
+    env.storage().instance().set(&POOL_KEY, &pool);
+    Ok(())
+}
+
+/// Stored balance plus reflections not yet credited
+pub fn balance_of(env: &Env, addr: &Address) -> i128 {
+    storage::balance_of(env, addr) + pending(env, addr)
+}
+
+/// Credit `addr` with its pending reflections. Call before changing its
+/// balance.
+pub fn settle(env: &Env, addr: &Address) {
+    let acc = accumulator(env);
+    if acc == 0 {
+        return;
+    }
+    let owed = pending(env, addr);
+    if owed > 0 {
+        let balance = storage::balance_of(env, addr);
+        storage::set_balance(env, addr, &(balance + owed));
+        env.storage().instance().set(&POOL_KEY, &(pool(env) - owed));
+        let key = DataKey::Reflected(addr.clone());
+        let credited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(credited + owed));
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReflectionSnapshot(addr.clone()), &acc);
+}
+
+/// Reflections credited to `addr` so far, including pending ones
+pub fn reflected(env: &Env, addr: &Address) -> i128 {
+    let credited: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Reflected(addr.clone()))
+        .unwrap_or(0);
+    credited + pending(env, addr)
+}
+
+fn pending(env: &Env, addr: &Address) -> i128 {
+    let acc = accumulator(env);
+    if acc == 0 {
+        return 0;
+    }
+    let snapshot: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ReflectionSnapshot(addr.clone()))
+        .unwrap_or(0);
+    storage::balance_of(env, addr) * (acc - snapshot) / SCALE
+}
+
+fn accumulator(env: &Env) -> i128 {
+    env.storage().instance().get(&ACC_KEY).unwrap_or(0)
+}
+
+/// Reflections distributed but not yet credited to a stored balance
+fn pool(env: &Env) -> i128 {
+    env.storage().instance().get(&POOL_KEY).unwrap_or(0)
+}
+
+fn bps(amount: i128, bps: u32) -> Result<i128, TokenError> {
+    mul_div(amount, bps as i128, BPS_DENOMINATOR as i128).ok_or(TokenError::Overflow)
+}
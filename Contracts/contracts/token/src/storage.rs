@@ -1,14 +1,99 @@
-use soroban_sdk::{Env, Address, Symbol};
+use crate::checkpoints;
+use crate::error::TokenError;
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol};
 
-const ADMIN_KEY: Symbol = Symbol::short("ADMIN");
+const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+const METADATA_KEY: Symbol = symbol_short!("METADATA");
+
+/// Descriptive token metadata shown by wallets and explorers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    pub uri: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Balance(Address),
+    Allowance(Address, Address),
+    PermitKey(Address),
+    Frozen(Address),
+    LimitExempt(Address),
+    LastTransfer(Address),
+    ReflectionSnapshot(Address),
+    Reflected(Address),
+    Operator(Address, Address),
+    Checkpoints(Address),
+}
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&ADMIN_KEY)
+}
 
 pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&ADMIN_KEY, admin);
 }
 
-pub fn get_admin(env: &Env) -> Address {
+pub fn get_admin(env: &Env) -> Result<Address, TokenError> {
     env.storage()
         .instance()
         .get(&ADMIN_KEY)
-        .expect("Admin not set")
+        .ok_or(TokenError::NotInitialized)
+}
+
+pub fn balance_of(env: &Env, addr: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(addr.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_balance(env: &Env, addr: &Address, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(addr.clone()), amount);
+    checkpoints::record(env, addr, *amount);
+}
+
+pub fn get_metadata(env: &Env) -> Result<TokenMetadata, TokenError> {
+    env.storage()
+        .instance()
+        .get(&METADATA_KEY)
+        .ok_or(TokenError::NotInitialized)
+}
+
+pub fn set_metadata(env: &Env, metadata: &TokenMetadata) {
+    env.storage().instance().set(&METADATA_KEY, metadata);
+}
+
+pub fn allowance(env: &Env, owner: &Address, spender: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Allowance(owner.clone(), spender.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_allowance(env: &Env, owner: &Address, spender: &Address, amount: i128) {
+    let key = DataKey::Allowance(owner.clone(), spender.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+pub fn permit_key(env: &Env, owner: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PermitKey(owner.clone()))
+}
+
+pub fn set_permit_key(env: &Env, owner: &Address, key: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PermitKey(owner.clone()), key);
 }
@@ -0,0 +1,154 @@
+//! Supply cap and emission schedule
+//!
+//! The admin may fix a hard `max_supply` (which can afterwards only be
+//! lowered) and, independently, an emission schedule: consecutive epochs of
+//! `epoch_length` seconds starting at `start`, each with a non-increasing
+//! mint allowance. Once the schedule runs out nothing more can be minted.
+//! Violations are reported with the shared `ContractError`.
+
+use shared::epoch::EpochClock;
+use shared::errors::ContractError;
+use soroban_sdk::{contracttype, symbol_short, Env, Symbol, Vec};
+
+const TOTAL_SUPPLY_KEY: Symbol = symbol_short!("SUPPLY");
+const MAX_SUPPLY_KEY: Symbol = symbol_short!("MAX_SUPP");
+const SCHEDULE_KEY: Symbol = symbol_short!("EMISSION");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionSchedule {
+    pub start: u64,
+    pub epoch_length: u64,
+    /// Mint allowance of each epoch, in order; must not increase
+    pub allowances: Vec<i128>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum SupplyKey {
+    EpochMinted(u32),
+}
+
+pub fn total_supply(env: &Env) -> i128 {
+    env.storage().instance().get(&TOTAL_SUPPLY_KEY).unwrap_or(0)
+}
+
+fn set_total_supply(env: &Env, supply: i128) {
+    env.storage().instance().set(&TOTAL_SUPPLY_KEY, &supply);
+}
+
+pub fn max_supply(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&MAX_SUPPLY_KEY)
+}
+
+/// Set the hard cap. Once set it may only be lowered, never below the
+/// current supply.
+pub fn set_max_supply(env: &Env, max: i128) -> Result<(), ContractError> {
+    if max < total_supply(env) {
+        return Err(ContractError::InvalidAmount);
+    }
+    if let Some(current) = max_supply(env) {
+        if max > current {
+            return Err(ContractError::SupplyCapExceeded);
+        }
+    }
+    env.storage().instance().set(&MAX_SUPPLY_KEY, &max);
+    Ok(())
+}
+
+pub fn schedule(env: &Env) -> Option<EmissionSchedule> {
+    env.storage().instance().get(&SCHEDULE_KEY)
+}
+
+/// Install the emission schedule. It can be set only once.
+pub fn set_schedule(env: &Env, schedule: &EmissionSchedule) -> Result<(), ContractError> {
+    if env.storage().instance().has(&SCHEDULE_KEY) {
+        return Err(ContractError::AlreadyExists);
+    }
+    if schedule.epoch_length == 0 || schedule.allowances.is_empty() {
+        return Err(ContractError::InvalidAmount);
+    }
+    let mut previous = i128::MAX;
+    for allowance in schedule.allowances.iter() {
+        if allowance < 0 || allowance > previous {
+            return Err(ContractError::InvalidAmount);
+        }
+        previous = allowance;
+    }
+    env.storage().instance().set(&SCHEDULE_KEY, schedule);
+    Ok(())
+}
+
+/// Index of the current epoch, `None` before the schedule starts
+fn current_epoch(env: &Env, schedule: &EmissionSchedule) -> Option<u32> {
+    if env.ledger().timestamp() < schedule.start {
+        return None;
+    }
+    let clock = EpochClock {
+        genesis: schedule.start,
+        length: schedule.epoch_length,
+    };
+    Some(clock.current_epoch(env) as u32)
+}
+
+fn epoch_minted(env: &Env, epoch: u32) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&SupplyKey::EpochMinted(epoch))
+        .unwrap_or(0)
+}
+
+/// Amount the schedule still allows in the current epoch
+fn epoch_remaining(env: &Env, schedule: &EmissionSchedule) -> i128 {
+    match current_epoch(env, schedule) {
+        Some(epoch) => match schedule.allowances.get(epoch) {
+            Some(allowance) => allowance - epoch_minted(env, epoch),
+            None => 0,
+        },
+        None => 0,
+    }
+}
+
+/// How much can be minted right now under both the cap and the schedule
+pub fn remaining_mintable(env: &Env) -> i128 {
+    let mut remaining = match max_supply(env) {
+        Some(max) => max - total_supply(env),
+        None => i128::MAX - total_supply(env),
+    };
+    if let Some(schedule) = schedule(env) {
+        remaining = remaining.min(epoch_remaining(env, &schedule));
+    }
+    remaining.max(0)
+}
+
+/// Check `amount` against the cap and the schedule and record it as minted
+pub fn record_mint(env: &Env, amount: i128) -> Result<(), ContractError> {
+    let supply = total_supply(env)
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    if let Some(max) = max_supply(env) {
+        if supply > max {
+            return Err(ContractError::SupplyCapExceeded);
+        }
+    }
+
+    if let Some(schedule) = schedule(env) {
+        if amount > epoch_remaining(env, &schedule) {
+            return Err(ContractError::EmissionLimitExceeded);
+        }
+        // epoch_remaining > 0 implies the schedule has started
+        let epoch = current_epoch(env, &schedule).ok_or(ContractError::EmissionLimitExceeded)?;
+        env.storage().persistent().set(
+            &SupplyKey::EpochMinted(epoch),
+            &(epoch_minted(env, epoch) + amount),
+        );
+    }
+
+    set_total_supply(env, supply);
+    Ok(())
+}
+
+/// Remove burned tokens from the supply
+pub fn record_burn(env: &Env, amount: i128) {
+    set_total_supply(env, total_supply(env) - amount);
+}
@@ -1,16 +1,43 @@
-use soroban_sdk::{Env, testutils::Address as _};
-use token::TokenContract;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    Address, Env, IntoVal, String, Symbol,
+};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+fn metadata(env: &Env) -> TokenMetadata {
+    TokenMetadata {
+        name: String::from_str(env, "Stellara"),
+        symbol: String::from_str(env, "STLR"),
+        decimals: 7,
+        uri: String::from_str(env, "https://stellara.network/token.json"),
+    }
+}
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
 fn non_admin_cannot_mint() {
     let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(&env, &contract_id);
 
-    let admin = Address::random(&env);
-    let attacker = Address::random(&env);
-
-    TokenContract::initialize(env.clone(), admin);
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    client.initialize(&admin, &metadata(&env), &false);
 
-    // attacker tries mint
-    TokenContract::mint(env, attacker, 100);
+    // Minting to the attacker still requires the admin's signature
+    client.mint(&attacker, &100);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin,
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id,
+                    Symbol::new(&env, "mint"),
+                    (attacker, 100_i128).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
 }
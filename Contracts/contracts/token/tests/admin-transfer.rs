@@ -0,0 +1,80 @@
+use shared::admin_transfer::{AdminTransferError, ADMIN_TRANSFER_EXPIRY, TRANSFER_REASON};
+use shared::events::{topics, AdminChangedEvent};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use test_utils::{assert_standard_event, payload};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    (client, admin)
+}
+
+#[test]
+fn proposed_admin_takes_over_on_accept() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let successor = Address::generate(&env);
+
+    client.propose_admin(&successor);
+    assert_standard_event(&env, topics::ADMIN_TRANSFER_PROPOSED, |_| true);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(client.pending_admin().unwrap().proposed_admin, successor);
+
+    client.accept_admin(&successor);
+    assert_eq!(client.admin(), successor);
+    assert!(client.pending_admin().is_none());
+
+    let captured = assert_standard_event(&env, topics::ADMIN_CHANGED, |_| true);
+    let event: AdminChangedEvent = payload(&captured);
+    assert_eq!(event.previous_admin, admin);
+    assert_eq!(event.reason, TRANSFER_REASON);
+}
+
+#[test]
+fn only_the_proposed_admin_can_accept() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let result = client.try_accept_admin(&admin);
+    assert_eq!(result, Err(Ok(AdminTransferError::NoPendingTransfer)));
+
+    client.propose_admin(&Address::generate(&env));
+    let result = client.try_accept_admin(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(AdminTransferError::NotProposedAdmin)));
+    assert_eq!(client.admin(), admin);
+}
+
+#[test]
+fn proposals_expire_and_can_be_cancelled() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let successor = Address::generate(&env);
+
+    client.propose_admin(&successor);
+    env.ledger()
+        .with_mut(|li| li.timestamp += ADMIN_TRANSFER_EXPIRY + 1);
+    let result = client.try_accept_admin(&successor);
+    assert_eq!(result, Err(Ok(AdminTransferError::TransferExpired)));
+
+    client.propose_admin(&successor);
+    client.cancel_admin_transfer();
+    let result = client.try_accept_admin(&successor);
+    assert_eq!(result, Err(Ok(AdminTransferError::NoPendingTransfer)));
+    assert_eq!(client.admin(), admin);
+}
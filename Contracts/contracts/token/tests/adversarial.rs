@@ -1,14 +1,25 @@
+use shared::errors::ContractError;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
 #[test]
-#[should_panic(expected = "Overflow")]
 fn mint_overflow_attack() {
     let env = Env::default();
-    let admin = Address::random(&env);
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
 
-    TokenContract::initialize(env.clone(), admin.clone());
+    let metadata = TokenMetadata {
+        name: String::from_str(&env, "Stellara"),
+        symbol: String::from_str(&env, "STLR"),
+        decimals: 7,
+        uri: String::from_str(&env, ""),
+    };
+    client.initialize(&admin, &metadata, &false);
 
-    TokenContract::mint(
-        env,
-        admin,
-        i128::MAX
-    );
+    client.mint(&admin, &1);
+    let result = client.try_mint(&admin, &i128::MAX);
+    assert_eq!(result, Err(Ok(ContractError::Overflow)));
+    assert_eq!(client.balance(&admin), 1);
 }
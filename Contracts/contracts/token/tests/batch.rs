@@ -0,0 +1,93 @@
+use shared::events::{metadata_keys, topics};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+use test_utils::standard_events;
+use token::{FuncCall, TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let payer = Address::generate(env);
+    client.mint(&payer, &1_000);
+    (client, payer)
+}
+
+#[test]
+fn transfer_batch_tags_every_leg() {
+    let env = Env::default();
+    let (client, payer) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let batch_id = client.transfer_batch(
+        &payer,
+        &vec![&env, (alice.clone(), 100), (bob.clone(), 250)],
+    );
+    assert_eq!(client.balance(&alice), 100);
+    assert_eq!(client.balance(&bob), 250);
+    assert_eq!(client.balance(&payer), 650);
+
+    let events = standard_events(&env);
+    assert_eq!(events.len(), 2);
+    for captured in events.iter() {
+        assert_eq!(captured.event.topic, topics::TRANSFER);
+        let id = captured.metadata::<u64>(&env, metadata_keys::BATCH_KEY);
+        assert_eq!(id, Some(batch_id));
+    }
+
+    // Every batch gets a fresh id
+    let next = client.transfer_batch(&payer, &vec![&env, (alice, 1)]);
+    assert_eq!(next, batch_id + 1);
+}
+
+#[test]
+fn transfer_batch_is_all_or_nothing() {
+    let env = Env::default();
+    let (client, payer) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let result = client.try_transfer_batch(
+        &payer,
+        &vec![&env, (alice.clone(), 600), (bob.clone(), 600)],
+    );
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.balance(&payer), 1_000);
+}
+
+#[test]
+fn multicall_runs_mixed_operations() {
+    let env = Env::default();
+    let (client, payer) = setup(&env);
+    let spender = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.multicall(&vec![
+        &env,
+        FuncCall::Approve(payer.clone(), spender.clone(), 300),
+        FuncCall::TransferFrom(spender.clone(), payer.clone(), alice.clone(), 200),
+        FuncCall::Transfer(payer.clone(), alice.clone(), 50),
+    ]);
+    assert_eq!(client.balance(&alice), 250);
+    assert_eq!(client.allowance(&payer, &spender), 100);
+
+    let result = client.try_multicall(&vec![
+        &env,
+        FuncCall::Transfer(payer.clone(), alice.clone(), 100),
+        FuncCall::TransferFrom(spender, payer.clone(), alice.clone(), 101),
+    ]);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(client.balance(&alice), 250);
+}
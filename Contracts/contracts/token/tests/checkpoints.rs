@@ -0,0 +1,96 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use token::{Checkpoint, TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    set_ledger(env, 10);
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+fn set_ledger(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = sequence);
+}
+
+#[test]
+fn balance_at_reads_past_ledgers() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let friend = Address::generate(&env);
+
+    set_ledger(&env, 20);
+    client.transfer(&holder, &friend, &1_000);
+    // Several changes in one ledger keep one checkpoint
+    client.transfer(&holder, &friend, &1_000);
+    set_ledger(&env, 30);
+    client.transfer(&friend, &holder, &500);
+    set_ledger(&env, 40);
+
+    assert_eq!(client.balance_at(&holder, &9), 0);
+    assert_eq!(client.balance_at(&holder, &10), 10_000);
+    assert_eq!(client.balance_at(&holder, &19), 10_000);
+    assert_eq!(client.balance_at(&holder, &20), 8_000);
+    assert_eq!(client.balance_at(&holder, &29), 8_000);
+    assert_eq!(client.balance_at(&holder, &40), 8_500);
+    assert_eq!(client.balance_at(&friend, &25), 2_000);
+    assert_eq!(client.balance_at(&friend, &30), 1_500);
+    assert_eq!(client.checkpoints(&holder).len(), 3);
+
+    let result = client.try_balance_at(&holder, &41);
+    assert_eq!(result, Err(Ok(TokenError::SnapshotUnavailable)));
+}
+
+#[test]
+fn horizon_prunes_old_checkpoints() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let friend = Address::generate(&env);
+    for ledger in [20, 30, 40, 50] {
+        set_ledger(&env, ledger);
+        client.transfer(&holder, &friend, &100);
+    }
+    assert_eq!(client.checkpoints(&holder).len(), 5);
+
+    let result = client.try_set_checkpoint_horizon(&Some(0));
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+    client.set_checkpoint_horizon(&Some(25));
+    set_ledger(&env, 60);
+    client.transfer(&holder, &friend, &100);
+
+    // The checkpoint at 30 still covers the cutoff at 35
+    assert_eq!(
+        client.checkpoints(&holder).get(0),
+        Some(Checkpoint {
+            ledger: 30,
+            balance: 9_800
+        })
+    );
+    assert_eq!(client.checkpoints(&holder).len(), 4);
+    assert_eq!(client.balance_at(&holder, &35), 9_800);
+    assert_eq!(client.balance_at(&holder, &45), 9_700);
+    let result = client.try_balance_at(&holder, &34);
+    assert_eq!(result, Err(Ok(TokenError::SnapshotUnavailable)));
+
+    // Dropped history stays unavailable after the horizon is lifted
+    client.set_checkpoint_horizon(&None);
+    let result = client.try_balance_at(&holder, &20);
+    assert_eq!(result, Err(Ok(TokenError::SnapshotUnavailable)));
+    assert_eq!(client.balance_at(&holder, &35), 9_800);
+}
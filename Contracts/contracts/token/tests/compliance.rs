@@ -0,0 +1,148 @@
+use attestations::{AttestationRegistry, AttestationRegistryClient};
+use shared::attestations::{claims, ClaimRequirement};
+use shared::events::{metadata_keys, topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    Address, Env, String, Symbol, TryFromVal,
+};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env, compliance_enabled: bool) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Regulated USD"),
+            symbol: String::from_str(env, "RUSD"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &compliance_enabled,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &1_000);
+    (client, holder)
+}
+
+fn last_standard_event(env: &Env) -> StandardEventV2 {
+    let prefix = Symbol::new(env, EVENT_PREFIX);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(env, &data).unwrap())
+        .last()
+        .unwrap()
+}
+
+fn reason(env: &Env, event: &StandardEventV2) -> Symbol {
+    let value = event.metadata.get(metadata_keys::REASON_KEY).unwrap();
+    Symbol::try_from_val(env, &value).unwrap()
+}
+
+#[test]
+fn frozen_accounts_cannot_send_or_receive() {
+    let env = Env::default();
+    let (client, holder) = setup(&env, true);
+    let other = Address::generate(&env);
+
+    client.set_frozen(&holder, &true);
+    assert!(client.is_frozen(&holder));
+    let event = last_standard_event(&env);
+    assert_eq!(event.topic, topics::AUTHORIZATION_CHANGED);
+    assert_eq!(reason(&env, &event), symbol_short!("freeze"));
+
+    let result = client.try_transfer(&holder, &other, &10);
+    assert_eq!(result, Err(Ok(TokenError::AccountFrozen)));
+
+    client.mint(&other, &10);
+    let result = client.try_transfer(&other, &holder, &10);
+    assert_eq!(result, Err(Ok(TokenError::AccountFrozen)));
+
+    client.set_frozen(&holder, &false);
+    client.transfer(&holder, &other, &10);
+    assert_eq!(client.balance(&other), 20);
+}
+
+#[test]
+fn clawback_burns_balance() {
+    let env = Env::default();
+    let (client, holder) = setup(&env, true);
+
+    client.clawback(&holder, &400);
+    assert_eq!(client.balance(&holder), 600);
+
+    let event = last_standard_event(&env);
+    assert_eq!(event.topic, topics::BURN);
+    assert_eq!(reason(&env, &event), symbol_short!("clawback"));
+
+    let result = client.try_clawback(&holder, &601);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+}
+
+#[test]
+fn compliance_disabled_at_init_is_permanent() {
+    let env = Env::default();
+    let (client, holder) = setup(&env, false);
+    assert!(!client.compliance_enabled());
+
+    let result = client.try_clawback(&holder, &1);
+    assert_eq!(result, Err(Ok(TokenError::ComplianceDisabled)));
+    let result = client.try_set_frozen(&holder, &true);
+    assert_eq!(result, Err(Ok(TokenError::ComplianceDisabled)));
+
+    // The admin cannot re-enable it by appointing an officer
+    let result = client.try_set_compliance_officer(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(TokenError::ComplianceDisabled)));
+}
+
+#[test]
+fn compliance_officer_can_be_reassigned() {
+    let env = Env::default();
+    let (client, holder) = setup(&env, true);
+    let officer = Address::generate(&env);
+
+    client.set_compliance_officer(&officer);
+    client.set_frozen(&holder, &true);
+    assert_eq!(env.auths()[0].0, officer);
+    assert!(client.is_frozen(&holder));
+}
+
+#[test]
+fn transfers_require_attested_claims() {
+    let env = Env::default();
+    let (client, holder) = setup(&env, true);
+    let other = Address::generate(&env);
+    let registry_id = env.register_contract(None, AttestationRegistry);
+    let registry = AttestationRegistryClient::new(&env, &registry_id);
+    let admin = Address::generate(&env);
+    let kyc = Address::generate(&env);
+    registry.initialize(&admin);
+    registry.set_attestor(&admin, &kyc, &claims::KYC_TIER_2, &true);
+
+    let requirement = ClaimRequirement {
+        registry: registry_id,
+        claim: claims::KYC_TIER_2,
+    };
+    client.set_claim_requirement(&Some(requirement.clone()));
+    assert_eq!(client.claim_requirement(), Some(requirement));
+
+    registry.issue(&kyc, &holder, &claims::KYC_TIER_2, &0);
+    let result = client.try_transfer(&holder, &other, &10);
+    assert_eq!(result, Err(Ok(TokenError::MissingClaim)));
+    registry.issue(&kyc, &other, &claims::KYC_TIER_2, &0);
+    client.transfer(&holder, &other, &10);
+
+    registry.revoke(&kyc, &other, &claims::KYC_TIER_2);
+    let result = client.try_transfer(&holder, &other, &10);
+    assert_eq!(result, Err(Ok(TokenError::MissingClaim)));
+    client.set_claim_requirement(&None);
+    client.transfer(&holder, &other, &10);
+    assert_eq!(client.balance(&other), 20);
+}
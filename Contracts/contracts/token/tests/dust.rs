@@ -0,0 +1,90 @@
+use shared::events::{topics, DustSweptEvent};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+use test_utils::{assert_standard_event, payload};
+use token::{DustPolicy, TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+fn policy(env: &Env, min_transfer: i128) -> DustPolicy {
+    DustPolicy {
+        min_transfer,
+        treasury: Address::generate(env),
+    }
+}
+
+#[test]
+fn transfers_below_minimum_are_rejected() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(client.dust_policy(), None);
+    client.transfer(&holder, &other, &1);
+
+    client.set_dust_policy(&Some(policy(&env, 100)));
+    let result = client.try_transfer(&holder, &other, &99);
+    assert_eq!(result, Err(Ok(TokenError::BelowDustMinimum)));
+    client.transfer(&holder, &other, &100);
+    assert_eq!(client.balance(&other), 101);
+
+    client.set_dust_policy(&None);
+    client.transfer(&holder, &other, &1);
+    assert_eq!(client.balance(&other), 102);
+
+    let result = client.try_set_dust_policy(&Some(policy(&env, 0)));
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+}
+
+#[test]
+fn sweep_collects_sub_minimum_balances_to_treasury() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let (small, smaller, empty) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    client.mint(&small, &99);
+    client.mint(&smaller, &1);
+
+    let holders = vec![&env, holder.clone(), small.clone(), smaller.clone(), empty];
+    let result = client.try_sweep_dust(&holders);
+    assert_eq!(result, Err(Ok(TokenError::NoDustPolicy)));
+
+    let policy = policy(&env, 100);
+    client.set_dust_policy(&Some(policy.clone()));
+    assert_eq!(client.sweep_dust(&holders), 100);
+
+    assert_eq!(client.balance(&small), 0);
+    assert_eq!(client.balance(&smaller), 0);
+    assert_eq!(client.balance(&holder), 10_000);
+    assert_eq!(client.balance(&policy.treasury), 100);
+    assert_eq!(client.total_supply(), 10_100);
+
+    let captured = assert_standard_event(&env, topics::DUST_SWEPT, |_| true);
+    let event: DustSweptEvent = payload(&captured);
+    assert_eq!(event.token, client.address);
+    assert_eq!(event.treasury, policy.treasury);
+    assert_eq!((event.count, event.total), (2, 100));
+
+    // Nothing left to sweep
+    assert_eq!(client.sweep_dust(&holders), 0);
+}
@@ -0,0 +1,66 @@
+use shared::events::EventConfig;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, String,
+};
+use test_utils::standard_events;
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &1_000);
+    (client, holder)
+}
+
+#[test]
+fn both_formats_are_emitted_by_default() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    assert_eq!(client.event_config(), EventConfig::default());
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(env.events().all().len(), 2);
+    assert_eq!(standard_events(&env).len(), 1);
+}
+
+#[test]
+fn legacy_events_can_be_switched_off() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    client.set_event_config(&EventConfig {
+        emit_legacy: false,
+        emit_standard: true,
+    });
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(env.events().all().len(), 1);
+    assert_eq!(standard_events(&env).len(), 1);
+}
+
+#[test]
+fn standard_events_can_be_switched_off() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    client.set_event_config(&EventConfig {
+        emit_legacy: true,
+        emit_standard: false,
+    });
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(env.events().all().len(), 1);
+    assert!(standard_events(&env).is_empty());
+}
@@ -0,0 +1,87 @@
+use shared::events::{topics, EventLogConfig};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &1_000);
+    (client, holder)
+}
+
+fn enable(client: &TokenContractClient, capacity: u32, ttl_ledgers: u32) {
+    client.set_event_log(&EventLogConfig {
+        capacity,
+        ttl_ledgers,
+    });
+}
+
+#[test]
+fn log_is_off_by_default() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(client.event_log_config(), None);
+    assert!(client.get_events(&0, &10).is_empty());
+}
+
+#[test]
+fn ring_buffer_keeps_most_recent_events() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    enable(&client, 3, 1_000);
+
+    let to = Address::generate(&env);
+    for _ in 0..5 {
+        client.transfer(&holder, &to, &1);
+    }
+
+    // Transfers are sequences 1..=5 and only the last 3 are kept
+    let events = client.get_events(&0, &10);
+    assert_eq!(events.len(), 3);
+    assert_eq!(events.get(0).unwrap().sequence, 3);
+    assert_eq!(events.get(2).unwrap().sequence, 5);
+    assert_eq!(events.get(2).unwrap().topic, topics::TRANSFER);
+
+    let page = client.get_events(&4, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().sequence, 4);
+}
+
+#[test]
+fn expired_entries_are_skipped() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    enable(&client, 10, 20);
+
+    let to = Address::generate(&env);
+    client.transfer(&holder, &to, &1);
+    env.ledger().with_mut(|li| li.sequence_number += 15);
+    client.transfer(&holder, &to, &1);
+    assert_eq!(client.get_events(&0, &10).len(), 2);
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    let events = client.get_events(&0, &10);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events.get(0).unwrap().sequence, 2);
+
+    enable(&client, 0, 0);
+    assert!(client.get_events(&0, &10).is_empty());
+}
@@ -0,0 +1,156 @@
+use shared::events::{topics, TransferHookChangedEvent};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use test_utils::{assert_standard_event, payload};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+mod deny_list {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+    use token::TransferHook;
+
+    /// Refuses transfers from or to a denied address
+    #[contract]
+    pub struct DenyListHook;
+
+    #[contractimpl]
+    impl DenyListHook {
+        pub fn deny(env: Env, addr: Address) {
+            env.storage().persistent().set(&addr, &true);
+        }
+    }
+
+    #[contractimpl]
+    impl TransferHook for DenyListHook {
+        fn before_transfer(
+            env: Env,
+            _token: Address,
+            from: Address,
+            to: Address,
+            _amount: i128,
+        ) -> bool {
+            !env.storage().persistent().has(&from) && !env.storage().persistent().has(&to)
+        }
+    }
+}
+
+mod reentrant {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+    use token::{TokenContractClient, TransferHook};
+
+    /// Tries to move funds back through the token from inside its decision
+    /// and records whether it could
+    #[contract]
+    pub struct ReentrantHook;
+
+    #[contractimpl]
+    impl ReentrantHook {
+        pub fn reentered(env: Env) -> Option<bool> {
+            env.storage().instance().get(&symbol_short!("reentered"))
+        }
+    }
+
+    #[contractimpl]
+    impl TransferHook for ReentrantHook {
+        fn before_transfer(
+            env: Env,
+            token: Address,
+            from: Address,
+            to: Address,
+            _amount: i128,
+        ) -> bool {
+            let result = TokenContractClient::new(&env, &token).try_transfer(&to, &from, &1);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("reentered"), &result.is_ok());
+            true
+        }
+    }
+}
+
+use deny_list::{DenyListHook, DenyListHookClient};
+use reentrant::{ReentrantHook, ReentrantHookClient};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+#[test]
+fn deny_list_hook_vetoes_transfers() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let friend = Address::generate(&env);
+    let sanctioned = Address::generate(&env);
+    let hook_id = env.register_contract(None, DenyListHook);
+    DenyListHookClient::new(&env, &hook_id).deny(&sanctioned);
+
+    client.set_transfer_hook(&Some(hook_id.clone()));
+    assert_eq!(client.transfer_hook(), Some(hook_id.clone()));
+    let captured = assert_standard_event(&env, topics::TRANSFER_HOOK_CHANGED, |_| true);
+    let event: TransferHookChangedEvent = payload(&captured);
+    assert_eq!((event.hook, event.installed), (hook_id.clone(), true));
+
+    client.transfer(&holder, &friend, &1_000);
+    let result = client.try_transfer(&holder, &sanctioned, &1_000);
+    assert_eq!(result, Err(Ok(TokenError::TransferVetoed)));
+    client.approve(&holder, &friend, &500);
+    let result = client.try_transfer_from(&friend, &holder, &sanctioned, &500);
+    assert_eq!(result, Err(Ok(TokenError::TransferVetoed)));
+    assert_eq!(client.balance(&sanctioned), 0);
+    assert_eq!(client.balance(&holder), 9_000);
+
+    // Mints are not hooked
+    client.mint(&sanctioned, &100);
+    let result = client.try_transfer(&sanctioned, &friend, &100);
+    assert_eq!(result, Err(Ok(TokenError::TransferVetoed)));
+
+    client.set_transfer_hook(&None);
+    let captured = assert_standard_event(&env, topics::TRANSFER_HOOK_CHANGED, |_| true);
+    let event: TransferHookChangedEvent = payload(&captured);
+    assert_eq!((event.hook, event.installed), (hook_id, false));
+    client.transfer(&sanctioned, &friend, &100);
+}
+
+#[test]
+fn failing_hook_vetoes_instead_of_aborting() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    // Nothing is deployed at the hook address, so every call to it fails
+    client.set_transfer_hook(&Some(Address::generate(&env)));
+
+    let result = client.try_transfer(&holder, &Address::generate(&env), &1);
+    assert_eq!(result, Err(Ok(TokenError::TransferVetoed)));
+}
+
+#[test]
+fn hook_cannot_reenter_the_token() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let friend = Address::generate(&env);
+    client.transfer(&holder, &friend, &1_000);
+    let hook_id = env.register_contract(None, ReentrantHook);
+    client.set_transfer_hook(&Some(hook_id.clone()));
+
+    // The hook's own transfer is refused; the one it approved goes ahead
+    client.transfer(&holder, &friend, &100);
+    assert_eq!(
+        ReentrantHookClient::new(&env, &hook_id).reentered(),
+        Some(false)
+    );
+    assert_eq!(client.balance(&holder), 8_900);
+    assert_eq!(client.balance(&friend), 1_100);
+}
@@ -0,0 +1,80 @@
+use shared::events::{topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, String, Symbol, TryFromVal,
+};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, "ipfs://stellara"),
+        },
+        &false,
+    );
+    (client, admin)
+}
+
+#[test]
+fn metadata_getters() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    assert_eq!(client.name(), String::from_str(&env, "Stellara"));
+    assert_eq!(client.symbol(), String::from_str(&env, "STLR"));
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.uri(), String::from_str(&env, "ipfs://stellara"));
+}
+
+#[test]
+fn update_metadata_emits_standard_event() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let mut updated = client.metadata();
+    updated.uri = String::from_str(&env, "ipfs://stellara-v2");
+    client.update_metadata(&updated);
+    assert_eq!(client.metadata(), updated);
+
+    let prefix = Symbol::new(&env, EVENT_PREFIX);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&env, &data).unwrap())
+        .unwrap();
+    assert_eq!(event.topic, topics::METADATA_UPDATED);
+    assert_eq!(event.user_address, admin);
+    let uri = event
+        .metadata
+        .get(shared::events::metadata_keys::URI_KEY)
+        .unwrap();
+    assert_eq!(String::try_from_val(&env, &uri).unwrap(), updated.uri);
+}
+
+#[test]
+fn update_metadata_rejects_invalid_values() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    let mut updated = client.metadata();
+    updated.decimals = 19;
+    let result = client.try_update_metadata(&updated);
+    assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
+
+    let mut updated = client.metadata();
+    updated.symbol = String::from_str(&env, "");
+    let result = client.try_update_metadata(&updated);
+    assert_eq!(result, Err(Ok(TokenError::InvalidMetadata)));
+}
@@ -0,0 +1,95 @@
+use shared::events::{metadata_keys, topics, AuthorizationChangedEvent};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String, Symbol};
+use test_utils::{assert_standard_event, payload};
+use token::{OperatorApproval, TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+#[test]
+fn operator_moves_funds_without_allowance() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let vault = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let result = client.try_transfer_from(&vault, &holder, &other, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+
+    client.set_operator(&holder, &vault, &true);
+    let captured = assert_standard_event(&env, topics::AUTHORIZATION_CHANGED, |_| true);
+    let event: AuthorizationChangedEvent = payload(&captured);
+    assert_eq!(
+        (event.account, event.changed_by),
+        (vault.clone(), holder.clone())
+    );
+    assert!(event.authorized);
+    let reason: Option<Symbol> = captured.metadata(&env, metadata_keys::REASON_KEY);
+    assert_eq!(reason, Some(symbol_short!("operator")));
+
+    client.transfer_from(&vault, &holder, &other, &4_000);
+    client.transfer_from(&vault, &holder, &vault, &5_000);
+    assert_eq!(client.balance(&holder), 1_000);
+    assert_eq!(client.allowance(&holder, &vault), 0);
+
+    client.set_operator(&holder, &vault, &false);
+    assert_eq!(client.operator(&holder, &vault), None);
+    let captured = assert_standard_event(&env, topics::AUTHORIZATION_CHANGED, |_| true);
+    let event: AuthorizationChangedEvent = payload(&captured);
+    assert!(!event.authorized);
+    let result = client.try_transfer_from(&vault, &holder, &other, &1);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+}
+
+#[test]
+fn operator_cap_bounds_each_transfer() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let router = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let result = client.try_set_operator_cap(&holder, &router, &Some(500));
+    assert_eq!(result, Err(Ok(TokenError::NotOperator)));
+
+    client.set_operator(&holder, &router, &true);
+    client.set_operator_cap(&holder, &router, &Some(500));
+    assert_eq!(
+        client.operator(&holder, &router),
+        Some(OperatorApproval { cap: Some(500) })
+    );
+    let result = client.try_transfer_from(&router, &holder, &other, &501);
+    assert_eq!(result, Err(Ok(TokenError::OperatorCapExceeded)));
+    client.transfer_from(&router, &holder, &other, &500);
+    client.transfer_from(&router, &holder, &other, &500);
+    assert_eq!(client.balance(&other), 1_000);
+
+    // Re-approving keeps the cap
+    client.set_operator(&holder, &router, &true);
+    assert_eq!(
+        client.operator(&holder, &router),
+        Some(OperatorApproval { cap: Some(500) })
+    );
+    client.set_operator_cap(&holder, &router, &None);
+    client.transfer_from(&router, &holder, &other, &5_000);
+    assert_eq!(client.balance(&holder), 4_000);
+
+    let result = client.try_set_operator_cap(&holder, &router, &Some(0));
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+}
@@ -0,0 +1,197 @@
+use ed25519_dalek::{Signer, SigningKey};
+use shared::events::{metadata_keys, topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, String, Symbol, TryFromVal,
+};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata};
+
+struct Setup {
+    env: Env,
+    client: TokenContractClient<'static>,
+    owner: Address,
+    spender: Address,
+    key: SigningKey,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(&env, "Stellara"),
+            symbol: String::from_str(&env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(&env, ""),
+        },
+        &false,
+    );
+    client.mint(&owner, &1_000);
+
+    let key = SigningKey::from_bytes(&[7; 32]);
+    client.set_permit_key(
+        &owner,
+        &BytesN::from_array(&env, &key.verifying_key().to_bytes()),
+    );
+
+    Setup {
+        env,
+        client,
+        owner,
+        spender,
+        key,
+    }
+}
+
+fn sign(s: &Setup, amount: i128, deadline: u64) -> BytesN<64> {
+    let digest = s
+        .client
+        .permit_digest(&s.owner, &s.spender, &amount, &deadline);
+    let signature = s.key.sign(&digest.to_array());
+    BytesN::from_array(&s.env, &signature.to_bytes())
+}
+
+#[test]
+fn approve_and_transfer_from() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+
+    s.client.approve(&s.owner, &s.spender, &300);
+    assert_eq!(s.client.allowance(&s.owner, &s.spender), 300);
+
+    s.client
+        .transfer_from(&s.spender, &s.owner, &recipient, &200);
+    assert_eq!(s.client.balance(&recipient), 200);
+    assert_eq!(s.client.allowance(&s.owner, &s.spender), 100);
+
+    let result = s
+        .client
+        .try_transfer_from(&s.spender, &s.owner, &recipient, &101);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+}
+
+#[test]
+fn permit_grants_allowance_and_consumes_nonce() {
+    let s = setup();
+    let signature = sign(&s, 500, 2_000);
+
+    s.client
+        .permit(&s.owner, &s.spender, &500, &2_000, &signature);
+    assert_eq!(s.client.allowance(&s.owner, &s.spender), 500);
+    assert_eq!(s.client.nonce(&s.owner), 1);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let event = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .last()
+        .unwrap();
+    assert_eq!(event.topic, topics::APPROVAL);
+    let via_permit = event
+        .metadata
+        .get(Symbol::new(&s.env, metadata_keys::VIA_PERMIT_KEY))
+        .unwrap();
+    assert!(bool::try_from_val(&s.env, &via_permit).unwrap());
+}
+
+#[test]
+fn permit_rejects_expired_deadline() {
+    let s = setup();
+    let signature = sign(&s, 500, 999);
+    let result = s
+        .client
+        .try_permit(&s.owner, &s.spender, &500, &999, &signature);
+    assert_eq!(result, Err(Ok(TokenError::PermitExpired)));
+    assert_eq!(s.client.nonce(&s.owner), 0);
+}
+
+#[test]
+fn permit_requires_registered_key() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let signature = sign(&s, 500, 2_000);
+    let result = s
+        .client
+        .try_permit(&stranger, &s.spender, &500, &2_000, &signature);
+    assert_eq!(result, Err(Ok(TokenError::PermitKeyNotSet)));
+}
+
+/// Set in the child process `permit_is_bound_to_the_signing_token` runs,
+/// naming the token the permit is submitted to
+const PERMIT_TARGET: &str = "STELLARA_PERMIT_TARGET";
+
+/// Printed by the child once setup is done, so a child that failed before
+/// reaching `permit` is not mistaken for a rejected permit
+const PERMIT_SUBMITTED: &str = "submitting permit";
+
+/// A signature that fails to verify traps the host, and a trap inside a
+/// natively registered contract aborts the whole test process. So the
+/// permit is submitted from a child process running this same test, once
+/// to the token it was signed for and once to another token.
+#[test]
+fn permit_is_bound_to_the_signing_token() {
+    let s = setup();
+    let signature = sign(&s, 500, 2_000);
+
+    let other = TokenContractClient::new(&s.env, &s.env.register_contract(None, TokenContract));
+    other.initialize(
+        &Address::generate(&s.env),
+        &TokenMetadata {
+            name: String::from_str(&s.env, "Other"),
+            symbol: String::from_str(&s.env, "OTHR"),
+            decimals: 7,
+            uri: String::from_str(&s.env, ""),
+        },
+        &false,
+    );
+    other.set_permit_key(
+        &s.owner,
+        &BytesN::from_array(&s.env, &s.key.verifying_key().to_bytes()),
+    );
+    assert_ne!(
+        other.permit_digest(&s.owner, &s.spender, &500, &2_000),
+        s.client.permit_digest(&s.owner, &s.spender, &500, &2_000)
+    );
+
+    if let Some(target) = std::env::var_os(PERMIT_TARGET) {
+        let token = if target == "other" { &other } else { &s.client };
+        println!("{PERMIT_SUBMITTED}");
+        token.permit(&s.owner, &s.spender, &500, &2_000, &signature);
+        assert_eq!(token.allowance(&s.owner, &s.spender), 500);
+        return;
+    }
+
+    let submit_to = |target: &str| {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "permit_is_bound_to_the_signing_token",
+                "--exact",
+                "--nocapture",
+            ])
+            .env(PERMIT_TARGET, target)
+            .output()
+            .unwrap();
+        let stdout = std::string::String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains(PERMIT_SUBMITTED),
+            "child failed during setup"
+        );
+        output.status
+    };
+    assert!(submit_to("signing").success());
+    assert!(!submit_to("other").success());
+}
@@ -0,0 +1,119 @@
+use shared::events::{metadata_keys, topics, RebaseEvent, TransferEvent};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use test_utils::{assert_standard_event, payload};
+use token::{
+    TokenContract, TokenContractClient, TokenError, TokenMetadata, TransferFee, INDEX_SCALE,
+};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+#[test]
+fn balances_scale_with_the_index() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let savings = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let result = client.try_set_rebase_index(&(2 * INDEX_SCALE));
+    assert_eq!(result, Err(Ok(TokenError::RebaseDisabled)));
+    client.enable_rebasing(&savings);
+    assert_eq!(client.balance(&holder), 10_000);
+
+    // 10% interest
+    client.set_rebase_index(&(11 * INDEX_SCALE / 10));
+    let captured = assert_standard_event(&env, topics::REBASED, |_| true);
+    let event: RebaseEvent = payload(&captured);
+    assert_eq!(
+        (event.previous_index, event.index, event.updated_by),
+        (INDEX_SCALE, 11 * INDEX_SCALE / 10, savings.clone())
+    );
+    assert_eq!(client.balance(&holder), 11_000);
+    assert_eq!(client.shares_of(&holder), 10_000);
+    assert_eq!(client.total_supply(), 11_000);
+
+    // 1_100 is exactly 1_000 shares
+    client.transfer(&holder, &friend, &1_100);
+    let captured = assert_standard_event(&env, topics::TRANSFER, |_| true);
+    let event: TransferEvent = payload(&captured);
+    assert_eq!(event.amount, 1_100);
+    let shares: Option<i128> = captured.metadata(&env, metadata_keys::SHARES_KEY);
+    assert_eq!(shares, Some(1_000));
+    let index: Option<i128> = captured.metadata(&env, metadata_keys::INDEX_KEY);
+    assert_eq!(index, Some(11 * INDEX_SCALE / 10));
+    assert_eq!(client.balance(&friend), 1_100);
+    assert_eq!(client.shares_of(&friend), 1_000);
+
+    // Odd amounts round the sender's shares up
+    client.transfer(&holder, &friend, &1);
+    assert_eq!(client.shares_of(&holder), 8_999);
+    assert_eq!(client.balance(&friend), 1_101);
+
+    // Mints are in balance units and round shares down
+    client.mint(&friend, &2_200);
+    assert_eq!(client.shares_of(&friend), 3_001);
+
+    let result = client.try_set_rebase_index(&INDEX_SCALE);
+    assert_eq!(result, Err(Ok(TokenError::InvalidIndex)));
+    let result = client.try_transfer(&holder, &friend, &9_900);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    client.transfer(&holder, &friend, &client.balance(&holder));
+}
+
+#[test]
+fn overflowing_balances_are_an_error() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let whale = Address::generate(&env);
+    client.mint(&whale, &2_000_000_000_000_000);
+    client.enable_rebasing(&Address::generate(&env));
+    let index = i128::MAX / 1_000_000;
+    client.set_rebase_index(&index);
+
+    // The whale's shares are worth more than an i128 holds
+    assert_eq!(client.balance(&holder), 10_000 * index / INDEX_SCALE);
+    let result = client.try_balance(&whale);
+    assert_eq!(result, Err(Ok(TokenError::Overflow)));
+    let result = client.try_total_supply();
+    assert_eq!(result, Err(Ok(TokenError::Overflow)));
+    let result = client.try_transfer(&holder, &whale, &1);
+    assert_eq!(result, Err(Ok(TokenError::Overflow)));
+}
+
+#[test]
+fn rebasing_excludes_the_transfer_fee() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let fee = TransferFee {
+        fee_bps: 100,
+        burn_bps: 10_000,
+        treasury_bps: 0,
+        holders_bps: 0,
+        treasury: Address::generate(&env),
+    };
+    client.set_transfer_fee(&Some(fee.clone()));
+    let result = client.try_enable_rebasing(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(TokenError::RebaseConflict)));
+
+    client.set_transfer_fee(&None);
+    client.enable_rebasing(&Address::generate(&env));
+    let result = client.try_set_transfer_fee(&Some(fee));
+    assert_eq!(result, Err(Ok(TokenError::RebaseConflict)));
+}
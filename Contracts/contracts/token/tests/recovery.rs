@@ -0,0 +1,131 @@
+use shared::events::{topics, AdminChangedEvent};
+use shared::recovery::{RecoveryError, RECOVERY_REASON};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+use test_utils::{assert_standard_event, payload};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+const DAY: u64 = 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: TokenContractClient<'static>,
+    admin: Address,
+    guardians: [Address; 3],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(&env, "Stellara"),
+            symbol: String::from_str(&env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(&env, ""),
+        },
+        &false,
+    );
+    let guardians = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_recovery(
+        &vec![
+            &env,
+            guardians[0].clone(),
+            guardians[1].clone(),
+            guardians[2].clone(),
+        ],
+        &2,
+        &(30 * DAY),
+    );
+    Setup {
+        env,
+        client,
+        admin,
+        guardians,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn guardians_rotate_an_inactive_admin() {
+    let s = setup();
+    let new_admin = Address::generate(&s.env);
+    advance(&s.env, 31 * DAY);
+
+    assert!(!s.client.recover_admin(&s.guardians[0], &new_admin));
+    assert_eq!(s.client.admin(), s.admin);
+    assert!(s.client.recover_admin(&s.guardians[2], &new_admin));
+    assert_eq!(s.client.admin(), new_admin);
+
+    let captured = assert_standard_event(&s.env, topics::ADMIN_CHANGED, |_| true);
+    let event: AdminChangedEvent = payload(&captured);
+    assert_eq!(event.previous_admin, s.admin);
+    assert_eq!(event.reason, RECOVERY_REASON);
+}
+
+#[test]
+fn recovery_is_closed_while_admin_is_active() {
+    let s = setup();
+    let new_admin = Address::generate(&s.env);
+
+    advance(&s.env, 29 * DAY);
+    let result = s.client.try_recover_admin(&s.guardians[0], &new_admin);
+    assert_eq!(result, Err(Ok(RecoveryError::AdminActive)));
+
+    // A heartbeat restarts the inactivity window and drops pending approvals
+    advance(&s.env, 2 * DAY);
+    assert!(!s.client.recover_admin(&s.guardians[0], &new_admin));
+    s.client.heartbeat();
+    advance(&s.env, 29 * DAY);
+    let result = s.client.try_recover_admin(&s.guardians[1], &new_admin);
+    assert_eq!(result, Err(Ok(RecoveryError::AdminActive)));
+    assert_eq!(s.client.admin(), s.admin);
+}
+
+#[test]
+fn only_guardians_approve_once_each() {
+    let s = setup();
+    let new_admin = Address::generate(&s.env);
+    advance(&s.env, 31 * DAY);
+
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_recover_admin(&stranger, &new_admin);
+    assert_eq!(result, Err(Ok(RecoveryError::NotGuardian)));
+
+    s.client.recover_admin(&s.guardians[0], &new_admin);
+    let result = s.client.try_recover_admin(&s.guardians[0], &new_admin);
+    assert_eq!(result, Err(Ok(RecoveryError::AlreadyApproved)));
+
+    // Backing a different candidate starts a new round
+    let other = Address::generate(&s.env);
+    assert!(!s.client.recover_admin(&s.guardians[1], &other));
+    assert_eq!(s.client.admin(), s.admin);
+}
+
+#[test]
+fn recovery_config_is_validated() {
+    let s = setup();
+    let g = &s.guardians[0];
+    let result = s
+        .client
+        .try_set_recovery(&vec![&s.env, g.clone()], &2, &DAY);
+    assert_eq!(result, Err(Ok(RecoveryError::InvalidConfig)));
+    let result = s
+        .client
+        .try_set_recovery(&vec![&s.env, g.clone(), g.clone()], &1, &DAY);
+    assert_eq!(result, Err(Ok(RecoveryError::InvalidConfig)));
+    assert_eq!(s.client.recovery_config().unwrap().threshold, 2);
+}
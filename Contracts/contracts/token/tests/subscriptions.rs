@@ -0,0 +1,81 @@
+use shared::events::{metadata_keys::NOTIFY_KEY, topics};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+use test_utils::{assert_standard_event, standard_events};
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &1_000);
+    (client, holder)
+}
+
+fn notified(env: &Env) -> Option<bool> {
+    let events = standard_events(env);
+    events.last().unwrap().metadata::<bool>(env, NOTIFY_KEY)
+}
+
+#[test]
+fn events_carry_no_hint_without_subscription() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(notified(&env), None);
+}
+
+#[test]
+fn subscribed_topics_are_flagged() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let spender = Address::generate(&env);
+
+    client.subscribe(&holder, &vec![&env, topics::TRANSFER]);
+    assert_standard_event(&env, topics::SUBSCRIBED, |_| true);
+    assert_eq!(
+        client.subscription(&holder),
+        Some(vec![&env, topics::TRANSFER])
+    );
+
+    client.transfer(&holder, &Address::generate(&env), &10);
+    assert_eq!(notified(&env), Some(true));
+
+    // Not a subscribed topic
+    client.approve(&holder, &spender, &10);
+    assert_eq!(notified(&env), None);
+
+    // Events about other users are not flagged
+    client.mint(&spender, &10);
+    client.transfer(&spender, &holder, &1);
+    assert_eq!(notified(&env), None);
+}
+
+#[test]
+fn empty_subscription_covers_all_topics_until_cleared() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let spender = Address::generate(&env);
+
+    client.subscribe(&holder, &vec![&env]);
+    client.approve(&holder, &spender, &10);
+    assert_eq!(notified(&env), Some(true));
+
+    assert!(client.unsubscribe(&holder));
+    assert_standard_event(&env, topics::UNSUBSCRIBED, |_| true);
+    assert!(!client.unsubscribe(&holder));
+    client.transfer(&holder, &spender, &10);
+    assert_eq!(notified(&env), None);
+}
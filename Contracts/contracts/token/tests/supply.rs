@@ -0,0 +1,115 @@
+use shared::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+use token::{EmissionSchedule, TokenContract, TokenContractClient, TokenMetadata};
+
+const DAY: u64 = 86_400;
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 10 * DAY);
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &true,
+    );
+    (client, Address::generate(env))
+}
+
+#[test]
+fn max_supply_is_enforced() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+
+    client.set_max_supply(&1_000);
+    client.mint(&holder, &800);
+    assert_eq!(client.total_supply(), 800);
+    assert_eq!(client.remaining_mintable(), 200);
+
+    let result = client.try_mint(&holder, &201);
+    assert_eq!(result, Err(Ok(ContractError::SupplyCapExceeded)));
+
+    // The cap can be lowered but never raised
+    let result = client.try_set_max_supply(&2_000);
+    assert_eq!(result, Err(Ok(ContractError::SupplyCapExceeded)));
+    let result = client.try_set_max_supply(&799);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    client.set_max_supply(&900);
+    assert_eq!(client.remaining_mintable(), 100);
+}
+
+#[test]
+fn clawback_frees_capped_supply() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+
+    client.set_max_supply(&1_000);
+    client.mint(&holder, &1_000);
+    client.clawback(&holder, &300);
+    assert_eq!(client.total_supply(), 700);
+    assert_eq!(client.remaining_mintable(), 300);
+}
+
+#[test]
+fn emission_schedule_limits_each_epoch() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+
+    client.set_emission_schedule(&EmissionSchedule {
+        start: 11 * DAY,
+        epoch_length: DAY,
+        allowances: vec![&env, 500, 300],
+    });
+
+    // Before the schedule starts nothing is mintable
+    assert_eq!(client.remaining_mintable(), 0);
+    let result = client.try_mint(&holder, &1);
+    assert_eq!(result, Err(Ok(ContractError::EmissionLimitExceeded)));
+
+    env.ledger().with_mut(|l| l.timestamp = 11 * DAY);
+    client.mint(&holder, &400);
+    assert_eq!(client.remaining_mintable(), 100);
+    let result = client.try_mint(&holder, &101);
+    assert_eq!(result, Err(Ok(ContractError::EmissionLimitExceeded)));
+
+    // Unused allowance does not roll over
+    env.ledger().with_mut(|l| l.timestamp = 12 * DAY);
+    assert_eq!(client.remaining_mintable(), 300);
+    client.mint(&holder, &300);
+
+    env.ledger().with_mut(|l| l.timestamp = 13 * DAY);
+    assert_eq!(client.remaining_mintable(), 0);
+    assert_eq!(client.total_supply(), 700);
+}
+
+#[test]
+fn emission_schedule_must_decline_and_is_set_once() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    let result = client.try_set_emission_schedule(&EmissionSchedule {
+        start: 0,
+        epoch_length: DAY,
+        allowances: vec![&env, 100, 200],
+    });
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+
+    let schedule = EmissionSchedule {
+        start: 0,
+        epoch_length: DAY,
+        allowances: vec![&env, 200, 100],
+    };
+    client.set_emission_schedule(&schedule);
+    let result = client.try_set_emission_schedule(&schedule);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyExists)));
+}
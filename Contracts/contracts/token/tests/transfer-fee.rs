@@ -0,0 +1,177 @@
+use shared::events::{metadata_keys, topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, String, Symbol, TryFromVal,
+};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata, TransferFee};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let whale = Address::generate(env);
+    let minnow = Address::generate(env);
+    client.mint(&whale, &9_000);
+    client.mint(&minnow, &1_000);
+    (client, whale, minnow)
+}
+
+/// 10% fee: 20% burned, 30% to the treasury, 50% to holders
+fn fee(treasury: &Address) -> TransferFee {
+    TransferFee {
+        fee_bps: 1_000,
+        burn_bps: 2_000,
+        treasury_bps: 3_000,
+        holders_bps: 5_000,
+        treasury: treasury.clone(),
+    }
+}
+
+fn last_transfer_event(env: &Env) -> StandardEventV2 {
+    let prefix = Symbol::new(env, EVENT_PREFIX);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(env, &data).unwrap())
+        .filter(|event| event.topic == topics::TRANSFER)
+        .last()
+        .unwrap()
+}
+
+fn metadata_i128(env: &Env, event: &StandardEventV2, key: Symbol) -> Option<i128> {
+    event
+        .metadata
+        .get(key)
+        .map(|value| i128::try_from_val(env, &value).unwrap())
+}
+
+#[test]
+fn fee_mode_is_off_by_default() {
+    let env = Env::default();
+    let (client, whale, minnow) = setup(&env);
+
+    assert_eq!(client.transfer_fee(), None);
+    client.transfer(&whale, &minnow, &1_000);
+    assert_eq!(client.balance(&minnow), 2_000);
+    let event = last_transfer_event(&env);
+    assert_eq!(
+        metadata_i128(&env, &event, metadata_keys::RECEIVED_KEY),
+        None
+    );
+}
+
+#[test]
+fn fee_is_split_between_burn_treasury_and_holders() {
+    let env = Env::default();
+    let (client, whale, minnow) = setup(&env);
+    let treasury = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_transfer_fee(&Some(fee(&treasury)));
+
+    client.transfer(&whale, &recipient, &1_000);
+    let event = last_transfer_event(&env);
+    assert_eq!(
+        metadata_i128(&env, &event, metadata_keys::AMOUNT_KEY),
+        Some(1_000)
+    );
+    assert_eq!(
+        metadata_i128(&env, &event, metadata_keys::RECEIVED_KEY),
+        Some(900)
+    );
+    assert_eq!(
+        metadata_i128(&env, &event, metadata_keys::FEE_KEY),
+        Some(100)
+    );
+
+    assert_eq!(client.total_supply(), 9_980);
+    assert_eq!(client.balance(&treasury), 30);
+    // 50 is reflected over the 9 930 still held: 8 000 / 9 930 of it to the whale
+    assert_eq!(client.reflection_balance_of(&whale), 40);
+    assert_eq!(client.reflection_balance_of(&minnow), 5);
+    assert_eq!(client.reflection_balance_of(&recipient), 4);
+    assert_eq!(client.balance(&whale), 8_040);
+    assert_eq!(client.balance(&recipient), 904);
+    let held = client.balance(&whale)
+        + client.balance(&minnow)
+        + client.balance(&recipient)
+        + client.balance(&treasury);
+    assert!(held <= client.total_supply());
+}
+
+#[test]
+fn reflections_reach_holders_in_full() {
+    let env = Env::default();
+    let (client, whale, minnow) = setup(&env);
+    client.mint(&whale, &8_991_000);
+    client.mint(&minnow, &999_000);
+    let treasury = Address::generate(&env);
+    client.set_transfer_fee(&Some(fee(&treasury)));
+
+    // The 50 000 reflected is spread over the 9 930 000 held, not over the
+    // supply that still counts it, so only rounding dust is left unpaid
+    client.transfer(&whale, &minnow, &1_000_000);
+    assert_eq!(client.total_supply(), 9_980_000);
+    assert_eq!(client.reflection_balance_of(&whale), 40_281);
+    assert_eq!(client.reflection_balance_of(&minnow), 9_566);
+    assert_eq!(client.reflection_balance_of(&treasury), 151);
+    let held = client.balance(&whale) + client.balance(&minnow) + client.balance(&treasury);
+    assert_eq!(client.total_supply() - held, 2);
+
+    // Crediting reflections takes them out of the pool, so later fees
+    // are spread over the grown balances
+    client.transfer(&minnow, &whale, &1_000_000);
+    let held = client.balance(&whale) + client.balance(&minnow) + client.balance(&treasury);
+    assert_eq!(client.total_supply() - held, 3);
+}
+
+#[test]
+fn reflections_are_spendable_and_kept_on_record() {
+    let env = Env::default();
+    let (client, whale, minnow) = setup(&env);
+    let treasury = Address::generate(&env);
+    client.set_transfer_fee(&Some(fee(&treasury)));
+    client.transfer(&whale, &treasury, &1_000);
+
+    let balance = client.balance(&minnow);
+    assert_eq!(balance, 1_005);
+    client.transfer(&minnow, &whale, &balance);
+    assert_eq!(client.balance(&minnow), 0);
+    assert_eq!(client.reflection_balance_of(&minnow), 5);
+
+    // Turning the fee off stops new reflections but keeps the old ones
+    client.set_transfer_fee(&None);
+    client.transfer(&whale, &minnow, &100);
+    assert_eq!(client.balance(&minnow), 100);
+    assert_eq!(client.reflection_balance_of(&minnow), 5);
+}
+
+#[test]
+fn fee_config_is_validated() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let treasury = Address::generate(&env);
+
+    let mut too_high = fee(&treasury);
+    too_high.fee_bps = 1_001;
+    let result = client.try_set_transfer_fee(&Some(too_high));
+    assert_eq!(result, Err(Ok(TokenError::InvalidFee)));
+
+    let mut bad_split = fee(&treasury);
+    bad_split.holders_bps = 4_000;
+    let result = client.try_set_transfer_fee(&Some(bad_split));
+    assert_eq!(result, Err(Ok(TokenError::InvalidFee)));
+}
@@ -0,0 +1,136 @@
+use shared::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, Logs},
+    Address, Env, String,
+};
+use token::{TokenContract, TokenContractClient, TokenError, TokenMetadata, TransferLimits};
+
+fn setup(env: &Env) -> (TokenContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(env, "Stellara"),
+            symbol: String::from_str(env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(env, ""),
+        },
+        &false,
+    );
+    let holder = Address::generate(env);
+    client.mint(&holder, &10_000);
+    (client, holder)
+}
+
+fn limits(max_transfer: Option<i128>, max_balance: Option<i128>, cooldown: u64) -> TransferLimits {
+    TransferLimits {
+        max_transfer,
+        max_balance,
+        cooldown,
+    }
+}
+
+#[test]
+fn limits_are_off_by_default() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(client.transfer_limits(), None);
+    client.transfer(&holder, &other, &10_000);
+    assert_eq!(client.balance(&other), 10_000);
+}
+
+#[test]
+fn transfer_and_balance_caps_use_shared_codes() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let other = Address::generate(&env);
+    client.set_transfer_limits(&Some(limits(Some(1_000), Some(1_500), 0)));
+
+    let result = client.try_transfer(&holder, &other, &1_001);
+    assert_eq!(result, Err(Ok(TokenError::TransferLimitExceeded)));
+    // The diagnostic survives the rollback of the rejected transfer
+    let hit = env.logs().all().last().unwrap().clone();
+    assert!(hit.contains("LIMIT_HIT"), "{hit}");
+    assert!(hit.contains(&format!(
+        "{}, 1000]",
+        TokenError::TransferLimitExceeded as u32
+    )));
+    assert_eq!(
+        TokenError::TransferLimitExceeded as u32,
+        ContractError::TransferLimitExceeded as u32
+    );
+
+    client.transfer(&holder, &other, &1_000);
+    let result = client.try_transfer(&holder, &other, &501);
+    assert_eq!(result, Err(Ok(TokenError::BalanceLimitExceeded)));
+    assert_eq!(
+        TokenError::BalanceLimitExceeded as u32,
+        ContractError::BalanceLimitExceeded as u32
+    );
+    assert_eq!(client.balance(&holder), 9_000);
+
+    // Clearing the limits lifts them
+    client.set_transfer_limits(&None);
+    client.transfer(&holder, &other, &5_000);
+}
+
+#[test]
+fn cooldown_spaces_out_transfers() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let other = Address::generate(&env);
+    client.set_transfer_limits(&Some(limits(None, None, 60)));
+
+    client.transfer(&holder, &other, &100);
+    let result = client.try_transfer(&holder, &other, &100);
+    assert_eq!(result, Err(Ok(TokenError::TransferCooldown)));
+    assert_eq!(
+        TokenError::TransferCooldown as u32,
+        ContractError::TransferCooldown as u32
+    );
+    // The recipient has no cooldown of its own
+    client.transfer(&other, &holder, &50);
+
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    client.transfer(&holder, &other, &100);
+}
+
+#[test]
+fn exempt_addresses_skip_their_side() {
+    let env = Env::default();
+    let (client, holder) = setup(&env);
+    let pool = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.set_transfer_limits(&Some(limits(Some(1_000), Some(1_000), 60)));
+    client.set_limit_exempt(&pool, &true);
+    client.set_limit_exempt(&treasury, &true);
+    assert!(client.is_limit_exempt(&pool));
+
+    // The pool may hold more than the cap...
+    client.mint(&pool, &5_000);
+    client.transfer(&holder, &pool, &1_000);
+    assert_eq!(client.balance(&pool), 6_000);
+    // ...and send large amounts back to back, but recipients stay capped
+    client.transfer(&pool, &treasury, &1_200);
+    client.transfer(&pool, &user, &500);
+    let result = client.try_transfer(&pool, &user, &501);
+    assert_eq!(result, Err(Ok(TokenError::BalanceLimitExceeded)));
+
+    client.set_limit_exempt(&pool, &false);
+    let result = client.try_transfer(&treasury, &pool, &1_001);
+    assert_eq!(result, Err(Ok(TokenError::BalanceLimitExceeded)));
+}
+
+#[test]
+fn limits_must_be_positive() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let result = client.try_set_transfer_limits(&Some(limits(Some(0), None, 0)));
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+}
@@ -140,13 +140,13 @@ impl GasBenchmark {
 
         // Benchmark pause
         env.budget().reset_default();
-        let _ = client.pause(&admin);
+        client.pause(&admin);
         let pause_cpu = env.budget().cpu_instruction_cost();
         let pause_mem = env.budget().memory_bytes_cost();
 
         // Benchmark unpause
         env.budget().reset_default();
-        let _ = client.unpause(&admin);
+        client.unpause(&admin);
         let unpause_cpu = env.budget().cpu_instruction_cost();
         let unpause_mem = env.budget().memory_bytes_cost();
 
@@ -1,18 +1,35 @@
 #![no_std]
 
 use shared::acl::ACL;
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::circuit_breaker::{BreakerConfig, BreakerState, CircuitBreaker, PriceFeed};
+use shared::events::{EventEmitter, ReferrerBoundEvent};
 use shared::fees::FeeManager;
-use shared::governance::{GovernanceManager, GovernanceRole, UpgradeProposal};
+use shared::governance::{GovernanceManager, GovernanceRole, ProposalSummary, UpgradeProposal};
+use shared::pagination::Pagination;
+use shared::positions::{Position, Positions};
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
 
+mod referral;
+mod volume;
+
+pub use referral::RebateTier;
+pub use volume::{FeeTier, TraderStats};
+
 /// Version of this contract implementation
 const CONTRACT_VERSION: u32 = 1;
 
-/// Maximum number of recent trades to keep in hot storage
-const MAX_RECENT_TRADES: u32 = 100;
+/// Prices are quoted per unit of the traded amount, so PnL needs no
+/// rescaling
+const POSITION_PRICE_SCALE: i128 = 1;
+
 /// Hard cap on the number of orders that can be executed atomically in one batch
 const MAX_BATCH_SIZE: u32 = 25;
 
+/// Admin permissions added after the first release, which `init` did not
+/// grant to contracts it set up
+const ADDED_ADMIN_PERMISSIONS: [&str; 4] = ["referral", "fee_tiers", "breaker", "price"];
+
 /// Storage keys as constants to avoid repeated symbol creation
 mod storage_keys {
     use soroban_sdk::{symbol_short, Symbol};
@@ -25,6 +42,8 @@ mod storage_keys {
     pub const TRADE_COUNT: Symbol = symbol_short!("t_cnt");
     pub const RL_CFG: Symbol = symbol_short!("rl_cfg");
     pub const PREM: Symbol = symbol_short!("prem");
+    pub const REF_TIERS: Symbol = symbol_short!("ref_tier");
+    pub const FEE_TIERS: Symbol = symbol_short!("fee_tier");
 }
 
 /// Trading contract with upgradeability and governance
@@ -99,6 +118,12 @@ pub enum TradeError {
     GlobalRateLimitExceeded = 3007,
     InvalidRateLimitConfig = 3008,
     BatchTooLarge = 3009,
+    ReferrerAlreadySet = 3010,
+    InvalidReferrer = 3011,
+    InvalidRebateTiers = 3012,
+    CircuitBreakerTripped = 3013,
+    InvalidBreakerConfig = 3014,
+    InvalidFeeTiers = 3015,
 }
 
 impl From<TradeError> for soroban_sdk::Error {
@@ -127,6 +152,29 @@ fn require_initialized(env: &Env) -> Result<(), TradeError> {
     }
 }
 
+fn grant_added_permissions(env: &Env) {
+    let admin_role = Symbol::new(env, "admin");
+    for permission in ADDED_ADMIN_PERMISSIONS {
+        ACL::assign_permission(env, &admin_role, &Symbol::new(env, permission));
+    }
+}
+
+/// Governance admins; `ACL::require_permission` would abort rather than
+/// return an error
+fn require_admin(env: &Env, admin: &Address) -> Result<(), TradeError> {
+    admin.require_auth();
+    require_initialized(env)?;
+    let roles: soroban_sdk::Map<Address, GovernanceRole> = env
+        .storage()
+        .persistent()
+        .get(&storage_keys::ROLES)
+        .ok_or(TradeError::NotInitialized)?;
+    if roles.get(admin.clone()) != Some(GovernanceRole::Admin) {
+        return Err(TradeError::Unauthorized);
+    }
+    Ok(())
+}
+
 fn read_rate_limit_config(env: &Env) -> RateLimitConfig {
     if let Some(cfg) = env.storage().persistent().get(&storage_keys::RL_CFG) {
         return cfg;
@@ -179,7 +227,7 @@ fn check_and_consume_trade_rate_limit(env: &Env, trader: &Address) -> Result<(),
     #[cfg(test)]
     {
         let _ = (env, trader); // Suppress unused warnings in test mode
-        return Ok(());
+        Ok(())
     }
 
     #[cfg(not(test))]
@@ -251,6 +299,14 @@ fn ensure_tradeable(
     Ok(storage)
 }
 
+/// Refuse trades on `pair` while its price circuit breaker is tripped
+fn ensure_pair_open(env: &Env, pair: &Symbol) -> Result<(), TradeError> {
+    if !CircuitBreaker::check(env, &PriceFeed::Pair(pair.clone())) {
+        return Err(TradeError::CircuitBreakerTripped);
+    }
+    Ok(())
+}
+
 fn execute_trade_batch(
     env: &Env,
     trader: &Address,
@@ -272,10 +328,25 @@ fn execute_trade_batch(
     }
 
     let storage = ensure_tradeable(env, trader)?;
+    for (pair, _, _, _) in orders.iter() {
+        ensure_pair_open(env, &pair)?;
+    }
 
-    let total_fees = fee_per_trade * (orders.len() as i128);
-    FeeManager::collect_fee(env, fee_token, trader, fee_recipient, total_fees)
-        .map_err(|_| TradeError::InsufficientBalance)?;
+    let discount_bps = volume::discount_bps_for(env, trader);
+    let total_fees = FeeManager::discounted(fee_per_trade * (orders.len() as i128), discount_bps);
+    let mut batch_volume: i128 = 0;
+    for (_, amount, _, _) in orders.iter() {
+        batch_volume += amount;
+    }
+    referral::collect_trade_fee(
+        env,
+        trader,
+        fee_token,
+        fee_recipient,
+        total_fees,
+        batch_volume,
+    )?;
+    volume::record_volume(env, trader, batch_volume, orders.len() as u64);
 
     let current_timestamp = env.ledger().timestamp();
     let mut trade_id: u64 = storage.get(&storage_keys::TRADE_COUNT).unwrap_or(0);
@@ -301,6 +372,14 @@ fn execute_trade_batch(
 
         let trade_key = (symbol_short!("trade"), trade_id);
         storage.set(&trade_key, &trade);
+        Positions::record(
+            env,
+            trader,
+            &pair,
+            signed_amount,
+            price,
+            POSITION_PRICE_SCALE,
+        );
 
         stats.total_trades += 1;
         stats.total_volume += amount;
@@ -366,6 +445,7 @@ impl UpgradeableTradingContract {
         ACL::assign_permission(&env, &admin_role, &Symbol::new(&env, "pause"));
         ACL::assign_permission(&env, &admin_role, &Symbol::new(&env, "unpause"));
         ACL::assign_permission(&env, &admin_role, &Symbol::new(&env, "manage_acl"));
+        grant_added_permissions(&env);
 
         let stats = TradeStats {
             total_trades: 0,
@@ -394,6 +474,7 @@ impl UpgradeableTradingContract {
     }
 
     /// Execute a trade with fee collection - OPTIMIZED
+    #[allow(clippy::too_many_arguments)]
     pub fn trade(
         env: Env,
         trader: Address,
@@ -413,14 +494,25 @@ impl UpgradeableTradingContract {
         }
 
         let storage = ensure_tradeable(&env, &trader)?;
+        ensure_pair_open(&env, &pair)?;
 
         // OPTIMIZATION 3: Cache timestamp and compute signed_amount once
         let current_timestamp = env.ledger().timestamp();
         let signed_amount = if is_buy { amount } else { -amount };
 
-        // OPTIMIZATION 4: Collect fee before storage writes (fail fast)
-        FeeManager::collect_fee(&env, &fee_token, &trader, &fee_recipient, fee_amount)
-            .map_err(|_| TradeError::InsufficientBalance)?;
+        // OPTIMIZATION 4: Collect fee before storage writes (fail fast),
+        // discounted by the trader's volume tier before this trade
+        let fee_amount =
+            FeeManager::discounted(fee_amount, volume::discount_bps_for(&env, &trader));
+        referral::collect_trade_fee(
+            &env,
+            &trader,
+            &fee_token,
+            &fee_recipient,
+            fee_amount,
+            amount,
+        )?;
+        volume::record_volume(&env, &trader, amount, 1);
 
         // OPTIMIZATION 5: Batch storage reads - get both values at once
         let trade_id: u64 = storage.get(&storage_keys::TRADE_COUNT).unwrap_or(0) + 1;
@@ -446,6 +538,14 @@ impl UpgradeableTradingContract {
         let trade_key = (symbol_short!("trade"), trade_id);
         storage.set(&trade_key, &trade);
         storage.set(&storage_keys::TRADE_COUNT, &trade_id);
+        Positions::record(
+            &env,
+            &trader,
+            &pair,
+            signed_amount,
+            price,
+            POSITION_PRICE_SCALE,
+        );
         storage.set(&storage_keys::STATS, &stats);
 
         // OPTIMIZATION 8: Emit events after all storage writes (events are cheaper)
@@ -477,6 +577,75 @@ impl UpgradeableTradingContract {
         Ok(trade_id)
     }
 
+    /// Bind a referrer to the caller. A referrer can only be set once.
+    pub fn bind_referrer(env: Env, user: Address, referrer: Address) -> Result<(), TradeError> {
+        user.require_auth();
+        require_initialized(&env)?;
+        referral::bind_referrer(&env, &user, &referrer)?;
+
+        EventEmitter::referrer_bound(
+            &env,
+            ReferrerBoundEvent {
+                user,
+                referrer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the referrer bound to `user`, if any
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        referral::get_referrer(&env, &user)
+    }
+
+    /// Replace the rebate tiers (ACL protected). Tiers must be ordered by
+    /// strictly increasing `min_volume`.
+    pub fn set_rebate_tiers(
+        env: Env,
+        admin: Address,
+        tiers: Vec<RebateTier>,
+    ) -> Result<(), TradeError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &admin, &Symbol::new(&env, "referral"));
+        referral::write_tiers(&env, &tiers)
+    }
+
+    /// Get the configured rebate tiers
+    pub fn get_rebate_tiers(env: Env) -> Vec<RebateTier> {
+        referral::read_tiers(&env)
+    }
+
+    /// Volume referred by `referrer` in the current 30-day window and the
+    /// rebate rate (bps) it currently earns
+    pub fn get_referrer_stats(env: Env, referrer: Address) -> (i128, u32) {
+        (
+            referral::referred_volume(&env, &referrer),
+            referral::rebate_bps_for(&env, &referrer),
+        )
+    }
+
+    /// Replace the volume fee tiers (ACL protected). Tiers must be ordered
+    /// by strictly increasing `min_volume`.
+    pub fn set_fee_tiers(env: Env, admin: Address, tiers: Vec<FeeTier>) -> Result<(), TradeError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &admin, &Symbol::new(&env, "fee_tiers"));
+        volume::write_tiers(&env, &tiers)
+    }
+
+    /// Get the configured volume fee tiers
+    pub fn get_fee_tiers(env: Env) -> Vec<FeeTier> {
+        volume::read_tiers(&env)
+    }
+
+    /// Rolling 30-day volume, lifetime totals and current fee tier of
+    /// `trader`
+    pub fn trader_stats(env: Env, trader: Address) -> TraderStats {
+        volume::trader_stats(&env, &trader)
+    }
+
     /// Set rate-limit config (ACL protected)
     pub fn set_rate_limit_config(
         env: Env,
@@ -530,6 +699,66 @@ impl UpgradeableTradingContract {
         Ok(())
     }
 
+    /// Report the oracle price of `pair` (ACL protected). Returns `false`
+    /// if trading on the pair is halted by the circuit breaker afterwards.
+    pub fn report_price(
+        env: Env,
+        reporter: Address,
+        pair: Symbol,
+        price: i128,
+    ) -> Result<bool, TradeError> {
+        reporter.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &reporter, &Symbol::new(&env, "price"));
+        if price <= 0 {
+            return Err(TradeError::InvalidAmount);
+        }
+        Ok(CircuitBreaker::record(&env, &PriceFeed::Pair(pair), price))
+    }
+
+    /// Install or, with `None`, disable the price circuit breaker (ACL
+    /// protected)
+    pub fn set_circuit_breaker(
+        env: Env,
+        admin: Address,
+        config: Option<BreakerConfig>,
+    ) -> Result<(), TradeError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &admin, &Symbol::new(&env, "breaker"));
+        if config.as_ref().is_some_and(|c| !c.is_valid()) {
+            return Err(TradeError::InvalidBreakerConfig);
+        }
+        CircuitBreaker::set_config(&env, config);
+        Ok(())
+    }
+
+    /// Reopen trading on `pair` before its cooldown ends, with `price` as
+    /// the new reference (ACL protected)
+    pub fn reset_circuit_breaker(
+        env: Env,
+        admin: Address,
+        pair: Symbol,
+        price: i128,
+    ) -> Result<(), TradeError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &admin, &Symbol::new(&env, "breaker"));
+        if price <= 0 {
+            return Err(TradeError::InvalidAmount);
+        }
+        CircuitBreaker::reset(&env, &PriceFeed::Pair(pair), price);
+        Ok(())
+    }
+
+    pub fn get_circuit_breaker(env: Env) -> Option<BreakerConfig> {
+        CircuitBreaker::config(&env)
+    }
+
+    pub fn get_breaker_state(env: Env, pair: Symbol) -> Option<BreakerState> {
+        CircuitBreaker::state(&env, &PriceFeed::Pair(pair))
+    }
+
     /// Read current rate-limit config
     pub fn get_rate_limit_config(env: Env) -> Result<RateLimitConfig, TradeError> {
         require_initialized(&env)?;
@@ -555,14 +784,21 @@ impl UpgradeableTradingContract {
             })
     }
 
+    /// Net position, average entry price and realized PnL of `user` in
+    /// `pair`
+    pub fn position_of(env: Env, user: Address, pair: Symbol) -> Position {
+        Positions::position_of(&env, &user, &pair)
+    }
+
     /// Get a specific trade by ID
     pub fn get_trade(env: Env, trade_id: u64) -> Option<Trade> {
         let trade_key = (symbol_short!("trade"), trade_id);
         env.storage().persistent().get(&trade_key)
     }
 
-    /// Get recent trades
-    pub fn get_recent_trades(env: Env, count: u32) -> Vec<Trade> {
+    /// One page of trades, most recent first, and the cursor of the next.
+    /// Cursor `n` is the `n`th most recent trade.
+    pub fn get_recent_trades(env: Env, page: Pagination) -> (Vec<Trade>, Option<u32>) {
         let mut trades = Vec::new(&env);
         let trade_count: u64 = env
             .storage()
@@ -570,21 +806,15 @@ impl UpgradeableTradingContract {
             .get(&storage_keys::TRADE_COUNT)
             .unwrap_or(0);
 
-        let limit = count.min(MAX_RECENT_TRADES).min(trade_count as u32);
-        let start_id = if trade_count > limit as u64 {
-            trade_count - limit as u64 + 1
-        } else {
-            1
-        };
-
-        for id in start_id..=trade_count {
-            let trade_key = (symbol_short!("trade"), id);
+        let (start, end, next) = page.bounds(trade_count.min(u32::MAX as u64) as u32);
+        for offset in start..end {
+            let trade_key = (symbol_short!("trade"), trade_count - offset as u64);
             if let Some(trade) = env.storage().persistent().get(&trade_key) {
                 trades.push_back(trade);
             }
         }
 
-        trades
+        (trades, next)
     }
 
     /// Execute multiple trades atomically with a single fee transfer.
@@ -697,6 +927,17 @@ impl UpgradeableTradingContract {
         Ok(())
     }
 
+    /// Grant the admin role the permissions added since the first release.
+    /// Contracts initialized by that release need this once after being
+    /// upgraded; running it again changes nothing.
+    pub fn migrate_permissions(env: Env, admin: Address) -> Result<(), TradeError> {
+        admin.require_auth();
+        require_initialized(&env)?;
+        ACL::require_permission(&env, &admin, &Symbol::new(&env, "manage_acl"));
+        grant_added_permissions(&env);
+        Ok(())
+    }
+
     pub fn set_role_parent(
         env: Env,
         admin: Address,
@@ -725,6 +966,45 @@ impl UpgradeableTradingContract {
         Ok(ACL::has_permission(&env, &user, &permission))
     }
 
+    /// Propose `new_admin` to take over the admin role; takes effect once
+    /// they accept
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), TradeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending proposal, taking the governance and ACL admin roles
+    /// from the address that proposed it
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let previous = PendingAdmin::get(&env).map(|pending| pending.current_admin);
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let admin_role = Symbol::new(&env, "admin");
+        let mut roles: soroban_sdk::Map<Address, GovernanceRole> = env
+            .storage()
+            .persistent()
+            .get(&storage_keys::ROLES)
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        if let Some(previous) = previous {
+            roles.remove(previous.clone());
+            ACL::revoke_role(&env, &previous, &admin_role);
+        }
+        roles.set(admin.clone(), GovernanceRole::Admin);
+        ACL::assign_role(&env, &admin, &admin_role);
+        env.storage().persistent().set(&storage_keys::ROLES, &roles);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), TradeError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
     /// Propose an upgrade via governance
     pub fn propose_upgrade(
         env: Env,
@@ -781,6 +1061,22 @@ impl UpgradeableTradingContract {
             .map_err(|_| TradeError::Unauthorized)
     }
 
+    /// Execute several approved upgrade proposals in one transaction. Any
+    /// proposal that cannot execute reverts the batch unless `best_effort`
+    /// is set, in which case it is skipped. Returns the ids executed.
+    pub fn execute_upgrade_batch(
+        env: Env,
+        proposal_ids: Vec<u64>,
+        executor: Address,
+        best_effort: bool,
+    ) -> Result<Vec<u64>, TradeError> {
+        executor.require_auth();
+        require_initialized(&env)?;
+
+        GovernanceManager::execute_batch(&env, proposal_ids, executor, best_effort)
+            .map_err(|_| TradeError::Unauthorized)
+    }
+
     /// Get upgrade proposal details
     pub fn get_upgrade_proposal(env: Env, proposal_id: u64) -> Result<UpgradeProposal, TradeError> {
         require_initialized(&env)?;
@@ -804,6 +1100,37 @@ impl UpgradeableTradingContract {
         GovernanceManager::cancel_proposal(&env, proposal_id, admin)
             .map_err(|_| TradeError::Unauthorized)
     }
+
+    /// Archive an upgrade proposal that has been closed long enough,
+    /// keeping only its summary. Anyone can archive.
+    pub fn archive_upgrade(
+        env: Env,
+        proposal_id: u64,
+        archiver: Address,
+    ) -> Result<ProposalSummary, TradeError> {
+        archiver.require_auth();
+        require_initialized(&env)?;
+
+        GovernanceManager::archive_proposal(&env, proposal_id, archiver)
+            .map_err(|_| TradeError::Unauthorized)
+    }
+
+    /// Outcome of an upgrade proposal, archived or not
+    pub fn get_proposal_summary(env: Env, proposal_id: u64) -> Result<ProposalSummary, TradeError> {
+        require_initialized(&env)?;
+        GovernanceManager::get_proposal_summary(&env, proposal_id)
+            .map_err(|_| TradeError::Unauthorized)
+    }
+
+    /// One page of upgrade proposal summaries, oldest first, and the
+    /// cursor of the next
+    pub fn get_upgrade_proposals(
+        env: Env,
+        page: Pagination,
+    ) -> Result<(Vec<ProposalSummary>, Option<u32>), TradeError> {
+        require_initialized(&env)?;
+        Ok(GovernanceManager::proposals(&env, &page))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,162 @@
+//! Referral registry and fee rebates
+//!
+//! A trader binds a referrer exactly once. Fees on that trader's subsequent
+//! trades are split: the referrer receives `rebate_bps` of the fee, where the
+//! rate is picked from admin-configured tiers based on the referrer's referred
+//! volume over the current 30-day window.
+
+use shared::events::{EventEmitter, FeeCollectedEvent};
+use shared::fees::FeeManager;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+use crate::{storage_keys, TradeError};
+
+/// Length of the volume window used for tier selection
+pub const VOLUME_WINDOW_SECS: u64 = 30 * 24 * 60 * 60;
+/// Basis point denominator
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Rebate rate applied once a referrer's window volume reaches `min_volume`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebateTier {
+    pub min_volume: i128,
+    pub rebate_bps: u32,
+}
+
+pub fn get_referrer(env: &Env, user: &Address) -> Option<Address> {
+    let key = (symbol_short!("ref"), user.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn bind_referrer(env: &Env, user: &Address, referrer: &Address) -> Result<(), TradeError> {
+    if user == referrer {
+        return Err(TradeError::InvalidReferrer);
+    }
+    // Prevent two-address loops where accounts refer each other
+    if get_referrer(env, referrer).as_ref() == Some(user) {
+        return Err(TradeError::InvalidReferrer);
+    }
+    if get_referrer(env, user).is_some() {
+        return Err(TradeError::ReferrerAlreadySet);
+    }
+
+    let key = (symbol_short!("ref"), user.clone());
+    env.storage().persistent().set(&key, referrer);
+    Ok(())
+}
+
+pub fn read_tiers(env: &Env) -> Vec<RebateTier> {
+    env.storage()
+        .persistent()
+        .get(&storage_keys::REF_TIERS)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Tiers must be sorted by strictly increasing `min_volume` with rates ≤ 100%
+pub fn write_tiers(env: &Env, tiers: &Vec<RebateTier>) -> Result<(), TradeError> {
+    let mut prev: Option<i128> = None;
+    for tier in tiers.iter() {
+        if tier.min_volume < 0 || tier.rebate_bps > BPS_DENOMINATOR as u32 {
+            return Err(TradeError::InvalidRebateTiers);
+        }
+        if let Some(p) = prev {
+            if tier.min_volume <= p {
+                return Err(TradeError::InvalidRebateTiers);
+            }
+        }
+        prev = Some(tier.min_volume);
+    }
+
+    env.storage().persistent().set(&storage_keys::REF_TIERS, tiers);
+    Ok(())
+}
+
+fn current_window(env: &Env) -> u64 {
+    env.ledger().timestamp() / VOLUME_WINDOW_SECS
+}
+
+/// Volume referred by `referrer` during the current 30-day window
+pub fn referred_volume(env: &Env, referrer: &Address) -> i128 {
+    let key = (symbol_short!("ref_vol"), referrer.clone(), current_window(env));
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+fn add_referred_volume(env: &Env, referrer: &Address, volume: i128) {
+    let key = (symbol_short!("ref_vol"), referrer.clone(), current_window(env));
+    let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &current.saturating_add(volume));
+}
+
+/// Rebate rate for a referrer based on its current window volume
+pub fn rebate_bps_for(env: &Env, referrer: &Address) -> u32 {
+    let volume = referred_volume(env, referrer);
+    let mut bps = 0;
+    for tier in read_tiers(env).iter() {
+        if volume >= tier.min_volume {
+            bps = tier.rebate_bps;
+        } else {
+            break;
+        }
+    }
+    bps
+}
+
+/// Collect a trade fee, routing the referrer's rebate share directly to the
+/// referrer when the trader has one. `volume` is credited to the referrer
+/// after the rate for this trade has been determined.
+pub fn collect_trade_fee(
+    env: &Env,
+    trader: &Address,
+    fee_token: &Address,
+    fee_recipient: &Address,
+    fee_amount: i128,
+    volume: i128,
+) -> Result<(), TradeError> {
+    let referrer = match get_referrer(env, trader) {
+        Some(referrer) => referrer,
+        None => {
+            return FeeManager::collect_fee(env, fee_token, trader, fee_recipient, fee_amount)
+                .map_err(|_| TradeError::InsufficientBalance);
+        }
+    };
+
+    let rebate_bps = rebate_bps_for(env, &referrer);
+    let referrer_share = fee_amount * rebate_bps as i128 / BPS_DENOMINATOR;
+    let protocol_share = fee_amount - referrer_share;
+
+    FeeManager::collect_fee(env, fee_token, trader, fee_recipient, protocol_share)
+        .map_err(|_| TradeError::InsufficientBalance)?;
+    FeeManager::collect_fee(env, fee_token, trader, &referrer, referrer_share)
+        .map_err(|_| TradeError::InsufficientBalance)?;
+
+    add_referred_volume(env, &referrer, volume);
+
+    let timestamp = env.ledger().timestamp();
+    EventEmitter::referral_fee_collected(
+        env,
+        FeeCollectedEvent {
+            payer: trader.clone(),
+            recipient: fee_recipient.clone(),
+            amount: protocol_share,
+            token: fee_token.clone(),
+            timestamp,
+        },
+        referrer.clone(),
+    );
+    EventEmitter::referral_fee_collected(
+        env,
+        FeeCollectedEvent {
+            payer: trader.clone(),
+            recipient: referrer.clone(),
+            amount: referrer_share,
+            token: fee_token.clone(),
+            timestamp,
+        },
+        referrer,
+    );
+
+    Ok(())
+}
@@ -4,8 +4,8 @@ use super::*;
 use shared::governance::ProposalStatus;
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Ledger},
-    vec, Env, Vec,
+    testutils::{Address as _, Events, Ledger},
+    vec, Env, TryFromVal, Vec,
 };
 
 // Use the auto-generated client from #[contractimpl]
@@ -449,14 +449,22 @@ fn test_optimized_get_recent_trades() {
         );
     }
 
-    // Get recent trades
-    let recent = client.get_recent_trades(&3u32);
+    // Get recent trades, newest first
+    let (recent, next) = client.get_recent_trades(&Pagination::first(3));
     assert_eq!(recent.len(), 3);
-
-    // Should get trades 3, 4, 5
-    assert_eq!(recent.get(0).unwrap().id, 3);
+    assert_eq!(recent.get(0).unwrap().id, 5);
     assert_eq!(recent.get(1).unwrap().id, 4);
-    assert_eq!(recent.get(2).unwrap().id, 5);
+    assert_eq!(recent.get(2).unwrap().id, 3);
+    assert_eq!(next, Some(3));
+
+    let (older, next) = client.get_recent_trades(&Pagination {
+        cursor: 3,
+        limit: 3,
+    });
+    assert_eq!(older.len(), 2);
+    assert_eq!(older.get(0).unwrap().id, 2);
+    assert_eq!(older.get(1).unwrap().id, 1);
+    assert_eq!(next, None);
 }
 
 #[test]
@@ -755,3 +763,677 @@ fn test_optimized_storage_access_pattern() {
     let stats = client.get_stats();
     assert_eq!(stats.total_trades, 1);
 }
+
+// ============ REFERRAL TESTS ============
+
+fn setup_fee_token(env: &Env, trader: &Address) -> Address {
+    let token_admin = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    soroban_sdk::token::StellarAssetClient::new(env, &token_id).mint(trader, &1_000_000);
+    token_id
+}
+
+#[test]
+fn test_bind_referrer_only_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.bind_referrer(&trader, &referrer);
+    assert_eq!(client.get_referrer(&trader), Some(referrer.clone()));
+
+    let result = client.try_bind_referrer(&trader, &other);
+    assert!(result.is_err());
+
+    // Self-referral and reciprocal referral are rejected
+    assert!(client.try_bind_referrer(&other, &other).is_err());
+    assert!(client.try_bind_referrer(&referrer, &trader).is_err());
+}
+
+#[test]
+fn test_rebate_tiers_must_be_sorted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _approver, _executor) = setup_contract(&env);
+
+    let unsorted = vec![
+        &env,
+        RebateTier {
+            min_volume: 1_000,
+            rebate_bps: 2_000,
+        },
+        RebateTier {
+            min_volume: 0,
+            rebate_bps: 1_000,
+        },
+    ];
+    assert!(client.try_set_rebate_tiers(&admin, &unsorted).is_err());
+
+    let too_high = vec![
+        &env,
+        RebateTier {
+            min_volume: 0,
+            rebate_bps: 10_001,
+        },
+    ];
+    assert!(client.try_set_rebate_tiers(&admin, &too_high).is_err());
+}
+
+#[test]
+fn test_referred_trade_splits_fee_by_tier() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    env.mock_all_auths();
+
+    let (client, admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token_id = setup_fee_token(&env, &trader);
+    let token = soroban_sdk::token::Client::new(&env, &token_id);
+
+    client.set_rebate_tiers(
+        &admin,
+        &vec![
+            &env,
+            RebateTier {
+                min_volume: 0,
+                rebate_bps: 1_000,
+            },
+            RebateTier {
+                min_volume: 5_000,
+                rebate_bps: 2_500,
+            },
+        ],
+    );
+    client.bind_referrer(&trader, &referrer);
+
+    // First trade: referrer has no volume yet -> 10%
+    client.trade(
+        &trader,
+        &symbol_short!("XLMUSDC"),
+        &5_000,
+        &10,
+        &true,
+        &token_id,
+        &1_000,
+        &fee_recipient,
+    );
+    assert_eq!(token.balance(&referrer), 100);
+    assert_eq!(token.balance(&fee_recipient), 900);
+    assert_eq!(client.get_referrer_stats(&referrer), (5_000, 2_500));
+
+    // Second trade: referrer crossed the 5_000 tier -> 25%
+    client.trade(
+        &trader,
+        &symbol_short!("XLMUSDC"),
+        &100,
+        &10,
+        &true,
+        &token_id,
+        &1_000,
+        &fee_recipient,
+    );
+    assert_eq!(token.balance(&referrer), 350);
+    assert_eq!(token.balance(&fee_recipient), 1_650);
+
+    // Volume window resets after 30 days
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + 31 * 24 * 60 * 60);
+    assert_eq!(client.get_referrer_stats(&referrer), (0, 1_000));
+}
+
+#[test]
+fn test_unreferred_trade_pays_full_fee_to_recipient() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    env.mock_all_auths();
+
+    let (client, _admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token_id = setup_fee_token(&env, &trader);
+
+    client.trade(
+        &trader,
+        &symbol_short!("XLMUSDC"),
+        &5_000,
+        &10,
+        &true,
+        &token_id,
+        &1_000,
+        &fee_recipient,
+    );
+    let token = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token.balance(&fee_recipient), 1_000);
+}
+
+// ============ VOLUME TIER TESTS ============
+
+#[test]
+fn test_volume_tiers_discount_fees() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    env.mock_all_auths();
+
+    let (client, admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token_id = setup_fee_token(&env, &trader);
+    let token = soroban_sdk::token::Client::new(&env, &token_id);
+    let pair = symbol_short!("XLMUSDC");
+
+    let unsorted = vec![
+        &env,
+        FeeTier {
+            min_volume: 10_000,
+            discount_bps: 1_000,
+        },
+        FeeTier {
+            min_volume: 10_000,
+            discount_bps: 2_000,
+        },
+    ];
+    let result = client.try_set_fee_tiers(&admin, &unsorted);
+    assert!(result.is_err());
+    client.set_fee_tiers(
+        &admin,
+        &vec![
+            &env,
+            FeeTier {
+                min_volume: 10_000,
+                discount_bps: 1_000,
+            },
+            FeeTier {
+                min_volume: 50_000,
+                discount_bps: 5_000,
+            },
+        ],
+    );
+
+    // The trade that crosses a tier still pays the old rate
+    client.trade(
+        &trader,
+        &pair,
+        &10_000,
+        &10,
+        &true,
+        &token_id,
+        &1_000,
+        &fee_recipient,
+    );
+    assert_eq!(token.balance(&fee_recipient), 1_000);
+    let changed = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.len() == 1
+                && Symbol::try_from_val(&env, &topics.get(0).unwrap())
+                    == Ok(shared::events::topics::FEE_TIER_CHANGED)
+        })
+        .count();
+    assert_eq!(changed, 1);
+
+    let orders = vec![
+        &env,
+        (pair.clone(), 20_000_i128, 10_i128, true),
+        (pair.clone(), 20_000_i128, 10_i128, false),
+    ];
+    client.batch_trade(&trader, &orders, &token_id, &1_000, &fee_recipient);
+    assert_eq!(token.balance(&fee_recipient), 1_000 + 1_800);
+
+    let stats = client.trader_stats(&trader);
+    assert_eq!(stats.volume_30d, 50_000);
+    assert_eq!(stats.trades, 3);
+    assert_eq!((stats.tier, stats.discount_bps), (2, 5_000));
+
+    // Volume older than 30 days no longer counts
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + 30 * 24 * 60 * 60);
+    let stats = client.trader_stats(&trader);
+    assert_eq!((stats.volume_30d, stats.tier), (0, 0));
+    assert_eq!(stats.lifetime_volume, 50_000);
+    client.trade(
+        &trader,
+        &pair,
+        &100,
+        &10,
+        &true,
+        &token_id,
+        &1_000,
+        &fee_recipient,
+    );
+    assert_eq!(token.balance(&fee_recipient), 1_000 + 1_800 + 1_000);
+}
+
+// ============ POSITION TESTS ============
+
+#[test]
+fn test_positions_track_entry_and_realized_pnl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token_id = setup_fee_token(&env, &trader);
+    let pair = symbol_short!("XLMUSDC");
+
+    let orders = vec![
+        &env,
+        (pair.clone(), 100_i128, 10_i128, true),
+        (pair.clone(), 300_i128, 20_i128, true),
+    ];
+    client.batch_trade(&trader, &orders, &token_id, &0, &fee_recipient);
+    let position = client.position_of(&trader, &pair);
+    assert_eq!((position.net, position.avg_entry_price), (400, 17));
+
+    client.trade(
+        &trader,
+        &pair,
+        &100,
+        &27,
+        &false,
+        &token_id,
+        &0,
+        &fee_recipient,
+    );
+    let position = client.position_of(&trader, &pair);
+    assert_eq!((position.net, position.realized_pnl), (300, 1_000));
+    let realized = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.len() == 1
+                && Symbol::try_from_val(&env, &topics.get(0).unwrap())
+                    == Ok(shared::events::topics::PNL_REALIZED)
+        })
+        .count();
+    assert_eq!(realized, 1);
+
+    // Other pairs are tracked separately
+    let other = client.position_of(&trader, &symbol_short!("BTCUSDC"));
+    assert_eq!(other.net, 0);
+}
+
+#[test]
+fn test_circuit_breaker_halts_pair_until_cooldown() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    env.mock_all_auths();
+
+    let (client, admin, _approver, _executor) = setup_contract(&env);
+    let trader = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(fee_recipient.clone());
+    let btc = symbol_short!("BTCUSD");
+    let eth = symbol_short!("ETHUSD");
+
+    let invalid = BreakerConfig {
+        max_move_bps: 0,
+        window: 300,
+        cooldown: 600,
+    };
+    assert!(client
+        .try_set_circuit_breaker(&admin, &Some(invalid))
+        .is_err());
+    client.set_circuit_breaker(
+        &admin,
+        &Some(BreakerConfig {
+            max_move_bps: 1_000,
+            window: 300,
+            cooldown: 600,
+        }),
+    );
+
+    assert!(client.report_price(&admin, &btc, &50_000));
+    assert!(client.report_price(&admin, &eth, &3_000));
+    // A 12% jump within the window trips BTCUSD only
+    env.ledger().with_mut(|li| li.timestamp += 60);
+    assert!(!client.report_price(&admin, &btc, &56_000));
+    let state = client.get_breaker_state(&btc).unwrap();
+    assert_eq!(state.reference_price, 50_000);
+    assert_eq!(state.tripped_price, 56_000);
+    assert_eq!(state.tripped_until, 1_660);
+
+    let result = client.try_trade(
+        &trader,
+        &btc,
+        &1_000,
+        &56_000,
+        &true,
+        &token_id,
+        &0,
+        &fee_recipient,
+    );
+    assert!(result.is_err());
+    let mut orders = Vec::new(&env);
+    orders.push_back((eth.clone(), 1_000i128, 3_000i128, true));
+    orders.push_back((btc.clone(), 1_000i128, 56_000i128, true));
+    assert!(client
+        .try_batch_trade(&trader, &orders, &token_id, &0i128, &fee_recipient)
+        .is_err());
+    client.trade(
+        &trader,
+        &eth,
+        &1_000,
+        &3_000,
+        &true,
+        &token_id,
+        &0,
+        &fee_recipient,
+    );
+
+    // Trading resumes after the cooldown from the tripping price
+    env.ledger().with_mut(|li| li.timestamp = 1_660);
+    client.trade(
+        &trader,
+        &btc,
+        &1_000,
+        &56_000,
+        &true,
+        &token_id,
+        &0,
+        &fee_recipient,
+    );
+    let state = client.get_breaker_state(&btc).unwrap();
+    assert_eq!(state.reference_price, 56_000);
+    assert_eq!(state.tripped_until, 0);
+
+    // Admins can reopen a pair early
+    assert!(!client.report_price(&admin, &btc, &40_000));
+    client.reset_circuit_breaker(&admin, &btc, &40_000);
+    client.trade(
+        &trader,
+        &btc,
+        &1_000,
+        &40_000,
+        &false,
+        &token_id,
+        &0,
+        &fee_recipient,
+    );
+}
+
+#[test]
+fn test_execute_upgrade_batch() {
+    use shared::events::{topics, StandardEventV2, EVENT_PREFIX};
+    use soroban_sdk::{testutils::Events, TryFromVal};
+
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    env.mock_all_auths();
+
+    let (client, admin, approver, executor) = setup_contract(&env);
+    let mut approvers = Vec::new(&env);
+    approvers.push_back(approver.clone());
+
+    let propose = |approve: bool| {
+        let id = client.propose_upgrade(
+            &admin,
+            &symbol_short!("v2hash"),
+            &symbol_short!("Upgrade"),
+            &approvers,
+            &1u32,
+            &3600u64,
+        );
+        if approve {
+            client.approve_upgrade(&id, &approver);
+        }
+        id
+    };
+    let first = propose(true);
+    let second = propose(true);
+    let unapproved = propose(false);
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+
+    // One bad proposal rolls back the whole batch
+    let result =
+        client.try_execute_upgrade_batch(&vec![&env, first, unapproved, second], &executor, &false);
+    assert!(result.is_err());
+    assert_eq!(
+        client.get_upgrade_proposal(&first).status,
+        ProposalStatus::Approved
+    );
+
+    // Best effort skips it and executes the rest
+    let executed =
+        client.execute_upgrade_batch(&vec![&env, first, unapproved, second], &executor, &true);
+    assert_eq!(executed, vec![&env, first, second]);
+    assert_eq!(
+        client.get_upgrade_proposal(&second).status,
+        ProposalStatus::Executed
+    );
+    assert_eq!(
+        client.get_upgrade_proposal(&unapproved).status,
+        ProposalStatus::Pending
+    );
+
+    let prefix = Symbol::new(&env, EVENT_PREFIX);
+    let mut events: Vec<StandardEventV2> = Vec::new(&env);
+    for (contract, topics, data) in env.events().all().iter() {
+        if contract == client.address
+            && Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        {
+            events.push_back(StandardEventV2::try_from_val(&env, &data).unwrap());
+        }
+    }
+    // Each execution is reported on its own, then the batch summary, all
+    // under one correlation id
+    let n = events.len();
+    let summary = events.get(n - 1).unwrap();
+    assert_eq!(summary.topic, topics::PROPOSAL_BATCH_EXECUTED);
+    for e in [events.get(n - 3).unwrap(), events.get(n - 2).unwrap()] {
+        assert_eq!(e.topic, topics::PROPOSAL_EXECUTED);
+        assert_eq!(e.correlation_id, summary.correlation_id);
+    }
+}
+
+#[test]
+fn test_archive_closed_upgrade_proposal() {
+    use shared::events::{metadata_keys, topics};
+    use shared::governance::ARCHIVE_AFTER_LEDGERS;
+    use test_utils::assert_standard_event;
+
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 100;
+    });
+    env.mock_all_auths();
+
+    let (client, admin, approver, executor) = setup_contract(&env);
+    let mut approvers = Vec::new(&env);
+    approvers.push_back(approver.clone());
+    let proposal_id = client.propose_upgrade(
+        &admin,
+        &symbol_short!("v2hash"),
+        &symbol_short!("Upgrade"),
+        &approvers,
+        &1u32,
+        &0u64,
+    );
+    let archiver = Address::generate(&env);
+
+    // Open proposals cannot be archived
+    assert!(client.try_archive_upgrade(&proposal_id, &archiver).is_err());
+
+    client.approve_upgrade(&proposal_id, &approver);
+    client.execute_upgrade(&proposal_id, &executor);
+    assert_eq!(client.get_proposal_summary(&proposal_id).closed_ledger, 100);
+    assert!(client.try_archive_upgrade(&proposal_id, &archiver).is_err());
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = 100 + ARCHIVE_AFTER_LEDGERS);
+    let summary = client.archive_upgrade(&proposal_id, &archiver);
+    assert_eq!(summary.status, ProposalStatus::Executed);
+    assert_eq!(summary.approvals_count, 1);
+    assert_eq!(summary.new_contract_hash, symbol_short!("v2hash"));
+    assert_eq!(summary.closed_ledger, 100);
+
+    let archived = assert_standard_event(&env, topics::PROPOSAL_ARCHIVED, |_| true);
+    let id: u64 = archived.metadata(&env, metadata_keys::ID_KEY).unwrap();
+    assert_eq!(id, proposal_id);
+
+    // Only the summary is left
+    assert!(client.try_get_upgrade_proposal(&proposal_id).is_err());
+    assert_eq!(client.get_proposal_summary(&proposal_id), summary);
+    assert!(client.try_archive_upgrade(&proposal_id, &archiver).is_err());
+}
+
+mod baseline {
+    use shared::governance::ProposalStatus;
+    use soroban_sdk::{contracttype, Address, Symbol, Vec};
+
+    /// `UpgradeProposal` as stored by the first deployed release
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct UpgradeProposal {
+        pub id: u64,
+        pub proposer: Address,
+        pub new_contract_hash: Symbol,
+        pub target_contract: Address,
+        pub description: Symbol,
+        pub approval_threshold: u32,
+        pub approvers: Vec<Address>,
+        pub approvals_count: u32,
+        pub status: ProposalStatus,
+        pub created_at: u64,
+        pub execution_time: u64,
+        pub executed: bool,
+    }
+}
+
+#[test]
+fn test_baseline_stored_proposals_still_decode() {
+    use shared::governance::ARCHIVE_AFTER_LEDGERS;
+    use soroban_sdk::Map;
+
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 100;
+    });
+    env.mock_all_auths();
+    let (client, admin, approver, executor) = setup_contract(&env);
+
+    // Proposals written by the baseline release, one open and one rejected
+    let stored = |id: u64, status: ProposalStatus| baseline::UpgradeProposal {
+        id,
+        proposer: admin.clone(),
+        new_contract_hash: symbol_short!("v2hash"),
+        target_contract: client.address.clone(),
+        description: symbol_short!("Upgrade"),
+        approval_threshold: 1,
+        approvers: vec![&env, approver.clone()],
+        approvals_count: 0,
+        status,
+        created_at: 900,
+        execution_time: 900,
+        executed: false,
+    };
+    env.as_contract(&client.address, || {
+        let mut proposals = Map::new(&env);
+        proposals.set(1u64, stored(1, ProposalStatus::Pending));
+        proposals.set(2u64, stored(2, ProposalStatus::Rejected));
+        let storage = env.storage().persistent();
+        storage.set(&symbol_short!("props"), &proposals);
+        storage.set(&symbol_short!("prop_cnt"), &2u64);
+    });
+
+    assert_eq!(client.get_upgrade_proposal(&1).approvals_count, 0);
+    client.approve_upgrade(&1, &approver);
+    client.execute_upgrade(&1, &executor);
+    assert_eq!(client.get_proposal_summary(&1).closed_ledger, 100);
+    assert_eq!(client.get_proposal_summary(&2).closed_ledger, 0);
+
+    // Rewriting proposal 1 moved it out of the baseline map
+    let (summaries, _) = client.get_upgrade_proposals(&Pagination::first(10));
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries.get(0).unwrap().status, ProposalStatus::Executed);
+    assert_eq!(summaries.get(1).unwrap().status, ProposalStatus::Rejected);
+    env.as_contract(&client.address, || {
+        let proposals: Map<u64, baseline::UpgradeProposal> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("props"))
+            .unwrap();
+        assert_eq!(proposals.keys(), vec![&env, 2u64]);
+    });
+
+    // A proposal closed before closing ledgers were recorded counts from 0
+    let archiver = Address::generate(&env);
+    env.ledger()
+        .with_mut(|li| li.sequence_number = ARCHIVE_AFTER_LEDGERS);
+    let summary = client.archive_upgrade(&2, &archiver);
+    assert_eq!(summary.status, ProposalStatus::Rejected);
+    assert!(client.try_archive_upgrade(&1, &archiver).is_err());
+    env.ledger()
+        .with_mut(|li| li.sequence_number = 100 + ARCHIVE_AFTER_LEDGERS);
+    client.archive_upgrade(&1, &archiver);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&symbol_short!("props")));
+    });
+}
+
+#[test]
+fn test_migration_grants_permissions_added_since_first_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, UpgradeableTradingContract);
+    let client = UpgradeableTradingContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    // ACL as the first release's `init` left it
+    env.as_contract(&contract_id, || {
+        let admin_role = Symbol::new(&env, "admin");
+        ACL::create_role(&env, &admin_role);
+        ACL::assign_role(&env, &admin, &admin_role);
+        for permission in ["set_rate", "premium", "pause", "unpause", "manage_acl"] {
+            ACL::assign_permission(&env, &admin_role, &Symbol::new(&env, permission));
+        }
+        env.storage().persistent().set(&storage_keys::INIT, &true);
+    });
+    for permission in ADDED_ADMIN_PERMISSIONS {
+        assert!(!client.has_permission(&admin, &Symbol::new(&env, permission)));
+    }
+
+    client.migrate_permissions(&admin);
+    client.migrate_permissions(&admin);
+    for permission in ADDED_ADMIN_PERMISSIONS {
+        assert!(client.has_permission(&admin, &Symbol::new(&env, permission)));
+    }
+    let permissions = client.get_role_permissions(&Symbol::new(&env, "admin"));
+    assert_eq!(permissions.len(), 9);
+
+    // The migrated admin can use the newer entry points
+    client.set_fee_tiers(&admin, &Vec::new(&env));
+}
+
+#[test]
+fn test_admin_transfer_moves_governance_and_acl_roles() {
+    let env = Env::default();
+    let (client, admin, _approver, _executor) = setup_contract(&env);
+    let successor = Address::generate(&env);
+    let pause = Symbol::new(&env, "pause");
+
+    client.propose_admin(&admin, &successor);
+    let result = client.try_propose_admin(&successor, &admin);
+    assert_eq!(result, Err(Ok(TradeError::Unauthorized)));
+    assert!(!client.has_permission(&successor, &pause));
+
+    client.accept_admin(&successor);
+    assert!(client.pending_admin().is_none());
+    assert!(client.has_permission(&successor, &pause));
+    assert!(!client.has_permission(&admin, &pause));
+    let result = client.try_cancel_admin_transfer(&admin);
+    assert_eq!(result, Err(Ok(TradeError::Unauthorized)));
+    client.pause(&successor);
+}
@@ -0,0 +1,183 @@
+//! Rolling trader volume and fee tiers
+//!
+//! Each trade's `amount` is credited to the trader in daily buckets, and a
+//! trader's volume is the sum of the buckets for the last 30 days. Fees are
+//! discounted by the admin-configured tier that volume reaches, evaluated
+//! before the trade is credited. Crossing into a different tier (either
+//! way) is reported with a `FEE_TIER_CHANGED` event on the trade that
+//! causes it; tiers lost because old volume aged out are reported on the
+//! trader's next trade.
+
+use shared::events::{EventEmitter, FeeTierChangedEvent};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+use crate::{storage_keys, TradeError};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+/// Number of daily buckets making up the rolling window
+pub const VOLUME_WINDOW_DAYS: u64 = 30;
+/// Basis point denominator
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Fee discount applied once a trader's rolling volume reaches `min_volume`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    pub min_volume: i128,
+    pub discount_bps: u32,
+}
+
+/// Volume credited to a trader on one day
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayVolume {
+    pub day: u64,
+    pub volume: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+struct VolumeRecord {
+    /// Buckets within the window, oldest first
+    days: Vec<DayVolume>,
+    lifetime_volume: i128,
+    trades: u64,
+    /// Tier reported in the last `FEE_TIER_CHANGED` event
+    tier: u32,
+}
+
+/// Rolling volume and fee tier of a trader
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraderStats {
+    /// Volume over the last 30 days
+    pub volume_30d: i128,
+    pub lifetime_volume: i128,
+    pub trades: u64,
+    /// 1-based index into the fee tiers, 0 when no tier applies
+    pub tier: u32,
+    pub discount_bps: u32,
+}
+
+pub fn read_tiers(env: &Env) -> Vec<FeeTier> {
+    env.storage()
+        .persistent()
+        .get(&storage_keys::FEE_TIERS)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Tiers must be sorted by strictly increasing `min_volume` with discounts
+/// ≤ 100%
+pub fn write_tiers(env: &Env, tiers: &Vec<FeeTier>) -> Result<(), TradeError> {
+    let mut prev: Option<i128> = None;
+    for tier in tiers.iter() {
+        if tier.min_volume < 0 || tier.discount_bps > BPS_DENOMINATOR {
+            return Err(TradeError::InvalidFeeTiers);
+        }
+        if prev.is_some_and(|p| tier.min_volume <= p) {
+            return Err(TradeError::InvalidFeeTiers);
+        }
+        prev = Some(tier.min_volume);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&storage_keys::FEE_TIERS, tiers);
+    Ok(())
+}
+
+fn current_day(env: &Env) -> u64 {
+    env.ledger().timestamp() / DAY_SECS
+}
+
+fn read_record(env: &Env, trader: &Address) -> VolumeRecord {
+    let key = (symbol_short!("vol"), trader.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| VolumeRecord {
+            days: Vec::new(env),
+            lifetime_volume: 0,
+            trades: 0,
+            tier: 0,
+        })
+}
+
+fn rolling_volume(env: &Env, record: &VolumeRecord) -> i128 {
+    let first_day = (current_day(env) + 1).saturating_sub(VOLUME_WINDOW_DAYS);
+    record
+        .days
+        .iter()
+        .filter(|bucket| bucket.day >= first_day)
+        .fold(0, |total: i128, bucket| total.saturating_add(bucket.volume))
+}
+
+/// Tier reached by `volume` (1-based, 0 for none) and its discount
+fn tier_for(env: &Env, volume: i128) -> (u32, u32) {
+    let mut tier = (0, 0);
+    for (index, fee_tier) in read_tiers(env).iter().enumerate() {
+        if volume < fee_tier.min_volume {
+            break;
+        }
+        tier = (index as u32 + 1, fee_tier.discount_bps);
+    }
+    tier
+}
+
+/// Discount (bps) `trader` currently earns on fees
+pub fn discount_bps_for(env: &Env, trader: &Address) -> u32 {
+    tier_for(env, rolling_volume(env, &read_record(env, trader))).1
+}
+
+pub fn trader_stats(env: &Env, trader: &Address) -> TraderStats {
+    let record = read_record(env, trader);
+    let volume_30d = rolling_volume(env, &record);
+    let (tier, discount_bps) = tier_for(env, volume_30d);
+    TraderStats {
+        volume_30d,
+        lifetime_volume: record.lifetime_volume,
+        trades: record.trades,
+        tier,
+        discount_bps,
+    }
+}
+
+/// Credit `trades` trades totalling `volume` to `trader`, emitting
+/// `FEE_TIER_CHANGED` if that moves them into a different tier
+pub fn record_volume(env: &Env, trader: &Address, volume: i128, trades: u64) {
+    let mut record = read_record(env, trader);
+    let today = current_day(env);
+    let first_day = (today + 1).saturating_sub(VOLUME_WINDOW_DAYS);
+    while record.days.first().is_some_and(|b| b.day < first_day) {
+        record.days.pop_front();
+    }
+    match record.days.last() {
+        Some(mut bucket) if bucket.day == today => {
+            bucket.volume = bucket.volume.saturating_add(volume);
+            record.days.set(record.days.len() - 1, bucket);
+        }
+        _ => record.days.push_back(DayVolume { day: today, volume }),
+    }
+    record.lifetime_volume = record.lifetime_volume.saturating_add(volume);
+    record.trades += trades;
+
+    let volume_30d = rolling_volume(env, &record);
+    let (tier, discount_bps) = tier_for(env, volume_30d);
+    if tier != record.tier {
+        EventEmitter::fee_tier_changed(
+            env,
+            FeeTierChangedEvent {
+                trader: trader.clone(),
+                old_tier: record.tier,
+                new_tier: tier,
+                discount_bps,
+                volume: volume_30d,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        record.tier = tier;
+    }
+
+    let key = (symbol_short!("vol"), trader.clone());
+    env.storage().persistent().set(&key, &record);
+}
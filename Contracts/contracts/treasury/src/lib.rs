@@ -0,0 +1,740 @@
+//! Protocol treasury
+//!
+//! Holds protocol-owned tokens and pays them out only through spending
+//! proposals: an admin proposes, `threshold` approvers sign off, and after
+//! the timelock the executor releases the funds. Every recipient has a
+//! per-token budget cap that resets each epoch, and balances are tracked per
+//! token so the treasury can hold any number of assets.
+//!
+//! Small spends can skip the approval round: an optimistic spend up to the
+//! token's optimistic limit executes once its challenge window passes.
+//! Anyone who disagrees can post a bond to challenge it, which sends the
+//! spend back through the full approval process. The bond is refunded if
+//! the spend is then cancelled and forfeited to the treasury if it executes.
+//!
+//! Every state-changing entry point is recorded in the shared audit log;
+//! `get_audit` reports its call count, last caller and last ledger.
+
+#![no_std]
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::audit::{AuditLog, AuditRecord};
+use shared::epoch::EpochClock;
+use shared::events::{
+    namespaces, BudgetSpentEvent, EventEmitter, ProposalChallengedEvent, TransferEvent,
+};
+use shared::governance::{GovernanceRole, ProposalStatus};
+use shared::invariants::Invariants;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, Map, Symbol, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TreasuryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidThreshold = 5,
+    ProposalNotFound = 6,
+    ProposalNotPending = 7,
+    ProposalNotApproved = 8,
+    DuplicateApproval = 9,
+    TimelockNotExpired = 10,
+    BudgetExceeded = 11,
+    InsufficientFunds = 12,
+    OptimisticDisabled = 13,
+    OptimisticLimitExceeded = 14,
+    NotOptimistic = 15,
+    ChallengeWindowClosed = 16,
+    AlreadyChallenged = 17,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Roles,
+    NextProposalId,
+    Proposal(u64),
+    Tokens,
+    Balance(Address),
+    TotalSpent(Address),
+    BudgetCap(Address, Address),
+    BudgetSpent(Address, Address, u64),
+    OptimisticConfig,
+    OptimisticLimit(Address),
+    Challenge(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryConfig {
+    /// Approvals required before a spend can be executed
+    pub threshold: u32,
+    /// Delay between proposal creation and earliest execution
+    pub timelock: u64,
+    /// Length of a budget epoch in seconds
+    pub epoch_length: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub description: Symbol,
+    pub approvals: Vec<Address>,
+    pub status: ProposalStatus,
+    pub created_at: u64,
+    pub executable_at: u64,
+    /// Skips approval and executes after the challenge window unless
+    /// challenged
+    pub optimistic: bool,
+}
+
+/// Settings for optimistic spends
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptimisticConfig {
+    /// How long an optimistic spend can be challenged
+    pub challenge_window: u64,
+    /// Token challengers post their bond in
+    pub bond_token: Address,
+    /// Bond required to challenge a spend
+    pub bond_amount: i128,
+}
+
+/// Bond posted against an optimistic spend
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub challenger: Address,
+    pub bond_token: Address,
+    pub bond: i128,
+    pub challenged_at: u64,
+}
+
+/// A recipient's budget for one token in the current epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Budget {
+    pub cap: i128,
+    pub spent: i128,
+    pub epoch: u64,
+}
+
+#[contract]
+pub struct TreasuryContract;
+
+#[contractimpl]
+impl TreasuryContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        approvers: Vec<Address>,
+        executor: Address,
+        threshold: u32,
+        timelock: u64,
+        epoch_length: u64,
+    ) -> Result<(), TreasuryError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(TreasuryError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if threshold == 0 || threshold > approvers.len() || epoch_length == 0 {
+            return Err(TreasuryError::InvalidThreshold);
+        }
+
+        let mut roles = Map::new(&env);
+        roles.set(admin, GovernanceRole::Admin);
+        for approver in approvers.iter() {
+            roles.set(approver, GovernanceRole::Approver);
+        }
+        roles.set(executor, GovernanceRole::Executor);
+
+        let config = TreasuryConfig {
+            threshold,
+            timelock,
+            epoch_length,
+        };
+        env.storage().instance().set(&DataKey::Roles, &roles);
+        env.storage().instance().set(&DataKey::Config, &config);
+        EventEmitter::init(&env, namespaces::TREASURY);
+        Ok(())
+    }
+
+    /// Fund the treasury with `amount` of `token`
+    pub fn deposit(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), TreasuryError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        audit(&env, "deposit", &from);
+        token::Client::new(&env, &token).transfer(&from, &env.current_contract_address(), &amount);
+        credit(&env, token.clone(), amount);
+        check_invariants(&env, &token);
+        Ok(())
+    }
+
+    /// Set the per-epoch budget cap of `recipient` for `token` (admin only)
+    pub fn set_budget(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+        token: Address,
+        cap: i128,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        audit(&env, "set_budget", &admin);
+        if cap < 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetCap(recipient, token), &cap);
+        Ok(())
+    }
+
+    /// Propose paying `amount` of `token` to `recipient` (admin only)
+    pub fn propose_spend(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        description: Symbol,
+    ) -> Result<u64, TreasuryError> {
+        require_role(&env, &proposer, GovernanceRole::Admin)?;
+        audit(&env, "propose_spend", &proposer);
+        if amount <= 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        if amount > budget_cap(&env, &recipient, &token) {
+            return Err(TreasuryError::BudgetExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let proposal = SpendProposal {
+            id: next_proposal_id(&env),
+            proposer,
+            recipient,
+            token,
+            amount,
+            description,
+            approvals: Vec::new(&env),
+            status: ProposalStatus::Pending,
+            created_at: now,
+            executable_at: now + load_config(&env)?.timelock,
+            optimistic: false,
+        };
+        save_proposal(&env, &proposal);
+        Ok(proposal.id)
+    }
+
+    /// Configure optimistic spends: their challenge window and the bond a
+    /// challenger must post (admin only)
+    pub fn set_optimistic_config(
+        env: Env,
+        admin: Address,
+        challenge_window: u64,
+        bond_token: Address,
+        bond_amount: i128,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        audit(&env, "set_optimistic_config", &admin);
+        if challenge_window == 0 || bond_amount <= 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        let config = OptimisticConfig {
+            challenge_window,
+            bond_token,
+            bond_amount,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::OptimisticConfig, &config);
+        Ok(())
+    }
+
+    /// Set the largest optimistic spend of `token`; zero disables
+    /// optimistic spends of it (admin only)
+    pub fn set_optimistic_limit(
+        env: Env,
+        admin: Address,
+        token: Address,
+        limit: i128,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        audit(&env, "set_optimistic_limit", &admin);
+        if limit < 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::OptimisticLimit(token), &limit);
+        Ok(())
+    }
+
+    /// Propose a spend that needs no approvals and becomes executable once
+    /// the challenge window passes unchallenged (admin only)
+    pub fn propose_optimistic_spend(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        description: Symbol,
+    ) -> Result<u64, TreasuryError> {
+        require_role(&env, &proposer, GovernanceRole::Admin)?;
+        audit(&env, "propose_optimistic_spend", &proposer);
+        let config = load_optimistic_config(&env)?;
+        if amount <= 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        if amount > Self::optimistic_limit(env.clone(), token.clone()) {
+            return Err(TreasuryError::OptimisticLimitExceeded);
+        }
+        if amount > budget_cap(&env, &recipient, &token) {
+            return Err(TreasuryError::BudgetExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let proposal = SpendProposal {
+            id: next_proposal_id(&env),
+            proposer,
+            recipient,
+            token,
+            amount,
+            description,
+            approvals: Vec::new(&env),
+            status: ProposalStatus::Approved,
+            created_at: now,
+            executable_at: now + config.challenge_window,
+            optimistic: true,
+        };
+        save_proposal(&env, &proposal);
+        Ok(proposal.id)
+    }
+
+    /// Challenge an optimistic spend during its challenge window by posting
+    /// the configured bond. The spend goes back to pending and needs the
+    /// full approval threshold and timelock before it can execute.
+    pub fn challenge_spend(
+        env: Env,
+        challenger: Address,
+        proposal_id: u64,
+    ) -> Result<(), TreasuryError> {
+        challenger.require_auth();
+        audit(&env, "challenge_spend", &challenger);
+        let config = load_optimistic_config(&env)?;
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if !proposal.optimistic {
+            return Err(TreasuryError::NotOptimistic);
+        }
+        let challenge_key = DataKey::Challenge(proposal_id);
+        if env.storage().persistent().has(&challenge_key) {
+            return Err(TreasuryError::AlreadyChallenged);
+        }
+        if proposal.status != ProposalStatus::Approved {
+            return Err(TreasuryError::ProposalNotApproved);
+        }
+        let now = env.ledger().timestamp();
+        if now >= proposal.executable_at {
+            return Err(TreasuryError::ChallengeWindowClosed);
+        }
+
+        token::Client::new(&env, &config.bond_token).transfer(
+            &challenger,
+            &env.current_contract_address(),
+            &config.bond_amount,
+        );
+        let challenge = Challenge {
+            challenger: challenger.clone(),
+            bond_token: config.bond_token.clone(),
+            bond: config.bond_amount,
+            challenged_at: now,
+        };
+        env.storage().persistent().set(&challenge_key, &challenge);
+
+        proposal.status = ProposalStatus::Pending;
+        proposal.executable_at = now + load_config(&env)?.timelock;
+        save_proposal(&env, &proposal);
+
+        EventEmitter::proposal_challenged(
+            &env,
+            ProposalChallengedEvent {
+                proposal_id,
+                challenger,
+                bond_token: config.bond_token,
+                bond: config.bond_amount,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Approve a pending spend. The proposal becomes executable once it
+    /// reaches the approval threshold.
+    pub fn approve_spend(
+        env: Env,
+        approver: Address,
+        proposal_id: u64,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &approver, GovernanceRole::Approver)?;
+        audit(&env, "approve_spend", &approver);
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(TreasuryError::ProposalNotPending);
+        }
+        if proposal.approvals.contains(&approver) {
+            return Err(TreasuryError::DuplicateApproval);
+        }
+
+        proposal.approvals.push_back(approver);
+        if proposal.approvals.len() >= load_config(&env)?.threshold {
+            proposal.status = ProposalStatus::Approved;
+        }
+        save_proposal(&env, &proposal);
+        Ok(())
+    }
+
+    /// Cancel a spend that has not been executed (admin only). A
+    /// challenger's bond is refunded.
+    pub fn cancel_spend(env: Env, admin: Address, proposal_id: u64) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        audit(&env, "cancel_spend", &admin);
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Approved
+        {
+            return Err(TreasuryError::ProposalNotPending);
+        }
+        proposal.status = ProposalStatus::Cancelled;
+        save_proposal(&env, &proposal);
+
+        if let Some(challenge) = Self::get_challenge(env.clone(), proposal_id) {
+            token::Client::new(&env, &challenge.bond_token).transfer(
+                &env.current_contract_address(),
+                &challenge.challenger,
+                &challenge.bond,
+            );
+            check_invariants(&env, &challenge.bond_token);
+        }
+        Ok(())
+    }
+
+    /// Pay out an approved spend once its timelock has passed. The amount is
+    /// charged against the recipient's budget for the current epoch. A
+    /// challenger's bond is forfeited to the treasury.
+    pub fn execute_spend(
+        env: Env,
+        executor: Address,
+        proposal_id: u64,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &executor, GovernanceRole::Executor)?;
+        audit(&env, "execute_spend", &executor);
+        let mut proposal = load_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Approved {
+            return Err(TreasuryError::ProposalNotApproved);
+        }
+        let now = env.ledger().timestamp();
+        if now < proposal.executable_at {
+            return Err(TreasuryError::TimelockNotExpired);
+        }
+
+        let epoch = Self::current_epoch(env.clone())?;
+        let cap = budget_cap(&env, &proposal.recipient, &proposal.token);
+        let spent_key =
+            DataKey::BudgetSpent(proposal.recipient.clone(), proposal.token.clone(), epoch);
+        let spent: i128 = env.storage().persistent().get(&spent_key).unwrap_or(0);
+        if spent + proposal.amount > cap {
+            return Err(TreasuryError::BudgetExceeded);
+        }
+
+        let balance = Self::balance(env.clone(), proposal.token.clone());
+        if balance < proposal.amount {
+            return Err(TreasuryError::InsufficientFunds);
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        save_proposal(&env, &proposal);
+        env.storage()
+            .persistent()
+            .set(&spent_key, &(spent + proposal.amount));
+        env.storage().persistent().set(
+            &DataKey::Balance(proposal.token.clone()),
+            &(balance - proposal.amount),
+        );
+        let total_spent = Self::total_spent(env.clone(), proposal.token.clone());
+        env.storage().persistent().set(
+            &DataKey::TotalSpent(proposal.token.clone()),
+            &(total_spent + proposal.amount),
+        );
+
+        if let Some(challenge) = Self::get_challenge(env.clone(), proposal_id) {
+            credit(&env, challenge.bond_token, challenge.bond);
+        }
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &proposal.token).transfer(
+            &this,
+            &proposal.recipient,
+            &proposal.amount,
+        );
+
+        EventEmitter::transfer(
+            &env,
+            TransferEvent {
+                from: this,
+                to: proposal.recipient.clone(),
+                token: proposal.token.clone(),
+                amount: proposal.amount,
+                timestamp: now,
+            },
+        );
+        EventEmitter::budget_spent(
+            &env,
+            BudgetSpentEvent {
+                proposal_id,
+                recipient: proposal.recipient,
+                token: proposal.token.clone(),
+                amount: proposal.amount,
+                epoch,
+                remaining: cap - spent - proposal.amount,
+                timestamp: now,
+            },
+        );
+        check_invariants(&env, &proposal.token);
+        Ok(())
+    }
+
+    /// Propose `new_admin` to take over the admin role; takes effect once
+    /// they accept
+    pub fn propose_admin(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        audit(&env, "propose_admin", &admin);
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending proposal, taking the admin role from the address
+    /// that proposed it
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let previous = PendingAdmin::get(&env).map(|pending| pending.current_admin);
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        let mut roles: Map<Address, GovernanceRole> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Roles)
+            .unwrap_or_else(|| Map::new(&env));
+        if let Some(previous) = previous {
+            roles.remove(previous);
+        }
+        roles.set(admin, GovernanceRole::Admin);
+        env.storage().instance().set(&DataKey::Roles, &roles);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), TreasuryError> {
+        require_role(&env, &admin, GovernanceRole::Admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<SpendProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+    }
+
+    /// Bond posted against an optimistic spend, if it was challenged
+    pub fn get_challenge(env: Env, proposal_id: u64) -> Option<Challenge> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Challenge(proposal_id))
+    }
+
+    /// Largest optimistic spend allowed for `token`
+    pub fn optimistic_limit(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OptimisticLimit(token))
+            .unwrap_or(0)
+    }
+
+    pub fn get_optimistic_config(env: Env) -> Result<OptimisticConfig, TreasuryError> {
+        load_optimistic_config(&env)
+    }
+
+    /// Budget of `recipient` for `token` in the current epoch
+    pub fn get_budget(
+        env: Env,
+        recipient: Address,
+        token: Address,
+    ) -> Result<Budget, TreasuryError> {
+        let epoch = Self::current_epoch(env.clone())?;
+        let spent = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BudgetSpent(
+                recipient.clone(),
+                token.clone(),
+                epoch,
+            ))
+            .unwrap_or(0);
+        Ok(Budget {
+            cap: budget_cap(&env, &recipient, &token),
+            spent,
+            epoch,
+        })
+    }
+
+    pub fn current_epoch(env: Env) -> Result<u64, TreasuryError> {
+        let clock = EpochClock {
+            genesis: 0,
+            length: load_config(&env)?.epoch_length,
+        };
+        Ok(clock.current_epoch(&env))
+    }
+
+    /// Treasury-accounted balance of `token`
+    pub fn balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(token))
+            .unwrap_or(0)
+    }
+
+    /// Total amount of `token` paid out over the treasury's lifetime
+    pub fn total_spent(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalSpent(token))
+            .unwrap_or(0)
+    }
+
+    /// Tokens the treasury has ever received
+    pub fn get_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tokens)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_config(env: Env) -> Result<TreasuryConfig, TreasuryError> {
+        load_config(&env)
+    }
+
+    /// Call count, last caller and last ledger of entry point `function`
+    pub fn get_audit(env: Env, function: Symbol) -> Option<AuditRecord> {
+        AuditLog::get(&env, function)
+    }
+}
+
+/// Record a successful call of `function` in the audit log
+fn audit(env: &Env, function: &str, caller: &Address) {
+    AuditLog::record(env, Symbol::new(env, function), caller);
+}
+
+fn require_role(env: &Env, address: &Address, role: GovernanceRole) -> Result<(), TreasuryError> {
+    address.require_auth();
+    let roles: Map<Address, GovernanceRole> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Roles)
+        .ok_or(TreasuryError::NotInitialized)?;
+    if roles.get(address.clone()) != Some(role) {
+        return Err(TreasuryError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_config(env: &Env) -> Result<TreasuryConfig, TreasuryError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(TreasuryError::NotInitialized)
+}
+
+fn load_optimistic_config(env: &Env) -> Result<OptimisticConfig, TreasuryError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::OptimisticConfig)
+        .ok_or(TreasuryError::OptimisticDisabled)
+}
+
+fn next_proposal_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextProposalId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextProposalId, &(id + 1));
+    id
+}
+
+/// Add `amount` of `token` to the treasury's accounted balance
+fn credit(env: &Env, token: Address, amount: i128) {
+    let mut tokens = TreasuryContract::get_tokens(env.clone());
+    if !tokens.contains(&token) {
+        tokens.push_back(token.clone());
+        env.storage().instance().set(&DataKey::Tokens, &tokens);
+    }
+    let balance = TreasuryContract::balance(env.clone(), token.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(token), &(balance + amount));
+}
+
+/// The balance the treasury accounts for must be held, on top of any
+/// challenge bonds it is holding
+fn check_invariants(env: &Env, token: &Address) {
+    Invariants::new(env, "treasury").le("tracked balance held", || {
+        let tracked = TreasuryContract::balance(env.clone(), token.clone());
+        let held = token::Client::new(env, token).balance(&env.current_contract_address());
+        (tracked, held)
+    });
+}
+
+fn budget_cap(env: &Env, recipient: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BudgetCap(recipient.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Result<SpendProposal, TreasuryError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .ok_or(TreasuryError::ProposalNotFound)
+}
+
+fn save_proposal(env: &Env, proposal: &SpendProposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(proposal.id), proposal);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,388 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use shared::events::{topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, TryFromVal,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: TreasuryContractClient<'static>,
+    usdc: TokenClient<'static>,
+    xlm: TokenClient<'static>,
+    admin: Address,
+    approvers: Vec<Address>,
+    executor: Address,
+    recipient: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 10 * DAY);
+
+    let admin = Address::generate(&env);
+    let executor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let approvers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    let usdc_id = env.register_stellar_asset_contract(admin.clone());
+    let xlm_id = env.register_stellar_asset_contract(admin.clone());
+    StellarAssetClient::new(&env, &usdc_id).mint(&admin, &1_000_000);
+    StellarAssetClient::new(&env, &xlm_id).mint(&admin, &1_000_000);
+
+    let contract_id = env.register_contract(None, TreasuryContract);
+    let client = TreasuryContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &approvers, &executor, &2, &DAY, &(30 * DAY));
+    client.deposit(&admin, &usdc_id, &500_000);
+    client.deposit(&admin, &xlm_id, &200_000);
+
+    Setup {
+        client,
+        usdc: TokenClient::new(&env, &usdc_id),
+        xlm: TokenClient::new(&env, &xlm_id),
+        env,
+        admin,
+        approvers,
+        executor,
+        recipient,
+    }
+}
+
+fn approved_spend(s: &Setup, amount: i128) -> u64 {
+    let id = s.client.propose_spend(
+        &s.admin,
+        &s.recipient,
+        &s.usdc.address,
+        &amount,
+        &symbol_short!("grant"),
+    );
+    s.client.approve_spend(&s.approvers.get(0).unwrap(), &id);
+    s.client.approve_spend(&s.approvers.get(1).unwrap(), &id);
+    id
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_multi_token_accounting() {
+    let s = setup();
+    assert_eq!(s.client.balance(&s.usdc.address), 500_000);
+    assert_eq!(s.client.balance(&s.xlm.address), 200_000);
+    assert_eq!(s.client.get_tokens().len(), 2);
+
+    s.client.deposit(&s.admin, &s.usdc.address, &1_000);
+    assert_eq!(s.client.balance(&s.usdc.address), 501_000);
+    assert_eq!(s.client.get_tokens().len(), 2);
+}
+
+#[test]
+fn test_spend_requires_threshold_and_timelock() {
+    let s = setup();
+    s.client
+        .set_budget(&s.admin, &s.recipient, &s.usdc.address, &100_000);
+
+    let id = s.client.propose_spend(
+        &s.admin,
+        &s.recipient,
+        &s.usdc.address,
+        &40_000,
+        &symbol_short!("grant"),
+    );
+    s.client.approve_spend(&s.approvers.get(0).unwrap(), &id);
+    let result = s.client.try_execute_spend(&s.executor, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::ProposalNotApproved)));
+
+    let result = s
+        .client
+        .try_approve_spend(&s.approvers.get(0).unwrap(), &id);
+    assert_eq!(result, Err(Ok(TreasuryError::DuplicateApproval)));
+    s.client.approve_spend(&s.approvers.get(1).unwrap(), &id);
+
+    let result = s.client.try_execute_spend(&s.executor, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::TimelockNotExpired)));
+
+    advance(&s.env, DAY);
+    s.client.execute_spend(&s.executor, &id);
+    assert_eq!(s.usdc.balance(&s.recipient), 40_000);
+    assert_eq!(s.client.balance(&s.usdc.address), 460_000);
+    assert_eq!(s.client.total_spent(&s.usdc.address), 40_000);
+    assert_eq!(
+        s.client.get_proposal(&id).unwrap().status,
+        ProposalStatus::Executed
+    );
+}
+
+#[test]
+fn test_budget_cap_resets_each_epoch() {
+    let s = setup();
+    s.client
+        .set_budget(&s.admin, &s.recipient, &s.usdc.address, &100_000);
+
+    let result = s.client.try_propose_spend(
+        &s.admin,
+        &s.recipient,
+        &s.usdc.address,
+        &100_001,
+        &symbol_short!("grant"),
+    );
+    assert_eq!(result, Err(Ok(TreasuryError::BudgetExceeded)));
+
+    let first = approved_spend(&s, 70_000);
+    let second = approved_spend(&s, 70_000);
+    advance(&s.env, DAY);
+    s.client.execute_spend(&s.executor, &first);
+
+    let result = s.client.try_execute_spend(&s.executor, &second);
+    assert_eq!(result, Err(Ok(TreasuryError::BudgetExceeded)));
+    assert_eq!(
+        s.client.get_budget(&s.recipient, &s.usdc.address).spent,
+        70_000
+    );
+
+    // Next epoch the budget is fresh
+    advance(&s.env, 30 * DAY);
+    s.client.execute_spend(&s.executor, &second);
+    assert_eq!(s.usdc.balance(&s.recipient), 140_000);
+}
+
+#[test]
+fn test_roles_are_enforced() {
+    let s = setup();
+    s.client
+        .set_budget(&s.admin, &s.recipient, &s.usdc.address, &100_000);
+
+    let stranger = Address::generate(&s.env);
+    let result = s.client.try_propose_spend(
+        &stranger,
+        &s.recipient,
+        &s.usdc.address,
+        &1_000,
+        &symbol_short!("grant"),
+    );
+    assert_eq!(result, Err(Ok(TreasuryError::Unauthorized)));
+
+    let id = approved_spend(&s, 1_000);
+    advance(&s.env, DAY);
+    let result = s.client.try_execute_spend(&stranger, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::Unauthorized)));
+
+    s.client.cancel_spend(&s.admin, &id);
+    let result = s.client.try_execute_spend(&s.executor, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::ProposalNotApproved)));
+}
+
+#[test]
+fn test_spend_emits_transfer_and_budget_events() {
+    let s = setup();
+    s.client
+        .set_budget(&s.admin, &s.recipient, &s.usdc.address, &100_000);
+    let id = approved_spend(&s, 25_000);
+    advance(&s.env, DAY);
+    s.client.execute_spend(&s.executor, &id);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let standard: std::vec::Vec<StandardEventV2> = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .filter(|event| event.topic != topics::AUDIT)
+        .collect();
+
+    assert_eq!(standard.len(), 2);
+    assert_eq!(standard[0].topic, topics::TRANSFER);
+    assert_eq!(standard[1].topic, topics::BUDGET_SPENT);
+    let proposal_id = standard[1]
+        .metadata
+        .get(shared::events::metadata_keys::ID_KEY)
+        .unwrap();
+    assert_eq!(u64::try_from_val(&s.env, &proposal_id).unwrap(), id);
+}
+
+#[test]
+fn test_audit_log_tracks_entry_points() {
+    let s = setup();
+    let deposit = Symbol::new(&s.env, "deposit");
+    let record = s.client.get_audit(&deposit).unwrap();
+    assert_eq!(record.calls, 2);
+    assert_eq!(record.last_caller, s.admin);
+    assert!(s
+        .client
+        .get_audit(&Symbol::new(&s.env, "execute_spend"))
+        .is_none());
+
+    s.env.ledger().with_mut(|l| l.sequence_number += 10);
+    let funder = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.usdc.address).mint(&funder, &100);
+    s.client.deposit(&funder, &s.usdc.address, &100);
+    let record = s.client.get_audit(&deposit).unwrap();
+    assert_eq!(record.calls, 3);
+    assert_eq!(record.last_caller, funder);
+    assert_eq!(record.last_ledger, record.first_ledger + 10);
+
+    // Failed calls are not counted
+    let result = s.client.try_deposit(&funder, &s.usdc.address, &0);
+    assert_eq!(result, Err(Ok(TreasuryError::InvalidAmount)));
+    assert_eq!(s.client.get_audit(&deposit).unwrap().calls, 3);
+}
+
+fn optimistic_setup() -> Setup {
+    let s = setup();
+    s.client
+        .set_budget(&s.admin, &s.recipient, &s.usdc.address, &100_000);
+    s.client
+        .set_optimistic_config(&s.admin, &(3 * DAY), &s.xlm.address, &5_000);
+    s.client
+        .set_optimistic_limit(&s.admin, &s.usdc.address, &10_000);
+    s
+}
+
+fn optimistic_spend(s: &Setup, amount: i128) -> u64 {
+    s.client.propose_optimistic_spend(
+        &s.admin,
+        &s.recipient,
+        &s.usdc.address,
+        &amount,
+        &symbol_short!("grant"),
+    )
+}
+
+#[test]
+fn test_optimistic_spend_executes_after_challenge_window() {
+    let s = optimistic_setup();
+
+    let result = s.client.try_propose_optimistic_spend(
+        &s.admin,
+        &s.recipient,
+        &s.usdc.address,
+        &10_001,
+        &symbol_short!("grant"),
+    );
+    assert_eq!(result, Err(Ok(TreasuryError::OptimisticLimitExceeded)));
+
+    let id = optimistic_spend(&s, 8_000);
+    let result = s.client.try_execute_spend(&s.executor, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::TimelockNotExpired)));
+
+    advance(&s.env, 3 * DAY);
+    let challenger = Address::generate(&s.env);
+    let result = s.client.try_challenge_spend(&challenger, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::ChallengeWindowClosed)));
+
+    s.client.execute_spend(&s.executor, &id);
+    assert_eq!(s.usdc.balance(&s.recipient), 8_000);
+    assert!(s.client.get_proposal(&id).unwrap().approvals.is_empty());
+}
+
+#[test]
+fn test_challenge_escalates_to_full_approval() {
+    let s = optimistic_setup();
+    let challenger = Address::generate(&s.env);
+    s.xlm.transfer(&s.admin, &challenger, &10_000);
+
+    let id = optimistic_spend(&s, 8_000);
+    advance(&s.env, DAY);
+    s.client.challenge_spend(&challenger, &id);
+    assert_eq!(s.xlm.balance(&challenger), 5_000);
+    assert_eq!(
+        s.client.get_proposal(&id).unwrap().status,
+        ProposalStatus::Pending
+    );
+    let result = s.client.try_challenge_spend(&challenger, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::AlreadyChallenged)));
+
+    // The original window passing is no longer enough
+    advance(&s.env, 2 * DAY);
+    let result = s.client.try_execute_spend(&s.executor, &id);
+    assert_eq!(result, Err(Ok(TreasuryError::ProposalNotApproved)));
+
+    // Approved in the full vote: the challenger's bond goes to the treasury
+    s.client.approve_spend(&s.approvers.get(0).unwrap(), &id);
+    s.client.approve_spend(&s.approvers.get(1).unwrap(), &id);
+    s.client.execute_spend(&s.executor, &id);
+    assert_eq!(s.usdc.balance(&s.recipient), 8_000);
+    assert_eq!(s.client.balance(&s.xlm.address), 205_000);
+
+    // Cancelled after a challenge: the bond is refunded
+    let id = optimistic_spend(&s, 1_000);
+    s.client.challenge_spend(&challenger, &id);
+    assert_eq!(s.xlm.balance(&challenger), 0);
+    s.client.cancel_spend(&s.admin, &id);
+    assert_eq!(s.xlm.balance(&challenger), 5_000);
+    assert_eq!(s.client.balance(&s.xlm.address), 205_000);
+
+    let regular = approved_spend(&s, 1_000);
+    let result = s.client.try_challenge_spend(&challenger, &regular);
+    assert_eq!(result, Err(Ok(TreasuryError::NotOptimistic)));
+}
+
+#[test]
+fn test_challenge_emits_event() {
+    let s = optimistic_setup();
+    let challenger = Address::generate(&s.env);
+    s.xlm.transfer(&s.admin, &challenger, &5_000);
+    let id = optimistic_spend(&s, 8_000);
+    s.client.challenge_spend(&challenger, &id);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let standard: std::vec::Vec<StandardEventV2> = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .filter(|event| event.topic != topics::AUDIT)
+        .collect();
+
+    assert_eq!(standard.len(), 1);
+    assert_eq!(standard[0].topic, topics::PROPOSAL_CHALLENGED);
+    assert_eq!(standard[0].user_address, challenger);
+    let challenge = s.client.get_challenge(&id).unwrap();
+    assert_eq!(challenge.bond, 5_000);
+    assert_eq!(challenge.bond_token, s.xlm.address);
+}
+
+#[test]
+fn test_admin_transfer_moves_the_admin_role() {
+    let s = setup();
+    let successor = Address::generate(&s.env);
+
+    s.client.propose_admin(&s.admin, &successor);
+    let result = s
+        .client
+        .try_set_budget(&successor, &s.recipient, &s.usdc.address, &1_000);
+    assert_eq!(result, Err(Ok(TreasuryError::Unauthorized)));
+
+    s.client.accept_admin(&successor);
+    assert!(s.client.pending_admin().is_none());
+    s.client
+        .set_budget(&successor, &s.recipient, &s.usdc.address, &1_000);
+    let result = s
+        .client
+        .try_set_budget(&s.admin, &s.recipient, &s.usdc.address, &1_000);
+    assert_eq!(result, Err(Ok(TreasuryError::Unauthorized)));
+}
@@ -0,0 +1,320 @@
+//! Share-based yield vault (ERC-4626 style)
+//!
+//! Depositors hand the vault an underlying asset and receive shares; the
+//! share price is `total_assets / total_shares`, so yield that lands in the
+//! vault (staking rewards, AMM fees, ...) accrues to every holder pro rata.
+//! Conversions round in the vault's favour and use a one-unit virtual
+//! offset, which keeps an attacker from inflating the price of the first
+//! share with a donation.
+//!
+//! Assets beyond the idle buffer are allocated across yield strategies by
+//! weight (see `strategy`); `total_assets` counts both.
+
+#![no_std]
+
+mod strategy;
+
+pub use strategy::{Allocation, Strategy, StrategyClient, MAX_STRATEGIES};
+
+use shared::admin_transfer::{AdminTransferError, PendingAdmin};
+use shared::events::{EventEmitter, VaultEvent};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env, Vec};
+
+/// Basis-point denominator for strategy weights
+pub const BPS_DENOMINATOR: i128 = 10_000;
+/// Virtual shares and assets added to both sides of every conversion
+const VIRTUAL_OFFSET: i128 = 1;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VaultError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InsufficientShares = 5,
+    ZeroShares = 6,
+    StrategyFailed = 7,
+    InvalidWeight = 8,
+    StrategyNotFound = 9,
+    TooManyStrategies = 10,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Asset,
+    TotalShares,
+    Shares(Address),
+}
+
+#[contract]
+pub struct VaultContract;
+
+#[contractimpl]
+impl VaultContract {
+    /// `asset` is the underlying token the vault accepts and pays out
+    pub fn initialize(env: Env, admin: Address, asset: Address) -> Result<(), VaultError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        Ok(())
+    }
+
+    /// Deposit `assets` and mint shares to `user`. Returns the shares minted.
+    pub fn deposit(env: Env, user: Address, assets: i128) -> Result<i128, VaultError> {
+        user.require_auth();
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let asset = vault_asset(&env)?;
+        let shares = Self::convert_to_shares(env.clone(), assets)?;
+        if shares == 0 {
+            return Err(VaultError::ZeroShares);
+        }
+        token::Client::new(&env, &asset).transfer(&user, &env.current_contract_address(), &assets);
+        mint_shares(&env, &user, shares);
+
+        EventEmitter::vault_deposit(&env, vault_event(&env, user, asset, assets, shares)?);
+        Ok(shares)
+    }
+
+    /// Withdraw exactly `assets`, burning as many of `user`'s shares as
+    /// needed (rounded up). Returns the shares burned.
+    pub fn withdraw(env: Env, user: Address, assets: i128) -> Result<i128, VaultError> {
+        user.require_auth();
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let asset = vault_asset(&env)?;
+        let shares = mul_div_up(
+            assets,
+            Self::total_shares(env.clone()) + VIRTUAL_OFFSET,
+            Self::total_assets(env.clone())? + VIRTUAL_OFFSET,
+        );
+        exit(&env, &user, &asset, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Burn `shares` and pay out their value. Returns the assets paid.
+    pub fn redeem(env: Env, user: Address, shares: i128) -> Result<i128, VaultError> {
+        user.require_auth();
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let asset = vault_asset(&env)?;
+        let assets = Self::convert_to_assets(env.clone(), shares)?;
+        if assets == 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        exit(&env, &user, &asset, assets, shares)?;
+        Ok(assets)
+    }
+
+    // ---- strategies ----
+
+    /// Allocate `weight_bps` of the vault's assets to `strategy`, adding it
+    /// if new. Weights across strategies may not exceed 100%.
+    pub fn set_strategy(
+        env: Env,
+        admin: Address,
+        strategy: Address,
+        weight_bps: u32,
+    ) -> Result<(), VaultError> {
+        require_admin(&env, &admin)?;
+        strategy::set_allocation(&env, &strategy, weight_bps)
+    }
+
+    /// Divest a strategy completely and drop it. Returns the assets
+    /// recovered.
+    pub fn remove_strategy(
+        env: Env,
+        admin: Address,
+        strategy: Address,
+    ) -> Result<i128, VaultError> {
+        require_admin(&env, &admin)?;
+        strategy::remove(&env, &vault_asset(&env)?, &strategy)
+    }
+
+    /// Move funds towards the target weights. Permissionless, for keepers.
+    pub fn rebalance(env: Env) -> Result<(), VaultError> {
+        let asset = vault_asset(&env)?;
+        let total = Self::total_assets(env.clone())?;
+        strategy::rebalance(&env, &asset, total)
+    }
+
+    /// Collect yield from every strategy into the vault. Permissionless,
+    /// for keepers. Returns the total profit.
+    pub fn harvest(env: Env) -> Result<i128, VaultError> {
+        strategy::harvest(&env, &vault_asset(&env)?)
+    }
+
+    pub fn strategies(env: Env) -> Vec<Allocation> {
+        strategy::allocations(&env)
+    }
+
+    // ---- views ----
+
+    /// Shares minted for `assets` at the current price, rounded down
+    pub fn convert_to_shares(env: Env, assets: i128) -> Result<i128, VaultError> {
+        Ok(assets * (Self::total_shares(env.clone()) + VIRTUAL_OFFSET)
+            / (Self::total_assets(env)? + VIRTUAL_OFFSET))
+    }
+
+    /// Assets paid for `shares` at the current price, rounded down
+    pub fn convert_to_assets(env: Env, shares: i128) -> Result<i128, VaultError> {
+        Ok(shares * (Self::total_assets(env.clone())? + VIRTUAL_OFFSET)
+            / (Self::total_shares(env) + VIRTUAL_OFFSET))
+    }
+
+    /// Underlying assets managed by the vault, idle or in strategies
+    pub fn total_assets(env: Env) -> Result<i128, VaultError> {
+        match env.storage().instance().get::<_, Address>(&DataKey::Asset) {
+            Some(asset) => {
+                let idle =
+                    token::Client::new(&env, &asset).balance(&env.current_contract_address());
+                Ok(idle + strategy::deployed(&env)?)
+            }
+            None => Ok(0),
+        }
+    }
+
+    pub fn total_shares(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    pub fn shares_of(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(user))
+            .unwrap_or(0)
+    }
+
+    pub fn asset(env: Env) -> Result<Address, VaultError> {
+        vault_asset(&env)
+    }
+
+    // ---- admin transfer ----
+
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), VaultError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::propose(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Become admin by accepting a pending proposal
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AdminTransferError> {
+        let admin = PendingAdmin::accept(&env, &new_admin)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), VaultError> {
+        require_admin(&env, &admin)?;
+        PendingAdmin::cancel(&env);
+        Ok(())
+    }
+
+    pub fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        PendingAdmin::get(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), VaultError> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(VaultError::NotInitialized)?;
+    if stored != *admin {
+        return Err(VaultError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn vault_asset(env: &Env) -> Result<Address, VaultError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Asset)
+        .ok_or(VaultError::NotInitialized)
+}
+
+fn mul_div_up(a: i128, b: i128, denominator: i128) -> i128 {
+    (a * b + denominator - 1) / denominator
+}
+
+fn mint_shares(env: &Env, user: &Address, shares: i128) {
+    let balance = VaultContract::shares_of(env.clone(), user.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shares(user.clone()), &(balance + shares));
+    let total = VaultContract::total_shares(env.clone());
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalShares, &(total + shares));
+}
+
+/// Burn `shares` from `user` and pay them `assets`
+fn exit(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    assets: i128,
+    shares: i128,
+) -> Result<(), VaultError> {
+    let balance = VaultContract::shares_of(env.clone(), user.clone());
+    if shares > balance {
+        return Err(VaultError::InsufficientShares);
+    }
+    if balance == shares {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Shares(user.clone()));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(user.clone()), &(balance - shares));
+    }
+    let total = VaultContract::total_shares(env.clone());
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalShares, &(total - shares));
+    strategy::free(env, asset, assets)?;
+    token::Client::new(env, asset).transfer(&env.current_contract_address(), user, &assets);
+
+    EventEmitter::vault_withdraw(
+        env,
+        vault_event(env, user.clone(), asset.clone(), assets, shares)?,
+    );
+    Ok(())
+}
+
+fn vault_event(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    assets: i128,
+    shares: i128,
+) -> Result<VaultEvent, VaultError> {
+    Ok(VaultEvent {
+        user,
+        asset,
+        assets,
+        shares,
+        total_assets: VaultContract::total_assets(env.clone())?,
+        total_shares: VaultContract::total_shares(env.clone()),
+        timestamp: env.ledger().timestamp(),
+    })
+}
+
+#[cfg(test)]
+mod test;
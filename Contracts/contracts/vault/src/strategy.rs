@@ -0,0 +1,224 @@
+//! Strategy adapters and allocation router
+//!
+//! A strategy is any contract implementing [`Strategy`] on behalf of one
+//! vault: a staking position, an AMM LP, a lending deposit. The vault sends
+//! it assets before calling `invest`, and the strategy sends assets back
+//! to the vault from `divest` and `harvest`. Amounts actually received are
+//! measured from the vault's balance rather than trusted from return values.
+//!
+//! The admin assigns each strategy a weight in basis points of
+//! `total_assets`; whatever is not allocated stays idle in the vault as a
+//! withdrawal buffer. `rebalance` and `harvest` take no arguments and no
+//! auth so they can be driven by the keeper registry.
+
+use crate::{VaultError, BPS_DENOMINATOR};
+use shared::events::{EventEmitter, HarvestEvent};
+use soroban_sdk::{contractclient, contracttype, token, Address, Env, Vec};
+
+/// Upper bound on strategies so rebalancing stays within one transaction's
+/// budget
+pub const MAX_STRATEGIES: u32 = 10;
+
+#[contractclient(name = "StrategyClient")]
+pub trait Strategy {
+    /// Put `amount`, already transferred to the strategy, to work
+    fn invest(env: Env, amount: i128) -> Result<(), soroban_sdk::Error>;
+    /// Send up to `amount` back to the vault. Returns the amount sent.
+    fn divest(env: Env, amount: i128) -> Result<i128, soroban_sdk::Error>;
+    /// Realise accrued yield and send it to the vault. Returns the profit.
+    fn harvest(env: Env) -> Result<i128, soroban_sdk::Error>;
+    /// Assets currently managed for the vault
+    fn report(env: Env) -> Result<i128, soroban_sdk::Error>;
+}
+
+/// A strategy's target share of the vault's assets
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    pub strategy: Address,
+    pub weight_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum StrategyKey {
+    Allocations,
+}
+
+pub fn allocations(env: &Env) -> Vec<Allocation> {
+    env.storage()
+        .instance()
+        .get(&StrategyKey::Allocations)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a strategy or change its weight. Funds move on the next rebalance.
+pub fn set_allocation(env: &Env, strategy: &Address, weight_bps: u32) -> Result<(), VaultError> {
+    let mut allocations = allocations(env);
+    let mut total = weight_bps as i128;
+    let mut index = None;
+    for (i, allocation) in allocations.iter().enumerate() {
+        if allocation.strategy == *strategy {
+            index = Some(i as u32);
+        } else {
+            total += allocation.weight_bps as i128;
+        }
+    }
+    if total > BPS_DENOMINATOR {
+        return Err(VaultError::InvalidWeight);
+    }
+    let allocation = Allocation {
+        strategy: strategy.clone(),
+        weight_bps,
+    };
+    match index {
+        Some(i) => allocations.set(i, allocation),
+        None if allocations.len() >= MAX_STRATEGIES => return Err(VaultError::TooManyStrategies),
+        None => allocations.push_back(allocation),
+    }
+    env.storage()
+        .instance()
+        .set(&StrategyKey::Allocations, &allocations);
+    Ok(())
+}
+
+/// Pull everything out of a strategy and stop allocating to it. Returns
+/// the assets recovered.
+pub fn remove(env: &Env, asset: &Address, strategy: &Address) -> Result<i128, VaultError> {
+    let mut allocations = allocations(env);
+    let index = allocations
+        .iter()
+        .position(|allocation| allocation.strategy == *strategy)
+        .ok_or(VaultError::StrategyNotFound)?;
+    let recovered = divest(env, asset, strategy, report(env, strategy)?)?;
+    allocations.remove(index as u32);
+    env.storage()
+        .instance()
+        .set(&StrategyKey::Allocations, &allocations);
+    Ok(recovered)
+}
+
+/// Assets deployed across all strategies
+pub fn deployed(env: &Env) -> Result<i128, VaultError> {
+    let mut total = 0;
+    for allocation in allocations(env).iter() {
+        total += report(env, &allocation.strategy)?;
+    }
+    Ok(total)
+}
+
+/// Move funds towards each strategy's target weight. Over-allocated
+/// strategies are divested first so their proceeds can fund the rest.
+pub fn rebalance(env: &Env, asset: &Address, total_assets: i128) -> Result<(), VaultError> {
+    let allocations = allocations(env);
+    let mut shortfalls: Vec<(Address, i128)> = Vec::new(env);
+    for allocation in allocations.iter() {
+        let target = total_assets * allocation.weight_bps as i128 / BPS_DENOMINATOR;
+        let current = report(env, &allocation.strategy)?;
+        if current > target {
+            divest(env, asset, &allocation.strategy, current - target)?;
+        } else if current < target {
+            shortfalls.push_back((allocation.strategy, target - current));
+        }
+    }
+
+    let token = token::Client::new(env, asset);
+    let vault = env.current_contract_address();
+    for (strategy, shortfall) in shortfalls.iter() {
+        let amount = shortfall.min(token.balance(&vault));
+        if amount <= 0 {
+            break;
+        }
+        token.transfer(&vault, &strategy, &amount);
+        if !matches!(
+            StrategyClient::new(env, &strategy).try_invest(&amount),
+            Ok(Ok(()))
+        ) {
+            return Err(VaultError::StrategyFailed);
+        }
+    }
+    Ok(())
+}
+
+/// Harvest every strategy, emitting one event per strategy. Returns the
+/// total profit received.
+pub fn harvest(env: &Env, asset: &Address) -> Result<i128, VaultError> {
+    let token = token::Client::new(env, asset);
+    let vault = env.current_contract_address();
+    let mut total = 0;
+    for allocation in allocations(env).iter() {
+        let before = token.balance(&vault);
+        let client = StrategyClient::new(env, &allocation.strategy);
+        if !matches!(client.try_harvest(), Ok(Ok(_))) {
+            return Err(VaultError::StrategyFailed);
+        }
+        let profit = token.balance(&vault) - before;
+        total += profit;
+
+        EventEmitter::harvested(
+            env,
+            HarvestEvent {
+                strategy: allocation.strategy.clone(),
+                asset: asset.clone(),
+                profit,
+                strategy_assets: report(env, &allocation.strategy)?,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(total)
+}
+
+/// Make sure at least `amount` is idle in the vault, divesting strategies
+/// in allocation order as needed
+pub fn free(env: &Env, asset: &Address, amount: i128) -> Result<(), VaultError> {
+    let token = token::Client::new(env, asset);
+    let vault = env.current_contract_address();
+    for allocation in allocations(env).iter() {
+        let idle = token.balance(&vault);
+        if idle >= amount {
+            return Ok(());
+        }
+        let available = report(env, &allocation.strategy)?;
+        if available > 0 {
+            divest(
+                env,
+                asset,
+                &allocation.strategy,
+                (amount - idle).min(available),
+            )?;
+        }
+    }
+    if token.balance(&vault) < amount {
+        return Err(VaultError::StrategyFailed);
+    }
+    Ok(())
+}
+
+fn report(env: &Env, strategy: &Address) -> Result<i128, VaultError> {
+    match StrategyClient::new(env, strategy).try_report() {
+        Ok(Ok(assets)) => Ok(assets),
+        _ => Err(VaultError::StrategyFailed),
+    }
+}
+
+fn divest(
+    env: &Env,
+    asset: &Address,
+    strategy: &Address,
+    amount: i128,
+) -> Result<i128, VaultError> {
+    if amount <= 0 {
+        return Ok(0);
+    }
+    let token = token::Client::new(env, asset);
+    let vault = env.current_contract_address();
+    let before = token.balance(&vault);
+    if !matches!(
+        StrategyClient::new(env, strategy).try_divest(&amount),
+        Ok(Ok(_))
+    ) {
+        return Err(VaultError::StrategyFailed);
+    }
+    Ok(token.balance(&vault) - before)
+}
@@ -0,0 +1,276 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use shared::events::{topics, HarvestEvent};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{contract, contractimpl, symbol_short, Symbol};
+use test_utils::{assert_standard_event, payload, standard_events};
+
+const VAULT: Symbol = symbol_short!("vault");
+const TOKEN: Symbol = symbol_short!("token");
+const PRINCIPAL: Symbol = symbol_short!("principal");
+
+/// Strategy that holds its principal and treats any surplus balance as
+/// yield
+#[contract]
+pub struct MockStrategy;
+
+#[contractimpl]
+impl MockStrategy {
+    pub fn init(env: Env, vault: Address, token: Address) {
+        env.storage().instance().set(&VAULT, &vault);
+        env.storage().instance().set(&TOKEN, &token);
+    }
+
+    pub fn invest(env: Env, amount: i128) {
+        Self::vault(&env).require_auth();
+        let principal = Self::report(env.clone()) + amount;
+        env.storage().instance().set(&PRINCIPAL, &principal);
+    }
+
+    pub fn divest(env: Env, amount: i128) -> i128 {
+        let vault = Self::vault(&env);
+        vault.require_auth();
+        let principal = Self::report(env.clone());
+        let amount = amount.min(principal);
+        env.storage()
+            .instance()
+            .set(&PRINCIPAL, &(principal - amount));
+        Self::token(&env).transfer(&env.current_contract_address(), &vault, &amount);
+        amount
+    }
+
+    pub fn harvest(env: Env) -> i128 {
+        let token = Self::token(&env);
+        let profit = token.balance(&env.current_contract_address()) - Self::report(env.clone());
+        token.transfer(&env.current_contract_address(), &Self::vault(&env), &profit);
+        profit
+    }
+
+    pub fn report(env: Env) -> i128 {
+        env.storage().instance().get(&PRINCIPAL).unwrap_or(0)
+    }
+
+    fn vault(env: &Env) -> Address {
+        env.storage().instance().get(&VAULT).unwrap()
+    }
+
+    fn token(env: &Env) -> TokenClient<'static> {
+        let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+        TokenClient::new(env, &token)
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: VaultContractClient<'static>,
+    token: TokenClient<'static>,
+    minter: StellarAssetClient<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+    let minter = StellarAssetClient::new(&env, &token_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    minter.mint(&alice, &10_000);
+    minter.mint(&bob, &10_000);
+
+    let contract_id = env.register_contract(None, VaultContract);
+    let client = VaultContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &token_id);
+
+    Setup {
+        env,
+        client,
+        token,
+        minter,
+        admin,
+        alice,
+        bob,
+    }
+}
+
+#[test]
+fn test_first_deposit_mints_one_share_per_asset() {
+    let s = setup();
+    assert_eq!(s.client.deposit(&s.alice, &1_000), 1_000);
+    assert_eq!(s.client.shares_of(&s.alice), 1_000);
+    assert_eq!(s.client.total_shares(), 1_000);
+    assert_eq!(s.client.total_assets(), 1_000);
+    assert_eq!(s.token.balance(&s.alice), 9_000);
+
+    let captured = assert_standard_event(&s.env, topics::VAULT_DEPOSIT, |_| true);
+    let event: VaultEvent = payload(&captured);
+    assert_eq!(event.user, s.alice);
+    assert_eq!(event.assets, 1_000);
+    assert_eq!(event.shares, 1_000);
+    assert_eq!(event.total_assets, 1_000);
+    assert_eq!(event.total_shares, 1_000);
+}
+
+#[test]
+fn test_yield_raises_share_price() {
+    let s = setup();
+    s.client.deposit(&s.alice, &1_000);
+    // Strategy profits land in the vault
+    s.minter.mint(&s.client.address, &1_000);
+
+    assert_eq!(s.client.convert_to_assets(&1_000), 1_999);
+    // Later depositors buy in at the higher price
+    assert_eq!(s.client.deposit(&s.bob, &1_000), 500);
+
+    let assets = s.client.redeem(&s.alice, &1_000);
+    assert_eq!(assets, 1_999);
+    assert_eq!(s.client.shares_of(&s.alice), 0);
+    let captured = assert_standard_event(&s.env, topics::VAULT_WITHDRAW, |_| true);
+    let event: VaultEvent = payload(&captured);
+    assert_eq!(event.shares, 1_000);
+    assert_eq!(event.assets, 1_999);
+    assert_eq!(event.total_shares, 500);
+}
+
+#[test]
+fn test_withdraw_burns_shares_rounded_up() {
+    let s = setup();
+    s.client.deposit(&s.alice, &1_000);
+    s.minter.mint(&s.client.address, &500);
+
+    // 100 assets are worth 66.6 shares, so 67 are burned
+    assert_eq!(s.client.withdraw(&s.alice, &100), 67);
+    assert_eq!(s.client.shares_of(&s.alice), 933);
+    assert_eq!(s.token.balance(&s.alice), 9_100);
+}
+
+#[test]
+fn test_cannot_exit_more_than_owned() {
+    let s = setup();
+    s.client.deposit(&s.alice, &1_000);
+    assert_eq!(
+        s.client.try_withdraw(&s.bob, &1),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    assert_eq!(
+        s.client.try_redeem(&s.alice, &1_001),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    assert_eq!(
+        s.client.try_deposit(&s.alice, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_donation_cannot_steal_small_deposit() {
+    let s = setup();
+    // Attacker seeds one share then donates to inflate its price
+    s.client.deposit(&s.alice, &1);
+    s.token.transfer(&s.alice, &s.client.address, &1_000);
+
+    assert_eq!(
+        s.client.try_deposit(&s.bob, &500),
+        Err(Ok(VaultError::ZeroShares))
+    );
+    assert_eq!(s.token.balance(&s.bob), 10_000);
+}
+
+fn add_strategy(s: &Setup, weight_bps: u32) -> MockStrategyClient<'static> {
+    let id = s.env.register_contract(None, MockStrategy);
+    let strategy = MockStrategyClient::new(&s.env, &id);
+    strategy.init(&s.client.address, &s.token.address);
+    s.client.set_strategy(&s.admin, &id, &weight_bps);
+    strategy
+}
+
+#[test]
+fn test_rebalance_allocates_by_weight() {
+    let s = setup();
+    let first = add_strategy(&s, 6_000);
+    let second = add_strategy(&s, 3_000);
+    s.client.deposit(&s.alice, &1_000);
+
+    s.client.rebalance();
+    assert_eq!(first.report(), 600);
+    assert_eq!(second.report(), 300);
+    assert_eq!(s.token.balance(&s.client.address), 100);
+    assert_eq!(s.client.total_assets(), 1_000);
+
+    // Shifting weight moves funds on the next rebalance
+    s.client.set_strategy(&s.admin, &first.address, &2_000);
+    s.client.set_strategy(&s.admin, &second.address, &7_000);
+    s.client.rebalance();
+    assert_eq!(first.report(), 200);
+    assert_eq!(second.report(), 700);
+}
+
+#[test]
+fn test_withdraw_divests_when_idle_is_short() {
+    let s = setup();
+    let strategy = add_strategy(&s, 9_000);
+    s.client.deposit(&s.alice, &1_000);
+    s.client.rebalance();
+
+    s.client.withdraw(&s.alice, &500);
+    assert_eq!(s.token.balance(&s.alice), 9_500);
+    assert_eq!(strategy.report(), 500);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_harvest_reports_each_strategy() {
+    let s = setup();
+    let first = add_strategy(&s, 5_000);
+    let second = add_strategy(&s, 5_000);
+    s.client.deposit(&s.alice, &1_000);
+    s.client.rebalance();
+
+    s.minter.mint(&first.address, &60);
+    s.minter.mint(&second.address, &30);
+    assert_eq!(s.client.harvest(), 90);
+    assert_eq!(s.client.total_assets(), 1_090);
+
+    let harvests: std::vec::Vec<HarvestEvent> = standard_events(&s.env)
+        .iter()
+        .filter(|captured| captured.event.topic == topics::HARVESTED)
+        .map(payload)
+        .collect();
+    assert_eq!(harvests.len(), 2);
+    assert_eq!(harvests[0].strategy, first.address);
+    assert_eq!(harvests[0].profit, 60);
+    assert_eq!(harvests[1].strategy, second.address);
+    assert_eq!(harvests[1].profit, 30);
+    assert_eq!(harvests[1].strategy_assets, 500);
+}
+
+#[test]
+fn test_strategy_weights_and_removal() {
+    let s = setup();
+    let strategy = add_strategy(&s, 8_000);
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_set_strategy(&s.admin, &other, &2_001),
+        Err(Ok(VaultError::InvalidWeight))
+    );
+    assert_eq!(
+        s.client.try_remove_strategy(&s.admin, &other),
+        Err(Ok(VaultError::StrategyNotFound))
+    );
+
+    s.client.deposit(&s.alice, &1_000);
+    s.client.rebalance();
+    assert_eq!(s.client.remove_strategy(&s.admin, &strategy.address), 800);
+    assert!(s.client.strategies().is_empty());
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+}
@@ -0,0 +1,229 @@
+//! Voting power history
+//!
+//! A lock of `amount` ending at `end` has voting power
+//! `amount * (end - t) / MAX_LOCK_TIME`, i.e. a line with slope
+//! `amount / MAX_LOCK_TIME`. Each user keeps a history of points
+//! `(bias, slope, ts)` written whenever their lock changes. The global
+//! supply is the sum of those lines: its history gets a point per week plus
+//! one per lock change, and the slope each lock stops contributing at its
+//! end is scheduled in `SlopeChange(end)`. Lock ends fall on week
+//! boundaries, so walking the supply line forward a week at a time is exact.
+//!
+//! Slopes and biases are kept scaled by `SLOPE_SCALE` so that small locks
+//! still decay smoothly.
+
+use crate::DataKey;
+use shared::voting::{MAX_LOCK_TIME, WEEK};
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Fixed-point scale applied to slopes and biases
+pub const SLOPE_SCALE: i128 = 1_000_000_000;
+/// Weeks walked in one pass; supply history stalls if nothing checkpoints
+/// for longer than this
+const MAX_WEEKS: u32 = 255;
+
+/// Tokens locked by one user
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LockedBalance {
+    pub amount: i128,
+    /// Lock expiry, on a week boundary
+    pub end: u64,
+}
+
+/// A line of voting power: `bias - slope * (t - ts)`, scaled
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Point {
+    pub bias: i128,
+    pub slope: i128,
+    pub ts: u64,
+}
+
+impl Point {
+    fn value_at(&self, t: u64) -> i128 {
+        let elapsed = t.saturating_sub(self.ts) as i128;
+        (self.bias - self.slope * elapsed).max(0) / SLOPE_SCALE
+    }
+}
+
+/// Record a change of `user`'s lock from `old` to `new`
+pub fn record(env: &Env, user: &Address, old: &LockedBalance, new: &LockedBalance) {
+    let now = env.ledger().timestamp();
+    let old_point = lock_point(old, now);
+    let new_point = lock_point(new, now);
+
+    let mut global = advance_global(env, now);
+    global.bias = (global.bias + new_point.bias - old_point.bias).max(0);
+    global.slope = (global.slope + new_point.slope - old_point.slope).max(0);
+    push_global(env, &global);
+
+    // Reschedule when this lock stops decaying the supply
+    if old.end > now {
+        let mut change = slope_change(env, old.end) - old_point.slope;
+        if new.end == old.end {
+            change += new_point.slope;
+        }
+        set_slope_change(env, old.end, change);
+    }
+    if new.end > now && new.end != old.end {
+        let change = slope_change(env, new.end) + new_point.slope;
+        set_slope_change(env, new.end, change);
+    }
+
+    push_user(env, user, &new_point);
+}
+
+/// Bring the supply history up to now
+pub fn checkpoint(env: &Env) {
+    let now = env.ledger().timestamp();
+    let global = advance_global(env, now);
+    push_global(env, &global);
+}
+
+pub fn balance_at(env: &Env, user: &Address, t: u64) -> i128 {
+    let count: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UserEpoch(user.clone()))
+        .unwrap_or(0);
+    let epoch = search(count, t, |epoch| user_point(env, user, epoch).ts);
+    if epoch == 0 {
+        return 0;
+    }
+    user_point(env, user, epoch).value_at(t)
+}
+
+pub fn supply_at(env: &Env, t: u64) -> i128 {
+    let epoch = search(global_epoch(env), t, |epoch| global_point(env, epoch).ts);
+    if epoch == 0 {
+        return 0;
+    }
+    let point = walk(env, global_point(env, epoch), t, |_| {});
+    point.bias / SLOPE_SCALE
+}
+
+fn lock_point(lock: &LockedBalance, now: u64) -> Point {
+    if lock.amount <= 0 || lock.end <= now {
+        return Point {
+            ts: now,
+            ..Point::default()
+        };
+    }
+    let slope = lock.amount * SLOPE_SCALE / MAX_LOCK_TIME as i128;
+    Point {
+        bias: slope * (lock.end - now) as i128,
+        slope,
+        ts: now,
+    }
+}
+
+/// Walk `point` forward to `t`, applying scheduled slope changes at each
+/// week boundary and handing every intermediate weekly point to `on_week`
+fn walk<F: FnMut(&Point)>(env: &Env, mut point: Point, t: u64, mut on_week: F) -> Point {
+    let mut week = point.ts / WEEK * WEEK;
+    for _ in 0..MAX_WEEKS {
+        week += WEEK;
+        let step = week.min(t);
+        point.bias = (point.bias - point.slope * (step - point.ts) as i128).max(0);
+        if step == week {
+            point.slope = (point.slope - slope_change(env, week)).max(0);
+        }
+        point.ts = step;
+        if step == t {
+            break;
+        }
+        on_week(&point);
+    }
+    point
+}
+
+/// Latest supply point moved to `now`, storing the weekly points crossed
+fn advance_global(env: &Env, now: u64) -> Point {
+    let epoch = global_epoch(env);
+    if epoch == 0 {
+        return Point {
+            ts: now,
+            ..Point::default()
+        };
+    }
+    let last = global_point(env, epoch);
+    if last.ts >= now {
+        return last;
+    }
+    walk(env, last, now, |point| push_global(env, point))
+}
+
+/// Binary search for the last of `count` points at or before `t`; 0 when
+/// there is none
+fn search<F: Fn(u32) -> u64>(count: u32, t: u64, ts_of: F) -> u32 {
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if ts_of(mid) <= t {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+fn global_epoch(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::GlobalEpoch)
+        .unwrap_or(0)
+}
+
+fn global_point(env: &Env, epoch: u32) -> Point {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GlobalPoint(epoch))
+        .unwrap_or_default()
+}
+
+/// Append a supply point, replacing the latest one if it has the same
+/// timestamp
+fn push_global(env: &Env, point: &Point) {
+    let mut epoch = global_epoch(env);
+    if epoch == 0 || global_point(env, epoch).ts < point.ts {
+        epoch += 1;
+        env.storage().instance().set(&DataKey::GlobalEpoch, &epoch);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::GlobalPoint(epoch), point);
+}
+
+fn user_point(env: &Env, user: &Address, epoch: u32) -> Point {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserPoint(user.clone(), epoch))
+        .unwrap_or_default()
+}
+
+fn push_user(env: &Env, user: &Address, point: &Point) {
+    let key = DataKey::UserEpoch(user.clone());
+    let mut epoch: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    if epoch == 0 || user_point(env, user, epoch).ts < point.ts {
+        epoch += 1;
+        env.storage().persistent().set(&key, &epoch);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserPoint(user.clone(), epoch), point);
+}
+
+fn slope_change(env: &Env, t: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SlopeChange(t))
+        .unwrap_or(0)
+}
+
+fn set_slope_change(env: &Env, t: u64, change: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SlopeChange(t), &change);
+}
@@ -0,0 +1,243 @@
+//! Vote-escrowed governance token
+//!
+//! Users lock the governance token for up to four years and receive voting
+//! power proportional to amount and remaining lock time, decaying linearly
+//! to zero at expiry. Locks can be topped up or extended but only withdrawn
+//! once expired. Voting power is not transferable; consumers read it at a
+//! snapshot timestamp through `shared::voting::VotingPowerClient`.
+
+#![no_std]
+
+mod checkpoint;
+
+pub use checkpoint::{LockedBalance, Point, SLOPE_SCALE};
+
+use shared::events::{EventEmitter, VeLockEvent};
+use shared::voting::{MAX_LOCK_TIME, WEEK};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VeError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidUnlockTime = 4,
+    LockTooLong = 5,
+    LockExists = 6,
+    NoLock = 7,
+    LockExpired = 8,
+    LockNotExpired = 9,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Token,
+    Locked(Address),
+    GlobalEpoch,
+    GlobalPoint(u32),
+    UserEpoch(Address),
+    UserPoint(Address, u32),
+    SlopeChange(u64),
+}
+
+#[contract]
+pub struct VotingEscrow;
+
+#[contractimpl]
+impl VotingEscrow {
+    /// `token` is the governance token being locked
+    pub fn initialize(env: Env, token: Address) -> Result<(), VeError> {
+        if env.storage().instance().has(&DataKey::Token) {
+            return Err(VeError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    /// Lock `amount` until `unlock_time`, rounded down to a week
+    pub fn create_lock(
+        env: Env,
+        user: Address,
+        amount: i128,
+        unlock_time: u64,
+    ) -> Result<(), VeError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(VeError::InvalidAmount);
+        }
+        let old = Self::locked(env.clone(), user.clone());
+        if old.amount > 0 {
+            return Err(VeError::LockExists);
+        }
+        let end = valid_end(&env, unlock_time)?;
+        let new = LockedBalance { amount, end };
+        deposit(&env, &user, &old, &new, amount)
+    }
+
+    /// Add `amount` to an unexpired lock without changing its end
+    pub fn increase_amount(env: Env, user: Address, amount: i128) -> Result<(), VeError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(VeError::InvalidAmount);
+        }
+        let old = active_lock(&env, &user)?;
+        let new = LockedBalance {
+            amount: old.amount + amount,
+            end: old.end,
+        };
+        deposit(&env, &user, &old, &new, amount)
+    }
+
+    /// Push an unexpired lock's end out to `unlock_time`, rounded down to a
+    /// week
+    pub fn increase_unlock_time(env: Env, user: Address, unlock_time: u64) -> Result<(), VeError> {
+        user.require_auth();
+        let old = active_lock(&env, &user)?;
+        let end = valid_end(&env, unlock_time)?;
+        if end <= old.end {
+            return Err(VeError::InvalidUnlockTime);
+        }
+        let new = LockedBalance {
+            amount: old.amount,
+            end,
+        };
+        deposit(&env, &user, &old, &new, 0)
+    }
+
+    /// Return an expired lock's tokens. Returns the amount withdrawn.
+    pub fn withdraw(env: Env, user: Address) -> Result<i128, VeError> {
+        user.require_auth();
+        let old = Self::locked(env.clone(), user.clone());
+        if old.amount <= 0 {
+            return Err(VeError::NoLock);
+        }
+        if env.ledger().timestamp() < old.end {
+            return Err(VeError::LockNotExpired);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Locked(user.clone()));
+        checkpoint::record(&env, &user, &old, &LockedBalance::default());
+        token::Client::new(&env, &locked_token(&env)?).transfer(
+            &env.current_contract_address(),
+            &user,
+            &old.amount,
+        );
+
+        EventEmitter::ve_withdrawn(
+            &env,
+            VeLockEvent {
+                user,
+                amount: old.amount,
+                locked: 0,
+                unlock_time: old.end,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(old.amount)
+    }
+
+    /// Record the weekly supply history up to now. Lock changes do this
+    /// implicitly; calling it keeps the history current during quiet weeks.
+    pub fn checkpoint(env: Env) {
+        checkpoint::checkpoint(&env);
+    }
+
+    // ---- views ----
+
+    pub fn locked(env: Env, user: Address) -> LockedBalance {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Locked(user))
+            .unwrap_or_default()
+    }
+
+    /// Current voting power of `user`
+    pub fn balance_of(env: Env, user: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        checkpoint::balance_at(&env, &user, now)
+    }
+
+    /// Voting power of `user` at `timestamp`
+    pub fn balance_of_at(env: Env, user: Address, timestamp: u64) -> i128 {
+        checkpoint::balance_at(&env, &user, timestamp)
+    }
+
+    /// Current sum of all voting power
+    pub fn total_supply(env: Env) -> i128 {
+        let now = env.ledger().timestamp();
+        checkpoint::supply_at(&env, now)
+    }
+
+    /// Sum of all voting power at `timestamp`
+    pub fn total_supply_at(env: Env, timestamp: u64) -> i128 {
+        checkpoint::supply_at(&env, timestamp)
+    }
+}
+
+fn locked_token(env: &Env) -> Result<Address, VeError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(VeError::NotInitialized)
+}
+
+fn active_lock(env: &Env, user: &Address) -> Result<LockedBalance, VeError> {
+    let lock = VotingEscrow::locked(env.clone(), user.clone());
+    if lock.amount <= 0 {
+        return Err(VeError::NoLock);
+    }
+    if lock.end <= env.ledger().timestamp() {
+        return Err(VeError::LockExpired);
+    }
+    Ok(lock)
+}
+
+fn valid_end(env: &Env, unlock_time: u64) -> Result<u64, VeError> {
+    let now = env.ledger().timestamp();
+    let end = unlock_time / WEEK * WEEK;
+    if end <= now {
+        return Err(VeError::InvalidUnlockTime);
+    }
+    if end > now + MAX_LOCK_TIME {
+        return Err(VeError::LockTooLong);
+    }
+    Ok(end)
+}
+
+/// Move the lock from `old` to `new`, pulling in `amount` new tokens
+fn deposit(
+    env: &Env,
+    user: &Address,
+    old: &LockedBalance,
+    new: &LockedBalance,
+    amount: i128,
+) -> Result<(), VeError> {
+    let token = locked_token(env)?;
+    if amount > 0 {
+        token::Client::new(env, &token).transfer(user, &env.current_contract_address(), &amount);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Locked(user.clone()), new);
+    checkpoint::record(env, user, old, new);
+
+    EventEmitter::ve_locked(
+        env,
+        VeLockEvent {
+            user: user.clone(),
+            amount,
+            locked: new.amount,
+            unlock_time: new.end,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::topics;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use test_utils::{assert_standard_event, payload};
+
+const START: u64 = 100 * WEEK;
+const AMOUNT: i128 = 10_000_000_000;
+
+struct Setup {
+    env: Env,
+    client: VotingEscrowClient<'static>,
+    token: TokenClient<'static>,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(token_admin);
+    let token = TokenClient::new(&env, &token_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&alice, &(10 * AMOUNT));
+    StellarAssetClient::new(&env, &token_id).mint(&bob, &(10 * AMOUNT));
+
+    let contract_id = env.register_contract(None, VotingEscrow);
+    let client = VotingEscrowClient::new(&env, &contract_id);
+    client.initialize(&token_id);
+
+    Setup {
+        env,
+        client,
+        token,
+        alice,
+        bob,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+/// Voting power of `amount` locked for `remaining` seconds
+fn expected(amount: i128, remaining: u64) -> i128 {
+    amount * SLOPE_SCALE / MAX_LOCK_TIME as i128 * remaining as i128 / SLOPE_SCALE
+}
+
+#[test]
+fn test_power_scales_with_lock_time_and_decays() {
+    let s = setup();
+    let end = START + 104 * WEEK;
+    s.client.create_lock(&s.alice, &AMOUNT, &end);
+    assert_eq!(s.token.balance(&s.alice), 9 * AMOUNT);
+    assert_eq!(s.client.balance_of(&s.alice), expected(AMOUNT, 104 * WEEK));
+    // Two years is roughly half of the four-year maximum
+    assert!(s.client.balance_of(&s.alice) * 2 < AMOUNT);
+    assert!(s.client.balance_of(&s.alice) * 2 > AMOUNT * 99 / 100);
+
+    advance(&s.env, 52 * WEEK);
+    assert_eq!(s.client.balance_of(&s.alice), expected(AMOUNT, 52 * WEEK));
+
+    let captured = assert_standard_event(&s.env, topics::VE_LOCKED, |_| true);
+    let event: VeLockEvent = payload(&captured);
+    assert_eq!(event.locked, AMOUNT);
+    assert_eq!(event.unlock_time, end);
+}
+
+#[test]
+fn test_withdraw_only_after_expiry() {
+    let s = setup();
+    s.client.create_lock(&s.alice, &AMOUNT, &(START + 2 * WEEK));
+    assert_eq!(
+        s.client.try_withdraw(&s.alice),
+        Err(Ok(VeError::LockNotExpired))
+    );
+
+    advance(&s.env, 2 * WEEK);
+    assert_eq!(s.client.balance_of(&s.alice), 0);
+    assert_eq!(s.client.total_supply(), 0);
+    assert_eq!(s.client.withdraw(&s.alice), AMOUNT);
+    assert_eq!(s.token.balance(&s.alice), 10 * AMOUNT);
+    assert_eq!(s.client.locked(&s.alice), LockedBalance::default());
+    assert_standard_event(&s.env, topics::VE_WITHDRAWN, |_| true);
+}
+
+#[test]
+fn test_total_supply_is_sum_of_balances() {
+    let s = setup();
+    s.client
+        .create_lock(&s.alice, &AMOUNT, &(START + 10 * WEEK));
+    advance(&s.env, WEEK / 2);
+    s.client
+        .create_lock(&s.bob, &(3 * AMOUNT), &(START + 50 * WEEK));
+
+    for _ in 0..4 {
+        let sum = s.client.balance_of(&s.alice) + s.client.balance_of(&s.bob);
+        assert!((s.client.total_supply() - sum).abs() <= 2);
+        advance(&s.env, 4 * WEEK);
+    }
+    // Alice's lock has expired and only Bob's power remains
+    assert_eq!(s.client.balance_of(&s.alice), 0);
+    assert!((s.client.total_supply() - s.client.balance_of(&s.bob)).abs() <= 2);
+}
+
+#[test]
+fn test_history_is_queryable_at_past_timestamps() {
+    let s = setup();
+    let end = START + 20 * WEEK;
+    s.client.create_lock(&s.alice, &AMOUNT, &end);
+    let before = s.client.balance_of(&s.alice);
+
+    advance(&s.env, WEEK);
+    s.client.increase_amount(&s.alice, &AMOUNT);
+    advance(&s.env, WEEK);
+    s.client
+        .increase_unlock_time(&s.alice, &(START + 40 * WEEK));
+    assert_eq!(
+        s.client.locked(&s.alice),
+        LockedBalance {
+            amount: 2 * AMOUNT,
+            end: START + 40 * WEEK,
+        }
+    );
+
+    assert_eq!(s.client.balance_of_at(&s.alice, &START), before);
+    assert_eq!(
+        s.client.balance_of_at(&s.alice, &(START + WEEK)),
+        expected(2 * AMOUNT, 19 * WEEK)
+    );
+    assert_eq!(s.client.balance_of_at(&s.alice, &(START - 1)), 0);
+    assert!((s.client.total_supply_at(&START) - before).abs() <= 1);
+    assert_eq!(s.client.total_supply_at(&(START - 1)), 0);
+}
+
+#[test]
+fn test_lock_validation() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_create_lock(&s.alice, &AMOUNT, &(START + MAX_LOCK_TIME + WEEK)),
+        Err(Ok(VeError::LockTooLong))
+    );
+    assert_eq!(
+        s.client.try_create_lock(&s.alice, &AMOUNT, &START),
+        Err(Ok(VeError::InvalidUnlockTime))
+    );
+    assert_eq!(
+        s.client.try_increase_amount(&s.alice, &AMOUNT),
+        Err(Ok(VeError::NoLock))
+    );
+
+    s.client.create_lock(&s.alice, &AMOUNT, &(START + 4 * WEEK));
+    assert_eq!(
+        s.client
+            .try_create_lock(&s.alice, &AMOUNT, &(START + 4 * WEEK)),
+        Err(Ok(VeError::LockExists))
+    );
+    assert_eq!(
+        s.client
+            .try_increase_unlock_time(&s.alice, &(START + 3 * WEEK)),
+        Err(Ok(VeError::InvalidUnlockTime))
+    );
+
+    advance(&s.env, 4 * WEEK);
+    assert_eq!(
+        s.client.try_increase_amount(&s.alice, &AMOUNT),
+        Err(Ok(VeError::LockExpired))
+    );
+}
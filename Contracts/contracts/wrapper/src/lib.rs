@@ -0,0 +1,252 @@
+//! Wrapped native XLM
+//!
+//! Holds native XLM (through its Stellar Asset Contract) and issues wXLM 1:1
+//! with the same interface as the Stellara token: `balance`, `transfer`,
+//! `approve`, `allowance`, `transfer_from` and the metadata getters. AMM
+//! pools, staking and any other code written against the Stellara token can
+//! therefore hold XLM without special-casing it.
+
+#![no_std]
+
+use shared::events::{ApprovalEvent, EventEmitter, TransferEvent, WrapEvent};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, String,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum WrapperError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    InsufficientAllowance = 5,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Underlying,
+    TotalSupply,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contract]
+pub struct WrapperContract;
+
+#[contractimpl]
+impl WrapperContract {
+    /// Bind the wrapper to the native asset contract
+    pub fn initialize(env: Env, underlying: Address) -> Result<(), WrapperError> {
+        if env.storage().instance().has(&DataKey::Underlying) {
+            return Err(WrapperError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Underlying, &underlying);
+        Ok(())
+    }
+
+    /// Wrap `amount` of XLM from `from` into wXLM
+    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<(), WrapperError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(WrapperError::InvalidAmount);
+        }
+        let underlying = underlying(&env)?;
+        token::Client::new(&env, &underlying).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        set_balance(&env, &from, balance_of(&env, &from) + amount);
+        set_total_supply(&env, Self::total_supply(env.clone()) + amount);
+
+        EventEmitter::wrapped(
+            &env,
+            WrapEvent {
+                account: from,
+                underlying,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Burn `amount` of wXLM from `from` and return the XLM
+    pub fn withdraw(env: Env, from: Address, amount: i128) -> Result<(), WrapperError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(WrapperError::InvalidAmount);
+        }
+        let balance = balance_of(&env, &from);
+        if balance < amount {
+            return Err(WrapperError::InsufficientBalance);
+        }
+        let underlying = underlying(&env)?;
+
+        set_balance(&env, &from, balance - amount);
+        set_total_supply(&env, Self::total_supply(env.clone()) - amount);
+        token::Client::new(&env, &underlying).transfer(
+            &env.current_contract_address(),
+            &from,
+            &amount,
+        );
+
+        EventEmitter::unwrapped(
+            &env,
+            WrapEvent {
+                account: from,
+                underlying,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        balance_of(&env, &id)
+    }
+
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), WrapperError> {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount)
+    }
+
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), WrapperError> {
+        owner.require_auth();
+        if amount < 0 {
+            return Err(WrapperError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(owner.clone(), spender.clone()), &amount);
+        EventEmitter::approval(
+            &env,
+            ApprovalEvent {
+                owner,
+                spender,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+            false,
+        );
+        Ok(())
+    }
+
+    pub fn allowance(env: Env, owner: Address, spender: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowance(owner, spender))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), WrapperError> {
+        spender.require_auth();
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            return Err(WrapperError::InsufficientAllowance);
+        }
+        move_balance(&env, &from, &to, amount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(from, spender), &(allowance - amount));
+        Ok(())
+    }
+
+    /// wXLM in circulation; always equal to the XLM held by the wrapper
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    pub fn underlying(env: Env) -> Result<Address, WrapperError> {
+        underlying(&env)
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Wrapped XLM")
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "wXLM")
+    }
+
+    pub fn decimals(env: Env) -> Result<u32, WrapperError> {
+        Ok(token::Client::new(&env, &underlying(&env)?).decimals())
+    }
+}
+
+fn underlying(env: &Env) -> Result<Address, WrapperError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Underlying)
+        .ok_or(WrapperError::NotInitialized)
+}
+
+fn balance_of(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+fn set_balance(env: &Env, id: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &amount);
+}
+
+fn set_total_supply(env: &Env, amount: i128) {
+    env.storage().instance().set(&DataKey::TotalSupply, &amount);
+}
+
+fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), WrapperError> {
+    if amount <= 0 {
+        return Err(WrapperError::InvalidAmount);
+    }
+    let from_balance = balance_of(env, from);
+    if from_balance < amount {
+        return Err(WrapperError::InsufficientBalance);
+    }
+    set_balance(env, from, from_balance - amount);
+    set_balance(env, to, balance_of(env, to) + amount);
+
+    EventEmitter::transfer(
+        env,
+        TransferEvent {
+            from: from.clone(),
+            to: to.clone(),
+            token: env.current_contract_address(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
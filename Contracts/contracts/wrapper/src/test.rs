@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use super::*;
+use shared::events::{topics, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, Symbol, TryFromVal,
+};
+
+struct Setup {
+    env: Env,
+    client: WrapperContractClient<'static>,
+    xlm: TokenClient<'static>,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Stand-in for the native asset contract
+    let issuer = Address::generate(&env);
+    let xlm_id = env.register_stellar_asset_contract(issuer);
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &xlm_id).mint(&user, &1_000);
+
+    let contract_id = env.register_contract(None, WrapperContract);
+    let client = WrapperContractClient::new(&env, &contract_id);
+    client.initialize(&xlm_id);
+
+    Setup {
+        client,
+        xlm: TokenClient::new(&env, &xlm_id),
+        env,
+        user,
+    }
+}
+
+#[test]
+fn test_wrap_and_unwrap() {
+    let s = setup();
+    s.client.deposit(&s.user, &600);
+    assert_eq!(s.client.balance(&s.user), 600);
+    assert_eq!(s.client.total_supply(), 600);
+    assert_eq!(s.xlm.balance(&s.client.address), 600);
+    assert_eq!(s.xlm.balance(&s.user), 400);
+
+    s.client.withdraw(&s.user, &250);
+    assert_eq!(s.client.balance(&s.user), 350);
+    assert_eq!(s.client.total_supply(), 350);
+    assert_eq!(s.xlm.balance(&s.user), 650);
+
+    let result = s.client.try_withdraw(&s.user, &351);
+    assert_eq!(result, Err(Ok(WrapperError::InsufficientBalance)));
+}
+
+#[test]
+fn test_token_interface() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    s.client.deposit(&s.user, &500);
+
+    s.client.transfer(&s.user, &other, &100);
+    s.client.approve(&s.user, &spender, &200);
+    s.client.transfer_from(&spender, &s.user, &other, &150);
+    assert_eq!(s.client.balance(&other), 250);
+    assert_eq!(s.client.allowance(&s.user, &spender), 50);
+
+    // The generic token client used by the AMM works against the wrapper
+    let as_token = TokenClient::new(&s.env, &s.client.address);
+    as_token.transfer(&other, &s.user, &50);
+    assert_eq!(as_token.balance(&s.user), 300);
+    assert_eq!(as_token.decimals(), 7);
+
+    // The other account can unwrap what it received
+    s.client.withdraw(&other, &200);
+    assert_eq!(s.xlm.balance(&other), 200);
+}
+
+#[test]
+fn test_wrap_emits_standard_event() {
+    let s = setup();
+    s.client.deposit(&s.user, &600);
+
+    let prefix = Symbol::new(&s.env, EVENT_PREFIX);
+    let event = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == s.client.address
+                && Symbol::try_from_val(&s.env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(&s.env, &data).unwrap())
+        .last()
+        .unwrap();
+    assert_eq!(event.topic, topics::WRAPPED);
+    assert_eq!(event.user_address, s.user);
+}
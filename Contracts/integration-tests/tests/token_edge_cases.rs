@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+//! Protocol contracts against the misbehaving tokens from `mocks`
+
+use mocks::{FaultyToken, FaultyTokenClient, MockToken, MockTokenClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use stellara_staking_rewards::{StakingRewardsContract, StakingRewardsContractClient};
+use subscriptions::{SubscriptionBilling, SubscriptionBillingClient, SubscriptionStatus};
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+
+#[test]
+fn test_staking_credits_fee_on_transfer_stakes_it_never_received() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let stake_id = env.register_contract(None, FaultyToken);
+    let stake_token = FaultyTokenClient::new(&env, &stake_id);
+    let reward_id = env.register_contract(None, MockToken);
+    stake_token.mint(&user, &100_000);
+
+    let staking_id = env.register_contract(None, StakingRewardsContract);
+    let staking = StakingRewardsContractClient::new(&env, &staking_id);
+    staking.initialize(&admin, &stake_id, &reward_id);
+    MockTokenClient::new(&env, &reward_id).mint(&staking_id, &1_000_000);
+
+    // The contract records the amount sent, not the amount received, so
+    // the last staker out would find the pool short
+    stake_token.set_fee_bps(&100);
+    staking.stake(&user, &10_000, &0);
+    assert_eq!(staking.staked_balance(&user), 10_000);
+    assert_eq!(stake_token.balance(&staking_id), 9_900);
+}
+
+#[test]
+fn test_subscription_charge_survives_a_reverting_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let merchant = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    let token_id = env.register_contract(None, FaultyToken);
+    let token = FaultyTokenClient::new(&env, &token_id);
+    token.mint(&subscriber, &1_000);
+
+    let billing =
+        SubscriptionBillingClient::new(&env, &env.register_contract(None, SubscriptionBilling));
+    let plan_id = billing.create_plan(&merchant, &token_id, &100, &MONTH, &MONTH, &3);
+    let id = billing.subscribe(&subscriber, &plan_id, &1_000, &100_000);
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH);
+    token.set_reverting(&true);
+    assert!(!billing.charge(&id));
+    let subscription = billing.get_subscription(&id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+    assert_eq!(subscription.charged, 100);
+
+    token.set_reverting(&false);
+    env.ledger()
+        .with_mut(|li| li.timestamp = subscription.retry_at);
+    assert!(billing.charge(&id));
+    assert_eq!(token.balance(&merchant), 200);
+}
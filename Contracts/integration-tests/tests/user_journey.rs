@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+//! End-to-end journey through the token, staking, governance and treasury
+//! contracts deployed together in one env
+//!
+//! A holder is minted tokens, stakes them, votes with the stake on a grant
+//! proposal, the grant is paid out by the treasury once the vote passes,
+//! and the holder claims and unstakes. Every step's balances are checked,
+//! and so is the exact order of the standard events the whole graph
+//! publishes.
+
+use governor::{Governor, GovernorClient};
+use shared::events::topics;
+use shared::governance::ProposalStatus;
+use shared::voting::VotingSource;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Symbol,
+};
+use stellara_staking_rewards::{StakingRewardsContract, StakingRewardsContractClient};
+use test_utils::standard_events;
+use token::{TokenContract, TokenContractClient, TokenMetadata};
+use treasury::{TreasuryContract, TreasuryContractClient};
+
+const DAY: u64 = 24 * 60 * 60;
+const START: u64 = 1_000 * DAY;
+const VOTING_PERIOD: u64 = 3 * DAY;
+
+struct Graph {
+    env: Env,
+    token: TokenContractClient<'static>,
+    staking: StakingRewardsContractClient<'static>,
+    governor: GovernorClient<'static>,
+    treasury: TreasuryContractClient<'static>,
+    admin: Address,
+    approvers: [Address; 2],
+    executor: Address,
+    alice: Address,
+    bob: Address,
+    grantee: Address,
+}
+
+fn deploy() -> Graph {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = START);
+
+    let admin = Address::generate(&env);
+    let approvers = [Address::generate(&env), Address::generate(&env)];
+    let executor = Address::generate(&env);
+
+    let token = TokenContractClient::new(&env, &env.register_contract(None, TokenContract));
+    token.initialize(
+        &admin,
+        &TokenMetadata {
+            name: String::from_str(&env, "Stellara"),
+            symbol: String::from_str(&env, "STLR"),
+            decimals: 7,
+            uri: String::from_str(&env, ""),
+        },
+        &false,
+    );
+
+    let staking = StakingRewardsContractClient::new(
+        &env,
+        &env.register_contract(None, StakingRewardsContract),
+    );
+    staking.initialize(&admin, &token.address, &token.address);
+
+    let governor = GovernorClient::new(&env, &env.register_contract(None, Governor));
+    governor.initialize(&admin, &VOTING_PERIOD, &1_000);
+    governor.register_asset(&admin, &VotingSource::Staking, &staking.address, &10_000);
+
+    let treasury =
+        TreasuryContractClient::new(&env, &env.register_contract(None, TreasuryContract));
+    treasury.initialize(
+        &admin,
+        &vec![&env, approvers[0].clone(), approvers[1].clone()],
+        &executor,
+        &2,
+        &DAY,
+        &(30 * DAY),
+    );
+
+    Graph {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        grantee: Address::generate(&env),
+        env,
+        token,
+        staking,
+        governor,
+        treasury,
+        admin,
+        approvers,
+        executor,
+    }
+}
+
+#[test]
+fn test_mint_stake_vote_execute_unstake() {
+    let g = deploy();
+
+    // Mint: holders, staking rewards and the treasury's float
+    g.token.mint(&g.alice, &10_000);
+    g.token.mint(&g.bob, &4_000);
+    g.token.mint(&g.staking.address, &100_000);
+    g.token.mint(&g.admin, &50_000);
+    g.treasury.deposit(&g.admin, &g.token.address, &50_000);
+    assert_eq!(g.treasury.balance(&g.token.address), 50_000);
+
+    // Stake into the 30-day pool
+    g.staking.stake(&g.alice, &10_000, &0);
+    g.staking.stake(&g.bob, &4_000, &0);
+    assert_eq!(g.token.balance(&g.alice), 0);
+    assert_eq!(g.token.balance(&g.staking.address), 114_000);
+
+    // Vote with the stake, as of the ledger before the proposal
+    g.env.ledger().with_mut(|li| li.sequence_number += 1);
+    let proposal_id = g.governor.propose(&g.alice, &symbol_short!("grant"), &0);
+    assert_eq!(g.governor.cast_vote(&g.alice, &proposal_id, &true), 10_000);
+    assert_eq!(g.governor.cast_vote(&g.bob, &proposal_id, &false), 4_000);
+    g.env.ledger().with_mut(|li| li.timestamp += VOTING_PERIOD);
+    assert_eq!(g.governor.finalize(&proposal_id), ProposalStatus::Approved);
+
+    // Execute the approved grant from the treasury
+    g.treasury
+        .set_budget(&g.admin, &g.grantee, &g.token.address, &20_000);
+    let spend_id = g.treasury.propose_spend(
+        &g.admin,
+        &g.grantee,
+        &g.token.address,
+        &20_000,
+        &symbol_short!("grant"),
+    );
+    g.treasury.approve_spend(&g.approvers[0], &spend_id);
+    g.treasury.approve_spend(&g.approvers[1], &spend_id);
+    g.env.ledger().with_mut(|li| li.timestamp += DAY);
+    g.treasury.execute_spend(&g.executor, &spend_id);
+    assert_eq!(g.token.balance(&g.grantee), 20_000);
+    assert_eq!(g.treasury.balance(&g.token.address), 30_000);
+
+    // Claim and unstake once the lockup is over
+    g.env
+        .ledger()
+        .with_mut(|li| li.timestamp = START + 30 * DAY);
+    let rewards = g.staking.claim(&g.alice);
+    assert!(rewards > 0);
+    assert_eq!(g.staking.unstake(&g.alice), 10_000);
+    assert_eq!(g.token.balance(&g.alice), 10_000 + rewards);
+    assert_eq!(g.staking.staked_balance(&g.alice), 0);
+
+    // One entry per standard event, in the order the graph published them
+    let token = g.token.address.clone();
+    let staking = g.staking.address.clone();
+    let governor = g.governor.address.clone();
+    let treasury = g.treasury.address.clone();
+    let expected = [
+        // deposit
+        (&treasury, topics::AUDIT),
+        (&token, topics::TRANSFER),
+        // two stakes
+        (&token, topics::TRANSFER),
+        (&token, topics::TRANSFER),
+        // two votes
+        (&governor, topics::VOTE_CAST),
+        (&governor, topics::VOTE_CAST),
+        // first calls of set_budget, propose_spend, approve_spend and
+        // execute_spend are audited; the second approval is not
+        (&treasury, topics::AUDIT),
+        (&treasury, topics::AUDIT),
+        (&treasury, topics::AUDIT),
+        (&treasury, topics::AUDIT),
+        // grant payout
+        (&token, topics::TRANSFER),
+        (&treasury, topics::TRANSFER),
+        (&treasury, topics::BUDGET_SPENT),
+        // claim
+        (&token, topics::TRANSFER),
+        (&staking, topics::REWARDS_CLAIMED),
+        // unstake
+        (&token, topics::TRANSFER),
+    ];
+    let published: std::vec::Vec<(Address, Symbol)> = standard_events(&g.env)
+        .into_iter()
+        .map(|captured| (captured.contract, captured.event.topic))
+        .collect();
+    let expected: std::vec::Vec<(Address, Symbol)> = expected
+        .into_iter()
+        .map(|(contract, topic)| (contract.clone(), topic))
+        .collect();
+    assert_eq!(published, expected);
+}
@@ -0,0 +1,83 @@
+//! Balance and allowance storage shared by the mock tokens
+
+use soroban_sdk::{contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+struct Allowance {
+    amount: i128,
+    expiration_ledger: u32,
+}
+
+pub fn balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+fn set_balance(env: &Env, id: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &amount);
+}
+
+pub fn credit(env: &Env, id: &Address, amount: i128) {
+    set_balance(env, id, balance(env, id) + amount);
+}
+
+/// Take `amount` from `id`, failing when the balance is short
+pub fn debit(env: &Env, id: &Address, amount: i128) -> bool {
+    let current = balance(env, id);
+    if amount < 0 || current < amount {
+        return false;
+    }
+    set_balance(env, id, current - amount);
+    true
+}
+
+pub fn allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+    match env
+        .storage()
+        .temporary()
+        .get::<_, Allowance>(&DataKey::Allowance(from.clone(), spender.clone()))
+    {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => {
+            allowance.amount
+        }
+        _ => 0,
+    }
+}
+
+pub fn approve(env: &Env, from: &Address, spender: &Address, amount: i128, expiration_ledger: u32) {
+    env.storage().temporary().set(
+        &DataKey::Allowance(from.clone(), spender.clone()),
+        &Allowance {
+            amount,
+            expiration_ledger,
+        },
+    );
+}
+
+/// Use up `amount` of the allowance `from` gave `spender`
+pub fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) -> bool {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match env.storage().temporary().get::<_, Allowance>(&key) {
+        Some(mut allowance)
+            if allowance.expiration_ledger >= env.ledger().sequence()
+                && allowance.amount >= amount =>
+        {
+            allowance.amount -= amount;
+            env.storage().temporary().set(&key, &allowance);
+            true
+        }
+        _ => amount == 0,
+    }
+}
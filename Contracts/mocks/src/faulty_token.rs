@@ -0,0 +1,139 @@
+//! Token with a transfer fee and a switch to reject transfers
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String};
+
+use crate::balances;
+
+const BPS: i128 = 10_000;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FaultyTokenError {
+    /// Transfers are switched off
+    Reverted = 1,
+    InsufficientBalance = 2,
+    InsufficientAllowance = 3,
+    InvalidFee = 4,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    FeeBps,
+    Reverting,
+}
+
+/// A token that misbehaves the way real tokens sometimes do. With a fee
+/// set, every transfer burns `fee_bps` of the amount, so the recipient
+/// gets less than was sent. With reverting on, every transfer fails with
+/// [`FaultyTokenError::Reverted`]. Otherwise it behaves like
+/// [`MockToken`](crate::MockToken).
+#[contract]
+pub struct FaultyToken;
+
+#[contractimpl]
+impl FaultyToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        balances::credit(&env, &to, amount);
+    }
+
+    /// Take `fee_bps` of every transfer from now on
+    pub fn set_fee_bps(env: Env, fee_bps: u32) -> Result<(), FaultyTokenError> {
+        if fee_bps as i128 > BPS {
+            return Err(FaultyTokenError::InvalidFee);
+        }
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Make every transfer fail until switched back
+    pub fn set_reverting(env: Env, reverting: bool) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Reverting, &reverting);
+    }
+
+    pub fn fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        balances::allowance(&env, &from, &spender)
+    }
+
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        from.require_auth();
+        balances::approve(&env, &from, &spender, amount, expiration_ledger);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        balances::balance(&env, &id)
+    }
+
+    /// Move `amount` out of `from`, of which `to` receives the amount less
+    /// the fee
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), FaultyTokenError> {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount)
+    }
+
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), FaultyTokenError> {
+        spender.require_auth();
+        if !balances::spend_allowance(&env, &from, &spender, amount) {
+            return Err(FaultyTokenError::InsufficientAllowance);
+        }
+        move_balance(&env, &from, &to, amount)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Faulty Token")
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "FAULTY")
+    }
+}
+
+fn move_balance(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), FaultyTokenError> {
+    if env
+        .storage()
+        .instance()
+        .get(&DataKey::Reverting)
+        .unwrap_or(false)
+    {
+        return Err(FaultyTokenError::Reverted);
+    }
+    if !balances::debit(env, from, amount) {
+        return Err(FaultyTokenError::InsufficientBalance);
+    }
+    let fee = amount * FaultyToken::fee_bps(env.clone()) as i128 / BPS;
+    balances::credit(env, to, amount - fee);
+    Ok(())
+}
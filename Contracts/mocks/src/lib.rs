@@ -0,0 +1,32 @@
+#![no_std]
+
+//! Mock contracts for integration tests
+//!
+//! Stand-ins for the external contracts the protocol talks to, so tests of
+//! the AMM, lending and staking contracts can set up edge cases directly:
+//!
+//! - [`MockToken`]: a token anyone can mint, implementing the standard
+//!   token interface
+//! - [`MockOracle`]: prices set by the test, including stale ones
+//! - [`FaultyToken`]: a token that takes a fee on every transfer or
+//!   rejects transfers outright, for contracts that assume the amount
+//!   sent is the amount received
+//!
+//! None of them check authorization beyond what the token interface
+//! requires; register them in a test env with auths mocked.
+//!
+//! A reverting transfer only comes back as an error to contracts that make
+//! it with a `try_` call; anywhere else it aborts a native test run, so
+//! point reverting tokens at code paths that are meant to survive them.
+
+mod balances;
+mod faulty_token;
+mod oracle;
+mod token;
+
+pub use faulty_token::{FaultyToken, FaultyTokenClient, FaultyTokenError};
+pub use oracle::{MockOracle, MockOracleClient, PriceData};
+pub use token::{MockToken, MockTokenClient};
+
+#[cfg(test)]
+mod test;
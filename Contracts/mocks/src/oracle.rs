@@ -0,0 +1,47 @@
+//! Price oracle the test sets directly
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    /// When the price was published
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Price(Address),
+}
+
+/// Serves whatever price the test last set for an asset. Prices can be
+/// backdated to exercise staleness checks, and removed to exercise
+/// missing feeds.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Publish `price` for `asset` at the current ledger time
+    pub fn set_price(env: Env, asset: Address, price: i128) {
+        let timestamp = env.ledger().timestamp();
+        Self::set_price_at(env, asset, price, timestamp);
+    }
+
+    /// Publish `price` for `asset` as of `timestamp`
+    pub fn set_price_at(env: Env, asset: Address, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Price(asset), &PriceData { price, timestamp });
+    }
+
+    pub fn remove_price(env: Env, asset: Address) {
+        env.storage().instance().remove(&DataKey::Price(asset));
+    }
+
+    pub fn price(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().instance().get(&DataKey::Price(asset))
+    }
+}
@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, token::TokenClient, Address, Env};
+
+#[test]
+fn test_mock_token_mints_and_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (alice, bob, spender) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    let id = env.register_contract(None, MockToken);
+    MockTokenClient::new(&env, &id).mint(&alice, &1_000);
+
+    let token = TokenClient::new(&env, &id);
+    token.transfer(&alice, &bob, &400);
+    token.approve(&alice, &spender, &250, &100);
+    token.transfer_from(&spender, &alice, &bob, &200);
+    assert_eq!(token.balance(&alice), 400);
+    assert_eq!(token.balance(&bob), 600);
+    assert_eq!(token.allowance(&alice, &spender), 50);
+    assert_eq!(token.decimals(), 7);
+
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    assert_eq!(token.allowance(&alice, &spender), 0);
+}
+
+#[test]
+fn test_mock_oracle_serves_set_prices() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    let asset = Address::generate(&env);
+    let oracle = MockOracleClient::new(&env, &env.register_contract(None, MockOracle));
+
+    assert_eq!(oracle.price(&asset), None);
+    oracle.set_price(&asset, &1_250);
+    assert_eq!(
+        oracle.price(&asset),
+        Some(PriceData {
+            price: 1_250,
+            timestamp: 5_000
+        })
+    );
+    oracle.set_price_at(&asset, &900, &1_000);
+    assert_eq!(oracle.price(&asset).unwrap().timestamp, 1_000);
+    oracle.remove_price(&asset);
+    assert_eq!(oracle.price(&asset), None);
+}
+
+#[test]
+fn test_faulty_token_takes_fees_and_reverts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    let token = FaultyTokenClient::new(&env, &env.register_contract(None, FaultyToken));
+    token.mint(&alice, &10_000);
+
+    token.set_fee_bps(&100);
+    token.transfer(&alice, &bob, &1_000);
+    assert_eq!(token.balance(&alice), 9_000);
+    assert_eq!(token.balance(&bob), 990);
+    assert_eq!(
+        token.try_set_fee_bps(&10_001),
+        Err(Ok(FaultyTokenError::InvalidFee))
+    );
+
+    token.set_reverting(&true);
+    assert_eq!(
+        token.try_transfer(&alice, &bob, &1),
+        Err(Ok(FaultyTokenError::Reverted))
+    );
+    token.set_reverting(&false);
+    assert_eq!(
+        token.try_transfer(&bob, &alice, &991),
+        Err(Ok(FaultyTokenError::InsufficientBalance))
+    );
+}
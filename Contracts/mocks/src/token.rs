@@ -0,0 +1,82 @@
+//! Mintable token with the standard interface
+
+use soroban_sdk::{contract, contractimpl, token::TokenInterface, Address, Env, String};
+
+use crate::balances;
+
+/// A token anyone can mint. Transfers and allowances behave like the
+/// Stellar asset contract's, without events or authorization flags.
+#[contract]
+pub struct MockToken;
+
+#[contractimpl]
+impl MockToken {
+    /// Mint `amount` to `to`, no authorization needed
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        balances::credit(&env, &to, amount);
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for MockToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        balances::allowance(&env, &from, &spender)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        balances::approve(&env, &from, &spender, amount, expiration_ledger);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        balances::balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        if !balances::spend_allowance(&env, &from, &spender, amount) {
+            panic!("insufficient allowance");
+        }
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        if !balances::debit(&env, &from, amount) {
+            panic!("insufficient balance");
+        }
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        if !balances::spend_allowance(&env, &from, &spender, amount)
+            || !balances::debit(&env, &from, amount)
+        {
+            panic!("insufficient allowance or balance");
+        }
+    }
+
+    fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    fn name(env: Env) -> String {
+        String::from_str(&env, "Mock Token")
+    }
+
+    fn symbol(env: Env) -> String {
+        String::from_str(&env, "MOCK")
+    }
+}
+
+fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+    if !balances::debit(env, from, amount) {
+        panic!("insufficient balance");
+    }
+    balances::credit(env, to, amount);
+}
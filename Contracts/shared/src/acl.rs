@@ -64,6 +64,24 @@ impl ACL {
         env.storage().persistent().set(&key, &roles);
     }
 
+    pub fn revoke_role(env: &Env, user: &Address, role: &Symbol) {
+        let key = Self::user_roles_key(env);
+        let mut roles: Map<Address, Vec<Symbol>> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+
+        let mut user_roles = roles.get(user.clone()).unwrap_or(Vec::new(env));
+
+        if let Some(index) = user_roles.first_index_of(role.clone()) {
+            user_roles.remove(index);
+        }
+
+        roles.set(user.clone(), user_roles);
+        env.storage().persistent().set(&key, &roles);
+    }
+
     pub fn assign_permission(env: &Env, role: &Symbol, permission: &Symbol) {
         if !Self::role_exists(env, role) {
             Self::create_role(env, role);
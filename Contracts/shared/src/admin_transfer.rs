@@ -0,0 +1,108 @@
+//! Two-step admin transfer
+//!
+//! The current admin proposes a successor, who must accept within
+//! [`ADMIN_TRANSFER_EXPIRY`] seconds. Until then the old admin stays in
+//! charge, so a mistyped address can never take over (or brick) a contract.
+//!
+//! Like [`crate::recovery`], the host contract keeps owning its admin slot:
+//! it authenticates the current admin before [`PendingAdmin::propose`] and
+//! stores the address returned by [`PendingAdmin::accept`].
+//!
+//! Every workspace contract with an admin uses it, except `launch`, whose
+//! admin is the seller of a one-off sale with no admin-gated operations.
+//! `stablecoin_reserve` and the contracts outside the workspace
+//! (`cross-chain-router`, `did-registry`, `identity-hub`,
+//! `liquidity-mining`, `parametric_insurance`, `verifiable-credentials`)
+//! are not built with it and keep their own admin handling.
+
+use crate::deadline::is_expired;
+use crate::events::{AdminChangedEvent, AdminTransferProposedEvent, EventEmitter};
+use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Symbol};
+
+/// Reason attached to `ADMIN_CHANGED` events emitted by an accepted transfer
+pub const TRANSFER_REASON: Symbol = symbol_short!("transfer");
+
+/// Seconds the proposed admin has to accept
+pub const ADMIN_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
+const PENDING_ADMIN_KEY: Symbol = symbol_short!("pend_adm");
+
+/// Admin transfer error codes, in the 6000 range
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AdminTransferError {
+    NoPendingTransfer = 6001,
+    NotProposedAdmin = 6002,
+    TransferExpired = 6003,
+}
+
+/// Admin transfer awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdmin {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub expires_at: u64,
+}
+
+impl PendingAdmin {
+    /// Propose `proposed_admin` as the successor of `current_admin`, who
+    /// must already be authenticated. Replaces any earlier proposal.
+    pub fn propose(env: &Env, current_admin: &Address, proposed_admin: &Address) -> PendingAdmin {
+        let now = env.ledger().timestamp();
+        let pending = PendingAdmin {
+            current_admin: current_admin.clone(),
+            proposed_admin: proposed_admin.clone(),
+            expires_at: now.saturating_add(ADMIN_TRANSFER_EXPIRY),
+        };
+        env.storage().instance().set(&PENDING_ADMIN_KEY, &pending);
+
+        EventEmitter::admin_transfer_proposed(
+            env,
+            AdminTransferProposedEvent {
+                current_admin: current_admin.clone(),
+                proposed_admin: proposed_admin.clone(),
+                expires_at: pending.expires_at,
+                timestamp: now,
+            },
+        );
+        pending
+    }
+
+    /// Accept the pending transfer as `new_admin`. Returns the new admin,
+    /// which the caller must store.
+    pub fn accept(env: &Env, new_admin: &Address) -> Result<Address, AdminTransferError> {
+        new_admin.require_auth();
+        let pending = Self::get(env).ok_or(AdminTransferError::NoPendingTransfer)?;
+        if pending.proposed_admin != *new_admin {
+            return Err(AdminTransferError::NotProposedAdmin);
+        }
+        if is_expired(env, pending.expires_at) {
+            return Err(AdminTransferError::TransferExpired);
+        }
+        let now = env.ledger().timestamp();
+        env.storage().instance().remove(&PENDING_ADMIN_KEY);
+
+        EventEmitter::admin_changed(
+            env,
+            AdminChangedEvent {
+                previous_admin: pending.current_admin,
+                new_admin: new_admin.clone(),
+                reason: TRANSFER_REASON,
+                timestamp: now,
+            },
+        );
+        Ok(new_admin.clone())
+    }
+
+    /// Withdraw the pending transfer. The caller must authenticate the
+    /// current admin.
+    pub fn cancel(env: &Env) {
+        env.storage().instance().remove(&PENDING_ADMIN_KEY);
+    }
+
+    pub fn get(env: &Env) -> Option<PendingAdmin> {
+        env.storage().instance().get(&PENDING_ADMIN_KEY)
+    }
+}
@@ -0,0 +1,53 @@
+//! Client side of the AMM
+//!
+//! Contracts built on top of AMM pairs (LP staking, order management,
+//! routing) call the pool through [`AmmClient`]. Prices are the price of a
+//! pair's `token_a` in `token_b`, scaled by [`PRICE_SCALE`]. Contracts that
+//! swap along multi-hop paths go through the swap router's
+//! [`RouterClient`] instead.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol, Vec};
+
+/// Fixed-point scale of AMM prices (7 decimals, matching Stellar assets)
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+#[contractclient(name = "AmmClient")]
+pub trait AmmInterface {
+    /// Shares of `pair` held by `provider`
+    fn shares_of(env: Env, pair: Symbol, provider: Address) -> i128;
+    /// Move `amount` shares of `pair`; requires `from`'s authorization
+    fn transfer_shares(env: Env, pair: Symbol, from: Address, to: Address, amount: i128);
+    /// Average price of `pair` over the last `window` seconds
+    fn get_twap(env: Env, pair: Symbol, window: u64) -> i128;
+    /// `token_a` and `token_b` of `pair`
+    fn pair_tokens(env: Env, pair: Symbol) -> (Address, Address);
+    /// Output of swapping `amount_in` of `token_in` at current reserves
+    fn quote(env: Env, pair: Symbol, token_in: Address, amount_in: i128) -> i128;
+    /// Swap an exact `amount_in` of `token_in` for at least `min_out` of
+    /// the other token. The AMM pulls `amount_in` from `trader`.
+    fn swap(
+        env: Env,
+        trader: Address,
+        pair: Symbol,
+        token_in: Address,
+        amount_in: i128,
+        min_out: i128,
+    ) -> i128;
+}
+
+#[contractclient(name = "RouterClient")]
+pub trait RouterInterface {
+    /// Output of selling `amount_in` along `path` at current reserves
+    fn quote(env: Env, path: Vec<Address>, amount_in: i128) -> i128;
+    /// Sell exactly `amount_in` of `path[0]` for at least `amount_out_min`
+    /// of the last token of `path`. The router pulls the input from
+    /// `trader` and pays the output back to them.
+    fn swap_exact_in(
+        env: Env,
+        trader: Address,
+        path: Vec<Address>,
+        amount_in: i128,
+        amount_out_min: i128,
+        deadline: u64,
+    ) -> i128;
+}
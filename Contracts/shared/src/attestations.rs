@@ -0,0 +1,41 @@
+//! Client side of the attestation registry
+//!
+//! Approved attestors record claims such as [`claims::KYC_TIER_2`] about
+//! addresses in the registry. Compliance-gated contracts hold a
+//! [`ClaimRequirement`] and check it before letting an address act.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Well-known claim names
+pub mod claims {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    /// Basic identity checks passed
+    pub const KYC_TIER_1: Symbol = symbol_short!("kyc_tier1");
+    /// Full identity and address verification passed
+    pub const KYC_TIER_2: Symbol = symbol_short!("kyc_tier2");
+    /// Qualifies as an accredited investor
+    pub const ACCREDITED: Symbol = symbol_short!("accredit");
+}
+
+/// Read-only interface exposed by the attestation registry
+#[contractclient(name = "AttestationClient")]
+pub trait AttestationInterface {
+    /// Whether `subject` holds an unexpired, unrevoked `claim` from an
+    /// attestor still approved for it
+    fn has_claim(env: Env, subject: Address, claim: Symbol) -> bool;
+}
+
+/// A claim an address must hold, and the registry that vouches for it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimRequirement {
+    pub registry: Address,
+    pub claim: Symbol,
+}
+
+impl ClaimRequirement {
+    pub fn is_met(&self, env: &Env, subject: &Address) -> bool {
+        AttestationClient::new(env, &self.registry).has_claim(subject, &self.claim)
+    }
+}
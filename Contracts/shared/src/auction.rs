@@ -0,0 +1,110 @@
+//! Descending-price collateral auctions
+//!
+//! Liquidations sell a lot of seized collateral for the debt it backed (the
+//! tab). The price starts `buf_bps` above the oracle price and is multiplied
+//! by `cut_bps` every `step` seconds, so keepers take the lot, in as many
+//! partial fills as they like, once the price suits them. An auction that
+//! runs longer than `tail` without clearing must be restarted from a fresh
+//! oracle price.
+//!
+//! Contracts own the auction storage and token movements; this module only
+//! holds the parameters and the pricing arithmetic.
+
+use soroban_sdk::{contracttype, Address};
+
+const BPS_DENOMINATOR: i128 = 10_000;
+/// Upper bound on `tail / step`, which bounds the price computation
+pub const MAX_AUCTION_STEPS: u64 = 1_000;
+
+/// Price curve shared by every auction of a contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionParams {
+    /// Starting price as a share of the oracle price, at least 10 000
+    pub buf_bps: u32,
+    /// Seconds between price cuts
+    pub step: u64,
+    /// Share of the price kept at each cut, below 10 000
+    pub cut_bps: u32,
+    /// Seconds after which the auction needs a restart
+    pub tail: u64,
+}
+
+impl Default for AuctionParams {
+    /// Start 10% above the oracle price and lose 1% a minute for three hours
+    fn default() -> Self {
+        AuctionParams {
+            buf_bps: 11_000,
+            step: 60,
+            cut_bps: 9_900,
+            tail: 3 * 60 * 60,
+        }
+    }
+}
+
+impl AuctionParams {
+    pub fn is_valid(&self) -> bool {
+        self.buf_bps as i128 >= BPS_DENOMINATOR
+            && self.cut_bps > 0
+            && (self.cut_bps as i128) < BPS_DENOMINATOR
+            && self.step > 0
+            && self.tail >= self.step
+            && self.tail / self.step <= MAX_AUCTION_STEPS
+    }
+
+    /// Opening price for collateral the oracle values at `oracle_price`
+    pub fn start_price(&self, oracle_price: i128) -> i128 {
+        oracle_price * self.buf_bps as i128 / BPS_DENOMINATOR
+    }
+}
+
+/// Collateral for sale against outstanding debt
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    /// Account whose collateral is being sold
+    pub borrower: Address,
+    pub collateral_asset: Address,
+    /// Asset bids are paid in
+    pub debt_asset: Address,
+    /// Collateral left to sell
+    pub lot: i128,
+    /// Debt left to cover
+    pub tab: i128,
+    pub start_price: i128,
+    pub started_at: u64,
+}
+
+impl Auction {
+    /// Price per unit of collateral at `now`, in the caller's price scale
+    pub fn price(&self, params: &AuctionParams, now: u64) -> i128 {
+        let steps = (now.saturating_sub(self.started_at) / params.step).min(MAX_AUCTION_STEPS);
+        let mut price = self.start_price;
+        for _ in 0..steps {
+            price = price * params.cut_bps as i128 / BPS_DENOMINATOR;
+        }
+        price
+    }
+
+    pub fn expired(&self, params: &AuctionParams, now: u64) -> bool {
+        now.saturating_sub(self.started_at) > params.tail
+    }
+
+    /// Collateral sold and debt paid when taking up to `max_collateral` at
+    /// `price`. The taker never pays more than the tab.
+    pub fn fill(&self, price: i128, price_scale: i128, max_collateral: i128) -> (i128, i128) {
+        let collateral = max_collateral.min(self.lot);
+        let owe = collateral * price / price_scale;
+        if owe <= self.tab {
+            return (collateral, owe);
+        }
+        // Only sell what covers the tab
+        let collateral = (self.tab * price_scale / price).min(self.lot);
+        (collateral, self.tab)
+    }
+
+    /// Whether nothing more can be sold or nothing more is owed
+    pub fn is_done(&self) -> bool {
+        self.lot <= 0 || self.tab <= 0
+    }
+}
@@ -0,0 +1,80 @@
+//! Per-entry-point call statistics
+//!
+//! An opt-in audit trail for security monitoring. A contract calls
+//! [`AuditLog::record`] from the entry points it wants watched, and each
+//! keeps a running call count, its last caller and the ledger it was last
+//! called in. Only successful calls are counted, since a failed call
+//! reverts the record with everything else.
+//!
+//! An `audit` event goes out on an entry point's first call and every
+//! [`AUDIT_INTERVAL`] calls after, so monitoring sees activity without an
+//! event per call. An entry point whose last ledger is long past is a
+//! candidate for removal before an upgrade.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::events::{AuditEvent, EventEmitter};
+
+/// Calls between `audit` events of one entry point
+pub const AUDIT_INTERVAL: u64 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecord {
+    /// Successful calls so far
+    pub calls: u64,
+    pub last_caller: Address,
+    pub last_ledger: u32,
+    /// Ledger of the first recorded call
+    pub first_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum AuditKey {
+    Record(Symbol),
+}
+
+pub struct AuditLog;
+
+impl AuditLog {
+    /// Count a call of `function` by `caller`. Returns the updated record.
+    pub fn record(env: &Env, function: Symbol, caller: &Address) -> AuditRecord {
+        let ledger = env.ledger().sequence();
+        let key = AuditKey::Record(function.clone());
+        let record = match env.storage().persistent().get::<_, AuditRecord>(&key) {
+            Some(record) => AuditRecord {
+                calls: record.calls + 1,
+                last_caller: caller.clone(),
+                last_ledger: ledger,
+                first_ledger: record.first_ledger,
+            },
+            None => AuditRecord {
+                calls: 1,
+                last_caller: caller.clone(),
+                last_ledger: ledger,
+                first_ledger: ledger,
+            },
+        };
+        env.storage().persistent().set(&key, &record);
+
+        if record.calls % AUDIT_INTERVAL == 1 {
+            EventEmitter::audit(
+                env,
+                AuditEvent {
+                    function,
+                    calls: record.calls,
+                    last_caller: record.last_caller.clone(),
+                    first_ledger: record.first_ledger,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+        record
+    }
+
+    /// Statistics of `function`, `None` if it was never recorded
+    pub fn get(env: &Env, function: Symbol) -> Option<AuditRecord> {
+        env.storage().persistent().get(&AuditKey::Record(function))
+    }
+}
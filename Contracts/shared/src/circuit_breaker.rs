@@ -0,0 +1,182 @@
+//! Price-deviation circuit breaker
+//!
+//! Contracts feed every price they accept through [`CircuitBreaker::record`].
+//! Each feed remembers a reference price; if a new price differs from it by
+//! more than `max_move_bps` within `window` seconds, the feed trips for
+//! `cooldown` seconds. Once the window has passed without a trip, the next
+//! price becomes the new reference.
+//!
+//! The breaker only keeps state and emits events. The host contract decides
+//! what a tripped feed means, e.g. reverting trades or refusing anything but
+//! risk-reducing operations, by consulting [`CircuitBreaker::check`].
+
+use crate::events::{CircuitResetEvent, CircuitTripEvent, EventEmitter};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+const CONFIG_KEY: Symbol = symbol_short!("cb_cfg");
+const STATE_KEY: Symbol = symbol_short!("cb_state");
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Price source guarded by the breaker
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PriceFeed {
+    Asset(Address),
+    Pair(Symbol),
+}
+
+/// Breaker thresholds, shared by every feed of a contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakerConfig {
+    /// Largest allowed move from the reference price, in basis points
+    pub max_move_bps: u32,
+    /// Seconds a reference price stays in force
+    pub window: u64,
+    /// Seconds a tripped feed stays tripped
+    pub cooldown: u64,
+}
+
+impl BreakerConfig {
+    pub fn is_valid(&self) -> bool {
+        self.max_move_bps > 0 && self.window > 0
+    }
+}
+
+/// Per-feed breaker state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakerState {
+    pub reference_price: i128,
+    pub reference_at: u64,
+    /// Price that tripped the feed; 0 when the feed is not tripped
+    pub tripped_price: i128,
+    /// End of the current trip; 0 when the feed is not tripped
+    pub tripped_until: u64,
+}
+
+pub struct CircuitBreaker;
+
+impl CircuitBreaker {
+    /// Install or, with `None`, disable the breaker. The caller authorizes
+    /// and validates the config.
+    pub fn set_config(env: &Env, config: Option<BreakerConfig>) {
+        match config {
+            Some(config) => env.storage().instance().set(&CONFIG_KEY, &config),
+            None => env.storage().instance().remove(&CONFIG_KEY),
+        }
+    }
+
+    pub fn config(env: &Env) -> Option<BreakerConfig> {
+        env.storage().instance().get(&CONFIG_KEY)
+    }
+
+    pub fn state(env: &Env, feed: &PriceFeed) -> Option<BreakerState> {
+        env.storage().persistent().get(&(STATE_KEY, feed.clone()))
+    }
+
+    /// Observe a new `price` for `feed`. Returns `false` if the feed is
+    /// tripped afterwards.
+    pub fn record(env: &Env, feed: &PriceFeed, price: i128) -> bool {
+        let Some(config) = Self::config(env) else {
+            return true;
+        };
+        let now = env.ledger().timestamp();
+        let Some(mut state) = Self::state(env, feed) else {
+            Self::save(env, feed, &Self::fresh(price, now));
+            return true;
+        };
+        if state.tripped_until != 0 {
+            if now < state.tripped_until {
+                return false;
+            }
+            // Cooldown over: restart from this price
+            Self::emit_reset(env, feed, &state, price, false);
+            Self::save(env, feed, &Self::fresh(price, now));
+            return true;
+        }
+        if now.saturating_sub(state.reference_at) > config.window {
+            Self::save(env, feed, &Self::fresh(price, now));
+            return true;
+        }
+
+        let move_bps =
+            (price - state.reference_price).abs() * BPS_DENOMINATOR / state.reference_price.max(1);
+        if move_bps <= config.max_move_bps as i128 {
+            return true;
+        }
+        state.tripped_price = price;
+        state.tripped_until = now.saturating_add(config.cooldown).max(now + 1);
+        Self::save(env, feed, &state);
+        EventEmitter::circuit_tripped(
+            env,
+            CircuitTripEvent {
+                feed: feed.clone(),
+                reference_price: state.reference_price,
+                price,
+                move_bps: move_bps as u32,
+                window: config.window,
+                tripped_until: state.tripped_until,
+                timestamp: now,
+            },
+        );
+        false
+    }
+
+    /// Whether operations relying on `feed` may proceed. Clears a trip whose
+    /// cooldown has passed.
+    pub fn check(env: &Env, feed: &PriceFeed) -> bool {
+        let Some(state) = Self::state(env, feed) else {
+            return true;
+        };
+        if state.tripped_until == 0 {
+            return true;
+        }
+        let now = env.ledger().timestamp();
+        if now < state.tripped_until {
+            return false;
+        }
+        Self::emit_reset(env, feed, &state, state.tripped_price, false);
+        Self::save(env, feed, &Self::fresh(state.tripped_price, now));
+        true
+    }
+
+    /// Clear a trip early, keeping `price` as the new reference. The caller
+    /// authorizes.
+    pub fn reset(env: &Env, feed: &PriceFeed, price: i128) {
+        if let Some(state) = Self::state(env, feed).filter(|s| s.tripped_until != 0) {
+            Self::emit_reset(env, feed, &state, price, true);
+        }
+        Self::save(env, feed, &Self::fresh(price, env.ledger().timestamp()));
+    }
+
+    fn fresh(price: i128, now: u64) -> BreakerState {
+        BreakerState {
+            reference_price: price,
+            reference_at: now,
+            tripped_price: 0,
+            tripped_until: 0,
+        }
+    }
+
+    fn save(env: &Env, feed: &PriceFeed, state: &BreakerState) {
+        env.storage()
+            .persistent()
+            .set(&(STATE_KEY, feed.clone()), state);
+    }
+
+    fn emit_reset(env: &Env, feed: &PriceFeed, state: &BreakerState, price: i128, manual: bool) {
+        EventEmitter::circuit_reset(
+            env,
+            CircuitResetEvent {
+                feed: feed.clone(),
+                reference_price: state.reference_price,
+                tripped_price: state.tripped_price,
+                price,
+                manual,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+}
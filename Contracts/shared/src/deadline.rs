@@ -0,0 +1,109 @@
+//! Deadline and expiry checks
+//!
+//! Swaps, permits, orders, session keys and pending admin transfers all
+//! carry a timestamp after which they must be rejected. They share one
+//! rule: a deadline is still good in the second it names and expired from
+//! the next, compared against the ledger timestamp. Failures are reported
+//! as [`ContractError::Expired`]; contracts with their own error enum map
+//! it to their variant.
+//!
+//! [`Deadline`] builds deadlines relative to now, for callers and tests.
+
+use soroban_sdk::Env;
+
+use crate::errors::ContractError;
+
+/// Whether `deadline` has passed
+pub fn is_expired(env: &Env, deadline: u64) -> bool {
+    env.ledger().timestamp() > deadline
+}
+
+/// Fail once `deadline` has passed
+pub fn require_not_expired(env: &Env, deadline: u64) -> Result<(), ContractError> {
+    if is_expired(env, deadline) {
+        return Err(ContractError::Expired);
+    }
+    Ok(())
+}
+
+/// Fail unless `expires_at` is still ahead, for things created with an
+/// expiry that must outlive the creating call
+pub fn require_future(env: &Env, expires_at: u64) -> Result<(), ContractError> {
+    if expires_at <= env.ledger().timestamp() {
+        return Err(ContractError::Expired);
+    }
+    Ok(())
+}
+
+/// A timestamp an operation must happen by
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Deadline(u64);
+
+impl Deadline {
+    /// Deadline at `timestamp`
+    pub fn at(timestamp: u64) -> Self {
+        Self(timestamp)
+    }
+
+    /// Deadline `seconds` after the current ledger timestamp
+    pub fn after(env: &Env, seconds: u64) -> Self {
+        Self(env.ledger().timestamp().saturating_add(seconds))
+    }
+
+    /// No deadline at all
+    pub fn never() -> Self {
+        Self(u64::MAX)
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.0
+    }
+
+    pub fn is_expired(&self, env: &Env) -> bool {
+        is_expired(env, self.0)
+    }
+
+    pub fn require_not_expired(&self, env: &Env) -> Result<(), ContractError> {
+        require_not_expired(env, self.0)
+    }
+
+    /// Seconds left, 0 once expired
+    pub fn remaining(&self, env: &Env) -> u64 {
+        self.0.saturating_sub(env.ledger().timestamp())
+    }
+}
+
+impl From<Deadline> for u64 {
+    fn from(deadline: Deadline) -> u64 {
+        deadline.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    #[test]
+    fn deadline_holds_through_its_own_second() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        assert_eq!(require_not_expired(&env, 1_000), Ok(()));
+        assert_eq!(require_not_expired(&env, 999), Err(ContractError::Expired));
+        assert_eq!(require_future(&env, 1_000), Err(ContractError::Expired));
+        assert_eq!(require_future(&env, 1_001), Ok(()));
+
+        let deadline = Deadline::after(&env, 60);
+        assert_eq!(deadline.timestamp(), 1_060);
+        env.ledger().with_mut(|li| li.timestamp = 1_060);
+        assert!(!deadline.is_expired(&env));
+        assert_eq!(deadline.remaining(&env), 0);
+        env.ledger().with_mut(|li| li.timestamp = 1_061);
+        assert_eq!(
+            deadline.require_not_expired(&env),
+            Err(ContractError::Expired)
+        );
+        assert!(!Deadline::never().is_expired(&env));
+    }
+}
@@ -0,0 +1,105 @@
+//! Epoch arithmetic
+//!
+//! Gauges, incentives, budgets, emissions and metrics all split time into
+//! fixed-length epochs. An [`EpochClock`] anchors epoch 0 at a genesis
+//! timestamp; epoch `n` covers `[epoch_start(n), epoch_end(n))`. Times
+//! before genesis fall in epoch 0.
+//!
+//! Contracts that act on epoch boundaries keep the last epoch they acted
+//! in and pass it to [`EpochClock::rollover`], which emits an
+//! `epoch_rollover` event the first time they act in a new epoch.
+
+use soroban_sdk::{contracttype, Env};
+
+use crate::events::{EpochRolloverEvent, EventEmitter};
+use crate::voting::WEEK;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EpochClock {
+    /// Start of epoch 0
+    pub genesis: u64,
+    /// Epoch length in seconds, never 0
+    pub length: u64,
+}
+
+impl EpochClock {
+    /// Weekly epochs aligned to the Unix epoch, as used by vote-escrow
+    /// voting and gauges
+    pub const WEEKLY: EpochClock = EpochClock {
+        genesis: 0,
+        length: WEEK,
+    };
+
+    /// Clock with epochs of `length` seconds starting at `genesis`.
+    /// Returns `None` for a zero length.
+    pub fn new(genesis: u64, length: u64) -> Option<Self> {
+        (length > 0).then_some(Self { genesis, length })
+    }
+
+    /// Epoch containing `timestamp`
+    pub fn epoch_at(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis) / self.length
+    }
+
+    /// Epoch containing the current ledger timestamp
+    pub fn current_epoch(&self, env: &Env) -> u64 {
+        self.epoch_at(env.ledger().timestamp())
+    }
+
+    /// First second of `epoch`
+    pub fn epoch_start(&self, epoch: u64) -> u64 {
+        self.genesis
+            .saturating_add(epoch.saturating_mul(self.length))
+    }
+
+    /// First second after `epoch`, i.e. the start of the next one
+    pub fn epoch_end(&self, epoch: u64) -> u64 {
+        self.epoch_start(epoch.saturating_add(1))
+    }
+
+    /// Seconds left in the current epoch
+    pub fn time_until_next(&self, env: &Env) -> u64 {
+        let now = env.ledger().timestamp();
+        self.epoch_end(self.epoch_at(now)).saturating_sub(now)
+    }
+
+    /// Emit `epoch_rollover` if the current epoch is later than
+    /// `last_epoch`, returning the new epoch for the caller to store
+    pub fn rollover(&self, env: &Env, last_epoch: u64) -> Option<u64> {
+        let epoch = self.current_epoch(env);
+        if epoch <= last_epoch {
+            return None;
+        }
+        EventEmitter::epoch_rollover(
+            env,
+            EpochRolloverEvent {
+                epoch,
+                previous_epoch: last_epoch,
+                epoch_start: self.epoch_start(epoch),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Some(epoch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_epoch_boundaries() {
+        assert_eq!(EpochClock::new(1_000, 0), None);
+        let clock = EpochClock::new(1_000, 100).unwrap();
+
+        assert_eq!(clock.epoch_at(0), 0);
+        assert_eq!(clock.epoch_at(1_099), 0);
+        assert_eq!(clock.epoch_at(1_100), 1);
+        assert_eq!(clock.epoch_start(3), 1_300);
+        assert_eq!(clock.epoch_end(3), 1_400);
+        assert_eq!(clock.epoch_at(clock.epoch_end(3)), 4);
+        assert_eq!(clock.epoch_end(u64::MAX), u64::MAX);
+        assert_eq!(EpochClock::WEEKLY.epoch_at(WEEK * 5 + 1), 5);
+    }
+}
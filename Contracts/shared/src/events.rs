@@ -4,7 +4,13 @@
 //! and notification systems. All contracts should use these event types
 //! to ensure reliable backend integration.
 
-use soroban_sdk::{contracttype, Address, Symbol};
+use crate::circuit_breaker::PriceFeed;
+use crate::governance::ProposalStatus;
+use crate::voting::VotingSource;
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol,
+    TryFromVal, Val, Vec,
+};
 
 // =============================================================================
 // Event Topics (standardized event names)
@@ -16,9 +22,27 @@ pub mod topics {
 
     // Trading events
     pub const TRADE_EXECUTED: Symbol = symbol_short!("trade");
+    pub const ORDER_TRIGGERED: Symbol = symbol_short!("ord_trig");
+    pub const ROUTE_EXECUTED: Symbol = symbol_short!("route_exe");
+    pub const BATCH_SETTLED: Symbol = symbol_short!("batch_set");
+    pub const ORDER_COMMITTED: Symbol = symbol_short!("ord_cmt");
+    pub const ORDER_REVEALED: Symbol = symbol_short!("ord_rvl");
+    pub const COMMIT_SLASHED: Symbol = symbol_short!("cmt_slash");
     pub const CONTRACT_PAUSED: Symbol = symbol_short!("paused");
     pub const CONTRACT_UNPAUSED: Symbol = symbol_short!("unpause");
+    pub const PAUSE_CHANGED: Symbol = symbol_short!("pause_chg");
+    pub const REPUTATION_UPDATED: Symbol = symbol_short!("rep_upd");
+    pub const NAME_REGISTERED: Symbol = symbol_short!("name_reg");
+    pub const NAME_RENEWED: Symbol = symbol_short!("name_rnw");
+    pub const NAME_TRANSFERRED: Symbol = symbol_short!("name_xfer");
+    pub const ATTESTATION_ISSUED: Symbol = symbol_short!("att_iss");
+    pub const ATTESTATION_REVOKED: Symbol = symbol_short!("att_rev");
     pub const FEE_COLLECTED: Symbol = symbol_short!("fee");
+    pub const REFERRER_BOUND: Symbol = symbol_short!("ref_bind");
+    pub const FEE_TIER_CHANGED: Symbol = symbol_short!("fee_tier");
+    pub const PNL_REALIZED: Symbol = symbol_short!("pnl_real");
+    pub const PRICE_UPDATED: Symbol = symbol_short!("price");
+    pub const FLASH_LOAN: Symbol = symbol_short!("flash");
 
     // Governance events
     pub const PROPOSAL_CREATED: Symbol = symbol_short!("propose");
@@ -26,17 +50,25 @@ pub mod topics {
     pub const PROPOSAL_REJECTED: Symbol = symbol_short!("reject");
     pub const PROPOSAL_EXECUTED: Symbol = symbol_short!("execute");
     pub const PROPOSAL_CANCELLED: Symbol = symbol_short!("cancel");
+    pub const PROPOSAL_BATCH_EXECUTED: Symbol = symbol_short!("exec_bat");
+    pub const PROPOSAL_CHALLENGED: Symbol = symbol_short!("challenge");
+    pub const PROPOSAL_ARCHIVED: Symbol = symbol_short!("archived");
+    pub const VOTE_CAST: Symbol = symbol_short!("vote_cast");
+    pub const CONVICTION_UPDATED: Symbol = symbol_short!("conv_upd");
+    pub const CONVICTION_PASSED: Symbol = symbol_short!("conv_pass");
 
     // Social rewards events
     pub const REWARD_ADDED: Symbol = symbol_short!("reward");
     pub const REWARD_CLAIMED: Symbol = symbol_short!("claimed");
 
     // Parametric insurance events
-    pub const POLICY_CREATED: Symbol = symbol_short!("pol_create");
-    pub const POLICY_CANCELLED: Symbol = symbol_short!("pol_cancel");
-    pub const POLICY_EXPIRED: Symbol = symbol_short!("pol_expire");
+    pub const POLICY_CREATED: Symbol = symbol_short!("pol_new");
+    pub const POLICY_CANCELLED: Symbol = symbol_short!("pol_cncl");
+    pub const POLICY_EXPIRED: Symbol = symbol_short!("pol_exp");
     pub const TRIGGER_ACTIVATED: Symbol = symbol_short!("trig_act");
-    pub const CLAIM_PAID: Symbol = symbol_short!("claim_paid");
+    pub const CLAIM_PAID: Symbol = symbol_short!("clm_paid");
+    pub const CLAIM_FILED: Symbol = symbol_short!("clm_file");
+    pub const CLAIM_REJECTED: Symbol = symbol_short!("clm_rej");
     pub const LIQUIDITY_DEPOSITED: Symbol = symbol_short!("liq_dep");
     pub const LIQUIDITY_WITHDRAWN: Symbol = symbol_short!("liq_wdraw");
 
@@ -44,6 +76,691 @@ pub mod topics {
     pub const TRANSFER: Symbol = symbol_short!("transfer");
     pub const MINT: Symbol = symbol_short!("mint");
     pub const BURN: Symbol = symbol_short!("burn");
+    pub const METADATA_UPDATED: Symbol = symbol_short!("meta_upd");
+    pub const APPROVAL: Symbol = symbol_short!("approval");
+    pub const AUTHORIZATION_CHANGED: Symbol = symbol_short!("auth_chg");
+    pub const WRAPPED: Symbol = symbol_short!("wrap");
+    pub const UNWRAPPED: Symbol = symbol_short!("unwrap");
+    pub const DUST_SWEPT: Symbol = symbol_short!("dust");
+    pub const TRANSFER_HOOK_CHANGED: Symbol = symbol_short!("hook_chg");
+    pub const REBASED: Symbol = symbol_short!("rebase");
+
+    // Streaming payment events
+    pub const STREAM_CREATED: Symbol = symbol_short!("strm_new");
+    pub const STREAM_WITHDRAWN: Symbol = symbol_short!("strm_wd");
+    pub const STREAM_CANCELLED: Symbol = symbol_short!("strm_cncl");
+
+    // NFT events
+    pub const NFT_MINTED: Symbol = symbol_short!("nft_mint");
+    pub const NFT_TRANSFERRED: Symbol = symbol_short!("nft_xfer");
+    pub const NFT_APPROVED: Symbol = symbol_short!("nft_appr");
+    pub const NFT_BURNED: Symbol = symbol_short!("nft_burn");
+
+    // Multi-token events
+    pub const MT_MINTED: Symbol = symbol_short!("mt_mint");
+    pub const MT_TRANSFERRED: Symbol = symbol_short!("mt_xfer");
+    pub const MT_BURNED: Symbol = symbol_short!("mt_burn");
+    pub const MT_URI: Symbol = symbol_short!("mt_uri");
+    pub const BADGE_ISSUED: Symbol = symbol_short!("badge_iss");
+    pub const BADGE_REVOKED: Symbol = symbol_short!("badge_rev");
+
+    // Marketplace events
+    pub const LISTED: Symbol = symbol_short!("listed");
+    pub const UNLISTED: Symbol = symbol_short!("unlisted");
+    pub const SALE: Symbol = symbol_short!("sale");
+    pub const OFFER_MADE: Symbol = symbol_short!("offer");
+    pub const OFFER_CANCELLED: Symbol = symbol_short!("offer_cxl");
+    pub const ROYALTY_PAID: Symbol = symbol_short!("royalty");
+    pub const AUCTION_CREATED: Symbol = symbol_short!("auc_new");
+    pub const BID_PLACED: Symbol = symbol_short!("bid");
+    pub const AUCTION_SETTLED: Symbol = symbol_short!("auc_done");
+
+    // Bridge events
+    pub const BRIDGE_LOCK: Symbol = symbol_short!("br_lock");
+    pub const BRIDGE_RELEASE: Symbol = symbol_short!("br_rel");
+
+    // Keeper events
+    pub const TASK_REGISTERED: Symbol = symbol_short!("task_reg");
+    pub const TASK_PERFORMED: Symbol = symbol_short!("task_run");
+    pub const TASK_CANCELLED: Symbol = symbol_short!("task_cxl");
+
+    // Relay registry events
+    pub const RELAY_REIMBURSED: Symbol = symbol_short!("relay_rmb");
+
+    // Audit events
+    pub const AUDIT: Symbol = symbol_short!("audit");
+
+    // Epoch events
+    pub const EPOCH_ROLLOVER: Symbol = symbol_short!("epoch_rol");
+
+    // Vault events
+    pub const VAULT_DEPOSIT: Symbol = symbol_short!("vlt_dep");
+    pub const VAULT_WITHDRAW: Symbol = symbol_short!("vlt_wd");
+    pub const HARVESTED: Symbol = symbol_short!("harvest");
+
+    // Vote-escrow events
+    pub const VE_LOCKED: Symbol = symbol_short!("ve_lock");
+    pub const VE_WITHDRAWN: Symbol = symbol_short!("ve_wd");
+
+    // Gauge events
+    pub const GAUGE_VOTED: Symbol = symbol_short!("g_vote");
+    pub const GAUGE_EPOCH: Symbol = symbol_short!("g_epoch");
+
+    // Gauge incentive events
+    pub const BRIBE_DEPOSITED: Symbol = symbol_short!("brb_dep");
+    pub const BRIBE_CLAIMED: Symbol = symbol_short!("brb_clm");
+
+    // Event subscription events
+    pub const SUBSCRIBED: Symbol = symbol_short!("sub_set");
+    pub const UNSUBSCRIBED: Symbol = symbol_short!("sub_clr");
+
+    // Staking position events
+    pub const POSITION_MINTED: Symbol = symbol_short!("pos_mint");
+    pub const POSITION_TRANSFERRED: Symbol = symbol_short!("pos_xfer");
+    pub const POSITION_BURNED: Symbol = symbol_short!("pos_burn");
+    pub const REWARDS_CLAIMED: Symbol = symbol_short!("rwd_claim");
+
+    // LP staking events
+    pub const STAKE: Symbol = symbol_short!("stake");
+    pub const UNSTAKE: Symbol = symbol_short!("unstake");
+    pub const IL_COMPENSATED: Symbol = symbol_short!("il_comp");
+
+    // Staking cooldown events
+    pub const UNSTAKE_REQUESTED: Symbol = symbol_short!("unstk_req");
+    pub const UNSTAKE_CANCELLED: Symbol = symbol_short!("unstk_cxl");
+
+    // Lending market events
+    pub const LEND_DEPOSIT: Symbol = symbol_short!("lend_dep");
+    pub const LEND_WITHDRAW: Symbol = symbol_short!("lend_wd");
+    pub const BORROW: Symbol = symbol_short!("borrow");
+    pub const REPAY: Symbol = symbol_short!("repay");
+    pub const LIQUIDATION: Symbol = symbol_short!("liquidate");
+
+    // Collateral auction events
+    pub const AUCTION_STARTED: Symbol = symbol_short!("la_start");
+    pub const AUCTION_TAKEN: Symbol = symbol_short!("la_take");
+    pub const AUCTION_CLOSED: Symbol = symbol_short!("la_settle");
+
+    // Circuit breaker events
+    pub const CIRCUIT_TRIPPED: Symbol = symbol_short!("cb_trip");
+    pub const CIRCUIT_RESET: Symbol = symbol_short!("cb_reset");
+
+    // Deployment events
+    pub const CONTRACT_DEPLOYED: Symbol = symbol_short!("deployed");
+    pub const REGISTRY_UPDATED: Symbol = symbol_short!("reg_upd");
+
+    // Admin events
+    pub const ADMIN_CHANGED: Symbol = symbol_short!("admin_chg");
+    pub const ADMIN_TRANSFER_PROPOSED: Symbol = symbol_short!("adm_prop");
+
+    // Treasury events
+    pub const BUDGET_SPENT: Symbol = symbol_short!("budget");
+
+    // Dividend events
+    pub const DISTRIBUTION_CREATED: Symbol = symbol_short!("div_new");
+    pub const DIVIDEND_CLAIMED: Symbol = symbol_short!("div_claim");
+
+    // Session account events
+    pub const SESSION_ADDED: Symbol = symbol_short!("sess_add");
+    pub const SESSION_REVOKED: Symbol = symbol_short!("sess_rev");
+    pub const RECOVERY_INITIATED: Symbol = symbol_short!("rec_init");
+    pub const RECOVERY_CANCELLED: Symbol = symbol_short!("rec_cncl");
+    pub const RECOVERY_COMPLETED: Symbol = symbol_short!("rec_done");
+
+    // Subscription billing events
+    pub const BILLING_STARTED: Symbol = symbol_short!("bill_new");
+    pub const BILLING_CHARGED: Symbol = symbol_short!("bill_chrg");
+    pub const BILLING_FAILED: Symbol = symbol_short!("bill_fail");
+    pub const BILLING_CANCELLED: Symbol = symbol_short!("bill_cncl");
+
+    // Payment splitter events
+    pub const SPLIT_RELEASED: Symbol = symbol_short!("split_rel");
+
+    // Savings events
+    pub const SAVINGS_DEPOSIT: Symbol = symbol_short!("sav_dep");
+    pub const SAVINGS_WITHDRAW: Symbol = symbol_short!("sav_wd");
+    pub const SAVINGS_RATE: Symbol = symbol_short!("sav_rate");
+
+    // Emission controller events
+    pub const EMISSION: Symbol = symbol_short!("emission");
+    pub const EMISSION_CURVE: Symbol = symbol_short!("emit_crv");
+
+    // Launch auction events
+    pub const LAUNCH_COMMIT: Symbol = symbol_short!("lch_cmt");
+    pub const LAUNCH_SETTLED: Symbol = symbol_short!("lch_set");
+    pub const LAUNCH_CLAIM: Symbol = symbol_short!("lch_claim");
+
+    // Quest events
+    pub const QUEST_COMPLETED: Symbol = symbol_short!("quest_ok");
+    pub const QUEST_CLAIMED: Symbol = symbol_short!("quest_clm");
+
+    // Prize lottery events
+    pub const DRAW_STARTED: Symbol = symbol_short!("draw_new");
+    pub const DRAW_WINNER: Symbol = symbol_short!("draw_win");
+    pub const PRIZE_CLAIMED: Symbol = symbol_short!("prize_clm");
+
+    // Quadratic funding events
+    pub const GRANT_CONTRIBUTED: Symbol = symbol_short!("grant_don");
+    pub const GRANT_MATCHED: Symbol = symbol_short!("grant_mat");
+
+    // OTC deal events
+    pub const OTC_CREATED: Symbol = symbol_short!("otc_new");
+    pub const OTC_FUNDED: Symbol = symbol_short!("otc_fund");
+    pub const OTC_SETTLED: Symbol = symbol_short!("otc_set");
+    pub const OTC_EXPIRED: Symbol = symbol_short!("otc_exp");
+}
+
+/// Well-known keys used in the `metadata` map of a [`StandardEventV2`]
+pub mod metadata_keys {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const AMOUNT_KEY: Symbol = symbol_short!("amount");
+    pub const TOKEN_KEY: Symbol = symbol_short!("token");
+    pub const FEE_KEY: Symbol = symbol_short!("fee");
+    pub const PRICE_KEY: Symbol = symbol_short!("price");
+    pub const PAIR_KEY: Symbol = symbol_short!("pair");
+    pub const RECIPIENT_KEY: Symbol = symbol_short!("recipient");
+    pub const ID_KEY: Symbol = symbol_short!("id");
+    pub const STATUS_KEY: Symbol = symbol_short!("status");
+    pub const REASON_KEY: Symbol = symbol_short!("reason");
+    pub const START_KEY: Symbol = symbol_short!("start");
+    pub const STOP_KEY: Symbol = symbol_short!("stop");
+    pub const RATE_KEY: Symbol = symbol_short!("rate");
+    pub const REFERRER_KEY: Symbol = symbol_short!("referrer");
+    pub const COLLATERAL_KEY: Symbol = symbol_short!("collat");
+    pub const SEIZED_KEY: Symbol = symbol_short!("seized");
+    pub const COLLATERAL_PRICE_KEY: Symbol = symbol_short!("col_price");
+    pub const HEALTH_KEY: Symbol = symbol_short!("health");
+    pub const EPOCH_KEY: Symbol = symbol_short!("epoch");
+    pub const NAME_KEY: Symbol = symbol_short!("name");
+    pub const SYMBOL_KEY: Symbol = symbol_short!("symbol");
+    pub const DECIMALS_KEY: Symbol = symbol_short!("decimals");
+    pub const URI_KEY: Symbol = symbol_short!("uri");
+    pub const SPENDER_KEY: Symbol = symbol_short!("spender");
+    pub const BATCH_KEY: Symbol = symbol_short!("batch");
+    pub const AUTHORIZED_KEY: Symbol = symbol_short!("authd");
+    pub const KIND_KEY: Symbol = symbol_short!("kind");
+    pub const OPERATION_KEY: Symbol = symbol_short!("operation");
+    /// Entry point an audit record belongs to
+    pub const FUNCTION_KEY: Symbol = symbol_short!("function");
+    /// Calls counted so far
+    pub const CALLS_KEY: Symbol = symbol_short!("calls");
+    pub const ADDRESS_KEY: Symbol = symbol_short!("address");
+    pub const ARGS_KEY: Symbol = symbol_short!("args");
+    pub const VERSION_KEY: Symbol = symbol_short!("version");
+    pub const BIDDER_KEY: Symbol = symbol_short!("bidder");
+    pub const CHAIN_KEY: Symbol = symbol_short!("chain");
+    pub const NONCE_KEY: Symbol = symbol_short!("nonce");
+    pub const SHARES_KEY: Symbol = symbol_short!("shares");
+    pub const INDEX_KEY: Symbol = symbol_short!("index");
+    pub const RECEIVED_KEY: Symbol = symbol_short!("received");
+    pub const STEP_KEY: Symbol = symbol_short!("step");
+    pub const CUT_KEY: Symbol = symbol_short!("cut");
+    pub const TAIL_KEY: Symbol = symbol_short!("tail");
+    pub const REFERENCE_KEY: Symbol = symbol_short!("ref_price");
+    pub const ASSET_KEY: Symbol = symbol_short!("asset");
+    pub const SOURCE_KEY: Symbol = symbol_short!("source");
+    pub const CONVICTION_KEY: Symbol = symbol_short!("conv");
+    /// Rewards multiplier applied, in basis points (10_000 = 1x)
+    pub const MULTIPLIER_KEY: Symbol = symbol_short!("mult");
+    pub const EXPIRY_KEY: Symbol = symbol_short!("expiry");
+    /// Earliest time a delayed action can be carried out
+    pub const READY_AT_KEY: Symbol = symbol_short!("ready_at");
+    /// Impermanent loss in basis points
+    pub const IL_BPS_KEY: Symbol = symbol_short!("il_bps");
+    /// Price a conditional order was set to trigger at
+    pub const TRIGGER_KEY: Symbol = symbol_short!("trigger");
+    /// Realized profit or loss, in quote units
+    pub const PNL_KEY: Symbol = symbol_short!("pnl");
+    /// Fee tier a trader moved into
+    pub const TIER_KEY: Symbol = symbol_short!("tier");
+    /// Number of items an aggregate event covers
+    pub const COUNT_KEY: Symbol = symbol_short!("count");
+    /// Uniform price a batch auction cleared at
+    pub const CLEARING_PRICE_KEY: Symbol = symbol_short!("clearing");
+    /// Multi-hop swap a trade was one hop of
+    pub const ROUTE_KEY: Symbol = symbol_short!("route");
+    pub const AMOUNT_IN_KEY: Symbol = symbol_short!("amount_in");
+    /// Position of a trade within its route, starting at 0
+    pub const HOP_KEY: Symbol = symbol_short!("hop");
+    /// Set to `true` when the event's user has a matching
+    /// [`super::EventSubscription`]
+    pub const NOTIFY_KEY: Symbol = symbol_short!("notify");
+    /// Seed a random draw was made with
+    pub const SEED_KEY: Symbol = symbol_short!("seed");
+
+    /// Set on approvals granted through a signed permit. Longer than a short
+    /// symbol, so build it with `Symbol::new`.
+    pub const VIA_PERMIT_KEY: &str = "via_permit";
+}
+
+// =============================================================================
+// Standard Event Envelope
+// =============================================================================
+
+/// Schema version of the original [`StandardEvent`] envelope
+pub const VERSION_1: u32 = 1;
+
+/// Schema version stamped on every event published by this build
+/// ([`StandardEventV2`])
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Correlation id of an event that is not part of a larger operation
+pub const NO_CORRELATION: u64 = 0;
+
+/// Instance storage key holding a contract's last event sequence number
+pub const EVENT_SEQUENCE_KEY: Symbol = symbol_short!("EVT_SEQ");
+
+/// First topic shared by every standardized event
+pub const EVENT_PREFIX: &str = "stellara_event";
+
+/// Instance storage key holding a contract's event namespace
+pub const EVENT_NAMESPACE_KEY: Symbol = symbol_short!("EVT_NS");
+
+/// Whether the crate was built with `lite-events`, which strips metadata and
+/// legacy duplicates from every emitter
+pub const LITE_EVENTS: bool = cfg!(feature = "lite-events");
+
+/// Second-level topics naming the subsystem a contract belongs to. A
+/// contract with a namespace publishes standard events under
+/// `(EVENT_PREFIX, namespace, topic)`, so indexers can subscribe to one
+/// subsystem by topic filter alone; contracts without one keep publishing
+/// under `(EVENT_PREFIX, topic)`.
+pub mod namespaces {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const TOKEN: Symbol = symbol_short!("token");
+    pub const STAKING: Symbol = symbol_short!("staking");
+    pub const GOVERNANCE: Symbol = symbol_short!("gov");
+    pub const DEX: Symbol = symbol_short!("dex");
+    pub const LENDING: Symbol = symbol_short!("lending");
+    pub const TREASURY: Symbol = symbol_short!("treasury");
+}
+
+/// Version 1 of the envelope published under `(EVENT_PREFIX, topic)`.
+/// No longer emitted; convert stored events with
+/// [`EventSchema::upgrade_v1_to_v2`].
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StandardEvent {
+    /// Schema version of this envelope
+    pub version: u32,
+    /// Event topic (one of [`topics`])
+    pub topic: Symbol,
+    /// Contract that emitted the event
+    pub contract: Address,
+    /// User (or admin) primarily affected by the action
+    pub user_address: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+    /// Ledger sequence number
+    pub ledger: u32,
+    /// Event payload
+    pub data: Vec<Val>,
+    /// Indexed key/value pairs for cheap filtering (see [`metadata_keys`])
+    pub metadata: Map<Symbol, Val>,
+}
+
+/// Uniform envelope published under `(EVENT_PREFIX, topic)` so indexers can
+/// consume every contract with a single decoder
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StandardEventV2 {
+    /// Schema version of this envelope
+    pub version: u32,
+    /// Event topic (one of [`topics`])
+    pub topic: Symbol,
+    /// Contract that emitted the event
+    pub contract: Address,
+    /// User (or admin) primarily affected by the action
+    pub user_address: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+    /// Ledger sequence number
+    pub ledger: u32,
+    /// Per-contract event counter, starting at 1. Gaps tell an indexer it
+    /// missed events; 0 marks an event upgraded from v1.
+    pub sequence: u64,
+    /// Shared by every event of one logical operation (e.g. the legs of a
+    /// batch transfer), or [`NO_CORRELATION`]
+    pub correlation_id: u64,
+    /// Typed event payload
+    pub payload: EventPayload,
+    /// Indexed key/value pairs for cheap filtering (see [`metadata_keys`])
+    pub metadata: Map<Symbol, Val>,
+}
+
+/// Instance storage key holding a contract's [`EventConfig`]
+pub const EVENT_CONFIG_KEY: Symbol = symbol_short!("EVT_CFG");
+
+/// Per-contract switches for the two event formats. Contracts without a
+/// stored config emit both, so existing deployments are unaffected until
+/// their admin opts out of the legacy format.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventConfig {
+    /// Publish the legacy `(topic,)` events
+    pub emit_legacy: bool,
+    /// Publish the [`StandardEventV2`] envelopes
+    pub emit_standard: bool,
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        Self {
+            emit_legacy: true,
+            emit_standard: true,
+        }
+    }
+}
+
+impl EventConfig {
+    /// Config of the current contract
+    pub fn load(env: &Env) -> Self {
+        env.storage()
+            .instance()
+            .get(&EVENT_CONFIG_KEY)
+            .unwrap_or_default()
+    }
+
+    /// Store the config of the current contract. Callers are responsible
+    /// for checking that the invoker is the contract's admin.
+    pub fn store(env: &Env, config: &EventConfig) {
+        env.storage().instance().set(&EVENT_CONFIG_KEY, config);
+    }
+}
+
+/// Instance storage key counting a contract's [`EventSubscription`]s
+pub const SUBSCRIBER_COUNT_KEY: Symbol = symbol_short!("EVT_SUBS");
+
+#[contracttype]
+#[derive(Clone)]
+enum SubscriptionKey {
+    EventSubscription(Address),
+}
+
+/// Per-user notification preferences, stored by each contract for its own
+/// events (subscribing on a contract is the contract filter). When the
+/// `user_address` of a standard event has a subscription covering its
+/// topic, the emitter adds `NOTIFY_KEY = true` to the metadata so notifier
+/// services can filter without tracking preferences themselves.
+pub struct EventSubscription;
+
+impl EventSubscription {
+    /// Subscribe `user` to `topics` on the current contract, replacing any
+    /// previous subscription. An empty list subscribes to every topic.
+    pub fn set(env: &Env, user: &Address, topics: Vec<Symbol>) {
+        user.require_auth();
+        let key = SubscriptionKey::EventSubscription(user.clone());
+        if !env.storage().persistent().has(&key) {
+            Self::adjust_count(env, 1);
+        }
+        env.storage().persistent().set(&key, &topics);
+
+        EventEmitter::subscription_changed(
+            env,
+            topics::SUBSCRIBED,
+            SubscriptionEvent {
+                user: user.clone(),
+                topics,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Remove `user`'s subscription. Returns whether one existed.
+    pub fn clear(env: &Env, user: &Address) -> bool {
+        user.require_auth();
+        let key = SubscriptionKey::EventSubscription(user.clone());
+        if !env.storage().persistent().has(&key) {
+            return false;
+        }
+        env.storage().persistent().remove(&key);
+        Self::adjust_count(env, -1);
+
+        EventEmitter::subscription_changed(
+            env,
+            topics::UNSUBSCRIBED,
+            SubscriptionEvent {
+                user: user.clone(),
+                topics: Vec::new(env),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        true
+    }
+
+    /// Topics `user` is subscribed to, empty meaning all of them
+    pub fn get(env: &Env, user: &Address) -> Option<Vec<Symbol>> {
+        env.storage()
+            .persistent()
+            .get(&SubscriptionKey::EventSubscription(user.clone()))
+    }
+
+    /// Whether an event under `topic` should carry the notify hint for
+    /// `user`. Contracts without subscribers skip the persistent read.
+    pub fn wants(env: &Env, user: &Address, topic: &Symbol) -> bool {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&SUBSCRIBER_COUNT_KEY)
+            .unwrap_or(0);
+        if count == 0 {
+            return false;
+        }
+        match Self::get(env, user) {
+            Some(topics) => topics.is_empty() || topics.contains(topic),
+            None => false,
+        }
+    }
+
+    fn adjust_count(env: &Env, delta: i32) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&SUBSCRIBER_COUNT_KEY)
+            .unwrap_or(0);
+        let count = count.saturating_add_signed(delta);
+        env.storage().instance().set(&SUBSCRIBER_COUNT_KEY, &count);
+    }
+}
+
+/// Instance storage key holding a contract's [`EventLogConfig`]
+pub const EVENT_LOG_CONFIG_KEY: Symbol = symbol_short!("EVT_LOG");
+
+/// Largest ring buffer a contract can configure
+pub const MAX_EVENT_LOG_CAPACITY: u32 = 1_000;
+
+/// Most events returned by one [`EventLog::get_events`] call
+pub const MAX_EVENT_LOG_QUERY: u32 = 100;
+
+/// Size and retention of a contract's [`EventLog`]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventLogConfig {
+    /// Number of most recent events kept
+    pub capacity: u32,
+    /// Ledgers an entry stays readable after it was written
+    pub ttl_ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum EventLogKey {
+    EventLogSlot(u32),
+}
+
+/// Opt-in ring buffer of a contract's recent [`StandardEventV2`]s for
+/// light clients that cannot follow the raw event stream.
+///
+/// Event `sequence` n lands in slot `(n - 1) % capacity`, overwriting the
+/// event `capacity` sequences older. Slots live in temporary storage with a
+/// TTL of `ttl_ledgers`, so the network prunes them once they expire and
+/// nobody pays rent on stale history. Every slot write costs storage, which
+/// is why the log is off until a contract enables it.
+pub struct EventLog;
+
+impl EventLog {
+    /// Config of the current contract, `None` while the log is disabled
+    pub fn config(env: &Env) -> Option<EventLogConfig> {
+        env.storage().instance().get(&EVENT_LOG_CONFIG_KEY)
+    }
+
+    /// Enable the log, or resize it. Callers are responsible for checking
+    /// that the invoker is the contract's admin. A capacity of zero
+    /// disables it.
+    pub fn configure(env: &Env, config: &EventLogConfig) {
+        if config.capacity == 0 {
+            env.storage().instance().remove(&EVENT_LOG_CONFIG_KEY);
+            return;
+        }
+        let config = EventLogConfig {
+            capacity: config.capacity.min(MAX_EVENT_LOG_CAPACITY),
+            ttl_ledgers: config.ttl_ledgers,
+        };
+        env.storage().instance().set(&EVENT_LOG_CONFIG_KEY, &config);
+    }
+
+    /// Store `event` if the log is enabled
+    pub fn record(env: &Env, event: &StandardEventV2) {
+        let config = match Self::config(env) {
+            Some(config) => config,
+            None => return,
+        };
+        let key = Self::slot_key(event.sequence, config.capacity);
+        let storage = env.storage().temporary();
+        storage.set(&key, event);
+        storage.extend_ttl(&key, config.ttl_ledgers, config.ttl_ledgers);
+    }
+
+    /// Up to `limit` logged events with sequence `from_seq` or later, oldest
+    /// first. Overwritten and expired events are skipped.
+    pub fn get_events(env: &Env, from_seq: u64, limit: u32) -> Vec<StandardEventV2> {
+        let mut events = Vec::new(env);
+        let config = match Self::config(env) {
+            Some(config) => config,
+            None => return events,
+        };
+        let latest: u64 = env
+            .storage()
+            .instance()
+            .get(&EVENT_SEQUENCE_KEY)
+            .unwrap_or(0);
+        let oldest = latest.saturating_sub(config.capacity as u64) + 1;
+        let limit = limit.min(MAX_EVENT_LOG_QUERY);
+        let now = env.ledger().sequence();
+
+        let mut sequence = from_seq.max(oldest);
+        while sequence <= latest && events.len() < limit {
+            let key = Self::slot_key(sequence, config.capacity);
+            if let Some(event) = env.storage().temporary().get::<_, StandardEventV2>(&key) {
+                let live = event.ledger.saturating_add(config.ttl_ledgers) >= now;
+                if event.sequence == sequence && live {
+                    events.push_back(event);
+                }
+            }
+            sequence += 1;
+        }
+        events
+    }
+
+    fn slot_key(sequence: u64, capacity: u32) -> EventLogKey {
+        EventLogKey::EventLogSlot((sequence.saturating_sub(1) % capacity as u64) as u32)
+    }
+}
+
+/// How a reader on one schema version can consume an event of another
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Compatibility {
+    /// Same version; decode directly
+    Native,
+    /// Older event; decode then upgrade to the reader's version
+    Upgradable,
+    /// Newer or unknown event; the reader must be updated
+    Unsupported,
+}
+
+/// Schema checks and conversions for decoded standard events
+pub struct EventSchema;
+
+impl EventSchema {
+    /// Whether events of `version` can be decoded by this build
+    pub fn is_supported(version: u32) -> bool {
+        Self::compatibility(CURRENT_VERSION, version) != Compatibility::Unsupported
+    }
+
+    /// Compatibility of an event of `event_version` for a reader on
+    /// `reader_version`. Every version upgrades to the next, so anything
+    /// between 1 and the reader's version can be read.
+    pub fn compatibility(reader_version: u32, event_version: u32) -> Compatibility {
+        if event_version == 0 || reader_version == 0 || event_version > reader_version {
+            Compatibility::Unsupported
+        } else if event_version == reader_version {
+            Compatibility::Native
+        } else {
+            Compatibility::Upgradable
+        }
+    }
+
+    /// Validate the envelope of a decoded v1 event
+    pub fn validate(event: &StandardEvent) -> bool {
+        event.version == VERSION_1 && !event.data.is_empty()
+    }
+
+    /// Validate the envelope of a decoded v2 event
+    pub fn validate_v2(event: &StandardEventV2) -> bool {
+        let has_payload = match &event.payload {
+            EventPayload::Raw(data) => !data.is_empty(),
+            _ => true,
+        };
+        event.version == CURRENT_VERSION && has_payload
+    }
+
+    /// Convert a v1 event. The payload is decoded into its typed variant
+    /// when the topic identifies one and falls back to
+    /// [`EventPayload::Raw`] otherwise; the sequence is unknown and set to 0.
+    pub fn upgrade_v1_to_v2(env: &Env, event: StandardEvent) -> Option<StandardEventV2> {
+        if !Self::validate(&event) {
+            return None;
+        }
+        let payload = event
+            .data
+            .get(0)
+            .and_then(|value| EventPayload::from_v1(env, &event.topic, &value))
+            .unwrap_or_else(|| EventPayload::Raw(event.data.clone()));
+        Some(StandardEventV2 {
+            version: CURRENT_VERSION,
+            topic: event.topic,
+            contract: event.contract,
+            user_address: event.user_address,
+            timestamp: event.timestamp,
+            ledger: event.ledger,
+            sequence: 0,
+            correlation_id: NO_CORRELATION,
+            payload,
+            metadata: event.metadata,
+        })
+    }
+
+    /// Decode the data of an event published under [`EVENT_PREFIX`],
+    /// whichever supported version it was emitted with. The envelope's
+    /// `version` field is read first, since decoding into the wrong
+    /// envelope struct traps rather than failing.
+    pub fn decode(env: &Env, value: &Val) -> Option<StandardEventV2> {
+        let fields = Map::<Symbol, Val>::try_from_val(env, value).ok()?;
+        let version = u32::try_from_val(env, &fields.get(symbol_short!("version"))?).ok()?;
+        match Self::compatibility(CURRENT_VERSION, version) {
+            Compatibility::Native => {
+                let event = StandardEventV2::try_from_val(env, value).ok()?;
+                Self::validate_v2(&event).then_some(event)
+            }
+            Compatibility::Upgradable => {
+                let event = StandardEvent::try_from_val(env, value).ok()?;
+                Self::upgrade_v1_to_v2(env, event)
+            }
+            Compatibility::Unsupported => None,
+        }
+    }
 }
 
 // =============================================================================
@@ -74,6 +791,118 @@ pub struct TradeExecutedEvent {
     pub timestamp: u64,
 }
 
+/// Emitted when a keeper executes a limit or stop order whose trigger
+/// price was crossed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OrderTriggeredEvent {
+    /// Order identifier
+    pub order_id: u64,
+    /// Order owner
+    pub owner: Address,
+    /// Keeper that executed the order
+    pub keeper: Address,
+    /// Trading pair symbol
+    pub pair: Symbol,
+    /// Price the order was set to trigger at
+    pub trigger_price: i128,
+    /// Pair TWAP that crossed it
+    pub price: i128,
+    /// Amount sold
+    pub amount_in: i128,
+    /// Amount received by the owner, after the execution fee
+    pub amount_out: i128,
+    /// Execution fee paid to the keeper
+    pub fee: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Summary of a multi-hop swap, emitted after one `TRADE_EXECUTED` per
+/// hop that share its `route_id` as correlation id
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RouteExecutedEvent {
+    /// Route identifier, also the correlation id of its hops
+    pub route_id: u64,
+    /// Address of the trader
+    pub trader: Address,
+    /// Tokens swapped through, from input to output
+    pub path: Vec<Address>,
+    /// Amount of the first token sold
+    pub amount_in: i128,
+    /// Amount of the last token received
+    pub amount_out: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Summary of a settled batch auction, emitted after one
+/// `TRADE_EXECUTED` per filled order that share its `batch_id` as
+/// correlation id
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BatchSettledEvent {
+    /// Batch identifier, also the correlation id of its fills
+    pub batch_id: u64,
+    /// Address that settled the batch
+    pub settled_by: Address,
+    /// Single price every fill in the batch traded at (0 if nothing
+    /// crossed)
+    pub clearing_price: i128,
+    /// Base amount matched
+    pub volume: i128,
+    /// Orders in the batch
+    pub orders: u32,
+    /// Orders at least partly filled
+    pub filled: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Event emitted when a trader commits to a hidden order, and when the
+/// bond of a commitment that was never revealed is slashed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OrderCommitEvent {
+    /// Batch the order will be matched in
+    pub batch_id: u64,
+    /// Commitment index within the batch
+    pub commit_id: u32,
+    /// Committing trader
+    pub trader: Address,
+    /// Hash of the hidden order and its salt
+    pub commitment: BytesN<32>,
+    /// Bond posted (or slashed)
+    pub bond: i128,
+    /// Last ledger the order can be revealed in
+    pub reveal_deadline: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Event emitted when a committed order is revealed and joins its batch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OrderRevealEvent {
+    /// Batch the order joined
+    pub batch_id: u64,
+    /// Commitment the order opened
+    pub commit_id: u32,
+    /// Order id within the batch
+    pub order_id: u32,
+    /// Trader who revealed
+    pub trader: Address,
+    /// Whether the order buys the base token
+    pub is_buy: bool,
+    /// Base amount
+    pub amount: i128,
+    /// Limit price
+    pub limit_price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
 /// Event emitted when contract is paused
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -94,6 +923,82 @@ pub struct ContractUnpausedEvent {
     pub timestamp: u64,
 }
 
+/// Emitted when the pause registry pauses or resumes an operation
+/// platform-wide
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PauseChangedEvent {
+    /// Operation name (e.g. `swap`, `borrow`, or `all`)
+    pub operation: Symbol,
+    pub paused: bool,
+    /// Governance account that made the change
+    pub changed_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a reporting contract adds activity to an account's
+/// reputation
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReputationEvent {
+    pub account: Address,
+    /// Activity name (e.g. `staking`, `gov`, `trading`)
+    pub activity: Symbol,
+    /// Contract that reported the activity
+    pub reporter: Address,
+    /// Points the activity added
+    pub points: i128,
+    /// Score after the update
+    pub score: i128,
+    /// Tier after the update
+    pub tier: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a name is registered or renewed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NameEvent {
+    pub name: Symbol,
+    pub owner: Address,
+    /// Account that paid the fee; anyone can renew a name
+    pub payer: Address,
+    pub fee: i128,
+    pub expires_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a name changes owner
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NameTransferEvent {
+    pub name: Symbol,
+    pub from: Address,
+    pub to: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an attestor issues or revokes a claim about an address
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AttestationEvent {
+    pub subject: Address,
+    /// Claim name (e.g. `kyc_tier2`)
+    pub claim: Symbol,
+    /// Attestor that issued the claim
+    pub attestor: Address,
+    /// When the claim lapses, or 0 if it never does
+    pub expires_at: u64,
+    /// Account that made the change: the attestor, or the admin revoking
+    pub changed_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
 /// Event emitted when a fee is collected
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -110,6 +1015,91 @@ pub struct FeeCollectedEvent {
     pub timestamp: u64,
 }
 
+/// Event emitted when an AMM records a new price observation
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PriceUpdatedEvent {
+    /// Trading pair symbol
+    pub pair: Symbol,
+    /// Spot price of the base asset in quote units (scaled)
+    pub price: i128,
+    /// Cumulative price accumulator after this observation
+    pub price_cumulative: i128,
+    /// Block timestamp of the observation
+    pub timestamp: u64,
+}
+
+/// Event emitted when a flash loan is repaid within the same invocation
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FlashLoanEvent {
+    /// Contract that received and repaid the loan
+    pub receiver: Address,
+    /// Token borrowed
+    pub token: Address,
+    /// Amount borrowed
+    pub amount: i128,
+    /// Fee paid on top of the principal
+    pub fee: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Event emitted when a trader binds a referrer
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReferrerBoundEvent {
+    /// Trader being referred
+    pub user: Address,
+    /// Referrer receiving fee rebates
+    pub referrer: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Event emitted when a fill reduces a position and realizes PnL
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PnlRealizedEvent {
+    /// Position owner
+    pub user: Address,
+    /// Trading pair symbol
+    pub pair: Symbol,
+    /// Base amount of the position closed
+    pub closed_amount: i128,
+    /// Average entry price of the closed amount
+    pub entry_price: i128,
+    /// Price it was closed at
+    pub exit_price: i128,
+    /// PnL realized by this fill, in quote units
+    pub pnl: i128,
+    /// PnL realized over the position's lifetime
+    pub total_realized_pnl: i128,
+    /// Net position left (positive long, negative short)
+    pub position: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Event emitted when a trader's rolling volume moves them into a
+/// different fee tier
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FeeTierChangedEvent {
+    /// Trader whose tier changed
+    pub trader: Address,
+    /// Previous tier (0 = no discount)
+    pub old_tier: u32,
+    /// New tier (0 = no discount)
+    pub new_tier: u32,
+    /// Fee discount of the new tier, in basis points
+    pub discount_bps: u32,
+    /// Rolling 30-day volume that placed the trader in the new tier
+    pub volume: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
 // =============================================================================
 // Governance Events
 // =============================================================================
@@ -190,19 +1180,135 @@ pub struct ProposalCancelledEvent {
     pub timestamp: u64,
 }
 
-// =============================================================================
-// Social Rewards Events
-// =============================================================================
+/// Event emitted when an optimistic proposal is challenged and sent to a
+/// full vote
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalChallengedEvent {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Address that posted the bond
+    pub challenger: Address,
+    /// Token the bond was posted in
+    pub bond_token: Address,
+    /// Bond amount
+    pub bond: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-/// Event emitted when a reward is added/granted to a user
+/// Event emitted when a vote is cast on a governor proposal
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct RewardAddedEvent {
-    /// Unique reward identifier
-    pub reward_id: u64,
-    /// User receiving the reward
-    pub user: Address,
-    /// Reward amount
+pub struct VoteCastEvent {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Voter
+    pub voter: Address,
+    /// Whether the vote is in favour
+    pub support: bool,
+    /// Registered voting asset the proposal counts
+    pub asset_id: u32,
+    /// Contract the voting power was read from
+    pub asset: Address,
+    /// How the voting power was read
+    pub source: VotingSource,
+    /// Power read from the asset, before weighting
+    pub raw_power: i128,
+    /// Power counted after applying the asset's weight
+    pub power: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when stake behind a conviction-voting proposal changes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ConvictionUpdatedEvent {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Supporter who added or withdrew stake
+    pub supporter: Address,
+    /// Total stake now behind the proposal
+    pub staked: i128,
+    /// Conviction at the time of the change
+    pub conviction: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a conviction-voting proposal reaches its threshold and is
+/// paid out
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ConvictionPassedEvent {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Recipient of the funds
+    pub beneficiary: Address,
+    /// Amount paid out
+    pub amount: i128,
+    /// Conviction when the proposal passed
+    pub conviction: i128,
+    /// Conviction that was required
+    pub threshold: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a closed proposal is reduced to its summary record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalArchivedEvent {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Address that archived the proposal
+    pub archiver: Address,
+    /// Final status of the proposal
+    pub status: ProposalStatus,
+    /// Approvals the proposal received
+    pub approvals_count: u32,
+    /// Contract hash the proposal carried
+    pub new_contract_hash: Symbol,
+    /// Ledger the proposal was closed in
+    pub closed_ledger: u32,
+    /// Approval records deleted
+    pub approvals_cleared: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Summary of proposals executed together in one batch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalBatchExecutedEvent {
+    /// Correlation id shared with the batch's `PROPOSAL_EXECUTED` events
+    pub batch_id: u64,
+    /// Address that executed
+    pub executor: Address,
+    /// Proposals executed, in order
+    pub executed: Vec<u64>,
+    /// Proposals skipped because they could not be executed
+    pub skipped: Vec<u64>,
+    /// Whether failures were skipped instead of reverting the batch
+    pub best_effort: bool,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Social Rewards Events
+// =============================================================================
+
+/// Event emitted when a reward is added/granted to a user
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardAddedEvent {
+    /// Unique reward identifier
+    pub reward_id: u64,
+    /// User receiving the reward
+    pub user: Address,
+    /// Reward amount
     pub amount: i128,
     /// Type of reward (e.g., "referral", "engagement", "achievement")
     pub reward_type: Symbol,
@@ -306,6 +1412,23 @@ pub struct ClaimPaidEvent {
     pub timestamp: u64,
 }
 
+/// Emitted when a claim against the protocol insurance fund is filed or
+/// rejected
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InsuranceClaimEvent {
+    /// Claim identifier
+    pub claim_id: u64,
+    /// Account the claim would pay
+    pub claimant: Address,
+    /// Amount claimed
+    pub amount: i128,
+    /// Incident, or why the claim was rejected
+    pub reason: Symbol,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
 /// Emitted when a liquidity provider deposits into the risk pool
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -331,104 +1454,3942 @@ pub struct LiquidityWithdrawnEvent {
 }
 
 // =============================================================================
-// Event Emission Helpers
+// Streaming Payment Events
 // =============================================================================
 
-use soroban_sdk::Env;
+/// Emitted when a payment stream is opened
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamCreatedEvent {
+    /// Stream identifier
+    pub stream_id: u64,
+    /// Address funding the stream
+    pub sender: Address,
+    /// Address receiving the stream
+    pub recipient: Address,
+    /// Token being streamed
+    pub token: Address,
+    /// Total amount escrowed for the stream
+    pub deposit: i128,
+    /// Tokens released per second
+    pub rate_per_second: i128,
+    /// Timestamp at which the stream starts flowing
+    pub start_time: u64,
+    /// Timestamp at which the stream stops flowing
+    pub stop_time: u64,
+}
 
-/// Helper trait for emitting standardized events
-pub struct EventEmitter;
+/// Emitted when the recipient withdraws from a stream
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamWithdrawnEvent {
+    /// Stream identifier
+    pub stream_id: u64,
+    /// Recipient that withdrew
+    pub recipient: Address,
+    /// Amount withdrawn
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-impl EventEmitter {
-    /// Emit a trade executed event
-    pub fn trade_executed(env: &Env, event: TradeExecutedEvent) {
-        env.events().publish((topics::TRADE_EXECUTED,), event);
-    }
+/// Emitted when a stream is cancelled and the balance is split
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamCancelledEvent {
+    /// Stream identifier
+    pub stream_id: u64,
+    /// Address that cancelled
+    pub cancelled_by: Address,
+    /// Amount paid out to the recipient
+    pub recipient_amount: i128,
+    /// Amount refunded to the sender
+    pub sender_amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a contract paused event
-    pub fn contract_paused(env: &Env, event: ContractPausedEvent) {
-        env.events().publish((topics::CONTRACT_PAUSED,), event);
-    }
+// =============================================================================
+// Lending Market Events
+// =============================================================================
 
-    /// Emit a contract unpaused event
-    pub fn contract_unpaused(env: &Env, event: ContractUnpausedEvent) {
-        env.events().publish((topics::CONTRACT_UNPAUSED,), event);
-    }
+/// Emitted for deposits, withdrawals, borrows and repayments in the lending
+/// market. The action is identified by the event topic.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LendingActionEvent {
+    /// Account whose position changed
+    pub user: Address,
+    /// Asset moved
+    pub asset: Address,
+    /// Amount moved
+    pub amount: i128,
+    /// Oracle price of the asset at the time of the action
+    pub price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a fee collected event
-    pub fn fee_collected(env: &Env, event: FeeCollectedEvent) {
-        env.events().publish((topics::FEE_COLLECTED,), event);
-    }
+/// Emitted when an underwater position is partially repaid by a liquidator
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LiquidationEvent {
+    /// Account that repaid the debt
+    pub liquidator: Address,
+    /// Account that was liquidated
+    pub borrower: Address,
+    /// Asset the debt was repaid in
+    pub debt_asset: Address,
+    /// Asset seized from the borrower
+    pub collateral_asset: Address,
+    /// Debt repaid by the liquidator
+    pub repaid: i128,
+    /// Collateral transferred to the liquidator, including the bonus
+    pub seized: i128,
+    /// Oracle price of the debt asset
+    pub debt_price: i128,
+    /// Oracle price of the collateral asset
+    pub collateral_price: i128,
+    /// Borrower health factor before liquidation
+    pub health_factor: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a proposal created event
-    pub fn proposal_created(env: &Env, event: ProposalCreatedEvent) {
-        env.events().publish((topics::PROPOSAL_CREATED,), event);
-    }
+/// Emitted when a collateral auction starts or restarts
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionStartEvent {
+    pub auction_id: u64,
+    /// Account whose collateral is sold
+    pub borrower: Address,
+    pub collateral_asset: Address,
+    /// Asset bids are paid in
+    pub debt_asset: Address,
+    /// Collateral for sale
+    pub lot: i128,
+    /// Debt to cover
+    pub tab: i128,
+    /// Opening price per unit of collateral
+    pub start_price: i128,
+    /// Seconds between price cuts
+    pub step: u64,
+    /// Share of the price kept at each cut, in basis points
+    pub cut_bps: u32,
+    /// Seconds until the auction needs a restart
+    pub tail: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a proposal approved event
-    pub fn proposal_approved(env: &Env, event: ProposalApprovedEvent) {
-        env.events().publish((topics::PROPOSAL_APPROVED,), event);
-    }
+/// Emitted when a keeper buys part of an auction's lot
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionTakeEvent {
+    pub auction_id: u64,
+    /// Buyer
+    pub taker: Address,
+    /// Collateral bought
+    pub collateral: i128,
+    /// Debt asset paid
+    pub paid: i128,
+    /// Price per unit of collateral
+    pub price: i128,
+    /// Collateral still for sale
+    pub lot: i128,
+    /// Debt still to cover
+    pub tab: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a proposal rejected event
-    pub fn proposal_rejected(env: &Env, event: ProposalRejectedEvent) {
-        env.events().publish((topics::PROPOSAL_REJECTED,), event);
-    }
+/// Emitted when an auction closes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionSettleEvent {
+    pub auction_id: u64,
+    pub borrower: Address,
+    /// Unsold collateral handed back to the borrower
+    pub returned: i128,
+    /// Debt the sale failed to cover
+    pub bad_debt: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a proposal executed event
-    pub fn proposal_executed(env: &Env, event: ProposalExecutedEvent) {
-        env.events().publish((topics::PROPOSAL_EXECUTED,), event);
-    }
+/// Emitted when a price move trips a circuit breaker
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CircuitTripEvent {
+    pub feed: PriceFeed,
+    /// Price the move was measured from
+    pub reference_price: i128,
+    /// Price that tripped the breaker
+    pub price: i128,
+    /// Size of the move in basis points
+    pub move_bps: u32,
+    /// Seconds the reference price was in force for
+    pub window: u64,
+    /// End of the cooldown
+    pub tripped_until: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a proposal cancelled event
-    pub fn proposal_cancelled(env: &Env, event: ProposalCancelledEvent) {
-        env.events().publish((topics::PROPOSAL_CANCELLED,), event);
-    }
+/// Emitted when a tripped circuit breaker closes again
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CircuitResetEvent {
+    pub feed: PriceFeed,
+    /// Reference price before the trip
+    pub reference_price: i128,
+    /// Price that tripped the breaker
+    pub tripped_price: i128,
+    /// New reference price
+    pub price: i128,
+    /// Whether an admin cleared the trip before its cooldown ended
+    pub manual: bool,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a reward added event
-    pub fn reward_added(env: &Env, event: RewardAddedEvent) {
-        env.events().publish((topics::REWARD_ADDED,), event);
-    }
+// =============================================================================
+// Deployment Events
+// =============================================================================
 
-    /// Emit a reward claimed event
-    pub fn reward_claimed(env: &Env, event: RewardClaimedEvent) {
-        env.events().publish((topics::REWARD_CLAIMED,), event);
-    }
+/// Emitted when the factory deploys and initializes a contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractDeployedEvent {
+    /// Address of the new contract
+    pub address: Address,
+    /// Kind of contract deployed (e.g. `amm_pool`, `vesting`)
+    pub kind: Symbol,
+    /// Account that requested the deployment
+    pub deployer: Address,
+    /// Wasm hash the contract was deployed from
+    pub wasm_hash: BytesN<32>,
+    /// Initialization function called on the new contract
+    pub init_fn: Symbol,
+    /// Arguments passed to the initialization function
+    pub init_args: Vec<Val>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    // ── Parametric insurance emitters ─────────────────────────────────────────
+/// Emitted when a name in the contract registry is pointed at a new address
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RegistryUpdatedEvent {
+    /// Registered name (e.g. `token`, `staking`)
+    pub name: Symbol,
+    /// Address the name now resolves to
+    pub address: Address,
+    /// Version of the entry, starting at 1
+    pub version: u32,
+    /// Governance account that approved the update
+    pub updated_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a policy created event
-    pub fn policy_created(env: &Env, event: PolicyCreatedEvent) {
-        env.events().publish((topics::POLICY_CREATED,), event);
-    }
+// =============================================================================
+// NFT Events
+// =============================================================================
 
-    /// Emit a policy cancelled event
-    pub fn policy_cancelled(env: &Env, event: PolicyCancelledEvent) {
-        env.events().publish((topics::POLICY_CANCELLED,), event);
-    }
+/// Emitted when an NFT is minted or burned
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NftEvent {
+    /// Token id
+    pub id: u64,
+    /// Owner receiving (mint) or giving up (burn) the token
+    pub owner: Address,
+    /// Token metadata URI
+    pub uri: String,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a policy expired event
-    pub fn policy_expired(env: &Env, event: PolicyExpiredEvent) {
-        env.events().publish((topics::POLICY_EXPIRED,), event);
-    }
+/// Emitted when an NFT changes hands
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NftTransferEvent {
+    /// Token id
+    pub id: u64,
+    /// Previous owner
+    pub from: Address,
+    /// New owner
+    pub to: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a trigger activated event
-    pub fn trigger_activated(env: &Env, event: TriggerActivatedEvent) {
-        env.events().publish((topics::TRIGGER_ACTIVATED,), event);
-    }
+/// Emitted when an NFT owner approves a spender for one token
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NftApprovalEvent {
+    /// Token id
+    pub id: u64,
+    /// Token owner
+    pub owner: Address,
+    /// Address allowed to transfer the token
+    pub spender: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a claim paid event
-    pub fn claim_paid(env: &Env, event: ClaimPaidEvent) {
-        env.events().publish((topics::CLAIM_PAID,), event);
-    }
+// =============================================================================
+// Multi-token Events
+// =============================================================================
 
-    /// Emit a liquidity deposited event
-    pub fn liquidity_deposited(env: &Env, event: LiquidityDepositedEvent) {
-        env.events().publish((topics::LIQUIDITY_DEPOSITED,), event);
-    }
+/// Emitted once per mint or burn call, however many token ids it covers.
+/// `ids[i]` was minted to or burned from `account` in `amounts[i]`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MultiTokenSupplyEvent {
+    /// Holder receiving (mint) or giving up (burn) the tokens
+    pub account: Address,
+    pub ids: Vec<u64>,
+    pub amounts: Vec<i128>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
 
-    /// Emit a liquidity withdrawn event
-    pub fn liquidity_withdrawn(env: &Env, event: LiquidityWithdrawnEvent) {
-        env.events().publish((topics::LIQUIDITY_WITHDRAWN,), event);
+/// Emitted once per transfer call, however many token ids it covers
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MultiTokenTransferEvent {
+    /// Address that authorized the transfer: `from` or an approved operator
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub ids: Vec<u64>,
+    pub amounts: Vec<i128>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a token id is created or its URI changes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MultiTokenUriEvent {
+    pub id: u64,
+    pub uri: String,
+    /// Admin that made the change
+    pub updated_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a soulbound badge is issued (or reissued) or revoked
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BadgeEvent {
+    /// Badge token id
+    pub id: u64,
+    pub holder: Address,
+    /// Issuer or admin that made the change
+    pub issuer: Address,
+    /// When the badge lapses, or 0 if it never does (0 on revocation)
+    pub expires_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Marketplace Events
+// =============================================================================
+
+/// Emitted when an NFT is listed for sale or its listing is cancelled
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ListingEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Seller
+    pub seller: Address,
+    /// Asking price in the payment token
+    pub price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an NFT sale settles, from a listing or an accepted offer
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SaleEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Seller
+    pub seller: Address,
+    /// Buyer
+    pub buyer: Address,
+    /// Total price paid by the buyer
+    pub price: i128,
+    /// Marketplace fee taken from the price
+    pub fee: i128,
+    /// Creator royalty taken from the price
+    pub royalty: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an offer is made or withdrawn
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OfferEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Bidder
+    pub buyer: Address,
+    /// Escrowed offer amount
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a creator royalty is paid out of a sale
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoyaltyPaidEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Royalty receiver
+    pub receiver: Address,
+    /// Royalty amount
+    pub amount: i128,
+    /// Sale price the royalty was computed from
+    pub sale_price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a bid is placed in an English auction
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BidEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Bidder
+    pub bidder: Address,
+    /// Escrowed bid amount
+    pub amount: i128,
+    /// Auction end time after any anti-sniping extension
+    pub end_time: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an auction closes. With no winning bid, `winner` is the
+/// seller and `amount` is zero.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionSettledEvent {
+    /// NFT collection contract
+    pub nft: Address,
+    /// Token id
+    pub id: u64,
+    /// Seller
+    pub seller: Address,
+    /// Address receiving the token
+    pub winner: Address,
+    /// Winning price
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Bridge Events
+// =============================================================================
+
+/// Emitted when tokens are locked for minting on another chain
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BridgeLockEvent {
+    /// Account locking the tokens
+    pub sender: Address,
+    /// Token locked
+    pub token: Address,
+    /// Amount locked
+    pub amount: i128,
+    /// Destination chain id
+    pub dest_chain: u32,
+    /// Recipient on the destination chain, in that chain's encoding
+    pub recipient: Bytes,
+    /// Outbound nonce, unique per bridge
+    pub nonce: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when validators release tokens locked on another chain
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BridgeReleaseEvent {
+    /// Account receiving the tokens
+    pub recipient: Address,
+    /// Token released
+    pub token: Address,
+    /// Amount released
+    pub amount: i128,
+    /// Inbound nonce the validators attested to
+    pub nonce: u64,
+    /// Number of validator signatures accepted
+    pub signers: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Subscription Events
+// =============================================================================
+
+/// Emitted when a user sets or clears an [`EventSubscription`]
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionEvent {
+    /// Subscribing user
+    pub user: Address,
+    /// Subscribed topics; empty means all topics (or none, when cleared)
+    pub topics: Vec<Symbol>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Keeper Events
+// =============================================================================
+
+/// Emitted when a keeper task is registered or cancelled
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TaskEvent {
+    /// Task id
+    pub task_id: u64,
+    /// Task owner
+    pub owner: Address,
+    /// Contract the task calls
+    pub target: Address,
+    /// Function the task calls
+    pub function: Symbol,
+    /// Seconds between executions
+    pub interval: u64,
+    /// Bounty paid per execution
+    pub payment: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a keeper performs a task
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TaskPerformedEvent {
+    /// Task id
+    pub task_id: u64,
+    /// Keeper that performed the task
+    pub keeper: Address,
+    /// Bounty paid to the keeper
+    pub payment: i128,
+    /// Earliest time of the next execution
+    pub next_run: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a relayer is reimbursed for a sponsored call
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RelayReimbursedEvent {
+    /// Relayer that submitted the call
+    pub relayer: Address,
+    /// Contract called
+    pub target: Address,
+    /// Function called
+    pub function: Symbol,
+    /// Amount reimbursed
+    pub amount: i128,
+    /// Sponsorship epoch charged
+    pub epoch: u64,
+    /// Reimbursed to this relayer in the epoch, including this call
+    pub relayer_spent: i128,
+    /// Sponsorship budget left in the epoch
+    pub budget_remaining: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted on an audited entry point's first call and periodically after
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuditEvent {
+    /// Entry point called
+    pub function: Symbol,
+    /// Successful calls so far, including this one
+    pub calls: u64,
+    /// Caller of this call
+    pub last_caller: Address,
+    /// Ledger of the first recorded call
+    pub first_ledger: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted the first time a contract acts in a new epoch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EpochRolloverEvent {
+    /// Epoch that started
+    pub epoch: u64,
+    /// Last epoch the contract acted in
+    pub previous_epoch: u64,
+    /// Start of `epoch`
+    pub epoch_start: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Vault Events
+// =============================================================================
+
+/// Emitted when assets enter or leave a share-based vault. The totals are
+/// taken after the action, so `total_assets / total_shares` is the share
+/// price indexers can track for APY.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VaultEvent {
+    /// Depositor or withdrawer
+    pub user: Address,
+    /// Underlying asset
+    pub asset: Address,
+    /// Assets moved
+    pub assets: i128,
+    /// Shares minted or burned
+    pub shares: i128,
+    /// Assets managed by the vault after the action
+    pub total_assets: i128,
+    /// Shares outstanding after the action
+    pub total_shares: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted once per strategy when a vault harvests, so yield can be
+/// attributed to the strategy that earned it
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct HarvestEvent {
+    /// Strategy harvested
+    pub strategy: Address,
+    /// Underlying asset
+    pub asset: Address,
+    /// Profit returned to the vault
+    pub profit: i128,
+    /// Assets the strategy manages after the harvest
+    pub strategy_assets: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Vote-Escrow Events
+// =============================================================================
+
+/// Emitted when a vote-escrow lock is created, topped up, extended or
+/// withdrawn
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VeLockEvent {
+    /// Lock owner
+    pub user: Address,
+    /// Tokens added to (or withdrawn from) the lock
+    pub amount: i128,
+    /// Tokens locked after the action
+    pub locked: i128,
+    /// When the lock expires
+    pub unlock_time: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Gauge Events
+// =============================================================================
+
+/// Emitted when a vote-escrow holder directs voting power to a gauge
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GaugeVoteEvent {
+    /// Voter
+    pub user: Address,
+    /// Gauge voted for
+    pub gauge_id: u32,
+    /// Epoch the vote counts in
+    pub epoch: u64,
+    /// Share of the voter's power given to the gauge
+    pub weight_bps: u32,
+    /// Voting power given to the gauge
+    pub power: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when gauge weights are finalized for a new epoch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GaugeEpochEvent {
+    /// Epoch the weights apply to
+    pub epoch: u64,
+    /// Voting power cast in the previous epoch
+    pub total_votes: i128,
+    /// Weight of each gauge in basis points, indexed by gauge id
+    pub weights: Vec<u32>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when incentives are deposited for, or claimed from, a gauge
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BribeEvent {
+    /// Depositor or claiming voter
+    pub account: Address,
+    /// Gauge the incentive is attached to
+    pub gauge_id: u32,
+    /// Voting epoch the incentive rewards
+    pub epoch: u64,
+    /// Incentive token
+    pub token: Address,
+    /// Amount deposited or claimed
+    pub amount: i128,
+    /// Incentive still unclaimed for this gauge, epoch and token
+    pub remaining: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Staking Position Events
+// =============================================================================
+
+/// Emitted when a staking position is minted or burned
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionEvent {
+    /// Position id
+    pub id: u64,
+    /// Owner receiving (mint) or giving up (burn) the position
+    pub owner: Address,
+    /// Staked principal
+    pub amount: i128,
+    /// Timestamp at which the lock ends
+    pub lock_end: u64,
+    /// Annual reward rate of the position in basis points
+    pub multiplier_bps: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when principal is added to (`STAKE`) or partly withdrawn from
+/// (`UNSTAKE`) a staking position
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionStakeEvent {
+    /// Position id
+    pub id: u64,
+    pub owner: Address,
+    /// Principal added, or withdrawn before any penalty
+    pub amount: i128,
+    /// Principal left in the position
+    pub total: i128,
+    /// Early withdrawal penalty withheld; 0 for top-ups
+    pub penalty: i128,
+    /// Timestamp at which the lock ends, after the change
+    pub lock_end: u64,
+    /// Annual reward rate of the position in basis points, after the change
+    pub multiplier_bps: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a staker starts an unstake cooldown
+/// (`UNSTAKE_REQUESTED`), cancels it (`UNSTAKE_CANCELLED`) or withdraws
+/// once it has elapsed (`UNSTAKE`)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnstakeRequestEvent {
+    pub user: Address,
+    /// Principal to be returned, after any early withdrawal penalty
+    pub amount: i128,
+    /// When the principal can be withdrawn
+    pub available_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when staking rewards are claimed, for a stake or a position
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardsClaimedEvent {
+    /// Staker receiving the rewards
+    pub user: Address,
+    /// Position claimed for, or `None` for the plain stake
+    pub position_id: Option<u64>,
+    /// Rewards paid out, boost included
+    pub amount: i128,
+    /// Boost applied in basis points; 10_000 when none
+    pub multiplier_bps: u32,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a staking position changes hands
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionTransferEvent {
+    /// Position id
+    pub id: u64,
+    /// Previous owner
+    pub from: Address,
+    /// New owner
+    pub to: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when AMM liquidity shares are staked or unstaked
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LpStakeEvent {
+    /// Liquidity provider
+    pub user: Address,
+    /// AMM pair the shares belong to
+    pub pair: Symbol,
+    /// Stake id
+    pub stake_id: u64,
+    /// Shares staked or returned
+    pub amount: i128,
+    /// Lock multiplier in basis points; 10_000 when unlocked
+    pub multiplier_bps: u32,
+    /// When the shares can be unstaked
+    pub unlock_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an unstaking LP is compensated for impermanent loss
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IlCompensationEvent {
+    /// Liquidity provider
+    pub user: Address,
+    /// AMM pair the shares belong to
+    pub pair: Symbol,
+    /// Stake id
+    pub stake_id: u64,
+    /// Impermanent loss between entry and exit, in basis points
+    pub il_bps: u32,
+    /// Governance tokens paid
+    pub amount: i128,
+    /// Pair TWAP when the shares were staked
+    pub entry_price: i128,
+    /// Pair TWAP when they were unstaked
+    pub exit_price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Admin Events
+// =============================================================================
+
+/// Emitted when a contract's admin is replaced
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminChangedEvent {
+    /// Admin before the change
+    pub previous_admin: Address,
+    /// Admin after the change
+    pub new_admin: Address,
+    /// How the change happened (e.g. `recovery`)
+    pub reason: Symbol,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when the admin proposes handing the contract to a new admin
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminTransferProposedEvent {
+    /// Admin proposing the transfer
+    pub current_admin: Address,
+    /// Address that must accept to become admin
+    pub proposed_admin: Address,
+    /// Last timestamp at which the proposal can be accepted
+    pub expires_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Token & Treasury Events
+// =============================================================================
+
+/// Emitted when tokens leave a contract on behalf of the protocol
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TransferEvent {
+    /// Sending address
+    pub from: Address,
+    /// Receiving address
+    pub to: Address,
+    /// Token transferred
+    pub token: Address,
+    /// Amount transferred
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a rebasing token's index moves, changing every holder's
+/// balance at once
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RebaseEvent {
+    pub token: Address,
+    /// Index before the update
+    pub previous_index: i128,
+    /// Index balances now scale by
+    pub index: i128,
+    /// Module that moved the index
+    pub updated_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an underlying asset is wrapped or unwrapped 1:1
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct WrapEvent {
+    /// Account depositing (wrap) or withdrawing (unwrap)
+    pub account: Address,
+    /// Underlying asset contract
+    pub underlying: Address,
+    /// Amount wrapped or unwrapped
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when tokens are created
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MintEvent {
+    /// Account credited
+    pub to: Address,
+    /// Amount created
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when tokens are destroyed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BurnEvent {
+    /// Account whose balance was reduced
+    pub from: Address,
+    /// Amount destroyed
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an account is frozen or unfrozen
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuthorizationChangedEvent {
+    /// Account affected
+    pub account: Address,
+    /// Address that made the change
+    pub changed_by: Address,
+    /// Whether the account may now send and receive
+    pub authorized: bool,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an owner sets a spender's allowance
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ApprovalEvent {
+    /// Token owner
+    pub owner: Address,
+    /// Address allowed to spend
+    pub spender: Address,
+    /// New allowance
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a token's descriptive metadata changes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MetadataUpdatedEvent {
+    /// Admin that performed the update
+    pub admin: Address,
+    /// New token name
+    pub name: String,
+    /// New ticker symbol
+    pub symbol: String,
+    /// Decimal places
+    pub decimals: u32,
+    /// URI of off-chain metadata (logo, description)
+    pub uri: String,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when balances below a contract's dust minimum are collected to
+/// its treasury
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DustSweptEvent {
+    /// Token swept
+    pub token: Address,
+    /// Treasury receiving the dust
+    pub treasury: Address,
+    /// Number of balances swept
+    pub count: u32,
+    /// Total amount swept
+    pub total: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a token installs, replaces or removes its transfer hook
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TransferHookChangedEvent {
+    pub token: Address,
+    /// Hook installed, or the one removed
+    pub hook: Address,
+    /// `false` when the hook was removed
+    pub installed: bool,
+    /// Admin that made the change
+    pub changed_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a treasury spend is charged against a recipient's budget
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BudgetSpentEvent {
+    /// Spending proposal that authorised the payment
+    pub proposal_id: u64,
+    /// Budget holder receiving the funds
+    pub recipient: Address,
+    /// Token spent
+    pub token: Address,
+    /// Amount spent
+    pub amount: i128,
+    /// Budget epoch the spend was charged to
+    pub epoch: u64,
+    /// Amount left in the recipient's budget for this epoch
+    pub remaining: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when revenue is split into a new dividend distribution
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DistributionCreatedEvent {
+    /// Distribution identifier
+    pub distribution_id: u64,
+    /// Token being distributed
+    pub token: Address,
+    /// Amount to distribute
+    pub amount: i128,
+    /// Epoch whose share balances the amount is split by
+    pub epoch: u64,
+    /// Expired distribution whose unclaimed remainder this is, if any
+    pub rolled_over_from: Option<u64>,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a holder claims their share of a dividend distribution
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DividendClaimedEvent {
+    /// Distribution claimed from
+    pub distribution_id: u64,
+    /// Holder
+    pub user: Address,
+    /// Token paid out
+    pub token: Address,
+    /// Amount paid out
+    pub amount: i128,
+    /// Epoch of the distribution
+    pub epoch: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a smart account registers or revokes a session key
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SessionKeyEvent {
+    /// Smart account the session signs for
+    pub account: Address,
+    /// ed25519 public key of the session
+    pub session_key: BytesN<32>,
+    /// Timestamp after which the session is rejected
+    pub expires_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when guardians start a smart account's key recovery, and when it
+/// is cancelled by the owner or completed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecoveryEvent {
+    /// Smart account being recovered
+    pub account: Address,
+    /// Owner key the account rotates to on completion
+    pub new_owner_key: BytesN<32>,
+    /// Guardian approvals so far
+    pub approvals: u32,
+    /// Earliest completion time
+    pub executable_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a billing subscription starts or is cancelled
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BillingEvent {
+    /// Subscription ID
+    pub subscription_id: u64,
+    /// Paying account
+    pub subscriber: Address,
+    /// Merchant receiving the charges
+    pub merchant: Address,
+    /// Plan subscribed to
+    pub plan_id: u64,
+    /// Why the subscription started or ended
+    pub reason: Symbol,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted for every attempted subscription charge
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BillingChargeEvent {
+    /// Subscription ID
+    pub subscription_id: u64,
+    /// Paying account
+    pub subscriber: Address,
+    /// Merchant receiving the charge
+    pub merchant: Address,
+    /// Token charged
+    pub token: Address,
+    /// Amount charged or attempted
+    pub amount: i128,
+    /// Consecutive failed charges, zero after a success
+    pub failures: u32,
+    /// When the next period's charge is due
+    pub next_charge: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted once per payment splitter release, after the per-payee
+/// transfer events
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SplitReleasedEvent {
+    /// Token released
+    pub token: Address,
+    /// Payees paid
+    pub payees: u32,
+    /// Sum paid to all of them
+    pub total: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when the stable asset enters or leaves a savings account
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SavingsEvent {
+    /// Saver
+    pub user: Address,
+    /// Stable asset
+    pub asset: Address,
+    /// Amount moved
+    pub amount: i128,
+    /// Part of a withdrawal paid out of accrued interest
+    pub interest: i128,
+    /// Account balance, including interest, after the action
+    pub balance: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when governance changes the savings rate
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SavingsRateEvent {
+    /// Account that set the rate
+    pub admin: Address,
+    /// Annual rate before the change, in basis points
+    pub old_rate_bps: u32,
+    /// Annual rate from now on, in basis points
+    pub new_rate_bps: u32,
+    /// Savings index the old rate was applied up to
+    pub index: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Emission Events
+// =============================================================================
+
+/// Emitted when an epoch's emission is minted to the reward distributor
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmissionEvent {
+    /// Epoch the emission belongs to
+    pub epoch: u64,
+    /// Amount minted
+    pub amount: i128,
+    /// Receiver of the emission
+    pub distributor: Address,
+    /// Emission minted so far, including this one
+    pub total_emitted: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when governance adjusts the emission curve
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmissionCurveEvent {
+    /// Account that made the change
+    pub admin: Address,
+    /// First epoch the new curve applies to
+    pub epoch: u64,
+    /// Emission of `epoch`
+    pub emission: i128,
+    /// Decay per epoch in basis points
+    pub decay_bps: u32,
+    /// Emission the curve never decays below
+    pub floor: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a user commits quote tokens to a launch auction
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LaunchCommitEvent {
+    pub user: Address,
+    /// Quote tokens committed
+    pub amount: i128,
+    /// Sale tokens the commitment bids for before any pro-rata scaling
+    pub demand: i128,
+    /// Price paid per sale token, scaled by the contract's price scale
+    pub price: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a launch auction is settled
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LaunchSettledEvent {
+    /// Address that settled the auction
+    pub settled_by: Address,
+    /// Quote tokens committed in total
+    pub committed: i128,
+    /// Sale tokens bid for in total
+    pub demand: i128,
+    /// Sale tokens distributed
+    pub sold: i128,
+    /// Quote tokens sent to the treasury
+    pub raised: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a participant claims sale tokens and any refund
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LaunchClaimEvent {
+    pub user: Address,
+    /// Sale tokens received
+    pub tokens: i128,
+    /// Quote tokens refunded
+    pub refund: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an account completes a quest's task
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QuestCompletedEvent {
+    pub quest_id: u64,
+    pub account: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an account claims a completed quest's reward
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QuestRewardEvent {
+    pub quest_id: u64,
+    pub account: Address,
+    /// Reward token, or the multi-token contract of a badge reward
+    pub asset: Address,
+    /// Tokens paid, or 1 for a badge
+    pub amount: i128,
+    /// Badge id issued, or 0 for a token reward
+    pub badge_id: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a prize draw locks in its prize and tickets
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DrawStartedEvent {
+    pub draw_id: u64,
+    /// Yield being given away
+    pub prize: i128,
+    /// Tickets in the draw: total principal deposited
+    pub tickets: i128,
+    /// Ledger the draw started in; the winner is picked in a later one
+    pub start_ledger: u32,
+    pub started_by: Address,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a prize draw picks its winner, disclosing the seed so
+/// anyone can replay the selection
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DrawWinnerEvent {
+    pub draw_id: u64,
+    pub winner: Address,
+    pub prize: i128,
+    /// Seed the PRNG was reseeded with for the draw
+    pub seed: BytesN<32>,
+    /// Winning ticket number, in `[0, tickets)`
+    pub ticket: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a draw winner collects their prize
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PrizeClaimedEvent {
+    pub draw_id: u64,
+    pub winner: Address,
+    pub prize: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when a donor contributes to a project in a funding round
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GrantContributionEvent {
+    pub round_id: u64,
+    pub project_id: u32,
+    pub donor: Address,
+    pub amount: i128,
+    /// Project's quadratic weight after the contribution
+    pub weight: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted for each project when a funding round pays out
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GrantMatchEvent {
+    pub round_id: u64,
+    pub project_id: u32,
+    pub recipient: Address,
+    /// Direct contributions passed through
+    pub contributions: i128,
+    /// Share of the matching pool
+    pub matched: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when two parties open an OTC deal
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OtcDealCreatedEvent {
+    pub deal_id: u64,
+    pub maker: Address,
+    pub taker: Address,
+    /// Token the maker gives
+    pub maker_token: Address,
+    pub maker_amount: i128,
+    /// Token the taker gives
+    pub taker_token: Address,
+    pub taker_amount: i128,
+    /// Last second either leg can be funded
+    pub expires_at: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when one party escrows their leg of an OTC deal
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OtcDealFundedEvent {
+    pub deal_id: u64,
+    pub party: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Emitted when an OTC deal settles (`OTC_SETTLED`) or expires
+/// (`OTC_EXPIRED`)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OtcDealClosedEvent {
+    pub deal_id: u64,
+    pub maker: Address,
+    pub taker: Address,
+    /// Maker tokens paid to the taker on settlement, or refunded to the
+    /// maker on expiry
+    pub maker_amount: i128,
+    /// Taker tokens paid to the maker on settlement, or refunded to the
+    /// taker on expiry
+    pub taker_amount: i128,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+// =============================================================================
+// Event Emission Helpers
+// =============================================================================
+
+use metadata_keys::*;
+
+/// Helper trait for emitting standardized events
+///
+/// Every typed emitter publishes the [`StandardEventV2`] envelope followed by
+/// the legacy `(topic,)` event so existing indexers keep working; either can
+/// be switched off per contract through [`EventConfig`].
+///
+/// The `*_lite` emitters and [`EventEmitter::emit_lite`] publish the envelope
+/// alone with empty metadata, dropping the fields it would duplicate from
+/// the payload. Building with
+/// the `lite-events` feature turns every emitter into its lite form.
+pub struct EventEmitter;
+
+impl EventEmitter {
+    /// Put every standard event the current contract publishes from now on
+    /// under `namespace` (one of [`namespaces`]). Contracts call this from
+    /// their initializer.
+    pub fn init(env: &Env, namespace: Symbol) {
+        env.storage()
+            .instance()
+            .set(&EVENT_NAMESPACE_KEY, &namespace);
+    }
+
+    /// Namespace of the current contract's standard events, if it has one
+    pub fn namespace(env: &Env) -> Option<Symbol> {
+        env.storage().instance().get(&EVENT_NAMESPACE_KEY)
+    }
+
+    /// Publish an untyped payload as a [`StandardEventV2`] under
+    /// `(EVENT_PREFIX, topic)`
+    pub fn emit_standard(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        data: Vec<Val>,
+        metadata: Map<Symbol, Val>,
+    ) {
+        let payload = EventPayload::Raw(data);
+        Self::emit_v2(env, topic, user_address, payload, metadata, NO_CORRELATION);
+    }
+
+    /// Publish a [`StandardEventV2`] under `(EVENT_PREFIX, topic)`, or
+    /// `(EVENT_PREFIX, namespace, topic)` once the contract has a
+    /// namespace, adding the notify hint when the user has a matching
+    /// [`EventSubscription`]
+    pub fn emit_v2(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        payload: EventPayload,
+        mut metadata: Map<Symbol, Val>,
+        correlation_id: u64,
+    ) {
+        if LITE_EVENTS {
+            metadata = Map::new(env);
+        } else if EventSubscription::wants(env, &user_address, &topic) {
+            metadata.set(NOTIFY_KEY, true.into_val(env));
+        }
+        Self::publish_v2(env, topic, user_address, payload, metadata, correlation_id);
+    }
+
+    /// Publish a typed event as a [`StandardEventV2`] with no metadata and
+    /// no legacy duplicate. Subscription hints live in metadata, so lite
+    /// events never carry one.
+    pub fn emit_lite<E>(env: &Env, topic: Symbol, user_address: Address, event: E)
+    where
+        E: Into<EventPayload>,
+    {
+        let metadata = Map::new(env);
+        Self::publish_v2(
+            env,
+            topic,
+            user_address,
+            event.into(),
+            metadata,
+            NO_CORRELATION,
+        );
+    }
+
+    fn publish_v2(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        payload: EventPayload,
+        metadata: Map<Symbol, Val>,
+        correlation_id: u64,
+    ) {
+        let event = StandardEventV2 {
+            version: CURRENT_VERSION,
+            topic: topic.clone(),
+            contract: env.current_contract_address(),
+            user_address,
+            timestamp: env.ledger().timestamp(),
+            ledger: env.ledger().sequence(),
+            sequence: Self::next_sequence(env),
+            correlation_id,
+            payload,
+            metadata,
+        };
+        EventLog::record(env, &event);
+        let prefix = Symbol::new(env, EVENT_PREFIX);
+        match Self::namespace(env) {
+            Some(namespace) => env.events().publish((prefix, namespace, topic), event),
+            None => env.events().publish((prefix, topic), event),
+        }
+    }
+
+    fn next_sequence(env: &Env) -> u64 {
+        let storage = env.storage().instance();
+        let sequence = storage.get::<_, u64>(&EVENT_SEQUENCE_KEY).unwrap_or(0) + 1;
+        storage.set(&EVENT_SEQUENCE_KEY, &sequence);
+        sequence
+    }
+
+    /// Build a metadata map from key/value pairs, or an empty one in
+    /// `lite-events` builds
+    pub fn metadata(env: &Env, entries: &[(Symbol, Val)]) -> Map<Symbol, Val> {
+        let mut metadata = Map::new(env);
+        if LITE_EVENTS {
+            return metadata;
+        }
+        for (key, value) in entries.iter() {
+            metadata.set(key.clone(), *value);
+        }
+        metadata
+    }
+
+    /// Publish only the standard envelope for a typed event. This is the
+    /// fast path for contracts whose indexers no longer read legacy events,
+    /// skipping the legacy duplicate (about 15% of the cost of
+    /// [`Self::emit_dual`], see `tests/event_budget.rs`).
+    pub fn emit_standard_only<E>(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        event: E,
+        metadata: Map<Symbol, Val>,
+    ) where
+        E: Into<EventPayload>,
+    {
+        Self::emit_v2(
+            env,
+            topic,
+            user_address,
+            event.into(),
+            metadata,
+            NO_CORRELATION,
+        );
+    }
+
+    /// Publish the standard envelope followed by the legacy `(topic,)`
+    /// event, each unless disabled in the contract's [`EventConfig`]
+    pub fn emit_dual<E>(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        event: E,
+        metadata: Map<Symbol, Val>,
+    ) where
+        E: IntoVal<Env, Val> + Into<EventPayload> + Clone,
+    {
+        Self::emit_correlated(env, topic, user_address, event, metadata, NO_CORRELATION);
+    }
+
+    fn emit_correlated<E>(
+        env: &Env,
+        topic: Symbol,
+        user_address: Address,
+        event: E,
+        metadata: Map<Symbol, Val>,
+        correlation_id: u64,
+    ) where
+        E: IntoVal<Env, Val> + Into<EventPayload> + Clone,
+    {
+        if LITE_EVENTS {
+            let metadata = Map::new(env);
+            let payload = event.into();
+            Self::publish_v2(env, topic, user_address, payload, metadata, correlation_id);
+            return;
+        }
+        let config = EventConfig::load(env);
+        if config.emit_standard {
+            let payload = event.clone().into();
+            Self::emit_v2(
+                env,
+                topic.clone(),
+                user_address,
+                payload,
+                metadata,
+                correlation_id,
+            );
+        }
+        if config.emit_legacy {
+            env.events().publish((topic,), event);
+        }
+    }
+
+    /// Emit a trade executed event
+    pub fn trade_executed(env: &Env, event: TradeExecutedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.trade_id.into_val(env)),
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+                (FEE_KEY, event.fee_amount.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_dual(env, topics::TRADE_EXECUTED, user, event, metadata);
+    }
+
+    /// Emit a trade executed event for a triggered order, tagging the
+    /// trigger price
+    pub fn order_trade_executed(env: &Env, event: TradeExecutedEvent, trigger_price: i128) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.trade_id.into_val(env)),
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+                (FEE_KEY, event.fee_amount.into_val(env)),
+                (TRIGGER_KEY, trigger_price.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_dual(env, topics::TRADE_EXECUTED, user, event, metadata);
+    }
+
+    /// Emit a trade executed event for hop `hop` of route `route_id`,
+    /// correlated with the route's other hops and its summary
+    pub fn route_trade_executed(env: &Env, event: TradeExecutedEvent, route_id: u64, hop: u32) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.trade_id.into_val(env)),
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+                (FEE_KEY, event.fee_amount.into_val(env)),
+                (ROUTE_KEY, route_id.into_val(env)),
+                (HOP_KEY, hop.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_correlated(env, topics::TRADE_EXECUTED, user, event, metadata, route_id);
+    }
+
+    /// Emit the summary of a multi-hop swap
+    pub fn route_executed(env: &Env, event: RouteExecutedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ROUTE_KEY, event.route_id.into_val(env)),
+                (AMOUNT_KEY, event.amount_in.into_val(env)),
+                (RECEIVED_KEY, event.amount_out.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        let route_id = event.route_id;
+        Self::emit_correlated(env, topics::ROUTE_EXECUTED, user, event, metadata, route_id);
+    }
+
+    /// Emit a trade executed event for an order filled in batch
+    /// `batch_id`, correlated with the batch's other fills and summary
+    pub fn batch_trade_executed(env: &Env, event: TradeExecutedEvent, batch_id: u64) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.trade_id.into_val(env)),
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (CLEARING_PRICE_KEY, event.price.into_val(env)),
+                (BATCH_KEY, batch_id.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_correlated(env, topics::TRADE_EXECUTED, user, event, metadata, batch_id);
+    }
+
+    /// Emit the settlement of a batch auction
+    pub fn batch_settled(env: &Env, event: BatchSettledEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (BATCH_KEY, event.batch_id.into_val(env)),
+                (CLEARING_PRICE_KEY, event.clearing_price.into_val(env)),
+                (AMOUNT_KEY, event.volume.into_val(env)),
+            ],
+        );
+        let user = event.settled_by.clone();
+        let batch_id = event.batch_id;
+        Self::emit_correlated(env, topics::BATCH_SETTLED, user, event, metadata, batch_id);
+    }
+
+    /// Emit an order committed event
+    pub fn order_committed(env: &Env, event: OrderCommitEvent) {
+        Self::order_commit_action(env, topics::ORDER_COMMITTED, event);
+    }
+
+    /// Emit a commitment slashed event
+    pub fn commit_slashed(env: &Env, event: OrderCommitEvent) {
+        Self::order_commit_action(env, topics::COMMIT_SLASHED, event);
+    }
+
+    fn order_commit_action(env: &Env, topic: Symbol, event: OrderCommitEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (BATCH_KEY, event.batch_id.into_val(env)),
+                (ID_KEY, event.commit_id.into_val(env)),
+                (AMOUNT_KEY, event.bond.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit an order revealed event
+    pub fn order_revealed(env: &Env, event: OrderRevealEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (BATCH_KEY, event.batch_id.into_val(env)),
+                (ID_KEY, event.commit_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.limit_price.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_dual(env, topics::ORDER_REVEALED, user, event, metadata);
+    }
+
+    /// Emit an order triggered event
+    pub fn order_triggered(env: &Env, event: OrderTriggeredEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.order_id.into_val(env)),
+                (PAIR_KEY, event.pair.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+                (TRIGGER_KEY, event.trigger_price.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topics::ORDER_TRIGGERED, user, event, metadata);
+    }
+
+    /// Emit a contract paused event
+    pub fn contract_paused(env: &Env, event: ContractPausedEvent) {
+        let user = event.paused_by.clone();
+        Self::emit_dual(env, topics::CONTRACT_PAUSED, user, event, Map::new(env));
+    }
+
+    /// Emit a contract unpaused event
+    pub fn contract_unpaused(env: &Env, event: ContractUnpausedEvent) {
+        let user = event.unpaused_by.clone();
+        Self::emit_dual(env, topics::CONTRACT_UNPAUSED, user, event, Map::new(env));
+    }
+
+    /// Emit a pause state change for one operation
+    pub fn pause_changed(env: &Env, event: PauseChangedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (OPERATION_KEY, event.operation.into_val(env)),
+                (STATUS_KEY, event.paused.into_val(env)),
+            ],
+        );
+        let user = event.changed_by.clone();
+        Self::emit_dual(env, topics::PAUSE_CHANGED, user, event, metadata);
+    }
+
+    /// Emit a reputation update
+    pub fn reputation_updated(env: &Env, event: ReputationEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (KIND_KEY, event.activity.clone().into_val(env)),
+                (AMOUNT_KEY, event.points.into_val(env)),
+                (TIER_KEY, event.tier.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topics::REPUTATION_UPDATED, user, event, metadata);
+    }
+
+    /// Emit a name registered event
+    pub fn name_registered(env: &Env, event: NameEvent) {
+        Self::name_action(env, topics::NAME_REGISTERED, event);
+    }
+
+    /// Emit a name renewed event
+    pub fn name_renewed(env: &Env, event: NameEvent) {
+        Self::name_action(env, topics::NAME_RENEWED, event);
+    }
+
+    fn name_action(env: &Env, topic: Symbol, event: NameEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (NAME_KEY, event.name.clone().into_val(env)),
+                (FEE_KEY, event.fee.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a name transferred event
+    pub fn name_transferred(env: &Env, event: NameTransferEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (NAME_KEY, event.name.clone().into_val(env)),
+                (RECIPIENT_KEY, event.to.clone().into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::NAME_TRANSFERRED, user, event, metadata);
+    }
+
+    /// Emit an attestation issued event
+    pub fn attestation_issued(env: &Env, event: AttestationEvent) {
+        Self::attestation_action(env, topics::ATTESTATION_ISSUED, event);
+    }
+
+    /// Emit an attestation revoked event
+    pub fn attestation_revoked(env: &Env, event: AttestationEvent) {
+        Self::attestation_action(env, topics::ATTESTATION_REVOKED, event);
+    }
+
+    fn attestation_action(env: &Env, topic: Symbol, event: AttestationEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (KIND_KEY, event.claim.clone().into_val(env)),
+                (ADDRESS_KEY, event.attestor.clone().into_val(env)),
+            ],
+        );
+        let user = event.subject.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a fee collected event
+    pub fn fee_collected(env: &Env, event: FeeCollectedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (RECIPIENT_KEY, event.recipient.into_val(env)),
+            ],
+        );
+        let user = event.payer.clone();
+        Self::emit_dual(env, topics::FEE_COLLECTED, user, event, metadata);
+    }
+
+    /// Emit a fee collected event for a referred trade, tagging the referrer
+    pub fn referral_fee_collected(env: &Env, event: FeeCollectedEvent, referrer: Address) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (RECIPIENT_KEY, event.recipient.into_val(env)),
+                (REFERRER_KEY, referrer.into_val(env)),
+            ],
+        );
+        let user = event.payer.clone();
+        Self::emit_dual(env, topics::FEE_COLLECTED, user, event, metadata);
+    }
+
+    /// Emit a price updated event
+    pub fn price_updated(env: &Env, event: PriceUpdatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PAIR_KEY, event.pair.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+            ],
+        );
+        let user = env.current_contract_address();
+        Self::emit_dual(env, topics::PRICE_UPDATED, user, event, metadata);
+    }
+
+    /// Emit a flash loan event
+    pub fn flash_loan(env: &Env, event: FlashLoanEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (FEE_KEY, event.fee.into_val(env)),
+                (RECIPIENT_KEY, event.receiver.into_val(env)),
+            ],
+        );
+        let user = event.receiver.clone();
+        Self::emit_dual(env, topics::FLASH_LOAN, user, event, metadata);
+    }
+
+    /// Emit a referrer bound event
+    pub fn referrer_bound(env: &Env, event: ReferrerBoundEvent) {
+        let metadata = Self::metadata(env, &[(REFERRER_KEY, event.referrer.into_val(env))]);
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::REFERRER_BOUND, user, event, metadata);
+    }
+
+    /// Emit a PnL realized event
+    pub fn pnl_realized(env: &Env, event: PnlRealizedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.closed_amount.into_val(env)),
+                (PRICE_KEY, event.exit_price.into_val(env)),
+                (PNL_KEY, event.pnl.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::PNL_REALIZED, user, event, metadata);
+    }
+
+    /// Emit a fee tier changed event
+    pub fn fee_tier_changed(env: &Env, event: FeeTierChangedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TIER_KEY, event.new_tier.into_val(env)),
+                (AMOUNT_KEY, event.volume.into_val(env)),
+            ],
+        );
+        let user = event.trader.clone();
+        Self::emit_dual(env, topics::FEE_TIER_CHANGED, user, event, metadata);
+    }
+
+    /// Emit a proposal created event
+    pub fn proposal_created(env: &Env, event: ProposalCreatedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.proposal_id.into_val(env))]);
+        let user = event.proposer.clone();
+        Self::emit_dual(env, topics::PROPOSAL_CREATED, user, event, metadata);
+    }
+
+    /// Emit a proposal approved event
+    pub fn proposal_approved(env: &Env, event: ProposalApprovedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.proposal_id.into_val(env))]);
+        let user = event.approver.clone();
+        Self::emit_dual(env, topics::PROPOSAL_APPROVED, user, event, metadata);
+    }
+
+    /// Emit a proposal rejected event
+    pub fn proposal_rejected(env: &Env, event: ProposalRejectedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.proposal_id.into_val(env))]);
+        let user = event.rejector.clone();
+        Self::emit_dual(env, topics::PROPOSAL_REJECTED, user, event, metadata);
+    }
+
+    /// Emit a proposal executed event
+    pub fn proposal_executed(env: &Env, event: ProposalExecutedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.proposal_id.into_val(env))]);
+        let user = event.executor.clone();
+        Self::emit_dual(env, topics::PROPOSAL_EXECUTED, user, event, metadata);
+    }
+
+    /// Emit a proposal executed event as part of batch `batch_id`
+    pub fn batch_proposal_executed(env: &Env, event: ProposalExecutedEvent, batch_id: u64) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (BATCH_KEY, batch_id.into_val(env)),
+            ],
+        );
+        let user = event.executor.clone();
+        Self::emit_correlated(
+            env,
+            topics::PROPOSAL_EXECUTED,
+            user,
+            event,
+            metadata,
+            batch_id,
+        );
+    }
+
+    /// Emit the summary of an executed proposal batch
+    pub fn proposal_batch_executed(env: &Env, event: ProposalBatchExecutedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (BATCH_KEY, event.batch_id.into_val(env)),
+                (AMOUNT_KEY, event.executed.len().into_val(env)),
+            ],
+        );
+        let user = event.executor.clone();
+        let batch_id = event.batch_id;
+        Self::emit_correlated(
+            env,
+            topics::PROPOSAL_BATCH_EXECUTED,
+            user,
+            event,
+            metadata,
+            batch_id,
+        );
+    }
+
+    /// Emit a proposal archived event
+    pub fn proposal_archived(env: &Env, event: ProposalArchivedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (STATUS_KEY, (event.status as u32).into_val(env)),
+            ],
+        );
+        let user = event.archiver.clone();
+        Self::emit_dual(env, topics::PROPOSAL_ARCHIVED, user, event, metadata);
+    }
+
+    /// Emit a proposal cancelled event
+    pub fn proposal_cancelled(env: &Env, event: ProposalCancelledEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.proposal_id.into_val(env))]);
+        let user = event.cancelled_by.clone();
+        Self::emit_dual(env, topics::PROPOSAL_CANCELLED, user, event, metadata);
+    }
+
+    /// Emit a proposal challenged event
+    pub fn proposal_challenged(env: &Env, event: ProposalChallengedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (TOKEN_KEY, event.bond_token.into_val(env)),
+                (AMOUNT_KEY, event.bond.into_val(env)),
+            ],
+        );
+        let user = event.challenger.clone();
+        Self::emit_dual(env, topics::PROPOSAL_CHALLENGED, user, event, metadata);
+    }
+
+    /// Emit a vote cast event
+    pub fn vote_cast(env: &Env, event: VoteCastEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (ASSET_KEY, event.asset_id.into_val(env)),
+                (TOKEN_KEY, event.asset.into_val(env)),
+                (SOURCE_KEY, event.source.name().into_val(env)),
+                (AMOUNT_KEY, event.power.into_val(env)),
+            ],
+        );
+        let user = event.voter.clone();
+        Self::emit_dual(env, topics::VOTE_CAST, user, event, metadata);
+    }
+
+    /// Emit a conviction updated event
+    pub fn conviction_updated(env: &Env, event: ConvictionUpdatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (AMOUNT_KEY, event.staked.into_val(env)),
+                (CONVICTION_KEY, event.conviction.into_val(env)),
+            ],
+        );
+        let user = event.supporter.clone();
+        Self::emit_dual(env, topics::CONVICTION_UPDATED, user, event, metadata);
+    }
+
+    /// Emit a conviction proposal passed event
+    pub fn conviction_passed(env: &Env, event: ConvictionPassedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (CONVICTION_KEY, event.conviction.into_val(env)),
+            ],
+        );
+        let user = event.beneficiary.clone();
+        Self::emit_dual(env, topics::CONVICTION_PASSED, user, event, metadata);
+    }
+
+    /// Emit a reward added event
+    pub fn reward_added(env: &Env, event: RewardAddedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.reward_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, event.reason.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::REWARD_ADDED, user, event, metadata);
+    }
+
+    /// Emit a reward claimed event
+    pub fn reward_claimed(env: &Env, event: RewardClaimedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.reward_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::REWARD_CLAIMED, user, event, metadata);
+    }
+
+    // ── Parametric insurance emitters ─────────────────────────────────────────
+
+    /// Emit a policy created event
+    pub fn policy_created(env: &Env, event: PolicyCreatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.policy_id.into_val(env)),
+                (AMOUNT_KEY, event.coverage_amount.into_val(env)),
+            ],
+        );
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::POLICY_CREATED, user, event, metadata);
+    }
+
+    /// Emit a policy cancelled event
+    pub fn policy_cancelled(env: &Env, event: PolicyCancelledEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.policy_id.into_val(env))]);
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::POLICY_CANCELLED, user, event, metadata);
+    }
+
+    /// Emit a policy expired event
+    pub fn policy_expired(env: &Env, event: PolicyExpiredEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.policy_id.into_val(env))]);
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::POLICY_EXPIRED, user, event, metadata);
+    }
+
+    /// Emit a trigger activated event
+    pub fn trigger_activated(env: &Env, event: TriggerActivatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.policy_id.into_val(env)),
+                (PRICE_KEY, event.oracle_value.into_val(env)),
+            ],
+        );
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::TRIGGER_ACTIVATED, user, event, metadata);
+    }
+
+    /// Emit a claim paid event
+    pub fn claim_paid(env: &Env, event: ClaimPaidEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.policy_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::CLAIM_PAID, user, event, metadata);
+    }
+
+    /// Emit an insurance fund claim filed event
+    pub fn claim_filed(env: &Env, event: InsuranceClaimEvent) {
+        Self::insurance_claim(env, topics::CLAIM_FILED, event);
+    }
+
+    /// Emit an insurance fund claim rejected event
+    pub fn claim_rejected(env: &Env, event: InsuranceClaimEvent) {
+        Self::insurance_claim(env, topics::CLAIM_REJECTED, event);
+    }
+
+    fn insurance_claim(env: &Env, topic: Symbol, event: InsuranceClaimEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.claim_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, event.reason.clone().into_val(env)),
+            ],
+        );
+        let user = event.claimant.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a claim paid event for an insurance fund claim. `reason` is the
+    /// incident the claim was filed for.
+    pub fn fund_claim_paid(env: &Env, event: ClaimPaidEvent, reason: Symbol) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.policy_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, reason.into_val(env)),
+            ],
+        );
+        let user = event.policyholder.clone();
+        Self::emit_dual(env, topics::CLAIM_PAID, user, event, metadata);
+    }
+
+    /// Emit a liquidity deposited event
+    pub fn liquidity_deposited(env: &Env, event: LiquidityDepositedEvent) {
+        let metadata = Self::metadata(env, &[(AMOUNT_KEY, event.amount.into_val(env))]);
+        let user = event.provider.clone();
+        Self::emit_dual(env, topics::LIQUIDITY_DEPOSITED, user, event, metadata);
+    }
+
+    /// Emit a liquidity withdrawn event
+    pub fn liquidity_withdrawn(env: &Env, event: LiquidityWithdrawnEvent) {
+        let metadata = Self::metadata(env, &[(AMOUNT_KEY, event.amount.into_val(env))]);
+        let user = event.provider.clone();
+        Self::emit_dual(env, topics::LIQUIDITY_WITHDRAWN, user, event, metadata);
+    }
+
+    // ── Streaming payment emitters ────────────────────────────────────────────
+
+    /// Emit a stream created event
+    pub fn stream_created(env: &Env, event: StreamCreatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.stream_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (RECIPIENT_KEY, event.recipient.into_val(env)),
+                (AMOUNT_KEY, event.deposit.into_val(env)),
+                (RATE_KEY, event.rate_per_second.into_val(env)),
+                (START_KEY, event.start_time.into_val(env)),
+                (STOP_KEY, event.stop_time.into_val(env)),
+            ],
+        );
+        let user = event.sender.clone();
+        Self::emit_dual(env, topics::STREAM_CREATED, user, event, metadata);
+    }
+
+    /// Emit a stream withdrawn event
+    pub fn stream_withdrawn(env: &Env, event: StreamWithdrawnEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.stream_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.recipient.clone();
+        Self::emit_dual(env, topics::STREAM_WITHDRAWN, user, event, metadata);
+    }
+
+    /// Emit a stream cancelled event
+    pub fn stream_cancelled(env: &Env, event: StreamCancelledEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.stream_id.into_val(env)),
+                (AMOUNT_KEY, event.recipient_amount.into_val(env)),
+                (STOP_KEY, event.timestamp.into_val(env)),
+            ],
+        );
+        let user = event.cancelled_by.clone();
+        Self::emit_dual(env, topics::STREAM_CANCELLED, user, event, metadata);
+    }
+
+    // ── Lending market emitters ───────────────────────────────────────────────
+
+    fn lending_action(env: &Env, topic: Symbol, event: LendingActionEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.asset.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a lending deposit event
+    pub fn lending_deposited(env: &Env, event: LendingActionEvent) {
+        Self::lending_action(env, topics::LEND_DEPOSIT, event);
+    }
+
+    /// Emit a lending withdrawal event
+    pub fn lending_withdrawn(env: &Env, event: LendingActionEvent) {
+        Self::lending_action(env, topics::LEND_WITHDRAW, event);
+    }
+
+    /// Emit a borrow event
+    pub fn borrowed(env: &Env, event: LendingActionEvent) {
+        Self::lending_action(env, topics::BORROW, event);
+    }
+
+    /// Emit a repayment event
+    pub fn repaid(env: &Env, event: LendingActionEvent) {
+        Self::lending_action(env, topics::REPAY, event);
+    }
+
+    /// Emit a collateral auction start, with the decay parameters keepers
+    /// need to price it
+    pub fn auction_started(env: &Env, event: AuctionStartEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.auction_id.into_val(env)),
+                (TOKEN_KEY, event.debt_asset.into_val(env)),
+                (AMOUNT_KEY, event.tab.into_val(env)),
+                (COLLATERAL_KEY, event.collateral_asset.into_val(env)),
+                (SEIZED_KEY, event.lot.into_val(env)),
+                (PRICE_KEY, event.start_price.into_val(env)),
+                (START_KEY, event.timestamp.into_val(env)),
+                (STEP_KEY, event.step.into_val(env)),
+                (CUT_KEY, event.cut_bps.into_val(env)),
+                (TAIL_KEY, event.tail.into_val(env)),
+            ],
+        );
+        let user = event.borrower.clone();
+        Self::emit_dual(env, topics::AUCTION_STARTED, user, event, metadata);
+    }
+
+    /// Emit a partial or full take of an auction's lot
+    pub fn auction_taken(env: &Env, event: AuctionTakeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.auction_id.into_val(env)),
+                (AMOUNT_KEY, event.paid.into_val(env)),
+                (SEIZED_KEY, event.collateral.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+            ],
+        );
+        let user = event.taker.clone();
+        Self::emit_dual(env, topics::AUCTION_TAKEN, user, event, metadata);
+    }
+
+    /// Emit an auction close
+    pub fn auction_closed(env: &Env, event: AuctionSettleEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.auction_id.into_val(env)),
+                (AMOUNT_KEY, event.bad_debt.into_val(env)),
+            ],
+        );
+        let user = event.borrower.clone();
+        Self::emit_dual(env, topics::AUCTION_CLOSED, user, event, metadata);
+    }
+
+    /// Emit a circuit breaker trip
+    pub fn circuit_tripped(env: &Env, event: CircuitTripEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PRICE_KEY, event.price.into_val(env)),
+                (REFERENCE_KEY, event.reference_price.into_val(env)),
+                (STOP_KEY, event.tripped_until.into_val(env)),
+            ],
+        );
+        let contract = env.current_contract_address();
+        Self::emit_dual(env, topics::CIRCUIT_TRIPPED, contract, event, metadata);
+    }
+
+    /// Emit a circuit breaker reset
+    pub fn circuit_reset(env: &Env, event: CircuitResetEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PRICE_KEY, event.price.into_val(env)),
+                (REFERENCE_KEY, event.reference_price.into_val(env)),
+            ],
+        );
+        let contract = env.current_contract_address();
+        Self::emit_dual(env, topics::CIRCUIT_RESET, contract, event, metadata);
+    }
+
+    /// Emit a liquidation event
+    pub fn liquidation(env: &Env, event: LiquidationEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.debt_asset.into_val(env)),
+                (AMOUNT_KEY, event.repaid.into_val(env)),
+                (PRICE_KEY, event.debt_price.into_val(env)),
+                (COLLATERAL_KEY, event.collateral_asset.into_val(env)),
+                (SEIZED_KEY, event.seized.into_val(env)),
+                (COLLATERAL_PRICE_KEY, event.collateral_price.into_val(env)),
+                (HEALTH_KEY, event.health_factor.into_val(env)),
+                (RECIPIENT_KEY, event.borrower.into_val(env)),
+            ],
+        );
+        let user = event.liquidator.clone();
+        Self::emit_dual(env, topics::LIQUIDATION, user, event, metadata);
+    }
+
+    // ── Token & treasury emitters ─────────────────────────────────────────────
+
+    /// Emit a transfer event
+    pub fn transfer(env: &Env, event: TransferEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (RECIPIENT_KEY, event.to.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::TRANSFER, user, event, metadata);
+    }
+
+    /// Emit an approval event. `via_permit` marks approvals granted through
+    /// an off-chain signature rather than a direct call by the owner.
+    pub fn approval(env: &Env, event: ApprovalEvent, via_permit: bool) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (SPENDER_KEY, event.spender.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (Symbol::new(env, VIA_PERMIT_KEY), via_permit.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topics::APPROVAL, user, event, metadata);
+    }
+
+    /// [`Self::transfer`] without metadata or the legacy event
+    pub fn transfer_lite(env: &Env, event: TransferEvent) {
+        let user = event.from.clone();
+        Self::emit_lite(env, topics::TRANSFER, user, event);
+    }
+
+    /// [`Self::approval`] without metadata or the legacy event
+    pub fn approval_lite(env: &Env, event: ApprovalEvent) {
+        let user = event.owner.clone();
+        Self::emit_lite(env, topics::APPROVAL, user, event);
+    }
+
+    /// Emit a contract deployed event
+    pub fn contract_deployed(env: &Env, event: ContractDeployedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (KIND_KEY, event.kind.into_val(env)),
+                (ADDRESS_KEY, event.address.into_val(env)),
+                (ARGS_KEY, event.init_args.clone().into_val(env)),
+            ],
+        );
+        let user = event.deployer.clone();
+        Self::emit_dual(env, topics::CONTRACT_DEPLOYED, user, event, metadata);
+    }
+
+    /// Emit a bridge lock event
+    pub fn bridge_lock(env: &Env, event: BridgeLockEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (CHAIN_KEY, event.dest_chain.into_val(env)),
+                (NONCE_KEY, event.nonce.into_val(env)),
+            ],
+        );
+        let user = event.sender.clone();
+        Self::emit_dual(env, topics::BRIDGE_LOCK, user, event, metadata);
+    }
+
+    /// Emit a bridge release event
+    pub fn bridge_release(env: &Env, event: BridgeReleaseEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (NONCE_KEY, event.nonce.into_val(env)),
+            ],
+        );
+        let user = event.recipient.clone();
+        Self::emit_dual(env, topics::BRIDGE_RELEASE, user, event, metadata);
+    }
+
+    /// Emit a subscription set or cleared event
+    pub fn subscription_changed(env: &Env, topic: Symbol, event: SubscriptionEvent) {
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, Map::new(env));
+    }
+
+    /// Emit a keeper task registered event
+    pub fn task_registered(env: &Env, event: TaskEvent) {
+        Self::task(env, topics::TASK_REGISTERED, event);
+    }
+
+    /// Emit a keeper task cancelled event
+    pub fn task_cancelled(env: &Env, event: TaskEvent) {
+        Self::task(env, topics::TASK_CANCELLED, event);
+    }
+
+    fn task(env: &Env, topic: Symbol, event: TaskEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.task_id.into_val(env)),
+                (ADDRESS_KEY, event.target.clone().into_val(env)),
+                (AMOUNT_KEY, event.payment.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a keeper task performed event
+    pub fn task_performed(env: &Env, event: TaskPerformedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.task_id.into_val(env)),
+                (AMOUNT_KEY, event.payment.into_val(env)),
+            ],
+        );
+        let user = event.keeper.clone();
+        Self::emit_dual(env, topics::TASK_PERFORMED, user, event, metadata);
+    }
+
+    /// Emit a relayer reimbursement event
+    pub fn relay_reimbursed(env: &Env, event: RelayReimbursedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ADDRESS_KEY, event.target.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+            ],
+        );
+        let user = event.relayer.clone();
+        Self::emit_dual(env, topics::RELAY_REIMBURSED, user, event, metadata);
+    }
+
+    /// Emit an audit event
+    pub fn audit(env: &Env, event: AuditEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (FUNCTION_KEY, event.function.into_val(env)),
+                (CALLS_KEY, event.calls.into_val(env)),
+            ],
+        );
+        let user = event.last_caller.clone();
+        Self::emit_dual(env, topics::AUDIT, user, event, metadata);
+    }
+
+    /// Emit an epoch rollover event
+    pub fn epoch_rollover(env: &Env, event: EpochRolloverEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (START_KEY, event.epoch_start.into_val(env)),
+            ],
+        );
+        let user = env.current_contract_address();
+        Self::emit_dual(env, topics::EPOCH_ROLLOVER, user, event, metadata);
+    }
+
+    /// Emit a vault deposit event
+    pub fn vault_deposit(env: &Env, event: VaultEvent) {
+        Self::vault_action(env, topics::VAULT_DEPOSIT, event);
+    }
+
+    /// Emit a vault withdrawal event
+    pub fn vault_withdraw(env: &Env, event: VaultEvent) {
+        Self::vault_action(env, topics::VAULT_WITHDRAW, event);
+    }
+
+    fn vault_action(env: &Env, topic: Symbol, event: VaultEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.asset.into_val(env)),
+                (AMOUNT_KEY, event.assets.into_val(env)),
+                (SHARES_KEY, event.shares.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a per-strategy vault harvest event
+    pub fn harvested(env: &Env, event: HarvestEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.asset.into_val(env)),
+                (AMOUNT_KEY, event.profit.into_val(env)),
+            ],
+        );
+        let user = event.strategy.clone();
+        Self::emit_dual(env, topics::HARVESTED, user, event, metadata);
+    }
+
+    /// Emit a vote-escrow lock event
+    pub fn ve_locked(env: &Env, event: VeLockEvent) {
+        Self::ve_lock(env, topics::VE_LOCKED, event);
+    }
+
+    /// Emit a vote-escrow withdrawal event
+    pub fn ve_withdrawn(env: &Env, event: VeLockEvent) {
+        Self::ve_lock(env, topics::VE_WITHDRAWN, event);
+    }
+
+    fn ve_lock(env: &Env, topic: Symbol, event: VeLockEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (STOP_KEY, event.unlock_time.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a gauge vote event
+    pub fn gauge_voted(env: &Env, event: GaugeVoteEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.gauge_id.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (AMOUNT_KEY, event.power.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::GAUGE_VOTED, user, event, metadata);
+    }
+
+    /// Emit a gauge epoch rollover event
+    pub fn gauge_epoch(env: &Env, event: GaugeEpochEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (AMOUNT_KEY, event.total_votes.into_val(env)),
+            ],
+        );
+        let user = env.current_contract_address();
+        Self::emit_dual(env, topics::GAUGE_EPOCH, user, event, metadata);
+    }
+
+    /// Emit a gauge incentive deposit event
+    pub fn bribe_deposited(env: &Env, event: BribeEvent) {
+        Self::bribe(env, topics::BRIBE_DEPOSITED, event);
+    }
+
+    /// Emit a gauge incentive claim event
+    pub fn bribe_claimed(env: &Env, event: BribeEvent) {
+        Self::bribe(env, topics::BRIBE_CLAIMED, event);
+    }
+
+    fn bribe(env: &Env, topic: Symbol, event: BribeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.gauge_id.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a marketplace listing event
+    pub fn listed(env: &Env, event: ListingEvent) {
+        Self::listing(env, topics::LISTED, event);
+    }
+
+    /// Emit a marketplace listing cancelled event
+    pub fn unlisted(env: &Env, event: ListingEvent) {
+        Self::listing(env, topics::UNLISTED, event);
+    }
+
+    fn listing(env: &Env, topic: Symbol, event: ListingEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+            ],
+        );
+        let user = event.seller.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a marketplace sale event
+    pub fn sale(env: &Env, event: SaleEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+                (FEE_KEY, event.fee.into_val(env)),
+            ],
+        );
+        let user = event.buyer.clone();
+        Self::emit_dual(env, topics::SALE, user, event, metadata);
+    }
+
+    /// Emit an offer made event
+    pub fn offer_made(env: &Env, event: OfferEvent) {
+        Self::offer(env, topics::OFFER_MADE, event);
+    }
+
+    /// Emit an offer cancelled event
+    pub fn offer_cancelled(env: &Env, event: OfferEvent) {
+        Self::offer(env, topics::OFFER_CANCELLED, event);
+    }
+
+    fn offer(env: &Env, topic: Symbol, event: OfferEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (PRICE_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.buyer.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit an auction created event
+    pub fn auction_created(env: &Env, event: ListingEvent) {
+        Self::listing(env, topics::AUCTION_CREATED, event);
+    }
+
+    /// Emit a bid placed event
+    pub fn bid_placed(env: &Env, event: BidEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (BIDDER_KEY, event.bidder.clone().into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.bidder.clone();
+        Self::emit_dual(env, topics::BID_PLACED, user, event, metadata);
+    }
+
+    /// Emit an auction settled event
+    pub fn auction_settled(env: &Env, event: AuctionSettledEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (BIDDER_KEY, event.winner.clone().into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.winner.clone();
+        Self::emit_dual(env, topics::AUCTION_SETTLED, user, event, metadata);
+    }
+
+    /// Emit a royalty paid event
+    pub fn royalty_paid(env: &Env, event: RoyaltyPaidEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (PRICE_KEY, event.sale_price.into_val(env)),
+                (FEE_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.receiver.clone();
+        Self::emit_dual(env, topics::ROYALTY_PAID, user, event, metadata);
+    }
+
+    /// Emit an NFT minted event
+    pub fn nft_minted(env: &Env, event: NftEvent) {
+        Self::nft_action(env, topics::NFT_MINTED, event);
+    }
+
+    /// Emit an NFT burned event
+    pub fn nft_burned(env: &Env, event: NftEvent) {
+        Self::nft_action(env, topics::NFT_BURNED, event);
+    }
+
+    fn nft_action(env: &Env, topic: Symbol, event: NftEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (URI_KEY, event.uri.clone().into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit an NFT transferred event
+    pub fn nft_transferred(env: &Env, event: NftTransferEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (RECIPIENT_KEY, event.to.clone().into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::NFT_TRANSFERRED, user, event, metadata);
+    }
+
+    /// Emit an NFT approval event
+    pub fn nft_approved(env: &Env, event: NftApprovalEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (SPENDER_KEY, event.spender.clone().into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topics::NFT_APPROVED, user, event, metadata);
+    }
+
+    /// Emit a multi-token minted event covering every id in the call
+    pub fn multi_token_minted(env: &Env, event: MultiTokenSupplyEvent) {
+        Self::multi_token_supply(env, topics::MT_MINTED, event);
+    }
+
+    /// Emit a multi-token burned event covering every id in the call
+    pub fn multi_token_burned(env: &Env, event: MultiTokenSupplyEvent) {
+        Self::multi_token_supply(env, topics::MT_BURNED, event);
+    }
+
+    fn multi_token_supply(env: &Env, topic: Symbol, event: MultiTokenSupplyEvent) {
+        let total: i128 = event.amounts.iter().sum();
+        let metadata = Self::metadata(
+            env,
+            &[
+                (COUNT_KEY, event.ids.len().into_val(env)),
+                (AMOUNT_KEY, total.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a multi-token transfer event covering every id in the call
+    pub fn multi_token_transferred(env: &Env, event: MultiTokenTransferEvent) {
+        let total: i128 = event.amounts.iter().sum();
+        let metadata = Self::metadata(
+            env,
+            &[
+                (RECIPIENT_KEY, event.to.clone().into_val(env)),
+                (COUNT_KEY, event.ids.len().into_val(env)),
+                (AMOUNT_KEY, total.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::MT_TRANSFERRED, user, event, metadata);
+    }
+
+    /// Emit a badge issued event
+    pub fn badge_issued(env: &Env, event: BadgeEvent) {
+        Self::badge_action(env, topics::BADGE_ISSUED, event);
+    }
+
+    /// Emit a badge revoked event
+    pub fn badge_revoked(env: &Env, event: BadgeEvent) {
+        Self::badge_action(env, topics::BADGE_REVOKED, event);
+    }
+
+    fn badge_action(env: &Env, topic: Symbol, event: BadgeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (EXPIRY_KEY, event.expires_at.into_val(env)),
+            ],
+        );
+        let user = event.holder.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a multi-token URI event
+    pub fn multi_token_uri(env: &Env, event: MultiTokenUriEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (URI_KEY, event.uri.clone().into_val(env)),
+            ],
+        );
+        let user = event.updated_by.clone();
+        Self::emit_dual(env, topics::MT_URI, user, event, metadata);
+    }
+
+    /// Emit a position minted event
+    pub fn position_minted(env: &Env, event: PositionEvent) {
+        Self::position_action(env, topics::POSITION_MINTED, event);
+    }
+
+    /// Emit a position burned event
+    pub fn position_burned(env: &Env, event: PositionEvent) {
+        Self::position_action(env, topics::POSITION_BURNED, event);
+    }
+
+    fn position_action(env: &Env, topic: Symbol, event: PositionEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a position top-up event
+    pub fn position_staked(env: &Env, event: PositionStakeEvent) {
+        Self::position_stake_action(env, topics::STAKE, event);
+    }
+
+    /// Emit a partial position unstake event
+    pub fn position_unstaked(env: &Env, event: PositionStakeEvent) {
+        Self::position_stake_action(env, topics::UNSTAKE, event);
+    }
+
+    fn position_stake_action(env: &Env, topic: Symbol, event: PositionStakeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (MULTIPLIER_KEY, event.multiplier_bps.into_val(env)),
+            ],
+        );
+        let user = event.owner.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit an unstake requested event
+    pub fn unstake_requested(env: &Env, event: UnstakeRequestEvent) {
+        Self::unstake_request(env, topics::UNSTAKE_REQUESTED, event);
+    }
+
+    /// Emit an unstake cancelled event
+    pub fn unstake_cancelled(env: &Env, event: UnstakeRequestEvent) {
+        Self::unstake_request(env, topics::UNSTAKE_CANCELLED, event);
+    }
+
+    /// Emit an unstake event for a withdrawal that completes a cooldown
+    pub fn unstake_withdrawn(env: &Env, event: UnstakeRequestEvent) {
+        Self::unstake_request(env, topics::UNSTAKE, event);
+    }
+
+    fn unstake_request(env: &Env, topic: Symbol, event: UnstakeRequestEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (READY_AT_KEY, event.available_at.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a staking rewards claimed event
+    pub fn rewards_claimed(env: &Env, event: RewardsClaimedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (MULTIPLIER_KEY, event.multiplier_bps.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::REWARDS_CLAIMED, user, event, metadata);
+    }
+
+    /// Emit a staking rewards claimed event for staked AMM liquidity,
+    /// tagging the pair
+    pub fn pair_rewards_claimed(env: &Env, event: RewardsClaimedEvent, pair: Symbol) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (MULTIPLIER_KEY, event.multiplier_bps.into_val(env)),
+                (PAIR_KEY, pair.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::REWARDS_CLAIMED, user, event, metadata);
+    }
+
+    /// Emit an LP stake event
+    pub fn lp_staked(env: &Env, event: LpStakeEvent) {
+        Self::lp_stake_action(env, topics::STAKE, event);
+    }
+
+    /// Emit an LP unstake event
+    pub fn lp_unstaked(env: &Env, event: LpStakeEvent) {
+        Self::lp_stake_action(env, topics::UNSTAKE, event);
+    }
+
+    fn lp_stake_action(env: &Env, topic: Symbol, event: LpStakeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (MULTIPLIER_KEY, event.multiplier_bps.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit an impermanent loss compensation event
+    pub fn il_compensated(env: &Env, event: IlCompensationEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (PAIR_KEY, event.pair.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (IL_BPS_KEY, event.il_bps.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::IL_COMPENSATED, user, event, metadata);
+    }
+
+    /// Emit a position transferred event
+    pub fn position_transferred(env: &Env, event: PositionTransferEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.id.into_val(env)),
+                (RECIPIENT_KEY, event.to.clone().into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::POSITION_TRANSFERRED, user, event, metadata);
+    }
+
+    /// Emit an admin changed event
+    pub fn admin_changed(env: &Env, event: AdminChangedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ADDRESS_KEY, event.new_admin.clone().into_val(env)),
+                (REASON_KEY, event.reason.clone().into_val(env)),
+            ],
+        );
+        let user = event.previous_admin.clone();
+        Self::emit_dual(env, topics::ADMIN_CHANGED, user, event, metadata);
+    }
+
+    /// Emit an admin transfer proposed event
+    pub fn admin_transfer_proposed(env: &Env, event: AdminTransferProposedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[(ADDRESS_KEY, event.proposed_admin.clone().into_val(env))],
+        );
+        let user = event.current_admin.clone();
+        Self::emit_dual(env, topics::ADMIN_TRANSFER_PROPOSED, user, event, metadata);
+    }
+
+    /// Emit a registry updated event
+    pub fn registry_updated(env: &Env, event: RegistryUpdatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (NAME_KEY, event.name.into_val(env)),
+                (ADDRESS_KEY, event.address.into_val(env)),
+                (VERSION_KEY, event.version.into_val(env)),
+            ],
+        );
+        let user = event.updated_by.clone();
+        Self::emit_dual(env, topics::REGISTRY_UPDATED, user, event, metadata);
+    }
+
+    /// Emit a wrap event
+    pub fn wrapped(env: &Env, event: WrapEvent) {
+        Self::wrap_action(env, topics::WRAPPED, event);
+    }
+
+    /// Emit an unwrap event
+    pub fn unwrapped(env: &Env, event: WrapEvent) {
+        Self::wrap_action(env, topics::UNWRAPPED, event);
+    }
+
+    fn wrap_action(env: &Env, topic: Symbol, event: WrapEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.underlying.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a mint event with the reason tokens were created
+    pub fn mint(env: &Env, event: MintEvent, reason: Symbol) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, reason.into_val(env)),
+            ],
+        );
+        let user = event.to.clone();
+        Self::emit_dual(env, topics::MINT, user, event, metadata);
+    }
+
+    /// Emit a burn event. `reason` distinguishes e.g. voluntary burns from
+    /// compliance clawbacks.
+    pub fn burn(env: &Env, event: BurnEvent, reason: Symbol) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, reason.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::BURN, user, event, metadata);
+    }
+
+    /// [`Self::mint`] without metadata or the legacy event
+    pub fn mint_lite(env: &Env, event: MintEvent) {
+        let user = event.to.clone();
+        Self::emit_lite(env, topics::MINT, user, event);
+    }
+
+    /// [`Self::burn`] without metadata or the legacy event
+    pub fn burn_lite(env: &Env, event: BurnEvent) {
+        let user = event.from.clone();
+        Self::emit_lite(env, topics::BURN, user, event);
+    }
+
+    /// Emit a burn of tokens bought back with `amount_in` of `token_in`
+    pub fn buyback_burn(env: &Env, event: BurnEvent, token_in: Address, amount_in: i128) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (REASON_KEY, symbol_short!("buyback").into_val(env)),
+                (TOKEN_KEY, token_in.into_val(env)),
+                (AMOUNT_IN_KEY, amount_in.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_dual(env, topics::BURN, user, event, metadata);
+    }
+
+    /// Emit an authorization changed event
+    pub fn authorization_changed(env: &Env, event: AuthorizationChangedEvent, reason: Symbol) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AUTHORIZED_KEY, event.authorized.into_val(env)),
+                (REASON_KEY, reason.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topics::AUTHORIZATION_CHANGED, user, event, metadata);
+    }
+
+    /// Emit a metadata updated event
+    pub fn metadata_updated(env: &Env, event: MetadataUpdatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (NAME_KEY, event.name.clone().into_val(env)),
+                (SYMBOL_KEY, event.symbol.clone().into_val(env)),
+                (DECIMALS_KEY, event.decimals.into_val(env)),
+                (URI_KEY, event.uri.clone().into_val(env)),
+            ],
+        );
+        let user = event.admin.clone();
+        Self::emit_dual(env, topics::METADATA_UPDATED, user, event, metadata);
+    }
+
+    /// Emit a dust swept event
+    pub fn dust_swept(env: &Env, event: DustSweptEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.clone().into_val(env)),
+                (COUNT_KEY, event.count.into_val(env)),
+                (AMOUNT_KEY, event.total.into_val(env)),
+            ],
+        );
+        let user = event.treasury.clone();
+        Self::emit_dual(env, topics::DUST_SWEPT, user, event, metadata);
+    }
+
+    /// Emit a transfer hook changed event
+    pub fn transfer_hook_changed(env: &Env, event: TransferHookChangedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.clone().into_val(env)),
+                (ADDRESS_KEY, event.hook.clone().into_val(env)),
+                (STATUS_KEY, event.installed.into_val(env)),
+            ],
+        );
+        let user = event.changed_by.clone();
+        Self::emit_dual(env, topics::TRANSFER_HOOK_CHANGED, user, event, metadata);
+    }
+
+    /// Emit a transfer event that is one leg of a batch. Every leg of the
+    /// batch carries the same `batch_id`, as metadata and as the envelope's
+    /// correlation id, so indexers can correlate them.
+    pub fn batch_transfer(env: &Env, event: TransferEvent, batch_id: u64) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (RECIPIENT_KEY, event.to.into_val(env)),
+                (BATCH_KEY, batch_id.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        Self::emit_correlated(env, topics::TRANSFER, user, event, metadata, batch_id);
+    }
+
+    /// Emit a transfer that was charged `fee`, so that `to` only received
+    /// `received` of the event's `amount`
+    pub fn fee_transfer(
+        env: &Env,
+        event: TransferEvent,
+        received: i128,
+        fee: i128,
+        batch_id: Option<u64>,
+    ) {
+        let mut metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (RECIPIENT_KEY, event.to.into_val(env)),
+                (RECEIVED_KEY, received.into_val(env)),
+                (FEE_KEY, fee.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        match batch_id {
+            Some(batch_id) => {
+                metadata.set(BATCH_KEY, batch_id.into_val(env));
+                Self::emit_correlated(env, topics::TRANSFER, user, event, metadata, batch_id);
+            }
+            None => Self::emit_dual(env, topics::TRANSFER, user, event, metadata),
+        }
+    }
+
+    /// Emit a transfer of a rebasing token. The event's `amount` is in
+    /// balance units; `shares` is the raw amount that moved, which does not
+    /// change when the token rebases.
+    pub fn rebasing_transfer(
+        env: &Env,
+        event: TransferEvent,
+        shares: i128,
+        index: i128,
+        batch_id: Option<u64>,
+    ) {
+        let mut metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (RECIPIENT_KEY, event.to.into_val(env)),
+                (SHARES_KEY, shares.into_val(env)),
+                (INDEX_KEY, index.into_val(env)),
+            ],
+        );
+        let user = event.from.clone();
+        match batch_id {
+            Some(batch_id) => {
+                metadata.set(BATCH_KEY, batch_id.into_val(env));
+                Self::emit_correlated(env, topics::TRANSFER, user, event, metadata, batch_id);
+            }
+            None => Self::emit_dual(env, topics::TRANSFER, user, event, metadata),
+        }
+    }
+
+    /// Emit a rebase event
+    pub fn rebased(env: &Env, event: RebaseEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.clone().into_val(env)),
+                (INDEX_KEY, event.index.into_val(env)),
+            ],
+        );
+        let user = event.updated_by.clone();
+        Self::emit_dual(env, topics::REBASED, user, event, metadata);
+    }
+
+    /// Emit a budget spent event
+    pub fn budget_spent(env: &Env, event: BudgetSpentEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.proposal_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+            ],
+        );
+        let user = event.recipient.clone();
+        Self::emit_dual(env, topics::BUDGET_SPENT, user, event, metadata);
+    }
+
+    /// Emit a dividend distribution created event
+    pub fn distribution_created(env: &Env, event: DistributionCreatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.distribution_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+            ],
+        );
+        let user = env.current_contract_address();
+        Self::emit_dual(env, topics::DISTRIBUTION_CREATED, user, event, metadata);
+    }
+
+    /// Emit a dividend claimed event
+    pub fn dividend_claimed(env: &Env, event: DividendClaimedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.distribution_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (EPOCH_KEY, event.epoch.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::DIVIDEND_CLAIMED, user, event, metadata);
+    }
+
+    /// Emit a session key added event
+    pub fn session_added(env: &Env, event: SessionKeyEvent) {
+        Self::session_action(env, topics::SESSION_ADDED, event);
+    }
+
+    /// Emit a session key revoked event
+    pub fn session_revoked(env: &Env, event: SessionKeyEvent) {
+        Self::session_action(env, topics::SESSION_REVOKED, event);
+    }
+
+    fn session_action(env: &Env, topic: Symbol, event: SessionKeyEvent) {
+        let metadata = Self::metadata(env, &[(EXPIRY_KEY, event.expires_at.into_val(env))]);
+        let user = event.account.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a recovery initiated event
+    pub fn recovery_initiated(env: &Env, event: RecoveryEvent) {
+        Self::recovery_action(env, topics::RECOVERY_INITIATED, event);
+    }
+
+    /// Emit a recovery cancelled event
+    pub fn recovery_cancelled(env: &Env, event: RecoveryEvent) {
+        Self::recovery_action(env, topics::RECOVERY_CANCELLED, event);
+    }
+
+    /// Emit a recovery completed event
+    pub fn recovery_completed(env: &Env, event: RecoveryEvent) {
+        Self::recovery_action(env, topics::RECOVERY_COMPLETED, event);
+    }
+
+    fn recovery_action(env: &Env, topic: Symbol, event: RecoveryEvent) {
+        let metadata = Self::metadata(env, &[(READY_AT_KEY, event.executable_at.into_val(env))]);
+        let user = event.account.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a subscription started event
+    pub fn billing_started(env: &Env, event: BillingEvent) {
+        Self::billing_action(env, topics::BILLING_STARTED, event);
+    }
+
+    /// Emit a subscription cancelled event
+    pub fn billing_cancelled(env: &Env, event: BillingEvent) {
+        Self::billing_action(env, topics::BILLING_CANCELLED, event);
+    }
+
+    fn billing_action(env: &Env, topic: Symbol, event: BillingEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.subscription_id.into_val(env)),
+                (REASON_KEY, event.reason.into_val(env)),
+            ],
+        );
+        let user = event.subscriber.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a successful subscription charge event
+    pub fn billing_charged(env: &Env, event: BillingChargeEvent) {
+        Self::billing_charge_action(env, topics::BILLING_CHARGED, event);
+    }
+
+    /// Emit a failed subscription charge event
+    pub fn billing_failed(env: &Env, event: BillingChargeEvent) {
+        Self::billing_charge_action(env, topics::BILLING_FAILED, event);
+    }
+
+    fn billing_charge_action(env: &Env, topic: Symbol, event: BillingChargeEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.subscription_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.subscriber.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a payment splitter release summary event
+    pub fn split_released(env: &Env, event: SplitReleasedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.total.into_val(env)),
+            ],
+        );
+        let user = env.current_contract_address();
+        Self::emit_dual(env, topics::SPLIT_RELEASED, user, event, metadata);
+    }
+
+    /// Emit a savings deposit event
+    pub fn savings_deposit(env: &Env, event: SavingsEvent) {
+        Self::savings_action(env, topics::SAVINGS_DEPOSIT, event);
+    }
+
+    /// Emit a savings withdrawal event
+    pub fn savings_withdraw(env: &Env, event: SavingsEvent) {
+        Self::savings_action(env, topics::SAVINGS_WITHDRAW, event);
+    }
+
+    fn savings_action(env: &Env, topic: Symbol, event: SavingsEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (TOKEN_KEY, event.asset.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+
+    /// Emit a savings rate change event
+    pub fn savings_rate_changed(env: &Env, event: SavingsRateEvent) {
+        let metadata = Self::metadata(env, &[(RATE_KEY, event.new_rate_bps.into_val(env))]);
+        let user = event.admin.clone();
+        Self::emit_dual(env, topics::SAVINGS_RATE, user, event, metadata);
+    }
+
+    /// Emit an epoch emission event
+    pub fn emission(env: &Env, event: EmissionEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.distributor.clone();
+        Self::emit_dual(env, topics::EMISSION, user, event, metadata);
+    }
+
+    /// Emit an emission curve change event
+    pub fn emission_curve_changed(env: &Env, event: EmissionCurveEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (EPOCH_KEY, event.epoch.into_val(env)),
+                (RATE_KEY, event.decay_bps.into_val(env)),
+            ],
+        );
+        let user = event.admin.clone();
+        Self::emit_dual(env, topics::EMISSION_CURVE, user, event, metadata);
+    }
+
+    /// Emit a launch commit event
+    pub fn launch_committed(env: &Env, event: LaunchCommitEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.amount.into_val(env)),
+                (PRICE_KEY, event.price.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::LAUNCH_COMMIT, user, event, metadata);
+    }
+
+    /// Emit a launch settled event
+    pub fn launch_settled(env: &Env, event: LaunchSettledEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.raised.into_val(env)),
+                (SHARES_KEY, event.sold.into_val(env)),
+            ],
+        );
+        let user = event.settled_by.clone();
+        Self::emit_dual(env, topics::LAUNCH_SETTLED, user, event, metadata);
+    }
+
+    /// Emit a launch claim event
+    pub fn launch_claimed(env: &Env, event: LaunchClaimEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (AMOUNT_KEY, event.tokens.into_val(env)),
+                (RECEIVED_KEY, event.refund.into_val(env)),
+            ],
+        );
+        let user = event.user.clone();
+        Self::emit_dual(env, topics::LAUNCH_CLAIM, user, event, metadata);
+    }
+
+    /// Emit a prize draw started event
+    pub fn draw_started(env: &Env, event: DrawStartedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.draw_id.into_val(env)),
+                (AMOUNT_KEY, event.prize.into_val(env)),
+            ],
+        );
+        let user = event.started_by.clone();
+        Self::emit_dual(env, topics::DRAW_STARTED, user, event, metadata);
+    }
+
+    /// Emit a prize draw winner event
+    pub fn draw_winner(env: &Env, event: DrawWinnerEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.draw_id.into_val(env)),
+                (AMOUNT_KEY, event.prize.into_val(env)),
+                (SEED_KEY, event.seed.clone().into_val(env)),
+            ],
+        );
+        let user = event.winner.clone();
+        Self::emit_dual(env, topics::DRAW_WINNER, user, event, metadata);
+    }
+
+    /// Emit a prize claimed event
+    pub fn prize_claimed(env: &Env, event: PrizeClaimedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.draw_id.into_val(env)),
+                (AMOUNT_KEY, event.prize.into_val(env)),
+            ],
+        );
+        let user = event.winner.clone();
+        Self::emit_dual(env, topics::PRIZE_CLAIMED, user, event, metadata);
+    }
+
+    /// Emit a grant contribution event
+    pub fn grant_contributed(env: &Env, event: GrantContributionEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.round_id.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.donor.clone();
+        Self::emit_dual(env, topics::GRANT_CONTRIBUTED, user, event, metadata);
+    }
+
+    /// Emit a grant match distribution event
+    pub fn grant_matched(env: &Env, event: GrantMatchEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.round_id.into_val(env)),
+                (AMOUNT_KEY, event.matched.into_val(env)),
+            ],
+        );
+        let user = event.recipient.clone();
+        Self::emit_dual(env, topics::GRANT_MATCHED, user, event, metadata);
+    }
+
+    /// Emit a quest completed event
+    pub fn quest_completed(env: &Env, event: QuestCompletedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.quest_id.into_val(env))]);
+        let user = event.account.clone();
+        Self::emit_dual(env, topics::QUEST_COMPLETED, user, event, metadata);
+    }
+
+    /// Emit a quest reward claimed event
+    pub fn quest_reward_claimed(env: &Env, event: QuestRewardEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.quest_id.into_val(env)),
+                (ASSET_KEY, event.asset.clone().into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.account.clone();
+        Self::emit_dual(env, topics::QUEST_CLAIMED, user, event, metadata);
+    }
+
+    /// Emit an OTC deal created event
+    pub fn otc_deal_created(env: &Env, event: OtcDealCreatedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.deal_id.into_val(env)),
+                (EXPIRY_KEY, event.expires_at.into_val(env)),
+            ],
+        );
+        let user = event.maker.clone();
+        Self::emit_dual(env, topics::OTC_CREATED, user, event, metadata);
+    }
+
+    /// Emit an OTC deal funded event
+    pub fn otc_deal_funded(env: &Env, event: OtcDealFundedEvent) {
+        let metadata = Self::metadata(
+            env,
+            &[
+                (ID_KEY, event.deal_id.into_val(env)),
+                (TOKEN_KEY, event.token.into_val(env)),
+                (AMOUNT_KEY, event.amount.into_val(env)),
+            ],
+        );
+        let user = event.party.clone();
+        Self::emit_dual(env, topics::OTC_FUNDED, user, event, metadata);
+    }
+
+    /// Emit an OTC deal settled event
+    pub fn otc_deal_settled(env: &Env, event: OtcDealClosedEvent) {
+        Self::otc_deal_closed(env, topics::OTC_SETTLED, event);
+    }
+
+    /// Emit an OTC deal expired event
+    pub fn otc_deal_expired(env: &Env, event: OtcDealClosedEvent) {
+        Self::otc_deal_closed(env, topics::OTC_EXPIRED, event);
+    }
+
+    fn otc_deal_closed(env: &Env, topic: Symbol, event: OtcDealClosedEvent) {
+        let metadata = Self::metadata(env, &[(ID_KEY, event.deal_id.into_val(env))]);
+        let user = event.maker.clone();
+        Self::emit_dual(env, topic, user, event, metadata);
+    }
+}
+
+// =============================================================================
+// Typed Payloads
+// =============================================================================
+
+/// Declares [`EventPayload`] and one sub-enum per event family, the
+/// conversions between them, and the topic-based decoding used to upgrade
+/// v1 payloads. Contract-type unions are capped at 50 cases, so typed
+/// events are grouped by family rather than listed flat.
+macro_rules! event_payloads {
+    ($(
+        $(#[$doc:meta])*
+        $group:ident($payload:ident) {
+            $($variant:ident($event:ident) => [$($topic:ident),+],)+
+        }
+    )+) => {
+        $(
+            $(#[$doc])*
+            #[contracttype]
+            #[derive(Clone, Debug)]
+            pub enum $payload {
+                $($variant($event),)+
+            }
+        )+
+
+        /// Payload of a [`StandardEventV2`]
+        #[contracttype]
+        #[derive(Clone, Debug)]
+        pub enum EventPayload {
+            $($group($payload),)+
+            /// Untyped payload, from [`EventEmitter::emit_standard`] or a v1
+            /// event whose topic is not recognised
+            Raw(Vec<Val>),
+        }
+
+        impl EventPayload {
+            /// Decode the payload of a v1 event published under `topic`.
+            /// Topics shared by several payloads try each in turn.
+            pub fn from_v1(env: &Env, topic: &Symbol, value: &Val) -> Option<Self> {
+                $($(
+                    if $(*topic == topics::$topic)||+ {
+                        if let Ok(event) = $event::try_from_val(env, value) {
+                            return Some(Self::$group($payload::$variant(event)));
+                        }
+                    }
+                )+)+
+                None
+            }
+        }
+
+        $($(
+            impl From<$event> for EventPayload {
+                fn from(event: $event) -> Self {
+                    Self::$group($payload::$variant(event))
+                }
+            }
+
+            impl TryFrom<EventPayload> for $event {
+                type Error = EventPayload;
+
+                fn try_from(payload: EventPayload) -> Result<Self, Self::Error> {
+                    match payload {
+                        EventPayload::$group($payload::$variant(event)) => Ok(event),
+                        other => Err(other),
+                    }
+                }
+            }
+        )+)+
+    };
+}
+
+event_payloads! {
+    /// Protocol-wide events: trading, fees, admin, governance, bridging and
+    /// automation
+    Protocol(ProtocolPayload) {
+        TradeExecuted(TradeExecutedEvent) => [TRADE_EXECUTED],
+        OrderTriggered(OrderTriggeredEvent) => [ORDER_TRIGGERED],
+        RouteExecuted(RouteExecutedEvent) => [ROUTE_EXECUTED],
+        BatchSettled(BatchSettledEvent) => [BATCH_SETTLED],
+        OrderCommit(OrderCommitEvent) => [ORDER_COMMITTED, COMMIT_SLASHED],
+        OrderReveal(OrderRevealEvent) => [ORDER_REVEALED],
+        OtcDealCreated(OtcDealCreatedEvent) => [OTC_CREATED],
+        OtcDealFunded(OtcDealFundedEvent) => [OTC_FUNDED],
+        OtcDealClosed(OtcDealClosedEvent) => [OTC_SETTLED, OTC_EXPIRED],
+        ContractPaused(ContractPausedEvent) => [CONTRACT_PAUSED],
+        ContractUnpaused(ContractUnpausedEvent) => [CONTRACT_UNPAUSED],
+        PauseChanged(PauseChangedEvent) => [PAUSE_CHANGED],
+        Reputation(ReputationEvent) => [REPUTATION_UPDATED],
+        Name(NameEvent) => [NAME_REGISTERED, NAME_RENEWED],
+        NameTransfer(NameTransferEvent) => [NAME_TRANSFERRED],
+        Attestation(AttestationEvent) => [ATTESTATION_ISSUED, ATTESTATION_REVOKED],
+        FeeCollected(FeeCollectedEvent) => [FEE_COLLECTED],
+        PriceUpdated(PriceUpdatedEvent) => [PRICE_UPDATED],
+        FlashLoan(FlashLoanEvent) => [FLASH_LOAN],
+        ReferrerBound(ReferrerBoundEvent) => [REFERRER_BOUND],
+        FeeTierChanged(FeeTierChangedEvent) => [FEE_TIER_CHANGED],
+        PnlRealized(PnlRealizedEvent) => [PNL_REALIZED],
+        ProposalCreated(ProposalCreatedEvent) => [PROPOSAL_CREATED],
+        ProposalApproved(ProposalApprovedEvent) => [PROPOSAL_APPROVED],
+        ProposalRejected(ProposalRejectedEvent) => [PROPOSAL_REJECTED],
+        ProposalExecuted(ProposalExecutedEvent) => [PROPOSAL_EXECUTED],
+        ProposalCancelled(ProposalCancelledEvent) => [PROPOSAL_CANCELLED],
+        ProposalBatchExecuted(ProposalBatchExecutedEvent) => [PROPOSAL_BATCH_EXECUTED],
+        ProposalChallenged(ProposalChallengedEvent) => [PROPOSAL_CHALLENGED],
+        ProposalArchived(ProposalArchivedEvent) => [PROPOSAL_ARCHIVED],
+        VoteCast(VoteCastEvent) => [VOTE_CAST],
+        ConvictionUpdated(ConvictionUpdatedEvent) => [CONVICTION_UPDATED],
+        ConvictionPassed(ConvictionPassedEvent) => [CONVICTION_PASSED],
+        ContractDeployed(ContractDeployedEvent) => [CONTRACT_DEPLOYED],
+        RegistryUpdated(RegistryUpdatedEvent) => [REGISTRY_UPDATED],
+        AdminChanged(AdminChangedEvent) => [ADMIN_CHANGED],
+        AdminTransferProposed(AdminTransferProposedEvent) => [ADMIN_TRANSFER_PROPOSED],
+        BudgetSpent(BudgetSpentEvent) => [BUDGET_SPENT],
+        BridgeLock(BridgeLockEvent) => [BRIDGE_LOCK],
+        BridgeRelease(BridgeReleaseEvent) => [BRIDGE_RELEASE],
+        Task(TaskEvent) => [TASK_REGISTERED, TASK_CANCELLED],
+        TaskPerformed(TaskPerformedEvent) => [TASK_PERFORMED],
+        RelayReimbursed(RelayReimbursedEvent) => [RELAY_REIMBURSED],
+        EpochRollover(EpochRolloverEvent) => [EPOCH_ROLLOVER],
+        Audit(AuditEvent) => [AUDIT],
+        Subscription(SubscriptionEvent) => [SUBSCRIBED, UNSUBSCRIBED],
+        SessionKey(SessionKeyEvent) => [SESSION_ADDED, SESSION_REVOKED],
+        Recovery(RecoveryEvent) => [RECOVERY_INITIATED, RECOVERY_CANCELLED, RECOVERY_COMPLETED],
+    }
+
+    /// Fungible token events
+    Token(TokenPayload) {
+        Transfer(TransferEvent) => [TRANSFER],
+        Wrap(WrapEvent) => [WRAPPED, UNWRAPPED],
+        Mint(MintEvent) => [MINT],
+        Burn(BurnEvent) => [BURN],
+        AuthorizationChanged(AuthorizationChangedEvent) => [AUTHORIZATION_CHANGED],
+        Approval(ApprovalEvent) => [APPROVAL],
+        MetadataUpdated(MetadataUpdatedEvent) => [METADATA_UPDATED],
+        DustSwept(DustSweptEvent) => [DUST_SWEPT],
+        TransferHookChanged(TransferHookChangedEvent) => [TRANSFER_HOOK_CHANGED],
+        Rebase(RebaseEvent) => [REBASED],
+    }
+
+    /// Rewards, liquidity, streaming, lending and staking events
+    Defi(DefiPayload) {
+        RewardAdded(RewardAddedEvent) => [REWARD_ADDED],
+        RewardClaimed(RewardClaimedEvent) => [REWARD_CLAIMED],
+        LiquidityDeposited(LiquidityDepositedEvent) => [LIQUIDITY_DEPOSITED],
+        LiquidityWithdrawn(LiquidityWithdrawnEvent) => [LIQUIDITY_WITHDRAWN],
+        StreamCreated(StreamCreatedEvent) => [STREAM_CREATED],
+        StreamWithdrawn(StreamWithdrawnEvent) => [STREAM_WITHDRAWN],
+        StreamCancelled(StreamCancelledEvent) => [STREAM_CANCELLED],
+        LendingAction(LendingActionEvent) => [LEND_DEPOSIT, LEND_WITHDRAW, BORROW, REPAY],
+        Liquidation(LiquidationEvent) => [LIQUIDATION],
+        AuctionStart(AuctionStartEvent) => [AUCTION_STARTED],
+        AuctionTake(AuctionTakeEvent) => [AUCTION_TAKEN],
+        AuctionSettle(AuctionSettleEvent) => [AUCTION_CLOSED],
+        CircuitTrip(CircuitTripEvent) => [CIRCUIT_TRIPPED],
+        CircuitReset(CircuitResetEvent) => [CIRCUIT_RESET],
+        Position(PositionEvent) => [POSITION_MINTED, POSITION_BURNED],
+        RewardsClaimed(RewardsClaimedEvent) => [REWARDS_CLAIMED],
+        PositionTransfer(PositionTransferEvent) => [POSITION_TRANSFERRED],
+        LpStake(LpStakeEvent) => [STAKE, UNSTAKE],
+        PositionStake(PositionStakeEvent) => [STAKE, UNSTAKE],
+        UnstakeRequest(UnstakeRequestEvent) => [UNSTAKE_REQUESTED, UNSTAKE_CANCELLED, UNSTAKE],
+        IlCompensation(IlCompensationEvent) => [IL_COMPENSATED],
+        Vault(VaultEvent) => [VAULT_DEPOSIT, VAULT_WITHDRAW],
+        Harvest(HarvestEvent) => [HARVESTED],
+        VeLock(VeLockEvent) => [VE_LOCKED, VE_WITHDRAWN],
+        GaugeVote(GaugeVoteEvent) => [GAUGE_VOTED],
+        GaugeEpoch(GaugeEpochEvent) => [GAUGE_EPOCH],
+        Bribe(BribeEvent) => [BRIBE_DEPOSITED, BRIBE_CLAIMED],
+        DistributionCreated(DistributionCreatedEvent) => [DISTRIBUTION_CREATED],
+        DividendClaimed(DividendClaimedEvent) => [DIVIDEND_CLAIMED],
+        Billing(BillingEvent) => [BILLING_STARTED, BILLING_CANCELLED],
+        BillingCharge(BillingChargeEvent) => [BILLING_CHARGED, BILLING_FAILED],
+        SplitReleased(SplitReleasedEvent) => [SPLIT_RELEASED],
+        Savings(SavingsEvent) => [SAVINGS_DEPOSIT, SAVINGS_WITHDRAW],
+        SavingsRate(SavingsRateEvent) => [SAVINGS_RATE],
+        Emission(EmissionEvent) => [EMISSION],
+        EmissionCurve(EmissionCurveEvent) => [EMISSION_CURVE],
+        LaunchCommit(LaunchCommitEvent) => [LAUNCH_COMMIT],
+        LaunchSettled(LaunchSettledEvent) => [LAUNCH_SETTLED],
+        LaunchClaim(LaunchClaimEvent) => [LAUNCH_CLAIM],
+        QuestCompleted(QuestCompletedEvent) => [QUEST_COMPLETED],
+        QuestReward(QuestRewardEvent) => [QUEST_CLAIMED],
+        DrawStarted(DrawStartedEvent) => [DRAW_STARTED],
+        DrawWinner(DrawWinnerEvent) => [DRAW_WINNER],
+        PrizeClaimed(PrizeClaimedEvent) => [PRIZE_CLAIMED],
+        GrantContribution(GrantContributionEvent) => [GRANT_CONTRIBUTED],
+        GrantMatch(GrantMatchEvent) => [GRANT_MATCHED],
+    }
+
+    /// Parametric insurance events
+    Insurance(InsurancePayload) {
+        PolicyCreated(PolicyCreatedEvent) => [POLICY_CREATED],
+        PolicyCancelled(PolicyCancelledEvent) => [POLICY_CANCELLED],
+        PolicyExpired(PolicyExpiredEvent) => [POLICY_EXPIRED],
+        TriggerActivated(TriggerActivatedEvent) => [TRIGGER_ACTIVATED],
+        ClaimPaid(ClaimPaidEvent) => [CLAIM_PAID],
+        InsuranceClaim(InsuranceClaimEvent) => [CLAIM_FILED, CLAIM_REJECTED],
+    }
+
+    /// NFT and marketplace events
+    Nft(NftPayload) {
+        Nft(NftEvent) => [NFT_MINTED, NFT_BURNED],
+        NftTransfer(NftTransferEvent) => [NFT_TRANSFERRED],
+        NftApproval(NftApprovalEvent) => [NFT_APPROVED],
+        MultiTokenSupply(MultiTokenSupplyEvent) => [MT_MINTED, MT_BURNED],
+        MultiTokenTransfer(MultiTokenTransferEvent) => [MT_TRANSFERRED],
+        MultiTokenUri(MultiTokenUriEvent) => [MT_URI],
+        Badge(BadgeEvent) => [BADGE_ISSUED, BADGE_REVOKED],
+        Listing(ListingEvent) => [LISTED, UNLISTED, AUCTION_CREATED],
+        Sale(SaleEvent) => [SALE],
+        Offer(OfferEvent) => [OFFER_MADE, OFFER_CANCELLED],
+        RoyaltyPaid(RoyaltyPaidEvent) => [ROYALTY_PAID],
+        Bid(BidEvent) => [BID_PLACED],
+        AuctionSettled(AuctionSettledEvent) => [AUCTION_SETTLED],
     }
 }
@@ -11,6 +11,13 @@ pub enum FeeError {
 pub struct FeeManager;
 
 impl FeeManager {
+    /// Fee left after a `discount_bps` discount, rounded in the payer's
+    /// favour. Discounts above 100% waive the fee entirely.
+    pub fn discounted(amount: i128, discount_bps: u32) -> i128 {
+        let discount_bps = discount_bps.min(10_000) as i128;
+        amount * (10_000 - discount_bps) / 10_000
+    }
+
     /// Collects a fee from a payer to a destination - OPTIMIZED
     ///
     /// # Arguments
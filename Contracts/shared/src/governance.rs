@@ -1,4 +1,14 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+use crate::events::{
+    EventEmitter, ProposalArchivedEvent, ProposalBatchExecutedEvent, ProposalExecutedEvent,
+};
+use crate::pagination::Pagination;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
+
+/// Most proposals `execute_batch` accepts in one call
+pub const MAX_BATCH_PROPOSALS: u32 = 20;
+/// Ledgers a proposal must have been closed for before it can be archived
+/// (about 30 days at 5 seconds per ledger)
+pub const ARCHIVE_AFTER_LEDGERS: u32 = 518_400;
 
 /// Upgrade proposal that must be approved via governance
 #[contracttype]
@@ -18,6 +28,21 @@ pub struct UpgradeProposal {
     pub executed: bool,
 }
 
+/// What is kept of a proposal once it is archived
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalSummary {
+    pub id: u64,
+    /// Final status: executed, rejected or cancelled
+    pub status: ProposalStatus,
+    pub approvals_count: u32,
+    pub approval_threshold: u32,
+    /// Hash the proposal would deploy, or deployed if executed
+    pub new_contract_hash: Symbol,
+    pub target_contract: Address,
+    pub closed_ledger: u32,
+}
+
 /// Status of an upgrade proposal
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -52,6 +77,9 @@ pub enum GovernanceError {
     InvalidThreshold = 2006,
     DuplicateApproval = 2007,
     ProposalNotFound = 2008,
+    BatchTooLarge = 2009,
+    ProposalNotClosed = 2010,
+    ArchiveTooEarly = 2011,
 }
 
 impl From<GovernanceError> for soroban_sdk::Error {
@@ -88,6 +116,7 @@ impl GovernanceManager {
     }
 
     /// Create a new upgrade proposal
+    #[allow(clippy::too_many_arguments)]
     pub fn propose_upgrade(
         env: &Env,
         proposer: Address,
@@ -102,7 +131,7 @@ impl GovernanceManager {
         Self::require_role(env, &proposer, GovernanceRole::Admin);
 
         // Validate threshold
-        if approval_threshold == 0 || approval_threshold > approvers.len() as u32 {
+        if approval_threshold == 0 || approval_threshold > approvers.len() {
             return Err(GovernanceError::InvalidThreshold);
         }
 
@@ -132,15 +161,7 @@ impl GovernanceManager {
         };
 
         // Store proposal
-        let proposals_key = symbol_short!("props");
-        let mut proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
-            .storage()
-            .persistent()
-            .get(&proposals_key)
-            .unwrap_or_else(|| soroban_sdk::Map::new(env));
-
-        proposals.set(next_id, proposal);
-        env.storage().persistent().set(&proposals_key, &proposals);
+        Self::save(env, &proposal);
 
         // Update counter
         env.storage()
@@ -159,16 +180,7 @@ impl GovernanceManager {
         // Validate approver has permission
         Self::require_role(env, &approver, GovernanceRole::Approver);
 
-        let proposals_key = symbol_short!("props");
-        let mut proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
-            .storage()
-            .persistent()
-            .get(&proposals_key)
-            .ok_or(GovernanceError::ProposalNotFound)?;
-
-        let mut proposal = proposals
-            .get(proposal_id)
-            .ok_or(GovernanceError::ProposalNotFound)?;
+        let mut proposal = Self::get_proposal(env, proposal_id)?;
 
         // Validate proposal status
         if proposal.status != ProposalStatus::Pending {
@@ -204,8 +216,7 @@ impl GovernanceManager {
             proposal.status = ProposalStatus::Approved;
         }
 
-        proposals.set(proposal_id, proposal);
-        env.storage().persistent().set(&proposals_key, &proposals);
+        Self::save(env, &proposal);
 
         Ok(())
     }
@@ -219,16 +230,71 @@ impl GovernanceManager {
         // Validate executor has permission
         Self::require_role(env, &executor, GovernanceRole::Executor);
 
-        let proposals_key = symbol_short!("props");
-        let mut proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
-            .storage()
-            .persistent()
-            .get(&proposals_key)
-            .ok_or(GovernanceError::ProposalNotFound)?;
+        let proposal = Self::mark_executed(env, proposal_id)?;
 
-        let mut proposal = proposals
-            .get(proposal_id)
-            .ok_or(GovernanceError::ProposalNotFound)?;
+        EventEmitter::proposal_executed(env, Self::executed_event(env, &proposal, executor));
+        Ok(())
+    }
+
+    /// Execute several approved proposals in order, in one transaction.
+    ///
+    /// A proposal that is unknown, not approved or still timelocked fails
+    /// the whole batch, so nothing is executed. With `best_effort` it is
+    /// skipped instead and the rest still execute. Returns the ids
+    /// executed.
+    pub fn execute_batch(
+        env: &Env,
+        proposal_ids: Vec<u64>,
+        executor: Address,
+        best_effort: bool,
+    ) -> Result<Vec<u64>, GovernanceError> {
+        Self::require_role(env, &executor, GovernanceRole::Executor);
+
+        if proposal_ids.is_empty() {
+            return Err(GovernanceError::InvalidProposal);
+        }
+        if proposal_ids.len() > MAX_BATCH_PROPOSALS {
+            return Err(GovernanceError::BatchTooLarge);
+        }
+
+        let batch_key = symbol_short!("exec_bat");
+        let batch_id: u64 = env.storage().persistent().get(&batch_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&batch_key, &batch_id);
+
+        let mut executed = Vec::new(env);
+        let mut skipped = Vec::new(env);
+        for proposal_id in proposal_ids.iter() {
+            match Self::mark_executed(env, proposal_id) {
+                Ok(proposal) => {
+                    executed.push_back(proposal_id);
+                    EventEmitter::batch_proposal_executed(
+                        env,
+                        Self::executed_event(env, &proposal, executor.clone()),
+                        batch_id,
+                    );
+                }
+                Err(_) if best_effort => skipped.push_back(proposal_id),
+                Err(error) => return Err(error),
+            }
+        }
+
+        EventEmitter::proposal_batch_executed(
+            env,
+            ProposalBatchExecutedEvent {
+                batch_id,
+                executor,
+                executed: executed.clone(),
+                skipped,
+                best_effort,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(executed)
+    }
+
+    /// Mark an approved proposal whose timelock has expired as executed
+    fn mark_executed(env: &Env, proposal_id: u64) -> Result<UpgradeProposal, GovernanceError> {
+        let mut proposal = Self::get_proposal(env, proposal_id)?;
 
         // Validate proposal is approved
         if proposal.status != ProposalStatus::Approved {
@@ -243,11 +309,23 @@ impl GovernanceManager {
         // Mark as executed
         proposal.executed = true;
         proposal.status = ProposalStatus::Executed;
+        Self::record_closed(env, proposal_id);
 
-        proposals.set(proposal_id, proposal);
-        env.storage().persistent().set(&proposals_key, &proposals);
+        Self::save(env, &proposal);
+        Ok(proposal)
+    }
 
-        Ok(())
+    fn executed_event(
+        env: &Env,
+        proposal: &UpgradeProposal,
+        executor: Address,
+    ) -> ProposalExecutedEvent {
+        ProposalExecutedEvent {
+            proposal_id: proposal.id,
+            executor,
+            new_contract_hash: proposal.new_contract_hash.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
     }
 
     /// Reject a proposal
@@ -258,24 +336,15 @@ impl GovernanceManager {
     ) -> Result<(), GovernanceError> {
         Self::require_role(env, &rejector, GovernanceRole::Approver);
 
-        let proposals_key = symbol_short!("props");
-        let mut proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
-            .storage()
-            .persistent()
-            .get(&proposals_key)
-            .ok_or(GovernanceError::ProposalNotFound)?;
-
-        let mut proposal = proposals
-            .get(proposal_id)
-            .ok_or(GovernanceError::ProposalNotFound)?;
+        let mut proposal = Self::get_proposal(env, proposal_id)?;
 
         if proposal.status != ProposalStatus::Pending {
             return Err(GovernanceError::InvalidProposal);
         }
 
         proposal.status = ProposalStatus::Rejected;
-        proposals.set(proposal_id, proposal);
-        env.storage().persistent().set(&proposals_key, &proposals);
+        Self::record_closed(env, proposal_id);
+        Self::save(env, &proposal);
 
         Ok(())
     }
@@ -288,39 +357,198 @@ impl GovernanceManager {
     ) -> Result<(), GovernanceError> {
         Self::require_role(env, &admin, GovernanceRole::Admin);
 
-        let proposals_key = symbol_short!("props");
-        let mut proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
-            .storage()
-            .persistent()
-            .get(&proposals_key)
-            .ok_or(GovernanceError::ProposalNotFound)?;
-
-        let mut proposal = proposals
-            .get(proposal_id)
-            .ok_or(GovernanceError::ProposalNotFound)?;
+        let mut proposal = Self::get_proposal(env, proposal_id)?;
 
         if proposal.executed {
             return Err(GovernanceError::InvalidProposal);
         }
 
         proposal.status = ProposalStatus::Cancelled;
-        proposals.set(proposal_id, proposal);
-        env.storage().persistent().set(&proposals_key, &proposals);
+        Self::record_closed(env, proposal_id);
+        Self::save(env, &proposal);
 
         Ok(())
     }
 
+    /// Replace a proposal closed for at least [`ARCHIVE_AFTER_LEDGERS`]
+    /// with its [`ProposalSummary`] and delete its approval records.
+    /// Anyone can archive. Returns the summary.
+    pub fn archive_proposal(
+        env: &Env,
+        proposal_id: u64,
+        archiver: Address,
+    ) -> Result<ProposalSummary, GovernanceError> {
+        let proposal = Self::get_proposal(env, proposal_id)?;
+
+        let closed = matches!(
+            proposal.status,
+            ProposalStatus::Executed | ProposalStatus::Rejected | ProposalStatus::Cancelled
+        );
+        if !closed {
+            return Err(GovernanceError::ProposalNotClosed);
+        }
+        let closed_ledger = Self::closed_ledger(env, proposal_id);
+        let ledger = env.ledger().sequence();
+        if ledger < closed_ledger.saturating_add(ARCHIVE_AFTER_LEDGERS) {
+            return Err(GovernanceError::ArchiveTooEarly);
+        }
+
+        // Approvals are only recorded for listed approvers
+        let approvals_key = symbol_short!("apprv");
+        let mut approvals: Map<(u64, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&approvals_key)
+            .unwrap_or_else(|| Map::new(env));
+        let mut approvals_cleared = 0;
+        for approver in proposal.approvers.iter() {
+            if approvals.remove((proposal_id, approver)).is_some() {
+                approvals_cleared += 1;
+            }
+        }
+        if approvals.is_empty() {
+            env.storage().persistent().remove(&approvals_key);
+        } else {
+            env.storage().persistent().set(&approvals_key, &approvals);
+        }
+
+        Self::remove(env, proposal_id);
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("closed"), proposal_id));
+
+        let summary = Self::summarize(&proposal, closed_ledger);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("prop_sum"), proposal_id), &summary);
+
+        EventEmitter::proposal_archived(
+            env,
+            ProposalArchivedEvent {
+                proposal_id,
+                archiver,
+                status: summary.status,
+                approvals_count: summary.approvals_count,
+                new_contract_hash: summary.new_contract_hash.clone(),
+                closed_ledger: summary.closed_ledger,
+                approvals_cleared,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(summary)
+    }
+
+    /// Summary of a proposal, whether archived or still stored in full
+    pub fn get_proposal_summary(
+        env: &Env,
+        proposal_id: u64,
+    ) -> Result<ProposalSummary, GovernanceError> {
+        if let Some(summary) = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("prop_sum"), proposal_id))
+        {
+            return Ok(summary);
+        }
+        Self::get_proposal(env, proposal_id)
+            .map(|proposal| Self::summarize(&proposal, Self::closed_ledger(env, proposal_id)))
+    }
+
+    /// Ledger a proposal was executed, rejected or cancelled in. Kept
+    /// outside [`UpgradeProposal`] so proposals stored by earlier versions
+    /// still decode; those closed before it was recorded read as ledger 0.
+    pub fn closed_ledger(env: &Env, proposal_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("closed"), proposal_id))
+            .unwrap_or(0)
+    }
+
+    fn record_closed(env: &Env, proposal_id: u64) {
+        env.storage().persistent().set(
+            &(symbol_short!("closed"), proposal_id),
+            &env.ledger().sequence(),
+        );
+    }
+
+    fn summarize(proposal: &UpgradeProposal, closed_ledger: u32) -> ProposalSummary {
+        ProposalSummary {
+            id: proposal.id,
+            status: proposal.status,
+            approvals_count: proposal.approvals_count,
+            approval_threshold: proposal.approval_threshold,
+            new_contract_hash: proposal.new_contract_hash.clone(),
+            target_contract: proposal.target_contract.clone(),
+            closed_ledger,
+        }
+    }
+
+    /// One page of proposal summaries, oldest first, and the cursor of
+    /// the next. Cursor `n` is proposal `n + 1`.
+    pub fn proposals(env: &Env, page: &Pagination) -> (Vec<ProposalSummary>, Option<u32>) {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("prop_cnt"))
+            .unwrap_or(0);
+        let (start, end, next) = page.bounds(count.min(u32::MAX as u64) as u32);
+        let mut summaries = Vec::new(env);
+        for index in start..end {
+            if let Ok(summary) = Self::get_proposal_summary(env, index as u64 + 1) {
+                summaries.push_back(summary);
+            }
+        }
+        (summaries, next)
+    }
+
     /// Get a proposal by ID
     pub fn get_proposal(env: &Env, proposal_id: u64) -> Result<UpgradeProposal, GovernanceError> {
-        let proposals_key = symbol_short!("props");
-        let proposals: soroban_sdk::Map<u64, UpgradeProposal> = env
+        if let Some(proposal) = env
             .storage()
             .persistent()
-            .get(&proposals_key)
-            .ok_or(GovernanceError::ProposalNotFound)?;
-
-        proposals
-            .get(proposal_id)
+            .get(&(symbol_short!("prop"), proposal_id))
+        {
+            return Ok(proposal);
+        }
+        Self::legacy_proposals(env)
+            .and_then(|proposals| proposals.get(proposal_id))
             .ok_or(GovernanceError::ProposalNotFound)
     }
+
+    /// Proposals are stored one per key. Earlier versions kept them all in
+    /// one map under `props`; a proposal is moved out of it the next time
+    /// it is written, and the map goes once it is empty.
+    fn save(env: &Env, proposal: &UpgradeProposal) {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("prop"), proposal.id), proposal);
+        Self::remove_legacy(env, proposal.id);
+    }
+
+    fn remove(env: &Env, proposal_id: u64) {
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("prop"), proposal_id));
+        Self::remove_legacy(env, proposal_id);
+    }
+
+    fn legacy_proposals(env: &Env) -> Option<Map<u64, UpgradeProposal>> {
+        env.storage().persistent().get(&symbol_short!("props"))
+    }
+
+    fn remove_legacy(env: &Env, proposal_id: u64) {
+        let Some(mut proposals) = Self::legacy_proposals(env) else {
+            return;
+        };
+        if proposals.remove(proposal_id).is_none() {
+            return;
+        }
+        if proposals.is_empty() {
+            env.storage().persistent().remove(&symbol_short!("props"));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&symbol_short!("props"), &proposals);
+        }
+    }
 }
@@ -0,0 +1,95 @@
+//! Accounting invariant checks for test and staging builds
+//!
+//! Contracts end their mutating calls with a chain of checks such as "pool
+//! reserves are backed by token balances". The checks only run when the
+//! `invariants` feature is on; contract crates enable it for their own
+//! tests and expose it as a feature of their own for staging builds.
+//! Otherwise the closures are never called and cost nothing.
+//!
+//! A violated invariant logs the contract, the invariant and both sides of
+//! the comparison, then traps, so the offending call fails where the
+//! accounting first went wrong instead of several calls later.
+
+use soroban_sdk::{log, Env};
+
+/// Whether invariant checks run in this build
+pub const ENABLED: bool = cfg!(any(test, feature = "invariants"));
+
+pub struct Invariants<'a> {
+    env: &'a Env,
+    contract: &'static str,
+}
+
+impl<'a> Invariants<'a> {
+    /// Start the checks of `contract`
+    pub fn new(env: &'a Env, contract: &'static str) -> Self {
+        Self { env, contract }
+    }
+
+    /// Check that `check` returns two equal amounts
+    pub fn eq(self, name: &'static str, check: impl FnOnce() -> (i128, i128)) -> Self {
+        if ENABLED {
+            let (left, right) = check();
+            if left != right {
+                self.violated(name, "==", left, right);
+            }
+        }
+        self
+    }
+
+    /// Check that `check` returns a left amount no larger than the right
+    pub fn le(self, name: &'static str, check: impl FnOnce() -> (i128, i128)) -> Self {
+        if ENABLED {
+            let (left, right) = check();
+            if left > right {
+                self.violated(name, "<=", left, right);
+            }
+        }
+        self
+    }
+
+    /// Check a condition with no amounts to report
+    pub fn holds(self, name: &'static str, check: impl FnOnce() -> bool) -> Self {
+        if ENABLED && !check() {
+            self.violated(name, "holds", 0, 0);
+        }
+        self
+    }
+
+    fn violated(&self, name: &'static str, relation: &'static str, left: i128, right: i128) -> ! {
+        log!(
+            self.env,
+            "invariant violated",
+            self.contract,
+            name,
+            relation,
+            left,
+            right
+        );
+        panic!(
+            "invariant `{}` of {} violated: expected {} {} {}",
+            name, self.contract, left, relation, right
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn passing_checks_do_nothing() {
+        let env = Env::default();
+        Invariants::new(&env, "test")
+            .eq("equal", || (5, 5))
+            .le("bounded", || (4, 5))
+            .holds("true", || true);
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant `reserves backed` of test violated: expected 6 <= 5")]
+    fn violation_traps_with_both_sides() {
+        let env = Env::default();
+        Invariants::new(&env, "test").le("reserves backed", || (6, 5));
+    }
+}
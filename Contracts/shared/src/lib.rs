@@ -11,14 +11,66 @@ pub struct ContractConfig {
 }
 
 pub mod acl;
+pub mod admin_transfer;
+pub mod amm;
+pub mod attestations;
+pub mod auction;
+pub mod audit;
+pub mod circuit_breaker;
+pub mod deadline;
+pub mod epoch;
+pub mod events;
 pub mod fees;
 pub mod governance;
+pub mod invariants;
+pub mod math;
+pub mod names;
+pub mod nft;
+pub mod nonce;
+#[cfg(feature = "std")]
+pub mod offchain;
+pub mod pagination;
+pub mod pause;
+pub mod positions;
+pub mod recovery;
+pub mod registry;
+pub mod reputation;
+pub mod staking;
+pub mod typed_data;
+pub mod voting;
 
 /// Standard contract error codes
 pub mod errors {
+    use soroban_sdk::contracterror;
+
     pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
     pub const NOT_FOUND: &str = "NOT_FOUND";
     pub const INVALID_AMOUNT: &str = "INVALID_AMOUNT";
     pub const PAUSED: &str = "PAUSED";
     pub const ALREADY_EXISTS: &str = "ALREADY_EXISTS";
+
+    /// Error enum shared across contracts for conditions that are not
+    /// specific to any one of them. Codes live in the 4000 range so they do
+    /// not collide with `FeeError` (1000s), `GovernanceError` (2000s) or a
+    /// contract's own error enum.
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[repr(u32)]
+    pub enum ContractError {
+        Unauthorized = 4001,
+        NotFound = 4002,
+        InvalidAmount = 4003,
+        Paused = 4004,
+        AlreadyExists = 4005,
+        NotInitialized = 4006,
+        Overflow = 4007,
+        SupplyCapExceeded = 4008,
+        EmissionLimitExceeded = 4009,
+        TransferLimitExceeded = 4010,
+        BalanceLimitExceeded = 4011,
+        TransferCooldown = 4012,
+        InvalidNonce = 4013,
+        Expired = 4014,
+        DivisionByZero = 4015,
+    }
 }
@@ -0,0 +1,200 @@
+//! Checked fixed-point helpers
+//!
+//! [`Checked`] wraps an amount so every step of a calculation fails with
+//! the shared [`ContractError`] instead of wrapping or trapping. In debug
+//! builds a failed step also logs a diagnostic event naming the operation
+//! and both operands, so the failing input shows up in the call's
+//! diagnostics.
+
+use crate::errors::ContractError;
+use soroban_sdk::{log, Env};
+
+/// `a * b / denominator`, truncating toward zero. Returns `None` instead of
+/// wrapping when the result (or an unavoidable intermediate) does not fit in
+/// an `i128`, or when `denominator` is zero.
+pub fn mul_div(a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator == 0 {
+        return None;
+    }
+    match a.checked_mul(b) {
+        Some(product) => product.checked_div(denominator),
+        None => {
+            // a * b / d == (a / d) * b + (a % d) * b / d, and both terms
+            // share the sign of the result so truncation is preserved.
+            let quotient = a.checked_div(denominator)?;
+            let remainder = a.checked_rem(denominator)?;
+            let whole = quotient.checked_mul(b)?;
+            let fraction = remainder.checked_mul(b)?.checked_div(denominator)?;
+            whole.checked_add(fraction)
+        }
+    }
+}
+
+/// Integer square root, rounded down. Zero for negative input.
+pub fn sqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value.max(0);
+    }
+    let mut x = value;
+    let mut y = x / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// An amount whose arithmetic reports overflow and division by zero as
+/// [`ContractError`]s
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Checked<T>(pub T);
+
+impl Checked<i128> {
+    pub fn get(self) -> i128 {
+        self.0
+    }
+
+    pub fn add(self, env: &Env, rhs: i128) -> Result<Self, ContractError> {
+        self.step(env, "add", rhs, self.0.checked_add(rhs))
+    }
+
+    pub fn sub(self, env: &Env, rhs: i128) -> Result<Self, ContractError> {
+        self.step(env, "sub", rhs, self.0.checked_sub(rhs))
+    }
+
+    pub fn mul(self, env: &Env, rhs: i128) -> Result<Self, ContractError> {
+        self.step(env, "mul", rhs, self.0.checked_mul(rhs))
+    }
+
+    /// Divide, truncating toward zero
+    pub fn div(self, env: &Env, rhs: i128) -> Result<Self, ContractError> {
+        if rhs == 0 {
+            return Err(self.fail(env, "div", rhs, ContractError::DivisionByZero));
+        }
+        self.step(env, "div", rhs, self.0.checked_div(rhs))
+    }
+
+    /// `self * b / denominator` through [`mul_div`], so an overflowing
+    /// intermediate alone does not fail the calculation
+    pub fn mul_div(self, env: &Env, b: i128, denominator: i128) -> Result<Self, ContractError> {
+        if denominator == 0 {
+            return Err(self.fail(env, "mul_div", denominator, ContractError::DivisionByZero));
+        }
+        self.step(env, "mul_div", b, mul_div(self.0, b, denominator))
+    }
+
+    fn step(
+        self,
+        env: &Env,
+        op: &'static str,
+        rhs: i128,
+        result: Option<i128>,
+    ) -> Result<Self, ContractError> {
+        result
+            .map(Checked)
+            .ok_or_else(|| self.fail(env, op, rhs, ContractError::Overflow))
+    }
+
+    fn fail(self, env: &Env, op: &'static str, rhs: i128, error: ContractError) -> ContractError {
+        log!(env, "checked arithmetic failed", op, self.0, rhs);
+        error
+    }
+}
+
+impl From<i128> for Checked<i128> {
+    fn from(value: i128) -> Self {
+        Checked(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use soroban_sdk::testutils::Logs;
+    use test_utils::strategies::{amount, bps, nonzero_i128};
+
+    #[test]
+    fn test_mul_div_handles_overflowing_intermediate() {
+        assert_eq!(mul_div(i128::MAX, 10, 20), Some(i128::MAX / 2));
+        assert_eq!(mul_div(i128::MAX, 2, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_sqrt_rounds_down() {
+        assert_eq!(sqrt(0), 0);
+        assert_eq!(sqrt(15), 3);
+        assert_eq!(sqrt(16), 4);
+        assert_eq!(sqrt(i128::MAX), 13_043_817_825_332_782_212);
+    }
+
+    #[test]
+    fn test_checked_reports_failures_with_diagnostics() {
+        let env = Env::default();
+        let fee = Checked(1_000)
+            .mul(&env, 30)
+            .and_then(|value| value.div(&env, 10_000))
+            .unwrap();
+        assert_eq!(fee.get(), 3);
+        assert_eq!(
+            Checked(i128::MAX).mul_div(&env, 10, 20).map(Checked::get),
+            Ok(i128::MAX / 2)
+        );
+        assert!(env.logs().all().is_empty());
+
+        assert_eq!(
+            Checked(i128::MAX).add(&env, 1),
+            Err(ContractError::Overflow)
+        );
+        assert_eq!(
+            Checked(i128::MIN).sub(&env, 1),
+            Err(ContractError::Overflow)
+        );
+        assert_eq!(Checked(5).div(&env, 0), Err(ContractError::DivisionByZero));
+
+        let logs = env.logs().all();
+        assert_eq!(logs.len(), 3);
+        assert!(logs[0].contains("checked arithmetic failed"));
+        assert!(logs[0].contains("\"add\""));
+        assert!(logs[0].contains(&i128::MAX.to_string()));
+        assert!(logs[2].contains("\"div\""));
+    }
+
+    proptest! {
+        #[test]
+        fn mul_div_matches_exact_result_or_reports_overflow(
+            a in any::<i64>(),
+            b in any::<i64>(),
+            d in nonzero_i128(),
+        ) {
+            // i64 operands keep the exact product representable, so the
+            // reference result is known
+            let exact = (a as i128 * b as i128) / d;
+            prop_assert_eq!(mul_div(a as i128, b as i128, d), Some(exact));
+        }
+
+        #[test]
+        fn mul_div_never_overflows_silently(
+            a in any::<i128>(),
+            b in any::<i128>(),
+            d in nonzero_i128(),
+        ) {
+            if let Some(result) = mul_div(a, b, d) {
+                // Reconstruct a * b from the result; a wrapped result could
+                // not land within |d| of it
+                let back = result.checked_mul(d);
+                if let (Some(back), Some(product)) = (back, a.checked_mul(b)) {
+                    prop_assert!((product - back).abs() < d.abs());
+                }
+                prop_assert!(result.signum() == 0 || result.signum() == a.signum() * b.signum() * d.signum());
+            }
+        }
+
+        #[test]
+        fn bps_share_never_exceeds_whole(value in amount(), share in bps()) {
+            let part = mul_div(value, share as i128, 10_000).unwrap();
+            prop_assert!(part >= 0 && part <= value);
+        }
+    }
+}
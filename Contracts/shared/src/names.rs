@@ -0,0 +1,16 @@
+//! Client side of the name service
+//!
+//! Contracts that take a recipient can accept a registered name instead
+//! and resolve it through [`NameServiceClient`] at call time, so a payment
+//! follows the name to whichever address it points at now.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Read-only interface exposed by the name service
+#[contractclient(name = "NameServiceClient")]
+pub trait NameServiceInterface {
+    /// Address `name` points at, or `None` if it is unregistered or expired
+    fn resolve(env: Env, name: Symbol) -> Option<Address>;
+    /// Primary name `account` has claimed, if it still points back at them
+    fn name_of(env: Env, account: Address) -> Option<Symbol>;
+}
@@ -0,0 +1,180 @@
+//! Lightweight non-fungible ownership
+//!
+//! Tracks who owns each `u64` token id, a per-owner index for enumeration,
+//! and one approved spender per token. Contracts layer their own per-token
+//! data on top (e.g. staking positions) and emit their own events; the
+//! module only guarantees that ownership changes are authorized and the
+//! indexes stay consistent.
+
+use soroban_sdk::{
+    contractclient, contracterror, contracttype, symbol_short, Address, Env, Symbol, Vec,
+};
+
+use crate::pagination::{IndexedList, Pagination};
+
+/// NFT error codes, in the 7000 range
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NftError {
+    NotFound = 7001,
+    NotOwner = 7002,
+    NotApproved = 7003,
+}
+
+/// Storage keys, named so they cannot clash with the host contract's own
+/// keys
+#[contracttype]
+#[derive(Clone)]
+enum NftKey {
+    NextNftId,
+    NftOwner(u64),
+    /// Position of a token in its owner's index
+    OwnedNftIndex(u64),
+    NftApproval(u64),
+}
+
+/// Prefix of each owner's [`IndexedList`] of token ids
+const OWNED_NFTS: Symbol = symbol_short!("owned_nft");
+
+/// Read-only interface exposed by NFT collections, used by contracts that
+/// reward holders
+#[contractclient(name = "NftBalanceClient")]
+pub trait NftBalanceInterface {
+    /// Number of tokens `owner` holds in the collection
+    fn balance_of(env: Env, owner: Address) -> u32;
+}
+
+pub struct NonFungible;
+
+impl NonFungible {
+    /// Mint the next id to `to`. Ids start at 1. The caller is responsible
+    /// for authorizing the mint.
+    pub fn mint(env: &Env, to: &Address) -> u64 {
+        let id = Self::total_minted(env) + 1;
+        env.storage().instance().set(&NftKey::NextNftId, &id);
+        env.storage().persistent().set(&NftKey::NftOwner(id), to);
+        Self::index_add(env, to, id);
+        id
+    }
+
+    /// Number of ids minted so far, including burned ones
+    pub fn total_minted(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&NftKey::NextNftId)
+            .unwrap_or(0)
+    }
+
+    pub fn owner_of(env: &Env, id: u64) -> Option<Address> {
+        env.storage().persistent().get(&NftKey::NftOwner(id))
+    }
+
+    /// Fail unless `owner` owns `id`
+    pub fn require_owner(env: &Env, owner: &Address, id: u64) -> Result<(), NftError> {
+        let current = Self::owner_of(env, id).ok_or(NftError::NotFound)?;
+        if current != *owner {
+            return Err(NftError::NotOwner);
+        }
+        Ok(())
+    }
+
+    /// Every id held by `owner`. Reads the whole index; views should use
+    /// [`Self::tokens_page`].
+    pub fn tokens_of(env: &Env, owner: &Address) -> Vec<u64> {
+        Self::owned(owner).all(env)
+    }
+
+    /// One page of the ids held by `owner`, and the cursor of the next.
+    /// A transfer out moves the owner's last id into the gap it leaves.
+    pub fn tokens_page(env: &Env, owner: &Address, page: &Pagination) -> (Vec<u64>, Option<u32>) {
+        Self::owned(owner).page(env, page)
+    }
+
+    pub fn balance_of(env: &Env, owner: &Address) -> u32 {
+        Self::owned(owner).len(env)
+    }
+
+    /// Let `spender` transfer `id` on the owner's behalf until the next
+    /// transfer
+    pub fn approve(env: &Env, owner: &Address, spender: &Address, id: u64) -> Result<(), NftError> {
+        owner.require_auth();
+        Self::require_owner(env, owner, id)?;
+        env.storage()
+            .persistent()
+            .set(&NftKey::NftApproval(id), spender);
+        Ok(())
+    }
+
+    pub fn approved(env: &Env, id: u64) -> Option<Address> {
+        env.storage().persistent().get(&NftKey::NftApproval(id))
+    }
+
+    /// Move `id` from `from` to `to`, authorized by the owner
+    pub fn transfer(env: &Env, from: &Address, to: &Address, id: u64) -> Result<(), NftError> {
+        from.require_auth();
+        Self::require_owner(env, from, id)?;
+        Self::move_token(env, from, to, id);
+        Ok(())
+    }
+
+    /// Move `id` from `from` to `to`, authorized by the approved spender
+    pub fn transfer_from(
+        env: &Env,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        id: u64,
+    ) -> Result<(), NftError> {
+        spender.require_auth();
+        Self::require_owner(env, from, id)?;
+        if Self::approved(env, id).as_ref() != Some(spender) {
+            return Err(NftError::NotApproved);
+        }
+        Self::move_token(env, from, to, id);
+        Ok(())
+    }
+
+    /// Destroy `id`, authorized by the owner
+    pub fn burn(env: &Env, owner: &Address, id: u64) -> Result<(), NftError> {
+        owner.require_auth();
+        Self::require_owner(env, owner, id)?;
+        let storage = env.storage().persistent();
+        storage.remove(&NftKey::NftOwner(id));
+        storage.remove(&NftKey::NftApproval(id));
+        Self::index_remove(env, owner, id);
+        Ok(())
+    }
+
+    fn move_token(env: &Env, from: &Address, to: &Address, id: u64) {
+        let storage = env.storage().persistent();
+        storage.set(&NftKey::NftOwner(id), to);
+        storage.remove(&NftKey::NftApproval(id));
+        Self::index_remove(env, from, id);
+        Self::index_add(env, to, id);
+    }
+
+    fn owned(owner: &Address) -> IndexedList<Address> {
+        IndexedList::new(OWNED_NFTS, owner.clone())
+    }
+
+    fn index_add(env: &Env, owner: &Address, id: u64) {
+        let index = Self::owned(owner).push(env, &id);
+        env.storage()
+            .persistent()
+            .set(&NftKey::OwnedNftIndex(id), &index);
+    }
+
+    fn index_remove(env: &Env, owner: &Address, id: u64) {
+        let key = NftKey::OwnedNftIndex(id);
+        let Some(index) = env.storage().persistent().get::<_, u32>(&key) else {
+            return;
+        };
+        env.storage().persistent().remove(&key);
+        if let Some(moved) = Self::owned(owner).swap_remove::<u64>(env, index) {
+            env.storage()
+                .persistent()
+                .set(&NftKey::OwnedNftIndex(moved), &index);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Replay protection for signed payloads
+//!
+//! Signature-based flows (permits, votes by signature, attestations) give
+//! each signer a nonce per domain. The signed payload carries the signer's
+//! next nonce and verification consumes it, so every signature works once
+//! and signatures are used in order. Domains keep flows independent: using
+//! a permit does not invalidate a pending signed vote.
+//!
+//! Payloads are signed through [`signing_digest`], usually by way of
+//! [`crate::typed_data::TypedData`], which prefixes them
+//! with a [`domain_separator`] binding the network, the verifying contract
+//! and the domain, so a signature cannot be replayed on another network,
+//! another deployment or in another flow.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::errors::ContractError;
+
+#[contracttype]
+#[derive(Clone)]
+enum NonceKey {
+    Nonce(Address, Symbol),
+}
+
+pub struct NonceManager;
+
+impl NonceManager {
+    /// Nonce the next payload `owner` signs for `domain` must carry
+    pub fn peek_nonce(env: &Env, owner: &Address, domain: &Symbol) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&NonceKey::Nonce(owner.clone(), domain.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Consume and return `owner`'s next nonce for `domain`
+    pub fn consume_nonce(env: &Env, owner: &Address, domain: &Symbol) -> u64 {
+        let nonce = Self::peek_nonce(env, owner, domain);
+        env.storage().persistent().set(
+            &NonceKey::Nonce(owner.clone(), domain.clone()),
+            &(nonce + 1),
+        );
+        nonce
+    }
+
+    /// Consume `nonce`, failing unless it is `owner`'s next nonce for
+    /// `domain`
+    pub fn consume_expected(
+        env: &Env,
+        owner: &Address,
+        domain: &Symbol,
+        nonce: u64,
+    ) -> Result<(), ContractError> {
+        if nonce != Self::peek_nonce(env, owner, domain) {
+            return Err(ContractError::InvalidNonce);
+        }
+        Self::consume_nonce(env, owner, domain);
+        Ok(())
+    }
+}
+
+/// Hash identifying `domain` of the current contract on this network
+pub fn domain_separator(env: &Env, domain: &Symbol) -> BytesN<32> {
+    let payload = (
+        env.ledger().network_id(),
+        env.current_contract_address(),
+        domain.clone(),
+    )
+        .to_xdr(env);
+    env.crypto().sha256(&payload)
+}
+
+/// Digest a signer signs for `payload` in `domain`
+pub fn signing_digest(env: &Env, domain: &Symbol, payload: &Bytes) -> BytesN<32> {
+    let mut message = Bytes::from_array(env, &domain_separator(env, domain).to_array());
+    message.append(payload);
+    env.crypto().sha256(&message)
+}
@@ -0,0 +1,249 @@
+//! Standard event decoding for indexers and backends
+//!
+//! Enabled by the `std` feature. Takes the topics and data of a contract
+//! event as XDR, as returned by RPC `getEvents`, checks it against the same
+//! [`EventSchema`] the contracts emit with, and returns a [`DecodedEvent`]
+//! that serializes to JSON. v1 events are upgraded on the way, so readers
+//! only ever see the current envelope.
+//!
+//! JSON values follow the XDR closely: maps with symbol keys become
+//! objects, addresses become strkeys, bytes become hex, and 64- and 128-bit
+//! integers become strings so JavaScript readers do not lose precision.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
+use soroban_sdk::xdr::{Limits, ReadXdr, ScVal};
+use soroban_sdk::{Env, IntoVal, Symbol, TryFromVal, Val};
+
+use crate::events::{EventSchema, EVENT_PREFIX};
+
+/// A standard event, detached from any env
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    pub version: u32,
+    /// Subsystem of the emitting contract, see [`namespaces`](crate::events::namespaces)
+    pub namespace: Option<String>,
+    pub topic: String,
+    /// Strkey of the emitting contract
+    pub contract: String,
+    pub user_address: String,
+    pub timestamp: u64,
+    pub ledger: u32,
+    pub sequence: u64,
+    pub correlation_id: u64,
+    /// Payload variant, e.g. `Transfer`, or `Raw` for untyped payloads
+    pub kind: String,
+    pub payload: Json,
+    pub metadata: BTreeMap<String, Json>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A topic or the data is not valid XDR
+    InvalidXdr,
+    /// The first topic is not [`EVENT_PREFIX`]
+    NotStandard,
+    /// The envelope version is unknown to this build, or the data does not
+    /// match it
+    Unsupported,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeError::InvalidXdr => "invalid XDR",
+            DecodeError::NotStandard => "not a standard event",
+            DecodeError::Unsupported => "unsupported standard event",
+        })
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl DecodedEvent {
+    /// Decode an event from base64 XDR topics and data
+    pub fn from_xdr_base64(topics: &[&str], data: &str) -> Result<Self, DecodeError> {
+        let read = |value: &str| {
+            ScVal::from_xdr_base64(value, Limits::none()).map_err(|_| DecodeError::InvalidXdr)
+        };
+        let topics = topics
+            .iter()
+            .map(|topic| read(topic))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_scval(&topics, &read(data)?)
+    }
+
+    /// Decode an event from its topics and data
+    pub fn from_scval(topics: &[ScVal], data: &ScVal) -> Result<Self, DecodeError> {
+        match topics.first() {
+            Some(ScVal::Symbol(prefix)) if prefix.to_utf8_string_lossy() == EVENT_PREFIX => {}
+            _ => return Err(DecodeError::NotStandard),
+        }
+        let env = Env::default();
+        let value = Val::try_from_val(&env, data).map_err(|_| DecodeError::InvalidXdr)?;
+        let event = EventSchema::decode(&env, &value).ok_or(DecodeError::Unsupported)?;
+        let scval = |value: Val| ScVal::try_from_val(&env, &value).unwrap();
+
+        let (kind, payload) = match to_json(&scval(event.payload.into_val(&env))) {
+            // [group, [variant, fields]] for typed payloads, ["Raw", [..]]
+            Json::Array(outer) => match outer.as_slice() {
+                [Json::String(group), Json::Array(inner)] if group == "Raw" => {
+                    ("Raw".to_string(), Json::Array(inner.clone()))
+                }
+                [_, Json::Array(inner)] => match inner.as_slice() {
+                    [Json::String(variant), fields] => (variant.clone(), fields.clone()),
+                    _ => return Err(DecodeError::Unsupported),
+                },
+                _ => return Err(DecodeError::Unsupported),
+            },
+            _ => return Err(DecodeError::Unsupported),
+        };
+        let metadata = event
+            .metadata
+            .iter()
+            .map(|(key, value)| (symbol_string(&env, &key), to_json(&scval(value))))
+            .collect();
+
+        Ok(Self {
+            version: event.version,
+            namespace: (topics.len() == 3).then(|| string_of(&topics[1])),
+            topic: symbol_string(&env, &event.topic),
+            contract: string_of(&scval(event.contract.to_val())),
+            user_address: string_of(&scval(event.user_address.to_val())),
+            timestamp: event.timestamp,
+            ledger: event.ledger,
+            sequence: event.sequence,
+            correlation_id: event.correlation_id,
+            kind,
+            payload,
+            metadata,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("decoded events always serialize")
+    }
+}
+
+/// JSON form of an XDR value
+pub fn to_json(value: &ScVal) -> Json {
+    match value {
+        ScVal::Bool(value) => json!(value),
+        ScVal::Void => Json::Null,
+        ScVal::U32(value) => json!(value),
+        ScVal::I32(value) => json!(value),
+        ScVal::U64(value) => json!(value.to_string()),
+        ScVal::I64(value) => json!(value.to_string()),
+        ScVal::Timepoint(value) => json!(value.0.to_string()),
+        ScVal::Duration(value) => json!(value.0.to_string()),
+        ScVal::U128(parts) => json!((((parts.hi as u128) << 64) | parts.lo as u128).to_string()),
+        ScVal::I128(parts) => {
+            json!((((parts.hi as i128) << 64) | parts.lo as i128).to_string())
+        }
+        ScVal::Bytes(bytes) => json!(hex::encode(bytes.as_slice())),
+        ScVal::String(value) => json!(value.to_utf8_string_lossy()),
+        ScVal::Symbol(value) => json!(value.to_utf8_string_lossy()),
+        ScVal::Address(address) => json!(address.to_string()),
+        ScVal::Vec(Some(items)) => Json::Array(items.iter().map(to_json).collect()),
+        ScVal::Vec(None) => Json::Array(Vec::new()),
+        ScVal::Map(Some(entries)) => {
+            let symbol_keys = entries
+                .iter()
+                .all(|entry| matches!(entry.key, ScVal::Symbol(_)));
+            if symbol_keys {
+                Json::Object(
+                    entries
+                        .iter()
+                        .map(|entry| (string_of(&entry.key), to_json(&entry.val)))
+                        .collect(),
+                )
+            } else {
+                Json::Array(
+                    entries
+                        .iter()
+                        .map(|entry| json!([to_json(&entry.key), to_json(&entry.val)]))
+                        .collect(),
+                )
+            }
+        }
+        ScVal::Map(None) => Json::Object(Default::default()),
+        // 256-bit integers, errors and ledger keys never appear in events;
+        // keep their XDR JSON form rather than guess
+        other => serde_json::to_value(other).unwrap_or(Json::Null),
+    }
+}
+
+fn string_of(value: &ScVal) -> String {
+    match to_json(value) {
+        Json::String(value) => value,
+        other => other.to_string(),
+    }
+}
+
+fn symbol_string(env: &Env, symbol: &Symbol) -> String {
+    string_of(&ScVal::try_from_val(env, &symbol.to_val()).unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::{EventEmitter, TransferEvent};
+    use soroban_sdk::testutils::{Address as _, Events};
+    use soroban_sdk::xdr::WriteXdr;
+    use soroban_sdk::{contract, Address};
+
+    #[contract]
+    struct Emitter;
+
+    #[test]
+    fn transfer_decodes_from_xdr_to_json() {
+        let env = Env::default();
+        let contract = env.register_contract(None, Emitter);
+        let (from, to) = (Address::generate(&env), Address::generate(&env));
+        env.as_contract(&contract, || {
+            EventEmitter::transfer(
+                &env,
+                TransferEvent {
+                    from: from.clone(),
+                    to,
+                    token: contract.clone(),
+                    amount: 1 << 70,
+                    timestamp: 42,
+                },
+            );
+        });
+
+        let (_, topics_val, data) = env.events().all().get(0).unwrap();
+        let base64 = |value: Val| {
+            ScVal::try_from_val(&env, &value)
+                .unwrap()
+                .to_xdr_base64(Limits::none())
+                .unwrap()
+        };
+        let topics_xdr: Vec<String> = topics_val.iter().map(base64).collect();
+        let topics_xdr: Vec<&str> = topics_xdr.iter().map(String::as_str).collect();
+        let event = DecodedEvent::from_xdr_base64(&topics_xdr, &base64(data)).unwrap();
+
+        assert_eq!(event.topic, "transfer");
+        assert_eq!(event.kind, "Transfer");
+        let strkey =
+            |address: &Address| string_of(&ScVal::try_from_val(&env, &address.to_val()).unwrap());
+        assert_eq!(event.contract, strkey(&contract));
+        assert_eq!(event.user_address, strkey(&from));
+        assert!(event.contract.starts_with('C') && event.contract.len() == 56);
+        assert_eq!(event.payload["amount"], json!((1i128 << 70).to_string()));
+        assert_eq!(event.payload["timestamp"], json!("42"));
+
+        let json = event.to_json();
+        assert_eq!(serde_json::from_str::<DecodedEvent>(&json).unwrap(), event);
+
+        let plain = ScVal::Symbol("transfer".try_into().unwrap());
+        assert_eq!(
+            DecodedEvent::from_scval(&[plain], &ScVal::Void),
+            Err(DecodeError::NotStandard)
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Cursor pagination for list views
+//!
+//! Views that return a list a user can grow without bound (their stakes,
+//! streams, NFTs, a batch's orders) take a [`Pagination`] and return one
+//! page plus the cursor of the next, so no single call reads more than
+//! [`MAX_PAGE_LIMIT`] entries. The cursor is an index into the stored
+//! list; callers start at 0 and stop when the next cursor is `None`.
+//!
+//! Such lists are kept as an [`IndexedList`], one entry per storage key,
+//! so a page only reads the entries it returns.
+
+use soroban_sdk::{contracttype, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+/// Most entries one page returns, whatever limit is asked for
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pagination {
+    /// Index of the first entry of the page
+    pub cursor: u32,
+    /// Entries wanted, capped at [`MAX_PAGE_LIMIT`]
+    pub limit: u32,
+}
+
+impl Pagination {
+    /// First page of up to `limit` entries
+    pub fn first(limit: u32) -> Self {
+        Self { cursor: 0, limit }
+    }
+
+    /// Index range `[start, end)` of this page in a list of `len` entries,
+    /// and the cursor of the next page if there is one
+    pub fn bounds(&self, len: u32) -> (u32, u32, Option<u32>) {
+        let start = self.cursor.min(len);
+        let end = start
+            .saturating_add(self.limit.min(MAX_PAGE_LIMIT))
+            .min(len);
+        let next = (end < len).then_some(end);
+        (start, end, next)
+    }
+}
+
+/// A list stored one entry per key, `(prefix, owner, index)`, with its
+/// length under `(prefix, owner)`. Appending, updating and paging only
+/// touch the entries involved, however long the list grows.
+///
+/// Lists sharing a prefix are told apart by `owner`, which can be any
+/// storage key part: an address for per-user lists, an id for per-batch
+/// ones.
+#[derive(Clone)]
+pub struct IndexedList<K> {
+    prefix: Symbol,
+    owner: K,
+}
+
+impl<K> IndexedList<K>
+where
+    K: Clone,
+    Val: TryFromVal<Env, K>,
+{
+    pub fn new(prefix: Symbol, owner: K) -> Self {
+        Self { prefix, owner }
+    }
+
+    pub fn len(&self, env: &Env) -> u32 {
+        env.storage().persistent().get(&self.len_key()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self, env: &Env) -> bool {
+        self.len(env) == 0
+    }
+
+    pub fn get<T: TryFromVal<Env, Val>>(&self, env: &Env, index: u32) -> Option<T> {
+        env.storage().persistent().get(&self.entry_key(index))
+    }
+
+    /// Replace the entry at `index`, which must exist
+    pub fn set<T: IntoVal<Env, Val>>(&self, env: &Env, index: u32, item: &T) {
+        env.storage().persistent().set(&self.entry_key(index), item);
+    }
+
+    /// Append `item`. Returns its index.
+    pub fn push<T: IntoVal<Env, Val>>(&self, env: &Env, item: &T) -> u32 {
+        let index = self.len(env);
+        self.set(env, index, item);
+        env.storage()
+            .persistent()
+            .set(&self.len_key(), &(index + 1));
+        index
+    }
+
+    /// Remove the entry at `index` by moving the last entry into its place.
+    /// Returns the moved entry, now at `index`, unless the removed entry
+    /// was the last.
+    pub fn swap_remove<T>(&self, env: &Env, index: u32) -> Option<T>
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let last = self.len(env).checked_sub(1)?;
+        let storage = env.storage().persistent();
+        let moved = if index < last {
+            let moved: T = storage.get(&self.entry_key(last))?;
+            storage.set(&self.entry_key(index), &moved);
+            Some(moved)
+        } else {
+            None
+        };
+        storage.remove(&self.entry_key(last));
+        if last == 0 {
+            storage.remove(&self.len_key());
+        } else {
+            storage.set(&self.len_key(), &last);
+        }
+        moved
+    }
+
+    /// This page of the list and the cursor of the next page
+    pub fn page<T>(&self, env: &Env, page: &Pagination) -> (Vec<T>, Option<u32>)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let (start, end, next) = page.bounds(self.len(env));
+        (self.range(env, start, end), next)
+    }
+
+    /// Every entry. Only for lists bounded by other means, or callers that
+    /// must visit each entry anyway.
+    pub fn all<T>(&self, env: &Env) -> Vec<T>
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        self.range(env, 0, self.len(env))
+    }
+
+    fn range<T>(&self, env: &Env, start: u32, end: u32) -> Vec<T>
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let mut items = Vec::new(env);
+        for index in start..end {
+            if let Some(item) = self.get(env, index) {
+                items.push_back(item);
+            }
+        }
+        items
+    }
+
+    fn len_key(&self) -> (Symbol, K) {
+        (self.prefix.clone(), self.owner.clone())
+    }
+
+    fn entry_key(&self, index: u32) -> (Symbol, K, u32) {
+        (self.prefix.clone(), self.owner.clone(), index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{contract, symbol_short, vec, Address};
+
+    #[contract]
+    struct Lists;
+
+    #[test]
+    fn pages_cover_the_list_once_and_cap_the_limit() {
+        let env = Env::default();
+        let contract = env.register_contract(None, Lists);
+        let owner = Address::generate(&env);
+        let list = IndexedList::new(symbol_short!("items"), owner);
+        env.as_contract(&contract, || {
+            for item in 1u64..=5 {
+                list.push(&env, &item);
+            }
+
+            let (page, next) = list.page::<u64>(&env, &Pagination::first(2));
+            assert_eq!(page, vec![&env, 1, 2]);
+            assert_eq!(next, Some(2));
+            let last = Pagination {
+                cursor: 4,
+                limit: 2,
+            };
+            let (page, next) = list.page::<u64>(&env, &last);
+            assert_eq!(page, vec![&env, 5]);
+            assert_eq!(next, None);
+            let past_the_end = Pagination {
+                cursor: 9,
+                limit: 2,
+            };
+            let (page, next) = list.page::<u64>(&env, &past_the_end);
+            assert!(page.is_empty());
+            assert_eq!(next, None);
+        });
+
+        assert_eq!(Pagination::first(u32::MAX).bounds(500), (0, 100, Some(100)));
+    }
+
+    #[test]
+    fn swap_remove_keeps_the_list_dense() {
+        let env = Env::default();
+        let contract = env.register_contract(None, Lists);
+        let list = IndexedList::new(symbol_short!("items"), 7u64);
+        env.as_contract(&contract, || {
+            for item in 1u64..=3 {
+                list.push(&env, &item);
+            }
+            assert_eq!(list.swap_remove::<u64>(&env, 0), Some(3));
+            assert_eq!(list.all::<u64>(&env), vec![&env, 3, 2]);
+            assert_eq!(list.swap_remove::<u64>(&env, 1), None);
+            assert_eq!(list.swap_remove::<u64>(&env, 0), None);
+            assert!(list.is_empty(&env));
+            assert_eq!(list.swap_remove::<u64>(&env, 0), None);
+        });
+    }
+}
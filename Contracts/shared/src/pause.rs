@@ -0,0 +1,103 @@
+//! Client side of the platform pause registry
+//!
+//! Governance pauses named operations (`swap`, `borrow`, `mint`, ...)
+//! platform-wide in the pause registry contract. A contract records the
+//! registry once with [`set_pause_registry`] and guards each operation with
+//! [`require_not_paused`]. Answers are cached in temporary storage for
+//! `cache_ttl` seconds, so a pause can take up to that long to reach a
+//! contract that checked the flag just before it. A call that fails keeps
+//! nothing it cached, so while an operation is paused every attempt asks
+//! the registry again and resuming takes effect at once.
+
+use crate::errors::ContractError;
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol};
+
+const CONFIG_KEY: Symbol = symbol_short!("pause_reg");
+const CACHE_KEY: Symbol = symbol_short!("pause_c");
+
+/// Well-known operation names
+pub mod operations {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    /// Pausing `ALL` pauses every operation
+    pub const ALL: Symbol = symbol_short!("all");
+    pub const SWAP: Symbol = symbol_short!("swap");
+    pub const BORROW: Symbol = symbol_short!("borrow");
+    pub const MINT: Symbol = symbol_short!("mint");
+    pub const DEPOSIT: Symbol = symbol_short!("deposit");
+    pub const WITHDRAW: Symbol = symbol_short!("withdraw");
+}
+
+/// Read-only interface exposed by the pause registry contract
+#[contractclient(name = "PauseRegistryClient")]
+pub trait PauseRegistryInterface {
+    /// Whether `operation`, or every operation, is paused
+    fn is_paused(env: Env, operation: Symbol) -> bool;
+}
+
+/// Pause registry used by a contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseRegistryConfig {
+    pub registry: Address,
+    /// Seconds an answer from the registry is reused; 0 asks every time
+    pub cache_ttl: u64,
+}
+
+/// Answer last read from the registry for one operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedPause {
+    pub paused: bool,
+    pub checked_at: u64,
+}
+
+/// Record the pause registry used by the current contract. The caller
+/// authorizes the change.
+pub fn set_pause_registry(env: &Env, registry: &Address, cache_ttl: u64) {
+    env.storage().instance().set(
+        &CONFIG_KEY,
+        &PauseRegistryConfig {
+            registry: registry.clone(),
+            cache_ttl,
+        },
+    );
+}
+
+pub fn pause_registry(env: &Env) -> Option<PauseRegistryConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Whether `operation` is paused platform-wide. Always `false` for a
+/// contract without a registry.
+pub fn is_paused(env: &Env, operation: &Symbol) -> bool {
+    let Some(config) = pause_registry(env) else {
+        return false;
+    };
+    let key = (CACHE_KEY, operation.clone());
+    let now = env.ledger().timestamp();
+    if let Some(cached) = env.storage().temporary().get::<_, CachedPause>(&key) {
+        if now.saturating_sub(cached.checked_at) < config.cache_ttl {
+            return cached.paused;
+        }
+    }
+    let paused = PauseRegistryClient::new(env, &config.registry).is_paused(operation);
+    if config.cache_ttl > 0 {
+        env.storage().temporary().set(
+            &key,
+            &CachedPause {
+                paused,
+                checked_at: now,
+            },
+        );
+    }
+    paused
+}
+
+/// Fail with [`ContractError::Paused`] if `operation` is paused
+pub fn require_not_paused(env: &Env, operation: &Symbol) -> Result<(), ContractError> {
+    if is_paused(env, operation) {
+        return Err(ContractError::Paused);
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Per-user position and PnL accounting
+//!
+//! Trading contracts report every fill through [`Positions::record`], which
+//! keeps each user's net position in a pair (positive long, negative short)
+//! and its average entry price. Fills that add to a position move the
+//! average entry; fills that reduce it realize PnL against the average
+//! entry and publish a `PNL_REALIZED` event. A fill larger than the
+//! position closes it and opens the remainder on the other side at the
+//! fill price.
+//!
+//! PnL is in quote units: `closed * (exit - entry) / price_scale`, where
+//! `price_scale` is the fixed-point scale the reporting contract quotes
+//! prices in.
+
+use crate::events::{EventEmitter, PnlRealizedEvent};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+const POSITION_KEY: Symbol = symbol_short!("position");
+
+/// A user's position in one pair
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Position {
+    /// Signed base amount: positive long, negative short
+    pub net: i128,
+    /// Average price the open position was entered at, 0 when flat
+    pub avg_entry_price: i128,
+    /// PnL realized over the position's lifetime
+    pub realized_pnl: i128,
+    pub updated_at: u64,
+}
+
+impl Position {
+    /// Apply a fill of `signed_amount` at `price`. Returns the base amount
+    /// closed and the PnL it realized.
+    pub fn apply(&mut self, signed_amount: i128, price: i128, price_scale: i128) -> (i128, i128) {
+        if signed_amount == 0 {
+            return (0, 0);
+        }
+        if self.net == 0 || self.net.signum() == signed_amount.signum() {
+            let size = self.net.abs();
+            let added = signed_amount.abs();
+            self.avg_entry_price = (size * self.avg_entry_price + added * price) / (size + added);
+            self.net += signed_amount;
+            return (0, 0);
+        }
+
+        let closed = signed_amount.abs().min(self.net.abs());
+        let pnl = closed * (price - self.avg_entry_price) * self.net.signum() / price_scale;
+        self.realized_pnl += pnl;
+        self.net += signed_amount;
+        if self.net == 0 {
+            self.avg_entry_price = 0;
+        } else if self.net.signum() == signed_amount.signum() {
+            // Flipped sides: the remainder was opened at this price
+            self.avg_entry_price = price;
+        }
+        (closed, pnl)
+    }
+}
+
+pub struct Positions;
+
+impl Positions {
+    pub fn position_of(env: &Env, user: &Address, pair: &Symbol) -> Position {
+        env.storage()
+            .persistent()
+            .get(&(POSITION_KEY, user.clone(), pair.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Update `user`'s position in `pair` with a fill of `signed_amount`
+    /// (positive buys) at `price`, emitting `PNL_REALIZED` if the fill
+    /// reduced the position
+    pub fn record(
+        env: &Env,
+        user: &Address,
+        pair: &Symbol,
+        signed_amount: i128,
+        price: i128,
+        price_scale: i128,
+    ) -> Position {
+        let mut position = Self::position_of(env, user, pair);
+        let entry_price = position.avg_entry_price;
+        let (closed, pnl) = position.apply(signed_amount, price, price_scale);
+        position.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&(POSITION_KEY, user.clone(), pair.clone()), &position);
+
+        if closed > 0 {
+            EventEmitter::pnl_realized(
+                env,
+                PnlRealizedEvent {
+                    user: user.clone(),
+                    pair: pair.clone(),
+                    closed_amount: closed,
+                    entry_price,
+                    exit_price: price,
+                    pnl,
+                    total_realized_pnl: position.realized_pnl,
+                    position: position.net,
+                    timestamp: position.updated_at,
+                },
+            );
+        }
+        position
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_average_entry_and_realized_pnl() {
+        let mut position = Position::default();
+        position.apply(100, 10, 1);
+        position.apply(100, 20, 1);
+        assert_eq!((position.net, position.avg_entry_price), (200, 15));
+
+        // Selling half at 25 realizes 10 per unit on the long
+        assert_eq!(position.apply(-100, 25, 1), (100, 1_000));
+        assert_eq!(position.avg_entry_price, 15);
+
+        // Selling past flat opens a short at the fill price
+        assert_eq!(position.apply(-150, 5, 1), (100, -1_000));
+        assert_eq!((position.net, position.avg_entry_price), (-50, 5));
+        assert_eq!(position.realized_pnl, 0);
+
+        // A short profits when bought back lower
+        assert_eq!(position.apply(50, 3, 1), (50, 100));
+        assert_eq!((position.net, position.avg_entry_price), (0, 0));
+    }
+}
@@ -0,0 +1,163 @@
+//! Guardian recovery for admin keys
+//!
+//! The admin names a set of guardians, an approval threshold and an
+//! inactivity timeout. Every admin action refreshes the admin's activity;
+//! once the admin has been silent for longer than the timeout, guardians can
+//! rotate the admin with M-of-N approvals. The rotation is published as an
+//! `ADMIN_CHANGED` event with the `recovery` reason.
+//!
+//! The host contract keeps owning its admin slot: it calls
+//! [`Recovery::record_activity`] from its admin check and stores the address
+//! returned by [`Recovery::approve`] once the threshold is reached.
+
+use crate::events::{AdminChangedEvent, EventEmitter};
+use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+/// Reason attached to `ADMIN_CHANGED` events emitted by a recovery
+pub const RECOVERY_REASON: Symbol = symbol_short!("recovery");
+
+const CONFIG_KEY: Symbol = symbol_short!("rec_cfg");
+const LAST_ACTIVE_KEY: Symbol = symbol_short!("rec_last");
+const PENDING_KEY: Symbol = symbol_short!("rec_pend");
+
+/// Recovery error codes, in the 5000 range
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RecoveryError {
+    NotConfigured = 5001,
+    InvalidConfig = 5002,
+    NotGuardian = 5003,
+    AdminActive = 5004,
+    AlreadyApproved = 5005,
+}
+
+/// Guardians allowed to recover the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub guardians: Vec<Address>,
+    /// Guardian approvals needed to rotate the admin
+    pub threshold: u32,
+    /// Seconds of admin inactivity after which recovery opens
+    pub timeout: u64,
+}
+
+/// Recovery in progress towards `new_admin`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRecovery {
+    pub new_admin: Address,
+    pub approvals: Vec<Address>,
+}
+
+pub struct Recovery;
+
+impl Recovery {
+    /// Set the guardians. The caller must already have authenticated the
+    /// admin; configuring counts as admin activity.
+    pub fn configure(
+        env: &Env,
+        guardians: Vec<Address>,
+        threshold: u32,
+        timeout: u64,
+    ) -> Result<(), RecoveryError> {
+        if threshold == 0 || threshold > guardians.len() || timeout == 0 {
+            return Err(RecoveryError::InvalidConfig);
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardians.iter().skip(i + 1).any(|other| other == guardian) {
+                return Err(RecoveryError::InvalidConfig);
+            }
+        }
+        let config = RecoveryConfig {
+            guardians,
+            threshold,
+            timeout,
+        };
+        env.storage().instance().set(&CONFIG_KEY, &config);
+        Self::record_activity(env);
+        Ok(())
+    }
+
+    pub fn config(env: &Env) -> Option<RecoveryConfig> {
+        env.storage().instance().get(&CONFIG_KEY)
+    }
+
+    pub fn pending(env: &Env) -> Option<PendingRecovery> {
+        env.storage().instance().get(&PENDING_KEY)
+    }
+
+    /// Note that the admin just acted. A live admin also cancels any
+    /// recovery in progress.
+    pub fn record_activity(env: &Env) {
+        let storage = env.storage().instance();
+        storage.set(&LAST_ACTIVE_KEY, &env.ledger().timestamp());
+        storage.remove(&PENDING_KEY);
+    }
+
+    /// Timestamp of the admin's last recorded action
+    pub fn last_active(env: &Env) -> u64 {
+        env.storage().instance().get(&LAST_ACTIVE_KEY).unwrap_or(0)
+    }
+
+    /// Whether the admin has been inactive past the configured timeout
+    pub fn is_admin_inactive(env: &Env) -> bool {
+        match Self::config(env) {
+            Some(config) => {
+                env.ledger().timestamp() > Self::last_active(env).saturating_add(config.timeout)
+            }
+            None => false,
+        }
+    }
+
+    /// Record `guardian`'s approval to make `new_admin` the admin. Approvals
+    /// for a different candidate start a fresh round. Returns the new admin
+    /// once the threshold is reached, after emitting `ADMIN_CHANGED`; the
+    /// caller must then store it as its admin.
+    pub fn approve(
+        env: &Env,
+        guardian: &Address,
+        current_admin: &Address,
+        new_admin: &Address,
+    ) -> Result<Option<Address>, RecoveryError> {
+        guardian.require_auth();
+        let config = Self::config(env).ok_or(RecoveryError::NotConfigured)?;
+        if !config.guardians.contains(guardian) {
+            return Err(RecoveryError::NotGuardian);
+        }
+        if !Self::is_admin_inactive(env) {
+            return Err(RecoveryError::AdminActive);
+        }
+
+        let mut pending = match Self::pending(env) {
+            Some(pending) if pending.new_admin == *new_admin => pending,
+            _ => PendingRecovery {
+                new_admin: new_admin.clone(),
+                approvals: Vec::new(env),
+            },
+        };
+        if pending.approvals.contains(guardian) {
+            return Err(RecoveryError::AlreadyApproved);
+        }
+        pending.approvals.push_back(guardian.clone());
+
+        if pending.approvals.len() < config.threshold {
+            env.storage().instance().set(&PENDING_KEY, &pending);
+            return Ok(None);
+        }
+
+        // The new admin starts with a fresh activity window
+        Self::record_activity(env);
+        EventEmitter::admin_changed(
+            env,
+            AdminChangedEvent {
+                previous_admin: current_admin.clone(),
+                new_admin: new_admin.clone(),
+                reason: RECOVERY_REASON,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(Some(new_admin.clone()))
+    }
+}
@@ -0,0 +1,47 @@
+//! Client side of the Stellara contract registry
+//!
+//! Contracts record the registry's address once with [`set_registry`] and
+//! then look up their peers by name with [`resolve`] instead of storing
+//! each address themselves.
+
+use crate::errors::ContractError;
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+/// Instance storage key holding the registry address in a consuming contract
+pub const REGISTRY_KEY: Symbol = symbol_short!("REGISTRY");
+
+/// Well-known registry names
+pub mod names {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const TOKEN: Symbol = symbol_short!("token");
+    pub const STAKING: Symbol = symbol_short!("staking");
+    pub const GOVERNANCE: Symbol = symbol_short!("gov");
+    pub const TREASURY: Symbol = symbol_short!("treasury");
+    pub const FACTORY: Symbol = symbol_short!("factory");
+    pub const VOTING_ESCROW: Symbol = symbol_short!("ve");
+}
+
+/// Read-only interface exposed by the registry contract
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn resolve(env: Env, name: Symbol) -> Option<Address>;
+}
+
+/// Record the registry used by the current contract
+pub fn set_registry(env: &Env, registry: &Address) {
+    env.storage().instance().set(&REGISTRY_KEY, registry);
+}
+
+/// Registry used by the current contract, if one has been set
+pub fn registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&REGISTRY_KEY)
+}
+
+/// Resolve `name` to its current address through the registry
+pub fn resolve(env: &Env, name: &Symbol) -> Result<Address, ContractError> {
+    let registry = registry(env).ok_or(ContractError::NotInitialized)?;
+    RegistryClient::new(env, &registry)
+        .resolve(name)
+        .ok_or(ContractError::NotFound)
+}
@@ -0,0 +1,29 @@
+//! Client side of the reputation contract
+//!
+//! Staking, governance and trading contracts push activity to the
+//! reputation contract under the names in [`activities`]; fee and
+//! governance logic reads the resulting score or tier through
+//! [`ReputationClient`].
+
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Well-known activity names
+pub mod activities {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    /// Stake held, reported as amount times seconds staked
+    pub const STAKING: Symbol = symbol_short!("staking");
+    /// Votes cast on proposals
+    pub const GOVERNANCE: Symbol = symbol_short!("gov");
+    /// Traded volume in quote units
+    pub const TRADING: Symbol = symbol_short!("trading");
+}
+
+/// Read-only interface exposed by the reputation contract
+#[contractclient(name = "ReputationClient")]
+pub trait ReputationInterface {
+    /// Current score of `account`, decayed to now
+    fn score_of(env: Env, account: Address) -> i128;
+    /// Tier of `account`'s current score: 0 below the first threshold
+    fn tier_of(env: Env, account: Address) -> u32;
+}
@@ -0,0 +1,119 @@
+//! Reward-per-share accounting for staking pools
+//!
+//! A pool emits rewards at a fixed rate per second, shared by stake
+//! weight. Instead of updating every staker on each change, the pool keeps
+//! a running total of rewards per unit of weight ([`RewardPool`]) and each
+//! stake remembers the total it was last paid up to; what a stake earned
+//! is its weight times the difference. The pool must be accrued before any
+//! weight changes so past emissions are shared by the old weights.
+//!
+//! Stakes can carry a multiplier (e.g. for locking) by staking with
+//! [`boosted_weight`] instead of the raw amount. Overflow is reported as a
+//! [`ContractError`] for the host contract to map onto its own errors.
+
+use crate::errors::ContractError;
+use crate::math::Checked;
+use soroban_sdk::{contracttype, Env};
+
+/// Fixed-point scale of [`RewardPool::reward_per_share`]
+pub const REWARD_SCALE: i128 = 1_000_000_000_000;
+/// A multiplier of 1x, in basis points
+pub const BASE_MULTIPLIER_BPS: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RewardPool {
+    /// Rewards emitted per unit of weight since the pool started, scaled
+    /// by [`REWARD_SCALE`]
+    pub reward_per_share: i128,
+    /// Sum of the weights of all stakes
+    pub total_weight: i128,
+    /// Timestamp emissions were last accrued up to
+    pub last_update: u64,
+}
+
+impl RewardPool {
+    pub fn new(now: u64) -> Self {
+        RewardPool {
+            last_update: now,
+            ..Default::default()
+        }
+    }
+
+    /// Share `rate` per second since the last update between the current
+    /// weights. Emissions while nothing is staked are not distributed.
+    pub fn accrue(&mut self, env: &Env, rate: i128, now: u64) -> Result<(), ContractError> {
+        if now <= self.last_update {
+            return Ok(());
+        }
+        if self.total_weight > 0 && rate > 0 {
+            let increment = Checked(rate)
+                .mul(env, (now - self.last_update) as i128)?
+                .mul_div(env, REWARD_SCALE, self.total_weight)?;
+            self.reward_per_share = Checked(self.reward_per_share)
+                .add(env, increment.get())?
+                .get();
+        }
+        self.last_update = now;
+        Ok(())
+    }
+
+    /// Rewards earned by `weight` since the pool stood at `paid_per_share`
+    pub fn earned(
+        &self,
+        env: &Env,
+        weight: i128,
+        paid_per_share: i128,
+    ) -> Result<i128, ContractError> {
+        let delta = Checked(self.reward_per_share).sub(env, paid_per_share)?;
+        Ok(Checked(weight)
+            .mul_div(env, delta.get(), REWARD_SCALE)?
+            .get())
+    }
+}
+
+/// Weight of `amount` staked with a `multiplier_bps` boost
+pub fn boosted_weight(env: &Env, amount: i128, multiplier_bps: u32) -> Result<i128, ContractError> {
+    Ok(Checked(amount)
+        .mul_div(env, multiplier_bps as i128, BASE_MULTIPLIER_BPS as i128)?
+        .get())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rewards_split_by_weight_and_skip_empty_periods() {
+        let env = Env::default();
+        let mut pool = RewardPool::new(100);
+        // Nothing staked: the first 10 seconds are not distributed
+        pool.accrue(&env, 5, 110).unwrap();
+        assert_eq!(pool.reward_per_share, 0);
+
+        pool.total_weight = boosted_weight(&env, 300, 20_000).unwrap() + 400;
+        pool.accrue(&env, 5, 130).unwrap();
+        assert_eq!(pool.earned(&env, 600, 0), Ok(60));
+        assert_eq!(pool.earned(&env, 400, 0), Ok(40));
+        assert_eq!(pool.last_update, 130);
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let env = Env::default();
+        let mut pool = RewardPool::new(0);
+        pool.total_weight = 1;
+        assert_eq!(
+            pool.accrue(&env, i128::MAX, 10),
+            Err(ContractError::Overflow)
+        );
+        assert_eq!(pool.last_update, 0);
+
+        pool.reward_per_share = i128::MAX;
+        assert_eq!(pool.earned(&env, 1, -1), Err(ContractError::Overflow));
+        assert_eq!(
+            boosted_weight(&env, i128::MAX, 20_000),
+            Err(ContractError::Overflow)
+        );
+    }
+}
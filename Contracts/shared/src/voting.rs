@@ -0,0 +1,97 @@
+//! Client side of the vote-escrow contract
+//!
+//! Governance weight comes from locked tokens, not raw balances: anything
+//! that counts votes (proposal snapshots, gauge weights) reads historical
+//! voting power through [`VotingPowerClient`] at a fixed timestamp so
+//! tokens moved after the snapshot cannot vote twice. Token balances and
+//! stakes are read the same way, at a fixed ledger, through
+//! [`BalanceHistoryClient`] and [`StakedBalanceClient`].
+//!
+//! The gauge controller turns those votes into per-pool emission weights,
+//! which reward distributors read through [`GaugeControllerClient`].
+//! Incentive markets read the per-epoch vote tallies through the same
+//! client.
+//!
+//! Proposals in the governor can count a different asset each; the
+//! [`VotingSource`] of the chosen asset decides which of these interfaces
+//! its power is read through.
+
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+/// Voting power decays weekly; lock ends are rounded down to a week
+pub const WEEK: u64 = 7 * 24 * 60 * 60;
+/// Longest lock, which gets one vote per token
+pub const MAX_LOCK_TIME: u64 = 4 * 365 * 24 * 60 * 60;
+
+/// Read-only interface exposed by the vote-escrow contract
+#[contractclient(name = "VotingPowerClient")]
+pub trait VotingPowerInterface {
+    /// Voting power of `user` at `timestamp`
+    fn balance_of_at(env: Env, user: Address, timestamp: u64) -> i128;
+    /// Sum of all voting power at `timestamp`
+    fn total_supply_at(env: Env, timestamp: u64) -> i128;
+}
+
+/// Balance history exposed by tokens that keep checkpoints
+#[contractclient(name = "BalanceHistoryClient")]
+pub trait BalanceHistoryInterface {
+    /// Balance of `id` at the end of `ledger`
+    fn balance_at(env: Env, id: Address, ledger: u32) -> i128;
+}
+
+/// Read-only interface exposed by staking contracts
+#[contractclient(name = "StakedBalanceClient")]
+pub trait StakedBalanceInterface {
+    /// Principal `user` currently has staked, across all of their stakes
+    fn staked_balance(env: Env, user: Address) -> i128;
+    /// `staked_balance` of `user` at the end of `ledger`
+    fn staked_balance_at(env: Env, user: Address, ledger: u32) -> i128;
+}
+
+/// Kind of asset a governance vote counts
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VotingSource {
+    /// Token balance at the proposal's snapshot ledger
+    Token = 0,
+    /// Vote-escrow power at the proposal snapshot
+    VoteEscrow = 1,
+    /// Staked principal at the proposal's snapshot ledger
+    Staking = 2,
+}
+
+impl VotingSource {
+    /// Name recorded in vote event metadata
+    pub fn name(&self) -> Symbol {
+        match self {
+            VotingSource::Token => symbol_short!("token"),
+            VotingSource::VoteEscrow => symbol_short!("ve"),
+            VotingSource::Staking => symbol_short!("staking"),
+        }
+    }
+}
+
+/// Part of a user's gauge vote in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserVote {
+    pub gauge_id: u32,
+    pub weight_bps: u32,
+    pub power: i128,
+}
+
+/// Read-only interface exposed by the gauge controller
+#[contractclient(name = "GaugeControllerClient")]
+pub trait GaugeControllerInterface {
+    /// Share of emissions, in basis points, the current epoch directs to
+    /// pool `pool_id` of `target`
+    fn gauge_weight(env: Env, target: Address, pool_id: u32) -> u32;
+    /// Latest epoch whose weights are in effect; votes cast in earlier
+    /// epochs are final
+    fn finalized_epoch(env: Env) -> u64;
+    /// Voting power given to `gauge_id` during `epoch`
+    fn gauge_votes(env: Env, epoch: u64, gauge_id: u32) -> i128;
+    /// How `user` split their voting power during `epoch`
+    fn user_votes(env: Env, user: Address, epoch: u64) -> Vec<UserVote>;
+}
@@ -0,0 +1,105 @@
+//! Budget benchmarks for the event emission paths
+//!
+//! Run with `cargo test -p shared --test event_budget -- --nocapture` to
+//! print the measured costs. The thresholds are ~25% above the costs
+//! measured when they were set; a failure means an emission path got
+//! noticeably more expensive and the change needs a look.
+//!
+//! `lite-events` builds collapse every path into the lite one, so there is
+//! nothing to compare.
+#![cfg(not(feature = "lite-events"))]
+
+use shared::events::metadata_keys::{AMOUNT_KEY, RECIPIENT_KEY, TOKEN_KEY};
+use shared::events::{topics, EventEmitter, TransferEvent};
+use soroban_sdk::{contract, testutils::Address as _, Address, Env, IntoVal, Map, Symbol, Val};
+
+#[contract]
+pub struct Bench;
+
+/// Upper bounds for a single transfer event, in (cpu instructions, bytes).
+/// Measured at 8.3k/1.4k legacy, 24.1k/3.4k standard only and 28.0k/3.8k
+/// dual: the standard envelope (with its sequence counter) dominates, the
+/// legacy duplicate adds ~15%. Lite, at 21.2k/3.1k, saves the metadata map.
+const LEGACY_MAX: (u64, u64) = (10_500, 1_800);
+const STANDARD_MAX: (u64, u64) = (30_000, 4_200);
+const DUAL_MAX: (u64, u64) = (35_000, 4_800);
+const LITE_MAX: (u64, u64) = (26_500, 3_900);
+
+struct Cost {
+    cpu: u64,
+    mem: u64,
+}
+
+fn measure<F: FnOnce(&Env, TransferEvent)>(name: &str, emit: F) -> Cost {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Bench);
+    let event = TransferEvent {
+        from: Address::generate(&env),
+        to: Address::generate(&env),
+        token: Address::generate(&env),
+        amount: 10_000_000_000,
+        timestamp: 1_700_000_000,
+    };
+
+    env.budget().reset_unlimited();
+    env.as_contract(&contract_id, || emit(&env, event));
+    let cost = Cost {
+        cpu: env.budget().cpu_instruction_cost(),
+        mem: env.budget().memory_bytes_cost(),
+    };
+    std::println!("{name:>14}: {} cpu, {} bytes", cost.cpu, cost.mem);
+    cost
+}
+
+/// Same metadata `EventEmitter::transfer` attaches
+fn transfer_metadata(env: &Env, event: &TransferEvent) -> Map<Symbol, Val> {
+    EventEmitter::metadata(
+        env,
+        &[
+            (TOKEN_KEY, event.token.clone().into_val(env)),
+            (AMOUNT_KEY, event.amount.into_val(env)),
+            (RECIPIENT_KEY, event.to.clone().into_val(env)),
+        ],
+    )
+}
+
+fn assert_within(name: &str, cost: &Cost, max: (u64, u64)) {
+    assert!(
+        cost.cpu <= max.0,
+        "{name} cpu cost {} exceeds budget {}",
+        cost.cpu,
+        max.0
+    );
+    assert!(
+        cost.mem <= max.1,
+        "{name} memory cost {} exceeds budget {}",
+        cost.mem,
+        max.1
+    );
+}
+
+#[test]
+fn event_emission_budget() {
+    let legacy = measure("legacy", |env, event| {
+        env.events().publish((topics::TRANSFER,), event);
+    });
+    let standard = measure("standard only", |env, event| {
+        let metadata = transfer_metadata(env, &event);
+        let user = event.from.clone();
+        EventEmitter::emit_standard_only(env, topics::TRANSFER, user, event, metadata);
+    });
+    let dual = measure("dual", EventEmitter::transfer);
+    let lite = measure("lite", EventEmitter::transfer_lite);
+
+    assert_within("legacy", &legacy, LEGACY_MAX);
+    assert_within("standard only", &standard, STANDARD_MAX);
+    assert_within("dual", &dual, DUAL_MAX);
+    assert_within("lite", &lite, LITE_MAX);
+
+    // The fast path must actually save the legacy publish
+    assert!(standard.cpu < dual.cpu);
+    assert!(standard.mem < dual.mem);
+    // and the lite path the metadata map
+    assert!(lite.cpu < standard.cpu);
+    assert!(lite.mem < standard.mem);
+}
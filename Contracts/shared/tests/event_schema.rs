@@ -0,0 +1,197 @@
+use shared::events::{
+    namespaces, topics, Compatibility, EventEmitter, EventPayload, EventSchema, StandardEvent,
+    StandardEventV2, TokenPayload, TransferEvent, CURRENT_VERSION, EVENT_PREFIX, NO_CORRELATION,
+    VERSION_1,
+};
+use soroban_sdk::{
+    contract, testutils::Address as _, testutils::Events, vec, Address, Env, IntoVal, Map, Symbol,
+    TryFromVal,
+};
+
+#[contract]
+pub struct Emitter;
+
+fn transfer(env: &Env) -> TransferEvent {
+    TransferEvent {
+        from: Address::generate(env),
+        to: Address::generate(env),
+        token: Address::generate(env),
+        amount: 500,
+        timestamp: 0,
+    }
+}
+
+fn published(env: &Env) -> std::vec::Vec<StandardEventV2> {
+    let prefix = Symbol::new(env, EVENT_PREFIX);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(prefix.clone())
+        })
+        .map(|(_, _, data)| StandardEventV2::try_from_val(env, &data).unwrap())
+        .collect()
+}
+
+#[test]
+fn compatibility_matrix() {
+    use Compatibility::*;
+    assert_eq!(EventSchema::compatibility(2, 2), Native);
+    assert_eq!(EventSchema::compatibility(2, 1), Upgradable);
+    assert_eq!(EventSchema::compatibility(1, 1), Native);
+    assert_eq!(EventSchema::compatibility(1, 2), Unsupported);
+    assert_eq!(EventSchema::compatibility(2, 0), Unsupported);
+    assert!(EventSchema::is_supported(VERSION_1));
+    assert!(EventSchema::is_supported(CURRENT_VERSION));
+    assert!(!EventSchema::is_supported(CURRENT_VERSION + 1));
+}
+
+#[test]
+fn upgrade_v1_decodes_typed_payload() {
+    let env = Env::default();
+    let event = transfer(&env);
+    let v1 = StandardEvent {
+        version: VERSION_1,
+        topic: topics::TRANSFER,
+        contract: Address::generate(&env),
+        user_address: event.from.clone(),
+        timestamp: 10,
+        ledger: 3,
+        data: vec![&env, event.clone().into_val(&env)],
+        metadata: Map::new(&env),
+    };
+
+    let v2 = EventSchema::decode(&env, &v1.clone().into_val(&env)).unwrap();
+    assert_eq!(v2.version, CURRENT_VERSION);
+    assert_eq!(v2.sequence, 0);
+    assert_eq!(v2.correlation_id, NO_CORRELATION);
+    let upgraded = TransferEvent::try_from(v2.payload).unwrap();
+    assert_eq!(upgraded.to, event.to);
+
+    // An unrecognised topic keeps the raw payload
+    let unknown = StandardEvent {
+        topic: Symbol::new(&env, "custom"),
+        ..v1
+    };
+    let v2 = EventSchema::upgrade_v1_to_v2(&env, unknown).unwrap();
+    assert!(matches!(v2.payload, EventPayload::Raw(data) if data.len() == 1));
+}
+
+#[test]
+fn upgrade_rejects_empty_v1_payload() {
+    let env = Env::default();
+    let v1 = StandardEvent {
+        version: VERSION_1,
+        topic: topics::TRANSFER,
+        contract: Address::generate(&env),
+        user_address: Address::generate(&env),
+        timestamp: 0,
+        ledger: 0,
+        data: vec![&env],
+        metadata: Map::new(&env),
+    };
+    assert!(EventSchema::upgrade_v1_to_v2(&env, v1).is_none());
+}
+
+#[test]
+fn emitted_events_carry_sequence_and_correlation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+
+    env.as_contract(&contract_id, || {
+        EventEmitter::transfer(&env, transfer(&env));
+        EventEmitter::batch_transfer(&env, transfer(&env), 42);
+        EventEmitter::batch_transfer(&env, transfer(&env), 42);
+    });
+
+    let events = published(&env);
+    assert_eq!(events.len(), 3);
+    let sequences: std::vec::Vec<u64> = events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, [1, 2, 3]);
+    assert_eq!(events[0].correlation_id, NO_CORRELATION);
+    assert_eq!(events[1].correlation_id, 42);
+    assert_eq!(events[2].correlation_id, 42);
+    assert!(events.iter().all(EventSchema::validate_v2));
+    assert!(matches!(
+        events[0].payload,
+        EventPayload::Token(TokenPayload::Transfer(_))
+    ));
+}
+
+#[test]
+fn namespace_adds_second_topic() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+    let prefix = Symbol::new(&env, EVENT_PREFIX);
+    // topics of the last standard event, skipping the legacy duplicates
+    let topics_of = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .map(|(_, topics, _)| {
+                topics
+                    .iter()
+                    .map(|topic| Symbol::try_from_val(env, &topic).unwrap())
+                    .collect::<std::vec::Vec<_>>()
+            })
+            .filter(|topics| topics[0] == prefix)
+            .last()
+            .unwrap()
+    };
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(EventEmitter::namespace(&env), None);
+        EventEmitter::transfer(&env, transfer(&env));
+    });
+    assert_eq!(topics_of(&env), [prefix.clone(), topics::TRANSFER]);
+
+    env.as_contract(&contract_id, || {
+        EventEmitter::init(&env, namespaces::TOKEN);
+        assert_eq!(EventEmitter::namespace(&env), Some(namespaces::TOKEN));
+        EventEmitter::transfer(&env, transfer(&env));
+    });
+    assert_eq!(
+        topics_of(&env),
+        [prefix, namespaces::TOKEN, topics::TRANSFER]
+    );
+    assert_eq!(published(&env).len(), 2);
+}
+
+#[test]
+fn lite_event_has_typed_payload_and_no_metadata() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+
+    env.as_contract(&contract_id, || {
+        EventEmitter::transfer_lite(&env, transfer(&env));
+    });
+
+    // no legacy duplicate
+    assert_eq!(env.events().all().len(), 1);
+    let events = published(&env);
+    assert_eq!(events.len(), 1);
+    assert!(events[0].metadata.is_empty());
+    assert!(EventSchema::validate_v2(&events[0]));
+    assert!(matches!(
+        events[0].payload,
+        EventPayload::Token(TokenPayload::Transfer(_))
+    ));
+}
+
+/// Run with `cargo test -p shared --features lite-events`
+#[cfg(feature = "lite-events")]
+#[test]
+fn lite_build_strips_heavy_path() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+
+    env.as_contract(&contract_id, || {
+        EventEmitter::transfer(&env, transfer(&env));
+        EventEmitter::batch_transfer(&env, transfer(&env), 42);
+    });
+
+    assert_eq!(env.events().all().len(), 2);
+    let events = published(&env);
+    assert!(events.iter().all(|event| event.metadata.is_empty()));
+    assert_eq!(events[1].correlation_id, 42);
+}
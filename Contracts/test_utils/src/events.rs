@@ -0,0 +1,105 @@
+//! Capture and assertion helpers for standardized events
+
+use shared::events::{EventPayload, EventSchema, StandardEventV2, EVENT_PREFIX};
+use soroban_sdk::{testutils::Events, Address, Env, Symbol, TryFromVal};
+
+/// A decoded standard event together with the contract that published it
+#[derive(Clone, Debug)]
+pub struct CapturedEvent {
+    pub contract: Address,
+    /// Second-level topic of a contract with an event namespace
+    pub namespace: Option<Symbol>,
+    pub event: StandardEventV2,
+}
+
+impl CapturedEvent {
+    /// Decode a metadata entry, or `None` when the key is absent or holds a
+    /// different type
+    pub fn metadata<T>(&self, env: &Env, key: Symbol) -> Option<T>
+    where
+        T: TryFromVal<Env, soroban_sdk::Val>,
+    {
+        let value = self.event.metadata.get(key)?;
+        T::try_from_val(env, &value).ok()
+    }
+}
+
+/// Every standard event published so far, in emission order, upgraded to
+/// the current envelope. Legacy events and anything not under
+/// [`EVENT_PREFIX`] are skipped.
+pub fn standard_events(env: &Env) -> Vec<CapturedEvent> {
+    let prefix = Symbol::new(env, EVENT_PREFIX);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(0)
+                .map(|first| Symbol::try_from_val(env, &first) == Ok(prefix.clone()))
+                .unwrap_or(false)
+        })
+        .map(|(contract, topics, data)| CapturedEvent {
+            contract,
+            namespace: (topics.len() == 3)
+                .then(|| Symbol::try_from_val(env, &topics.get_unchecked(1)).unwrap()),
+            event: EventSchema::decode(env, &data)
+                .expect("event under the standard prefix is not a supported standard event"),
+        })
+        .collect()
+}
+
+/// Standard events published by `contract`
+pub fn standard_events_from(env: &Env, contract: &Address) -> Vec<CapturedEvent> {
+    standard_events(env)
+        .into_iter()
+        .filter(|captured| captured.contract == *contract)
+        .collect()
+}
+
+/// Standard events published under `namespace`
+pub fn standard_events_in(env: &Env, namespace: &Symbol) -> Vec<CapturedEvent> {
+    standard_events(env)
+        .into_iter()
+        .filter(|captured| captured.namespace.as_ref() == Some(namespace))
+        .collect()
+}
+
+/// Assert that a standard event with `topic` satisfying `matcher` was
+/// published, and return the last such event
+pub fn assert_standard_event<F>(env: &Env, topic: Symbol, matcher: F) -> CapturedEvent
+where
+    F: Fn(&CapturedEvent) -> bool,
+{
+    let events = standard_events(env);
+    let found = events
+        .iter()
+        .rev()
+        .find(|captured| captured.event.topic == topic && matcher(captured))
+        .cloned();
+    found.unwrap_or_else(|| {
+        let seen: Vec<Symbol> = events.iter().map(|c| c.event.topic.clone()).collect();
+        panic!("no matching standard event with topic {topic:?}; saw {seen:?}")
+    })
+}
+
+/// Assert that no standard event with `topic` was published
+pub fn assert_no_standard_event(env: &Env, topic: Symbol) {
+    let count = standard_events(env)
+        .iter()
+        .filter(|captured| captured.event.topic == topic)
+        .count();
+    assert_eq!(count, 0, "unexpected standard event with topic {topic:?}");
+}
+
+/// Extract the typed event struct carried in a standard event's payload
+pub fn payload<T>(captured: &CapturedEvent) -> T
+where
+    T: TryFrom<EventPayload>,
+{
+    T::try_from(captured.event.payload.clone()).unwrap_or_else(|_| {
+        panic!(
+            "payload of {:?} has an unexpected type",
+            captured.event.topic
+        )
+    })
+}
@@ -0,0 +1,49 @@
+//! Fixture builders for addresses and tokens
+
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+/// `count` freshly generated addresses
+pub fn addresses(env: &Env, count: usize) -> Vec<Address> {
+    (0..count).map(|_| Address::generate(env)).collect()
+}
+
+/// A Stellar asset contract and the clients needed to use and administer it
+pub struct TestToken<'a> {
+    pub address: Address,
+    pub admin: Address,
+    pub client: token::Client<'a>,
+    pub admin_client: token::StellarAssetClient<'a>,
+}
+
+impl TestToken<'_> {
+    /// Mint `amount` to `to`. Requires mocked auths.
+    pub fn mint(&self, to: &Address, amount: i128) {
+        self.admin_client.mint(to, &amount);
+    }
+
+    pub fn balance(&self, id: &Address) -> i128 {
+        self.client.balance(id)
+    }
+}
+
+/// Register a Stellar asset contract administered by a fresh address
+pub fn stellar_asset(env: &Env) -> TestToken<'static> {
+    let admin = Address::generate(env);
+    let address = env.register_stellar_asset_contract(admin.clone());
+    TestToken {
+        client: token::Client::new(env, &address),
+        admin_client: token::StellarAssetClient::new(env, &address),
+        address,
+        admin,
+    }
+}
+
+/// Register a Stellar asset contract and mint `amount` to each holder.
+/// Requires mocked auths.
+pub fn funded_token(env: &Env, holders: &[Address], amount: i128) -> TestToken<'static> {
+    let token = stellar_asset(env);
+    for holder in holders {
+        token.mint(holder, amount);
+    }
+    token
+}
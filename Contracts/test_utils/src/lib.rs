@@ -0,0 +1,21 @@
+//! Test helpers shared across the Stellara contracts
+//!
+//! Std-only; pull this in as a dev-dependency. It decodes the
+//! standard event envelopes published by `shared::events::EventEmitter`
+//! so tests can assert on typed fields instead of raw `(topics, data)`
+//! tuples, provides fixture builders for the usual test setup, replays
+//! recorded call scenarios for fuzz regressions, and captures contract
+//! storage for upgrade tests.
+
+pub mod events;
+pub mod fixtures;
+pub mod scenario;
+pub mod state;
+pub mod strategies;
+
+pub use events::{
+    assert_no_standard_event, assert_standard_event, payload, standard_events,
+    standard_events_from, standard_events_in, CapturedEvent,
+};
+pub use scenario::{Arg, Scenario, Step};
+pub use state::{carry_address, StateFixture};
@@ -0,0 +1,267 @@
+//! Replayable call sequences for fuzz regressions
+//!
+//! A [`Scenario`] is an ordered list of contract calls and ledger time
+//! jumps. Fuzz harnesses build one as they go and print it when a property
+//! fails; the printed text parses back with [`str::parse`] and replays
+//! against a fresh env, so the failing sequence can be committed as a
+//! plain regression test.
+//!
+//! One step per line, or `;` between steps:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! @0 stake i1000 u0     # actor 0 calls stake(actor 0, 1000i128, 0u32)
+//! +86400                # advance the ledger by a day
+//! - finalize U1         # finalize(1u64), with no actor
+//! ```
+//!
+//! The actor of a call is passed as its first argument, which is where the
+//! contracts take the address that authorizes the call. Arguments are
+//! `i` (i128), `u` (u32), `U` (u64), `@` (actor address), `:` (symbol) or
+//! `true`/`false`. Replays expect auths to be mocked.
+
+use core::fmt;
+use core::str::FromStr;
+
+use soroban_sdk::{
+    testutils::Ledger, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec as SorobanVec,
+};
+
+/// A call argument, with actors referred to by index
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Arg {
+    I128(i128),
+    U32(u32),
+    U64(u64),
+    Bool(bool),
+    Actor(usize),
+    Symbol(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Step {
+    /// Move the ledger timestamp forward by this many seconds
+    Advance(u64),
+    Call {
+        actor: Option<usize>,
+        function: String,
+        args: Vec<Arg>,
+    },
+}
+
+/// Result of one replayed call
+pub type Outcome = Result<Val, InvokeError>;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Scenario {
+    pub steps: Vec<Step>,
+}
+
+/// A step that failed to parse
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Index of the step, counting from 0 and skipping comments
+    pub step: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: {}", self.step, self.message)
+    }
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&mut self, seconds: u64) -> &mut Self {
+        self.steps.push(Step::Advance(seconds));
+        self
+    }
+
+    /// Record `function` called by `actor` with the remaining `args`
+    pub fn call(&mut self, actor: usize, function: &str, args: &[Arg]) -> &mut Self {
+        self.push_call(Some(actor), function, args)
+    }
+
+    /// Record a call that takes no actor, such as a permissionless crank
+    pub fn call_anonymous(&mut self, function: &str, args: &[Arg]) -> &mut Self {
+        self.push_call(None, function, args)
+    }
+
+    fn push_call(&mut self, actor: Option<usize>, function: &str, args: &[Arg]) -> &mut Self {
+        self.steps.push(Step::Call {
+            actor,
+            function: function.into(),
+            args: args.to_vec(),
+        });
+        self
+    }
+
+    /// Replay every step against `contract`, with actor `n` played by
+    /// `actors[n]`. Failed calls do not stop the replay; their outcome is
+    /// recorded and the next step runs. Returns one outcome per call.
+    pub fn replay(&self, env: &Env, contract: &Address, actors: &[Address]) -> Vec<Outcome> {
+        let mut outcomes = Vec::new();
+        for step in &self.steps {
+            match step {
+                Step::Advance(seconds) => env.ledger().with_mut(|li| {
+                    li.timestamp += seconds;
+                    li.sequence_number += 1;
+                }),
+                Step::Call {
+                    actor,
+                    function,
+                    args,
+                } => {
+                    let mut call_args = SorobanVec::<Val>::new(env);
+                    if let Some(actor) = actor {
+                        call_args.push_back(actors[*actor].into_val(env));
+                    }
+                    for arg in args {
+                        call_args.push_back(arg.to_val(env, actors));
+                    }
+                    let outcome = match env.try_invoke_contract::<Val, InvokeError>(
+                        contract,
+                        &Symbol::new(env, function),
+                        call_args,
+                    ) {
+                        Ok(Ok(value)) => Ok(value),
+                        Ok(Err(_)) => Err(InvokeError::Abort),
+                        Err(Ok(err)) | Err(Err(err)) => Err(err),
+                    };
+                    outcomes.push(outcome);
+                }
+            }
+        }
+        outcomes
+    }
+}
+
+impl Arg {
+    fn to_val(&self, env: &Env, actors: &[Address]) -> Val {
+        match self {
+            Arg::I128(value) => value.into_val(env),
+            Arg::U32(value) => value.into_val(env),
+            Arg::U64(value) => value.into_val(env),
+            Arg::Bool(value) => value.into_val(env),
+            Arg::Actor(index) => actors[*index].into_val(env),
+            Arg::Symbol(name) => Symbol::new(env, name).into_val(env),
+        }
+    }
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arg::I128(value) => write!(f, "i{}", value),
+            Arg::U32(value) => write!(f, "u{}", value),
+            Arg::U64(value) => write!(f, "U{}", value),
+            Arg::Bool(value) => write!(f, "{}", value),
+            Arg::Actor(index) => write!(f, "@{}", index),
+            Arg::Symbol(name) => write!(f, ":{}", name),
+        }
+    }
+}
+
+impl FromStr for Arg {
+    type Err = String;
+
+    fn from_str(token: &str) -> Result<Self, String> {
+        let number = |rest: &str| format!("bad number in `{}`: {}", token, rest);
+        match token {
+            "true" => return Ok(Arg::Bool(true)),
+            "false" => return Ok(Arg::Bool(false)),
+            _ => {}
+        }
+        let (tag, rest) = token.split_at(token.chars().next().map_or(0, char::len_utf8));
+        match tag {
+            "i" => rest.parse().map(Arg::I128).map_err(|_| number(rest)),
+            "u" => rest.parse().map(Arg::U32).map_err(|_| number(rest)),
+            "U" => rest.parse().map(Arg::U64).map_err(|_| number(rest)),
+            "@" => rest.parse().map(Arg::Actor).map_err(|_| number(rest)),
+            ":" if !rest.is_empty() => Ok(Arg::Symbol(rest.into())),
+            _ => Err(format!("unknown argument `{}`", token)),
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Advance(seconds) => write!(f, "+{}", seconds),
+            Step::Call {
+                actor,
+                function,
+                args,
+            } => {
+                match actor {
+                    Some(actor) => write!(f, "@{} {}", actor, function)?,
+                    None => write!(f, "- {}", function)?,
+                }
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, String> {
+        if let Some(seconds) = line.strip_prefix('+') {
+            return seconds
+                .parse()
+                .map(Step::Advance)
+                .map_err(|_| format!("bad time jump `{}`", line));
+        }
+        let mut tokens = line.split_whitespace();
+        let actor = match tokens.next() {
+            Some("-") => None,
+            Some(token) => match token.strip_prefix('@').map(str::parse) {
+                Some(Ok(index)) => Some(index),
+                _ => return Err(format!("bad actor `{}`", token)),
+            },
+            None => return Err("empty step".into()),
+        };
+        let function = tokens
+            .next()
+            .ok_or_else(|| format!("missing function in `{}`", line))?
+            .to_string();
+        let args = tokens.map(str::parse).collect::<Result<_, _>>()?;
+        Ok(Step::Call {
+            actor,
+            function,
+            args,
+        })
+    }
+}
+
+impl fmt::Display for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let steps = text
+            .split(['\n', ';'])
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .map(|(step, line)| line.parse().map_err(|message| ParseError { step, message }))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { steps })
+    }
+}
@@ -0,0 +1,109 @@
+//! Contract storage fixtures for upgrade and migration tests
+//!
+//! [`StateFixture::capture`] takes a contract's persistent and instance
+//! storage out of a test env, and [`StateFixture::restore`] writes it into
+//! another contract, usually a fresh env running the next version of the
+//! code. Fixtures round-trip through JSON files, so state built up by a
+//! realistic flow against v1 can be committed once and migrations tested
+//! against it instead of against empty storage.
+//!
+//! Temporary storage is left out, as it is not expected to survive an
+//! upgrade. Addresses inside keys and values are kept as they are; use
+//! [`carry_address`] to refer to them from the new env.
+
+use std::path::Path;
+
+use soroban_ledger_snapshot::LedgerSnapshot;
+use soroban_sdk::{
+    xdr::{ContractDataDurability, LedgerEntryData, LedgerKey, ScVal},
+    Address, Env, TryFromVal, Val,
+};
+
+/// Storage of one contract, detached from any env
+#[derive(Clone, Debug)]
+pub struct StateFixture {
+    snapshot: LedgerSnapshot,
+}
+
+impl StateFixture {
+    /// Persistent and instance storage of `contract` in `env`
+    pub fn capture(env: &Env, contract: &Address) -> Self {
+        let contract = ScVal::try_from_val(env, &contract.to_val()).unwrap();
+        let mut snapshot = env.to_ledger_snapshot();
+        snapshot
+            .ledger_entries
+            .retain(|(key, _)| match key.as_ref() {
+                LedgerKey::ContractData(data) => {
+                    data.durability == ContractDataDurability::Persistent
+                        && ScVal::Address(data.contract.clone()) == contract
+                }
+                _ => false,
+            });
+        Self { snapshot }
+    }
+
+    pub fn read_file(path: impl AsRef<Path>) -> Self {
+        Self {
+            snapshot: LedgerSnapshot::read_file(path).expect("unreadable state fixture"),
+        }
+    }
+
+    pub fn write_file(&self, path: impl AsRef<Path>) {
+        self.snapshot
+            .write_file(path)
+            .expect("state fixture could not be written");
+    }
+
+    /// Number of stored entries, counting each instance storage key
+    pub fn len(&self) -> usize {
+        self.entries().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write every captured entry into the storage of `contract`, which
+    /// must already be registered in `env`. Entries the contract already
+    /// has under the same keys are overwritten; others are kept.
+    pub fn restore(&self, env: &Env, contract: &Address) {
+        env.as_contract(contract, || {
+            for (instance, key, value) in self.entries() {
+                let key = Val::try_from_val(env, &key).unwrap();
+                let value = Val::try_from_val(env, &value).unwrap();
+                if instance {
+                    env.storage().instance().set(&key, &value);
+                } else {
+                    env.storage().persistent().set(&key, &value);
+                }
+            }
+        });
+    }
+
+    /// `(is_instance, key, value)` of every entry
+    fn entries(&self) -> impl Iterator<Item = (bool, ScVal, ScVal)> + '_ {
+        self.snapshot
+            .ledger_entries
+            .iter()
+            .flat_map(|(_, (entry, _))| match &entry.data {
+                LedgerEntryData::ContractData(data) => match (&data.key, &data.val) {
+                    (ScVal::LedgerKeyContractInstance, ScVal::ContractInstance(instance)) => {
+                        instance
+                            .storage
+                            .iter()
+                            .flat_map(|map| map.iter())
+                            .map(|entry| (true, entry.key.clone(), entry.val.clone()))
+                            .collect::<Vec<_>>()
+                    }
+                    (key, value) => vec![(false, key.clone(), value.clone())],
+                },
+                _ => Vec::new(),
+            })
+    }
+}
+
+/// The same address, usable in `env`
+pub fn carry_address(env: &Env, address: &Address) -> Address {
+    let value = ScVal::try_from_val(address.env(), &address.to_val()).unwrap();
+    Address::try_from_val(env, &Val::try_from_val(env, &value).unwrap()).unwrap()
+}
@@ -0,0 +1,38 @@
+//! Reusable proptest strategies for contract arithmetic
+
+use proptest::prelude::*;
+
+/// Basis points denominator used across the contracts
+pub const BPS: u32 = 10_000;
+
+/// Seconds in a 365-day year
+pub const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Positive token amount up to a trillion units at 7 decimals, roomy enough
+/// to stress rounding without overflowing typical reward formulas
+pub fn amount() -> impl Strategy<Value = i128> {
+    1i128..=10_000_000_000_000_000_000
+}
+
+/// Basis points in `0..=10_000`
+pub fn bps() -> impl Strategy<Value = u32> {
+    0..=BPS
+}
+
+/// Elapsed time in seconds, up to ten years
+pub fn duration() -> impl Strategy<Value = u64> {
+    0..=10 * YEAR
+}
+
+/// Any `i128` except zero, for use as a divisor
+pub fn nonzero_i128() -> impl Strategy<Value = i128> {
+    any::<i128>().prop_filter("divisor must be non-zero", |d| *d != 0)
+}
+
+/// An ordered list of `count` checkpoints splitting `0..=total`
+pub fn checkpoints(total: u64, count: usize) -> impl Strategy<Value = Vec<u64>> {
+    proptest::collection::vec(0..=total, count).prop_map(|mut points| {
+        points.sort_unstable();
+        points
+    })
+}
@@ -0,0 +1,70 @@
+use shared::events::{metadata_keys, topics, EventEmitter, TransferEvent};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use test_utils::{
+    assert_no_standard_event, assert_standard_event, fixtures, payload, standard_events,
+    standard_events_from,
+};
+
+#[contract]
+pub struct Emitter;
+
+#[contractimpl]
+impl Emitter {
+    pub fn emit(env: Env, from: Address, to: Address, amount: i128) {
+        EventEmitter::transfer(
+            &env,
+            TransferEvent {
+                from,
+                to,
+                token: env.current_contract_address(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+}
+
+#[test]
+fn decodes_standard_events_and_skips_legacy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+    let client = EmitterClient::new(&env, &contract_id);
+    let users = fixtures::addresses(&env, 2);
+
+    client.emit(&users[0], &users[1], &100);
+
+    let events = standard_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].contract, contract_id);
+    assert_eq!(events[0].event.topic, topics::TRANSFER);
+    assert_eq!(standard_events_from(&env, &users[0]).len(), 0);
+}
+
+#[test]
+fn asserts_on_topic_metadata_and_payload() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Emitter);
+    let client = EmitterClient::new(&env, &contract_id);
+    let users = fixtures::addresses(&env, 2);
+
+    client.emit(&users[0], &users[1], &250);
+
+    let captured = assert_standard_event(&env, topics::TRANSFER, |e| {
+        e.metadata::<i128>(&env, metadata_keys::AMOUNT_KEY) == Some(250)
+    });
+    let transfer: TransferEvent = payload(&captured);
+    assert_eq!(transfer.to, users[1]);
+    assert_no_standard_event(&env, topics::MINT);
+}
+
+#[test]
+fn funded_token_mints_to_every_holder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let holders = fixtures::addresses(&env, 3);
+
+    let token = fixtures::funded_token(&env, &holders, 500);
+    for holder in holders.iter() {
+        assert_eq!(token.balance(holder), 500);
+    }
+}